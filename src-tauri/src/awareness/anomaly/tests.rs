@@ -22,6 +22,7 @@ fn test_anomaly_detection() {
             execution_time_ms: 200.0, // 100% deviation
             rows_affected: 1,
         },
+        parameters: None,
     };
     
     let result = AnomalyDetector::detect(&execution, &baseline, &config);
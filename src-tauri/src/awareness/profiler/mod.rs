@@ -17,6 +17,9 @@ pub struct QueryExecution {
     pub exact_query: String,
     pub timestamp: DateTime<Utc>,
     pub resources: ResourceUsage,
+    /// Bind parameters the query ran with, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
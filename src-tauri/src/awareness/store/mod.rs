@@ -47,6 +47,7 @@ impl AwarenessStore {
                 duration_ms REAL NOT NULL,
                 timestamp DATETIME NOT NULL,
                 rows_affected INTEGER,
+                parameters_json TEXT,
                 FOREIGN KEY(query_hash) REFERENCES baseline_profiles(query_hash)
             );
             "#,
@@ -55,6 +56,11 @@ impl AwarenessStore {
         .await
         .map_err(|e| e.to_string())?;
 
+        // Attempt to add parameters_json column if it doesn't exist (migration)
+        let _ = sqlx::query("ALTER TABLE execution_history ADD COLUMN parameters_json TEXT")
+            .execute(&self.pool)
+            .await;
+
         // Anomaly Log Table
         // severity: 1=INFO, 2=WARNING, 3=CRITICAL
         sqlx::query(
@@ -125,8 +131,8 @@ impl AwarenessStore {
         // 3. Insert into execution_history
         sqlx::query(
             r#"
-            INSERT INTO execution_history (query_hash, exact_query, duration_ms, timestamp, rows_affected)
-            VALUES (?, ?, ?, ?, ?)
+            INSERT INTO execution_history (query_hash, exact_query, duration_ms, timestamp, rows_affected, parameters_json)
+            VALUES (?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&execution.query_hash)
@@ -134,6 +140,7 @@ impl AwarenessStore {
         .bind(&execution.resources.execution_time_ms)
         .bind(execution.timestamp)
         .bind(execution.resources.rows_affected as i64)
+        .bind(execution.parameters.as_ref().map(|p| p.to_string()))
         .execute(&self.pool)
         .await
         .map_err(|e| format!("Failed to log execution: {}", e))?;
@@ -472,7 +479,7 @@ impl AwarenessStore {
         end: Option<chrono::DateTime<chrono::Utc>>,
         limit: i64,
     ) -> Result<Vec<crate::awareness::profiler::QueryExecution>, String> {
-        let mut query = "SELECT query_hash, exact_query, duration_ms, timestamp, rows_affected FROM execution_history".to_string();
+        let mut query = "SELECT query_hash, exact_query, duration_ms, timestamp, rows_affected, parameters_json FROM execution_history".to_string();
         let mut conditions = Vec::new();
         if start.is_some() {
             conditions.push("timestamp >= ?");
@@ -511,6 +518,11 @@ impl AwarenessStore {
                     execution_time_ms: row.try_get("duration_ms").unwrap_or_default(),
                     rows_affected: row.try_get::<i64, _>("rows_affected").unwrap_or(0) as u64,
                 },
+                parameters: row
+                    .try_get::<Option<String>, _>("parameters_json")
+                    .ok()
+                    .flatten()
+                    .and_then(|json| serde_json::from_str(&json).ok()),
             });
         }
         Ok(executions)
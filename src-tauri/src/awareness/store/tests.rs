@@ -15,6 +15,7 @@ async fn test_awareness_store_logging() {
             execution_time_ms: 100.0,
             rows_affected: 1,
         },
+        parameters: None,
     };
     
     // First execution: creates baseline
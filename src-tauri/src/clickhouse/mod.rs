@@ -6,6 +6,12 @@ use crate::db_types::*;
 use clickhouse::Client;
 use serde::{Serialize, Deserialize};
 use serde_json::Value;
+use std::time::Duration;
+
+const MAX_QUERY_TIMEOUT_SECS: u64 = 3600;
+// Headroom for the server to report `max_execution_time` itself
+const CLIENT_DEADLINE_GRACE_SECS: u64 = 2;
+const KILL_TIMEOUT_SECS: u64 = 5;
 
 // --- Connection ---
 
@@ -50,7 +56,13 @@ pub(crate) fn create_client(config: &ConnectionConfig) -> Result<Client, String>
 
 // --- Raw HTTP Query Execution (to avoid clickhouse crate's FORMAT RowBinary enforcement) ---
 
-async fn execute_raw_query(config: &ConnectionConfig, query: &str) -> Result<(String, Option<String>), String> {
+async fn send_raw_query(
+    config: &ConnectionConfig,
+    query: &str,
+    query_id: Option<&str>,
+    params: &[(String, String)],
+    max_execution_time: Option<u64>,
+) -> Result<reqwest::Response, String> {
     let mut url = format!("http://{}:{}", config.host, config.port);
     if !url.starts_with("http") {
         url = format!("http://{}", url);
@@ -70,6 +82,22 @@ async fn execute_raw_query(config: &ConnectionConfig, query: &str) -> Result<(St
         }
     }
 
+    if let Some(id) = query_id {
+        rb = rb.query(&[("query_id", id)]);
+    }
+
+    // Values for `{name:Type}` placeholders travel as `param_<name>` settings
+    for (name, value) in params {
+        rb = rb.query(&[(format!("param_{}", name), value)]);
+    }
+
+    if let Some(secs) = max_execution_time {
+        rb = rb.query(&[
+            ("max_execution_time", secs.to_string()),
+            ("timeout_overflow_mode", "throw".to_string()),
+        ]);
+    }
+
     let response = rb.body(query.to_string())
         .send()
         .await
@@ -81,16 +109,221 @@ async fn execute_raw_query(config: &ConnectionConfig, query: &str) -> Result<(St
         return Err(format!("ClickHouse error ({}): {}", status, err_body));
     }
 
-    let query_id = response.headers()
-        .get("X-ClickHouse-Query-Id")
-        .and_then(|v| v.to_str().ok())
-        .map(|s| s.to_string());
+    Ok(response)
+}
+
+async fn execute_raw_query(config: &ConnectionConfig, query: &str) -> Result<(String, Option<String>), String> {
+    execute_raw_query_with_params(config, query, &[], None, None).await
+}
+
+/// Sends `query` and reads the whole body. With a timeout the server is told to
+/// stop via `max_execution_time`, and a client-side deadline kills the query by
+/// id should the server not give up on its own.
+async fn execute_raw_query_with_params(
+    config: &ConnectionConfig,
+    query: &str,
+    params: &[(String, String)],
+    query_id: Option<&str>,
+    timeout_secs: Option<u64>,
+) -> Result<(String, Option<String>), String> {
+    let request = async {
+        let response = send_raw_query(config, query, query_id, params, timeout_secs).await?;
+
+        let query_id = response.headers()
+            .get("X-ClickHouse-Query-Id")
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string());
+
+        let body = response.text().await.map_err(|e| format!("Failed to read response body: {}", e))?;
+        Ok::<_, String>((body, query_id))
+    };
+
+    let Some(timeout_secs) = timeout_secs else {
+        return request.await;
+    };
+
+    let deadline = Duration::from_secs(timeout_secs + CLIENT_DEADLINE_GRACE_SECS);
+    match tokio::time::timeout(deadline, request).await {
+        Ok(result) => result,
+        Err(_) => {
+            if let Some(id) = query_id {
+                let kill = kill_query_by_id(config, id);
+                if let Ok(Err(e)) = tokio::time::timeout(Duration::from_secs(KILL_TIMEOUT_SECS), kill).await {
+                    eprintln!("Failed to kill timed out ClickHouse query {}: {}", id, e);
+                }
+            }
+            Err(format!("Query timed out after {} seconds", timeout_secs))
+        }
+    }
+}
+
+fn normalize_query_timeout_seconds(timeout_secs: Option<u64>) -> Option<u64> {
+    match timeout_secs {
+        Some(0) | None => None,
+        Some(value) => Some(value.min(MAX_QUERY_TIMEOUT_SECS)),
+    }
+}
+
+fn strip_format_clause(query: &str) -> &str {
+    query.trim().trim_end_matches(';').split("FORMAT").next().unwrap_or(query).trim()
+}
 
-    let body = response.text().await.map_err(|e| format!("Failed to read response body: {}", e))?;
-    Ok((body, query_id))
+/// Parses one `JSONCompactEachRow*` line. ClickHouse reports mid-stream failures
+/// as plain text, which surfaces here as an error carrying that text.
+pub(crate) fn parse_each_row_line(line: &[u8]) -> Result<Option<Vec<Value>>, String> {
+    let text = String::from_utf8_lossy(line);
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+    match serde_json::from_str::<Value>(text) {
+        Ok(Value::Array(values)) => Ok(Some(values)),
+        _ => Err(format!("ClickHouse stream error: {}", text)),
+    }
+}
+
+/// Streams a query over HTTP using `JSONCompactEachRowWithNames`, forwarding rows
+/// in batches. `query_id` is sent with the request so the query can be killed.
+pub async fn stream_query(
+    config: &ConnectionConfig,
+    query: &str,
+    query_id: &str,
+    tx: &tokio::sync::mpsc::Sender<ResultStreamEvent>,
+) -> Result<(), String> {
+    let query_with_format = format!(
+        "{} FORMAT JSONCompactEachRowWithNames",
+        strip_format_clause(query)
+    );
+    let mut response = send_raw_query(config, &query_with_format, Some(query_id), &[], None).await?;
+
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut columns_sent = false;
+    let mut batch = Vec::with_capacity(RESULT_STREAM_BATCH_SIZE);
+    let mut finished = false;
+
+    while !finished {
+        let chunk = tokio::select! {
+            chunk = response.chunk() => chunk.map_err(|e| format!("Failed to read response body: {}", e))?,
+            _ = tx.closed() => return Ok(()),
+        };
+
+        match chunk {
+            Some(bytes) => buffer.extend_from_slice(&bytes),
+            None => {
+                // Flush a trailing line that has no newline
+                buffer.push(b'\n');
+                finished = true;
+            }
+        }
+
+        while let Some(pos) = buffer.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = buffer.drain(..=pos).collect();
+            let Some(values) = parse_each_row_line(&line)? else {
+                continue;
+            };
+
+            if !columns_sent {
+                let columns = values
+                    .iter()
+                    .map(|v| v.as_str().map(str::to_string).unwrap_or_else(|| v.to_string()))
+                    .collect();
+                if tx.send(ResultStreamEvent::Columns(columns)).await.is_err() {
+                    return Ok(());
+                }
+                columns_sent = true;
+                continue;
+            }
+
+            batch.push(values);
+            if batch.len() >= RESULT_STREAM_BATCH_SIZE
+                && tx
+                    .send(ResultStreamEvent::Rows(std::mem::take(&mut batch)))
+                    .await
+                    .is_err()
+            {
+                return Ok(());
+            }
+        }
+    }
+
+    if !batch.is_empty() {
+        let _ = tx.send(ResultStreamEvent::Rows(batch)).await;
+    }
+
+    Ok(())
+}
+
+// --- Bulk Insert ---
+
+/// Builds an `INSERT ... FORMAT JSONEachRow` body for `rows`. Date/time text is
+/// read best-effort since rows may come from another engine.
+pub(crate) fn build_json_each_row_insert(
+    table_ref: &str,
+    columns: &[String],
+    rows: &[Vec<Value>],
+) -> Result<String, String> {
+    let column_list = columns
+        .iter()
+        .map(|c| format!("`{}`", c.replace('\\', "\\\\").replace('`', "\\`")))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut body = format!(
+        "INSERT INTO {} ({}) SETTINGS date_time_input_format = 'best_effort', input_format_json_read_objects_as_strings = 1 FORMAT JSONEachRow\n",
+        table_ref, column_list
+    );
+    for row in rows {
+        let object: serde_json::Map<String, Value> = columns
+            .iter()
+            .enumerate()
+            .map(|(idx, column)| (column.clone(), row.get(idx).cloned().unwrap_or(Value::Null)))
+            .collect();
+        let line = serde_json::to_string(&Value::Object(object))
+            .map_err(|e| format!("Failed to encode ClickHouse row: {}", e))?;
+        body.push_str(&line);
+        body.push('\n');
+    }
+    Ok(body)
+}
+
+/// Inserts `rows` in a single HTTP request and returns how many were sent.
+pub async fn insert_json_each_row(
+    config: &ConnectionConfig,
+    table_ref: &str,
+    columns: &[String],
+    rows: &[Vec<Value>],
+) -> Result<usize, String> {
+    if rows.is_empty() {
+        return Ok(0);
+    }
+    let body = build_json_each_row_insert(table_ref, columns, rows)?;
+    send_raw_query(config, &body, None, &[], None).await?;
+    Ok(rows.len())
 }
 
 pub async fn execute_query_generic(config: &ConnectionConfig, query: String) -> Result<Vec<QueryResult>, String> {
+    execute_query_internal(config, &query, &[], None, None).await
+}
+
+/// Runs `query` with server-side parameters bound to its `{name:Type}`
+/// placeholders, under `query_id` so a timeout can kill it.
+pub async fn execute_query_with_params(
+    config: &ConnectionConfig,
+    query: &str,
+    params: &[(String, String)],
+    query_id: &str,
+    timeout_secs: Option<u64>,
+) -> Result<Vec<QueryResult>, String> {
+    execute_query_internal(config, query, params, Some(query_id), timeout_secs).await
+}
+
+async fn execute_query_internal(
+    config: &ConnectionConfig,
+    query: &str,
+    params: &[(String, String)],
+    query_id: Option<&str>,
+    timeout_secs: Option<u64>,
+) -> Result<Vec<QueryResult>, String> {
+    let timeout_secs = normalize_query_timeout_seconds(timeout_secs);
     let query_trimmed = query.trim();
     if query_trimmed.is_empty() {
         return Ok(vec![QueryResult {
@@ -105,7 +338,8 @@ pub async fn execute_query_generic(config: &ConnectionConfig, query: String) ->
     // Special handling for EXPLAIN AST and EXPLAIN PIPELINE (return raw text)
     let upper_query = query_trimmed.to_uppercase();
     if upper_query.starts_with("EXPLAIN AST") || upper_query.starts_with("EXPLAIN PIPELINE") {
-        let (body, query_id) = execute_raw_query(config, query_trimmed).await?;
+        let (body, query_id) =
+            execute_raw_query_with_params(config, query_trimmed, params, query_id, timeout_secs).await?;
         return Ok(vec![QueryResult {
             columns: vec!["Explain Output".to_string()],
             rows: vec![vec![serde_json::Value::String(body)]],
@@ -116,10 +350,10 @@ pub async fn execute_query_generic(config: &ConnectionConfig, query: String) ->
     }
 
     // Use JSONCompact for robust dynamic results and statistics
-    let base_query = query_trimmed.trim_end_matches(';').split("FORMAT").next().unwrap_or(query_trimmed).trim();
-    let query_with_format = format!("{} FORMAT JSONCompact", base_query);
+    let query_with_format = format!("{} FORMAT JSONCompact", strip_format_clause(query_trimmed));
     
-    let (body, query_id) = execute_raw_query(config, &query_with_format).await?;
+    let (body, query_id) =
+        execute_raw_query_with_params(config, &query_with_format, params, query_id, timeout_secs).await?;
 
     // Parse the entire body as a JSON object
     let response: Value = serde_json::from_str(&body)
@@ -199,9 +433,21 @@ pub async fn execute_query(config: &ConnectionConfig, query: String) -> Result<V
 pub async fn execute_query_with_timeout(
     config: &ConnectionConfig,
     query: String,
-    _timeout_secs: Option<u64>,
+    timeout_secs: Option<u64>,
 ) -> Result<Vec<QueryResult>, String> {
-    execute_query_generic(config, query).await
+    let query_id = uuid::Uuid::new_v4().to_string();
+    execute_query_tracked(config, &query, &query_id, timeout_secs).await
+}
+
+/// Runs `query` under a caller-chosen `query_id`, so it can be killed while it
+/// is still running.
+pub async fn execute_query_tracked(
+    config: &ConnectionConfig,
+    query: &str,
+    query_id: &str,
+    timeout_secs: Option<u64>,
+) -> Result<Vec<QueryResult>, String> {
+    execute_query_internal(config, query, &[], Some(query_id), timeout_secs).await
 }
 
 // --- Metadata ---
@@ -3197,3 +3443,13 @@ pub async fn kill_query(config: &ConnectionConfig, process_id: i64) -> Result<St
         }
     }
 }
+
+pub async fn kill_query_by_id(config: &ConnectionConfig, query_id: &str) -> Result<String, String> {
+    let query = format!(
+        "KILL QUERY WHERE query_id = '{}' ASYNC",
+        query_id.replace('\\', "\\\\").replace('\'', "\\'")
+    );
+    // Sent directly: the timeout path of the query runner calls back into here
+    send_raw_query(config, &query, None, &[], None).await?;
+    Ok(format!("Query {} cancelled successfully", query_id))
+}
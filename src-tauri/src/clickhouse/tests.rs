@@ -130,3 +130,40 @@ fn test_kafka_consumer_info_serialization() {
     assert!(json.contains("lag"));
     assert!(json.contains("intent_size"));
 }
+
+#[test]
+fn test_parse_each_row_line() {
+    let values = parse_each_row_line(b"[1,\"a\",null]\n").unwrap().unwrap();
+    assert_eq!(values, vec![json!(1), json!("a"), Value::Null]);
+
+    assert!(parse_each_row_line(b"  \n").unwrap().is_none());
+
+    let err = parse_each_row_line(b"Code: 241. DB::Exception: Memory limit exceeded").unwrap_err();
+    assert!(err.contains("Memory limit exceeded"));
+}
+
+#[test]
+fn test_normalize_query_timeout_seconds() {
+    assert_eq!(normalize_query_timeout_seconds(None), None);
+    assert_eq!(normalize_query_timeout_seconds(Some(0)), None);
+    assert_eq!(normalize_query_timeout_seconds(Some(30)), Some(30));
+    assert_eq!(
+        normalize_query_timeout_seconds(Some(MAX_QUERY_TIMEOUT_SECS + 1)),
+        Some(MAX_QUERY_TIMEOUT_SECS)
+    );
+}
+
+#[test]
+fn test_build_json_each_row_insert() {
+    let columns = vec!["id".to_string(), "na`me".to_string()];
+    let rows = vec![vec![json!(1), json!("a")], vec![json!(2), Value::Null]];
+    let body = build_json_each_row_insert("`db`.`t`", &columns, &rows).unwrap();
+    let mut lines = body.lines();
+
+    let header = lines.next().unwrap();
+    assert!(header.starts_with("INSERT INTO `db`.`t` (`id`, `na\\`me`) SETTINGS"));
+    assert!(header.ends_with("FORMAT JSONEachRow"));
+    assert_eq!(lines.next().unwrap(), r#"{"id":1,"na`me":"a"}"#);
+    assert_eq!(lines.next().unwrap(), r#"{"id":2,"na`me":null}"#);
+    assert!(lines.next().is_none());
+}
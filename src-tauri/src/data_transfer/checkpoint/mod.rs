@@ -0,0 +1,261 @@
+use crate::data_transfer::models::{DataTransferPartitionCheckpoint, DataTransferStepCheckpoint};
+use crate::data_transfer::planner::{DataTransferExecutionPlan, DataTransferPlanStep};
+use crate::data_transfer::sink::DataTransferSinkType;
+use crate::data_transfer::storage;
+use crate::db_types::DatabaseType;
+use chrono::Utc;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Where a resumed keyset step picks up.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResumePoint {
+    pub cursor_values: Vec<Value>,
+    pub written_rows: usize,
+}
+
+/// Where a resumed partitioned step picks up.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartitionResume {
+    pub partitions: Vec<DataTransferPartitionCheckpoint>,
+    pub written_rows: usize,
+}
+
+/// Records the committed progress of one step of one run.
+#[derive(Debug, Clone)]
+pub struct StepCheckpointer {
+    operation_id: String,
+    step_key: String,
+    previous: Option<DataTransferStepCheckpoint>,
+}
+
+impl StepCheckpointer {
+    pub fn new(
+        operation_id: &str,
+        step_key: &str,
+        previous: Option<DataTransferStepCheckpoint>,
+    ) -> Self {
+        Self {
+            operation_id: operation_id.to_string(),
+            step_key: step_key.to_string(),
+            previous,
+        }
+    }
+
+    /// The earlier attempt's cursor, when it paged over the same columns.
+    pub fn resume_point(&self, cursor_columns: &[String]) -> Option<ResumePoint> {
+        let previous = self.previous.as_ref()?;
+        let same_columns = previous.cursor_columns.len() == cursor_columns.len()
+            && previous
+                .cursor_columns
+                .iter()
+                .zip(cursor_columns)
+                .all(|(a, b)| a.eq_ignore_ascii_case(b));
+        if !same_columns {
+            return None;
+        }
+
+        Some(ResumePoint {
+            cursor_values: previous.cursor_values.clone()?,
+            written_rows: previous.written_rows,
+        })
+    }
+
+    /// The earlier attempt's partitions, when it split on the same key.
+    pub fn resume_partitions(&self, key_columns: &[String]) -> Option<PartitionResume> {
+        let previous = self.previous.as_ref()?;
+        let same_key = previous.cursor_columns.len() == key_columns.len()
+            && previous
+                .cursor_columns
+                .iter()
+                .zip(key_columns)
+                .all(|(a, b)| a.eq_ignore_ascii_case(b));
+        if !same_key || previous.partitions.is_empty() {
+            return None;
+        }
+
+        Some(PartitionResume {
+            partitions: previous.partitions.clone(),
+            written_rows: previous.written_rows,
+        })
+    }
+
+    /// Whether the earlier attempt committed rows this one continues after, so
+    /// the target must be kept rather than cleared.
+    pub fn has_resume_position(&self) -> bool {
+        self.previous
+            .as_ref()
+            .is_some_and(|previous| is_partial(previous) && has_position(previous))
+    }
+
+    /// Marks the step as begun; a resumed step keeps its earlier cursor. Until
+    /// rows or a cursor are recorded, a resume runs the step as planned.
+    pub async fn record_started(&self) {
+        if self.previous.is_none() {
+            self.record(Vec::new(), None, Vec::new(), 0, false).await;
+        }
+    }
+
+    pub async fn record_cursor(
+        &self,
+        cursor_columns: &[String],
+        cursor_values: &[Value],
+        written_rows: usize,
+    ) {
+        self.record(
+            cursor_columns.to_vec(),
+            Some(cursor_values.to_vec()),
+            Vec::new(),
+            written_rows,
+            false,
+        )
+        .await;
+    }
+
+    /// Progress without a resumable position; a resume restarts the step and
+    /// upserts on `key_columns`.
+    pub async fn record_keys(&self, key_columns: &[String], written_rows: usize) {
+        self.record(key_columns.to_vec(), None, Vec::new(), written_rows, false)
+            .await;
+    }
+
+    /// Progress of a copy split into key ranges that finish out of order; a
+    /// resume continues each range after its high-water key.
+    pub async fn record_partitions(
+        &self,
+        key_columns: &[String],
+        partitions: Vec<DataTransferPartitionCheckpoint>,
+        written_rows: usize,
+    ) {
+        self.record(key_columns.to_vec(), None, partitions, written_rows, false)
+            .await;
+    }
+
+    pub async fn record_completed(&self, written_rows: usize) {
+        self.record(Vec::new(), None, Vec::new(), written_rows, true)
+            .await;
+    }
+
+    async fn record(
+        &self,
+        cursor_columns: Vec<String>,
+        cursor_values: Option<Vec<Value>>,
+        partitions: Vec<DataTransferPartitionCheckpoint>,
+        written_rows: usize,
+        completed: bool,
+    ) {
+        storage::put_checkpoint(DataTransferStepCheckpoint {
+            operation_id: self.operation_id.clone(),
+            step_key: self.step_key.clone(),
+            cursor_columns,
+            cursor_values,
+            partitions,
+            written_rows,
+            completed,
+            updated_at: Utc::now(),
+        })
+        .await;
+    }
+}
+
+/// What a (re)started run does with each step of its plan.
+#[derive(Debug, Clone)]
+pub enum StepRunAction {
+    Skip {
+        step_key: String,
+    },
+    Run {
+        step: Box<DataTransferPlanStep>,
+        previous: Option<DataTransferStepCheckpoint>,
+    },
+}
+
+pub fn fresh_step_actions(plan: &DataTransferExecutionPlan) -> Vec<StepRunAction> {
+    plan.steps
+        .iter()
+        .map(|step| StepRunAction::Run {
+            step: Box::new(step.clone()),
+            previous: None,
+        })
+        .collect()
+}
+
+/// Plans a resumed run: finished steps are skipped, and steps that already
+/// wrote part of their rows are re-run in a mode that tolerates rows landing
+/// twice.
+pub fn resume_step_actions(
+    plan: &DataTransferExecutionPlan,
+    checkpoints: &HashMap<String, DataTransferStepCheckpoint>,
+    target_type: &DatabaseType,
+) -> Result<Vec<StepRunAction>, String> {
+    plan.steps
+        .iter()
+        .map(|step| {
+            let previous = checkpoints.get(&step.step_key);
+            if previous.is_some_and(|checkpoint| checkpoint.completed) {
+                return Ok(StepRunAction::Skip {
+                    step_key: step.step_key.clone(),
+                });
+            }
+            let step = match previous {
+                Some(checkpoint) if is_partial(checkpoint) => {
+                    idempotent_step(step, checkpoint, target_type)?
+                }
+                _ => step.clone(),
+            };
+            Ok(StepRunAction::Run {
+                step: Box::new(step),
+                previous: previous.cloned(),
+            })
+        })
+        .collect()
+}
+
+/// Whether the earlier attempt committed anything; a step that only recorded
+/// its start wrote no rows.
+fn is_partial(checkpoint: &DataTransferStepCheckpoint) -> bool {
+    checkpoint.written_rows > 0 || checkpoint.cursor_values.is_some()
+}
+
+/// A cursor or partition high-water keys a resume continues after.
+fn has_position(checkpoint: &DataTransferStepCheckpoint) -> bool {
+    checkpoint.cursor_values.is_some() || !checkpoint.partitions.is_empty()
+}
+
+fn idempotent_step(
+    step: &DataTransferPlanStep,
+    checkpoint: &DataTransferStepCheckpoint,
+    target_type: &DatabaseType,
+) -> Result<DataTransferPlanStep, String> {
+    let mode = step.mode.trim().to_ascii_lowercase();
+    // File sinks are rewritten whole, and upserts already tolerate repeats
+    if step.sink_type != DataTransferSinkType::Database || mode == "upsert" {
+        return Ok(step.clone());
+    }
+
+    // Without a position the step restarts, and its truncate makes that safe
+    if mode == "replace" && !has_position(checkpoint) {
+        return Ok(step.clone());
+    }
+
+    let key_columns = if step.key_columns.is_empty() {
+        checkpoint.cursor_columns.clone()
+    } else {
+        step.key_columns.clone()
+    };
+    if key_columns.is_empty() || *target_type == DatabaseType::ClickHouse {
+        return Err(format!(
+            "Step '{}' ({}) already wrote rows in {} mode and has no key to upsert on; resuming would duplicate them",
+            step.step_key, step.source_table, mode
+        ));
+    }
+
+    Ok(DataTransferPlanStep {
+        mode: "upsert".to_string(),
+        key_columns,
+        ..step.clone()
+    })
+}
+
+#[cfg(test)]
+mod tests;
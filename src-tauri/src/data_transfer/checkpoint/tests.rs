@@ -0,0 +1,189 @@
+use super::*;
+use serde_json::json;
+
+fn step(key: &str, mode: &str, key_columns: &[&str]) -> DataTransferPlanStep {
+    DataTransferPlanStep {
+        step_key: key.to_string(),
+        source_table: "orders".to_string(),
+        target_table: "orders".to_string(),
+        mode: mode.to_string(),
+        key_columns: key_columns.iter().map(|c| c.to_string()).collect(),
+        sink_type: DataTransferSinkType::Database,
+        sink_path: None,
+        depends_on: Vec::new(),
+        column_mapping: None,
+    }
+}
+
+fn checkpoint(key: &str, cursor: Option<i64>, completed: bool) -> DataTransferStepCheckpoint {
+    DataTransferStepCheckpoint {
+        operation_id: "op1".to_string(),
+        step_key: key.to_string(),
+        cursor_columns: if cursor.is_some() {
+            vec!["id".to_string()]
+        } else {
+            Vec::new()
+        },
+        cursor_values: cursor.map(|value| vec![json!(value)]),
+        partitions: Vec::new(),
+        written_rows: 1000,
+        completed,
+        updated_at: Utc::now(),
+    }
+}
+
+#[test]
+fn resume_skips_finished_steps_and_upserts_partial_ones() {
+    let plan = DataTransferExecutionPlan {
+        steps: vec![
+            step("step_1", "append", &[]),
+            step("step_2", "replace", &[]),
+            step("step_3", "append", &[]),
+        ],
+    };
+    let checkpoints = HashMap::from([
+        ("step_1".to_string(), checkpoint("step_1", None, true)),
+        (
+            "step_2".to_string(),
+            checkpoint("step_2", Some(1000), false),
+        ),
+    ]);
+
+    let actions = resume_step_actions(&plan, &checkpoints, &DatabaseType::MySQL).unwrap();
+    assert!(matches!(&actions[0], StepRunAction::Skip { step_key } if step_key == "step_1"));
+    match &actions[1] {
+        StepRunAction::Run { step, previous } => {
+            assert_eq!(step.mode, "upsert");
+            assert_eq!(step.key_columns, vec!["id".to_string()]);
+            assert!(previous.is_some());
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+    // Never started, so it runs as planned
+    match &actions[2] {
+        StepRunAction::Run { step, previous } => {
+            assert_eq!(step.mode, "append");
+            assert!(previous.is_none());
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn resume_refuses_steps_that_would_duplicate_rows() {
+    let plan = DataTransferExecutionPlan {
+        steps: vec![step("step_1", "append", &[])],
+    };
+    let started = HashMap::from([("step_1".to_string(), checkpoint("step_1", None, false))]);
+    assert!(resume_step_actions(&plan, &started, &DatabaseType::PostgreSQL).is_err());
+
+    let with_cursor = HashMap::from([("step_1".to_string(), checkpoint("step_1", Some(5), false))]);
+    assert!(resume_step_actions(&plan, &with_cursor, &DatabaseType::ClickHouse).is_err());
+
+    // A replace that never committed a chunk just reloads
+    let plan = DataTransferExecutionPlan {
+        steps: vec![step("step_1", "replace", &[])],
+    };
+    let actions = resume_step_actions(&plan, &started, &DatabaseType::ClickHouse).unwrap();
+    assert!(matches!(&actions[0], StepRunAction::Run { step, .. } if step.mode == "replace"));
+}
+
+#[test]
+fn resume_runs_steps_that_only_started_as_planned() {
+    let plan = DataTransferExecutionPlan {
+        steps: vec![step("step_1", "append", &[])],
+    };
+    let mut started = checkpoint("step_1", None, false);
+    started.written_rows = 0;
+    let checkpoints = HashMap::from([("step_1".to_string(), started)]);
+
+    let actions = resume_step_actions(&plan, &checkpoints, &DatabaseType::ClickHouse).unwrap();
+    match &actions[0] {
+        StepRunAction::Run { step, previous } => {
+            assert_eq!(step.mode, "append");
+            assert!(previous.is_some());
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn resume_point_requires_matching_cursor_columns() {
+    let checkpointer =
+        StepCheckpointer::new("op1", "step_1", Some(checkpoint("step_1", Some(42), false)));
+    assert_eq!(
+        checkpointer.resume_point(&["ID".to_string()]),
+        Some(ResumePoint {
+            cursor_values: vec![json!(42)],
+            written_rows: 1000,
+        })
+    );
+    assert_eq!(
+        checkpointer.resume_point(&["id".to_string(), "region".to_string()]),
+        None
+    );
+    assert_eq!(
+        StepCheckpointer::new("op1", "step_1", None).resume_point(&["id".to_string()]),
+        None
+    );
+}
+
+#[test]
+fn resume_partitions_continue_after_each_high_water_key() {
+    let mut previous = checkpoint("step_1", None, false);
+    previous.cursor_columns = vec!["customer_id".to_string()];
+    previous.partitions = vec![
+        DataTransferPartitionCheckpoint {
+            lower: 1,
+            upper: 50,
+            high_water: Some(50),
+        },
+        DataTransferPartitionCheckpoint {
+            lower: 51,
+            upper: 100,
+            high_water: None,
+        },
+    ];
+    let checkpointer = StepCheckpointer::new("op1", "step_1", Some(previous));
+
+    let resume = checkpointer
+        .resume_partitions(&["CUSTOMER_ID".to_string()])
+        .unwrap();
+    assert_eq!(resume.written_rows, 1000);
+    assert_eq!(resume.partitions[0].high_water, Some(50));
+    assert_eq!(resume.partitions[1].high_water, None);
+    // The source key name is not what the target was checkpointed under
+    assert!(checkpointer
+        .resume_partitions(&["id".to_string()])
+        .is_none());
+}
+
+#[test]
+fn resume_upserts_replace_steps_that_saved_partitions() {
+    let plan = DataTransferExecutionPlan {
+        steps: vec![step("step_1", "replace", &[])],
+    };
+    let mut previous = checkpoint("step_1", None, false);
+    previous.cursor_columns = vec!["id".to_string()];
+    previous.partitions = vec![DataTransferPartitionCheckpoint {
+        lower: 1,
+        upper: 100,
+        high_water: Some(40),
+    }];
+    let checkpoints = HashMap::from([("step_1".to_string(), previous.clone())]);
+
+    let actions = resume_step_actions(&plan, &checkpoints, &DatabaseType::PostgreSQL).unwrap();
+    match &actions[0] {
+        StepRunAction::Run { step, .. } => {
+            assert_eq!(step.mode, "upsert");
+            assert_eq!(step.key_columns, vec!["id".to_string()]);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+    // The loaded rows must survive, so the target is not cleared
+    assert!(StepCheckpointer::new("op1", "step_1", Some(previous)).has_resume_position());
+    assert!(
+        !StepCheckpointer::new("op1", "step_1", Some(checkpoint("step_1", None, false)))
+            .has_resume_position()
+    );
+}
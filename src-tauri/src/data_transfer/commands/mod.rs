@@ -1,17 +1,23 @@
+use crate::data_transfer::checkpoint::{self, StepCheckpointer, StepRunAction};
 use crate::data_transfer::connection_resolver::{self, ResolvedTransferConnection};
+use crate::data_transfer::constraints::{self, ConstraintSuspension};
 use crate::data_transfer::engine;
+use crate::data_transfer::mapper::{self, ColumnMappingRule};
 use crate::data_transfer::models::{
-    DataTransferPlanPreview, DataTransferPlanRequest, DataTransferRunStatus,
-    DataTransferRunSummary, DataTransferSchemaMigrationPreflight, StartDataTransferRequest,
+    DataTransferConstraintHandling, DataTransferMappingProfile, DataTransferPlanPreview,
+    DataTransferPlanRequest, DataTransferRunStatus, DataTransferRunSummary, DataTransferSchemaMigrationPreflight,
+    StartDataTransferRequest,
 };
+use crate::data_transfer::planner::{self, DataTransferExecutionPlan};
 use crate::data_transfer::sink::DataTransferSinkType;
-use crate::data_transfer::planner;
 use crate::data_transfer::storage;
 use crate::db_types::{AppState, DatabaseType};
 use crate::schema_tracker::migration::MigrationStrategy;
 use chrono::Utc;
 use serde_json::{json, Value};
-use std::collections::HashMap;
+use futures::StreamExt;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering as AtomicOrdering};
 use std::sync::LazyLock;
 use tauri::{command, AppHandle, State};
 use tokio::sync::Mutex;
@@ -19,6 +25,36 @@ use uuid::Uuid;
 
 static DATA_TRANSFER_RUN_STORE: LazyLock<Mutex<HashMap<String, DataTransferRunSummary>>> =
     LazyLock::new(|| Mutex::new(HashMap::new()));
+/// Operations whose worker has not exited yet. Cancellation is only seen
+/// between steps, so a cancelled run can still be writing.
+static LIVE_TRANSFER_WORKERS: LazyLock<std::sync::Mutex<HashSet<String>>> =
+    LazyLock::new(|| std::sync::Mutex::new(HashSet::new()));
+
+/// Marks an operation's worker as live until dropped.
+struct TransferWorkerGuard {
+    operation_id: String,
+}
+
+impl TransferWorkerGuard {
+    /// None while another worker of the same operation is still running.
+    fn claim(operation_id: &str) -> Option<Self> {
+        let mut guard = LIVE_TRANSFER_WORKERS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        guard.insert(operation_id.to_string()).then(|| Self {
+            operation_id: operation_id.to_string(),
+        })
+    }
+}
+
+impl Drop for TransferWorkerGuard {
+    fn drop(&mut self) {
+        let mut guard = LIVE_TRANSFER_WORKERS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        guard.remove(&self.operation_id);
+    }
+}
 
 fn db_type_label(db_type: &crate::db_types::DatabaseType) -> &'static str {
     match db_type {
@@ -75,29 +111,6 @@ fn build_script_preview(script: &str, max_lines: usize) -> Option<String> {
     Some(preview)
 }
 
-fn build_skipped_preflight(
-    plan: &DataTransferPlanRequest,
-    reason: String,
-) -> DataTransferSchemaMigrationPreflight {
-    DataTransferSchemaMigrationPreflight {
-        status: "skipped".to_string(),
-        source_scope: plan.source_database.trim().to_string(),
-        target_scope: plan.target_database.trim().to_string(),
-        strategy: None,
-        has_schema_changes: false,
-        new_table_count: 0,
-        dropped_table_count: 0,
-        modified_table_count: 0,
-        breaking_change_count: 0,
-        migration_warning_count: 0,
-        external_command_count: 0,
-        unsupported_statement_count: 0,
-        warnings: vec![reason],
-        error: None,
-        migration_script_preview: None,
-    }
-}
-
 fn build_error_preflight(
     plan: &DataTransferPlanRequest,
     db_type: &DatabaseType,
@@ -120,6 +133,7 @@ fn build_error_preflight(
         warnings: vec![format!("Schema migration preflight failed: {}", error)],
         error: Some(error),
         migration_script_preview: None,
+        rollback_script_preview: None,
     }
 }
 
@@ -155,10 +169,22 @@ async fn capture_schema_snapshot(
             .await
         }
         DatabaseType::MSSQL => {
-            Err("Schema tracking not yet supported for MSSQL".to_string())
+            let pool = crate::mssql::create_pool(&connection.config).await?;
+            crate::schema_tracker::capture::capture_snapshot_mssql(
+                &pool,
+                scope,
+                &connection.connection_id,
+            )
+            .await
         }
         DatabaseType::SQLite => {
-            Err("Schema tracking not yet supported for SQLite".to_string())
+            let pool = crate::sqlite::create_pool(&connection.config.host).await?;
+            crate::schema_tracker::capture::capture_snapshot_sqlite(
+                &pool,
+                scope,
+                &connection.connection_id,
+            )
+            .await
         }
         DatabaseType::Disconnected => {
             Err("Disconnected database type is not valid for schema preflight".to_string())
@@ -175,43 +201,64 @@ async fn build_schema_migration_preflight(
         return None;
     }
 
-    if source_connection.db_type != target_connection.db_type {
-        return Some(build_skipped_preflight(
-            plan,
-            format!(
-                "Schema migration preflight skipped for cross-engine route (source: {}, target: {})",
-                db_type_label(&source_connection.db_type),
-                db_type_label(&target_connection.db_type)
-            ),
-        ));
-    }
-
     let source_scope = plan.source_database.trim().to_string();
     let target_scope = plan.target_database.trim().to_string();
-    let strategy = resolve_migration_strategy(plan.lock_guard, &source_connection.db_type);
+    let target_db_type = &target_connection.db_type;
+    let strategy = resolve_migration_strategy(plan.lock_guard, target_db_type);
 
     let result = async {
         let source_snapshot = capture_schema_snapshot(source_connection, &source_scope).await?;
         let target_snapshot = capture_schema_snapshot(target_connection, &target_scope).await?;
 
         // Diff direction is target -> source so migration plan describes how to align target with source.
-        let diff = crate::schema_tracker::diff::compare_schemas(&target_snapshot, &source_snapshot);
+        let mut translation_warnings = Vec::new();
+        let diff = if source_connection.db_type != *target_db_type {
+            // Cross-engine routes compare both sides in the target's canonical spelling,
+            // then plan with the types each side declares
+            let (translated, notes) = crate::schema_tracker::type_mapping::translate_snapshot(
+                &source_snapshot,
+                &source_connection.db_type,
+                target_db_type,
+            );
+            translation_warnings = notes;
+            let mut diff = crate::schema_tracker::diff::compare_schemas(
+                &crate::schema_tracker::type_mapping::normalize_snapshot(
+                    &target_snapshot,
+                    target_db_type,
+                    true,
+                ),
+                &crate::schema_tracker::type_mapping::normalize_snapshot(
+                    &translated,
+                    target_db_type,
+                    true,
+                ),
+            );
+            crate::schema_tracker::type_mapping::restore_declared_types(
+                &mut diff,
+                &target_snapshot,
+                &translated,
+            );
+            diff
+        } else {
+            crate::schema_tracker::diff::compare_schemas(&target_snapshot, &source_snapshot)
+        };
         let breaking_changes = crate::schema_tracker::diff::detect_breaking_changes(&diff);
         let migration_plan = crate::schema_tracker::migration::generate_migration_plan(
             &diff,
-            &source_connection.db_type,
+            target_db_type,
             Some(strategy.clone()),
         );
 
-        let has_schema_changes = !diff.new_tables.is_empty()
-            || !diff.dropped_tables.is_empty()
-            || !diff.modified_tables.is_empty();
+        let has_schema_changes = diff.has_changes();
 
         let mut warnings = migration_plan
             .warnings
             .iter()
             .map(|warning| format!("[{}] {}", warning.severity, warning.message))
             .collect::<Vec<_>>();
+        for note in translation_warnings {
+            push_warning_once(&mut warnings, format!("[type mapping] {}", note));
+        }
 
         if !has_schema_changes {
             warnings.push("No schema differences detected between source and target scopes.".to_string());
@@ -238,17 +285,14 @@ async fn build_schema_migration_preflight(
             warnings,
             error: None,
             migration_script_preview: build_script_preview(&migration_plan.script, 120),
+            rollback_script_preview: build_script_preview(&migration_plan.rollback_script, 120),
         })
     }
     .await;
 
     match result {
         Ok(preflight) => Some(preflight),
-        Err(error) => Some(build_error_preflight(
-            plan,
-            &source_connection.db_type,
-            error,
-        )),
+        Err(error) => Some(build_error_preflight(plan, target_db_type, error)),
     }
 }
 
@@ -346,6 +390,65 @@ fn build_plan_preview(
     }
 }
 
+/// Builds the plan's steps and gives steps without inline column mappings the
+/// mappings of the plan's saved profile.
+async fn build_mapped_execution_plan(
+    plan: &DataTransferPlanRequest,
+) -> Result<DataTransferExecutionPlan, String> {
+    let mut execution_plan = planner::build_execution_plan(plan)?;
+    if let Some(profile_name) = plan.normalized_mapping_profile() {
+        let profile = storage::get_mapping_profile(&profile_name)
+            .await
+            .ok_or_else(|| format!("Mapping profile '{}' not found", profile_name))?;
+        planner::apply_mapping_profile(&mut execution_plan, &profile);
+    }
+    Ok(execution_plan)
+}
+
+/// Orders the plan's steps parent-first by the target's foreign keys and, when
+/// the plan asks for it, prepares suspending constraints for the load.
+async fn prepare_target_constraints(
+    plan: &DataTransferPlanRequest,
+    target_connection: &ResolvedTransferConnection,
+    execution_plan: &mut DataTransferExecutionPlan,
+) -> Result<(Vec<String>, Option<ConstraintSuspension>), String> {
+    let mut tables: Vec<String> = Vec::new();
+    for step in &execution_plan.steps {
+        if step.sink_type == DataTransferSinkType::Database && !tables.contains(&step.target_table)
+        {
+            tables.push(step.target_table.clone());
+        }
+    }
+    if tables.is_empty() {
+        return Ok((Vec::new(), None));
+    }
+
+    let target_database = plan.target_database.trim();
+    let foreign_keys =
+        constraints::fetch_target_foreign_keys(target_connection, target_database, &tables)
+            .await?;
+    let mut warnings = planner::order_steps_by_dependencies(
+        execution_plan,
+        &constraints::table_dependencies(&foreign_keys),
+        plan.constraint_handling.enforces_order(),
+    )?;
+
+    let suspension = match plan.constraint_handling {
+        DataTransferConstraintHandling::Enforce => None,
+        DataTransferConstraintHandling::DisableDuringLoad => {
+            let suspension = ConstraintSuspension::new(
+                target_connection.db_type.clone(),
+                target_database,
+                tables,
+                foreign_keys,
+            );
+            warnings.extend(suspension.warnings());
+            Some(suspension)
+        }
+    };
+    Ok((warnings, suspension))
+}
+
 fn resolve_plan_connections(
     app_handle: &AppHandle,
     app_state: &AppState,
@@ -439,6 +542,7 @@ async fn finalize_run_success(operation_id: &str) {
     let snapshot = run.clone();
     drop(store);
     upsert_run_snapshot(&snapshot).await;
+    storage::clear_resume_state(operation_id).await;
 }
 
 async fn finalize_run_failed(operation_id: &str, error: String) {
@@ -464,40 +568,130 @@ async fn finalize_run_failed(operation_id: &str, error: String) {
 async fn run_transfer_execution(
     operation_id: String,
     plan_request: DataTransferPlanRequest,
-    execution_plan: planner::DataTransferExecutionPlan,
+    step_actions: Vec<StepRunAction>,
     source_connection: ResolvedTransferConnection,
     target_connection: ResolvedTransferConnection,
     dry_run: bool,
+    constraints: Option<ConstraintSuspension>,
 ) {
     let Some(object_count) = update_run_status_running(&operation_id).await else {
         return;
     };
 
-    for (index, step) in execution_plan.steps.iter().enumerate() {
-        if is_run_cancelled(&operation_id).await {
-            return;
+    let mut skipped = 0usize;
+    let mut runs = Vec::with_capacity(step_actions.len());
+    for action in step_actions {
+        match action {
+            StepRunAction::Skip { .. } => skipped += 1,
+            StepRunAction::Run { step, previous } => runs.push((*step, previous)),
         }
+    }
+    if skipped > 0 && !update_run_progress(&operation_id, skipped, object_count).await {
+        return;
+    }
 
-        if let Err(error) = engine::execute_step(
-            &source_connection,
-            &target_connection,
-            &plan_request.source_database,
-            &plan_request.target_database,
-            step,
-            dry_run,
-        )
-        .await
-        {
+    let mut runtime = engine::TransferRuntime::new(plan_request.performance.clone());
+    // Dry runs write nothing, so the target's constraints stay as they are
+    let constraints = constraints.filter(|_| !dry_run);
+    if let Some(suspension) = constraints.as_ref() {
+        runtime = runtime.with_target_session(suspension.session_settings());
+        if let Err(error) = suspension.suspend(&target_connection).await {
+            // Undo whatever part of the suspension did apply
+            let _ = suspension.restore(&target_connection).await;
             finalize_run_failed(&operation_id, error).await;
             return;
         }
+    }
+
+    let steps = runs.iter().map(|(step, _)| step.clone()).collect::<Vec<_>>();
+    let processed = AtomicUsize::new(skipped);
+    let stop = AtomicBool::new(false);
+
+    // Waves keep parents ahead of the tables referencing them. Groups inside a
+    // wave write distinct targets; a failure lets the others finish their
+    // current step instead of abandoning it mid-write
+    let mut errors = Vec::new();
+    for wave in engine::schedule_step_waves(&steps) {
+        if !errors.is_empty() || stop.load(AtomicOrdering::SeqCst) {
+            break;
+        }
+        errors = futures::stream::iter(wave)
+            .map(|group| {
+                let (operation_id, runs, runtime) = (&operation_id, &runs, &runtime);
+                let (processed, stop) = (&processed, &stop);
+                let (source_connection, target_connection) = (&source_connection, &target_connection);
+                let plan_request = &plan_request;
+                async move {
+                    for index in group {
+                        if stop.load(AtomicOrdering::SeqCst) || is_run_cancelled(operation_id).await {
+                            stop.store(true, AtomicOrdering::SeqCst);
+                            return None;
+                        }
+
+                        let (step, previous) = &runs[index];
+                        // Dry runs write nothing, so there is nothing to resume
+                        let checkpointer = (!dry_run).then(|| {
+                            StepCheckpointer::new(operation_id, &step.step_key, previous.clone())
+                        });
+                        if let Some(checkpointer) = checkpointer.as_ref() {
+                            checkpointer.record_started().await;
+                        }
+
+                        let result = engine::execute_step(
+                            source_connection,
+                            target_connection,
+                            &plan_request.source_database,
+                            &plan_request.target_database,
+                            step,
+                            dry_run,
+                            engine::StepContext {
+                                checkpoint: checkpointer.as_ref(),
+                                runtime,
+                            },
+                        )
+                        .await;
+                        match result {
+                            Ok(result) => {
+                                if let Some(checkpointer) = checkpointer.as_ref() {
+                                    checkpointer.record_completed(result.written_rows).await;
+                                }
+                            }
+                            Err(error) => {
+                                stop.store(true, AtomicOrdering::SeqCst);
+                                return Some(error);
+                            }
+                        }
+
+                        let done = processed.fetch_add(1, AtomicOrdering::SeqCst) + 1;
+                        if !update_run_progress(operation_id, done, object_count).await {
+                            stop.store(true, AtomicOrdering::SeqCst);
+                            return None;
+                        }
+                    }
+                    None
+                }
+            })
+            .buffer_unordered(runtime.table_parallelism())
+            .filter_map(|error| async move { error })
+            .collect::<Vec<String>>()
+            .await;
+    }
 
-        if !update_run_progress(&operation_id, index + 1, object_count).await {
-            return;
+    let mut error = errors.into_iter().next();
+    if let Some(suspension) = constraints.as_ref() {
+        // Restored even after a failure so the target is not left unchecked
+        if let Err(restore_error) = suspension.restore(&target_connection).await {
+            error = Some(match error {
+                Some(error) => format!("{}; {}", error, restore_error),
+                None => restore_error,
+            });
         }
     }
 
-    finalize_run_success(&operation_id).await;
+    match error {
+        Some(error) => finalize_run_failed(&operation_id, error).await,
+        None => finalize_run_success(&operation_id).await,
+    }
 }
 
 pub async fn start_data_transfer_with_context(
@@ -506,22 +700,26 @@ pub async fn start_data_transfer_with_context(
     request: StartDataTransferRequest,
 ) -> Result<DataTransferRunSummary, String> {
     request.plan.validate()?;
-    let execution_plan = planner::build_execution_plan(&request.plan)?;
+    let mut execution_plan = build_mapped_execution_plan(&request.plan).await?;
     let (source_connection, target_connection) =
         resolve_plan_connections(app_handle, app_state, &request.plan)?;
+    let (order_warnings, constraints) =
+        prepare_target_constraints(&request.plan, &target_connection, &mut execution_plan).await?;
     let schema_migration_preflight =
-        build_schema_migration_preflight(&request.plan, &source_connection, &target_connection).await;
+        build_schema_migration_preflight(&request.plan, &source_connection, &target_connection)
+            .await;
     if let Some(block_reason) =
         lock_guard_block_reason(&request.plan, schema_migration_preflight.as_ref())
     {
         return Err(block_reason);
     }
-    let preview = build_plan_preview(
+    let mut preview = build_plan_preview(
         &request.plan,
         &source_connection,
         &target_connection,
         schema_migration_preflight.clone(),
     );
+    append_unique_warnings(&mut preview.warnings, &order_warnings);
     let dry_run = request.dry_run.unwrap_or(false);
     let now = Utc::now();
     let operation_id = Uuid::new_v4().to_string();
@@ -552,15 +750,122 @@ pub async fn start_data_transfer_with_context(
         store.insert(operation_id.clone(), run.clone());
     }
     upsert_run_snapshot(&run).await;
+    if !dry_run {
+        storage::put_run_request(&operation_id, &request.plan).await;
+    }
 
+    let step_actions = checkpoint::fresh_step_actions(&execution_plan);
+    let worker = TransferWorkerGuard::claim(&operation_id);
     tauri::async_runtime::spawn(async move {
+        let _worker = worker;
         run_transfer_execution(
             operation_id,
             request.plan,
-            execution_plan,
+            step_actions,
             source_connection,
             target_connection,
             dry_run,
+            constraints,
+        )
+        .await;
+    });
+
+    Ok(run)
+}
+
+async fn load_run(operation_id: &str) -> Option<DataTransferRunSummary> {
+    {
+        let store = DATA_TRANSFER_RUN_STORE.lock().await;
+        if let Some(run) = store.get(operation_id) {
+            return Some(run.clone());
+        }
+    }
+    storage::get_snapshot(operation_id).await
+}
+
+pub async fn resume_data_transfer_with_context(
+    app_handle: &AppHandle,
+    app_state: &AppState,
+    operation_id: &str,
+) -> Result<DataTransferRunSummary, String> {
+    let mut run = load_run(operation_id)
+        .await
+        .ok_or_else(|| format!("Transfer run '{}' not found", operation_id))?;
+    if !matches!(
+        run.status,
+        DataTransferRunStatus::Failed | DataTransferRunStatus::Cancelled
+    ) {
+        return Err(format!(
+            "Transfer run '{}' is {:?}; only failed or cancelled runs can be resumed",
+            operation_id, run.status
+        ));
+    }
+    if run.dry_run {
+        return Err("Dry-run transfers have nothing to resume".to_string());
+    }
+    let worker = TransferWorkerGuard::claim(operation_id).ok_or_else(|| {
+        format!(
+            "Transfer run '{}' is still finishing its current step; resume it once that step stops",
+            operation_id
+        )
+    })?;
+
+    let plan_request = storage::get_run_request(operation_id).await.ok_or_else(|| {
+        format!(
+            "Transfer run '{}' has no stored plan to resume from",
+            operation_id
+        )
+    })?;
+    let mut execution_plan = build_mapped_execution_plan(&plan_request).await?;
+    let (source_connection, target_connection) =
+        resolve_plan_connections(app_handle, app_state, &plan_request)?;
+    let (order_warnings, constraints) =
+        prepare_target_constraints(&plan_request, &target_connection, &mut execution_plan).await?;
+    let checkpoints = storage::list_checkpoints(operation_id).await;
+    let step_actions = checkpoint::resume_step_actions(
+        &execution_plan,
+        &checkpoints,
+        &target_connection.db_type,
+    )?;
+
+    let skipped = step_actions
+        .iter()
+        .filter(|action| matches!(action, StepRunAction::Skip { .. }))
+        .count();
+    push_warning_once(
+        &mut run.warnings,
+        format!(
+            "Resumed at {} from checkpoints; {} of {} step(s) were already complete.",
+            Utc::now().to_rfc3339(),
+            skipped,
+            step_actions.len()
+        ),
+    );
+    append_unique_warnings(&mut run.warnings, &order_warnings);
+    run.warning_count = run.warnings.len();
+    run.status = DataTransferRunStatus::Queued;
+    run.processed_objects = skipped;
+    run.updated_at = Utc::now();
+    run.finished_at = None;
+    run.error = None;
+
+    {
+        let mut store = DATA_TRANSFER_RUN_STORE.lock().await;
+        store.insert(run.operation_id.clone(), run.clone());
+    }
+    upsert_run_snapshot(&run).await;
+
+    let operation_id = run.operation_id.clone();
+    tauri::async_runtime::spawn(async move {
+        let _worker = worker;
+        run_transfer_execution(
+            operation_id,
+            plan_request,
+            step_actions,
+            source_connection,
+            target_connection,
+            false,
+            constraints,
         )
         .await;
     });
@@ -579,12 +884,19 @@ pub async fn preview_data_transfer_plan(
         resolve_plan_connections(&app_handle, app_state.inner(), &request)?;
     let schema_migration_preflight =
         build_schema_migration_preflight(&request, &source_connection, &target_connection).await;
-    Ok(build_plan_preview(
+    let mut preview = build_plan_preview(
         &request,
         &source_connection,
         &target_connection,
         schema_migration_preflight,
-    ))
+    );
+
+    let mut execution_plan = build_mapped_execution_plan(&request).await?;
+    match prepare_target_constraints(&request, &target_connection, &mut execution_plan).await {
+        Ok((order_warnings, _)) => append_unique_warnings(&mut preview.warnings, &order_warnings),
+        Err(error) => push_warning_once(&mut preview.warnings, error),
+    }
+    Ok(preview)
 }
 
 #[command]
@@ -597,7 +909,22 @@ pub async fn start_data_transfer(
 }
 
 #[command]
-pub async fn get_data_transfer_status(operation_id: String) -> Result<DataTransferRunSummary, String> {
+pub async fn resume_data_transfer(
+    app_handle: AppHandle,
+    app_state: State<'_, AppState>,
+    operation_id: String,
+) -> Result<DataTransferRunSummary, String> {
+    let op_id = operation_id.trim();
+    if op_id.is_empty() {
+        return Err("operationId is required".to_string());
+    }
+    resume_data_transfer_with_context(&app_handle, app_state.inner(), op_id).await
+}
+
+#[command]
+pub async fn get_data_transfer_status(
+    operation_id: String,
+) -> Result<DataTransferRunSummary, String> {
     let op_id = operation_id.trim();
     if op_id.is_empty() {
         return Err("operationId is required".to_string());
@@ -615,7 +942,9 @@ pub async fn get_data_transfer_status(operation_id: String) -> Result<DataTransf
 }
 
 #[command]
-pub async fn list_data_transfer_runs(limit: Option<usize>) -> Result<Vec<DataTransferRunSummary>, String> {
+pub async fn list_data_transfer_runs(
+    limit: Option<usize>,
+) -> Result<Vec<DataTransferRunSummary>, String> {
     let max_rows = limit.unwrap_or(50).clamp(1, 500);
 
     let mut merged = storage::list_snapshots(max_rows).await;
@@ -684,13 +1013,41 @@ pub async fn cancel_data_transfer(operation_id: String) -> Result<DataTransferRu
 }
 
 #[command]
-pub fn validate_data_transfer_mapping(
-    rules: Vec<crate::data_transfer::mapper::ColumnMappingRule>,
-) -> Result<String, String> {
-    crate::data_transfer::mapper::validate_mapping_rules(&rules)?;
+pub fn validate_data_transfer_mapping(rules: Vec<ColumnMappingRule>) -> Result<String, String> {
+    mapper::validate_mapping_rules(&rules)?;
     Ok(format!("{} mapping rules validated", rules.len()))
 }
 
+#[command]
+pub async fn save_data_transfer_mapping_profile(
+    profile: DataTransferMappingProfile,
+) -> Result<DataTransferMappingProfile, String> {
+    let mut profile = profile;
+    profile.name = profile.name.trim().to_string();
+    profile.validate()?;
+    profile.updated_at = Utc::now();
+    storage::put_mapping_profile(profile.clone()).await?;
+    Ok(profile)
+}
+
+#[command]
+pub async fn list_data_transfer_mapping_profiles() -> Result<Vec<DataTransferMappingProfile>, String>
+{
+    Ok(storage::list_mapping_profiles().await)
+}
+
+#[command]
+pub async fn delete_data_transfer_mapping_profile(name: String) -> Result<(), String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Mapping profile name is required".to_string());
+    }
+    if !storage::delete_mapping_profile(name).await? {
+        return Err(format!("Mapping profile '{}' not found", name));
+    }
+    Ok(())
+}
+
 #[command]
 pub fn generate_transfer_task_payload(request: DataTransferPlanRequest) -> Result<Value, String> {
     request.validate()?;
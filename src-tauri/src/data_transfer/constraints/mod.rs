@@ -0,0 +1,402 @@
+// =====================================================
+// TARGET CONSTRAINTS
+// Foreign key metadata for load ordering, and suspending
+// constraint enforcement for the length of a run
+// =====================================================
+
+use crate::data_transfer::connection_resolver::ResolvedTransferConnection;
+use crate::data_transfer::engine::mssql_table_ref;
+use crate::data_transfer::planner::TableDependency;
+use crate::db::sql_utils::{qualified_table_name, quote_column_name};
+use crate::db_types::{DatabaseType, ForeignKey, QueryResult};
+use crate::mssql;
+
+/// One foreign key of a target table, with composite columns collapsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TargetForeignKey {
+    pub table: String,
+    pub constraint_name: String,
+    pub columns: Vec<String>,
+    pub referenced_schema: Option<String>,
+    pub referenced_table: String,
+    pub referenced_columns: Vec<String>,
+}
+
+/// Collapses per-column metadata rows into one entry per constraint.
+pub fn group_foreign_keys(table: &str, rows: Vec<ForeignKey>) -> Vec<TargetForeignKey> {
+    let mut grouped: Vec<TargetForeignKey> = Vec::new();
+    for row in rows {
+        if row.referenced_table.trim().is_empty() {
+            continue;
+        }
+        match grouped
+            .iter_mut()
+            .find(|existing| existing.constraint_name == row.constraint_name)
+        {
+            Some(existing) => {
+                existing.columns.push(row.column_name);
+                existing.referenced_columns.push(row.referenced_column);
+            }
+            None => grouped.push(TargetForeignKey {
+                table: table.to_string(),
+                constraint_name: row.constraint_name,
+                columns: vec![row.column_name],
+                referenced_schema: row.referenced_schema,
+                referenced_table: row.referenced_table,
+                referenced_columns: vec![row.referenced_column],
+            }),
+        }
+    }
+    grouped
+}
+
+pub fn table_dependencies(foreign_keys: &[TargetForeignKey]) -> Vec<TableDependency> {
+    foreign_keys
+        .iter()
+        .map(|foreign_key| TableDependency {
+            child: foreign_key.table.clone(),
+            parent: foreign_key.referenced_table.clone(),
+        })
+        .collect()
+}
+
+/// Reads the foreign keys of `tables` on the target. A table whose metadata
+/// cannot be read (typically one the run has yet to create) contributes none.
+pub async fn fetch_target_foreign_keys(
+    target: &ResolvedTransferConnection,
+    database: &str,
+    tables: &[String],
+) -> Result<Vec<TargetForeignKey>, String> {
+    let mut foreign_keys = Vec::new();
+    match target.db_type {
+        DatabaseType::MySQL => {
+            let pool = crate::mysql::create_pool(&target.config).await?;
+            for table in tables {
+                if let Ok(rows) = crate::mysql::get_table_foreign_keys(&pool, database, table).await
+                {
+                    foreign_keys.extend(group_foreign_keys(table, rows));
+                }
+            }
+        }
+        DatabaseType::PostgreSQL => {
+            let pool = crate::postgres::create_pool(&target.config).await?;
+            for table in tables {
+                if let Ok(rows) =
+                    crate::postgres::get_table_foreign_keys(&pool, database, table).await
+                {
+                    foreign_keys.extend(group_foreign_keys(table, rows));
+                }
+            }
+        }
+        DatabaseType::SQLite => {
+            let pool = crate::sqlite::create_pool(&target.config.host).await?;
+            for table in tables {
+                if let Ok(rows) =
+                    crate::sqlite::get_table_foreign_keys(&pool, database, table).await
+                {
+                    foreign_keys.extend(group_foreign_keys(table, rows));
+                }
+            }
+        }
+        DatabaseType::MSSQL => {
+            let pool = mssql::create_pool(&target.config).await?;
+            for table in tables {
+                if let Ok(rows) = mssql::get_table_foreign_keys(&pool, database, "dbo", table).await
+                {
+                    foreign_keys.extend(group_foreign_keys(table, rows));
+                }
+            }
+        }
+        // ClickHouse has no foreign keys
+        DatabaseType::ClickHouse | DatabaseType::Disconnected => {}
+    }
+    Ok(foreign_keys)
+}
+
+// =====================================================
+// CONSTRAINT SUSPENSION
+// =====================================================
+
+/// Suspends constraint enforcement on the target tables of one run, then
+/// restores it and checks the loaded rows.
+///
+/// MySQL, PostgreSQL and SQLite scope the switch to a session, so it is
+/// applied to every target connection the run opens and lapses with them;
+/// SQL Server disables constraints and triggers per table.
+#[derive(Debug, Clone)]
+pub struct ConstraintSuspension {
+    db_type: DatabaseType,
+    database: String,
+    tables: Vec<String>,
+    foreign_keys: Vec<TargetForeignKey>,
+}
+
+impl ConstraintSuspension {
+    pub fn new(
+        db_type: DatabaseType,
+        database: &str,
+        tables: Vec<String>,
+        foreign_keys: Vec<TargetForeignKey>,
+    ) -> Self {
+        Self {
+            db_type,
+            database: database.to_string(),
+            tables,
+            foreign_keys,
+        }
+    }
+
+    /// Settings every target connection of the run opens with.
+    pub fn session_settings(&self) -> Vec<(String, String)> {
+        let setting = match self.db_type {
+            DatabaseType::MySQL => ("FOREIGN_KEY_CHECKS", "0"),
+            // Also skips ordinary triggers, which fire only for the origin role
+            DatabaseType::PostgreSQL => ("session_replication_role", "replica"),
+            DatabaseType::SQLite => ("foreign_keys", "OFF"),
+            _ => return Vec::new(),
+        };
+        vec![(setting.0.to_string(), setting.1.to_string())]
+    }
+
+    pub fn warnings(&self) -> Vec<String> {
+        match self.db_type {
+            DatabaseType::MySQL | DatabaseType::SQLite => vec![
+                "Foreign key checks are suspended during the load; triggers cannot be suspended on this engine and still fire"
+                    .to_string(),
+            ],
+            DatabaseType::PostgreSQL => vec![
+                "Constraints are suspended with session_replication_role = replica, which requires superuser (or equivalent) privileges on the target"
+                    .to_string(),
+            ],
+            DatabaseType::MSSQL => vec![
+                "Foreign keys, check constraints and triggers are disabled on the target tables during the load and re-checked WITH CHECK afterwards"
+                    .to_string(),
+            ],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Statements run once before the first step.
+    pub fn suspend_statements(&self) -> Vec<String> {
+        match self.db_type {
+            DatabaseType::MSSQL => self
+                .tables
+                .iter()
+                .flat_map(|table| {
+                    let table_ref = mssql_table_ref(&self.database, table);
+                    [
+                        format!("ALTER TABLE {} NOCHECK CONSTRAINT ALL", table_ref),
+                        format!("ALTER TABLE {} DISABLE TRIGGER ALL", table_ref),
+                    ]
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Queries counting child rows whose parent row is missing, one per
+    /// foreign key. SQL Server validates on restore and needs none.
+    pub fn validation_queries(&self) -> Vec<(String, String)> {
+        if !matches!(
+            self.db_type,
+            DatabaseType::MySQL | DatabaseType::PostgreSQL | DatabaseType::SQLite
+        ) {
+            return Vec::new();
+        }
+
+        self.foreign_keys
+            .iter()
+            .map(|foreign_key| {
+                let label = format!(
+                    "{}.{} -> {}",
+                    foreign_key.table, foreign_key.constraint_name, foreign_key.referenced_table
+                );
+                (label, self.orphan_count_query(foreign_key))
+            })
+            .collect()
+    }
+
+    fn orphan_count_query(&self, foreign_key: &TargetForeignKey) -> String {
+        let child = self.table_ref(None, &foreign_key.table);
+        let parent = self.table_ref(
+            foreign_key.referenced_schema.as_deref(),
+            &foreign_key.referenced_table,
+        );
+        let present = foreign_key
+            .columns
+            .iter()
+            .map(|column| format!("c.{} IS NOT NULL", quote_column_name(&self.db_type, column)))
+            .collect::<Vec<_>>()
+            .join(" AND ");
+        let matches = foreign_key
+            .columns
+            .iter()
+            .zip(&foreign_key.referenced_columns)
+            .map(|(column, referenced)| {
+                format!(
+                    "p.{} = c.{}",
+                    quote_column_name(&self.db_type, referenced),
+                    quote_column_name(&self.db_type, column)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(" AND ");
+
+        format!(
+            "SELECT COUNT(*) AS cnt FROM {} c WHERE {} AND NOT EXISTS (SELECT 1 FROM {} p WHERE {})",
+            child, present, parent, matches
+        )
+    }
+
+    fn table_ref(&self, schema: Option<&str>, table: &str) -> String {
+        match self.db_type {
+            DatabaseType::SQLite => quote_column_name(&self.db_type, table),
+            _ => {
+                let database = schema
+                    .map(str::trim)
+                    .filter(|schema| !schema.is_empty())
+                    .unwrap_or(&self.database);
+                qualified_table_name(&self.db_type, database, table)
+            }
+        }
+    }
+
+    pub async fn suspend(&self, target: &ResolvedTransferConnection) -> Result<(), String> {
+        let statements = self.suspend_statements();
+        if statements.is_empty() {
+            return Ok(());
+        }
+        let pool = mssql::create_pool(&target.config).await?;
+        for statement in statements {
+            mssql::execute_query(&pool, statement)
+                .await
+                .map_err(|e| format!("Failed to suspend target constraints: {}", e))?;
+        }
+        Ok(())
+    }
+
+    /// Puts enforcement back and fails with every foreign key the loaded rows
+    /// violate.
+    pub async fn restore(&self, target: &ResolvedTransferConnection) -> Result<(), String> {
+        let violations = match self.db_type {
+            DatabaseType::MSSQL => self.restore_mssql(target).await?,
+            DatabaseType::MySQL | DatabaseType::PostgreSQL | DatabaseType::SQLite => {
+                self.count_orphans(target).await?
+            }
+            _ => Vec::new(),
+        };
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "Foreign key re-validation failed after load: {}",
+                violations.join("; ")
+            ))
+        }
+    }
+
+    async fn restore_mssql(
+        &self,
+        target: &ResolvedTransferConnection,
+    ) -> Result<Vec<String>, String> {
+        let pool = mssql::create_pool(&target.config).await?;
+        // One table failing must not leave the rest with triggers and checks off
+        let mut violations = Vec::new();
+        let mut errors = Vec::new();
+        for table in &self.tables {
+            let table_ref = mssql_table_ref(&self.database, table);
+            if let Err(e) = mssql::execute_query(
+                &pool,
+                format!("ALTER TABLE {} ENABLE TRIGGER ALL", table_ref),
+            )
+            .await
+            {
+                errors.push(format!("Failed to re-enable triggers on {}: {}", table, e));
+            }
+
+            let validated = mssql::execute_query(
+                &pool,
+                format!("ALTER TABLE {} WITH CHECK CHECK CONSTRAINT ALL", table_ref),
+            )
+            .await;
+            if let Err(error) = validated {
+                // Enforce new writes at least; the constraints stay untrusted
+                if let Err(e) = mssql::execute_query(
+                    &pool,
+                    format!("ALTER TABLE {} CHECK CONSTRAINT ALL", table_ref),
+                )
+                .await
+                {
+                    errors.push(format!(
+                        "Failed to re-enable constraints on {}: {}",
+                        table, e
+                    ));
+                }
+                violations.push(format!("{}: {}", table, error));
+            }
+        }
+        if !errors.is_empty() {
+            errors.extend(violations);
+            return Err(errors.join("; "));
+        }
+        Ok(violations)
+    }
+
+    async fn count_orphans(
+        &self,
+        target: &ResolvedTransferConnection,
+    ) -> Result<Vec<String>, String> {
+        let queries = self.validation_queries();
+        if queries.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut counts = Vec::with_capacity(queries.len());
+        match self.db_type {
+            DatabaseType::MySQL => {
+                let pool = crate::mysql::create_pool(&target.config).await?;
+                for (label, query) in queries {
+                    let results = crate::mysql::execute_query(&pool, query).await?;
+                    counts.push((label, first_count(&results)?));
+                }
+            }
+            DatabaseType::PostgreSQL => {
+                let pool = crate::postgres::create_pool(&target.config).await?;
+                for (label, query) in queries {
+                    let results = crate::postgres::execute_query(&pool, query).await?;
+                    counts.push((label, first_count(&results)?));
+                }
+            }
+            _ => {
+                let pool = crate::sqlite::create_pool(&target.config.host).await?;
+                for (label, query) in queries {
+                    let results = crate::sqlite::execute_query(&pool, &query).await?;
+                    counts.push((label, first_count(&results)?));
+                }
+            }
+        }
+
+        Ok(counts
+            .into_iter()
+            .filter(|(_, orphans)| *orphans > 0)
+            .map(|(label, orphans)| format!("{} has {} row(s) without a parent", label, orphans))
+            .collect())
+    }
+}
+
+fn first_count(results: &[QueryResult]) -> Result<usize, String> {
+    let value = results
+        .first()
+        .and_then(|result| result.rows.first())
+        .and_then(|row| row.first())
+        .ok_or_else(|| "Foreign key check returned no count".to_string())?;
+    value
+        .as_u64()
+        .map(|count| count as usize)
+        .or_else(|| value.as_str().and_then(|text| text.parse().ok()))
+        .ok_or_else(|| format!("Unexpected foreign key check result: {}", value))
+}
+
+#[cfg(test)]
+mod tests;
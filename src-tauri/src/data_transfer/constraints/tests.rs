@@ -0,0 +1,91 @@
+use super::*;
+
+fn fk_row(constraint: &str, column: &str, table: &str, referenced: &str) -> ForeignKey {
+    ForeignKey {
+        constraint_name: constraint.to_string(),
+        column_name: column.to_string(),
+        referenced_table: table.to_string(),
+        referenced_column: referenced.to_string(),
+        referenced_schema: None,
+    }
+}
+
+#[test]
+fn composite_foreign_keys_collapse_into_one_constraint() {
+    let grouped = group_foreign_keys(
+        "order_lines",
+        vec![
+            fk_row("fk_line_order", "order_id", "orders", "id"),
+            fk_row("fk_line_product", "sku", "products", "sku"),
+            fk_row("fk_line_product", "region", "products", "region"),
+        ],
+    );
+
+    assert_eq!(grouped.len(), 2);
+    assert_eq!(
+        grouped[1].columns,
+        vec!["sku".to_string(), "region".to_string()]
+    );
+    assert_eq!(
+        grouped[1].referenced_columns,
+        vec!["sku".to_string(), "region".to_string()]
+    );
+    assert_eq!(
+        table_dependencies(&grouped)[0],
+        TableDependency {
+            child: "order_lines".to_string(),
+            parent: "orders".to_string(),
+        }
+    );
+}
+
+#[test]
+fn suspension_uses_each_engines_switch() {
+    let foreign_keys = group_foreign_keys(
+        "orders",
+        vec![fk_row("fk_customer", "customer_id", "customers", "id")],
+    );
+    let tables = vec!["orders".to_string()];
+
+    let mysql = ConstraintSuspension::new(
+        DatabaseType::MySQL,
+        "shop",
+        tables.clone(),
+        foreign_keys.clone(),
+    );
+    assert_eq!(
+        mysql.session_settings(),
+        vec![("FOREIGN_KEY_CHECKS".to_string(), "0".to_string())]
+    );
+    assert!(mysql.suspend_statements().is_empty());
+    assert_eq!(
+        mysql.validation_queries()[0].1,
+        "SELECT COUNT(*) AS cnt FROM `shop`.`orders` c WHERE c.`customer_id` IS NOT NULL AND NOT EXISTS (SELECT 1 FROM `shop`.`customers` p WHERE p.`id` = c.`customer_id`)"
+    );
+
+    let postgres = ConstraintSuspension::new(
+        DatabaseType::PostgreSQL,
+        "public",
+        tables.clone(),
+        foreign_keys.clone(),
+    );
+    assert_eq!(
+        postgres.session_settings(),
+        vec![(
+            "session_replication_role".to_string(),
+            "replica".to_string()
+        )]
+    );
+
+    let mssql = ConstraintSuspension::new(DatabaseType::MSSQL, "shop", tables, foreign_keys);
+    assert!(mssql.session_settings().is_empty());
+    assert_eq!(
+        mssql.suspend_statements(),
+        vec![
+            "ALTER TABLE [shop].[dbo].[orders] NOCHECK CONSTRAINT ALL".to_string(),
+            "ALTER TABLE [shop].[dbo].[orders] DISABLE TRIGGER ALL".to_string(),
+        ]
+    );
+    // WITH CHECK re-validates on restore instead
+    assert!(mssql.validation_queries().is_empty());
+}
@@ -0,0 +1,1381 @@
+// =====================================================
+// GENERIC TRANSFER MATRIX
+// Any supported engine can feed any other through one source reader and one
+// target writer; engine pairs with dedicated keyset paths keep using those
+// unless the run asks for partitioning, adaptive chunks, a throttle or a
+// column mapping
+// =====================================================
+
+use super::*;
+use crate::data_transfer::mapper::{StepMapping, TableColumnMapping};
+use crate::data_transfer::models::DataTransferPartitionCheckpoint;
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use std::borrow::Cow;
+use std::time::Instant;
+use tiberius::{ColumnData, IntoSql, TokenRow};
+use tokio::sync::Mutex;
+
+/// Rows per INSERT statement for SQL targets; MSSQL caps a VALUES list at 1000.
+const INSERT_ROWS_PER_STATEMENT: usize = 500;
+
+// =====================================================
+// SOURCE READER
+// =====================================================
+
+pub(super) enum SourceReader {
+    MySql {
+        pool: sqlx::Pool<sqlx::MySql>,
+        table_ref: String,
+    },
+    Postgres {
+        pool: sqlx::Pool<sqlx::Postgres>,
+        table_ref: String,
+    },
+    Mssql {
+        pool: deadpool_tiberius::Pool,
+        table_ref: String,
+    },
+    ClickHouse {
+        config: Box<crate::db_types::ConnectionConfig>,
+        table_ref: String,
+    },
+    Sqlite {
+        pool: sqlx::Pool<sqlx::Sqlite>,
+        table_ref: String,
+    },
+}
+
+impl SourceReader {
+    pub(super) async fn connect(
+        connection: &ResolvedTransferConnection,
+        database: &str,
+        table: &str,
+    ) -> Result<Self, String> {
+        match connection.db_type {
+            DatabaseType::MySQL => Ok(Self::MySql {
+                pool: crate::mysql::create_pool(&connection.config).await?,
+                table_ref: qualified_table_name(&DatabaseType::MySQL, database, table),
+            }),
+            DatabaseType::PostgreSQL => Ok(Self::Postgres {
+                pool: crate::postgres::create_pool(&connection.config).await?,
+                table_ref: qualified_table_name(&DatabaseType::PostgreSQL, database, table),
+            }),
+            DatabaseType::MSSQL => Ok(Self::Mssql {
+                pool: mssql::create_pool(&connection.config).await?,
+                table_ref: mssql_table_ref(database, table),
+            }),
+            DatabaseType::ClickHouse => Ok(Self::ClickHouse {
+                config: Box::new(connection.config.clone()),
+                table_ref: qualified_table_name(&DatabaseType::ClickHouse, database, table),
+            }),
+            DatabaseType::SQLite => Ok(Self::Sqlite {
+                pool: crate::sqlite::create_pool(&connection.config.host).await?,
+                table_ref: quote_column_name(&DatabaseType::SQLite, table),
+            }),
+            DatabaseType::Disconnected => {
+                Err("Disconnected database type is not valid for transfer".to_string())
+            }
+        }
+    }
+
+    pub(super) async fn count_rows(&self) -> Result<usize, String> {
+        match self {
+            Self::MySql { pool, table_ref } => query_row_count_mysql(pool, table_ref).await,
+            Self::Postgres { pool, table_ref } => query_row_count_postgres(pool, table_ref).await,
+            Self::Mssql { pool, table_ref } => query_row_count_mssql(pool, table_ref).await,
+            Self::ClickHouse { config, table_ref } => {
+                query_row_count_clickhouse(config, table_ref).await
+            }
+            Self::Sqlite { pool, table_ref } => query_row_count_sqlite(pool, table_ref).await,
+        }
+    }
+
+    /// Reads `limit` rows starting at `offset`, in `order_by` order.
+    pub(super) async fn read_chunk(
+        &self,
+        offset: usize,
+        limit: usize,
+        order_by: &[String],
+    ) -> Result<(Vec<String>, Vec<Vec<Value>>), String> {
+        let query =
+            build_offset_page_query(&self.db_type(), self.table_ref(), order_by, offset, limit);
+        Ok(first_result_set(&self.run_query(query).await?))
+    }
+
+    fn db_type(&self) -> DatabaseType {
+        match self {
+            Self::MySql { .. } => DatabaseType::MySQL,
+            Self::Postgres { .. } => DatabaseType::PostgreSQL,
+            Self::Mssql { .. } => DatabaseType::MSSQL,
+            Self::ClickHouse { .. } => DatabaseType::ClickHouse,
+            Self::Sqlite { .. } => DatabaseType::SQLite,
+        }
+    }
+
+    fn table_ref(&self) -> &str {
+        match self {
+            Self::MySql { table_ref, .. }
+            | Self::Postgres { table_ref, .. }
+            | Self::Mssql { table_ref, .. }
+            | Self::ClickHouse { table_ref, .. }
+            | Self::Sqlite { table_ref, .. } => table_ref,
+        }
+    }
+
+    async fn run_query(&self, query: String) -> Result<Vec<QueryResult>, String> {
+        match self {
+            Self::MySql { pool, .. } => crate::mysql::execute_query(pool, query).await,
+            Self::Postgres { pool, .. } => crate::postgres::execute_query(pool, query).await,
+            Self::Mssql { pool, .. } => mssql::execute_query(pool, query).await,
+            Self::ClickHouse { config, .. } => {
+                crate::clickhouse::execute_query(config, query).await
+            }
+            Self::Sqlite { pool, .. } => crate::sqlite::execute_query(pool, &query).await,
+        }
+    }
+
+    /// Columns that identify a row: the primary key, else a unique index over
+    /// NOT NULL columns. ClickHouse has no unique keys.
+    pub(super) async fn key_columns(
+        &self,
+        database: &str,
+        table: &str,
+    ) -> Result<Vec<String>, String> {
+        match self {
+            Self::MySql { pool, .. } => resolve_keyset_cursors_mysql(pool, database, table).await,
+            Self::Postgres { pool, .. } => {
+                resolve_keyset_cursors_postgres(pool, database, table).await
+            }
+            Self::Mssql { pool, .. } => resolve_keyset_cursors_mssql(pool, database, table).await,
+            Self::Sqlite { pool, .. } => {
+                let query = format!(
+                    "SELECT name FROM pragma_table_info('{}') WHERE pk > 0 ORDER BY pk",
+                    escape_sql_string(table)
+                );
+                Ok(parse_first_column_strings(
+                    &crate::sqlite::execute_query(pool, &query).await?,
+                ))
+            }
+            Self::ClickHouse { .. } => Ok(Vec::new()),
+        }
+    }
+
+    /// MIN and MAX of `column`, or None when they are not integers.
+    pub(super) async fn key_bounds(&self, column: &str) -> Result<Option<KeyRange>, String> {
+        let quoted = quote_column_name(&self.db_type(), column);
+        let results = self
+            .run_query(format!(
+                "SELECT MIN({quoted}), MAX({quoted}) FROM {}",
+                self.table_ref()
+            ))
+            .await?;
+        let (_, rows) = first_result_set(&results);
+        let Some(row) = rows.first() else {
+            return Ok(None);
+        };
+        Ok(
+            match (
+                row.first().and_then(value_as_i64),
+                row.get(1).and_then(value_as_i64),
+            ) {
+                (Some(lower), Some(upper)) if lower <= upper => Some(KeyRange { lower, upper }),
+                _ => None,
+            },
+        )
+    }
+
+    pub(super) async fn read_key_range(
+        &self,
+        column: &str,
+        range: KeyRange,
+        after: Option<i64>,
+        limit: usize,
+    ) -> Result<(Vec<String>, Vec<Vec<Value>>), String> {
+        let query = build_key_range_query(
+            &self.db_type(),
+            self.table_ref(),
+            column,
+            range,
+            after,
+            limit,
+        );
+        Ok(first_result_set(&self.run_query(query).await?))
+    }
+}
+
+/// Inclusive key range of one partition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct KeyRange {
+    pub(super) lower: i64,
+    pub(super) upper: i64,
+}
+
+/// Splits `range` into at most `parts` contiguous, non-overlapping ranges.
+pub(super) fn split_key_range(range: KeyRange, parts: usize) -> Vec<KeyRange> {
+    let span = range.upper as i128 - range.lower as i128 + 1;
+    let parts = (parts.max(1) as i128).min(span);
+    let width = (span + parts - 1) / parts;
+
+    let mut ranges = Vec::with_capacity(parts as usize);
+    let mut lower = range.lower as i128;
+    while lower <= range.upper as i128 {
+        let upper = (lower + width - 1).min(range.upper as i128);
+        ranges.push(KeyRange {
+            lower: lower as i64,
+            upper: upper as i64,
+        });
+        lower = upper + 1;
+    }
+    ranges
+}
+
+/// OFFSET page in key order, so pages neither overlap nor skip rows. Without a
+/// key SQLite still has its rowid; the other engines return rows in no stable order.
+pub(super) fn build_offset_page_query(
+    db_type: &DatabaseType,
+    table_ref: &str,
+    order_by: &[String],
+    offset: usize,
+    limit: usize,
+) -> String {
+    let order = order_by
+        .iter()
+        .map(|column| quote_column_name(db_type, column))
+        .collect::<Vec<_>>()
+        .join(", ");
+    match db_type {
+        DatabaseType::MSSQL => format!(
+            "SELECT * FROM {} ORDER BY {} OFFSET {} ROWS FETCH NEXT {} ROWS ONLY",
+            table_ref,
+            if order.is_empty() {
+                "(SELECT NULL)"
+            } else {
+                &order
+            },
+            offset,
+            limit
+        ),
+        DatabaseType::SQLite if order.is_empty() => format!(
+            "SELECT * FROM {} ORDER BY rowid LIMIT {} OFFSET {}",
+            table_ref, limit, offset
+        ),
+        _ if order.is_empty() => build_chunk_select_query(table_ref, limit, offset),
+        _ => format!(
+            "SELECT * FROM {} ORDER BY {} LIMIT {} OFFSET {}",
+            table_ref, order, limit, offset
+        ),
+    }
+}
+
+pub(super) fn build_key_range_query(
+    db_type: &DatabaseType,
+    table_ref: &str,
+    column: &str,
+    range: KeyRange,
+    after: Option<i64>,
+    limit: usize,
+) -> String {
+    let quoted = quote_column_name(db_type, column);
+    let lower = match after {
+        Some(after) => format!("{} > {}", quoted, after),
+        None => format!("{} >= {}", quoted, range.lower),
+    };
+    let condition = format!("{} AND {} <= {}", lower, quoted, range.upper);
+    match db_type {
+        DatabaseType::MSSQL => format!(
+            "SELECT TOP ({}) * FROM {} WHERE {} ORDER BY {}",
+            limit, table_ref, condition, quoted
+        ),
+        _ => format!(
+            "SELECT * FROM {} WHERE {} ORDER BY {} LIMIT {}",
+            table_ref, condition, quoted, limit
+        ),
+    }
+}
+
+fn value_as_i64(value: &Value) -> Option<i64> {
+    value
+        .as_i64()
+        .or_else(|| value.as_str().and_then(|text| text.trim().parse().ok()))
+}
+
+// =====================================================
+// TARGET WRITER
+// =====================================================
+
+/// Column of an MSSQL target as bulk load sees it.
+#[derive(Debug, Clone)]
+pub(super) struct MssqlTargetColumn {
+    pub(super) name: String,
+    pub(super) data_type: String,
+    pub(super) scale: u8,
+    pub(super) nullable: bool,
+    pub(super) identity: bool,
+    /// Computed and rowversion columns; the server fills them
+    pub(super) generated: bool,
+}
+
+enum TargetSink {
+    MySql(sqlx::Pool<sqlx::MySql>),
+    Postgres(sqlx::Pool<sqlx::Postgres>),
+    Sqlite(sqlx::Pool<sqlx::Sqlite>),
+    Mssql {
+        pool: deadpool_tiberius::Pool,
+        columns: Vec<MssqlTargetColumn>,
+    },
+    ClickHouse(Box<crate::db_types::ConnectionConfig>),
+}
+
+pub(super) struct TargetWriter {
+    db_type: DatabaseType,
+    table_ref: String,
+    hints: TargetColumnHintMap,
+    sink: TargetSink,
+    mapping: Option<StepMapping>,
+}
+
+impl TargetWriter {
+    pub(super) async fn connect(
+        connection: &ResolvedTransferConnection,
+        database: &str,
+        table: &str,
+        session: &[(String, String)],
+    ) -> Result<Self, String> {
+        let db_type = connection.db_type.clone();
+        let (table_ref, hints, sink) = match db_type {
+            DatabaseType::MySQL => {
+                let pool =
+                    crate::mysql::create_pool_with_session(&connection.config, session).await?;
+                let hints = resolve_target_column_hints_mysql(&pool, database, table).await?;
+                (
+                    qualified_table_name(&db_type, database, table),
+                    hints,
+                    TargetSink::MySql(pool),
+                )
+            }
+            DatabaseType::PostgreSQL => {
+                let pool =
+                    crate::postgres::create_pool_with_session(&connection.config, session).await?;
+                let hints = resolve_target_column_hints_postgres(&pool, database, table).await?;
+                (
+                    qualified_table_name(&db_type, database, table),
+                    hints,
+                    TargetSink::Postgres(pool),
+                )
+            }
+            DatabaseType::SQLite => {
+                let pool =
+                    crate::sqlite::create_pool_with_session(&connection.config.host, session)
+                        .await?;
+                let columns = crate::sqlite::get_table_schema(&pool, database, table).await?;
+                (
+                    quote_column_name(&db_type, table),
+                    build_target_column_hint_map(&columns),
+                    TargetSink::Sqlite(pool),
+                )
+            }
+            DatabaseType::MSSQL => {
+                let pool = mssql::create_pool(&connection.config).await?;
+                let (schema, name) = mssql::split_table_name("dbo", table);
+                let hints =
+                    resolve_target_column_hints_mssql(&pool, database, schema, name).await?;
+                let columns = query_mssql_target_columns(&pool, database, table).await?;
+                (
+                    mssql_table_ref(database, table),
+                    hints,
+                    TargetSink::Mssql { pool, columns },
+                )
+            }
+            DatabaseType::ClickHouse => {
+                let hints =
+                    resolve_target_column_hints_clickhouse(&connection.config, database, table)
+                        .await?;
+                (
+                    qualified_table_name(&db_type, database, table),
+                    hints,
+                    TargetSink::ClickHouse(Box::new(connection.config.clone())),
+                )
+            }
+            DatabaseType::Disconnected => {
+                return Err("Disconnected database type is not valid for transfer".to_string())
+            }
+        };
+
+        Ok(Self {
+            db_type,
+            table_ref,
+            hints,
+            sink,
+            mapping: None,
+        })
+    }
+
+    /// Rewrites every chunk through `mapping` before it is written.
+    pub(super) fn with_mapping(mut self, mapping: Option<TableColumnMapping>) -> Self {
+        self.mapping = mapping.map(StepMapping::new);
+        self
+    }
+
+    pub(super) async fn count_rows(&self) -> Result<usize, String> {
+        match &self.sink {
+            TargetSink::MySql(pool) => query_row_count_mysql(pool, &self.table_ref).await,
+            TargetSink::Postgres(pool) => query_row_count_postgres(pool, &self.table_ref).await,
+            TargetSink::Sqlite(pool) => query_row_count_sqlite(pool, &self.table_ref).await,
+            TargetSink::Mssql { pool, .. } => query_row_count_mssql(pool, &self.table_ref).await,
+            TargetSink::ClickHouse(config) => {
+                query_row_count_clickhouse(config, &self.table_ref).await
+            }
+        }
+    }
+
+    /// Empties the target for `replace` mode.
+    pub(super) async fn clear(&self) -> Result<(), String> {
+        let statement = match self.db_type {
+            // SQLite has no TRUNCATE
+            DatabaseType::SQLite => format!("DELETE FROM {}", self.table_ref),
+            _ => format!("TRUNCATE TABLE {}", self.table_ref),
+        };
+        self.execute(statement).await
+    }
+
+    async fn execute(&self, statement: String) -> Result<(), String> {
+        match &self.sink {
+            TargetSink::MySql(pool) => crate::mysql::execute_query(pool, statement).await?,
+            TargetSink::Postgres(pool) => crate::postgres::execute_query(pool, statement).await?,
+            TargetSink::Sqlite(pool) => crate::sqlite::execute_query(pool, &statement).await?,
+            TargetSink::Mssql { pool, .. } => mssql::execute_query(pool, statement).await?,
+            TargetSink::ClickHouse(config) => {
+                crate::clickhouse::execute_query(config, statement).await?
+            }
+        };
+        Ok(())
+    }
+
+    /// Writes one chunk and returns how many rows were written.
+    pub(super) async fn write_rows(
+        &self,
+        columns: &[String],
+        rows: &[Vec<Value>],
+        mode: &str,
+        key_columns: &[String],
+    ) -> Result<usize, String> {
+        if rows.is_empty() {
+            return Ok(0);
+        }
+        let mapped = match self.mapping.as_ref() {
+            Some(mapping) => Some(mapping.apply(columns, rows)?),
+            None => None,
+        };
+        let (columns, rows) = match mapped.as_ref() {
+            Some((columns, rows)) => (columns.as_slice(), rows.as_slice()),
+            None => (columns, rows),
+        };
+        let hints = materialize_target_column_hints(columns, &self.hints);
+        let upsert = mode == "upsert" && !key_columns.is_empty();
+
+        match &self.sink {
+            TargetSink::ClickHouse(config) => {
+                if upsert {
+                    return Err(
+                        "ClickHouse targets do not support upsert mode; append into a ReplacingMergeTree table instead"
+                            .to_string(),
+                    );
+                }
+                crate::clickhouse::insert_json_each_row(config, &self.table_ref, columns, rows)
+                    .await
+            }
+            TargetSink::Mssql {
+                pool,
+                columns: target_columns,
+            } => {
+                if !upsert {
+                    if let Some(plan) = mssql_bulk_plan(target_columns, columns) {
+                        return mssql_bulk_insert(pool, &self.table_ref, &plan, rows).await;
+                    }
+                }
+                let statements =
+                    self.build_statements(columns, rows, mode, key_columns, &hints, upsert)?;
+                let identity_insert = columns.iter().any(|column| {
+                    target_columns.iter().any(|target| {
+                        target.identity
+                            && normalize_column_name(&target.name) == normalize_column_name(column)
+                    })
+                });
+                for statement in statements {
+                    let batch = if identity_insert {
+                        format!(
+                            "SET IDENTITY_INSERT {table} ON; {statement}; SET IDENTITY_INSERT {table} OFF;",
+                            table = self.table_ref,
+                            statement = statement.trim_end_matches(';'),
+                        )
+                    } else {
+                        statement
+                    };
+                    mssql::execute_query(pool, batch).await?;
+                }
+                Ok(rows.len())
+            }
+            TargetSink::Sqlite(pool) => {
+                // One transaction per chunk; autocommit would sync every row
+                let statements =
+                    self.build_statements(columns, rows, mode, key_columns, &hints, upsert)?;
+                let mut conn = pool
+                    .acquire()
+                    .await
+                    .map_err(|e| format!("Failed to acquire SQLite connection: {}", e))?;
+                crate::sqlite::execute_query_on_connection(&mut conn, "BEGIN").await?;
+                for statement in &statements {
+                    if let Err(error) =
+                        crate::sqlite::execute_query_on_connection(&mut conn, statement).await
+                    {
+                        let _ =
+                            crate::sqlite::execute_query_on_connection(&mut conn, "ROLLBACK").await;
+                        return Err(error);
+                    }
+                }
+                crate::sqlite::execute_query_on_connection(&mut conn, "COMMIT").await?;
+                Ok(rows.len())
+            }
+            TargetSink::MySql(_) | TargetSink::Postgres(_) => {
+                for statement in
+                    self.build_statements(columns, rows, mode, key_columns, &hints, upsert)?
+                {
+                    self.execute(statement).await?;
+                }
+                Ok(rows.len())
+            }
+        }
+    }
+
+    /// Multi-row INSERTs, or one upsert per row since MERGE / ON CONFLICT
+    /// statements are built per row.
+    fn build_statements(
+        &self,
+        columns: &[String],
+        rows: &[Vec<Value>],
+        mode: &str,
+        key_columns: &[String],
+        hints: &[TargetColumnHint],
+        upsert: bool,
+    ) -> Result<Vec<String>, String> {
+        if upsert {
+            return rows
+                .iter()
+                .map(|row| {
+                    build_insert_statement(
+                        &self.db_type,
+                        &self.table_ref,
+                        columns,
+                        row,
+                        mode,
+                        key_columns,
+                        Some(hints),
+                    )
+                })
+                .collect();
+        }
+        Ok(rows
+            .chunks(INSERT_ROWS_PER_STATEMENT)
+            .map(|chunk| {
+                build_multi_row_insert_statement(
+                    &self.db_type,
+                    &self.table_ref,
+                    columns,
+                    chunk,
+                    Some(hints),
+                )
+            })
+            .collect())
+    }
+}
+
+// =====================================================
+// STEP EXECUTION
+// =====================================================
+
+pub(super) async fn execute_step_generic(
+    source: &ResolvedTransferConnection,
+    target: &ResolvedTransferConnection,
+    source_database: &str,
+    target_database: &str,
+    step: &DataTransferPlanStep,
+    dry_run: bool,
+    context: StepContext<'_>,
+) -> Result<EngineStepResult, String> {
+    let mode = step.mode.trim().to_ascii_lowercase();
+    if mode == "upsert" && target.db_type == DatabaseType::ClickHouse {
+        return Err(format!(
+            "Step '{}' uses upsert mode, which ClickHouse targets do not support",
+            step.step_key
+        ));
+    }
+
+    let reader = SourceReader::connect(source, source_database, &step.source_table).await?;
+    let writer = TargetWriter::connect(
+        target,
+        target_database,
+        &step.target_table,
+        context.runtime.target_session(),
+    )
+    .await?
+    .with_mapping(step.column_mapping.clone());
+    let source_rows = reader.count_rows().await?;
+
+    if dry_run {
+        let _ = writer.count_rows().await?;
+        check_column_mapping(&reader, step).await?;
+        return Ok(EngineStepResult {
+            step_key: step.step_key.clone(),
+            source_rows,
+            written_rows: 0,
+            dry_run: true,
+        });
+    }
+
+    // A resume continues after rows the earlier attempt kept
+    let resuming = context
+        .checkpoint
+        .is_some_and(StepCheckpointer::has_resume_position);
+    if mode == "replace" && !resuming {
+        writer.clear().await?;
+    }
+
+    let runtime = context.runtime;
+    let order_by = reader
+        .key_columns(source_database, &step.source_table)
+        .await?;
+    // A single integer key is paged by key range; bounds are only an optimisation,
+    // so key types MIN/MAX cannot handle fall back to ordered OFFSET pages
+    let key_range = match order_by.as_slice() {
+        [column] => reader
+            .key_bounds(column)
+            .await
+            .unwrap_or(None)
+            .map(|bounds| (column.clone(), bounds)),
+        _ => None,
+    };
+    // Concurrent writers would only contend for SQLite's single write lock
+    let partitions = if target.db_type == DatabaseType::SQLite {
+        1
+    } else {
+        runtime.partitions_for(source_rows)
+    };
+    // A resume upserts on the key under the name the target knows it by
+    let target_keys = match step.column_mapping.as_ref() {
+        Some(mapping) => order_by
+            .iter()
+            .map(|column| mapping.target_column_for(column))
+            .collect::<Option<Vec<_>>>()
+            .unwrap_or_default(),
+        None => order_by.clone(),
+    };
+    let copy = PageCopy {
+        reader: &reader,
+        writer: &writer,
+        mode: &mode,
+        key_columns: &step.key_columns,
+        runtime,
+        checkpoint: context.checkpoint,
+        target_keys,
+    };
+
+    let written_rows = match key_range {
+        Some((column, bounds)) => {
+            // Partitions finish out of order, so each records its own high-water
+            // key and a resume continues every range after it
+            let resumed = context
+                .checkpoint
+                .and_then(|checkpoint| checkpoint.resume_partitions(&copy.target_keys));
+            let (ranges, written_rows) = match resumed {
+                Some(resume) => (
+                    widen_partitions(resume.partitions, bounds),
+                    resume.written_rows,
+                ),
+                None => (
+                    split_key_range(bounds, partitions)
+                        .into_iter()
+                        .map(|range| DataTransferPartitionCheckpoint {
+                            lower: range.lower,
+                            upper: range.upper,
+                            high_water: None,
+                        })
+                        .collect(),
+                    0,
+                ),
+            };
+            let progress = Mutex::new(PartitionProgress {
+                partitions: ranges.clone(),
+                written_rows,
+            });
+            let copies = ranges.iter().enumerate().map(|(index, partition)| {
+                copy_key_range(&copy, &column, index, *partition, &progress)
+            });
+            futures::future::try_join_all(copies).await?;
+            progress.into_inner().written_rows
+        }
+        None => copy_offset_pages(&copy, source_rows, &order_by).await?,
+    };
+
+    Ok(EngineStepResult {
+        step_key: step.step_key.clone(),
+        source_rows,
+        written_rows,
+        dry_run: false,
+    })
+}
+
+/// What every page of one step shares.
+struct PageCopy<'a> {
+    reader: &'a SourceReader,
+    writer: &'a TargetWriter,
+    mode: &'a str,
+    /// Upsert keys of the step
+    key_columns: &'a [String],
+    runtime: &'a TransferRuntime,
+    checkpoint: Option<&'a StepCheckpointer>,
+    /// Target names of the source key, recorded for a resume to upsert on
+    target_keys: Vec<String>,
+}
+
+/// Committed rows of a partitioned copy, shared by its ranges.
+struct PartitionProgress {
+    partitions: Vec<DataTransferPartitionCheckpoint>,
+    written_rows: usize,
+}
+
+/// Stretches the outer ranges of a resumed split over keys added since.
+fn widen_partitions(
+    mut partitions: Vec<DataTransferPartitionCheckpoint>,
+    bounds: KeyRange,
+) -> Vec<DataTransferPartitionCheckpoint> {
+    if let Some(first) = partitions.first_mut() {
+        first.lower = first.lower.min(bounds.lower);
+    }
+    if let Some(last) = partitions.last_mut() {
+        last.upper = last.upper.max(bounds.upper);
+    }
+    partitions
+}
+
+async fn copy_offset_pages(
+    copy: &PageCopy<'_>,
+    source_rows: usize,
+    order_by: &[String],
+) -> Result<usize, String> {
+    let mut batch = copy.runtime.batch_sizer();
+    let mut written_rows = 0usize;
+    let mut offset = 0usize;
+    while offset < source_rows {
+        let limit = batch.current();
+        copy.runtime.throttle(limit).await;
+        let started = Instant::now();
+
+        let (columns, rows) = copy.reader.read_chunk(offset, limit, order_by).await?;
+        if rows.is_empty() || columns.is_empty() {
+            break;
+        }
+
+        let written = copy
+            .writer
+            .write_rows(&columns, &rows, copy.mode, copy.key_columns)
+            .await?;
+        written_rows = written_rows.saturating_add(written);
+        batch.observe(rows.len(), started.elapsed());
+        // OFFSET pages have no cursor to seek to, so a resume restarts and upserts
+        if let Some(checkpoint) = copy.checkpoint {
+            checkpoint
+                .record_keys(&copy.target_keys, written_rows)
+                .await;
+        }
+
+        offset = offset.saturating_add(rows.len());
+        if rows.len() < limit {
+            break;
+        }
+    }
+    Ok(written_rows)
+}
+
+async fn copy_key_range(
+    copy: &PageCopy<'_>,
+    column: &str,
+    index: usize,
+    partition: DataTransferPartitionCheckpoint,
+    progress: &Mutex<PartitionProgress>,
+) -> Result<(), String> {
+    let range = KeyRange {
+        lower: partition.lower,
+        upper: partition.upper,
+    };
+    let mut batch = copy.runtime.batch_sizer();
+    let mut after = partition.high_water;
+    let mut key_index: Option<usize> = None;
+    loop {
+        let limit = batch.current();
+        copy.runtime.throttle(limit).await;
+        let started = Instant::now();
+
+        let (columns, rows) = copy
+            .reader
+            .read_key_range(column, range, after, limit)
+            .await?;
+        if rows.is_empty() || columns.is_empty() {
+            break;
+        }
+        let index_in_row = match key_index {
+            Some(index) => index,
+            None => *find_column_indices(&columns, &[column.to_string()])?
+                .first()
+                .ok_or_else(|| format!("Partition column '{}' not found", column))?,
+        };
+        key_index = Some(index_in_row);
+
+        let written = copy
+            .writer
+            .write_rows(&columns, &rows, copy.mode, copy.key_columns)
+            .await?;
+        batch.observe(rows.len(), started.elapsed());
+        let high_water = rows
+            .last()
+            .and_then(|row| row.get(index_in_row))
+            .and_then(value_as_i64)
+            .ok_or_else(|| format!("Partition column '{}' returned a non-integer value", column))?;
+        after = Some(high_water);
+
+        let mut state = progress.lock().await;
+        state.written_rows = state.written_rows.saturating_add(written);
+        state.partitions[index].high_water = Some(high_water);
+        if let Some(checkpoint) = copy.checkpoint {
+            checkpoint
+                .record_partitions(
+                    &copy.target_keys,
+                    state.partitions.clone(),
+                    state.written_rows,
+                )
+                .await;
+        }
+        drop(state);
+
+        if rows.len() < limit {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// File sinks for sources without a dedicated reader path.
+pub(super) async fn execute_step_generic_to_file_sink(
+    source: &ResolvedTransferConnection,
+    target: &ResolvedTransferConnection,
+    source_database: &str,
+    target_database: &str,
+    step: &DataTransferPlanStep,
+    dry_run: bool,
+) -> Result<EngineStepResult, String> {
+    let reader = SourceReader::connect(source, source_database, &step.source_table).await?;
+    let mode = step.mode.trim().to_ascii_lowercase();
+    let source_rows = reader.count_rows().await?;
+    let target_column_hints =
+        resolve_sql_sink_target_hints(target, target_database, &step.target_table, &step.sink_type)
+            .await?;
+
+    if dry_run {
+        check_column_mapping(&reader, step).await?;
+        return Ok(EngineStepResult {
+            step_key: step.step_key.clone(),
+            source_rows,
+            written_rows: 0,
+            dry_run: true,
+        });
+    }
+
+    let sink_path = resolve_sink_path(step)?;
+    let mut sink_writer = create_file_sink_writer(
+        &step.sink_type,
+        &sink_path,
+        &target.db_type,
+        target_database,
+        &step.target_table,
+    )
+    .await?;
+
+    let order_by = reader
+        .key_columns(source_database, &step.source_table)
+        .await?;
+    let mapping = step.column_mapping.clone().map(StepMapping::new);
+    let mut written_rows = 0usize;
+    let mut offset = 0usize;
+    while offset < source_rows {
+        let (columns, rows) = reader
+            .read_chunk(offset, TRANSFER_BATCH_SIZE, &order_by)
+            .await?;
+        if rows.is_empty() || columns.is_empty() {
+            break;
+        }
+        let read_rows = rows.len();
+        let (columns, rows) = match mapping.as_ref() {
+            Some(mapping) => mapping.apply(&columns, &rows)?,
+            None => (columns, rows),
+        };
+
+        let hints = materialize_target_column_hints(&columns, &target_column_hints);
+        sink_writer
+            .write_rows(&columns, &rows, &mode, &step.key_columns, Some(&hints))
+            .await?;
+        written_rows = written_rows.saturating_add(rows.len());
+
+        offset = offset.saturating_add(read_rows);
+        if read_rows < TRANSFER_BATCH_SIZE {
+            break;
+        }
+    }
+
+    sink_writer.flush().await?;
+
+    Ok(EngineStepResult {
+        step_key: step.step_key.clone(),
+        source_rows,
+        written_rows,
+        dry_run: false,
+    })
+}
+
+/// Dry runs apply the step's mapping to one source row so a missing column or
+/// a failing cast surfaces before anything is written.
+async fn check_column_mapping(
+    reader: &SourceReader,
+    step: &DataTransferPlanStep,
+) -> Result<(), String> {
+    let Some(mapping) = step.column_mapping.as_ref() else {
+        return Ok(());
+    };
+    let (columns, rows) = reader.read_chunk(0, 1, &[]).await?;
+    if columns.is_empty() {
+        return Ok(());
+    }
+    mapping
+        .apply(&columns, &rows)
+        .map(|_| ())
+        .map_err(|e| format!("Step '{}' column mapping: {}", step.step_key, e))
+}
+
+async fn query_row_count_sqlite(
+    pool: &sqlx::Pool<sqlx::Sqlite>,
+    table_ref: &str,
+) -> Result<usize, String> {
+    let query = format!("SELECT COUNT(*) AS cnt FROM {}", table_ref);
+    let results = crate::sqlite::execute_query(pool, &query).await?;
+    query_row_count_from_result(&results)
+}
+
+// =====================================================
+// MSSQL BULK LOAD
+// =====================================================
+
+/// `[database].[schema].[table]`, with the schema taken from a `schema.table`
+/// name and `dbo` otherwise.
+pub(crate) fn mssql_table_ref(database: &str, table: &str) -> String {
+    let (schema, name) = mssql::split_table_name("dbo", table);
+    let database = database.trim();
+    if database.is_empty() {
+        format!(
+            "{}.{}",
+            crate::db::sql_utils::quote_identifier_mssql(schema),
+            crate::db::sql_utils::quote_identifier_mssql(name)
+        )
+    } else {
+        format!(
+            "{}.{}.{}",
+            crate::db::sql_utils::quote_identifier_mssql(database),
+            crate::db::sql_utils::quote_identifier_mssql(schema),
+            crate::db::sql_utils::quote_identifier_mssql(name)
+        )
+    }
+}
+
+/// `[database].` prefix for catalog views of another database.
+fn mssql_catalog(database: &str) -> String {
+    if database.trim().is_empty() {
+        String::new()
+    } else {
+        format!(
+            "{}.",
+            crate::db::sql_utils::quote_identifier_mssql(database.trim())
+        )
+    }
+}
+
+/// Primary key columns, else those of the first unfiltered unique index without
+/// nullable columns.
+async fn resolve_keyset_cursors_mssql(
+    pool: &deadpool_tiberius::Pool,
+    database: &str,
+    table: &str,
+) -> Result<Vec<String>, String> {
+    let query = format!(
+        "SELECT c.name FROM {catalog}sys.index_columns ic \
+         JOIN {catalog}sys.columns c ON c.object_id = ic.object_id AND c.column_id = ic.column_id \
+         WHERE ic.object_id = OBJECT_ID(N'{table}') AND ic.is_included_column = 0 \
+           AND ic.index_id = ( \
+             SELECT TOP (1) i.index_id FROM {catalog}sys.indexes i \
+             WHERE i.object_id = ic.object_id AND i.is_unique = 1 AND i.has_filter = 0 \
+               AND NOT EXISTS ( \
+                 SELECT 1 FROM {catalog}sys.index_columns k \
+                 JOIN {catalog}sys.columns kc ON kc.object_id = k.object_id AND kc.column_id = k.column_id \
+                 WHERE k.object_id = i.object_id AND k.index_id = i.index_id AND kc.is_nullable = 1) \
+             ORDER BY i.is_primary_key DESC, i.index_id) \
+         ORDER BY ic.key_ordinal",
+        catalog = mssql_catalog(database),
+        table = mssql_table_ref(database, table).replace('\'', "''"),
+    );
+    let results = mssql::execute_query(pool, query).await?;
+    Ok(parse_first_column_strings(&results))
+}
+
+async fn query_mssql_target_columns(
+    pool: &deadpool_tiberius::Pool,
+    database: &str,
+    table: &str,
+) -> Result<Vec<MssqlTargetColumn>, String> {
+    let table_ref = mssql_table_ref(database, table);
+    let catalog = mssql_catalog(database);
+    let query = format!(
+        "SELECT c.name, t.name, CAST(c.scale AS INT), CAST(c.is_nullable AS INT), \
+         CAST(c.is_identity AS INT), CAST(c.is_computed AS INT) \
+         FROM {catalog}sys.columns c \
+         JOIN {catalog}sys.types t ON t.user_type_id = c.user_type_id \
+         WHERE c.object_id = OBJECT_ID(N'{table}') \
+         ORDER BY c.column_id",
+        catalog = catalog,
+        table = table_ref.replace('\'', "''"),
+    );
+    let results = mssql::execute_query(pool, query).await?;
+    let (_, rows) = first_result_set(&results);
+    if rows.is_empty() {
+        return Err(format!("MSSQL target table {} was not found", table_ref));
+    }
+
+    let flag = |value: Option<&Value>| value.and_then(Value::as_i64).unwrap_or(0) != 0;
+    Ok(rows
+        .iter()
+        .map(|row| {
+            let data_type = row
+                .get(1)
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+            MssqlTargetColumn {
+                name: row
+                    .first()
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+                scale: row.get(2).and_then(Value::as_u64).unwrap_or(0) as u8,
+                nullable: flag(row.get(3)),
+                identity: flag(row.get(4)),
+                generated: flag(row.get(5))
+                    || matches!(data_type.as_str(), "timestamp" | "rowversion"),
+                data_type,
+            }
+        })
+        .collect())
+}
+
+/// Bulk load columns paired with the source column feeding each, or None when
+/// the chunk must go through INSERT statements instead: an identity value is
+/// supplied, a type has no bulk encoding here, or a required column is missing.
+pub(super) fn mssql_bulk_plan<'a>(
+    target_columns: &'a [MssqlTargetColumn],
+    source_columns: &[String],
+) -> Option<Vec<(&'a MssqlTargetColumn, Option<usize>)>> {
+    let source_index: HashMap<String, usize> = source_columns
+        .iter()
+        .enumerate()
+        .map(|(idx, name)| (normalize_column_name(name), idx))
+        .collect();
+    let mut used = 0usize;
+    let mut plan = Vec::new();
+
+    for column in target_columns {
+        let source = source_index
+            .get(&normalize_column_name(&column.name))
+            .copied();
+        if column.identity || column.generated {
+            if source.is_some() {
+                return None;
+            }
+            continue;
+        }
+        if !mssql_bulk_supported(&column.data_type) || (source.is_none() && !column.nullable) {
+            return None;
+        }
+        used += usize::from(source.is_some());
+        plan.push((column, source));
+    }
+
+    // Source columns the target does not have would be dropped silently
+    (used == source_columns.len()).then_some(plan)
+}
+
+fn mssql_bulk_supported(data_type: &str) -> bool {
+    matches!(
+        data_type,
+        "bit"
+            | "tinyint"
+            | "smallint"
+            | "int"
+            | "bigint"
+            | "real"
+            | "float"
+            | "decimal"
+            | "numeric"
+            | "char"
+            | "varchar"
+            | "nchar"
+            | "nvarchar"
+            | "binary"
+            | "varbinary"
+            | "uniqueidentifier"
+            | "date"
+            | "time"
+            | "datetime"
+            | "datetime2"
+            | "datetimeoffset"
+    )
+}
+
+async fn mssql_bulk_insert(
+    pool: &deadpool_tiberius::Pool,
+    table_ref: &str,
+    plan: &[(&MssqlTargetColumn, Option<usize>)],
+    rows: &[Vec<Value>],
+) -> Result<usize, String> {
+    let mut client = pool.get().await.map_err(|e| e.to_string())?;
+    let mut request = client
+        .bulk_insert(table_ref)
+        .await
+        .map_err(|e| format!("Failed to start MSSQL bulk load into {}: {}", table_ref, e))?;
+
+    for row in rows {
+        let mut token_row = TokenRow::with_capacity(plan.len());
+        for (column, source) in plan {
+            let value = source.and_then(|idx| row.get(idx)).unwrap_or(&Value::Null);
+            token_row.push(mssql_bulk_value(column, value)?);
+        }
+        request
+            .send(token_row)
+            .await
+            .map_err(|e| format!("MSSQL bulk load into {} failed: {}", table_ref, e))?;
+    }
+
+    let result = request
+        .finalize()
+        .await
+        .map_err(|e| format!("MSSQL bulk load into {} failed: {}", table_ref, e))?;
+    Ok(result.total() as usize)
+}
+
+/// Encodes `value` as the exact TDS type of `column`; bulk load does not convert.
+pub(super) fn mssql_bulk_value(
+    column: &MssqlTargetColumn,
+    value: &Value,
+) -> Result<ColumnData<'static>, String> {
+    let invalid = || {
+        format!(
+            "Value {} does not fit MSSQL column '{}' ({})",
+            value, column.name, column.data_type
+        )
+    };
+    let text = match value {
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(match (n.as_i64(), n.as_f64()) {
+            (Some(i), _) => i.to_string(),
+            (None, Some(f)) => format!("{}", f),
+            _ => n.to_string(),
+        }),
+        Value::Bool(b) => Some(if *b { "1" } else { "0" }.to_string()),
+        other => Some(other.to_string()),
+    };
+    let text = text.as_deref().map(str::trim);
+    let integer = |text: Option<&str>| -> Result<Option<i64>, String> {
+        text.map(|t| match t.to_ascii_lowercase().as_str() {
+            "true" => Ok(1),
+            "false" => Ok(0),
+            lowered => lowered
+                .parse::<i64>()
+                .or_else(|_| {
+                    lowered
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|f| f.fract() == 0.0)
+                        .map(|f| f as i64)
+                        .ok_or(())
+                })
+                .map_err(|_| invalid()),
+        })
+        .transpose()
+    };
+    let float = |text: Option<&str>| -> Result<Option<f64>, String> {
+        text.map(|t| t.parse::<f64>().map_err(|_| invalid()))
+            .transpose()
+    };
+
+    let data = match column.data_type.as_str() {
+        "bit" => ColumnData::Bit(integer(text)?.map(|v| v != 0)),
+        "tinyint" => ColumnData::U8(
+            integer(text)?
+                .map(|v| u8::try_from(v).map_err(|_| invalid()))
+                .transpose()?,
+        ),
+        "smallint" => ColumnData::I16(
+            integer(text)?
+                .map(|v| i16::try_from(v).map_err(|_| invalid()))
+                .transpose()?,
+        ),
+        "int" => ColumnData::I32(
+            integer(text)?
+                .map(|v| i32::try_from(v).map_err(|_| invalid()))
+                .transpose()?,
+        ),
+        "bigint" => ColumnData::I64(integer(text)?),
+        "real" => ColumnData::F32(float(text)?.map(|v| v as f32)),
+        "float" => ColumnData::F64(float(text)?),
+        "decimal" | "numeric" => ColumnData::Numeric(
+            text.map(|t| {
+                parse_decimal_scaled(t, column.scale)
+                    .map(|v| tiberius::numeric::Numeric::new_with_scale(v, column.scale))
+                    .ok_or_else(invalid)
+            })
+            .transpose()?,
+        ),
+        "char" | "varchar" | "nchar" | "nvarchar" => {
+            ColumnData::String(text.map(|t| Cow::Owned(t.to_string())))
+        }
+        "binary" | "varbinary" => ColumnData::Binary(
+            text.map(|t| decode_binary_text(t).map(Cow::Owned).ok_or_else(invalid))
+                .transpose()?,
+        ),
+        "uniqueidentifier" => ColumnData::Guid(
+            text.map(|t| tiberius::Uuid::parse_str(t).map_err(|_| invalid()))
+                .transpose()?,
+        ),
+        "date" => match text {
+            Some(t) => parse_naive_datetime(t)
+                .ok_or_else(invalid)?
+                .date()
+                .into_sql(),
+            None => ColumnData::Date(None),
+        },
+        "time" => match text {
+            Some(t) => parse_naive_time(t).ok_or_else(invalid)?.into_sql(),
+            None => ColumnData::Time(None),
+        },
+        "datetime2" => match text {
+            Some(t) => parse_naive_datetime(t).ok_or_else(invalid)?.into_sql(),
+            None => ColumnData::DateTime2(None),
+        },
+        "datetime" => ColumnData::DateTime(
+            text.map(|t| {
+                parse_naive_datetime(t)
+                    .map(legacy_mssql_datetime)
+                    .ok_or_else(invalid)
+            })
+            .transpose()?,
+        ),
+        "datetimeoffset" => match text {
+            Some(t) => parse_datetime_offset(t).ok_or_else(invalid)?.into_sql(),
+            None => ColumnData::DateTimeOffset(None),
+        },
+        other => {
+            return Err(format!(
+                "MSSQL column '{}' has type '{}', which bulk load does not encode",
+                column.name, other
+            ))
+        }
+    };
+    Ok(data)
+}
+
+/// Decimal text as an integer of `scale` fractional digits, rounding half away
+/// from zero.
+pub(super) fn parse_decimal_scaled(text: &str, scale: u8) -> Option<i128> {
+    let text = text.trim();
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    if (whole.is_empty() && fraction.is_empty())
+        || !whole.bytes().all(|b| b.is_ascii_digit())
+        || !fraction.bytes().all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let scale = scale as usize;
+    let mut value: i128 = 0;
+    let fraction_digits = fraction.chars().chain(std::iter::repeat('0')).take(scale);
+    for digit in whole.chars().chain(fraction_digits) {
+        value = value
+            .checked_mul(10)?
+            .checked_add(digit.to_digit(10)? as i128)?;
+    }
+    if fraction.as_bytes().get(scale).is_some_and(|d| *d >= b'5') {
+        value = value.checked_add(1)?;
+    }
+    Some(if negative { -value } else { value })
+}
+
+fn parse_naive_datetime(text: &str) -> Option<NaiveDateTime> {
+    if let Ok(with_offset) = DateTime::parse_from_rfc3339(text) {
+        return Some(with_offset.naive_utc());
+    }
+    [
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H:%M",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+    .or_else(|| {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d")
+            .ok()
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+    })
+}
+
+fn parse_naive_time(text: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(text, "%H:%M:%S%.f")
+        .or_else(|_| NaiveTime::parse_from_str(text, "%H:%M"))
+        .ok()
+        .or_else(|| parse_naive_datetime(text).map(|dt| dt.time()))
+}
+
+/// Text without an offset is taken as UTC.
+fn parse_datetime_offset(text: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(text)
+        .or_else(|_| DateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f %:z"))
+        .or_else(|_| DateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f%#z"))
+        .ok()
+        .or_else(|| parse_naive_datetime(text).map(|dt| dt.and_utc().fixed_offset()))
+}
+
+/// Legacy `datetime`: days since 1900-01-01 and 1/300 second ticks. Rounding
+/// the last 1/600 second of a day up carries into the next day.
+fn legacy_mssql_datetime(value: NaiveDateTime) -> tiberius::time::DateTime {
+    const TICKS_PER_DAY: u64 = 24 * 60 * 60 * 300;
+    let epoch = NaiveDate::from_ymd_opt(1900, 1, 1).unwrap_or_default();
+    let mut days = value.date().signed_duration_since(epoch).num_days() as i32;
+    let time = value.time();
+    let nanos = time.num_seconds_from_midnight() as u64 * 1_000_000_000 + time.nanosecond() as u64;
+    let mut ticks = (nanos * 300 + 500_000_000) / 1_000_000_000;
+    if ticks >= TICKS_PER_DAY {
+        days += 1;
+        ticks -= TICKS_PER_DAY;
+    }
+    tiberius::time::DateTime::new(days, ticks as u32)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,271 @@
+use super::*;
+use serde_json::json;
+
+fn mssql_column(name: &str, data_type: &str) -> MssqlTargetColumn {
+    MssqlTargetColumn {
+        name: name.to_string(),
+        data_type: data_type.to_string(),
+        scale: 0,
+        nullable: true,
+        identity: false,
+        generated: false,
+    }
+}
+
+#[test]
+fn decimal_text_is_scaled_and_rounded() {
+    assert_eq!(parse_decimal_scaled("12.345", 2), Some(1235));
+    assert_eq!(parse_decimal_scaled("-12.344", 2), Some(-1234));
+    assert_eq!(parse_decimal_scaled("7", 3), Some(7000));
+    assert_eq!(parse_decimal_scaled(".5", 0), Some(1));
+    assert_eq!(parse_decimal_scaled("1e3", 0), None);
+    assert_eq!(parse_decimal_scaled("", 2), None);
+}
+
+#[test]
+fn bulk_values_match_the_column_type() {
+    let mut amount = mssql_column("amount", "decimal");
+    amount.scale = 2;
+    match mssql_bulk_value(&amount, &json!("19.999")).unwrap() {
+        ColumnData::Numeric(Some(n)) => {
+            assert_eq!(n.value(), 2000);
+            assert_eq!(n.scale(), 2);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+
+    assert!(matches!(
+        mssql_bulk_value(&mssql_column("flag", "bit"), &json!(true)).unwrap(),
+        ColumnData::Bit(Some(true))
+    ));
+    assert!(matches!(
+        mssql_bulk_value(&mssql_column("n", "int"), &Value::Null).unwrap(),
+        ColumnData::I32(None)
+    ));
+    assert!(mssql_bulk_value(&mssql_column("n", "tinyint"), &json!(300)).is_err());
+
+    match mssql_bulk_value(&mssql_column("blob", "varbinary"), &json!("0x0aff")).unwrap() {
+        ColumnData::Binary(Some(bytes)) => assert_eq!(bytes.as_ref(), &[0x0a, 0xff]),
+        other => panic!("unexpected {:?}", other),
+    }
+
+    match mssql_bulk_value(
+        &mssql_column("at", "datetime"),
+        &json!("1900-01-02 00:00:01"),
+    )
+    .unwrap()
+    {
+        ColumnData::DateTime(Some(dt)) => {
+            assert_eq!(dt.days(), 1);
+            assert_eq!(dt.seconds_fragments(), 300);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+    // The last 1/600 second rounds into the next day
+    let midnight = legacy_mssql_datetime(
+        NaiveDate::from_ymd_opt(1900, 1, 1)
+            .unwrap()
+            .and_hms_milli_opt(23, 59, 59, 999)
+            .unwrap(),
+    );
+    assert_eq!(midnight.days(), 1);
+    assert_eq!(midnight.seconds_fragments(), 0);
+    assert!(matches!(
+        mssql_bulk_value(
+            &mssql_column("at", "datetime2"),
+            &json!("2024-05-01T10:00:00Z")
+        )
+        .unwrap(),
+        ColumnData::DateTime2(Some(_))
+    ));
+}
+
+#[test]
+fn bulk_plan_falls_back_to_inserts_when_needed() {
+    let mut id = mssql_column("id", "int");
+    id.identity = true;
+    id.nullable = false;
+    let mut version = mssql_column("row_version", "timestamp");
+    version.generated = true;
+    let targets = vec![
+        id,
+        mssql_column("name", "nvarchar"),
+        version,
+        mssql_column("note", "ntext"),
+    ];
+
+    let names = vec!["Name".to_string()];
+    let plan = mssql_bulk_plan(&targets[..3], &names).unwrap();
+    assert_eq!(plan.len(), 1);
+    assert_eq!(plan[0].0.name, "name");
+    assert_eq!(plan[0].1, Some(0));
+
+    // Explicit identity values need IDENTITY_INSERT
+    assert!(mssql_bulk_plan(&targets[..3], &["id".to_string(), "name".to_string()]).is_none());
+    // ntext has no bulk encoding here
+    assert!(mssql_bulk_plan(&targets, &names).is_none());
+    // Source columns missing from the target
+    assert!(mssql_bulk_plan(&targets[..3], &["name".to_string(), "extra".to_string()]).is_none());
+
+    let mut required = mssql_column("code", "varchar");
+    required.nullable = false;
+    assert!(mssql_bulk_plan(&[required], &[]).is_none());
+}
+
+#[test]
+fn mssql_table_refs_are_three_part() {
+    assert_eq!(mssql_table_ref("app", "orders"), "[app].[dbo].[orders]");
+    assert_eq!(
+        mssql_table_ref("app", "sales.orders"),
+        "[app].[sales].[orders]"
+    );
+    assert_eq!(mssql_table_ref("", "orders"), "[dbo].[orders]");
+}
+
+#[test]
+fn sql_targets_get_multi_row_inserts_and_merges() {
+    let columns = vec!["id".to_string(), "name".to_string()];
+    let rows = vec![
+        vec![json!(1), json!("O'Brien")],
+        vec![json!(2), Value::Null],
+    ];
+
+    assert_eq!(
+        build_multi_row_insert_statement(&DatabaseType::SQLite, "\"users\"", &columns, &rows, None),
+        "INSERT INTO \"users\" (\"id\", \"name\") VALUES (1, 'O''Brien'), (2, NULL)"
+    );
+    assert_eq!(
+        build_multi_row_insert_statement(
+            &DatabaseType::MSSQL,
+            "[app].[dbo].[users]",
+            &columns,
+            &rows[..1],
+            None
+        ),
+        "INSERT INTO [app].[dbo].[users] ([id], [name]) VALUES (1, N'O''Brien')"
+    );
+
+    let merge = build_insert_statement(
+        &DatabaseType::MSSQL,
+        "[app].[dbo].[users]",
+        &columns,
+        &rows[0],
+        "upsert",
+        &["id".to_string()],
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        merge,
+        "MERGE INTO [app].[dbo].[users] AS target USING (VALUES (1, N'O''Brien')) AS source ([id], [name]) \
+         ON target.[id] = source.[id] WHEN MATCHED THEN UPDATE SET target.[name] = source.[name] \
+         WHEN NOT MATCHED THEN INSERT ([id], [name]) VALUES (source.[id], source.[name]);"
+    );
+
+    let sqlite_upsert = build_insert_statement(
+        &DatabaseType::SQLite,
+        "\"users\"",
+        &columns,
+        &rows[0],
+        "upsert",
+        &["id".to_string()],
+        None,
+    )
+    .unwrap();
+    assert!(sqlite_upsert.contains("ON CONFLICT (\"id\") DO UPDATE SET"));
+}
+
+#[test]
+fn binary_text_decodes_from_hex_and_base64() {
+    assert_eq!(decode_binary_text("0xDEAD"), Some(vec![0xde, 0xad]));
+    assert_eq!(decode_binary_text("\\x0102"), Some(vec![1, 2]));
+    assert_eq!(decode_binary_text("AQI="), Some(vec![1, 2]));
+    assert_eq!(decode_binary_text("0xZZ"), None);
+}
+
+#[test]
+fn key_ranges_cover_the_bounds_without_overlap() {
+    let ranges = split_key_range(
+        KeyRange {
+            lower: 1,
+            upper: 10,
+        },
+        3,
+    );
+    assert_eq!(
+        ranges,
+        vec![
+            KeyRange { lower: 1, upper: 4 },
+            KeyRange { lower: 5, upper: 8 },
+            KeyRange {
+                lower: 9,
+                upper: 10
+            },
+        ]
+    );
+    // Never more partitions than keys
+    assert_eq!(split_key_range(KeyRange { lower: 7, upper: 8 }, 4).len(), 2);
+    let full = split_key_range(
+        KeyRange {
+            lower: i64::MIN,
+            upper: i64::MAX,
+        },
+        2,
+    );
+    assert_eq!(full[0].lower, i64::MIN);
+    assert_eq!(full[1].upper, i64::MAX);
+    assert_eq!(full[0].upper + 1, full[1].lower);
+}
+
+#[test]
+fn key_range_queries_page_within_their_partition() {
+    let range = KeyRange {
+        lower: 100,
+        upper: 199,
+    };
+    assert_eq!(
+        build_key_range_query(
+            &DatabaseType::MySQL,
+            "`app`.`orders`",
+            "id",
+            range,
+            None,
+            500
+        ),
+        "SELECT * FROM `app`.`orders` WHERE `id` >= 100 AND `id` <= 199 ORDER BY `id` LIMIT 500"
+    );
+    assert_eq!(
+        build_key_range_query(
+            &DatabaseType::MSSQL,
+            "[app].[dbo].[orders]",
+            "id",
+            range,
+            Some(150),
+            500
+        ),
+        "SELECT TOP (500) * FROM [app].[dbo].[orders] WHERE [id] > 150 AND [id] <= 199 ORDER BY [id]"
+    );
+}
+
+#[test]
+fn offset_pages_are_ordered_by_the_key() {
+    let key = vec!["tenant".to_string(), "id".to_string()];
+    assert_eq!(
+        build_offset_page_query(&DatabaseType::MSSQL, "[app].[dbo].[orders]", &key, 1000, 500),
+        "SELECT * FROM [app].[dbo].[orders] ORDER BY [tenant], [id] OFFSET 1000 ROWS FETCH NEXT 500 ROWS ONLY"
+    );
+    assert_eq!(
+        build_offset_page_query(
+            &DatabaseType::PostgreSQL,
+            "\"public\".\"orders\"",
+            &key,
+            0,
+            500
+        ),
+        "SELECT * FROM \"public\".\"orders\" ORDER BY \"tenant\", \"id\" LIMIT 500 OFFSET 0"
+    );
+    assert_eq!(
+        build_offset_page_query(&DatabaseType::SQLite, "\"orders\"", &[], 500, 500),
+        "SELECT * FROM \"orders\" ORDER BY rowid LIMIT 500 OFFSET 500"
+    );
+}
@@ -1,3 +1,4 @@
+use crate::data_transfer::checkpoint::{ResumePoint, StepCheckpointer};
 use crate::data_transfer::connection_resolver::ResolvedTransferConnection;
 use crate::data_transfer::planner::{DataTransferExecutionPlan, DataTransferPlanStep};
 use crate::data_transfer::sink::DataTransferSinkType;
@@ -7,6 +8,7 @@ use crate::mssql;
 use crate::db::sql_utils::{escape_sql_string, qualified_table_name, quote_column_name, value_to_sql_literal};
 use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use base64::Engine;
+use futures::{StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use sqlx::Row;
@@ -16,6 +18,12 @@ use std::path::Path;
 use tokio::fs::File;
 use tokio::io::{AsyncWriteExt, BufWriter};
 
+mod matrix;
+mod pacing;
+
+pub(crate) use matrix::mssql_table_ref;
+pub use pacing::TransferRuntime;
+
 const TRANSFER_BATCH_SIZE: usize = 1_000;
 
 #[derive(Debug, Clone)]
@@ -193,6 +201,13 @@ pub struct EngineStepResult {
     pub dry_run: bool,
 }
 
+/// Per-step execution state beyond the plan itself.
+#[derive(Debug, Clone, Copy)]
+pub struct StepContext<'a> {
+    pub checkpoint: Option<&'a StepCheckpointer>,
+    pub runtime: &'a TransferRuntime,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EngineExecutionResult {
@@ -210,8 +225,21 @@ pub async fn execute_step(
     target_database: &str,
     step: &DataTransferPlanStep,
     dry_run: bool,
+    context: StepContext<'_>,
 ) -> Result<EngineStepResult, String> {
     if step.sink_type != DataTransferSinkType::Database {
+        // Only the generic reader applies column mappings
+        if step.column_mapping.is_some() {
+            return matrix::execute_step_generic_to_file_sink(
+                source,
+                target,
+                source_database,
+                target_database,
+                step,
+                dry_run,
+            )
+            .await;
+        }
         return execute_step_file_sink(
             source,
             target,
@@ -223,15 +251,30 @@ pub async fn execute_step(
         .await;
     }
 
+    let checkpoint = context.checkpoint;
+    // Partitioning, adaptive chunks, throttling, target session settings and
+    // column mappings live in the generic matrix
+    let runtime = context.runtime;
+    let tuned = ((runtime.is_tuned() || !runtime.target_session().is_empty()) && !dry_run)
+        || step.column_mapping.is_some();
     match (&source.db_type, &target.db_type) {
-        (DatabaseType::MySQL, DatabaseType::MySQL) => {
-            execute_step_mysql(source, target, source_database, target_database, step, dry_run).await
-        }
-        (DatabaseType::PostgreSQL, DatabaseType::PostgreSQL) => {
-            execute_step_postgres(source, target, source_database, target_database, step, dry_run)
+        (DatabaseType::MySQL, DatabaseType::MySQL) if !tuned => {
+            execute_step_mysql(source, target, source_database, target_database, step, dry_run, checkpoint)
                 .await
         }
-        (DatabaseType::MySQL, DatabaseType::PostgreSQL) => {
+        (DatabaseType::PostgreSQL, DatabaseType::PostgreSQL) if !tuned => {
+            execute_step_postgres(
+                source,
+                target,
+                source_database,
+                target_database,
+                step,
+                dry_run,
+                checkpoint,
+            )
+            .await
+        }
+        (DatabaseType::MySQL, DatabaseType::PostgreSQL) if !tuned => {
             execute_step_mysql_to_postgres(
                 source,
                 target,
@@ -239,10 +282,11 @@ pub async fn execute_step(
                 target_database,
                 step,
                 dry_run,
+                checkpoint,
             )
             .await
         }
-        (DatabaseType::PostgreSQL, DatabaseType::MySQL) => {
+        (DatabaseType::PostgreSQL, DatabaseType::MySQL) if !tuned => {
             execute_step_postgres_to_mysql(
                 source,
                 target,
@@ -250,28 +294,28 @@ pub async fn execute_step(
                 target_database,
                 step,
                 dry_run,
+                checkpoint,
             )
             .await
         }
-        (DatabaseType::MSSQL, _) | (_, DatabaseType::MSSQL) => {
-            Err(format!(
-                "Data transfer involving MSSQL is not yet supported (source: {}, target: {})",
-                db_type_label(&source.db_type),
-                db_type_label(&target.db_type)
-            ))
-        }
-        (DatabaseType::ClickHouse, _) | (_, DatabaseType::ClickHouse) => {
-            Err(format!(
-                "Data transfer involving ClickHouse is not yet fully supported (source: {}, target: {})",
-                db_type_label(&source.db_type),
-                db_type_label(&target.db_type)
-            ))
-        }
-        _ => Err(format!(
+        (DatabaseType::Disconnected, _) | (_, DatabaseType::Disconnected) => Err(format!(
             "Disconnected database type is not valid for transfer (source: {}, target: {})",
             db_type_label(&source.db_type),
             db_type_label(&target.db_type)
         )),
+        // Every other pairing, and tuned runs, go through the generic reader/writer matrix
+        _ => {
+            matrix::execute_step_generic(
+                source,
+                target,
+                source_database,
+                target_database,
+                step,
+                dry_run,
+                context,
+            )
+            .await
+        }
     }
 }
 
@@ -282,29 +326,50 @@ pub async fn execute_plan(
     target_database: &str,
     plan: &DataTransferExecutionPlan,
     dry_run: bool,
+    runtime: &TransferRuntime,
 ) -> Result<EngineExecutionResult, String> {
     if plan.steps.is_empty() {
         return Err("Execution plan has no steps".to_string());
     }
 
-    let mut steps = Vec::with_capacity(plan.steps.len());
-    let mut total_source_rows = 0usize;
-    let mut total_written_rows = 0usize;
-
-    for step in &plan.steps {
-        let step_result = execute_step(
-            source,
-            target,
-            source_database,
-            target_database,
-            step,
-            dry_run,
-        )
-        .await?;
-        total_source_rows = total_source_rows.saturating_add(step_result.source_rows);
-        total_written_rows = total_written_rows.saturating_add(step_result.written_rows);
-        steps.push(step_result);
+    let context = StepContext {
+        checkpoint: None,
+        runtime,
+    };
+    let mut finished = Vec::with_capacity(plan.steps.len());
+    for wave in schedule_step_waves(&plan.steps) {
+        let results = futures::stream::iter(wave)
+            .map(|group| async move {
+                let mut results = Vec::with_capacity(group.len());
+                for index in group {
+                    let step_result = execute_step(
+                        source,
+                        target,
+                        source_database,
+                        target_database,
+                        &plan.steps[index],
+                        dry_run,
+                        context,
+                    )
+                    .await?;
+                    results.push((index, step_result));
+                }
+                Ok::<_, String>(results)
+            })
+            .buffer_unordered(runtime.table_parallelism())
+            .try_concat()
+            .await?;
+        finished.extend(results);
     }
+    finished.sort_by_key(|(index, _)| *index);
+
+    let steps = finished.into_iter().map(|(_, result)| result).collect::<Vec<_>>();
+    let total_source_rows = steps
+        .iter()
+        .fold(0usize, |total, step| total.saturating_add(step.source_rows));
+    let total_written_rows = steps
+        .iter()
+        .fold(0usize, |total, step| total.saturating_add(step.written_rows));
 
     Ok(EngineExecutionResult {
         processed_steps: steps.len(),
@@ -315,6 +380,65 @@ pub async fn execute_plan(
     })
 }
 
+/// Step indices in waves that run one after another. Within a wave, steps are
+/// grouped by the table or file they write; groups may run concurrently, while
+/// the steps inside one group keep plan order. A step lands in a later wave
+/// than every step it depends on; dependencies on steps outside `steps`
+/// (already completed ones) count as met.
+pub fn schedule_step_waves(steps: &[DataTransferPlanStep]) -> Vec<Vec<Vec<usize>>> {
+    let mut levels = vec![0usize; steps.len()];
+    for (index, step) in steps.iter().enumerate() {
+        let target = step_target_key(step);
+        let level = steps[..index]
+            .iter()
+            .enumerate()
+            .filter_map(|(earlier, other)| {
+                if step.depends_on.contains(&other.step_key) {
+                    Some(levels[earlier] + 1)
+                } else if step_target_key(other) == target {
+                    // Same target: stay in (or after) its wave to keep plan order
+                    Some(levels[earlier])
+                } else {
+                    None
+                }
+            })
+            .max()
+            .unwrap_or(0);
+        levels[index] = level;
+    }
+
+    let wave_count = levels.iter().max().map_or(0, |level| level + 1);
+    (0..wave_count)
+        .map(|wave| {
+            let indices = (0..steps.len())
+                .filter(|index| levels[*index] == wave)
+                .collect::<Vec<_>>();
+            group_indices_by_target(steps, &indices)
+        })
+        .collect()
+}
+
+fn group_indices_by_target(steps: &[DataTransferPlanStep], indices: &[usize]) -> Vec<Vec<usize>> {
+    let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+    for index in indices {
+        let key = step_target_key(&steps[*index]);
+        match groups.iter_mut().find(|(existing, _)| *existing == key) {
+            Some((_, members)) => members.push(*index),
+            None => groups.push((key, vec![*index])),
+        }
+    }
+    groups.into_iter().map(|(_, members)| members).collect()
+}
+
+fn step_target_key(step: &DataTransferPlanStep) -> String {
+    match (&step.sink_type, step.sink_path.as_deref()) {
+        (DataTransferSinkType::Database, _) | (_, None) => {
+            format!("table:{}", normalize_column_name(&step.target_table))
+        }
+        (_, Some(path)) => format!("file:{}", path.trim()),
+    }
+}
+
 async fn execute_step_file_sink(
     source: &ResolvedTransferConnection,
     target: &ResolvedTransferConnection,
@@ -369,7 +493,15 @@ async fn execute_step_file_sink(
             .await
         }
         DatabaseType::SQLite => {
-            Err("Data transfer from SQLite is not yet supported".to_string())
+            matrix::execute_step_generic_to_file_sink(
+                source,
+                target,
+                source_database,
+                target_database,
+                step,
+                dry_run,
+            )
+            .await
         }
         DatabaseType::Disconnected => {
             Err("Disconnected database type is not valid for transfer".to_string())
@@ -712,7 +844,9 @@ async fn resolve_sql_sink_target_hints(
             resolve_target_column_hints_clickhouse(&target.config, target_database, target_table).await
         }
         DatabaseType::SQLite => {
-            Err("Data transfer to SQLite is not yet supported".to_string())
+            let pool = crate::sqlite::create_pool(&target.config.host).await?;
+            let columns = crate::sqlite::get_table_schema(&pool, target_database, target_table).await?;
+            Ok(build_target_column_hint_map(&columns))
         }
         DatabaseType::Disconnected => {
             Err("Disconnected database type is not valid for SQL sink schema mapping".to_string())
@@ -727,6 +861,7 @@ async fn execute_step_mysql(
     target_database: &str,
     step: &DataTransferPlanStep,
     dry_run: bool,
+    checkpoint: Option<&StepCheckpointer>,
 ) -> Result<EngineStepResult, String> {
     let source_pool = crate::mysql::create_pool(&source.config).await?;
     let target_pool = crate::mysql::create_pool(&target.config).await?;
@@ -750,7 +885,7 @@ async fn execute_step_mysql(
     let target_column_hints =
         resolve_target_column_hints_mysql(&target_pool, target_database, &step.target_table).await?;
 
-    if mode == "replace" {
+    if mode == "replace" && !checkpoint.is_some_and(StepCheckpointer::has_resume_position) {
         crate::mysql::execute_query(&target_pool, format!("TRUNCATE TABLE {}", target_table_ref))
             .await?;
     }
@@ -789,6 +924,8 @@ async fn execute_step_mysql(
             &mode,
             &step.key_columns,
             &target_column_hints,
+            checkpoint.and_then(|c| c.resume_point(&cursor_columns)),
+            checkpoint,
         )
         .await
         {
@@ -825,6 +962,7 @@ async fn execute_step_postgres(
     target_database: &str,
     step: &DataTransferPlanStep,
     dry_run: bool,
+    checkpoint: Option<&StepCheckpointer>,
 ) -> Result<EngineStepResult, String> {
     let source_pool = crate::postgres::create_pool(&source.config).await?;
     let target_pool = crate::postgres::create_pool(&target.config).await?;
@@ -854,7 +992,7 @@ async fn execute_step_postgres(
     )
     .await?;
 
-    if mode == "replace" {
+    if mode == "replace" && !checkpoint.is_some_and(StepCheckpointer::has_resume_position) {
         crate::postgres::execute_query(&target_pool, format!("TRUNCATE TABLE {}", target_table_ref))
             .await?;
     }
@@ -894,6 +1032,8 @@ async fn execute_step_postgres(
             &mode,
             &step.key_columns,
             &target_column_hints,
+            checkpoint.and_then(|c| c.resume_point(&cursor_columns)),
+            checkpoint,
         )
         .await
         {
@@ -930,6 +1070,7 @@ async fn execute_step_mysql_to_postgres(
     target_database: &str,
     step: &DataTransferPlanStep,
     dry_run: bool,
+    checkpoint: Option<&StepCheckpointer>,
 ) -> Result<EngineStepResult, String> {
     let source_pool = crate::mysql::create_pool(&source.config).await?;
     let target_pool = crate::postgres::create_pool(&target.config).await?;
@@ -958,7 +1099,7 @@ async fn execute_step_mysql_to_postgres(
     )
     .await?;
 
-    if mode == "replace" {
+    if mode == "replace" && !checkpoint.is_some_and(StepCheckpointer::has_resume_position) {
         crate::postgres::execute_query(&target_pool, format!("TRUNCATE TABLE {}", target_table_ref))
             .await?;
     }
@@ -997,6 +1138,8 @@ async fn execute_step_mysql_to_postgres(
             &mode,
             &step.key_columns,
             &target_column_hints,
+            checkpoint.and_then(|c| c.resume_point(&cursor_columns)),
+            checkpoint,
         )
         .await
         {
@@ -1033,6 +1176,7 @@ async fn execute_step_postgres_to_mysql(
     target_database: &str,
     step: &DataTransferPlanStep,
     dry_run: bool,
+    checkpoint: Option<&StepCheckpointer>,
 ) -> Result<EngineStepResult, String> {
     let source_pool = crate::postgres::create_pool(&source.config).await?;
     let target_pool = crate::mysql::create_pool(&target.config).await?;
@@ -1057,7 +1201,7 @@ async fn execute_step_postgres_to_mysql(
     let target_column_hints =
         resolve_target_column_hints_mysql(&target_pool, target_database, &step.target_table).await?;
 
-    if mode == "replace" {
+    if mode == "replace" && !checkpoint.is_some_and(StepCheckpointer::has_resume_position) {
         crate::mysql::execute_query(&target_pool, format!("TRUNCATE TABLE {}", target_table_ref))
             .await?;
     }
@@ -1096,6 +1240,8 @@ async fn execute_step_postgres_to_mysql(
             &mode,
             &step.key_columns,
             &target_column_hints,
+            checkpoint.and_then(|c| c.resume_point(&cursor_columns)),
+            checkpoint,
         )
         .await
         {
@@ -2053,11 +2199,13 @@ async fn transfer_rows_mysql_keyset(
     mode: &str,
     key_columns: &[String],
     target_column_hints: &TargetColumnHintMap,
+    resume_from: Option<ResumePoint>,
+    checkpoint: Option<&StepCheckpointer>,
 ) -> Result<usize, String> {
-    let mut written_rows = 0usize;
+    let mut written_rows = resume_from.as_ref().map_or(0, |point| point.written_rows);
     let mut source_columns = Vec::<String>::new();
     let mut cursor_indices: Option<Vec<usize>> = None;
-    let mut cursor_last: Option<Vec<Value>> = None;
+    let mut cursor_last: Option<Vec<Value>> = resume_from.map(|point| point.cursor_values);
     let mut materialized_hints: Option<Vec<TargetColumnHint>> = None;
 
     loop {
@@ -2125,6 +2273,9 @@ async fn transfer_rows_mysql_keyset(
             }
         }
 
+        if let Some(checkpoint) = checkpoint {
+            checkpoint.record_cursor(cursor_columns, &next_cursor, written_rows).await;
+        }
         cursor_last = Some(next_cursor);
         if processed_in_batch < TRANSFER_BATCH_SIZE {
             break;
@@ -2143,11 +2294,13 @@ async fn transfer_rows_postgres_keyset(
     mode: &str,
     key_columns: &[String],
     target_column_hints: &TargetColumnHintMap,
+    resume_from: Option<ResumePoint>,
+    checkpoint: Option<&StepCheckpointer>,
 ) -> Result<usize, String> {
-    let mut written_rows = 0usize;
+    let mut written_rows = resume_from.as_ref().map_or(0, |point| point.written_rows);
     let mut source_columns = Vec::<String>::new();
     let mut cursor_indices: Option<Vec<usize>> = None;
-    let mut cursor_last: Option<Vec<Value>> = None;
+    let mut cursor_last: Option<Vec<Value>> = resume_from.map(|point| point.cursor_values);
     let mut materialized_hints: Option<Vec<TargetColumnHint>> = None;
 
     loop {
@@ -2215,6 +2368,9 @@ async fn transfer_rows_postgres_keyset(
             }
         }
 
+        if let Some(checkpoint) = checkpoint {
+            checkpoint.record_cursor(cursor_columns, &next_cursor, written_rows).await;
+        }
         cursor_last = Some(next_cursor);
         if processed_in_batch < TRANSFER_BATCH_SIZE {
             break;
@@ -2295,11 +2451,13 @@ async fn transfer_rows_mysql_to_postgres_keyset(
     mode: &str,
     key_columns: &[String],
     target_column_hints: &TargetColumnHintMap,
+    resume_from: Option<ResumePoint>,
+    checkpoint: Option<&StepCheckpointer>,
 ) -> Result<usize, String> {
-    let mut written_rows = 0usize;
+    let mut written_rows = resume_from.as_ref().map_or(0, |point| point.written_rows);
     let mut source_columns = Vec::<String>::new();
     let mut cursor_indices: Option<Vec<usize>> = None;
-    let mut cursor_last: Option<Vec<Value>> = None;
+    let mut cursor_last: Option<Vec<Value>> = resume_from.map(|point| point.cursor_values);
     let mut materialized_hints: Option<Vec<TargetColumnHint>> = None;
 
     loop {
@@ -2367,6 +2525,9 @@ async fn transfer_rows_mysql_to_postgres_keyset(
             }
         }
 
+        if let Some(checkpoint) = checkpoint {
+            checkpoint.record_cursor(cursor_columns, &next_cursor, written_rows).await;
+        }
         cursor_last = Some(next_cursor);
         if processed_in_batch < TRANSFER_BATCH_SIZE {
             break;
@@ -2447,11 +2608,13 @@ async fn transfer_rows_postgres_to_mysql_keyset(
     mode: &str,
     key_columns: &[String],
     target_column_hints: &TargetColumnHintMap,
+    resume_from: Option<ResumePoint>,
+    checkpoint: Option<&StepCheckpointer>,
 ) -> Result<usize, String> {
-    let mut written_rows = 0usize;
+    let mut written_rows = resume_from.as_ref().map_or(0, |point| point.written_rows);
     let mut source_columns = Vec::<String>::new();
     let mut cursor_indices: Option<Vec<usize>> = None;
-    let mut cursor_last: Option<Vec<Value>> = None;
+    let mut cursor_last: Option<Vec<Value>> = resume_from.map(|point| point.cursor_values);
     let mut materialized_hints: Option<Vec<TargetColumnHint>> = None;
 
     loop {
@@ -2519,6 +2682,9 @@ async fn transfer_rows_postgres_to_mysql_keyset(
             }
         }
 
+        if let Some(checkpoint) = checkpoint {
+            checkpoint.record_cursor(cursor_columns, &next_cursor, written_rows).await;
+        }
         cursor_last = Some(next_cursor);
         if processed_in_batch < TRANSFER_BATCH_SIZE {
             break;
@@ -2692,7 +2858,7 @@ fn build_insert_statement(
     key_columns: &[String],
     hints: Option<&[TargetColumnHint]>,
 ) -> Result<String, String> {
-    if mode == "update" && !key_columns.is_empty() {
+    if matches!(mode, "update" | "upsert") && !key_columns.is_empty() {
         return build_upsert_statement(db_type, table_ref, columns, row, key_columns, hints);
     }
 
@@ -2746,7 +2912,7 @@ fn build_upsert_statement(
                 table_ref, col_list, val_list, updates
             ))
         }
-        DatabaseType::PostgreSQL => {
+        DatabaseType::PostgreSQL | DatabaseType::SQLite => {
             let keys = key_columns
                 .iter()
                 .map(|c| quote_column_name(db_type, c))
@@ -2773,10 +2939,74 @@ fn build_upsert_statement(
                 ))
             }
         }
+        DatabaseType::MSSQL => {
+            let source_list = columns
+                .iter()
+                .map(|c| format!("source.{}", quote_column_name(db_type, c)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let matches = key_columns
+                .iter()
+                .map(|c| {
+                    let quoted = quote_column_name(db_type, c);
+                    format!("target.{} = source.{}", quoted, quoted)
+                })
+                .collect::<Vec<_>>()
+                .join(" AND ");
+            let updates = columns
+                .iter()
+                .filter(|c| !key_columns.contains(c))
+                .map(|c| {
+                    let quoted = quote_column_name(db_type, c);
+                    format!("target.{} = source.{}", quoted, quoted)
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            let when_matched = if updates.is_empty() {
+                String::new()
+            } else {
+                format!(" WHEN MATCHED THEN UPDATE SET {}", updates)
+            };
+            Ok(format!(
+                "MERGE INTO {} AS target USING (VALUES ({})) AS source ({}) ON {}{} WHEN NOT MATCHED THEN INSERT ({}) VALUES ({});",
+                table_ref, val_list, col_list, matches, when_matched, col_list, source_list
+            ))
+        }
         _ => build_insert_statement(db_type, table_ref, columns, row, "insert", &[], hints),
     }
 }
 
+/// One INSERT carrying every row of `rows`; callers keep batches within the
+/// target's limit (1000 rows for MSSQL).
+fn build_multi_row_insert_statement(
+    db_type: &DatabaseType,
+    table_ref: &str,
+    columns: &[String],
+    rows: &[Vec<Value>],
+    hints: Option<&[TargetColumnHint]>,
+) -> String {
+    let col_list = columns
+        .iter()
+        .map(|c| quote_column_name(db_type, c))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let values = rows
+        .iter()
+        .map(|row| {
+            let val_list = row
+                .iter()
+                .enumerate()
+                .map(|(idx, val)| format_value_for_sink(db_type, val, hints.and_then(|h| h.get(idx))))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("({})", val_list)
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!("INSERT INTO {} ({}) VALUES {}", table_ref, col_list, values)
+}
+
 fn format_value_for_sink(db_type: &DatabaseType, value: &Value, hint: Option<&TargetColumnHint>) -> String {
     if value.is_null() {
         return "NULL".to_string();
@@ -2785,10 +3015,8 @@ fn format_value_for_sink(db_type: &DatabaseType, value: &Value, hint: Option<&Ta
     if let Some(h) = hint {
         match h.kind {
             TargetValueKind::Binary => {
-                if let Some(s) = value.as_str() {
-                    if let Ok(bytes) = BASE64_STANDARD.decode(s) {
-                        return format_binary_literal(db_type, &bytes);
-                    }
+                if let Some(bytes) = value.as_str().and_then(decode_binary_text) {
+                    return format_binary_literal(db_type, &bytes);
                 }
             }
             TargetValueKind::Boolean => {
@@ -2801,7 +3029,7 @@ fn format_value_for_sink(db_type: &DatabaseType, value: &Value, hint: Option<&Ta
     }
 
     match value {
-        Value::String(s) => format!("'{}'", escape_sql_string(s)),
+        Value::String(s) => format_string_literal(db_type, s),
         Value::Bool(b) => format_boolean_literal(db_type, *b),
         Value::Number(n) => n.to_string(),
         Value::Object(_) | Value::Array(_) => {
@@ -2814,13 +3042,35 @@ fn format_value_for_sink(db_type: &DatabaseType, value: &Value, hint: Option<&Ta
                         format!("'{}'::json", escape_sql_string(&json_str))
                     }
                 }
-                _ => format!("'{}'", escape_sql_string(&json_str)),
+                _ => format_string_literal(db_type, &json_str),
             }
         }
         Value::Null => "NULL".to_string(),
     }
 }
 
+/// MSSQL and SQLite take backslashes literally; only quotes are doubled there.
+fn format_string_literal(db_type: &DatabaseType, value: &str) -> String {
+    match db_type {
+        DatabaseType::MSSQL => format!("N'{}'", value.replace('\'', "''")),
+        DatabaseType::SQLite => format!("'{}'", value.replace('\'', "''")),
+        _ => format!("'{}'", escape_sql_string(value)),
+    }
+}
+
+/// Binary cells arrive base64 encoded, or as `0x`-prefixed hex from MSSQL sources.
+fn decode_binary_text(text: &str) -> Option<Vec<u8>> {
+    let trimmed = text.trim();
+    match trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+        .or_else(|| trimmed.strip_prefix("\\x"))
+    {
+        Some(hex_digits) => hex::decode(hex_digits).ok(),
+        None => BASE64_STANDARD.decode(trimmed).ok(),
+    }
+}
+
 fn format_boolean_literal(db_type: &DatabaseType, value: bool) -> String {
     match db_type {
         DatabaseType::PostgreSQL | DatabaseType::ClickHouse => if value { "TRUE" } else { "FALSE" }.to_string(),
@@ -2907,3 +3157,6 @@ fn db_type_label(db_type: &DatabaseType) -> &'static str {
         DatabaseType::Disconnected => "disconnected",
     }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,172 @@
+// =====================================================
+// TRANSFER PACING
+// Chunk sizing and the run-wide source read throttle
+// =====================================================
+
+use crate::data_transfer::models::DataTransferPerformanceOptions;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const MIN_BATCH_SIZE: usize = 100;
+const MAX_BATCH_SIZE: usize = 50_000;
+/// Adaptive sizing aims for chunks that take about this long end to end
+const TARGET_CHUNK_DURATION: Duration = Duration::from_secs(2);
+
+/// Tuning shared by every worker of one transfer run.
+#[derive(Debug, Clone, Default)]
+pub struct TransferRuntime {
+    options: DataTransferPerformanceOptions,
+    limiter: Option<Arc<RowRateLimiter>>,
+    target_session: Vec<(String, String)>,
+}
+
+impl TransferRuntime {
+    pub fn new(options: DataTransferPerformanceOptions) -> Self {
+        let limiter = options
+            .max_rows_per_second
+            .filter(|rate| *rate > 0)
+            .map(|rate| Arc::new(RowRateLimiter::new(rate)));
+        Self {
+            options,
+            limiter,
+            target_session: Vec::new(),
+        }
+    }
+
+    /// Session settings every target connection of the run opens with.
+    pub fn with_target_session(mut self, settings: Vec<(String, String)>) -> Self {
+        self.target_session = settings;
+        self
+    }
+
+    pub fn target_session(&self) -> &[(String, String)] {
+        &self.target_session
+    }
+
+    pub fn is_tuned(&self) -> bool {
+        self.options.is_tuned()
+    }
+
+    pub fn table_parallelism(&self) -> usize {
+        self.options.table_parallelism()
+    }
+
+    /// Partitions for a table of `source_rows`, keeping each above the minimum.
+    pub fn partitions_for(&self, source_rows: usize) -> usize {
+        let by_size = source_rows / self.options.min_rows_per_partition();
+        self.options.partitions().min(by_size).max(1)
+    }
+
+    pub fn batch_sizer(&self) -> AdaptiveBatchSize {
+        AdaptiveBatchSize::new(
+            self.options.initial_batch_size(),
+            self.options.adaptive_batch_size,
+        )
+    }
+
+    /// Waits until `rows` more source rows may be read.
+    pub async fn throttle(&self, rows: usize) {
+        if let Some(limiter) = self.limiter.as_ref() {
+            limiter.acquire(rows).await;
+        }
+    }
+}
+
+// =====================================================
+// ADAPTIVE BATCH SIZE
+// =====================================================
+
+#[derive(Debug, Clone)]
+pub struct AdaptiveBatchSize {
+    current: usize,
+    adaptive: bool,
+}
+
+impl AdaptiveBatchSize {
+    pub fn new(initial: usize, adaptive: bool) -> Self {
+        Self {
+            current: initial.clamp(MIN_BATCH_SIZE, MAX_BATCH_SIZE),
+            adaptive,
+        }
+    }
+
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    /// Feeds back one full chunk; the next size moves toward the row count the
+    /// observed throughput would move in `TARGET_CHUNK_DURATION`, at most
+    /// doubling or halving per step.
+    pub fn observe(&mut self, rows: usize, elapsed: Duration) {
+        if !self.adaptive || rows < self.current {
+            return;
+        }
+
+        let elapsed = elapsed.as_secs_f64().max(0.001);
+        let ideal = rows as f64 * TARGET_CHUNK_DURATION.as_secs_f64() / elapsed;
+        let bounded = ideal.clamp(self.current as f64 / 2.0, self.current as f64 * 2.0);
+        self.current = (bounded as usize).clamp(MIN_BATCH_SIZE, MAX_BATCH_SIZE);
+    }
+}
+
+// =====================================================
+// ROW RATE LIMITER
+// =====================================================
+
+/// Token bucket over source rows holding at most one second of burst. Callers
+/// may overdraw it; the debt becomes their wait, and later callers queue
+/// behind it.
+#[derive(Debug)]
+pub struct RowRateLimiter {
+    rows_per_second: f64,
+    state: Mutex<LimiterState>,
+}
+
+#[derive(Debug)]
+struct LimiterState {
+    available: f64,
+    refilled_at: Instant,
+}
+
+impl RowRateLimiter {
+    pub fn new(rows_per_second: u64) -> Self {
+        Self {
+            rows_per_second: rows_per_second as f64,
+            state: Mutex::new(LimiterState {
+                available: rows_per_second as f64,
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+
+    pub async fn acquire(&self, rows: usize) {
+        let wait = self.reserve(rows, Instant::now());
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Takes `rows` tokens at `now` and returns how long the caller must wait.
+    fn reserve(&self, rows: usize, now: Instant) -> Duration {
+        let mut state = match self.state.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let refill = now
+            .saturating_duration_since(state.refilled_at)
+            .as_secs_f64()
+            * self.rows_per_second;
+        state.available = (state.available + refill).min(self.rows_per_second);
+        state.refilled_at = now.max(state.refilled_at);
+        state.available -= rows as f64;
+
+        if state.available >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-state.available / self.rows_per_second)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
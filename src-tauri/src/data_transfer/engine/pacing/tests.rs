@@ -0,0 +1,61 @@
+use super::*;
+
+#[test]
+fn adaptive_batches_follow_throughput_within_bounds() {
+    let mut batch = AdaptiveBatchSize::new(1_000, true);
+
+    // Fast chunk: grows, but no more than double
+    batch.observe(1_000, Duration::from_millis(100));
+    assert_eq!(batch.current(), 2_000);
+
+    // Slow chunk: 2000 rows in 8s suggests 500, capped at half
+    batch.observe(2_000, Duration::from_secs(8));
+    assert_eq!(batch.current(), 1_000);
+
+    // On target: unchanged
+    batch.observe(1_000, Duration::from_secs(2));
+    assert_eq!(batch.current(), 1_000);
+
+    // A short final chunk says nothing about throughput
+    batch.observe(10, Duration::from_secs(30));
+    assert_eq!(batch.current(), 1_000);
+
+    let mut fixed = AdaptiveBatchSize::new(1_000, false);
+    fixed.observe(1_000, Duration::from_millis(1));
+    assert_eq!(fixed.current(), 1_000);
+    assert_eq!(AdaptiveBatchSize::new(5, true).current(), MIN_BATCH_SIZE);
+}
+
+#[test]
+fn rate_limiter_turns_overdraw_into_wait() {
+    let limiter = RowRateLimiter::new(1_000);
+    let start = limiter.state.lock().unwrap().refilled_at;
+
+    assert_eq!(limiter.reserve(1_000, start), Duration::ZERO);
+    assert_eq!(limiter.reserve(500, start), Duration::from_millis(500));
+    // The next caller queues behind the existing debt
+    assert_eq!(limiter.reserve(500, start), Duration::from_secs(1));
+
+    // Two seconds later the debt is repaid and only one second of burst is kept
+    let later = start + Duration::from_secs(3);
+    assert_eq!(limiter.reserve(1_000, later), Duration::ZERO);
+    assert_eq!(limiter.reserve(250, later), Duration::from_millis(250));
+}
+
+#[test]
+fn runtime_partitions_respect_the_minimum_size() {
+    let runtime = TransferRuntime::new(DataTransferPerformanceOptions {
+        partitions_per_table: Some(8),
+        min_rows_per_partition: Some(50_000),
+        ..Default::default()
+    });
+    assert!(runtime.is_tuned());
+    assert_eq!(runtime.partitions_for(1_000_000), 8);
+    assert_eq!(runtime.partitions_for(120_000), 2);
+    assert_eq!(runtime.partitions_for(10_000), 1);
+
+    let plain = TransferRuntime::default();
+    assert!(!plain.is_tuned());
+    assert_eq!(plain.table_parallelism(), 1);
+    assert_eq!(plain.batch_sizer().current(), 1_000);
+}
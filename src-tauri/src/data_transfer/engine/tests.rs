@@ -0,0 +1,42 @@
+use super::*;
+
+fn step(key: &str, table: &str, depends_on: &[&str]) -> DataTransferPlanStep {
+    DataTransferPlanStep {
+        step_key: key.to_string(),
+        source_table: table.to_string(),
+        target_table: table.to_string(),
+        mode: "append".to_string(),
+        key_columns: Vec::new(),
+        sink_type: DataTransferSinkType::Database,
+        sink_path: None,
+        depends_on: depends_on.iter().map(|key| key.to_string()).collect(),
+        column_mapping: None,
+    }
+}
+
+#[test]
+fn waves_follow_dependencies_and_keep_same_target_order() {
+    let steps = vec![
+        step("step_4", "customers", &[]),
+        step("step_2", "audit_log", &[]),
+        step("step_3", "orders", &["step_4"]),
+        step("step_1", "order_items", &["step_3"]),
+        step("step_5", "audit_log", &["step_3"]),
+        step("step_6", "customers", &[]),
+    ];
+
+    assert_eq!(
+        schedule_step_waves(&steps),
+        vec![
+            vec![vec![0, 5], vec![1]],
+            vec![vec![2]],
+            vec![vec![3], vec![4]],
+        ]
+    );
+
+    // A resumed run no longer holds the finished parent
+    assert_eq!(
+        schedule_step_waves(&steps[2..4]),
+        vec![vec![vec![0]], vec![vec![1]]]
+    );
+}
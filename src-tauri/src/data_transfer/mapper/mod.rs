@@ -1,24 +1,727 @@
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt::Write;
+use std::sync::OnceLock;
 
+/// One target column and where its value comes from: a source column, a
+/// constant, or an expression over source columns.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ColumnMappingRule {
+    #[serde(default)]
     pub source_column: String,
     pub target_column: String,
     pub cast_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub constant: Option<Value>,
+    /// e.g. `concat(first_name, ' ', last_name)`, `lower(trim(email))`,
+    /// `date_format(created_at, '%Y-%m')`, `coalesce(region, 'EU')`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expression: Option<String>,
+}
+
+/// The mapping of one table. Source columns that no rule renames pass through
+/// by name unless `drop_unmapped` is set.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TableColumnMapping {
+    pub source_table: String,
+    #[serde(default)]
+    pub rules: Vec<ColumnMappingRule>,
+    #[serde(default)]
+    pub drop_unmapped: bool,
 }
 
 pub fn validate_mapping_rules(rules: &[ColumnMappingRule]) -> Result<(), String> {
     for (index, rule) in rules.iter().enumerate() {
-        if rule.source_column.trim().is_empty() {
-            return Err(format!("Mapping rule {} has an empty sourceColumn", index + 1));
+        let has_source = !rule.source_column.trim().is_empty();
+        let sources = [
+            has_source,
+            rule.constant.is_some(),
+            rule.expression.is_some(),
+        ]
+        .iter()
+        .filter(|set| **set)
+        .count();
+        if sources == 0 {
+            return Err(format!(
+                "Mapping rule {} has an empty sourceColumn and no constant or expression",
+                index + 1
+            ));
+        }
+        if sources > 1 {
+            return Err(format!(
+                "Mapping rule {} sets more than one of sourceColumn, constant and expression",
+                index + 1
+            ));
         }
         if rule.target_column.trim().is_empty() {
-            return Err(format!("Mapping rule {} has an empty targetColumn", index + 1));
+            return Err(format!(
+                "Mapping rule {} has an empty targetColumn",
+                index + 1
+            ));
+        }
+        if let Some(cast_type) = rule.cast_type.as_deref() {
+            CastType::parse(cast_type).map_err(|e| format!("Mapping rule {}: {}", index + 1, e))?;
+        }
+        if let Some(expression) = rule.expression.as_deref() {
+            Expr::parse(expression).map_err(|e| format!("Mapping rule {}: {}", index + 1, e))?;
+        }
+        let duplicate = rules[..index].iter().any(|earlier| {
+            earlier
+                .target_column
+                .trim()
+                .eq_ignore_ascii_case(rule.target_column.trim())
+        });
+        if duplicate {
+            return Err(format!(
+                "Mapping rule {} maps to '{}' more than once",
+                index + 1,
+                rule.target_column.trim()
+            ));
         }
     }
     Ok(())
 }
 
+impl TableColumnMapping {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.rules.is_empty() && self.drop_unmapped {
+            return Err(format!(
+                "Mapping for '{}' drops unmapped columns but maps none",
+                self.source_table
+            ));
+        }
+        validate_mapping_rules(&self.rules).map_err(|e| format!("{}: {}", self.source_table, e))
+    }
+
+    /// Target name of source column `column` when it is copied as is: through a
+    /// plain rule, or by passing through unmapped.
+    pub fn target_column_for(&self, column: &str) -> Option<String> {
+        let column = column.trim();
+        let renamed = self.rules.iter().find(|rule| {
+            rule.constant.is_none()
+                && rule.expression.is_none()
+                && rule.source_column.trim().eq_ignore_ascii_case(column)
+        });
+        if let Some(rule) = renamed {
+            return Some(rule.target_column.trim().to_string());
+        }
+        let claimed = self.rules.iter().any(|rule| {
+            rule.source_column.trim().eq_ignore_ascii_case(column)
+                || rule.target_column.trim().eq_ignore_ascii_case(column)
+        });
+        (!self.drop_unmapped && !claimed).then(|| column.to_string())
+    }
+
+    /// Rewrites one chunk read from the source into the target's columns.
+    pub fn apply(
+        &self,
+        columns: &[String],
+        rows: &[Vec<Value>],
+    ) -> Result<(Vec<String>, Vec<Vec<Value>>), String> {
+        CompiledMapping::compile(self, columns)?.apply(rows)
+    }
+}
+
+/// The mapping of one step, compiled on its first chunk and reused while the
+/// source column list stays the same.
+#[derive(Debug)]
+pub struct StepMapping {
+    mapping: TableColumnMapping,
+    compiled: OnceLock<CompiledMapping>,
+}
+
+impl StepMapping {
+    pub fn new(mapping: TableColumnMapping) -> Self {
+        Self {
+            mapping,
+            compiled: OnceLock::new(),
+        }
+    }
+
+    /// Rewrites one chunk read from the source into the target's columns.
+    pub fn apply(
+        &self,
+        columns: &[String],
+        rows: &[Vec<Value>],
+    ) -> Result<(Vec<String>, Vec<Vec<Value>>), String> {
+        let compiled = match self.compiled.get() {
+            Some(compiled) => compiled,
+            None => {
+                let compiled = CompiledMapping::compile(&self.mapping, columns)?;
+                self.compiled.get_or_init(|| compiled)
+            }
+        };
+        if compiled.source_columns == columns {
+            compiled.apply(rows)
+        } else {
+            self.mapping.apply(columns, rows)
+        }
+    }
+}
+
+// =====================================================
+// COMPILED MAPPING
+// =====================================================
+
+#[derive(Debug)]
+enum ValueSource {
+    Column(usize),
+    Constant(Value),
+    Expression(Expr),
+}
+
+#[derive(Debug)]
+struct OutputColumn {
+    source: ValueSource,
+    cast: Option<CastType>,
+}
+
+/// A mapping resolved against the column list of one result set.
+#[derive(Debug)]
+struct CompiledMapping {
+    source_columns: Vec<String>,
+    target_columns: Vec<String>,
+    outputs: Vec<OutputColumn>,
+}
+
+impl CompiledMapping {
+    fn compile(mapping: &TableColumnMapping, columns: &[String]) -> Result<Self, String> {
+        let find = |name: &str| {
+            columns
+                .iter()
+                .position(|column| column.eq_ignore_ascii_case(name.trim()))
+                .ok_or_else(|| {
+                    format!(
+                        "Mapping for '{}' reads source column '{}', which does not exist",
+                        mapping.source_table,
+                        name.trim()
+                    )
+                })
+        };
+
+        let mut target_columns = Vec::new();
+        let mut outputs = Vec::new();
+        if !mapping.drop_unmapped {
+            for (index, column) in columns.iter().enumerate() {
+                let claimed = mapping.rules.iter().any(|rule| {
+                    rule.source_column.trim().eq_ignore_ascii_case(column)
+                        || rule.target_column.trim().eq_ignore_ascii_case(column)
+                });
+                if !claimed {
+                    target_columns.push(column.clone());
+                    outputs.push(OutputColumn {
+                        source: ValueSource::Column(index),
+                        cast: None,
+                    });
+                }
+            }
+        }
+
+        for rule in &mapping.rules {
+            let source = if let Some(constant) = rule.constant.as_ref() {
+                ValueSource::Constant(constant.clone())
+            } else if let Some(expression) = rule.expression.as_deref() {
+                let expr = Expr::parse(expression)?;
+                for column in expr.columns() {
+                    find(column)?;
+                }
+                ValueSource::Expression(expr.resolve(columns))
+            } else {
+                ValueSource::Column(find(&rule.source_column)?)
+            };
+            let cast = rule.cast_type.as_deref().map(CastType::parse).transpose()?;
+            target_columns.push(rule.target_column.trim().to_string());
+            outputs.push(OutputColumn { source, cast });
+        }
+
+        Ok(Self {
+            source_columns: columns.to_vec(),
+            target_columns,
+            outputs,
+        })
+    }
+
+    fn apply(&self, rows: &[Vec<Value>]) -> Result<(Vec<String>, Vec<Vec<Value>>), String> {
+        let rows = rows
+            .iter()
+            .map(|row| self.apply_row(row))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((self.target_columns.clone(), rows))
+    }
+
+    fn apply_row(&self, row: &[Value]) -> Result<Vec<Value>, String> {
+        self.outputs
+            .iter()
+            .zip(&self.target_columns)
+            .map(|(output, target)| {
+                let value = match &output.source {
+                    ValueSource::Column(index) => row.get(*index).cloned().unwrap_or(Value::Null),
+                    ValueSource::Constant(value) => value.clone(),
+                    ValueSource::Expression(expr) => expr.evaluate(row)?,
+                };
+                match output.cast {
+                    Some(cast) => cast
+                        .apply(value)
+                        .map_err(|e| format!("Column '{}': {}", target, e)),
+                    None => Ok(value),
+                }
+            })
+            .collect()
+    }
+}
+
+// =====================================================
+// CASTS
+// =====================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CastType {
+    String,
+    Integer,
+    Float,
+    /// Exact decimal text, so precision beyond f64 survives the copy
+    Decimal,
+    Boolean,
+    Date,
+    DateTime,
+    Json,
+}
+
+impl CastType {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "string" | "text" | "varchar" => Ok(Self::String),
+            "integer" | "int" | "bigint" => Ok(Self::Integer),
+            "float" | "double" => Ok(Self::Float),
+            "decimal" | "numeric" | "number" => Ok(Self::Decimal),
+            "boolean" | "bool" => Ok(Self::Boolean),
+            "date" => Ok(Self::Date),
+            "datetime" | "timestamp" => Ok(Self::DateTime),
+            "json" => Ok(Self::Json),
+            other => Err(format!("Unsupported castType '{}'", other)),
+        }
+    }
+
+    /// Converts `value`; NULL stays NULL.
+    pub fn apply(self, value: Value) -> Result<Value, String> {
+        if value.is_null() {
+            return Ok(value);
+        }
+        let failed = |value: &Value, target: &str| format!("cannot cast {} to {}", value, target);
+
+        match self {
+            Self::String => Ok(Value::String(value_to_text(&value))),
+            Self::Integer => {
+                let parsed = match &value {
+                    Value::Number(number) => number.as_i64().or_else(|| {
+                        number
+                            .as_f64()
+                            .filter(|f| f.fract() == 0.0)
+                            .map(|f| f as i64)
+                    }),
+                    Value::Bool(flag) => Some(i64::from(*flag)),
+                    Value::String(text) => text.trim().parse::<i64>().ok(),
+                    _ => None,
+                };
+                parsed
+                    .map(Value::from)
+                    .ok_or_else(|| failed(&value, "integer"))
+            }
+            Self::Float => {
+                let parsed = match &value {
+                    Value::Number(number) => number.as_f64(),
+                    Value::String(text) => text.trim().parse::<f64>().ok(),
+                    _ => None,
+                };
+                parsed
+                    .and_then(serde_json::Number::from_f64)
+                    .map(Value::Number)
+                    .ok_or_else(|| failed(&value, "float"))
+            }
+            Self::Decimal => {
+                let text = match &value {
+                    Value::Number(number) => number
+                        .as_i64()
+                        .map(|n| n.to_string())
+                        .or_else(|| number.as_u64().map(|n| n.to_string()))
+                        // f64 Display never uses an exponent
+                        .or_else(|| number.as_f64().map(|f| f.to_string())),
+                    Value::String(text) => Some(text.trim().to_string()),
+                    _ => None,
+                };
+                text.and_then(|text| normalize_decimal(&text))
+                    .map(Value::String)
+                    .ok_or_else(|| failed(&value, "decimal"))
+            }
+            Self::Boolean => {
+                let parsed = match &value {
+                    Value::Bool(flag) => Some(*flag),
+                    Value::Number(number) => number.as_f64().map(|n| n != 0.0),
+                    Value::String(text) => match text.trim().to_ascii_lowercase().as_str() {
+                        "true" | "t" | "yes" | "y" | "1" => Some(true),
+                        "false" | "f" | "no" | "n" | "0" => Some(false),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                parsed
+                    .map(Value::Bool)
+                    .ok_or_else(|| failed(&value, "boolean"))
+            }
+            Self::Date => parse_datetime(&value_to_text(&value))
+                .map(|parsed| Value::String(parsed.format("%Y-%m-%d").to_string()))
+                .ok_or_else(|| failed(&value, "date")),
+            Self::DateTime => parse_datetime(&value_to_text(&value))
+                .map(|parsed| Value::String(parsed.format("%Y-%m-%d %H:%M:%S%.f").to_string()))
+                .ok_or_else(|| failed(&value, "datetime")),
+            Self::Json => match &value {
+                Value::String(text) => {
+                    serde_json::from_str(text).map_err(|_| failed(&value, "json"))
+                }
+                _ => Ok(value),
+            },
+        }
+    }
+}
+
+/// `[+-]digits[.digits]` without a leading `+`, or None when `text` is not one.
+fn normalize_decimal(text: &str) -> Option<String> {
+    let unsigned = text.strip_prefix(['+', '-']).unwrap_or(text);
+    let (whole, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+    let digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if whole.is_empty() && fraction.is_empty() || !digits(whole) || !digits(fraction) {
+        return None;
+    }
+    Some(text.strip_prefix('+').unwrap_or(text).to_string())
+}
+
+fn value_to_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+fn parse_datetime(text: &str) -> Option<NaiveDateTime> {
+    let text = text.trim();
+    if let Ok(parsed) = DateTime::parse_from_rfc3339(text) {
+        return Some(parsed.naive_utc());
+    }
+    ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(text, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+}
+
+// =====================================================
+// EXPRESSIONS
+// =====================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Function {
+    Concat,
+    Trim,
+    Lower,
+    Upper,
+    DateFormat,
+    Coalesce,
+}
+
+impl Function {
+    fn parse(name: &str) -> Result<Self, String> {
+        match name.to_ascii_lowercase().as_str() {
+            "concat" => Ok(Self::Concat),
+            "trim" => Ok(Self::Trim),
+            "lower" => Ok(Self::Lower),
+            "upper" => Ok(Self::Upper),
+            "date_format" => Ok(Self::DateFormat),
+            "coalesce" => Ok(Self::Coalesce),
+            other => Err(format!("Unknown mapping function '{}'", other)),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Concat => "concat",
+            Self::Trim => "trim",
+            Self::Lower => "lower",
+            Self::Upper => "upper",
+            Self::DateFormat => "date_format",
+            Self::Coalesce => "coalesce",
+        }
+    }
+
+    fn check_arity(self, count: usize) -> Result<(), String> {
+        let valid = match self {
+            Self::Concat | Self::Coalesce => count >= 1,
+            Self::Trim | Self::Lower | Self::Upper => count == 1,
+            Self::DateFormat => count == 2,
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("Wrong number of arguments to {}()", self.name()))
+        }
+    }
+}
+
+/// A parsed mapping expression. Column references are resolved to indices
+/// before rows are evaluated.
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Column(String),
+    Index(usize),
+    Literal(Value),
+    Call(Function, Vec<Expr>),
+}
+
+impl Expr {
+    fn parse(input: &str) -> Result<Self, String> {
+        let tokens = tokenize(input)?;
+        let mut position = 0;
+        let expr = parse_expr(&tokens, &mut position)?;
+        if position != tokens.len() {
+            return Err(format!(
+                "Unexpected trailing input in expression '{}'",
+                input
+            ));
+        }
+        expr.check_formats()?;
+        Ok(expr)
+    }
+
+    /// date_format() takes a literal chrono format, checked here so a bad
+    /// specifier fails validation rather than a row.
+    fn check_formats(&self) -> Result<(), String> {
+        let Self::Call(function, args) = self else {
+            return Ok(());
+        };
+        if *function == Function::DateFormat {
+            let Some(Self::Literal(Value::String(format))) = args.get(1) else {
+                return Err("date_format() needs a quoted format string".to_string());
+            };
+            if StrftimeItems::new(format).any(|item| item == Item::Error) {
+                return Err(format!("date_format() format '{}' is not valid", format));
+            }
+        }
+        args.iter().try_for_each(Expr::check_formats)
+    }
+
+    fn columns(&self) -> Vec<&str> {
+        match self {
+            Self::Column(name) => vec![name.as_str()],
+            Self::Call(_, args) => args.iter().flat_map(Expr::columns).collect(),
+            Self::Index(_) | Self::Literal(_) => Vec::new(),
+        }
+    }
+
+    fn resolve(self, columns: &[String]) -> Self {
+        match self {
+            Self::Column(name) => columns
+                .iter()
+                .position(|column| column.eq_ignore_ascii_case(&name))
+                .map(Self::Index)
+                .unwrap_or(Self::Literal(Value::Null)),
+            Self::Call(function, args) => Self::Call(
+                function,
+                args.into_iter().map(|arg| arg.resolve(columns)).collect(),
+            ),
+            other => other,
+        }
+    }
+
+    fn evaluate(&self, row: &[Value]) -> Result<Value, String> {
+        let (function, args) = match self {
+            Self::Index(index) => return Ok(row.get(*index).cloned().unwrap_or(Value::Null)),
+            Self::Column(_) => return Ok(Value::Null),
+            Self::Literal(value) => return Ok(value.clone()),
+            Self::Call(function, args) => (function, args),
+        };
+        let values = args
+            .iter()
+            .map(|arg| arg.evaluate(row))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let text_of = |value: &Value, map: fn(&str) -> String| {
+            if value.is_null() {
+                Value::Null
+            } else {
+                Value::String(map(&value_to_text(value)))
+            }
+        };
+        match function {
+            // NULL arguments are skipped, as in PostgreSQL's concat()
+            Function::Concat => Ok(Value::String(
+                values
+                    .iter()
+                    .filter(|value| !value.is_null())
+                    .map(value_to_text)
+                    .collect(),
+            )),
+            Function::Trim => Ok(text_of(&values[0], |text| text.trim().to_string())),
+            Function::Lower => Ok(text_of(&values[0], str::to_lowercase)),
+            Function::Upper => Ok(text_of(&values[0], str::to_uppercase)),
+            Function::Coalesce => Ok(values
+                .into_iter()
+                .find(|value| !value.is_null())
+                .unwrap_or(Value::Null)),
+            Function::DateFormat => {
+                if values[0].is_null() {
+                    return Ok(Value::Null);
+                }
+                let text = value_to_text(&values[0]);
+                let parsed = parse_datetime(&text)
+                    .ok_or_else(|| format!("date_format cannot parse '{}' as a date", text))?;
+                let format = value_to_text(&values[1]);
+                let mut formatted = String::new();
+                write!(formatted, "{}", parsed.format(&format)).map_err(|_| {
+                    format!("date_format cannot format '{}' with '{}'", text, format)
+                })?;
+                Ok(Value::String(formatted))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Number(String),
+    Open,
+    Close,
+    Comma,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(&ch) = chars.peek() {
+        match ch {
+            ' ' | '\t' | '\n' | '\r' => {
+                chars.next();
+            }
+            '(' | ')' | ',' => {
+                chars.next();
+                tokens.push(match ch {
+                    '(' => Token::Open,
+                    ')' => Token::Close,
+                    _ => Token::Comma,
+                });
+            }
+            '\'' | '"' => {
+                chars.next();
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        // A doubled quote escapes itself
+                        Some(c) if c == ch && chars.peek() == Some(&ch) => {
+                            chars.next();
+                            text.push(ch);
+                        }
+                        Some(c) if c == ch => break,
+                        Some(c) => text.push(c),
+                        None => {
+                            return Err(format!("Unterminated quote in expression '{}'", input))
+                        }
+                    }
+                }
+                // Single quotes are strings, double quotes are column names
+                tokens.push(if ch == '\'' {
+                    Token::Str(text)
+                } else {
+                    Token::Ident(text)
+                });
+            }
+            c if c.is_ascii_digit() || c == '-' => {
+                let mut number = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_ascii_digit() || c == '.' || (c == '-' && number.is_empty()) {
+                        number.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Number(number));
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut ident = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_alphanumeric() || c == '_' || c == '$' {
+                        ident.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Ident(ident));
+            }
+            other => {
+                return Err(format!(
+                    "Unexpected character '{}' in expression '{}'",
+                    other, input
+                ))
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn parse_expr(tokens: &[Token], position: &mut usize) -> Result<Expr, String> {
+    let token = tokens
+        .get(*position)
+        .ok_or_else(|| "Expression ended unexpectedly".to_string())?;
+    *position += 1;
+    match token {
+        Token::Str(text) => Ok(Expr::Literal(Value::String(text.clone()))),
+        Token::Number(text) => text
+            .parse::<i64>()
+            .map(Value::from)
+            .ok()
+            .or_else(|| {
+                text.parse::<f64>()
+                    .ok()
+                    .and_then(serde_json::Number::from_f64)
+                    .map(Value::Number)
+            })
+            .map(Expr::Literal)
+            .ok_or_else(|| format!("Invalid number '{}'", text)),
+        Token::Ident(name) if tokens.get(*position) == Some(&Token::Open) => {
+            let function = Function::parse(name)?;
+            *position += 1;
+            let mut args = Vec::new();
+            if tokens.get(*position) == Some(&Token::Close) {
+                *position += 1;
+            } else {
+                loop {
+                    args.push(parse_expr(tokens, position)?);
+                    match tokens.get(*position) {
+                        Some(Token::Comma) => *position += 1,
+                        Some(Token::Close) => {
+                            *position += 1;
+                            break;
+                        }
+                        _ => return Err(format!("Expected ',' or ')' in {}()", name)),
+                    }
+                }
+            }
+            function.check_arity(args.len())?;
+            Ok(Expr::Call(function, args))
+        }
+        Token::Ident(name) => Ok(Expr::Column(name.clone())),
+        other => Err(format!("Unexpected {:?} in expression", other)),
+    }
+}
+
 #[cfg(test)]
 mod tests;
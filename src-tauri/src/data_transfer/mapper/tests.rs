@@ -2,21 +2,282 @@ use super::*;
 
 #[test]
 fn test_validate_mapping_rules() {
-    let valid = vec![
-        ColumnMappingRule {
-            source_column: "id".to_string(),
-            target_column: "id".to_string(),
-            cast_type: None,
-        }
-    ];
+    let valid = vec![ColumnMappingRule {
+        source_column: "id".to_string(),
+        target_column: "id".to_string(),
+        cast_type: None,
+        constant: None,
+        expression: None,
+    }];
     assert!(validate_mapping_rules(&valid).is_ok());
-    
-    let invalid = vec![
-        ColumnMappingRule {
-            source_column: "".to_string(),
-            target_column: "id".to_string(),
-            cast_type: None,
-        }
-    ];
+
+    let invalid = vec![ColumnMappingRule {
+        source_column: "".to_string(),
+        target_column: "id".to_string(),
+        cast_type: None,
+        constant: None,
+        expression: None,
+    }];
     assert!(validate_mapping_rules(&invalid).is_err());
 }
+
+fn rule(source: &str, target: &str) -> ColumnMappingRule {
+    ColumnMappingRule {
+        source_column: source.to_string(),
+        target_column: target.to_string(),
+        cast_type: None,
+        constant: None,
+        expression: None,
+    }
+}
+
+fn columns(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+#[test]
+fn test_mapping_renames_casts_and_drops() {
+    let mapping = TableColumnMapping {
+        source_table: "customers".to_string(),
+        rules: vec![
+            rule("ID", "customer_id"),
+            ColumnMappingRule {
+                cast_type: Some("integer".to_string()),
+                ..rule("age", "age")
+            },
+            ColumnMappingRule {
+                expression: Some("concat(first_name, ' ', last_name)".to_string()),
+                ..rule("", "full_name")
+            },
+            ColumnMappingRule {
+                constant: Some(serde_json::json!("crm")),
+                ..rule("", "origin")
+            },
+        ],
+        drop_unmapped: false,
+    };
+    assert!(mapping.validate().is_ok());
+
+    let source_columns = columns(&["id", "first_name", "last_name", "age"]);
+    let rows = vec![vec![
+        serde_json::json!(7),
+        serde_json::json!("Ada"),
+        serde_json::Value::Null,
+        serde_json::json!("36"),
+    ]];
+    let (target_columns, mapped) = mapping.apply(&source_columns, &rows).unwrap();
+    assert_eq!(
+        target_columns,
+        columns(&[
+            "first_name",
+            "last_name",
+            "customer_id",
+            "age",
+            "full_name",
+            "origin"
+        ])
+    );
+    assert_eq!(
+        mapped[0],
+        vec![
+            serde_json::json!("Ada"),
+            serde_json::Value::Null,
+            serde_json::json!(7),
+            serde_json::json!(36),
+            serde_json::json!("Ada "),
+            serde_json::json!("crm"),
+        ]
+    );
+
+    let dropping = TableColumnMapping {
+        drop_unmapped: true,
+        ..mapping
+    };
+    let (target_columns, _) = dropping.apply(&source_columns, &rows).unwrap();
+    assert_eq!(
+        target_columns,
+        columns(&["customer_id", "age", "full_name", "origin"])
+    );
+}
+
+#[test]
+fn test_target_column_for_follows_renames() {
+    let mapping = TableColumnMapping {
+        source_table: "customers".to_string(),
+        rules: vec![
+            rule("ID", "customer_id"),
+            ColumnMappingRule {
+                expression: Some("upper(region)".to_string()),
+                ..rule("", "region")
+            },
+        ],
+        drop_unmapped: false,
+    };
+    assert_eq!(
+        mapping.target_column_for("id"),
+        Some("customer_id".to_string())
+    );
+    assert_eq!(
+        mapping.target_column_for("email"),
+        Some("email".to_string())
+    );
+    // Overwritten by an expression, so the source value never reaches the target
+    assert_eq!(mapping.target_column_for("region"), None);
+
+    let dropping = TableColumnMapping {
+        drop_unmapped: true,
+        ..mapping
+    };
+    assert_eq!(dropping.target_column_for("email"), None);
+}
+
+#[test]
+fn test_mapping_expressions() {
+    let mapping = TableColumnMapping {
+        source_table: "events".to_string(),
+        rules: vec![
+            ColumnMappingRule {
+                expression: Some("lower(trim(\"E-mail\"))".to_string()),
+                ..rule("", "email")
+            },
+            ColumnMappingRule {
+                expression: Some("date_format(created_at, '%Y-%m')".to_string()),
+                ..rule("", "month")
+            },
+            ColumnMappingRule {
+                expression: Some("coalesce(region, 'EU')".to_string()),
+                ..rule("", "region")
+            },
+            ColumnMappingRule {
+                cast_type: Some("date".to_string()),
+                ..rule("created_at", "created_on")
+            },
+        ],
+        drop_unmapped: true,
+    };
+    let (_, mapped) = mapping
+        .apply(
+            &columns(&["E-mail", "created_at", "region"]),
+            &[vec![
+                serde_json::json!("  Ada@Example.COM "),
+                serde_json::json!("2024-03-05T10:15:00Z"),
+                serde_json::Value::Null,
+            ]],
+        )
+        .unwrap();
+    assert_eq!(
+        mapped[0],
+        vec![
+            serde_json::json!("ada@example.com"),
+            serde_json::json!("2024-03"),
+            serde_json::json!("EU"),
+            serde_json::json!("2024-03-05"),
+        ]
+    );
+}
+
+#[test]
+fn test_mapping_errors() {
+    let both = ColumnMappingRule {
+        constant: Some(serde_json::json!(1)),
+        ..rule("id", "id")
+    };
+    assert!(validate_mapping_rules(&[both]).is_err());
+    let unknown_function = ColumnMappingRule {
+        expression: Some("reverse(name)".to_string()),
+        ..rule("", "name")
+    };
+    assert!(validate_mapping_rules(&[unknown_function]).is_err());
+    for expression in [
+        "date_format(created_at, '%i')",
+        "date_format(created_at, fmt)",
+    ] {
+        let bad_format = ColumnMappingRule {
+            expression: Some(expression.to_string()),
+            ..rule("", "month")
+        };
+        assert!(validate_mapping_rules(&[bad_format]).is_err());
+    }
+    let bad_cast = ColumnMappingRule {
+        cast_type: Some("money".to_string()),
+        ..rule("id", "id")
+    };
+    assert!(validate_mapping_rules(&[bad_cast]).is_err());
+    assert!(validate_mapping_rules(&[rule("a", "x"), rule("b", "X")]).is_err());
+
+    let mapping = TableColumnMapping {
+        source_table: "orders".to_string(),
+        rules: vec![ColumnMappingRule {
+            cast_type: Some("integer".to_string()),
+            ..rule("qty", "qty")
+        }],
+        drop_unmapped: false,
+    };
+    let missing = mapping.apply(&columns(&["id"]), &[vec![serde_json::json!(1)]]);
+    assert!(missing.unwrap_err().contains("'qty'"));
+    let uncastable = mapping.apply(&columns(&["qty"]), &[vec![serde_json::json!("many")]]);
+    assert!(uncastable.unwrap_err().contains("cannot cast"));
+
+    // Parses, but a naive datetime has no offset to format
+    let offset = TableColumnMapping {
+        source_table: "orders".to_string(),
+        rules: vec![ColumnMappingRule {
+            expression: Some("date_format(created_at, '%z')".to_string()),
+            ..rule("", "zone")
+        }],
+        drop_unmapped: true,
+    };
+    let unformattable = offset.apply(
+        &columns(&["created_at"]),
+        &[vec![serde_json::json!("2024-03-05 10:15:00")]],
+    );
+    assert!(unformattable.unwrap_err().contains("cannot format"));
+}
+
+#[test]
+fn test_decimal_cast_keeps_exact_digits() {
+    assert_eq!(CastType::parse("NUMERIC").unwrap(), CastType::Decimal);
+    assert_eq!(CastType::parse("double").unwrap(), CastType::Float);
+    assert_eq!(
+        CastType::Decimal
+            .apply(serde_json::json!(" 12345678901234567890.123456789 "))
+            .unwrap(),
+        serde_json::json!("12345678901234567890.123456789")
+    );
+    assert_eq!(
+        CastType::Decimal.apply(serde_json::json!("+0.10")).unwrap(),
+        serde_json::json!("0.10")
+    );
+    assert_eq!(
+        CastType::Decimal.apply(serde_json::json!(42)).unwrap(),
+        serde_json::json!("42")
+    );
+    assert!(CastType::Decimal.apply(serde_json::json!("1e5")).is_err());
+    assert!(CastType::Decimal.apply(serde_json::json!(".")).is_err());
+}
+
+#[test]
+fn test_step_mapping_follows_the_column_list() {
+    let mapping = StepMapping::new(TableColumnMapping {
+        source_table: "orders".to_string(),
+        rules: vec![ColumnMappingRule {
+            expression: Some("upper(code)".to_string()),
+            ..rule("", "code")
+        }],
+        drop_unmapped: true,
+    });
+    let first = mapping
+        .apply(
+            &columns(&["id", "code"]),
+            &[vec![serde_json::json!(1), serde_json::json!("a")]],
+        )
+        .unwrap();
+    assert_eq!(first.1[0], vec![serde_json::json!("A")]);
+    let reordered = mapping
+        .apply(
+            &columns(&["code", "id"]),
+            &[vec![serde_json::json!("b"), serde_json::json!(2)]],
+        )
+        .unwrap();
+    assert_eq!(reordered.1[0], vec![serde_json::json!("B")]);
+}
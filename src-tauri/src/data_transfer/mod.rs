@@ -1,6 +1,8 @@
+pub mod checkpoint;
 pub mod commands;
 pub use commands::*;
 pub mod connection_resolver;
+pub mod constraints;
 pub mod engine;
 pub mod mapper;
 pub use mapper::*;
@@ -1,3 +1,4 @@
+use crate::data_transfer::mapper::{ColumnMappingRule, TableColumnMapping};
 use crate::data_transfer::sink::DataTransferSinkType;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -27,6 +28,11 @@ pub struct DataTransferObjectSpec {
     #[serde(default)]
     pub sink_type: DataTransferSinkType,
     pub sink_path: Option<String>,
+    /// Overrides the plan's mapping profile for this object
+    #[serde(default)]
+    pub column_mappings: Vec<ColumnMappingRule>,
+    #[serde(default)]
+    pub drop_unmapped_columns: bool,
 }
 
 impl DataTransferObjectSpec {
@@ -59,6 +65,17 @@ impl DataTransferObjectSpec {
             .filter(|value| !value.is_empty())
             .map(str::to_string)
     }
+
+    pub fn column_mapping(&self) -> Option<TableColumnMapping> {
+        if self.column_mappings.is_empty() && !self.drop_unmapped_columns {
+            return None;
+        }
+        Some(TableColumnMapping {
+            source_table: self.normalized_source_table(),
+            rules: self.column_mappings.clone(),
+            drop_unmapped: self.drop_unmapped_columns,
+        })
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -74,10 +91,23 @@ pub struct DataTransferPlanRequest {
     pub include_schema_migration: bool,
     #[serde(default = "default_true")]
     pub lock_guard: bool,
+    /// Name of a saved `DataTransferMappingProfile`
     pub mapping_profile: Option<String>,
+    #[serde(default)]
+    pub performance: DataTransferPerformanceOptions,
+    #[serde(default)]
+    pub constraint_handling: DataTransferConstraintHandling,
 }
 
 impl DataTransferPlanRequest {
+    pub fn normalized_mapping_profile(&self) -> Option<String> {
+        self.mapping_profile
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    }
+
     pub fn validate(&self) -> Result<(), String> {
         if self.source_connection_id.trim().is_empty() {
             return Err("sourceConnectionId is required".to_string());
@@ -94,6 +124,9 @@ impl DataTransferPlanRequest {
         if self.objects.is_empty() {
             return Err("At least one transfer object is required".to_string());
         }
+        if self.performance.max_rows_per_second == Some(0) {
+            return Err("performance.maxRowsPerSecond must be greater than zero".to_string());
+        }
 
         for (index, object) in self.objects.iter().enumerate() {
             let source_table = object.normalized_source_table();
@@ -115,6 +148,12 @@ impl DataTransferPlanRequest {
                 ));
             }
 
+            if let Some(mapping) = object.column_mapping() {
+                mapping.validate().map_err(|e| {
+                    format!("Object {} has an invalid column mapping: {}", index + 1, e)
+                })?;
+            }
+
             if matches!(object.mode, DataTransferMode::Upsert)
                 && !matches!(
                     object.sink_type,
@@ -148,6 +187,69 @@ impl DataTransferPlanRequest {
     }
 }
 
+/// How a run treats foreign keys and triggers on the target while it loads.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DataTransferConstraintHandling {
+    /// Leave constraints active; parents load before the tables that reference them
+    #[default]
+    Enforce,
+    /// Suspend foreign key checks and triggers for the load, then re-validate
+    DisableDuringLoad,
+}
+
+impl DataTransferConstraintHandling {
+    pub fn enforces_order(&self) -> bool {
+        matches!(self, DataTransferConstraintHandling::Enforce)
+    }
+}
+
+/// Throughput controls for a run. Left empty, every step runs on a single
+/// sequential worker with fixed 1000-row chunks.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DataTransferPerformanceOptions {
+    /// Steps on distinct targets that may run at the same time
+    pub max_parallel_tables: Option<usize>,
+    /// Key-range partitions copied concurrently within one large table
+    pub partitions_per_table: Option<usize>,
+    /// Each partition gets at least this many rows
+    pub min_rows_per_partition: Option<usize>,
+    /// Starting rows per chunk
+    pub batch_size: Option<usize>,
+    /// Grow or shrink chunks toward a steady per-chunk duration
+    #[serde(default)]
+    pub adaptive_batch_size: bool,
+    /// Source rows read per second across every worker of the run
+    pub max_rows_per_second: Option<u64>,
+}
+
+impl DataTransferPerformanceOptions {
+    pub fn table_parallelism(&self) -> usize {
+        self.max_parallel_tables.unwrap_or(1).clamp(1, 16)
+    }
+
+    pub fn partitions(&self) -> usize {
+        self.partitions_per_table.unwrap_or(1).clamp(1, 32)
+    }
+
+    pub fn min_rows_per_partition(&self) -> usize {
+        self.min_rows_per_partition.unwrap_or(100_000).max(1_000)
+    }
+
+    pub fn initial_batch_size(&self) -> usize {
+        self.batch_size.unwrap_or(1_000).clamp(100, 50_000)
+    }
+
+    /// Whether any step-level control differs from the plain sequential copy.
+    pub fn is_tuned(&self) -> bool {
+        self.partitions() > 1
+            || self.batch_size.is_some()
+            || self.adaptive_batch_size
+            || self.max_rows_per_second.is_some()
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct DataTransferSchemaMigrationPreflight {
@@ -166,6 +268,8 @@ pub struct DataTransferSchemaMigrationPreflight {
     pub warnings: Vec<String>,
     pub error: Option<String>,
     pub migration_script_preview: Option<String>,
+    #[serde(default)]
+    pub rollback_script_preview: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -235,3 +339,80 @@ pub struct DataTransferRunSummary {
     pub finished_at: Option<DateTime<Utc>>,
     pub error: Option<String>,
 }
+
+/// Named column mappings for a set of tables, saved once and referenced from
+/// plans through `mappingProfile`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DataTransferMappingProfile {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub tables: Vec<TableColumnMapping>,
+    #[serde(default = "Utc::now")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl DataTransferMappingProfile {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Mapping profile name is required".to_string());
+        }
+        for (index, table) in self.tables.iter().enumerate() {
+            if table.source_table.trim().is_empty() {
+                return Err(format!(
+                    "Mapping profile table {} has an empty sourceTable",
+                    index + 1
+                ));
+            }
+            if self.tables[..index]
+                .iter()
+                .any(|earlier| earlier.source_table.trim().eq_ignore_ascii_case(table.source_table.trim()))
+            {
+                return Err(format!(
+                    "Mapping profile maps table '{}' more than once",
+                    table.source_table.trim()
+                ));
+            }
+            table.validate()?;
+        }
+        Ok(())
+    }
+
+    pub fn mapping_for(&self, source_table: &str) -> Option<&TableColumnMapping> {
+        self.tables
+            .iter()
+            .find(|table| table.source_table.trim().eq_ignore_ascii_case(source_table.trim()))
+    }
+}
+
+/// Last committed position of one plan step, kept so a failed or cancelled run
+/// can resume without re-copying what already landed in the target.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DataTransferStepCheckpoint {
+    pub operation_id: String,
+    pub step_key: String,
+    #[serde(default)]
+    pub cursor_columns: Vec<String>,
+    /// Keyset cursor of the last written chunk; None until one commits
+    #[serde(default)]
+    pub cursor_values: Option<Vec<serde_json::Value>>,
+    /// Key ranges of a partitioned copy, each with its own high-water key
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub partitions: Vec<DataTransferPartitionCheckpoint>,
+    pub written_rows: usize,
+    pub completed: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// One key range of a partitioned step; rows up to `high_water` have landed.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DataTransferPartitionCheckpoint {
+    pub lower: i64,
+    pub upper: i64,
+    #[serde(default)]
+    pub high_water: Option<i64>,
+}
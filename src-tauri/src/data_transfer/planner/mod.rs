@@ -1,6 +1,9 @@
-use crate::data_transfer::models::DataTransferPlanRequest;
+use crate::data_transfer::mapper::TableColumnMapping;
+use crate::data_transfer::models::{DataTransferMappingProfile, DataTransferPlanRequest};
 use crate::data_transfer::sink::DataTransferSinkType;
+use crate::dependency_engine::graph::{DependencyGraph, EdgeType, NodeType};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -15,6 +18,13 @@ pub struct DataTransferPlanStep {
     pub sink_type: DataTransferSinkType,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sink_path: Option<String>,
+    /// Steps that must finish first because this step's table references theirs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+    /// Renames, casts and derived columns applied to every row; None copies
+    /// columns by name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column_mapping: Option<TableColumnMapping>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -38,11 +48,199 @@ pub fn build_execution_plan(request: &DataTransferPlanRequest) -> Result<DataTra
             key_columns: object.normalized_key_columns(),
             sink_type: object.sink_type.clone(),
             sink_path: object.normalized_sink_path(),
+            depends_on: Vec::new(),
+            column_mapping: object.column_mapping(),
         })
         .collect::<Vec<_>>();
 
     Ok(DataTransferExecutionPlan { steps })
 }
 
+/// Gives every step without its own mapping the profile's mapping for its
+/// source table.
+pub fn apply_mapping_profile(
+    plan: &mut DataTransferExecutionPlan,
+    profile: &DataTransferMappingProfile,
+) {
+    for step in plan.steps.iter_mut() {
+        if step.column_mapping.is_none() {
+            step.column_mapping = profile.mapping_for(&step.source_table).cloned();
+        }
+    }
+}
+
+/// A foreign key from `child` to `parent`, both target table names.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableDependency {
+    pub child: String,
+    pub parent: String,
+}
+
+/// Reorders database steps so each table loads after the tables it references,
+/// keeping the requested order wherever the foreign keys allow. With `enforce`
+/// every step also lists the steps it waits for. Tables in a foreign key cycle
+/// keep their requested order with a warning. Returns warnings for the run.
+pub fn order_steps_by_dependencies(
+    plan: &mut DataTransferExecutionPlan,
+    dependencies: &[TableDependency],
+    enforce: bool,
+) -> Result<Vec<String>, String> {
+    let mut warnings = Vec::new();
+    let keys = plan
+        .steps
+        .iter()
+        .map(|step| {
+            (step.sink_type == DataTransferSinkType::Database).then(|| table_key(&step.target_table))
+        })
+        .collect::<Vec<_>>();
+    let planned = keys.iter().flatten().cloned().collect::<HashSet<_>>();
+
+    let mut graph = DependencyGraph::new();
+    for key in keys.iter().flatten() {
+        graph.add_node(None, key.clone(), NodeType::Table);
+    }
+    let mut self_referencing = BTreeSet::new();
+    for dependency in dependencies {
+        let (child, parent) = (table_key(&dependency.child), table_key(&dependency.parent));
+        if !planned.contains(&child) || !planned.contains(&parent) {
+            continue;
+        }
+        if child == parent {
+            self_referencing.insert(child);
+            continue;
+        }
+        graph.add_edge(&child, &parent, EdgeType::ForeignKey);
+    }
+
+    let cycles = graph.find_cycles();
+    let mut cycle_of = HashMap::new();
+    for (index, cycle) in cycles.iter().enumerate() {
+        let mut tables = cycle.clone();
+        tables.sort();
+        warnings.push(if enforce {
+            format!(
+                "Foreign keys form a cycle between {}; no load order satisfies them, so these tables load in the requested order and rows referencing a later table fail unless constraintHandling is 'disable_during_load'",
+                tables.join(", ")
+            )
+        } else {
+            format!(
+                "Foreign keys form a cycle between {}; these tables load in the requested order while constraints are suspended",
+                tables.join(", ")
+            )
+        });
+        for table in tables {
+            cycle_of.insert(table, index);
+        }
+    }
+    if enforce {
+        for table in &self_referencing {
+            warnings.push(format!(
+                "Table '{}' references itself; rows must arrive parents-first unless constraintHandling is 'disable_during_load'",
+                table
+            ));
+        }
+    }
+
+    // Edges inside a cycle cannot be honoured, the rest form a DAG
+    let mut parents_of: HashMap<&str, HashSet<&str>> = HashMap::new();
+    for link in graph.to_data().edges {
+        let same_cycle = cycle_of.contains_key(&link.source)
+            && cycle_of.get(&link.source) == cycle_of.get(&link.target);
+        if !same_cycle {
+            let child = planned.get(&link.source).map(String::as_str);
+            let parent = planned.get(&link.target).map(String::as_str);
+            if let (Some(child), Some(parent)) = (child, parent) {
+                parents_of.entry(child).or_default().insert(parent);
+            }
+        }
+    }
+
+    // Step-level prerequisites: every step on a parent table, plus earlier
+    // steps on the same table so their relative order survives
+    let count = plan.steps.len();
+    let mut parent_steps = vec![Vec::new(); count];
+    let mut pending = vec![0usize; count];
+    let mut dependents = vec![Vec::new(); count];
+    for (index, key) in keys.iter().enumerate() {
+        let Some(key) = key else { continue };
+        let parents = parents_of.get(key.as_str());
+        for (other, other_key) in keys.iter().enumerate() {
+            let Some(other_key) = other_key else { continue };
+            let is_parent = parents.is_some_and(|parents| parents.contains(other_key.as_str()));
+            if is_parent {
+                parent_steps[index].push(other);
+            }
+            if is_parent || (other_key == key && other < index) {
+                pending[index] += 1;
+                dependents[other].push(index);
+            }
+        }
+    }
+
+    let mut ready = (0..count)
+        .filter(|index| pending[*index] == 0)
+        .collect::<BTreeSet<_>>();
+    let mut order = Vec::with_capacity(count);
+    while let Some(index) = ready.pop_first() {
+        order.push(index);
+        for dependent in &dependents[index] {
+            pending[*dependent] -= 1;
+            if pending[*dependent] == 0 {
+                ready.insert(*dependent);
+            }
+        }
+    }
+    if order.len() != count {
+        return Err("Failed to order transfer steps by foreign keys".to_string());
+    }
+
+    let step_keys = plan
+        .steps
+        .iter()
+        .map(|step| step.step_key.clone())
+        .collect::<Vec<_>>();
+    if enforce {
+        for (index, step) in plan.steps.iter_mut().enumerate() {
+            step.depends_on = parent_steps[index]
+                .iter()
+                .map(|parent| step_keys[*parent].clone())
+                .collect();
+        }
+    }
+
+    if order.iter().enumerate().any(|(position, index)| position != *index) {
+        let mut tables = Vec::new();
+        for index in &order {
+            let table = &plan.steps[*index].target_table;
+            if !tables.contains(table) {
+                tables.push(table.clone());
+            }
+        }
+        warnings.push(format!(
+            "Steps reordered so referenced tables load first: {}",
+            tables.join(", ")
+        ));
+    }
+
+    let mut steps = std::mem::take(&mut plan.steps)
+        .into_iter()
+        .map(Some)
+        .collect::<Vec<_>>();
+    plan.steps = order
+        .into_iter()
+        .filter_map(|index| steps[index].take())
+        .collect();
+
+    Ok(warnings)
+}
+
+/// Bare, case-folded table name used to match plan targets with FK metadata.
+fn table_key(table: &str) -> String {
+    let name = table.rsplit('.').next().unwrap_or(table);
+    name.trim()
+        .trim_matches(|c| matches!(c, '`' | '"' | '[' | ']'))
+        .to_ascii_lowercase()
+}
+
 #[cfg(test)]
 mod tests;
@@ -17,11 +17,15 @@ fn test_build_execution_plan() {
                 key_columns: vec![],
                 sink_type: DataTransferSinkType::Database,
                 sink_path: None,
+                column_mappings: vec![],
+                drop_unmapped_columns: false,
             }
         ],
         include_schema_migration: false,
         lock_guard: true,
         mapping_profile: None,
+        performance: Default::default(),
+        constraint_handling: Default::default(),
     };
     
     let plan = build_execution_plan(&req).unwrap();
@@ -29,3 +33,87 @@ fn test_build_execution_plan() {
     assert_eq!(plan.steps[0].source_table, "users");
     assert_eq!(plan.steps[0].target_table, "users"); // defaults to source
 }
+
+fn database_step(key: &str, table: &str) -> DataTransferPlanStep {
+    DataTransferPlanStep {
+        step_key: key.into(),
+        source_table: table.into(),
+        target_table: table.into(),
+        mode: "append".into(),
+        key_columns: vec![],
+        sink_type: DataTransferSinkType::Database,
+        sink_path: None,
+        depends_on: vec![],
+        column_mapping: None,
+    }
+}
+
+fn dependency(child: &str, parent: &str) -> TableDependency {
+    TableDependency {
+        child: child.into(),
+        parent: parent.into(),
+    }
+}
+
+#[test]
+fn test_steps_order_parents_first() {
+    let mut plan = DataTransferExecutionPlan {
+        steps: vec![
+            database_step("step_1", "order_items"),
+            database_step("step_2", "audit_log"),
+            database_step("step_3", "orders"),
+            database_step("step_4", "customers"),
+        ],
+    };
+    let dependencies = vec![
+        dependency("order_items", "orders"),
+        dependency("orders", "customers"),
+        // Tables outside the plan are ignored
+        dependency("orders", "regions"),
+    ];
+
+    let warnings = order_steps_by_dependencies(&mut plan, &dependencies, true).unwrap();
+    let order = plan.steps.iter().map(|s| s.step_key.as_str()).collect::<Vec<_>>();
+    // Unrelated steps keep their requested position as far as possible
+    assert_eq!(order, vec!["step_2", "step_4", "step_3", "step_1"]);
+    assert_eq!(plan.steps[2].depends_on, vec!["step_4".to_string()]);
+    assert_eq!(plan.steps[3].depends_on, vec!["step_3".to_string()]);
+    assert_eq!(warnings.len(), 1);
+
+    // Already in order: nothing moves and nothing is reported
+    let warnings = order_steps_by_dependencies(&mut plan, &dependencies, true).unwrap();
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn test_foreign_key_cycles_keep_the_requested_order() {
+    let steps = vec![
+        database_step("step_1", "employees"),
+        database_step("step_2", "departments"),
+        database_step("step_3", "locations"),
+    ];
+    let dependencies = vec![
+        dependency("employees", "departments"),
+        dependency("departments", "employees"),
+        dependency("departments", "locations"),
+        dependency("employees", "employees"),
+    ];
+
+    let mut plan = DataTransferExecutionPlan { steps: steps.clone() };
+    let warnings = order_steps_by_dependencies(&mut plan, &dependencies, true).unwrap();
+    let order = plan.steps.iter().map(|s| s.step_key.as_str()).collect::<Vec<_>>();
+    assert_eq!(order, vec!["step_1", "step_3", "step_2"]);
+    // Only the edge leaving the cycle is waited for
+    assert!(plan.steps[0].depends_on.is_empty());
+    assert_eq!(plan.steps[2].depends_on, vec!["step_3".to_string()]);
+    assert!(warnings[0].contains("departments, employees"));
+    assert!(warnings[0].contains("disable_during_load"));
+
+    let mut plan = DataTransferExecutionPlan { steps };
+    let warnings = order_steps_by_dependencies(&mut plan, &dependencies, false).unwrap();
+    let order = plan.steps.iter().map(|s| s.step_key.as_str()).collect::<Vec<_>>();
+    // The cycle keeps its requested order; edges leaving it still apply
+    assert_eq!(order, vec!["step_1", "step_3", "step_2"]);
+    assert!(plan.steps.iter().all(|step| step.depends_on.is_empty()));
+    assert!(warnings[0].contains("cycle"));
+}
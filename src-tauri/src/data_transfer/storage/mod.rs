@@ -1,4 +1,7 @@
-use crate::data_transfer::models::DataTransferRunSummary;
+use crate::data_transfer::models::{
+    DataTransferMappingProfile, DataTransferPlanRequest, DataTransferRunSummary,
+    DataTransferStepCheckpoint,
+};
 use sqlx::{Pool, Row, Sqlite};
 use std::collections::HashMap;
 use std::sync::LazyLock;
@@ -6,6 +9,12 @@ use tokio::sync::Mutex;
 
 static RUN_SNAPSHOT_STORE: LazyLock<Mutex<HashMap<String, DataTransferRunSummary>>> =
     LazyLock::new(|| Mutex::new(HashMap::new()));
+static RUN_REQUEST_STORE: LazyLock<Mutex<HashMap<String, DataTransferPlanRequest>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+static CHECKPOINT_STORE: LazyLock<Mutex<HashMap<(String, String), DataTransferStepCheckpoint>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+static MAPPING_PROFILE_STORE: LazyLock<Mutex<HashMap<String, DataTransferMappingProfile>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
 static LOCAL_DB_POOL: LazyLock<Mutex<Option<Pool<Sqlite>>>> = LazyLock::new(|| Mutex::new(None));
 
 async fn ensure_schema(pool: &Pool<Sqlite>) -> Result<(), String> {
@@ -19,6 +28,23 @@ async fn ensure_schema(pool: &Pool<Sqlite>) -> Result<(), String> {
         );
         CREATE INDEX IF NOT EXISTS idx_data_transfer_runs_started_at ON data_transfer_runs(started_at DESC);
         CREATE INDEX IF NOT EXISTS idx_data_transfer_runs_updated_at ON data_transfer_runs(updated_at DESC);
+        CREATE TABLE IF NOT EXISTS data_transfer_run_requests (
+            operation_id TEXT PRIMARY KEY,
+            request_json TEXT NOT NULL,
+            created_at INTEGER NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS data_transfer_checkpoints (
+            operation_id TEXT NOT NULL,
+            step_key TEXT NOT NULL,
+            checkpoint_json TEXT NOT NULL,
+            updated_at INTEGER NOT NULL,
+            PRIMARY KEY (operation_id, step_key)
+        );
+        CREATE TABLE IF NOT EXISTS data_transfer_mapping_profiles (
+            name TEXT PRIMARY KEY,
+            profile_json TEXT NOT NULL,
+            updated_at INTEGER NOT NULL
+        );
         "#,
     )
     .execute(pool)
@@ -108,6 +134,178 @@ async fn load_snapshots_from_db(pool: &Pool<Sqlite>, limit: usize) -> Vec<DataTr
         .collect()
 }
 
+async fn persist_run_request(
+    pool: &Pool<Sqlite>,
+    operation_id: &str,
+    request: &DataTransferPlanRequest,
+) -> Result<(), String> {
+    let request_json = serde_json::to_string(request)
+        .map_err(|e| format!("Failed to serialize data transfer request: {}", e))?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO data_transfer_run_requests (operation_id, request_json, created_at)
+        VALUES (?, ?, ?)
+        ON CONFLICT(operation_id) DO UPDATE SET request_json = excluded.request_json
+        "#,
+    )
+    .bind(operation_id)
+    .bind(request_json)
+    .bind(chrono::Utc::now().timestamp())
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to persist data transfer request: {}", e))?;
+
+    Ok(())
+}
+
+async fn load_run_request_from_db(
+    pool: &Pool<Sqlite>,
+    operation_id: &str,
+) -> Option<DataTransferPlanRequest> {
+    let row = sqlx::query(
+        r#"
+        SELECT request_json
+        FROM data_transfer_run_requests
+        WHERE operation_id = ?
+        LIMIT 1
+        "#,
+    )
+    .bind(operation_id)
+    .fetch_optional(pool)
+    .await
+    .ok()
+    .flatten()?;
+
+    let request_json: String = row.try_get("request_json").ok()?;
+    serde_json::from_str::<DataTransferPlanRequest>(&request_json).ok()
+}
+
+async fn persist_checkpoint(
+    pool: &Pool<Sqlite>,
+    checkpoint: &DataTransferStepCheckpoint,
+) -> Result<(), String> {
+    let checkpoint_json = serde_json::to_string(checkpoint)
+        .map_err(|e| format!("Failed to serialize data transfer checkpoint: {}", e))?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO data_transfer_checkpoints (operation_id, step_key, checkpoint_json, updated_at)
+        VALUES (?, ?, ?, ?)
+        ON CONFLICT(operation_id, step_key) DO UPDATE SET
+            checkpoint_json = excluded.checkpoint_json,
+            updated_at = excluded.updated_at
+        "#,
+    )
+    .bind(&checkpoint.operation_id)
+    .bind(&checkpoint.step_key)
+    .bind(checkpoint_json)
+    .bind(checkpoint.updated_at.timestamp())
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to persist data transfer checkpoint: {}", e))?;
+
+    Ok(())
+}
+
+async fn load_checkpoints_from_db(
+    pool: &Pool<Sqlite>,
+    operation_id: &str,
+) -> Vec<DataTransferStepCheckpoint> {
+    let rows = match sqlx::query(
+        r#"
+        SELECT checkpoint_json
+        FROM data_transfer_checkpoints
+        WHERE operation_id = ?
+        "#,
+    )
+    .bind(operation_id)
+    .fetch_all(pool)
+    .await
+    {
+        Ok(rows) => rows,
+        Err(_) => return Vec::new(),
+    };
+
+    rows.into_iter()
+        .filter_map(|row| {
+            let raw: String = row.try_get("checkpoint_json").ok()?;
+            serde_json::from_str::<DataTransferStepCheckpoint>(&raw).ok()
+        })
+        .collect()
+}
+
+async fn delete_resume_state_from_db(pool: &Pool<Sqlite>, operation_id: &str) -> Result<(), String> {
+    for table in ["data_transfer_checkpoints", "data_transfer_run_requests"] {
+        sqlx::query(&format!("DELETE FROM {} WHERE operation_id = ?", table))
+            .bind(operation_id)
+            .execute(pool)
+            .await
+            .map_err(|e| format!("Failed to delete data transfer resume state: {}", e))?;
+    }
+
+    Ok(())
+}
+
+async fn persist_mapping_profile(
+    pool: &Pool<Sqlite>,
+    profile: &DataTransferMappingProfile,
+) -> Result<(), String> {
+    let profile_json = serde_json::to_string(profile)
+        .map_err(|e| format!("Failed to serialize mapping profile: {}", e))?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO data_transfer_mapping_profiles (name, profile_json, updated_at)
+        VALUES (?, ?, ?)
+        ON CONFLICT(name) DO UPDATE SET
+            profile_json = excluded.profile_json,
+            updated_at = excluded.updated_at
+        "#,
+    )
+    .bind(&profile.name)
+    .bind(profile_json)
+    .bind(profile.updated_at.timestamp())
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to persist mapping profile: {}", e))?;
+
+    Ok(())
+}
+
+async fn load_mapping_profiles_from_db(pool: &Pool<Sqlite>) -> Vec<DataTransferMappingProfile> {
+    let rows = match sqlx::query(
+        r#"
+        SELECT profile_json
+        FROM data_transfer_mapping_profiles
+        ORDER BY name ASC
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(rows) => rows,
+        Err(_) => return Vec::new(),
+    };
+
+    rows.into_iter()
+        .filter_map(|row| {
+            let raw: String = row.try_get("profile_json").ok()?;
+            serde_json::from_str::<DataTransferMappingProfile>(&raw).ok()
+        })
+        .collect()
+}
+
+async fn delete_mapping_profile_from_db(pool: &Pool<Sqlite>, name: &str) -> Result<bool, String> {
+    let result = sqlx::query("DELETE FROM data_transfer_mapping_profiles WHERE name = ?")
+        .bind(name)
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Failed to delete mapping profile: {}", e))?;
+
+    Ok(result.rows_affected() > 0)
+}
+
 pub async fn set_local_pool(pool: Pool<Sqlite>) -> Result<(), String> {
     ensure_schema(&pool).await?;
     let mut guard = LOCAL_DB_POOL.lock().await;
@@ -162,5 +360,138 @@ pub async fn list_snapshots(limit: usize) -> Vec<DataTransferRunSummary> {
     snapshots
 }
 
+/// Keeps the plan a run was started with so it can be resumed later.
+pub async fn put_run_request(operation_id: &str, request: &DataTransferPlanRequest) {
+    {
+        let mut guard = RUN_REQUEST_STORE.lock().await;
+        guard.insert(operation_id.to_string(), request.clone());
+    }
+
+    if let Some(pool) = clone_pool().await {
+        if let Err(error) = persist_run_request(&pool, operation_id, request).await {
+            eprintln!("{}", error);
+        }
+    }
+}
+
+pub async fn get_run_request(operation_id: &str) -> Option<DataTransferPlanRequest> {
+    {
+        let guard = RUN_REQUEST_STORE.lock().await;
+        if let Some(request) = guard.get(operation_id) {
+            return Some(request.clone());
+        }
+    }
+
+    let pool = clone_pool().await?;
+    load_run_request_from_db(&pool, operation_id).await
+}
+
+pub async fn put_checkpoint(checkpoint: DataTransferStepCheckpoint) {
+    {
+        let mut guard = CHECKPOINT_STORE.lock().await;
+        guard.insert(
+            (checkpoint.operation_id.clone(), checkpoint.step_key.clone()),
+            checkpoint.clone(),
+        );
+    }
+
+    if let Some(pool) = clone_pool().await {
+        if let Err(error) = persist_checkpoint(&pool, &checkpoint).await {
+            eprintln!("{}", error);
+        }
+    }
+}
+
+/// Checkpoints of `operation_id` keyed by step key.
+pub async fn list_checkpoints(operation_id: &str) -> HashMap<String, DataTransferStepCheckpoint> {
+    let mut checkpoints = match clone_pool().await {
+        Some(pool) => load_checkpoints_from_db(&pool, operation_id)
+            .await
+            .into_iter()
+            .map(|checkpoint| (checkpoint.step_key.clone(), checkpoint))
+            .collect::<HashMap<_, _>>(),
+        None => HashMap::new(),
+    };
+
+    let guard = CHECKPOINT_STORE.lock().await;
+    for ((op_id, step_key), checkpoint) in guard.iter() {
+        if op_id == operation_id {
+            checkpoints.insert(step_key.clone(), checkpoint.clone());
+        }
+    }
+    checkpoints
+}
+
+/// Forgets the stored plan and checkpoints of a run that succeeded; only failed
+/// or cancelled runs are resumed.
+pub async fn clear_resume_state(operation_id: &str) {
+    RUN_REQUEST_STORE.lock().await.remove(operation_id);
+    CHECKPOINT_STORE
+        .lock()
+        .await
+        .retain(|(op_id, _), _| op_id != operation_id);
+
+    if let Some(pool) = clone_pool().await {
+        if let Err(error) = delete_resume_state_from_db(&pool, operation_id).await {
+            eprintln!("{}", error);
+        }
+    }
+}
+
+/// Saves a mapping profile, replacing any profile with the same name.
+pub async fn put_mapping_profile(profile: DataTransferMappingProfile) -> Result<(), String> {
+    if let Some(pool) = clone_pool().await {
+        persist_mapping_profile(&pool, &profile).await?;
+    }
+
+    let mut guard = MAPPING_PROFILE_STORE.lock().await;
+    guard.insert(profile.name.clone(), profile);
+    Ok(())
+}
+
+pub async fn get_mapping_profile(name: &str) -> Option<DataTransferMappingProfile> {
+    list_mapping_profiles()
+        .await
+        .into_iter()
+        .find(|profile| profile.name == name)
+}
+
+pub async fn list_mapping_profiles() -> Vec<DataTransferMappingProfile> {
+    let mut profiles = match clone_pool().await {
+        Some(pool) => load_mapping_profiles_from_db(&pool)
+            .await
+            .into_iter()
+            .map(|profile| (profile.name.clone(), profile))
+            .collect::<HashMap<_, _>>(),
+        None => HashMap::new(),
+    };
+
+    {
+        let guard = MAPPING_PROFILE_STORE.lock().await;
+        for (name, profile) in guard.iter() {
+            profiles.insert(name.clone(), profile.clone());
+        }
+    }
+
+    let mut profiles = profiles.into_values().collect::<Vec<_>>();
+    profiles.sort_by(|a, b| a.name.cmp(&b.name));
+    profiles
+}
+
+/// Removes a mapping profile; false when no profile had that name.
+pub async fn delete_mapping_profile(name: &str) -> Result<bool, String> {
+    let removed_in_memory = {
+        let mut guard = MAPPING_PROFILE_STORE.lock().await;
+        guard.remove(name).is_some()
+    };
+
+    let removed_in_db = match clone_pool().await {
+        Some(pool) => delete_mapping_profile_from_db(&pool, name).await?,
+        None => false,
+    };
+
+    Ok(removed_in_memory || removed_in_db)
+}
+
 #[cfg(test)]
 mod tests;
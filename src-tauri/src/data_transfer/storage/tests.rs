@@ -1,4 +1,5 @@
 use super::*;
+use crate::data_transfer::mapper::{ColumnMappingRule, TableColumnMapping};
 use crate::data_transfer::models::{DataTransferRunSummary, DataTransferRunStatus};
 use chrono::Utc;
 
@@ -48,3 +49,70 @@ async fn test_data_transfer_storage_crud() {
     let fetched2 = load_snapshot_from_db(&pool, "op1").await.unwrap();
     assert_eq!(fetched2.progress_pct, 50);
 }
+
+#[tokio::test]
+async fn test_data_transfer_checkpoint_storage() {
+    let pool = Pool::connect("sqlite::memory:").await.unwrap();
+    ensure_schema(&pool).await.unwrap();
+
+    let mut checkpoint = DataTransferStepCheckpoint {
+        operation_id: "op1".to_string(),
+        step_key: "step_1".to_string(),
+        cursor_columns: vec!["id".to_string()],
+        cursor_values: Some(vec![serde_json::json!(500)]),
+        partitions: Vec::new(),
+        written_rows: 500,
+        completed: false,
+        updated_at: Utc::now(),
+    };
+    persist_checkpoint(&pool, &checkpoint).await.unwrap();
+
+    checkpoint.cursor_values = Some(vec![serde_json::json!(1000)]);
+    checkpoint.written_rows = 1000;
+    persist_checkpoint(&pool, &checkpoint).await.unwrap();
+
+    let loaded = load_checkpoints_from_db(&pool, "op1").await;
+    assert_eq!(loaded, vec![checkpoint]);
+    assert!(load_checkpoints_from_db(&pool, "op2").await.is_empty());
+
+    assert!(load_run_request_from_db(&pool, "op1").await.is_none());
+
+    delete_resume_state_from_db(&pool, "op1").await.unwrap();
+    assert!(load_checkpoints_from_db(&pool, "op1").await.is_empty());
+}
+
+#[tokio::test]
+async fn test_data_transfer_mapping_profile_storage() {
+    let pool = Pool::connect("sqlite::memory:").await.unwrap();
+    ensure_schema(&pool).await.unwrap();
+
+    let mut profile = DataTransferMappingProfile {
+        name: "crm".to_string(),
+        description: None,
+        tables: vec![TableColumnMapping {
+            source_table: "customers".to_string(),
+            rules: vec![ColumnMappingRule {
+                source_column: "cust_name".to_string(),
+                target_column: "name".to_string(),
+                cast_type: None,
+                constant: None,
+                expression: None,
+            }],
+            drop_unmapped: true,
+        }],
+        updated_at: Utc::now(),
+    };
+    persist_mapping_profile(&pool, &profile).await.unwrap();
+
+    profile.description = Some("CRM import".to_string());
+    persist_mapping_profile(&pool, &profile).await.unwrap();
+
+    let loaded = load_mapping_profiles_from_db(&pool).await;
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded[0].description.as_deref(), Some("CRM import"));
+    assert_eq!(loaded[0].tables[0].rules[0].target_column, "name");
+
+    assert!(delete_mapping_profile_from_db(&pool, "crm").await.unwrap());
+    assert!(!delete_mapping_profile_from_db(&pool, "crm").await.unwrap());
+    assert!(load_mapping_profiles_from_db(&pool).await.is_empty());
+}
@@ -0,0 +1,1177 @@
+// =====================================================
+// BACKUP MODULE
+// Streaming logical backup and resumable restore with optional compression
+// =====================================================
+
+use crate::clickhouse;
+use crate::db_types::{AppState, DatabaseType, ResultStreamEvent};
+use crate::mssql;
+use crate::mysql;
+use crate::postgres;
+use crate::sqlite;
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::mpsc;
+
+use super::result_cursor::spawn_query_stream;
+use super::script_runner::{connect_script_target, ScriptSplitter, ScriptStatement};
+use super::sql_utils::{
+    build_batched_insert_statement, ensure_sql_terminated, qualified_table_name,
+    quote_identifier_mssql, quote_identifier_mysql,
+};
+
+// =====================================================
+// CONSTANTS
+// =====================================================
+
+pub const BACKUP_PROGRESS_EVENT: &str = "backup-progress";
+pub const RESTORE_PROGRESS_EVENT: &str = "restore-progress";
+const DEFAULT_INSERT_BATCH_ROWS: usize = 500;
+/// SQL Server rejects table value constructors with more than 1000 rows
+const MSSQL_MAX_INSERT_ROWS: usize = 1000;
+const BACKUP_STREAM_CHANNEL_CAPACITY: usize = 4;
+const PROGRESS_INTERVAL_ROWS: u64 = 10_000;
+const RESTORE_PROGRESS_INTERVAL: usize = 100;
+const FILE_COPY_CHUNK_BYTES: usize = 64 * 1024;
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+const SQLITE_MAGIC: &[u8] = b"SQLite format 3\0";
+
+// =====================================================
+// GLOBAL STATE
+// =====================================================
+
+/// Cancel flags of running backup/restore jobs, keyed by job id.
+static BACKUP_JOBS: LazyLock<Mutex<HashMap<String, Arc<AtomicBool>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+// =====================================================
+// STRUCTS
+// =====================================================
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BackupCompression {
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl BackupCompression {
+    /// Picks the format from the file extension (`.gz`, `.zst`).
+    pub fn from_path(path: &str) -> Self {
+        let lower = path.to_lowercase();
+        if lower.ends_with(".gz") || lower.ends_with(".gzip") {
+            BackupCompression::Gzip
+        } else if lower.ends_with(".zst") || lower.ends_with(".zstd") {
+            BackupCompression::Zstd
+        } else {
+            BackupCompression::None
+        }
+    }
+
+    /// Recognizes a compressed file by its leading magic bytes.
+    pub fn detect(header: &[u8]) -> Self {
+        if header.starts_with(&GZIP_MAGIC) {
+            BackupCompression::Gzip
+        } else if header.starts_with(&ZSTD_MAGIC) {
+            BackupCompression::Zstd
+        } else {
+            BackupCompression::None
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BackupFormat {
+    /// Replayable SQL script
+    Sql,
+    /// Consistent copy of the SQLite database file
+    Native,
+}
+
+impl BackupFormat {
+    /// SQLite database file extensions select a native copy; anything else is a SQL dump.
+    pub fn from_path(path: &str) -> Self {
+        let lower = path.to_lowercase();
+        let stem = lower
+            .strip_suffix(".gz")
+            .or_else(|| lower.strip_suffix(".zst"))
+            .unwrap_or(&lower);
+        if [".db", ".db3", ".sqlite", ".sqlite3"]
+            .iter()
+            .any(|ext| stem.ends_with(ext))
+        {
+            BackupFormat::Native
+        } else {
+            BackupFormat::Sql
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupOptions {
+    /// Defaults to the format implied by the file extension
+    pub compression: Option<BackupCompression>,
+    /// Defaults to the format implied by the file extension; `native` is SQLite only
+    pub format: Option<BackupFormat>,
+    pub insert_batch_size: Option<usize>,
+    /// Lets the caller cancel the job and match its progress events
+    pub job_id: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreOptions {
+    /// Continue after the last committed statement of an earlier attempt
+    pub resume: Option<bool>,
+    pub job_id: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupProgress {
+    pub job_id: String,
+    pub table: Option<String>,
+    pub tables_done: usize,
+    pub tables_total: usize,
+    pub rows_written: u64,
+    pub bytes_written: u64,
+    pub done: bool,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreProgress {
+    pub job_id: String,
+    pub statements_executed: usize,
+    pub next_statement: usize,
+    pub bytes_read: u64,
+    pub bytes_total: u64,
+    pub done: bool,
+}
+
+/// Sidecar written next to a backup while it is being restored, so a failed or
+/// cancelled restore can continue where it stopped.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreCheckpoint {
+    pub file_size: u64,
+    pub modified_at: Option<i64>,
+    /// Index of the first statement that has not been committed
+    pub next_statement: usize,
+}
+
+// =====================================================
+// JOB REGISTRY
+// =====================================================
+
+/// Keeps a job's cancel flag registered until dropped.
+struct BackupJob {
+    id: String,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl BackupJob {
+    fn register(job_id: Option<String>) -> Result<Self, String> {
+        let id = job_id
+            .filter(|id| !id.trim().is_empty())
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let mut guard = BACKUP_JOBS.lock().unwrap_or_else(|e| e.into_inner());
+        if guard.contains_key(&id) {
+            return Err(format!("Job {} is already running", id));
+        }
+        guard.insert(id.clone(), cancelled.clone());
+        Ok(BackupJob { id, cancelled })
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for BackupJob {
+    fn drop(&mut self) {
+        let mut guard = BACKUP_JOBS.lock().unwrap_or_else(|e| e.into_inner());
+        guard.remove(&self.id);
+    }
+}
+
+// =====================================================
+// BACKUP WRITER
+// =====================================================
+
+enum BackupSink {
+    Plain(BufWriter<File>),
+    Gzip(GzEncoder<BufWriter<File>>),
+    Zstd(zstd::Encoder<'static, BufWriter<File>>),
+}
+
+/// Appends SQL to a backup file through the chosen compressor.
+pub struct BackupWriter {
+    sink: BackupSink,
+    db_type: DatabaseType,
+    bytes_written: u64,
+}
+
+impl BackupWriter {
+    pub fn create(
+        file_path: &str,
+        compression: BackupCompression,
+        db_type: DatabaseType,
+    ) -> Result<Self, String> {
+        let target = Path::new(file_path);
+        if let Some(parent) = target.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create parent directory: {}", e))?;
+            }
+        }
+        let file = BufWriter::new(
+            File::create(target).map_err(|e| format!("Failed to create backup file: {}", e))?,
+        );
+        let sink = match compression {
+            BackupCompression::None => BackupSink::Plain(file),
+            BackupCompression::Gzip => {
+                BackupSink::Gzip(GzEncoder::new(file, flate2::Compression::default()))
+            }
+            BackupCompression::Zstd => BackupSink::Zstd(
+                zstd::Encoder::new(file, 0)
+                    .map_err(|e| format!("Failed to start zstd stream: {}", e))?,
+            ),
+        };
+        Ok(BackupWriter {
+            sink,
+            db_type,
+            bytes_written: 0,
+        })
+    }
+
+    pub fn write_text(&mut self, text: &str) -> Result<(), String> {
+        self.write_bytes(text.as_bytes())
+    }
+
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), String> {
+        let result = match &mut self.sink {
+            BackupSink::Plain(w) => w.write_all(bytes),
+            BackupSink::Gzip(w) => w.write_all(bytes),
+            BackupSink::Zstd(w) => w.write_all(bytes),
+        };
+        result.map_err(|e| format!("Failed to write backup: {}", e))?;
+        self.bytes_written += bytes.len() as u64;
+        Ok(())
+    }
+
+    /// Writes one terminated statement. MSSQL statements also get a `GO` line,
+    /// since restore splits MSSQL scripts on batches.
+    pub fn write_statement(&mut self, statement: &str) -> Result<(), String> {
+        let statement = ensure_sql_terminated(statement);
+        if statement.is_empty() {
+            return Ok(());
+        }
+        self.write_text(&statement)?;
+        if self.db_type == DatabaseType::MSSQL {
+            self.write_text("\nGO\n")
+        } else {
+            self.write_text("\n")
+        }
+    }
+
+    /// Uncompressed bytes written so far.
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+
+    pub fn finish(self) -> Result<(), String> {
+        let file = match self.sink {
+            BackupSink::Plain(w) => Ok(w),
+            BackupSink::Gzip(w) => w.finish(),
+            BackupSink::Zstd(w) => w.finish(),
+        };
+        file.and_then(|mut w| w.flush())
+            .map_err(|e| format!("Failed to finish backup file: {}", e))
+    }
+}
+
+// =====================================================
+// BACKUP READER
+// =====================================================
+
+/// Counts bytes pulled from the underlying (possibly compressed) file.
+struct CountingReader<R> {
+    inner: R,
+    count: Arc<AtomicU64>,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.count.fetch_add(n as u64, Ordering::Relaxed);
+        Ok(n)
+    }
+}
+
+/// Opens a backup for line-by-line reading, decompressing by magic bytes.
+/// The counter tracks bytes read from disk, for progress against the file size.
+pub fn open_backup_reader(
+    file_path: &str,
+) -> Result<(Box<dyn BufRead + Send>, Arc<AtomicU64>), String> {
+    let mut file =
+        File::open(file_path).map_err(|e| format!("Failed to open backup file: {}", e))?;
+    let mut header = [0u8; 4];
+    let read = file
+        .read(&mut header)
+        .map_err(|e| format!("Failed to read backup file: {}", e))?;
+    file.seek(SeekFrom::Start(0))
+        .map_err(|e| format!("Failed to read backup file: {}", e))?;
+
+    let count = Arc::new(AtomicU64::new(0));
+    let counting = CountingReader {
+        inner: file,
+        count: count.clone(),
+    };
+    let reader: Box<dyn BufRead + Send> = match BackupCompression::detect(&header[..read]) {
+        BackupCompression::Gzip => Box::new(BufReader::new(MultiGzDecoder::new(counting))),
+        BackupCompression::Zstd => Box::new(BufReader::new(
+            zstd::Decoder::new(counting)
+                .map_err(|e| format!("Failed to start zstd stream: {}", e))?,
+        )),
+        BackupCompression::None => Box::new(BufReader::new(counting)),
+    };
+    Ok((reader, count))
+}
+
+// =====================================================
+// RESTORE CHECKPOINTS
+// =====================================================
+
+pub fn checkpoint_path(file_path: &str) -> PathBuf {
+    PathBuf::from(format!("{}.restore-checkpoint.json", file_path))
+}
+
+fn file_fingerprint(file_path: &str) -> Result<(u64, Option<i64>), String> {
+    let metadata =
+        fs::metadata(file_path).map_err(|e| format!("Failed to read backup file: {}", e))?;
+    let modified_at = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs() as i64);
+    Ok((metadata.len(), modified_at))
+}
+
+fn load_checkpoint(file_path: &str) -> Result<Option<RestoreCheckpoint>, String> {
+    let path = checkpoint_path(file_path);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read restore checkpoint: {}", e))?;
+    let checkpoint: RestoreCheckpoint =
+        serde_json::from_str(&content).map_err(|e| format!("Invalid restore checkpoint: {}", e))?;
+
+    let (file_size, modified_at) = file_fingerprint(file_path)?;
+    if checkpoint.file_size != file_size || checkpoint.modified_at != modified_at {
+        return Err("Backup file changed since the interrupted restore; cannot resume".into());
+    }
+    Ok(Some(checkpoint))
+}
+
+fn save_checkpoint(file_path: &str, next_statement: usize) -> Result<(), String> {
+    let (file_size, modified_at) = file_fingerprint(file_path)?;
+    let checkpoint = RestoreCheckpoint {
+        file_size,
+        modified_at,
+        next_statement,
+    };
+    let content = serde_json::to_string(&checkpoint).map_err(|e| e.to_string())?;
+    fs::write(checkpoint_path(file_path), content)
+        .map_err(|e| format!("Failed to write restore checkpoint: {}", e))
+}
+
+/// Runs blocking file work off the async runtime.
+async fn run_blocking<T, F>(work: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, String> + Send + 'static,
+{
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| format!("Background file task failed: {}", e))?
+}
+
+async fn persist_checkpoint(file_path: &str, next_statement: usize) -> Result<(), String> {
+    let file_path = file_path.to_string();
+    run_blocking(move || save_checkpoint(&file_path, next_statement)).await
+}
+
+/// Statements that only set up session state. They are replayed while skipping
+/// already-restored statements, so a resumed restore targets the same schema.
+pub fn is_session_setup_statement(sql: &str) -> bool {
+    let head = sql
+        .trim_start()
+        .split(|c: char| c.is_whitespace() || c == ';')
+        .next()
+        .unwrap_or("")
+        .to_uppercase();
+    matches!(head.as_str(), "USE" | "SET")
+}
+
+// =====================================================
+// BACKUP
+// =====================================================
+
+async fn list_backup_tables(
+    app_state: &AppState,
+    db_type: &DatabaseType,
+    database: &str,
+) -> Result<Vec<String>, String> {
+    match db_type {
+        DatabaseType::PostgreSQL => {
+            let pool = {
+                let guard = app_state.postgres_pool.lock().await;
+                guard
+                    .clone()
+                    .ok_or("No PostgreSQL connection established")?
+            };
+            postgres::get_tables(&pool, database).await
+        }
+        DatabaseType::MySQL => {
+            let pool = {
+                let guard = app_state.mysql_pool.lock().await;
+                guard.clone().ok_or("No MySQL connection established")?
+            };
+            mysql::get_tables(&pool, database).await
+        }
+        DatabaseType::MSSQL => {
+            let pool = {
+                let guard = app_state.mssql_pool.lock().await;
+                guard.clone().ok_or("No MSSQL connection established")?
+            };
+            mssql::get_tables(&pool, database, "dbo").await
+        }
+        DatabaseType::ClickHouse => {
+            let config = {
+                let guard = app_state.clickhouse_config.lock().await;
+                guard
+                    .clone()
+                    .ok_or("No ClickHouse connection established")?
+            };
+            clickhouse::get_tables(&config, database).await
+        }
+        DatabaseType::SQLite => {
+            let pool = {
+                let guard = app_state.sqlite_pool.lock().await;
+                guard.clone().ok_or("No SQLite connection established")?
+            };
+            sqlite::get_tables(&pool).await
+        }
+        DatabaseType::Disconnected => Err("No connection established".into()),
+    }
+}
+
+async fn backup_table_ddl(
+    app_state: &AppState,
+    db_type: &DatabaseType,
+    database: &str,
+    table: &str,
+) -> Result<String, String> {
+    match db_type {
+        DatabaseType::PostgreSQL => {
+            let pool = {
+                let guard = app_state.postgres_pool.lock().await;
+                guard
+                    .clone()
+                    .ok_or("No PostgreSQL connection established")?
+            };
+            postgres::get_table_ddl(&pool, database, table).await
+        }
+        DatabaseType::MySQL => {
+            let pool = {
+                let guard = app_state.mysql_pool.lock().await;
+                guard.clone().ok_or("No MySQL connection established")?
+            };
+            mysql::get_table_ddl(&pool, database, table).await
+        }
+        DatabaseType::MSSQL => {
+            let pool = {
+                let guard = app_state.mssql_pool.lock().await;
+                guard.clone().ok_or("No MSSQL connection established")?
+            };
+            Ok(mssql::get_table_ddl(&pool, database, "dbo", table)
+                .await
+                .unwrap_or_default())
+        }
+        DatabaseType::ClickHouse => {
+            let config = {
+                let guard = app_state.clickhouse_config.lock().await;
+                guard
+                    .clone()
+                    .ok_or("No ClickHouse connection established")?
+            };
+            clickhouse::get_table_ddl(&config, database, table).await
+        }
+        DatabaseType::SQLite => {
+            let pool = {
+                let guard = app_state.sqlite_pool.lock().await;
+                guard.clone().ok_or("No SQLite connection established")?
+            };
+            sqlite::get_table_ddl(&pool, database, table).await
+        }
+        DatabaseType::Disconnected => Err("No connection established".into()),
+    }
+}
+
+struct BackupRun<'a> {
+    app: &'a AppHandle,
+    job: &'a BackupJob,
+    writer: BackupWriter,
+    progress: BackupProgress,
+    last_reported_rows: u64,
+}
+
+impl BackupRun<'_> {
+    fn emit_progress(&mut self) {
+        self.progress.bytes_written = self.writer.bytes_written();
+        self.last_reported_rows = self.progress.rows_written;
+        let _ = self.app.emit(BACKUP_PROGRESS_EVENT, self.progress.clone());
+    }
+
+    fn write_rows(
+        &mut self,
+        database: &str,
+        table: &str,
+        columns: &[String],
+        rows: &[Vec<serde_json::Value>],
+    ) -> Result<(), String> {
+        let db_type = self.writer.db_type.clone();
+        if let Some(statement) =
+            build_batched_insert_statement(&db_type, database, table, columns, rows)
+        {
+            self.writer.write_statement(&statement)?;
+            self.progress.rows_written += rows.len() as u64;
+        }
+        if self.progress.rows_written - self.last_reported_rows >= PROGRESS_INTERVAL_ROWS {
+            self.emit_progress();
+        }
+        Ok(())
+    }
+
+    /// Streams the rows of `query` and writes them as batched INSERTs into
+    /// `database`.`table`.
+    async fn write_table_data(
+        &mut self,
+        app_state: &AppState,
+        query: String,
+        database: &str,
+        table: &str,
+        batch_size: usize,
+    ) -> Result<(), String> {
+        let db_type = self.writer.db_type.clone();
+        let query_id = format!("tactilesql-backup-{}", uuid::Uuid::new_v4());
+        let (tx, mut rx) = mpsc::channel(BACKUP_STREAM_CHANNEL_CAPACITY);
+        spawn_query_stream(app_state, &db_type, query, &query_id, tx).await?;
+
+        let mut columns = Vec::new();
+        let mut pending: Vec<Vec<serde_json::Value>> = Vec::with_capacity(batch_size);
+        while let Some(event) = rx.recv().await {
+            if self.job.is_cancelled() {
+                // Dropping the receiver stops the engine stream
+                return Err("Backup cancelled".into());
+            }
+            match event {
+                ResultStreamEvent::Columns(cols) => columns = cols,
+                ResultStreamEvent::Rows(rows) => {
+                    pending.extend(rows);
+                    while pending.len() >= batch_size {
+                        let rest = pending.split_off(batch_size);
+                        let batch = std::mem::replace(&mut pending, rest);
+                        self.write_rows(database, table, &columns, &batch)?;
+                    }
+                }
+                ResultStreamEvent::Error(e) => {
+                    return Err(format!("Failed to read table {}: {}", table, e))
+                }
+            }
+        }
+        self.write_rows(database, table, &columns, &pending)
+    }
+}
+
+/// Writes an MSSQL table as schema-qualified DDL plus data. Identity values are
+/// kept with `IDENTITY_INSERT`, and server-generated columns are left out of the data.
+async fn write_mssql_table(
+    run: &mut BackupRun<'_>,
+    app_state: &AppState,
+    database: &str,
+    table: &str,
+    include_data: bool,
+    batch_size: usize,
+    created_schemas: &mut HashSet<String>,
+) -> Result<(), String> {
+    let pool = {
+        let guard = app_state.mssql_pool.lock().await;
+        guard.clone().ok_or("No MSSQL connection established")?
+    };
+    let (schema, table_name) = mssql::split_table_name("dbo", table);
+
+    if !schema.eq_ignore_ascii_case("dbo") && created_schemas.insert(schema.to_string()) {
+        run.writer.write_statement(&format!(
+            "IF SCHEMA_ID(N'{}') IS NULL EXEC(N'CREATE SCHEMA {}')",
+            schema.replace('\'', "''"),
+            quote_identifier_mssql(schema).replace('\'', "''")
+        ))?;
+    }
+    let ddl = mssql::get_table_ddl(&pool, database, schema, table_name).await?;
+    run.writer.write_statement(&ddl)?;
+    if !include_data {
+        return Ok(());
+    }
+
+    let (columns, has_identity) =
+        mssql::get_insertable_columns(&pool, database, schema, table_name).await?;
+    if columns.is_empty() {
+        return Ok(());
+    }
+    let target = qualified_table_name(&DatabaseType::MSSQL, schema, table_name);
+    let query = format!(
+        "SELECT {} FROM {}.{}",
+        columns
+            .iter()
+            .map(|c| quote_identifier_mssql(c))
+            .collect::<Vec<_>>()
+            .join(", "),
+        quote_identifier_mssql(database),
+        target
+    );
+
+    if has_identity {
+        run.writer
+            .write_statement(&format!("SET IDENTITY_INSERT {} ON", target))?;
+    }
+    run.write_table_data(app_state, query, schema, table_name, batch_size)
+        .await?;
+    if has_identity {
+        run.writer
+            .write_statement(&format!("SET IDENTITY_INSERT {} OFF", target))?;
+    }
+    Ok(())
+}
+
+/// Copies `source` into a new backup file through the chosen compressor.
+fn compress_file(
+    source: &str,
+    file_path: &str,
+    compression: BackupCompression,
+    job: &BackupJob,
+) -> Result<u64, String> {
+    let mut input =
+        File::open(source).map_err(|e| format!("Failed to read database snapshot: {}", e))?;
+    let mut writer = BackupWriter::create(file_path, compression, DatabaseType::SQLite)?;
+    let mut buffer = vec![0u8; FILE_COPY_CHUNK_BYTES];
+    loop {
+        if job.is_cancelled() {
+            return Err("Backup cancelled".into());
+        }
+        let read = input
+            .read(&mut buffer)
+            .map_err(|e| format!("Failed to read database snapshot: {}", e))?;
+        if read == 0 {
+            break;
+        }
+        writer.write_bytes(&buffer[..read])?;
+    }
+    let bytes = writer.bytes_written();
+    writer.finish()?;
+    Ok(bytes)
+}
+
+/// Backs up a SQLite database as a consistent copy of the database file.
+async fn run_sqlite_file_backup(
+    app: &AppHandle,
+    app_state: &AppState,
+    job: &BackupJob,
+    file_path: &str,
+    compression: BackupCompression,
+) -> Result<BackupProgress, String> {
+    let pool = {
+        let guard = app_state.sqlite_pool.lock().await;
+        guard.clone().ok_or("No SQLite connection established")?
+    };
+    let tables_total = sqlite::get_tables(&pool).await?.len();
+    if let Some(parent) = Path::new(file_path).parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create parent directory: {}", e))?;
+        }
+    }
+
+    let bytes_written = if compression == BackupCompression::None {
+        sqlite::backup_to_file(&pool, file_path).await?;
+        fs::metadata(file_path).map(|m| m.len()).unwrap_or(0)
+    } else {
+        let snapshot = format!("{}.snapshot", file_path);
+        sqlite::backup_to_file(&pool, &snapshot).await?;
+        let result = compress_file(&snapshot, file_path, compression, job);
+        let _ = fs::remove_file(&snapshot);
+        result?
+    };
+
+    let progress = BackupProgress {
+        job_id: job.id.clone(),
+        table: None,
+        tables_done: tables_total,
+        tables_total,
+        rows_written: 0,
+        bytes_written,
+        done: true,
+    };
+    let _ = app.emit(BACKUP_PROGRESS_EVENT, progress.clone());
+    Ok(progress)
+}
+
+async fn run_backup(
+    app: &AppHandle,
+    app_state: &AppState,
+    job: &BackupJob,
+    database: &str,
+    file_path: &str,
+    include_data: bool,
+    options: &BackupOptions,
+) -> Result<BackupProgress, String> {
+    let db_type = {
+        let guard = app_state.active_db_type.lock().await;
+        guard.clone()
+    };
+    let compression = options
+        .compression
+        .unwrap_or_else(|| BackupCompression::from_path(file_path));
+    match options
+        .format
+        .unwrap_or_else(|| BackupFormat::from_path(file_path))
+    {
+        BackupFormat::Native if db_type == DatabaseType::SQLite => {
+            return run_sqlite_file_backup(app, app_state, job, file_path, compression).await;
+        }
+        BackupFormat::Native => {
+            return Err("Native file backups are only supported for SQLite".into());
+        }
+        BackupFormat::Sql => {}
+    }
+    let mut batch_size = options
+        .insert_batch_size
+        .unwrap_or(DEFAULT_INSERT_BATCH_ROWS)
+        .max(1);
+    if db_type == DatabaseType::MSSQL {
+        batch_size = batch_size.min(MSSQL_MAX_INSERT_ROWS);
+    }
+
+    let tables = list_backup_tables(app_state, &db_type, database).await?;
+    let mut run = BackupRun {
+        app,
+        job,
+        writer: BackupWriter::create(file_path, compression, db_type.clone())?,
+        progress: BackupProgress {
+            job_id: job.id.clone(),
+            table: None,
+            tables_done: 0,
+            tables_total: tables.len(),
+            rows_written: 0,
+            bytes_written: 0,
+            done: false,
+        },
+        last_reported_rows: 0,
+    };
+
+    run.writer.write_text("-- TactileSQL Backup\n")?;
+    run.writer
+        .write_text(&format!("-- Database/Schema: {}\n", database))?;
+    run.writer.write_text(&format!(
+        "-- Generated at: {}\n\n",
+        chrono::Utc::now().to_rfc3339()
+    ))?;
+    match db_type {
+        DatabaseType::MySQL => {
+            run.writer
+                .write_statement(&format!("USE {}", quote_identifier_mysql(database)))?;
+            run.writer.write_text("\n")?;
+        }
+        DatabaseType::MSSQL => {
+            run.writer
+                .write_statement(&format!("USE {}", quote_identifier_mssql(database)))?;
+            run.writer.write_text("\n")?;
+        }
+        _ => {}
+    }
+
+    let mut created_schemas = HashSet::new();
+    for table in &tables {
+        if job.is_cancelled() {
+            return Err("Backup cancelled".into());
+        }
+        run.progress.table = Some(table.clone());
+        run.emit_progress();
+
+        run.writer.write_text(&format!("-- Table: {}\n", table))?;
+        if db_type == DatabaseType::MSSQL {
+            write_mssql_table(
+                &mut run,
+                app_state,
+                database,
+                table,
+                include_data,
+                batch_size,
+                &mut created_schemas,
+            )
+            .await?;
+        } else {
+            let ddl = backup_table_ddl(app_state, &db_type, database, table).await?;
+            run.writer.write_statement(&ddl)?;
+            if include_data {
+                let query = format!(
+                    "SELECT * FROM {}",
+                    qualified_table_name(&db_type, database, table)
+                );
+                run.write_table_data(app_state, query, database, table, batch_size)
+                    .await?;
+            }
+        }
+        run.writer.write_text("\n")?;
+        run.progress.tables_done += 1;
+    }
+
+    run.progress.table = None;
+    run.progress.done = true;
+    run.progress.bytes_written = run.writer.bytes_written();
+    let progress = run.progress.clone();
+    run.writer.finish()?;
+    let _ = app.emit(BACKUP_PROGRESS_EVENT, progress.clone());
+    Ok(progress)
+}
+
+// =====================================================
+// RESTORE
+// =====================================================
+
+/// True when the (decompressed) backup starts with the SQLite file header.
+pub fn is_sqlite_database_file(header: &[u8]) -> bool {
+    header.starts_with(SQLITE_MAGIC)
+}
+
+/// Restores a native SQLite backup over the connected SQLite database.
+async fn run_sqlite_file_restore(
+    app_state: &AppState,
+    file_path: &str,
+    mut reader: Box<dyn BufRead + Send>,
+) -> Result<(), String> {
+    let db_type = {
+        let guard = app_state.active_db_type.lock().await;
+        guard.clone()
+    };
+    if db_type != DatabaseType::SQLite {
+        return Err("SQLite database files can only be restored into a SQLite connection".into());
+    }
+    let pool = {
+        let guard = app_state.sqlite_pool.lock().await;
+        guard.clone().ok_or("No SQLite connection established")?
+    };
+
+    // The backup API needs a database file, so compressed backups are unpacked first
+    let mut header = [0u8; 16];
+    let stored_uncompressed = File::open(file_path)
+        .and_then(|mut f| f.read_exact(&mut header))
+        .is_ok()
+        && is_sqlite_database_file(&header);
+    let (source, temp) = if stored_uncompressed {
+        (file_path.to_string(), None)
+    } else {
+        let temp = std::env::temp_dir()
+            .join(format!("tactilesql-restore-{}.db", uuid::Uuid::new_v4()))
+            .to_string_lossy()
+            .to_string();
+        let output_path = temp.clone();
+        run_blocking(move || {
+            let mut output = File::create(&output_path)
+                .map_err(|e| format!("Failed to unpack backup: {}", e))?;
+            std::io::copy(&mut reader, &mut output)
+                .map_err(|e| format!("Failed to unpack backup: {}", e))?;
+            Ok(())
+        })
+        .await?;
+        (temp.clone(), Some(temp))
+    };
+
+    let result = async {
+        let conn = pool
+            .acquire()
+            .await
+            .map_err(|e| format!("Failed to acquire SQLite connection: {}", e))?;
+        sqlite::restore_from_file(conn, &source).await
+    }
+    .await;
+    if let Some(temp) = temp {
+        let _ = fs::remove_file(temp);
+    }
+    result
+}
+
+type StatementBatch = Result<Vec<ScriptStatement>, String>;
+
+/// Reads and splits the backup on a blocking thread, handing statements to the
+/// restore loop as they are cut out. Dropping the receiver stops the reader.
+fn spawn_statement_reader(
+    mut reader: Box<dyn BufRead + Send>,
+    db_type: DatabaseType,
+) -> (mpsc::Receiver<StatementBatch>, tokio::task::JoinHandle<()>) {
+    let (tx, rx) = mpsc::channel(BACKUP_STREAM_CHANNEL_CAPACITY);
+    let handle = tokio::task::spawn_blocking(move || {
+        let mut splitter = ScriptSplitter::new(&db_type);
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let statements = match reader.read_line(&mut line) {
+                Ok(0) => {
+                    let _ = tx.blocking_send(Ok(splitter.finish()));
+                    return;
+                }
+                Ok(_) => splitter.push_line(&line),
+                Err(e) => {
+                    let _ = tx.blocking_send(Err(format!("Failed to read backup file: {}", e)));
+                    return;
+                }
+            };
+            if !statements.is_empty() && tx.blocking_send(Ok(statements)).is_err() {
+                return;
+            }
+        }
+    });
+    (rx, handle)
+}
+
+async fn run_restore(
+    app: &AppHandle,
+    app_state: &AppState,
+    job: &BackupJob,
+    file_path: &str,
+    (reader, bytes_read): (Box<dyn BufRead + Send>, Arc<AtomicU64>),
+    resume: bool,
+) -> Result<RestoreProgress, String> {
+    let db_type = {
+        let guard = app_state.active_db_type.lock().await;
+        guard.clone()
+    };
+    let (resume_from, bytes_total) = {
+        let file_path = file_path.to_string();
+        run_blocking(move || {
+            let resume_from = if resume {
+                load_checkpoint(&file_path)?
+                    .map(|c| c.next_statement)
+                    .unwrap_or(0)
+            } else {
+                0
+            };
+            let (bytes_total, _) = file_fingerprint(&file_path)?;
+            Ok((resume_from, bytes_total))
+        })
+        .await?
+    };
+
+    let mut target = connect_script_target(app_state, &db_type).await?;
+    let mut progress = RestoreProgress {
+        job_id: job.id.clone(),
+        statements_executed: 0,
+        next_statement: resume_from,
+        bytes_read: 0,
+        bytes_total,
+        done: false,
+    };
+
+    let (mut batches, read_task) = spawn_statement_reader(reader, db_type.clone());
+    while let Some(batch) = batches.recv().await {
+        for statement in batch? {
+            if job.is_cancelled() {
+                persist_checkpoint(file_path, progress.next_statement).await?;
+                return Err(format!(
+                    "Restore cancelled before statement {}; resume to continue",
+                    statement.index + 1
+                ));
+            }
+            let skip = statement.index < resume_from;
+            if skip && !is_session_setup_statement(&statement.sql) {
+                continue;
+            }
+
+            if let Err(e) = target.execute(&statement.sql, None).await {
+                persist_checkpoint(file_path, progress.next_statement).await?;
+                return Err(format!(
+                    "Restore failed at statement {} (line {}): {}. Resume to continue from it.",
+                    statement.index + 1,
+                    statement.line,
+                    e
+                ));
+            }
+            if skip {
+                continue;
+            }
+
+            progress.statements_executed += 1;
+            progress.next_statement = statement.index + 1;
+            // Every committed statement is recorded, so a crash replays none
+            persist_checkpoint(file_path, progress.next_statement).await?;
+            if progress
+                .statements_executed
+                .is_multiple_of(RESTORE_PROGRESS_INTERVAL)
+            {
+                progress.bytes_read = bytes_read.load(Ordering::Relaxed);
+                let _ = app.emit(RESTORE_PROGRESS_EVENT, progress.clone());
+            }
+        }
+    }
+    read_task
+        .await
+        .map_err(|e| format!("Failed to read backup file: {}", e))?;
+
+    let checkpoint = checkpoint_path(file_path);
+    run_blocking(move || {
+        let _ = fs::remove_file(checkpoint);
+        Ok(())
+    })
+    .await?;
+    progress.bytes_read = bytes_total;
+    progress.done = true;
+    let _ = app.emit(RESTORE_PROGRESS_EVENT, progress.clone());
+    Ok(progress)
+}
+
+/// Runs a backup as a cancellable job, removing the partial file on failure.
+/// Shared by the backup command and scheduled backup tasks.
+pub(crate) async fn backup_to_file(
+    app: &AppHandle,
+    app_state: &AppState,
+    database: &str,
+    file_path: &str,
+    include_data: bool,
+    options: BackupOptions,
+) -> Result<BackupProgress, String> {
+    let job = BackupJob::register(options.job_id.clone())?;
+    let result = run_backup(
+        app,
+        app_state,
+        &job,
+        database,
+        file_path,
+        include_data,
+        &options,
+    )
+    .await;
+    if result.is_err() {
+        // A partial dump is not restorable, so do not leave it behind
+        let _ = fs::remove_file(file_path);
+    }
+    result
+}
+
+// =====================================================
+// TAURI COMMANDS
+// =====================================================
+
+#[tauri::command]
+pub async fn backup_database(
+    app: AppHandle,
+    app_state: State<'_, AppState>,
+    database: String,
+    file_path: String,
+    include_data: Option<bool>,
+    options: Option<BackupOptions>,
+    session_id: Option<String>,
+) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
+    let progress = backup_to_file(
+        &app,
+        &app_state,
+        &database,
+        &file_path,
+        include_data.unwrap_or(true),
+        options.unwrap_or_default(),
+    )
+    .await?;
+    Ok(format!(
+        "Backup completed and saved to {} ({} tables, {} rows)",
+        file_path, progress.tables_done, progress.rows_written
+    ))
+}
+
+#[tauri::command]
+pub async fn restore_database(
+    app: AppHandle,
+    app_state: State<'_, AppState>,
+    file_path: String,
+    options: Option<RestoreOptions>,
+    session_id: Option<String>,
+) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
+    let options = options.unwrap_or_default();
+    let job = BackupJob::register(options.job_id.clone())?;
+
+    let (reader, is_database_file) = {
+        let file_path = file_path.clone();
+        run_blocking(move || {
+            let mut reader = open_backup_reader(&file_path)?;
+            let header = reader
+                .0
+                .fill_buf()
+                .map_err(|e| format!("Failed to read backup file: {}", e))?;
+            let is_database_file = is_sqlite_database_file(header);
+            Ok((reader, is_database_file))
+        })
+        .await?
+    };
+    if is_database_file {
+        run_sqlite_file_restore(&app_state, &file_path, reader.0).await?;
+        return Ok(format!(
+            "Restore completed from {} (SQLite database file)",
+            file_path
+        ));
+    }
+
+    let progress = run_restore(
+        &app,
+        &app_state,
+        &job,
+        &file_path,
+        reader,
+        options.resume.unwrap_or(false),
+    )
+    .await?;
+    if progress.statements_executed == 0 && progress.next_statement == 0 {
+        return Err("SQL file is empty".to_string());
+    }
+    Ok(format!(
+        "Restore completed from {} ({} statements)",
+        file_path, progress.statements_executed
+    ))
+}
+
+#[tauri::command]
+pub async fn cancel_backup_job(job_id: String) -> Result<(), String> {
+    let guard = BACKUP_JOBS.lock().unwrap_or_else(|e| e.into_inner());
+    let flag = guard
+        .get(&job_id)
+        .ok_or_else(|| format!("No running backup job: {}", job_id))?;
+    flag.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
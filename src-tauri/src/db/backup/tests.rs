@@ -0,0 +1,109 @@
+use super::*;
+
+fn temp_path(name: &str) -> String {
+    std::env::temp_dir()
+        .join(format!(
+            "tactilesql-backup-{}-{}",
+            uuid::Uuid::new_v4(),
+            name
+        ))
+        .to_string_lossy()
+        .to_string()
+}
+
+fn read_back(path: &str) -> String {
+    let (mut reader, _) = open_backup_reader(path).unwrap();
+    let mut content = String::new();
+    reader.read_to_string(&mut content).unwrap();
+    content
+}
+
+#[test]
+fn test_compression_from_path_and_magic() {
+    assert_eq!(
+        BackupCompression::from_path("a.sql"),
+        BackupCompression::None
+    );
+    assert_eq!(
+        BackupCompression::from_path("a.SQL.GZ"),
+        BackupCompression::Gzip
+    );
+    assert_eq!(
+        BackupCompression::from_path("a.sql.zst"),
+        BackupCompression::Zstd
+    );
+    assert_eq!(
+        BackupCompression::detect(&[0x1f, 0x8b, 0x08]),
+        BackupCompression::Gzip
+    );
+    assert_eq!(
+        BackupCompression::detect(&[0x28, 0xb5, 0x2f, 0xfd]),
+        BackupCompression::Zstd
+    );
+    assert_eq!(BackupCompression::detect(b"-- T"), BackupCompression::None);
+    assert_eq!(BackupCompression::detect(&[]), BackupCompression::None);
+}
+
+#[test]
+fn test_writer_round_trips_every_compression() {
+    for compression in [
+        BackupCompression::None,
+        BackupCompression::Gzip,
+        BackupCompression::Zstd,
+    ] {
+        let path = temp_path("roundtrip.sql");
+        let mut writer = BackupWriter::create(&path, compression, DatabaseType::MySQL).unwrap();
+        writer.write_text("-- header\n").unwrap();
+        writer.write_statement("INSERT INTO t VALUES (1)").unwrap();
+        assert_eq!(writer.bytes_written(), 36);
+        writer.finish().unwrap();
+
+        assert_eq!(read_back(&path), "-- header\nINSERT INTO t VALUES (1);\n");
+        let _ = fs::remove_file(&path);
+    }
+}
+
+#[test]
+fn test_mssql_statements_end_with_go() {
+    let path = temp_path("mssql.sql");
+    let mut writer =
+        BackupWriter::create(&path, BackupCompression::None, DatabaseType::MSSQL).unwrap();
+    writer.write_statement("SELECT 1").unwrap();
+    writer.finish().unwrap();
+    assert_eq!(read_back(&path), "SELECT 1;\nGO\n");
+    let _ = fs::remove_file(&path);
+}
+
+#[test]
+fn test_checkpoint_is_rejected_after_file_changes() {
+    let path = temp_path("checkpoint.sql");
+    fs::write(&path, "SELECT 1;\nSELECT 2;\n").unwrap();
+    save_checkpoint(&path, 1).unwrap();
+    assert_eq!(load_checkpoint(&path).unwrap().unwrap().next_statement, 1);
+
+    fs::write(&path, "SELECT 1;\nSELECT 2;\nSELECT 3;\n").unwrap();
+    assert!(load_checkpoint(&path).is_err());
+
+    let _ = fs::remove_file(checkpoint_path(&path));
+    let _ = fs::remove_file(&path);
+}
+
+#[test]
+fn test_session_setup_statements() {
+    assert!(is_session_setup_statement("USE `shop`;"));
+    assert!(is_session_setup_statement("  set NAMES utf8mb4"));
+    assert!(!is_session_setup_statement(
+        "INSERT INTO settings VALUES (1)"
+    ));
+    assert!(!is_session_setup_statement("CREATE TABLE users (id int)"));
+}
+
+#[test]
+fn test_format_from_path() {
+    assert_eq!(BackupFormat::from_path("app.db"), BackupFormat::Native);
+    assert_eq!(BackupFormat::from_path("app.sqlite3.gz"), BackupFormat::Native);
+    assert_eq!(BackupFormat::from_path("dump.sql.zst"), BackupFormat::Sql);
+    assert_eq!(BackupFormat::from_path("dump"), BackupFormat::Sql);
+    assert!(is_sqlite_database_file(b"SQLite format 3\0\x10\x00"));
+    assert!(!is_sqlite_database_file(b"-- TactileSQL Backup"));
+}
@@ -3,7 +3,10 @@
 // Handles database connection lifecycle, configuration storage, and testing
 // =====================================================
 
-use crate::db_types::{AppState, ConnectionConfig, DatabaseType, MySqlVersion, SSHTunnelConfig};
+use crate::db_types::{
+    AppState, ConnectionConfig, ConnectionSession, DatabaseType, MySqlVersion, SSHTunnelConfig,
+    SessionInfo,
+};
 use crate::mysql;
 use crate::postgres;
 use crate::clickhouse;
@@ -13,6 +16,7 @@ use crate::ssh_tunnel;
 use super::crypto::{decrypt_password_with_key, encrypt_password_with_key};
 use std::fs;
 use std::path::PathBuf;
+use std::sync::Arc;
 use tauri::{AppHandle, Manager, State};
 
 // =====================================================
@@ -40,7 +44,7 @@ pub fn get_connections_file_path(app_handle: &AppHandle) -> PathBuf {
     app_data_dir.join("connections.json")
 }
 
-pub async fn clone_local_db_pool(app_state: &State<'_, AppState>) -> Option<sqlx::Pool<sqlx::Sqlite>> {
+pub async fn clone_local_db_pool(app_state: &AppState) -> Option<sqlx::Pool<sqlx::Sqlite>> {
     let guard = app_state.local_db_pool.lock().await;
     guard.clone()
 }
@@ -118,14 +122,28 @@ pub async fn establish_connection(
     config: ConnectionConfig,
 ) -> Result<String, String> {
     let connection_id = config.id.clone();
+    let session_id = connection_id
+        .clone()
+        .filter(|id| !id.trim().is_empty())
+        .unwrap_or_else(|| format!("session-{}", uuid::Uuid::new_v4()));
     let mut effective_config = config.clone();
     let mut active_tunnel_key: Option<String> = None;
 
+    // Re-establishing an already open connection replaces its session, but only
+    // once the new connection works; until then the old one stays usable.
+    let reconnecting = {
+        let guard = app_state.sessions.lock().await;
+        guard.contains_key(&session_id)
+    };
+
     if config.use_ssh_tunnel {
         let ssh_config = ssh_tunnel::extract_ssh_config(&config)?;
-        let tunnel_key = connection_id
-            .clone()
-            .unwrap_or_else(|| format!("session-{}", uuid::Uuid::new_v4()));
+        // A reconnect gets its own tunnel so the old session's keeps running
+        let tunnel_key = if reconnecting {
+            format!("{}-{}", session_id, uuid::Uuid::new_v4())
+        } else {
+            session_id.clone()
+        };
         let local_port = ssh_tunnel::open_or_replace_tunnel(
             &tunnel_key,
             ssh_config,
@@ -139,152 +157,178 @@ pub async fn establish_connection(
         active_tunnel_key = Some(tunnel_key);
     }
 
-    let establish_result = match effective_config.db_type {
+    let session = ConnectionSession::new(
+        session_id.clone(),
+        config.clone(),
+        active_tunnel_key.clone(),
+    );
+
+    let establish_result = open_session_pools(&session, &effective_config).await;
+
+    if establish_result.is_err() {
+        if let Some(key) = active_tunnel_key.as_deref() {
+            let _ = ssh_tunnel::close_tunnel(key).await;
+        }
+        return establish_result;
+    }
+
+    let session = Arc::new(session);
+    let previous = {
+        let mut guard = app_state.sessions.lock().await;
+        guard.insert(session_id.clone(), Arc::clone(&session))
+    };
+    app_state.activate_session(&session).await;
+
+    if let Some(previous) = previous {
+        crate::db::rollback_transaction_for_session(&session_id).await;
+        crate::db::close_result_cursors_for_session(&session_id).await;
+        previous.close().await;
+        if let Some(key) = previous.tunnel_key.as_deref() {
+            if active_tunnel_key.as_deref() != Some(key) {
+                let _ = ssh_tunnel::close_tunnel(key).await;
+            }
+        }
+    }
+
+    if let Some(id) = connection_id.as_deref() {
+        let store = {
+            let guard = app_state.dependency_engine_store.lock().await;
+            guard.clone()
+        };
+        if let Some(store) = store {
+            store.invalidate_connection_cache(id);
+        }
+    }
+
+    establish_result
+}
+
+/// Connects `session` to the database described by `effective_config`.
+async fn open_session_pools(
+    session: &ConnectionSession,
+    effective_config: &ConnectionConfig,
+) -> Result<String, String> {
+    match effective_config.db_type {
         DatabaseType::PostgreSQL => {
-            let pool = postgres::create_pool(&effective_config).await?;
+            let pool = postgres::create_pool(effective_config).await?;
 
-            let mut pg_guard = app_state.postgres_pool.lock().await;
+            let mut pg_guard = session.postgres_pool.lock().await;
             *pg_guard = Some(pool);
 
-            let mut db_type_guard = app_state.active_db_type.lock().await;
-            *db_type_guard = DatabaseType::PostgreSQL;
-
             Ok("PostgreSQL connection established successfully".to_string())
         }
         DatabaseType::MySQL => {
-            let pool = mysql::create_pool(&effective_config).await?;
+            let pool = mysql::create_pool(effective_config).await?;
 
             // Detect MySQL version for compatibility branching
             let version = mysql::detect_mysql_version(&pool).await?;
 
-            let mut mysql_guard = app_state.mysql_pool.lock().await;
+            let mut mysql_guard = session.mysql_pool.lock().await;
             *mysql_guard = Some(pool);
 
-            let mut version_guard = app_state.mysql_version.lock().await;
+            let mut version_guard = session.mysql_version.lock().await;
             *version_guard = Some(version);
 
-            let mut db_type_guard = app_state.active_db_type.lock().await;
-            *db_type_guard = DatabaseType::MySQL;
-
             Ok("MySQL connection established successfully".to_string())
         }
         DatabaseType::ClickHouse => {
-            let client = clickhouse::create_client(&effective_config)?;
+            let client = clickhouse::create_client(effective_config)?;
 
-            let mut ch_guard = app_state.clickhouse_pool.lock().await;
+            let mut ch_guard = session.clickhouse_pool.lock().await;
             *ch_guard = Some(client);
 
-            let mut ch_config_guard = app_state.clickhouse_config.lock().await;
+            let mut ch_config_guard = session.clickhouse_config.lock().await;
             *ch_config_guard = Some(effective_config.clone());
 
-            let mut db_type_guard = app_state.active_db_type.lock().await;
-            *db_type_guard = DatabaseType::ClickHouse;
-
             Ok("ClickHouse connection established successfully".to_string())
         }
         DatabaseType::MSSQL => {
-            let pool = mssql::create_pool(&effective_config).await?;
+            let pool = mssql::create_pool(effective_config).await?;
 
-            let mut mssql_guard = app_state.mssql_pool.lock().await;
+            let mut mssql_guard = session.mssql_pool.lock().await;
             *mssql_guard = Some(pool);
 
-            let mut db_type_guard = app_state.active_db_type.lock().await;
-            *db_type_guard = DatabaseType::MSSQL;
-
             Ok("MSSQL connection established successfully".to_string())
         }
         DatabaseType::SQLite => {
             let db_path = effective_config.host.clone();
             let pool = sqlite::create_pool(&db_path).await?;
 
-            let mut sqlite_guard = app_state.sqlite_pool.lock().await;
+            let mut sqlite_guard = session.sqlite_pool.lock().await;
             *sqlite_guard = Some(pool);
 
-            let mut path_guard = app_state.sqlite_db_path.lock().await;
+            let mut path_guard = session.sqlite_db_path.lock().await;
             *path_guard = Some(db_path);
 
-            let mut db_type_guard = app_state.active_db_type.lock().await;
-            *db_type_guard = DatabaseType::SQLite;
-
             Ok("SQLite connection established successfully".to_string())
         }
         DatabaseType::Disconnected => Err("Cannot establish a 'Disconnected' connection".into()),
+    }
+}
+
+/// Closes one session, or the active session when no id is given.
+#[tauri::command]
+pub async fn disconnect(
+    app_state: State<'_, AppState>,
+    session_id: Option<String>,
+) -> Result<String, String> {
+    let active_session_id = {
+        let guard = app_state.active_session_id.lock().await;
+        guard.clone()
     };
+    let target_id = session_id
+        .filter(|id| !id.trim().is_empty())
+        .or_else(|| active_session_id.clone());
 
-    if establish_result.is_err() {
-        if let Some(key) = active_tunnel_key.as_deref() {
-            let _ = ssh_tunnel::close_tunnel(key).await;
-        }
-        return establish_result;
+    let Some(target_id) = target_id else {
+        app_state.clear_active_session().await;
+        return Ok("Disconnected successfully".to_string());
+    };
+
+    let session = {
+        let mut guard = app_state.sessions.lock().await;
+        guard.remove(&target_id)
+    };
+
+    if active_session_id.as_deref() == Some(target_id.as_str()) {
+        app_state.clear_active_session().await;
     }
 
-    if let Some(id) = connection_id.as_deref() {
-        let store = {
-            let guard = app_state.dependency_engine_store.lock().await;
-            guard.clone()
-        };
-        if let Some(store) = store {
-            store.invalidate_connection_cache(id);
+    crate::db::rollback_transaction_for_session(&target_id).await;
+    crate::db::close_result_cursors_for_session(&target_id).await;
+
+    if let Some(session) = session {
+        session.close().await;
+        if let Some(key) = session.tunnel_key.as_deref() {
+            ssh_tunnel::close_tunnel(key).await?;
         }
     }
 
-    if active_tunnel_key.is_some() {
-        ssh_tunnel::close_all_except(active_tunnel_key.as_deref()).await?;
-    } else {
-        ssh_tunnel::close_all_tunnels().await?;
+    let store = {
+        let guard = app_state.dependency_engine_store.lock().await;
+        guard.clone()
+    };
+    if let Some(store) = store {
+        store.invalidate_connection_cache(&target_id);
     }
 
-    establish_result
+    Ok("Disconnected successfully".to_string())
 }
 
 #[tauri::command]
-pub async fn disconnect(app_state: State<'_, AppState>) -> Result<String, String> {
-    let db_type = {
-        let guard = app_state.active_db_type.lock().await;
-        guard.clone()
+pub async fn disconnect_all(app_state: State<'_, AppState>) -> Result<String, String> {
+    let sessions: Vec<Arc<ConnectionSession>> = {
+        let mut guard = app_state.sessions.lock().await;
+        guard.drain().map(|(_, session)| session).collect()
     };
 
-    match db_type {
-        DatabaseType::PostgreSQL => {
-            let mut guard = app_state.postgres_pool.lock().await;
-            if let Some(pool) = guard.take() {
-                pool.close().await;
-            }
-        }
-        DatabaseType::MySQL => {
-            let mut guard = app_state.mysql_pool.lock().await;
-            if let Some(pool) = guard.take() {
-                pool.close().await;
-            }
-        }
-        DatabaseType::MSSQL => {
-            let mut guard = app_state.mssql_pool.lock().await;
-            if let Some(pool) = guard.take() {
-                pool.close();
-            }
-        }
-        DatabaseType::ClickHouse => {
-            let mut guard = app_state.clickhouse_pool.lock().await;
-            if let Some(_client) = guard.take() {
-                // ClickHouse HTTP client doesn't need explicit close
-            }
-            let mut config_guard = app_state.clickhouse_config.lock().await;
-            *config_guard = None;
-        }
-        DatabaseType::SQLite => {
-            let mut guard = app_state.sqlite_pool.lock().await;
-            if let Some(pool) = guard.take() {
-                pool.close().await;
-            }
-            let mut path_guard = app_state.sqlite_db_path.lock().await;
-            *path_guard = None;
-        }
-        DatabaseType::Disconnected => {}
+    app_state.clear_active_session().await;
+    crate::db::rollback_all_transactions().await;
+    crate::db::close_all_result_cursors().await;
+    for session in sessions {
+        session.close().await;
     }
 
-    let mut db_type_guard = app_state.active_db_type.lock().await;
-    *db_type_guard = DatabaseType::Disconnected;
-
     let store = {
         let guard = app_state.dependency_engine_store.lock().await;
         guard.clone()
@@ -299,13 +343,48 @@ pub async fn disconnect(app_state: State<'_, AppState>) -> Result<String, String
 }
 
 #[tauri::command]
-pub async fn get_mysql_version(app_state: State<'_, AppState>) -> Result<Option<MySqlVersion>, String> {
+pub async fn list_connection_sessions(
+    app_state: State<'_, AppState>,
+) -> Result<Vec<SessionInfo>, String> {
+    let active_session_id = {
+        let guard = app_state.active_session_id.lock().await;
+        guard.clone()
+    };
+    Ok(app_state
+        .list_sessions()
+        .await
+        .iter()
+        .map(|session| session.info(active_session_id.as_deref()))
+        .collect())
+}
+
+/// Makes an open session the default target for commands called without a session id.
+#[tauri::command]
+pub async fn set_active_session(
+    app_state: State<'_, AppState>,
+    session_id: String,
+) -> Result<SessionInfo, String> {
+    let session = app_state.get_session(&session_id).await?;
+    app_state.activate_session(&session).await;
+    Ok(session.info(Some(session_id.as_str())))
+}
+
+#[tauri::command]
+pub async fn get_mysql_version(
+    app_state: State<'_, AppState>,
+    session_id: Option<String>,
+) -> Result<Option<MySqlVersion>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let guard = app_state.mysql_version.lock().await;
     Ok(guard.clone())
 }
 
 #[tauri::command]
-pub async fn get_active_db_type(app_state: State<'_, AppState>) -> Result<DatabaseType, String> {
+pub async fn get_active_db_type(
+    app_state: State<'_, AppState>,
+    session_id: Option<String>,
+) -> Result<DatabaseType, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let guard = app_state.active_db_type.lock().await;
     Ok(guard.clone())
 }
@@ -1373,16 +1373,20 @@ pub async fn generate_data_sync_script_with_state(
 pub async fn compare_table_data(
     app_state: State<'_, AppState>,
     request: DataCompareRequest,
+    session_id: Option<String>,
 ) -> Result<DataCompareResult, String> {
-    compare_table_data_with_state(app_state.inner(), request).await
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
+    compare_table_data_with_state(&app_state, request).await
 }
 
 #[tauri::command]
 pub async fn generate_data_sync_script(
     app_state: State<'_, AppState>,
     request: DataCompareRequest,
+    session_id: Option<String>,
 ) -> Result<DataSyncPlan, String> {
-    generate_data_sync_script_with_state(app_state.inner(), request).await
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
+    generate_data_sync_script_with_state(&app_state, request).await
 }
 
 #[cfg(test)]
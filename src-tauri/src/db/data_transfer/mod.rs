@@ -1,6 +1,6 @@
 use crate::db::sql_utils::{
     build_insert_statements, ensure_sql_terminated, escape_sql_string, qualified_table_name,
-    quote_column_name, quote_identifier_mysql, value_to_csv_cell, write_text_file,
+    quote_column_name, value_to_csv_cell, write_text_file,
 };
 use crate::db_types::{AppState, DatabaseType, QueryResult};
 use crate::mysql;
@@ -27,7 +27,9 @@ pub async fn export_table_csv(
     table: String,
     file_path: String,
     include_headers: Option<bool>,
+    session_id: Option<String>,
 ) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let include_headers = include_headers.unwrap_or(true);
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
@@ -133,7 +135,9 @@ pub async fn export_table_json(
     database: String,
     table: String,
     file_path: String,
+    session_id: Option<String>,
 ) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -230,7 +234,9 @@ pub async fn export_table_sql(
     table: String,
     file_path: String,
     include_create: Option<bool>,
+    session_id: Option<String>,
 ) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let include_create = include_create.unwrap_or(true);
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
@@ -324,7 +330,9 @@ pub async fn import_csv(
     table: String,
     file_path: String,
     has_headers: Option<bool>,
+    session_id: Option<String>,
 ) -> Result<ImportCsvResult, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let has_headers = has_headers.unwrap_or(true);
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
@@ -667,235 +675,5 @@ pub async fn import_csv(
     })
 }
 
-#[tauri::command]
-pub async fn backup_database(
-    app_state: State<'_, AppState>,
-    database: String,
-    file_path: String,
-    include_data: Option<bool>,
-) -> Result<String, String> {
-    let include_data = include_data.unwrap_or(true);
-    let db_type = {
-        let guard = app_state.active_db_type.lock().await;
-        guard.clone()
-    };
-
-    let mut output = String::new();
-    output.push_str("-- TactileSQL Backup\n");
-    output.push_str(&format!("-- Database/Schema: {}\n", database));
-    output.push_str(&format!(
-        "-- Generated at: {}\n\n",
-        chrono::Utc::now().to_rfc3339()
-    ));
-
-    match db_type {
-        DatabaseType::PostgreSQL => {
-            let guard = app_state.postgres_pool.lock().await;
-            let pool = guard
-                .as_ref()
-                .ok_or("No PostgreSQL connection established")?;
-            let tables = postgres::get_tables(pool, &database).await?;
-
-            for table in tables {
-                output.push_str(&format!("-- Table: {}\n", table));
-                let ddl = postgres::get_table_ddl(pool, &database, &table).await?;
-                output.push_str(&ensure_sql_terminated(&ddl));
-                output.push('\n');
-
-                if include_data {
-                    let query = format!(
-                        "SELECT * FROM {}",
-                        qualified_table_name(&db_type, &database, &table)
-                    );
-                    let results = postgres::execute_query(pool, query).await?;
-                    if let Some(first) = results.first() {
-                        for stmt in build_insert_statements(&db_type, &database, &table, first) {
-                            output.push_str(&stmt);
-                            output.push('\n');
-                        }
-                    }
-                }
-                output.push('\n');
-            }
-        }
-        DatabaseType::MySQL => {
-            let guard = app_state.mysql_pool.lock().await;
-            let pool = guard.as_ref().ok_or("No MySQL connection established")?;
-            let tables = mysql::get_tables(pool, &database).await?;
-
-            output.push_str(&format!("USE {};\n\n", quote_identifier_mysql(&database)));
-
-            for table in tables {
-                output.push_str(&format!("-- Table: {}\n", table));
-                let ddl = mysql::get_table_ddl(pool, &database, &table).await?;
-                output.push_str(&ensure_sql_terminated(&ddl));
-                output.push('\n');
-
-                if include_data {
-                    let query = format!(
-                        "SELECT * FROM {}",
-                        qualified_table_name(&db_type, &database, &table)
-                    );
-                    let results = mysql::execute_query(pool, query).await?;
-                    if let Some(first) = results.first() {
-                        for stmt in build_insert_statements(&db_type, &database, &table, first) {
-                            output.push_str(&stmt);
-                            output.push('\n');
-                        }
-                    }
-                }
-                output.push('\n');
-            }
-        }
-        DatabaseType::MSSQL => {
-            let guard = app_state.mssql_pool.lock().await;
-            let pool = guard.as_ref().ok_or("No MSSQL connection established")?;
-            let tables = mssql::get_tables(pool, &database, "dbo").await?;
-
-            for table in tables {
-                output.push_str(&format!("-- Table: {}\n", table));
-                let ddl = mssql::get_table_ddl(pool, &database, "dbo", &table).await.unwrap_or_default();
-                output.push_str(&ensure_sql_terminated(&ddl));
-                output.push('\n');
-
-                if include_data {
-                    let query = format!(
-                        "SELECT * FROM {}",
-                        qualified_table_name(&db_type, &database, &table)
-                    );
-                    let results = mssql::execute_query(pool, query).await?;
-                    if let Some(first) = results.first() {
-                        for stmt in build_insert_statements(&db_type, &database, &table, first) {
-                            output.push_str(&stmt);
-                            output.push('\n');
-                        }
-                    }
-                }
-                output.push('\n');
-            }
-        }
-        DatabaseType::ClickHouse => {
-            let guard = app_state.clickhouse_config.lock().await;
-            let config = guard.as_ref().ok_or("No ClickHouse connection established")?;
-            let tables = clickhouse::get_tables(config, &database).await?;
-
-            for table in tables {
-                output.push_str(&format!("-- Table: {}\n", table));
-                let ddl = clickhouse::get_table_ddl(config, &database, &table).await?;
-                output.push_str(&ensure_sql_terminated(&ddl));
-                output.push('\n');
-
-                if include_data {
-                    let query = format!(
-                        "SELECT * FROM {}",
-                        qualified_table_name(&db_type, &database, &table)
-                    );
-                    let results = clickhouse::execute_query(config, query).await?;
-                    if let Some(first) = results.first() {
-                        for stmt in build_insert_statements(&db_type, &database, &table, first) {
-                            output.push_str(&stmt);
-                            output.push('\n');
-                        }
-                    }
-                }
-                output.push('\n');
-            }
-        }
-        DatabaseType::SQLite => {
-            let guard = app_state.sqlite_pool.lock().await;
-            let pool = guard.as_ref().ok_or("No SQLite connection established")?;
-            let tables = crate::sqlite::get_tables(pool).await?;
-
-            for table in tables {
-                output.push_str(&format!("-- Table: {}\n", table));
-                let ddl = crate::sqlite::get_table_ddl(pool, &database, &table).await?;
-                output.push_str(&ensure_sql_terminated(&ddl));
-                output.push('\n');
-
-                if include_data {
-                    let query = format!(
-                        "SELECT * FROM {}",
-                        qualified_table_name(&db_type, &database, &table)
-                    );
-                    let results = crate::sqlite::execute_query(pool, &query).await?;
-                    if let Some(first) = results.first() {
-                        for stmt in build_insert_statements(&db_type, &database, &table, first) {
-                            output.push_str(&stmt);
-                            output.push('\n');
-                        }
-                    }
-                }
-                output.push('\n');
-            }
-        }
-        DatabaseType::Disconnected => return Err("No connection established".into()),
-    }
-
-    write_text_file(&file_path, &output)?;
-    Ok(format!("Backup completed and saved to {}", file_path))
-}
-
-#[tauri::command]
-pub async fn restore_database(
-    app_state: State<'_, AppState>,
-    file_path: String,
-) -> Result<String, String> {
-    let sql_content =
-        fs::read_to_string(&file_path).map_err(|e| format!("Failed to read SQL file: {}", e))?;
-    if sql_content.trim().is_empty() {
-        return Err("SQL file is empty".to_string());
-    }
-
-    let db_type = {
-        let guard = app_state.active_db_type.lock().await;
-        guard.clone()
-    };
-
-    match db_type {
-        DatabaseType::PostgreSQL => {
-            let guard = app_state.postgres_pool.lock().await;
-            let pool = guard
-                .as_ref()
-                .ok_or("No PostgreSQL connection established")?;
-            sqlx::raw_sql(&sql_content)
-                .execute(pool)
-                .await
-                .map_err(|e| format!("Restore failed: {}", e))?;
-        }
-        DatabaseType::MySQL => {
-            let guard = app_state.mysql_pool.lock().await;
-            let pool = guard.as_ref().ok_or("No MySQL connection established")?;
-            sqlx::raw_sql(&sql_content)
-                .execute(pool)
-                .await
-                .map_err(|e| format!("Restore failed: {}", e))?;
-        }
-        DatabaseType::MSSQL => {
-            let guard = app_state.mssql_pool.lock().await;
-            let pool = guard.as_ref().ok_or("No MSSQL connection established")?;
-            mssql::execute_query(pool, sql_content)
-                .await
-                .map_err(|e| format!("Restore failed: {}", e))?;
-        }
-        DatabaseType::ClickHouse => {
-            let guard = app_state.clickhouse_config.lock().await;
-            let config = guard.as_ref().ok_or("No ClickHouse connection established")?;
-            clickhouse::execute_query(config, sql_content)
-                .await
-                .map_err(|e| format!("Restore failed: {}", e))?;
-        }
-        DatabaseType::SQLite => {
-            let guard = app_state.sqlite_pool.lock().await;
-            let pool = guard.as_ref().ok_or("No SQLite connection established")?;
-            crate::sqlite::execute_query(pool, &sql_content)
-                .await
-                .map_err(|e| format!("Restore failed: {}", e))?;
-        }
-        DatabaseType::Disconnected => return Err("No connection established".into()),
-    }
-
-    Ok(format!("Restore completed from {}", file_path))
-}
-
 #[cfg(test)]
 mod tests;
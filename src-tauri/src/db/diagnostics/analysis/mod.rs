@@ -22,7 +22,9 @@ use crate::db::helpers::{clamp_i32, round2};
 pub async fn get_execution_plan(
     app_state: State<'_, AppState>,
     query: String,
+    session_id: Option<String>,
 ) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -67,7 +69,8 @@ pub async fn get_execution_plan(
 }
 
 #[tauri::command]
-pub async fn get_lock_analysis(app_state: State<'_, AppState>) -> Result<LockAnalysis, String> {
+pub async fn get_lock_analysis(app_state: State<'_, AppState>, session_id: Option<String>) -> Result<LockAnalysis, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -105,7 +108,9 @@ pub async fn get_lock_analysis(app_state: State<'_, AppState>) -> Result<LockAna
 pub async fn get_slow_queries(
     app_state: State<'_, AppState>,
     limit: i32,
+    session_id: Option<String>,
 ) -> Result<Vec<SlowQuery>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -145,7 +150,9 @@ pub async fn get_slow_queries(
 pub async fn analyze_query(
     app_state: State<'_, AppState>,
     query: String,
+    session_id: Option<String>,
 ) -> Result<QueryAnalysis, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -324,7 +331,9 @@ pub async fn simulate_index_drop(
     database: String,
     table: String,
     index_name: String,
+    session_id: Option<String>,
 ) -> Result<IndexDropSimulation, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -538,7 +547,9 @@ pub async fn get_ai_index_recommendations(
     app_state: State<'_, AppState>,
     database: String,
     table: String,
+    session_id: Option<String>,
 ) -> Result<AiIndexRecommendations, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
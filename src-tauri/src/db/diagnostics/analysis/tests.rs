@@ -11,6 +11,7 @@ fn make_exec(query: &str, duration_ms: f64) -> QueryExecution {
             execution_time_ms: duration_ms,
             rows_affected: 0,
         },
+        parameters: None,
     }
 }
 
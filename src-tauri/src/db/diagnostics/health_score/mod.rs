@@ -26,7 +26,13 @@ pub struct CategoryScoreJson {
 #[tauri::command]
 pub async fn get_database_health_report(
     app_state: State<'_, AppState>,
+    session_id: Option<String>,
 ) -> Result<DatabaseHealthReport, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
+    build_health_report(&app_state).await
+}
+
+async fn build_health_report(app_state: &AppState) -> Result<DatabaseHealthReport, String> {
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -37,29 +43,29 @@ pub async fn get_database_health_report(
             let guard = app_state.mysql_pool.lock().await;
             let pool = guard.as_ref().ok_or("No MySQL connection established")?;
             
-            let connection_id = get_connection_id(&app_state).await;
-            generate_mysql_health_report(pool, &app_state, &connection_id).await
+            let connection_id = get_connection_id(app_state).await;
+            generate_mysql_health_report(pool, app_state, &connection_id).await
         }
         DatabaseType::PostgreSQL => {
             let guard = app_state.postgres_pool.lock().await;
             let pool = guard.as_ref().ok_or("No PostgreSQL connection established")?;
             
-            let connection_id = get_connection_id(&app_state).await;
-            generate_postgres_health_report(pool, &app_state, &connection_id).await
+            let connection_id = get_connection_id(app_state).await;
+            generate_postgres_health_report(pool, app_state, &connection_id).await
         }
         DatabaseType::MSSQL => {
             let guard = app_state.mssql_pool.lock().await;
             let pool = guard.as_ref().ok_or("No MSSQL connection established")?;
             
-            let connection_id = get_connection_id(&app_state).await;
-            generate_mssql_health_report(pool, &app_state, &connection_id).await
+            let connection_id = get_connection_id(app_state).await;
+            generate_mssql_health_report(pool, app_state, &connection_id).await
         }
         DatabaseType::ClickHouse => {
             let guard = app_state.clickhouse_config.lock().await;
             let config = guard.as_ref().ok_or("No ClickHouse connection established")?;
             
-            let connection_id = get_connection_id(&app_state).await;
-            generate_clickhouse_health_report(config, &app_state, &connection_id).await
+            let connection_id = get_connection_id(app_state).await;
+            generate_clickhouse_health_report(config, app_state, &connection_id).await
         }
         DatabaseType::SQLite => {
             Err("Health score not yet supported for SQLite".to_string())
@@ -280,6 +286,9 @@ async fn get_previous_scores(app_state: &AppState, connection_id: &str, days: i3
 }
 
 async fn get_connection_id(app_state: &AppState) -> String {
+    if let Some(session_id) = app_state.active_session_id.lock().await.clone() {
+        return session_id;
+    }
     let db_type = app_state.active_db_type.lock().await.clone();
     format!("{:?}", db_type)
 }
@@ -289,8 +298,18 @@ pub async fn get_health_recommendations(
     app_state: State<'_, AppState>,
     category: Option<String>,
     severity: Option<String>,
+    session_id: Option<String>,
+) -> Result<Vec<HealthRecommendation>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
+    build_health_recommendations(&app_state, category, severity).await
+}
+
+async fn build_health_recommendations(
+    app_state: &AppState,
+    category: Option<String>,
+    severity: Option<String>,
 ) -> Result<Vec<HealthRecommendation>, String> {
-    let report = get_database_health_report(app_state).await?;
+    let report = build_health_report(app_state).await?;
     
     let mut recommendations = recommendations::generate_recommendations(&report.categories);
     
@@ -309,8 +328,10 @@ pub async fn get_health_recommendations(
 pub async fn apply_recommendation(
     app_state: State<'_, AppState>,
     recommendation_id: String,
+    session_id: Option<String>,
 ) -> Result<ApplyRecommendationResult, String> {
-    let recommendations = get_health_recommendations(app_state.clone(), None, None).await?;
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
+    let recommendations = build_health_recommendations(&app_state, None, None).await?;
     
     let recommendation = recommendations
         .iter()
@@ -374,10 +395,13 @@ pub async fn apply_recommendation(
 pub async fn get_health_score_history(
     app_state: State<'_, AppState>,
     days: i32,
+    session_id: Option<String>,
 ) -> Result<Vec<ScoreHistoryPoint>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let connection_id = get_connection_id(&app_state).await;
     
-    if let Some(pool) = app_state.local_db_pool.lock().await.as_ref() {
+    let pool = app_state.local_db_pool.lock().await.clone();
+    if let Some(pool) = pool.as_ref() {
         persistence::get_health_score_history(pool, &connection_id, days)
             .await
             .map(|h| h.records)
@@ -389,15 +413,18 @@ pub async fn get_health_score_history(
 #[tauri::command]
 pub async fn refresh_health_score(
     app_state: State<'_, AppState>,
+    session_id: Option<String>,
 ) -> Result<DatabaseHealthReport, String> {
-    get_database_health_report(app_state).await
+    get_database_health_report(app_state, session_id).await
 }
 
 #[tauri::command]
 pub async fn get_quick_fix_recommendations(
     app_state: State<'_, AppState>,
+    session_id: Option<String>,
 ) -> Result<Vec<HealthRecommendation>, String> {
-    let report = get_database_health_report(app_state).await?;
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
+    let report = build_health_report(&app_state).await?;
     Ok(recommendations::get_quick_fixes(&report.categories))
 }
 
@@ -11,7 +11,9 @@ use super::monitor_store::{HistoricalMetric};
 pub async fn kill_process(
     app_state: State<'_, AppState>,
     process_id: i64,
+    session_id: Option<String>,
 ) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -48,21 +50,24 @@ pub async fn kill_process(
 // --- PostgreSQL Specific Monitoring ---
 
 #[tauri::command]
-pub async fn get_pg_activity(app_state: State<'_, AppState>) -> Result<Vec<ActivityRecord>, String> {
+pub async fn get_pg_activity(app_state: State<'_, AppState>, session_id: Option<String>) -> Result<Vec<ActivityRecord>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let guard = app_state.postgres_pool.lock().await;
     let pool = guard.as_ref().ok_or("No PostgreSQL connection established")?;
     postgres::get_pg_activity(pool).await
 }
 
 #[tauri::command]
-pub async fn kill_pg_session(app_state: State<'_, AppState>, pid: i32) -> Result<String, String> {
+pub async fn kill_pg_session(app_state: State<'_, AppState>, pid: i32, session_id: Option<String>) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let guard = app_state.postgres_pool.lock().await;
     let pool = guard.as_ref().ok_or("No PostgreSQL connection established")?;
     postgres::kill_pg_session(pool, pid).await
 }
 
 #[tauri::command]
-pub async fn get_pg_locks(app_state: State<'_, AppState>) -> Result<Vec<PgLockRecord>, String> {
+pub async fn get_pg_locks(app_state: State<'_, AppState>, session_id: Option<String>) -> Result<Vec<PgLockRecord>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let guard = app_state.postgres_pool.lock().await;
     let pool = guard.as_ref().ok_or("No PostgreSQL connection established")?;
     postgres::get_pg_locks(pool).await
@@ -74,7 +79,8 @@ pub async fn get_pg_locks(app_state: State<'_, AppState>) -> Result<Vec<PgLockRe
 // =====================================================
 
 #[tauri::command]
-pub async fn get_bloat_analysis(app_state: State<'_, AppState>) -> Result<Vec<BloatInfo>, String> {
+pub async fn get_bloat_analysis(app_state: State<'_, AppState>, session_id: Option<String>) -> Result<Vec<BloatInfo>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -100,6 +106,7 @@ pub async fn get_monitor_history(
     app_state: State<'_, AppState>,
     start_time: String,
     end_time: String,
+    session_id: Option<String>,
 ) -> Result<Vec<HistoricalMetric>, String> {
     let start = DateTime::parse_from_rfc3339(&start_time)
         .map_err(|e| format!("Invalid start time: {}", e))?
@@ -111,12 +118,22 @@ pub async fn get_monitor_history(
     let store_guard = app_state.monitor_store.lock().await;
     let store = store_guard.as_ref().ok_or("Monitor store not initialized")?;
 
-    let connection_id = "default_active";
-    store.get_history(connection_id, start, end).await
+    // Snapshots are stored per session; fall back to the legacy key when nothing is open.
+    let connection_id = match session_id.filter(|id| !id.trim().is_empty()) {
+        Some(id) => id,
+        None => app_state
+            .active_session_id
+            .lock()
+            .await
+            .clone()
+            .unwrap_or_else(|| "default_active".to_string()),
+    };
+    store.get_history(&connection_id, start, end).await
 }
 
 #[tauri::command]
-pub async fn get_monitor_snapshot(app_state: State<'_, AppState>) -> Result<MonitorSnapshot, String> {
+pub async fn get_monitor_snapshot(app_state: State<'_, AppState>, session_id: Option<String>) -> Result<MonitorSnapshot, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -268,7 +285,9 @@ pub async fn get_index_fragmentation(
     app_state: State<'_, AppState>,
     database: String,
     table: String,
+    session_id: Option<String>,
 ) -> Result<Vec<crate::db_types::IndexFragmentationInfo>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -285,7 +304,8 @@ pub async fn get_index_fragmentation(
 }
 
 #[tauri::command]
-pub async fn get_agent_jobs(app_state: State<'_, AppState>) -> Result<Vec<crate::db_types::AgentJob>, String> {
+pub async fn get_agent_jobs(app_state: State<'_, AppState>, session_id: Option<String>) -> Result<Vec<crate::db_types::AgentJob>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -302,7 +322,8 @@ pub async fn get_agent_jobs(app_state: State<'_, AppState>) -> Result<Vec<crate:
 }
 
 #[tauri::command]
-pub async fn start_agent_job(app_state: State<'_, AppState>, job_name: String) -> Result<String, String> {
+pub async fn start_agent_job(app_state: State<'_, AppState>, job_name: String, session_id: Option<String>) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -319,7 +340,8 @@ pub async fn start_agent_job(app_state: State<'_, AppState>, job_name: String) -
 }
 
 #[tauri::command]
-pub async fn stop_agent_job(app_state: State<'_, AppState>, job_name: String) -> Result<String, String> {
+pub async fn stop_agent_job(app_state: State<'_, AppState>, job_name: String, session_id: Option<String>) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -336,7 +358,8 @@ pub async fn stop_agent_job(app_state: State<'_, AppState>, job_name: String) ->
 }
 
 #[tauri::command]
-pub async fn get_storage_stats(app_state: State<'_, AppState>, database: String) -> Result<Vec<crate::db_types::StorageStats>, String> {
+pub async fn get_storage_stats(app_state: State<'_, AppState>, database: String, session_id: Option<String>) -> Result<Vec<crate::db_types::StorageStats>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -12,7 +12,9 @@ use tauri::State;
 pub async fn get_capacity_metrics(
     app_state: State<'_, AppState>,
     database: String,
+    session_id: Option<String>,
 ) -> Result<CapacityMetrics, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -68,7 +70,9 @@ pub async fn get_capacity_metrics(
 pub async fn get_sequences(
     app_state: State<'_, AppState>,
     schema: String,
+    session_id: Option<String>,
 ) -> Result<Vec<String>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -94,7 +98,9 @@ pub async fn get_sequences(
 pub async fn get_custom_types(
     app_state: State<'_, AppState>,
     schema: String,
+    session_id: Option<String>,
 ) -> Result<Vec<String>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -117,7 +123,8 @@ pub async fn get_custom_types(
 
 
 #[tauri::command]
-pub async fn get_tablespaces(app_state: State<'_, AppState>) -> Result<Vec<String>, String> {
+pub async fn get_tablespaces(app_state: State<'_, AppState>, session_id: Option<String>) -> Result<Vec<String>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -146,7 +153,9 @@ pub async fn get_index_suggestions(
     app_state: State<'_, AppState>,
     database: String,
     table: String,
+    session_id: Option<String>,
 ) -> Result<Vec<IndexSuggestion>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -185,7 +194,9 @@ pub async fn get_index_usage(
     app_state: State<'_, AppState>,
     database: String,
     table: String,
+    session_id: Option<String>,
 ) -> Result<Vec<IndexUsage>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -224,7 +235,9 @@ pub async fn get_index_sizes(
     app_state: State<'_, AppState>,
     database: String,
     table: String,
+    session_id: Option<String>,
 ) -> Result<Vec<IndexSize>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
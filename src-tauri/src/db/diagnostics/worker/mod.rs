@@ -15,8 +15,14 @@ pub fn start_monitoring_worker(app: AppHandle) {
 
         loop {
             let state = app.state::<AppState>();
-            if let Err(e) = monitor_tick(&app, &state).await {
-                eprintln!("Monitoring tick failed: {}", e);
+            for session in state.list_sessions().await {
+                let scoped = match state.for_session(Some(&session.session_id)).await {
+                    Ok(scoped) => scoped,
+                    Err(_) => continue,
+                };
+                if let Err(e) = monitor_tick(&app, &scoped, &session.session_id).await {
+                    eprintln!("Monitoring tick failed for {}: {}", session.session_id, e);
+                }
             }
 
             sleep(Duration::from_secs(MONITOR_POLL_INTERVAL_SECONDS)).await;
@@ -24,7 +30,7 @@ pub fn start_monitoring_worker(app: AppHandle) {
     });
 }
 
-async fn monitor_tick(app: &AppHandle, state: &AppState) -> Result<(), String> {
+async fn monitor_tick(app: &AppHandle, state: &AppState, session_id: &str) -> Result<(), String> {
     let db_type = {
         let guard = state.active_db_type.lock().await;
         guard.clone()
@@ -85,16 +91,15 @@ async fn monitor_tick(app: &AppHandle, state: &AppState) -> Result<(), String> {
         let elapsed = if *last_tick_guard > 0 { now - *last_tick_guard } else { 0 };
         *last_tick_guard = now;
 
-        // Save to history
-        let connection_id = "default_active"; 
+        // Save to history, keyed by session; alert rules stay shared across sessions
         let store_guard = state.monitor_store.lock().await;
         if let Some(store) = store_guard.as_ref() {
-            if let Err(e) = store.save_snapshot(connection_id, &s).await {
+            if let Err(e) = store.save_snapshot(session_id, &s).await {
                 eprintln!("Failed to save monitor snapshot: {}", e);
             }
 
             // Check Alerts
-            if let Ok(alerts) = store.get_alerts(connection_id).await {
+            if let Ok(alerts) = store.get_alerts("default_active").await {
                 for alert in alerts.into_iter().filter(|a| a.is_enabled) {
                     let current_val = calculate_current_metric(&alert.metric_name, &s, prev_status.as_ref(), elapsed);
 
@@ -106,8 +111,8 @@ async fn monitor_tick(app: &AppHandle, state: &AppState) -> Result<(), String> {
                             let _ = app.notification()
                                 .builder()
                                 .title("TactileSQL Monitor Alert")
-                                .body(format!("Metric {} reached {:.2} (Threshold: {} {})", 
-                                    alert.metric_name, current_val, alert.operator, alert.threshold))
+                                .body(format!("[{}] Metric {} reached {:.2} (Threshold: {} {})",
+                                    session_id, alert.metric_name, current_val, alert.operator, alert.threshold))
                                 .show();
                         }
                     }
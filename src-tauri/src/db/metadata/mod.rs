@@ -11,7 +11,8 @@ use crate::mssql;
 use crate::sqlite;
 
 #[tauri::command]
-pub async fn get_databases(app_state: State<'_, AppState>) -> Result<Vec<String>, String> {
+pub async fn get_databases(app_state: State<'_, AppState>, session_id: Option<String>) -> Result<Vec<String>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -53,7 +54,9 @@ pub async fn get_databases(app_state: State<'_, AppState>) -> Result<Vec<String>
 pub async fn get_schemas(
     app_state: State<'_, AppState>,
     database: String,
+    session_id: Option<String>,
 ) -> Result<Vec<String>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -84,7 +87,9 @@ pub async fn get_tables(
     app_state: State<'_, AppState>,
     database: Option<String>,
     schema: Option<String>,
+    session_id: Option<String>,
 ) -> Result<Vec<String>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -132,7 +137,9 @@ pub async fn get_table_schema(
     database: Option<String>,
     schema: Option<String>,
     table: String,
+    session_id: Option<String>,
 ) -> Result<Vec<ColumnSchema>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -180,7 +187,9 @@ pub async fn get_table_ddl(
     database: Option<String>,
     schema: Option<String>,
     table: String,
+    session_id: Option<String>,
 ) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -227,7 +236,9 @@ pub async fn get_table_indexes(
     database: Option<String>,
     schema: Option<String>,
     table: String,
+    session_id: Option<String>,
 ) -> Result<Vec<TableIndex>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -274,7 +285,9 @@ pub async fn get_table_foreign_keys(
     database: Option<String>,
     schema: Option<String>,
     table: String,
+    session_id: Option<String>,
 ) -> Result<Vec<ForeignKey>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -333,7 +346,9 @@ pub async fn get_table_primary_keys(
     database: Option<String>,
     schema: Option<String>,
     table: String,
+    session_id: Option<String>,
 ) -> Result<Vec<PrimaryKey>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -380,7 +395,9 @@ pub async fn get_table_constraints(
     database: Option<String>,
     schema: Option<String>,
     table: String,
+    session_id: Option<String>,
 ) -> Result<Vec<TableConstraint>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -418,7 +435,9 @@ pub async fn get_table_stats(
     database: Option<String>,
     schema: Option<String>,
     table: String,
+    session_id: Option<String>,
 ) -> Result<TableStats, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -463,7 +482,9 @@ pub async fn get_table_stats(
 pub async fn get_dictionaries(
     app_state: State<'_, AppState>,
     database: String,
+    session_id: Option<String>,
 ) -> Result<Vec<String>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -923,7 +923,9 @@ pub async fn start_mock_data_generation(
     include_nullable_columns: Option<bool>,
     column_rules: Option<HashMap<String, mock_data::MockColumnRule>>,
     dry_run: Option<bool>,
+    session_id: Option<String>,
 ) -> Result<MockDataJobStatus, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let row_count = row_count.unwrap_or(100).clamp(1, 100_000);
     let include_nullable_columns = include_nullable_columns.unwrap_or(true);
     let column_rules = column_rules.unwrap_or_default();
@@ -1131,7 +1133,9 @@ pub async fn preview_mock_data(
     seed: Option<u64>,
     include_nullable_columns: Option<bool>,
     column_rules: Option<HashMap<String, mock_data::MockColumnRule>>,
+    session_id: Option<String>,
 ) -> Result<MockDataPreviewResponse, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let row_count = row_count.unwrap_or(20).clamp(1, 200);
     let include_nullable_columns = include_nullable_columns.unwrap_or(true);
     let column_rules = column_rules.unwrap_or_default();
@@ -9,6 +9,7 @@ pub mod data_transfer;
 mod helpers;
 pub mod lock_analysis;
 pub mod mock_jobs;
+mod sql_lexer;
 pub mod sql_utils;
 
 // Re-export submodule functions
@@ -19,6 +20,9 @@ pub use data_compare::*;
 pub use data_transfer::*;
 pub use mock_jobs::*;
 
+pub mod backup;
+pub use backup::*;
+
 pub mod connections;
 pub use connections::*;
 
@@ -28,6 +32,24 @@ pub use diagnostics::*;
 pub mod query_execution;
 pub use query_execution::*;
 
+pub mod query_params;
+pub use query_params::*;
+
+pub mod result_cursor;
+pub use result_cursor::*;
+
+pub mod result_edits;
+pub use result_edits::*;
+
+pub mod running_queries;
+pub use running_queries::*;
+
+pub mod script_runner;
+pub use script_runner::*;
+
+pub mod transactions;
+pub use transactions::*;
+
 
 
 
@@ -13,7 +13,9 @@ use crate::sqlite;
 pub async fn get_events(
     app_state: State<'_, AppState>,
     database: String,
+    session_id: Option<String>,
 ) -> Result<Vec<EventInfo>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -38,7 +40,9 @@ pub async fn get_events(
 #[tauri::command]
 pub async fn get_extensions(
     app_state: State<'_, AppState>,
+    session_id: Option<String>,
 ) -> Result<Vec<ExtensionRecord>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -61,7 +65,9 @@ pub async fn manage_extension(
     app_state: State<'_, AppState>,
     name: String,
     action: String,
+    session_id: Option<String>,
 ) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -83,7 +89,9 @@ pub async fn manage_extension(
 pub async fn get_views(
     app_state: State<'_, AppState>,
     database: String,
+    session_id: Option<String>,
 ) -> Result<Vec<String>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -127,7 +135,9 @@ pub async fn get_view_definition(
     app_state: State<'_, AppState>,
     _database: String,
     view: String,
+    session_id: Option<String>,
 ) -> Result<ViewDefinition, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -183,7 +193,9 @@ pub async fn alter_view(
     app_state: State<'_, AppState>,
     database: String,
     definition: String,
+    session_id: Option<String>,
 ) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -228,7 +240,9 @@ pub async fn alter_view(
 pub async fn get_triggers(
     app_state: State<'_, AppState>,
     database: String,
+    session_id: Option<String>,
 ) -> Result<Vec<TriggerInfo>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -258,6 +272,7 @@ pub async fn get_triggers(
                     event: r[1].as_str().unwrap_or("").to_string(),
                     timing: r[2].as_str().unwrap_or("").to_string(),
                     table_name: r[3].as_str().unwrap_or("").to_string(),
+                    definition: None,
                 }).collect())
             } else {
                 Ok(vec![])
@@ -278,7 +293,9 @@ pub async fn get_table_triggers(
     app_state: State<'_, AppState>,
     database: String,
     table: String,
+    session_id: Option<String>,
 ) -> Result<Vec<TriggerInfo>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -308,6 +325,7 @@ pub async fn get_table_triggers(
                     event: r[1].as_str().unwrap_or("").to_string(),
                     timing: r[2].as_str().unwrap_or("").to_string(),
                     table_name: r[3].as_str().unwrap_or("").to_string(),
+                    definition: None,
                 }).collect())
             } else {
                 Ok(vec![])
@@ -328,7 +346,9 @@ pub async fn get_table_triggers(
 pub async fn get_procedures(
     app_state: State<'_, AppState>,
     database: String,
+    session_id: Option<String>,
 ) -> Result<Vec<RoutineInfo>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -356,6 +376,8 @@ pub async fn get_procedures(
                 Ok(first.rows.iter().map(|r| RoutineInfo {
                     name: r[0].as_str().unwrap_or("").to_string(),
                     definer: r[1].as_str().unwrap_or("").to_string(),
+                    routine_type: Some("PROCEDURE".to_string()),
+                    definition: None,
                 }).collect())
             } else {
                 Ok(vec![])
@@ -371,7 +393,9 @@ pub async fn get_procedures(
 pub async fn get_functions(
     app_state: State<'_, AppState>,
     database: String,
+    session_id: Option<String>,
 ) -> Result<Vec<RoutineInfo>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -399,6 +423,8 @@ pub async fn get_functions(
                 Ok(first.rows.iter().map(|r| RoutineInfo {
                     name: r[0].as_str().unwrap_or("").to_string(),
                     definer: r[1].as_str().unwrap_or("").to_string(),
+                    routine_type: Some("FUNCTION".to_string()),
+                    definition: None,
                 }).collect())
             } else {
                 Ok(vec![])
@@ -418,7 +444,9 @@ pub async fn maintain_index(
     table: String,
     index: String,
     action: String,
+    session_id: Option<String>,
 ) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -7,9 +7,7 @@ use tauri::State;
 use crate::db_types::{AppState, DatabaseType, QueryResult};
 use crate::mysql;
 use crate::postgres;
-use crate::clickhouse;
 use crate::mssql;
-use crate::sqlite;
 
 static SYSTEM_QUERY_REGEX: LazyLock<Regex> = LazyLock::new(|| {
     // Matches:
@@ -29,6 +27,7 @@ pub struct ProfiledQueryResponse {
     pub results: Vec<QueryResult>,
     pub duration_ms: f64,
     pub status_diff: Option<HashMap<String, i64>>,
+    pub execution_id: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -40,6 +39,7 @@ pub struct ProfileOptions {
 pub fn spawn_awareness_log(
     app_state: &AppState,
     query: String,
+    parameters: Option<serde_json::Value>,
     duration_ms: f64,
     rows_affected: u64,
     db_type: DatabaseType,
@@ -72,6 +72,7 @@ pub fn spawn_awareness_log(
                     execution_time_ms: duration_ms,
                     rows_affected,
                 },
+                parameters,
             };
 
             match store.log_query_execution(&execution).await {
@@ -201,7 +202,9 @@ fn is_safe_for_explain(query: &str) -> bool {
 pub async fn execute_query(
     app_state: State<'_, AppState>,
     query: String,
+    session_id: Option<String>,
 ) -> Result<Vec<QueryResult>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let start_time = chrono::Utc::now();
 
     let db_type = {
@@ -209,54 +212,23 @@ pub async fn execute_query(
         guard.clone()
     };
 
-    let result = match db_type {
-        DatabaseType::PostgreSQL => {
-            let guard = app_state.postgres_pool.lock().await;
-            let pool = guard
-                .as_ref()
-                .ok_or("No PostgreSQL connection established")?;
-            postgres::execute_query(pool, query.clone()).await
-        }
-        DatabaseType::MySQL => {
-            let guard = app_state.mysql_pool.lock().await;
-            let pool = guard.as_ref().ok_or("No MySQL connection established")?;
-            
-            let (normalized_query, warnings): (String, Vec<String>) = {
-                let version_guard = app_state.mysql_version.lock().await;
-                if let Some(version) = version_guard.as_ref() {
-                    let normalized = mysql::normalize_mysql_query(&query, version);
-                    let warnings = mysql::validate_query_compatibility(&query, version);
-                    (normalized, warnings)
-                } else {
-                    (query.clone(), vec![])
-                }
-            };
-            
-            let mut results = mysql::execute_query(pool, normalized_query).await?;
-            if !warnings.is_empty() {
-                for r in results.iter_mut() {
-                    r.warnings.extend(warnings.clone());
-                }
-            }
-            Ok(results)
-        }
-        DatabaseType::MSSQL => {
-            let guard = app_state.mssql_pool.lock().await;
-            let pool = guard.as_ref().ok_or("No MSSQL connection established")?;
-            mssql::execute_query(pool, query.clone()).await
-        }
-        DatabaseType::ClickHouse => {
-            let guard = app_state.clickhouse_config.lock().await;
-            let config = guard.as_ref().ok_or("No ClickHouse connection established")?;
-            clickhouse::execute_query(config, query.clone()).await
-        }
-        DatabaseType::SQLite => {
-            let guard = app_state.sqlite_pool.lock().await;
-            let pool = guard.as_ref().ok_or("No SQLite connection established")?;
-            sqlite::execute_query(pool, &query).await
-        }
-        DatabaseType::Disconnected => Err("No connection established".into()),
-    };
+    let execution = crate::db::register_execution(&app_state, &query, None).await?;
+    // An open transaction pins the session to one connection
+    if let Some(results) =
+        crate::db::execute_in_transaction(&app_state, &execution, &query, None).await?
+    {
+        return Ok(results);
+    }
+
+    let (normalized_query, warnings) = normalize_for_server(&app_state, &db_type, &query).await;
+    let result =
+        crate::db::execute_cancellable(&app_state, &execution, &normalized_query, None, false)
+            .await
+            .map(|(mut results, _)| {
+                append_warnings(&mut results, &warnings);
+                results
+            });
+    drop(execution);
 
     let duration_ms = (chrono::Utc::now() - start_time).num_milliseconds() as f64;
 
@@ -267,6 +239,7 @@ pub async fn execute_query(
         spawn_awareness_log(
             &app_state,
             query.clone(),
+            None,
             duration_ms,
             rows_affected,
             db_type.clone(),
@@ -277,13 +250,45 @@ pub async fn execute_query(
     result
 }
 
+/// Rewrites MySQL queries for the connected server version and returns the
+/// compatibility warnings; other engines pass through unchanged.
+async fn normalize_for_server(
+    app_state: &AppState,
+    db_type: &DatabaseType,
+    query: &str,
+) -> (String, Vec<String>) {
+    if *db_type != DatabaseType::MySQL {
+        return (query.to_string(), vec![]);
+    }
+    let version_guard = app_state.mysql_version.lock().await;
+    if let Some(version) = version_guard.as_ref() {
+        let normalized = mysql::normalize_mysql_query(query, version);
+        let warnings = mysql::validate_query_compatibility(query, version);
+        (normalized, warnings)
+    } else {
+        (query.to_string(), vec![])
+    }
+}
+
+fn append_warnings(results: &mut [QueryResult], warnings: &[String]) {
+    if warnings.is_empty() {
+        return;
+    }
+    for r in results.iter_mut() {
+        r.warnings.extend(warnings.iter().cloned());
+    }
+}
+
 #[tauri::command]
 pub async fn execute_query_profiled(
     app_state: State<'_, AppState>,
     query: String,
     profile_options: Option<ProfileOptions>,
     _query_timeout_seconds: Option<u64>,
+    execution_id: Option<String>,
+    session_id: Option<String>,
 ) -> Result<ProfiledQueryResponse, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let start_time = chrono::Utc::now();
 
     let db_type = {
@@ -291,44 +296,24 @@ pub async fn execute_query_profiled(
         guard.clone()
     };
 
-    // Reset cancel flag at start
+    let execution = crate::db::register_execution(&app_state, &query, execution_id).await?;
+    let execution_id = execution.id().to_string();
+    // An open transaction pins the session to one connection
+    if let Some(results) = crate::db::execute_in_transaction(
+        &app_state,
+        &execution,
+        &query,
+        _query_timeout_seconds,
+    )
+    .await?
     {
-        let mut cancel_guard = app_state.query_cancel_requested.lock().await;
-        *cancel_guard = false;
-    }
-
-    // Get and store the current connection/thread ID for potential cancellation
-    let pid_result: Result<Option<i64>, String> = match &db_type {
-        DatabaseType::MySQL => {
-            let guard = app_state.mysql_pool.lock().await;
-            if let Some(pool) = guard.as_ref() {
-                mysql::get_connection_id(pool).await
-            } else {
-                Ok(None)
-            }
-        }
-        DatabaseType::PostgreSQL => {
-            let guard = app_state.postgres_pool.lock().await;
-            if let Some(pool) = guard.as_ref() {
-                postgres::get_backend_pid(pool).await
-            } else {
-                Ok(None)
-            }
-        }
-        DatabaseType::MSSQL => {
-            let guard = app_state.mssql_pool.lock().await;
-            if let Some(pool) = guard.as_ref() {
-                mssql::get_session_id(pool).await
-            } else {
-                Ok(None)
-            }
-        }
-        DatabaseType::ClickHouse | DatabaseType::SQLite | DatabaseType::Disconnected => Ok(None),
-    };
-
-    if let Ok(Some(pid)) = pid_result {
-        let mut pid_guard = app_state.running_query_pid.lock().await;
-        *pid_guard = Some(pid);
+        let duration_ms = (chrono::Utc::now() - start_time).num_milliseconds() as f64;
+        return Ok(ProfiledQueryResponse {
+            results,
+            duration_ms,
+            status_diff: None,
+            execution_id: Some(execution_id),
+        });
     }
 
     let explain_analyze_enabled = profile_options
@@ -336,79 +321,30 @@ pub async fn execute_query_profiled(
         .and_then(|opts| opts.explain_analyze)
         .unwrap_or(true);
 
-    let (results, status_diff) = match db_type {
-        DatabaseType::PostgreSQL => {
+    let (normalized_query, warnings) = normalize_for_server(&app_state, &db_type, &query).await;
+    let (mut results, mut status_diff) = crate::db::execute_cancellable(
+        &app_state,
+        &execution,
+        &normalized_query,
+        _query_timeout_seconds,
+        db_type == DatabaseType::MySQL,
+    )
+    .await?;
+    drop(execution);
+    append_warnings(&mut results, &warnings);
+
+    if db_type == DatabaseType::PostgreSQL && explain_analyze_enabled && is_safe_for_explain(&query)
+    {
+        let pool = {
             let guard = app_state.postgres_pool.lock().await;
-            let pool = guard
-                .as_ref()
-                .ok_or("No PostgreSQL connection established")?;
-            let res =
-                postgres::execute_query_with_timeout(pool, query.clone(), _query_timeout_seconds)
-                    .await?;
-            let explain_metrics = if explain_analyze_enabled && is_safe_for_explain(&query) {
-                postgres::get_explain_analyze_metrics(pool, &query)
-                    .await
-                    .ok()
-            } else {
-                None
-            };
-            (res, explain_metrics)
-        }
-        DatabaseType::MySQL => {
-            let guard = app_state.mysql_pool.lock().await;
-            let pool = guard.as_ref().ok_or("No MySQL connection established")?;
-            
-            let (normalized_query, warnings) = {
-                let version_guard = app_state.mysql_version.lock().await;
-                if let Some(version) = version_guard.as_ref() {
-                    let normalized = mysql::normalize_mysql_query(&query, version);
-                    let warnings = mysql::validate_query_compatibility(&query, version);
-                    (normalized, warnings)
-                } else {
-                    (query.clone(), vec![])
-                }
-            };
-            
-            let (mut results, status_diff) = mysql::execute_query_with_status_with_timeout(
-                pool,
-                normalized_query,
-                _query_timeout_seconds,
-            )
-            .await?;
-
-            if !warnings.is_empty() {
-                for r in results.iter_mut() {
-                    r.warnings.extend(warnings.clone());
-                }
-            }
-            (results, status_diff)
-        }
-        DatabaseType::MSSQL => {
-            let guard = app_state.mssql_pool.lock().await;
-            let pool = guard.as_ref().ok_or("No MSSQL connection established")?;
-            let res =
-                mssql::execute_query_with_timeout(pool, query.clone(), _query_timeout_seconds)
-                    .await?;
-            (res, None)
+            guard.clone()
+        };
+        if let Some(pool) = pool {
+            status_diff = postgres::get_explain_analyze_metrics(&pool, &query)
+                .await
+                .ok();
         }
-        DatabaseType::ClickHouse => {
-            let guard = app_state.clickhouse_config.lock().await;
-            let config = guard.as_ref().ok_or("No ClickHouse connection established")?;
-            let res = clickhouse::execute_query(
-                config,
-                query.clone(),
-            )
-            .await?;
-            (res, None)
-        }
-        DatabaseType::SQLite => {
-            let guard = app_state.sqlite_pool.lock().await;
-            let pool = guard.as_ref().ok_or("No SQLite connection established")?;
-            let res = sqlite::execute_query(pool, &query).await?;
-            (res, None)
-        }
-        DatabaseType::Disconnected => return Err("No connection established".into()),
-    };
+    }
 
     let duration_ms = (chrono::Utc::now() - start_time).num_milliseconds() as f64;
 
@@ -417,6 +353,7 @@ pub async fn execute_query_profiled(
     spawn_awareness_log(
         &app_state,
         query.clone(),
+        None,
         duration_ms,
         rows_affected,
         db_type.clone(),
@@ -427,86 +364,9 @@ pub async fn execute_query_profiled(
         results,
         duration_ms,
         status_diff,
+        execution_id: Some(execution_id),
     })
 }
 
-#[tauri::command]
-pub async fn cancel_running_query(
-    app_state: State<'_, AppState>,
-) -> Result<String, String> {
-    let db_type = {
-        let guard = app_state.active_db_type.lock().await;
-        guard.clone()
-    };
-
-    let pid_opt = {
-        let guard = app_state.running_query_pid.lock().await;
-        *guard
-    };
-
-    let Some(process_id) = pid_opt else {
-        return Err("No running query to cancel".to_string());
-    };
-
-    {
-        let mut guard = app_state.query_cancel_requested.lock().await;
-        *guard = true;
-    }
-
-    let result = match db_type {
-        DatabaseType::PostgreSQL => {
-            let guard = app_state.postgres_pool.lock().await;
-            let pool = guard
-                .as_ref()
-                .ok_or("No PostgreSQL connection established")?;
-            postgres::kill_process(pool, process_id).await
-        }
-        DatabaseType::MySQL => {
-            let guard = app_state.mysql_pool.lock().await;
-            let pool = guard.as_ref().ok_or("No MySQL connection established")?;
-            mysql::kill_process(pool, process_id).await
-        }
-        DatabaseType::MSSQL => {
-            let guard = app_state.mssql_pool.lock().await;
-            let pool = guard.as_ref().ok_or("No MSSQL connection established")?;
-            mssql::kill_process(pool, process_id).await
-        }
-        DatabaseType::ClickHouse => {
-            let guard = app_state.clickhouse_config.lock().await;
-            let config = guard.as_ref().ok_or("No ClickHouse connection established")?;
-            clickhouse::kill_query(config, process_id).await
-        }
-        DatabaseType::SQLite => {
-            Err("Cancel not applicable for SQLite".to_string())
-        }
-        DatabaseType::Disconnected => Err("No connection established".into()),
-    };
-
-    {
-        let mut guard = app_state.running_query_pid.lock().await;
-        *guard = None;
-    }
-
-    result
-}
-
-#[tauri::command]
-pub async fn get_running_query_pid(
-    app_state: State<'_, AppState>,
-) -> Result<Option<i64>, String> {
-    let guard = app_state.running_query_pid.lock().await;
-    Ok(*guard)
-}
-
-#[tauri::command]
-pub async fn set_running_query_pid(
-    app_state: State<'_, AppState>,
-    pid: Option<i64>,
-) -> Result<(), String> {
-    let mut guard = app_state.running_query_pid.lock().await;
-    *guard = pid;
-    Ok(())
-}
-
 #[cfg(test)]
 mod tests;
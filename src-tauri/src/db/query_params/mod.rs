@@ -0,0 +1,648 @@
+// =====================================================
+// QUERY PARAMETERS MODULE
+// Named and positional placeholders mapped onto engine-native binds
+// =====================================================
+
+use crate::clickhouse;
+use crate::db_types::{AppState, BindValue, DatabaseType, QueryParameter, QueryResult};
+use base64::Engine;
+use serde_json::Value;
+use std::collections::HashMap;
+use tauri::State;
+
+use super::running_queries::{annotate_cancelled, CancelHandle, ExecutionGuard};
+use super::sql_lexer::{self, SqlLexer};
+use super::transactions::pin_connection;
+
+// =====================================================
+// STRUCTS
+// =====================================================
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlaceholderRef {
+    /// `:name` or `@name`
+    Named(String),
+    /// `$1`, 1-based over the unnamed parameters
+    Numbered(usize),
+    /// `?`, the next unnamed parameter
+    Next,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaceholderToken {
+    pub start: usize,
+    pub end: usize,
+    pub reference: PlaceholderRef,
+}
+
+/// A query rewritten to the engine's placeholder syntax with its binds in order.
+#[derive(Debug, Clone, Default)]
+pub struct BoundQuery {
+    pub sql: String,
+    pub binds: Vec<BindValue>,
+    /// ClickHouse `param_<name>` values, sent alongside the HTTP request
+    pub clickhouse_params: Vec<(String, String)>,
+    pub warnings: Vec<String>,
+}
+
+// =====================================================
+// VALUE CONVERSION
+// =====================================================
+
+fn parameter_label(param: &QueryParameter, index: usize) -> String {
+    match param.name.as_deref().filter(|n| !n.is_empty()) {
+        Some(name) => format!(":{}", name.trim_start_matches([':', '@', '$'])),
+        None => format!("#{}", index + 1),
+    }
+}
+
+fn value_as_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn parse_bool(value: &Value) -> Option<bool> {
+    match value {
+        Value::Bool(b) => Some(*b),
+        Value::Number(n) => n.as_i64().map(|n| n != 0),
+        Value::String(s) => match s.trim().to_lowercase().as_str() {
+            "true" | "t" | "1" | "yes" | "y" => Some(true),
+            "false" | "f" | "0" | "no" | "n" => Some(false),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn parse_int(value: &Value) -> Option<i64> {
+    match value {
+        Value::Number(n) => n
+            .as_i64()
+            .or_else(|| n.as_f64().filter(|f| f.fract() == 0.0).map(|f| f as i64)),
+        Value::String(s) => s.trim().parse().ok(),
+        Value::Bool(b) => Some(*b as i64),
+        _ => None,
+    }
+}
+
+fn parse_float(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn parse_naive_datetime(s: &str) -> Option<chrono::NaiveDateTime> {
+    [
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M",
+    ]
+    .iter()
+    .find_map(|fmt| chrono::NaiveDateTime::parse_from_str(s, fmt).ok())
+}
+
+/// Converts a JSON parameter into a typed bind, honouring `type_hint` when set.
+pub fn to_bind_value(param: &QueryParameter) -> Result<BindValue, String> {
+    let hint = param
+        .type_hint
+        .as_deref()
+        .map(|h| h.trim().to_lowercase())
+        .filter(|h| !h.is_empty());
+    let value = &param.value;
+    let is_null = value.is_null();
+    let invalid = |kind: &str| format!("Value {} is not a valid {}", value, kind);
+
+    let Some(hint) = hint else {
+        return Ok(match value {
+            Value::Null => BindValue::Text(None),
+            Value::Bool(b) => BindValue::Bool(Some(*b)),
+            Value::Number(n) => match n.as_i64() {
+                Some(i) => BindValue::Int(Some(i)),
+                None if n.is_u64() => BindValue::Decimal(Some(n.to_string())),
+                None => BindValue::Float(n.as_f64()),
+            },
+            Value::String(s) => BindValue::Text(Some(s.clone())),
+            Value::Array(_) | Value::Object(_) => BindValue::Json(Some(value.clone())),
+        });
+    };
+
+    let bind = match hint.as_str() {
+        "bool" | "boolean" | "bit" => BindValue::Bool(if is_null {
+            None
+        } else {
+            Some(parse_bool(value).ok_or_else(|| invalid("boolean"))?)
+        }),
+        "int" | "integer" | "bigint" | "smallint" | "tinyint" | "long" | "int32" | "int64" => {
+            BindValue::Int(if is_null {
+                None
+            } else {
+                Some(parse_int(value).ok_or_else(|| invalid("integer"))?)
+            })
+        }
+        "float" | "double" | "real" | "float32" | "float64" => BindValue::Float(if is_null {
+            None
+        } else {
+            Some(parse_float(value).ok_or_else(|| invalid("float"))?)
+        }),
+        "decimal" | "numeric" | "money" => BindValue::Decimal(if is_null {
+            None
+        } else {
+            let text = value_as_text(value).trim().to_string();
+            text.parse::<f64>().map_err(|_| invalid("decimal"))?;
+            Some(text)
+        }),
+        "text" | "string" | "varchar" | "char" | "nvarchar" | "nchar" => {
+            BindValue::Text(if is_null {
+                None
+            } else {
+                Some(value_as_text(value))
+            })
+        }
+        "json" | "jsonb" => BindValue::Json(if is_null {
+            None
+        } else if let Value::String(s) = value {
+            // Accept pre-serialized JSON text as well as structured values
+            Some(serde_json::from_str(s).unwrap_or_else(|_| value.clone()))
+        } else {
+            Some(value.clone())
+        }),
+        "bytes" | "binary" | "varbinary" | "blob" | "bytea" => BindValue::Bytes(match value {
+            Value::Null => None,
+            Value::String(s) => Some(
+                base64::engine::general_purpose::STANDARD
+                    .decode(s.trim())
+                    .map_err(|_| invalid("base64 byte string"))?,
+            ),
+            Value::Array(items) => Some(
+                items
+                    .iter()
+                    .map(|v| v.as_u64().filter(|b| *b <= 255).map(|b| b as u8))
+                    .collect::<Option<Vec<u8>>>()
+                    .ok_or_else(|| invalid("byte array"))?,
+            ),
+            _ => return Err(invalid("byte string")),
+        }),
+        "date" => BindValue::Date(if is_null {
+            None
+        } else {
+            Some(
+                chrono::NaiveDate::parse_from_str(value_as_text(value).trim(), "%Y-%m-%d")
+                    .map_err(|_| invalid("date (YYYY-MM-DD)"))?,
+            )
+        }),
+        "time" => BindValue::Time(if is_null {
+            None
+        } else {
+            Some(
+                chrono::NaiveTime::parse_from_str(value_as_text(value).trim(), "%H:%M:%S%.f")
+                    .or_else(|_| {
+                        chrono::NaiveTime::parse_from_str(value_as_text(value).trim(), "%H:%M")
+                    })
+                    .map_err(|_| invalid("time (HH:MM:SS)"))?,
+            )
+        }),
+        "datetime" | "datetime2" | "timestamp" => {
+            if is_null {
+                BindValue::DateTime(None)
+            } else {
+                let text = value_as_text(value);
+                match chrono::DateTime::parse_from_rfc3339(text.trim()) {
+                    Ok(dt) => BindValue::Timestamp(Some(dt.with_timezone(&chrono::Utc))),
+                    Err(_) => BindValue::DateTime(Some(
+                        parse_naive_datetime(text.trim()).ok_or_else(|| invalid("datetime"))?,
+                    )),
+                }
+            }
+        }
+        "timestamptz" | "datetimeoffset" => BindValue::Timestamp(if is_null {
+            None
+        } else {
+            Some(
+                chrono::DateTime::parse_from_rfc3339(value_as_text(value).trim())
+                    .map_err(|_| invalid("RFC 3339 timestamp"))?
+                    .with_timezone(&chrono::Utc),
+            )
+        }),
+        "uuid" | "uniqueidentifier" => BindValue::Uuid(if is_null {
+            None
+        } else {
+            let text = value_as_text(value).trim().to_string();
+            uuid::Uuid::parse_str(&text).map_err(|_| invalid("uuid"))?;
+            Some(text)
+        }),
+        "null" => BindValue::Text(None),
+        other => return Err(format!("Unsupported parameter type: {}", other)),
+    };
+    Ok(bind)
+}
+
+/// Accepts either an array of `QueryParameter` objects or a plain `{ name: value }` map.
+pub fn parameters_from_json(value: &Value) -> Result<Vec<QueryParameter>, String> {
+    match value {
+        Value::Null => Ok(Vec::new()),
+        Value::Array(_) => serde_json::from_value(value.clone())
+            .map_err(|e| format!("Invalid query parameters: {}", e)),
+        Value::Object(map) => Ok(map
+            .iter()
+            .map(|(name, value)| QueryParameter {
+                name: Some(name.clone()),
+                value: value.clone(),
+                type_hint: None,
+            })
+            .collect()),
+        _ => Err("Query parameters must be an array or an object".to_string()),
+    }
+}
+
+// =====================================================
+// PLACEHOLDER SCANNING
+// =====================================================
+
+fn is_ident_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// Finds bind placeholders outside string literals, quoted identifiers, comments
+/// and PostgreSQL dollar-quoted bodies, as the script splitter lexes them.
+pub fn scan_placeholders(query: &str, db_type: &DatabaseType) -> Vec<PlaceholderToken> {
+    let chars: Vec<char> = query.chars().collect();
+    let offsets: Vec<usize> = query
+        .char_indices()
+        .map(|(offset, _)| offset)
+        .chain(std::iter::once(query.len()))
+        .collect();
+    let mut lexer = SqlLexer::new(db_type);
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        if !lexer.in_code() {
+            i += lexer.step(&chars[i..]);
+            continue;
+        }
+
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let prev = i.checked_sub(1).map(|p| chars[p]);
+        let e_prefix =
+            matches!(prev, Some('E' | 'e')) && (i < 2 || !sql_lexer::is_ident_char(chars[i - 2]));
+        if let Some((_, len)) = lexer.open(&chars[i..], prev, e_prefix) {
+            i += len;
+            continue;
+        }
+
+        match c {
+            '$' if next.is_some_and(|n| n.is_ascii_digit()) => {
+                let mut end = i + 1;
+                while end < chars.len() && chars[end].is_ascii_digit() {
+                    end += 1;
+                }
+                let number = query[offsets[i + 1]..offsets[end]].parse().unwrap_or(0);
+                tokens.push(PlaceholderToken {
+                    start: offsets[i],
+                    end: offsets[end],
+                    reference: PlaceholderRef::Numbered(number),
+                });
+                i = end;
+            }
+            '?' => {
+                // `?|` and `?&` are PostgreSQL jsonb operators
+                if *db_type == DatabaseType::PostgreSQL && matches!(next, Some('|') | Some('&')) {
+                    i += 2;
+                } else {
+                    tokens.push(PlaceholderToken {
+                        start: offsets[i],
+                        end: offsets[i + 1],
+                        reference: PlaceholderRef::Next,
+                    });
+                    i += 1;
+                }
+            }
+            ':' | '@' => {
+                if next == Some(c) {
+                    // `::type` casts and `@@system_variables`
+                    i += 2;
+                    while i < chars.len() && is_ident_char(chars[i]) {
+                        i += 1;
+                    }
+                } else if next.is_some_and(is_ident_start) && !prev.is_some_and(is_ident_char) {
+                    let mut end = i + 1;
+                    while end < chars.len() && is_ident_char(chars[end]) {
+                        end += 1;
+                    }
+                    tokens.push(PlaceholderToken {
+                        start: offsets[i],
+                        end: offsets[end],
+                        reference: PlaceholderRef::Named(
+                            query[offsets[i + 1]..offsets[end]].to_string(),
+                        ),
+                    });
+                    i = end;
+                } else {
+                    i += 1;
+                }
+            }
+            _ => i += 1,
+        }
+    }
+
+    tokens
+}
+
+// =====================================================
+// BINDING
+// =====================================================
+
+fn clickhouse_param(value: &BindValue) -> Result<(String, Option<String>), String> {
+    let escape = |s: &str| {
+        s.replace('\\', "\\\\")
+            .replace('\t', "\\t")
+            .replace('\n', "\\n")
+    };
+    let (ty, text) = match value {
+        BindValue::Bool(v) => ("Bool", v.map(|b| b.to_string())),
+        BindValue::Int(v) => ("Int64", v.map(|n| n.to_string())),
+        BindValue::Float(v) => ("Float64", v.map(|n| n.to_string())),
+        BindValue::Decimal(v) => ("Decimal(38, 10)", v.clone()),
+        BindValue::Text(v) => ("String", v.as_deref().map(escape)),
+        BindValue::Json(v) => ("String", v.as_ref().map(|j| escape(&j.to_string()))),
+        BindValue::Bytes(_) => {
+            return Err("Binary parameters are not supported for ClickHouse".into())
+        }
+        BindValue::Date(v) => ("Date", v.map(|d| d.format("%Y-%m-%d").to_string())),
+        BindValue::Time(v) => ("String", v.map(|t| t.format("%H:%M:%S%.f").to_string())),
+        BindValue::DateTime(v) => (
+            "DateTime64(6)",
+            v.map(|d| d.format("%Y-%m-%d %H:%M:%S%.6f").to_string()),
+        ),
+        BindValue::Timestamp(v) => (
+            "DateTime64(6, 'UTC')",
+            v.map(|d| d.format("%Y-%m-%d %H:%M:%S%.6f").to_string()),
+        ),
+        BindValue::Uuid(v) => ("UUID", v.clone()),
+    };
+    Ok((ty.to_string(), text))
+}
+
+/// Rewrites `query`'s placeholders into the syntax `db_type` expects and lines up
+/// the typed binds. Named placeholders without a matching parameter are left
+/// untouched, since `@var` and `:name` can be legitimate SQL.
+pub fn bind_query_parameters(
+    query: &str,
+    params: &[QueryParameter],
+    db_type: &DatabaseType,
+) -> Result<BoundQuery, String> {
+    let values = params
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            to_bind_value(p).map_err(|e| format!("Parameter {}: {}", parameter_label(p, i), e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut named: HashMap<String, usize> = HashMap::new();
+    let mut positional: Vec<usize> = Vec::new();
+    for (i, param) in params.iter().enumerate() {
+        match param
+            .name
+            .as_deref()
+            .map(|n| n.trim_start_matches([':', '@', '$']))
+        {
+            Some(name) if !name.is_empty() => {
+                named.insert(name.to_lowercase(), i);
+            }
+            _ => positional.push(i),
+        }
+    }
+
+    let mut bound = BoundQuery::default();
+    let mut used = vec![false; params.len()];
+    let mut slots: HashMap<usize, usize> = HashMap::new();
+    let mut next_positional = 0;
+    let mut cursor = 0;
+
+    for token in scan_placeholders(query, db_type) {
+        let param_index = match &token.reference {
+            PlaceholderRef::Named(name) => match named.get(&name.to_lowercase()) {
+                Some(index) => *index,
+                None => continue,
+            },
+            PlaceholderRef::Numbered(n) => *positional
+                .get(n.wrapping_sub(1))
+                .ok_or_else(|| format!("Missing value for parameter ${}", n))?,
+            PlaceholderRef::Next => {
+                if positional.is_empty() {
+                    continue;
+                }
+                let index = *positional.get(next_positional).ok_or_else(|| {
+                    format!("Missing value for parameter #{}", next_positional + 1)
+                })?;
+                next_positional += 1;
+                index
+            }
+        };
+        used[param_index] = true;
+        bound.sql.push_str(&query[cursor..token.start]);
+        cursor = token.end;
+
+        let value = &values[param_index];
+        match db_type {
+            DatabaseType::MySQL | DatabaseType::SQLite => {
+                bound.sql.push('?');
+                bound.binds.push(value.clone());
+            }
+            DatabaseType::PostgreSQL | DatabaseType::MSSQL | DatabaseType::ClickHouse => {
+                let slot = match slots.get(&param_index) {
+                    Some(slot) => *slot,
+                    None => {
+                        bound.binds.push(value.clone());
+                        slots.insert(param_index, bound.binds.len());
+                        bound.binds.len()
+                    }
+                };
+                match db_type {
+                    DatabaseType::PostgreSQL => {
+                        bound.sql.push_str(&format!("${}", slot));
+                        match value {
+                            BindValue::Decimal(_) => bound.sql.push_str("::numeric"),
+                            BindValue::Uuid(_) => bound.sql.push_str("::uuid"),
+                            _ => {}
+                        }
+                    }
+                    DatabaseType::MSSQL => bound.sql.push_str(&format!("@P{}", slot)),
+                    _ => {
+                        let (ty, text) = clickhouse_param(value)?;
+                        let name = format!("p{}", slot);
+                        match text {
+                            Some(text) => {
+                                bound.sql.push_str(&format!("{{{}:{}}}", name, ty));
+                                if bound.clickhouse_params.iter().all(|(n, _)| n != &name) {
+                                    bound.clickhouse_params.push((name, text));
+                                }
+                            }
+                            None => {
+                                bound
+                                    .sql
+                                    .push_str(&format!("{{{}:Nullable({})}}", name, ty));
+                                if bound.clickhouse_params.iter().all(|(n, _)| n != &name) {
+                                    bound.clickhouse_params.push((name, "\\N".to_string()));
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            DatabaseType::Disconnected => return Err("No connection established".into()),
+        }
+    }
+    bound.sql.push_str(&query[cursor..]);
+
+    for (i, param) in params.iter().enumerate() {
+        if !used[i] {
+            bound.warnings.push(format!(
+                "Parameter {} is not referenced by the query",
+                parameter_label(param, i)
+            ));
+        }
+    }
+
+    Ok(bound)
+}
+
+// =====================================================
+// EXECUTION
+// =====================================================
+
+/// Binds `params` and runs `query` on the session's engine, inside its open
+/// transaction when there is one.
+pub async fn execute_query_with_parameters(
+    app_state: &AppState,
+    query: &str,
+    params: &[QueryParameter],
+    query_timeout_seconds: Option<u64>,
+) -> Result<Vec<QueryResult>, String> {
+    let db_type = {
+        let guard = app_state.active_db_type.lock().await;
+        guard.clone()
+    };
+    let bound = bind_query_parameters(query, params, &db_type)?;
+
+    let execution = crate::db::register_execution(app_state, query, None).await?;
+    let in_transaction = crate::db::execute_parameterized_in_transaction(
+        app_state,
+        &execution,
+        &bound.sql,
+        &bound.binds,
+        query_timeout_seconds,
+    )
+    .await?;
+
+    let mut results = match in_transaction {
+        Some(results) => results,
+        None => execute_bound_cancellable(
+            app_state,
+            &execution,
+            &db_type,
+            &bound,
+            query_timeout_seconds,
+        )
+        .await
+        .map_err(|e| annotate_cancelled(&execution, e))?,
+    };
+
+    if !bound.warnings.is_empty() {
+        for result in results.iter_mut() {
+            result.warnings.extend(bound.warnings.clone());
+        }
+    }
+    Ok(results)
+}
+
+/// Runs a bound query on a dedicated connection whose cancel handle is
+/// registered on `execution` while it runs.
+async fn execute_bound_cancellable(
+    app_state: &AppState,
+    execution: &ExecutionGuard,
+    db_type: &DatabaseType,
+    bound: &BoundQuery,
+    query_timeout_seconds: Option<u64>,
+) -> Result<Vec<QueryResult>, String> {
+    if *db_type == DatabaseType::ClickHouse {
+        let config = {
+            let guard = app_state.clickhouse_config.lock().await;
+            guard
+                .clone()
+                .ok_or("No ClickHouse connection established")?
+        };
+        let query_id = uuid::Uuid::new_v4().to_string();
+        execution.set_cancel_handle(CancelHandle::ClickHouse(query_id.clone()));
+        return clickhouse::execute_query_with_params(
+            &config,
+            &bound.sql,
+            &bound.clickhouse_params,
+            &query_id,
+            query_timeout_seconds,
+        )
+        .await;
+    }
+
+    let mut connection = pin_connection(app_state, db_type).await?;
+    if let Some(handle) = connection.cancel_handle().await {
+        execution.set_cancel_handle(handle);
+    }
+    let result = connection
+        .execute_parameterized(&bound.sql, &bound.binds, query_timeout_seconds)
+        .await;
+    execution.clear_cancel_handle();
+    result
+}
+
+// =====================================================
+// TAURI COMMANDS
+// =====================================================
+
+#[tauri::command]
+pub async fn execute_parameterized_query(
+    app_state: State<'_, AppState>,
+    query: String,
+    parameters: Vec<QueryParameter>,
+    query_timeout_seconds: Option<u64>,
+    session_id: Option<String>,
+) -> Result<Vec<QueryResult>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
+    let start_time = chrono::Utc::now();
+    let db_type = {
+        let guard = app_state.active_db_type.lock().await;
+        guard.clone()
+    };
+
+    let results =
+        execute_query_with_parameters(&app_state, &query, &parameters, query_timeout_seconds)
+            .await?;
+
+    let duration_ms = (chrono::Utc::now() - start_time).num_milliseconds() as f64;
+    let rows_affected = results.iter().map(|r| r.rows.len()).sum::<usize>() as u64;
+    crate::db::spawn_awareness_log(
+        &app_state,
+        query,
+        serde_json::to_value(&parameters).ok(),
+        duration_ms,
+        rows_affected,
+        db_type,
+        start_time,
+    );
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,232 @@
+use super::*;
+use serde_json::json;
+
+fn named(name: &str, value: Value) -> QueryParameter {
+    QueryParameter {
+        name: Some(name.to_string()),
+        value,
+        type_hint: None,
+    }
+}
+
+fn positional(value: Value) -> QueryParameter {
+    QueryParameter {
+        name: None,
+        value,
+        type_hint: None,
+    }
+}
+
+fn typed(name: &str, value: Value, hint: &str) -> QueryParameter {
+    QueryParameter {
+        name: Some(name.to_string()),
+        value,
+        type_hint: Some(hint.to_string()),
+    }
+}
+
+#[test]
+fn test_to_bind_value_infers_from_json() {
+    assert_eq!(
+        to_bind_value(&positional(json!(5))).unwrap(),
+        BindValue::Int(Some(5))
+    );
+    assert_eq!(
+        to_bind_value(&positional(json!(1.5))).unwrap(),
+        BindValue::Float(Some(1.5))
+    );
+    assert_eq!(
+        to_bind_value(&positional(json!("a"))).unwrap(),
+        BindValue::Text(Some("a".into()))
+    );
+    assert_eq!(
+        to_bind_value(&positional(Value::Null)).unwrap(),
+        BindValue::Text(None)
+    );
+    assert_eq!(
+        to_bind_value(&positional(json!({"a": 1}))).unwrap(),
+        BindValue::Json(Some(json!({"a": 1})))
+    );
+}
+
+#[test]
+fn test_to_bind_value_honours_type_hint() {
+    assert_eq!(
+        to_bind_value(&typed("n", json!("42"), "bigint")).unwrap(),
+        BindValue::Int(Some(42))
+    );
+    assert_eq!(
+        to_bind_value(&typed("n", Value::Null, "int")).unwrap(),
+        BindValue::Int(None)
+    );
+    assert_eq!(
+        to_bind_value(&typed("b", json!("yes"), "boolean")).unwrap(),
+        BindValue::Bool(Some(true))
+    );
+    assert_eq!(
+        to_bind_value(&typed("d", json!("2024-02-29"), "date")).unwrap(),
+        BindValue::Date(chrono::NaiveDate::from_ymd_opt(2024, 2, 29))
+    );
+    assert!(matches!(
+        to_bind_value(&typed("t", json!("2024-01-01T10:00:00Z"), "timestamp")).unwrap(),
+        BindValue::Timestamp(Some(_))
+    ));
+    assert!(matches!(
+        to_bind_value(&typed("t", json!("2024-01-01 10:00:00"), "datetime")).unwrap(),
+        BindValue::DateTime(Some(_))
+    ));
+    assert_eq!(
+        to_bind_value(&typed("x", json!("AQID"), "bytea")).unwrap(),
+        BindValue::Bytes(Some(vec![1, 2, 3]))
+    );
+    assert!(to_bind_value(&typed("n", json!("abc"), "int")).is_err());
+    assert!(to_bind_value(&typed("u", json!("not-a-uuid"), "uuid")).is_err());
+    assert!(to_bind_value(&typed("x", json!(1), "geometry")).is_err());
+}
+
+#[test]
+fn test_scan_placeholders_skips_literals_and_comments() {
+    let query = "SELECT ':skip', \"@skip\", x::int FROM t -- :skip\nWHERE a = :a /* ? */ AND b = ?";
+    let refs: Vec<PlaceholderRef> = scan_placeholders(query, &DatabaseType::PostgreSQL)
+        .into_iter()
+        .map(|t| t.reference)
+        .collect();
+    assert_eq!(
+        refs,
+        vec![PlaceholderRef::Named("a".into()), PlaceholderRef::Next]
+    );
+
+    let dollar = "CREATE FUNCTION f() RETURNS int AS $body$ SELECT $1 $body$; SELECT $2";
+    let refs: Vec<PlaceholderRef> = scan_placeholders(dollar, &DatabaseType::PostgreSQL)
+        .into_iter()
+        .map(|t| t.reference)
+        .collect();
+    assert_eq!(refs, vec![PlaceholderRef::Numbered(2)]);
+
+    assert!(scan_placeholders("SELECT @@VERSION, [col:x]", &DatabaseType::MSSQL).is_empty());
+}
+
+#[test]
+fn test_scan_placeholders_follows_splitter_lexing() {
+    let names = |query: &str, db_type: &DatabaseType| -> Vec<PlaceholderRef> {
+        scan_placeholders(query, db_type)
+            .into_iter()
+            .map(|t| t.reference)
+            .collect()
+    };
+
+    // ClickHouse strings take backslash escapes, as in the script splitter
+    let clickhouse = "SELECT 'it\\'s :skip' AS a, :b";
+    assert_eq!(
+        names(clickhouse, &DatabaseType::ClickHouse),
+        vec![PlaceholderRef::Named("b".into())]
+    );
+    assert_eq!(
+        crate::db::script_runner::split_sql_script(clickhouse, &DatabaseType::ClickHouse).len(),
+        1
+    );
+
+    // MySQL needs whitespace after `--` for a comment
+    assert_eq!(
+        names("SELECT 1--:a", &DatabaseType::MySQL),
+        vec![PlaceholderRef::Named("a".into())]
+    );
+    assert_eq!(
+        names("SELECT E'\\' :skip' , :b", &DatabaseType::PostgreSQL),
+        vec![PlaceholderRef::Named("b".into())]
+    );
+}
+
+#[test]
+fn test_bind_query_parameters_per_engine() {
+    let params = vec![named("id", json!(7)), named("name", json!("bob"))];
+    let query = "SELECT * FROM users WHERE id = :id OR parent_id = :id AND name = @name";
+
+    let mysql = bind_query_parameters(query, &params, &DatabaseType::MySQL).unwrap();
+    assert_eq!(
+        mysql.sql,
+        "SELECT * FROM users WHERE id = ? OR parent_id = ? AND name = ?"
+    );
+    assert_eq!(mysql.binds.len(), 3);
+
+    let pg = bind_query_parameters(query, &params, &DatabaseType::PostgreSQL).unwrap();
+    assert_eq!(
+        pg.sql,
+        "SELECT * FROM users WHERE id = $1 OR parent_id = $1 AND name = $2"
+    );
+    assert_eq!(
+        pg.binds,
+        vec![BindValue::Int(Some(7)), BindValue::Text(Some("bob".into()))]
+    );
+
+    let mssql = bind_query_parameters(query, &params, &DatabaseType::MSSQL).unwrap();
+    assert_eq!(
+        mssql.sql,
+        "SELECT * FROM users WHERE id = @P1 OR parent_id = @P1 AND name = @P2"
+    );
+
+    let ch = bind_query_parameters(query, &params, &DatabaseType::ClickHouse).unwrap();
+    assert_eq!(ch.sql, "SELECT * FROM users WHERE id = {p1:Int64} OR parent_id = {p1:Int64} AND name = {p2:String}");
+    assert_eq!(
+        ch.clickhouse_params,
+        vec![
+            ("p1".to_string(), "7".to_string()),
+            ("p2".to_string(), "bob".to_string())
+        ]
+    );
+}
+
+#[test]
+fn test_bind_query_parameters_positional_and_warnings() {
+    let params = vec![
+        positional(json!(1)),
+        positional(json!(2)),
+        named("unused", json!(3)),
+    ];
+    let bound = bind_query_parameters("SELECT ? + $2", &params, &DatabaseType::PostgreSQL).unwrap();
+    assert_eq!(bound.sql, "SELECT $1 + $2");
+    assert_eq!(bound.warnings.len(), 1);
+    assert!(bound.warnings[0].contains(":unused"));
+
+    let missing = bind_query_parameters("SELECT ?, ?, ?", &params, &DatabaseType::MySQL);
+    assert!(missing.is_err());
+
+    // MySQL user variables stay untouched when no parameter claims them
+    let vars = bind_query_parameters("SET @total = ?", &params[..1], &DatabaseType::MySQL).unwrap();
+    assert_eq!(vars.sql, "SET @total = ?");
+    assert_eq!(vars.binds, vec![BindValue::Int(Some(1))]);
+}
+
+#[test]
+fn test_bind_query_parameters_casts_and_nulls() {
+    let params = vec![
+        typed("amount", json!("10.50"), "decimal"),
+        typed("note", Value::Null, "text"),
+    ];
+    let pg = bind_query_parameters(
+        "UPDATE t SET amount = :amount, note = :note",
+        &params,
+        &DatabaseType::PostgreSQL,
+    )
+    .unwrap();
+    assert_eq!(pg.sql, "UPDATE t SET amount = $1::numeric, note = $2");
+
+    let ch =
+        bind_query_parameters("SELECT :note", &params[1..], &DatabaseType::ClickHouse).unwrap();
+    assert_eq!(ch.sql, "SELECT {p1:Nullable(String)}");
+    assert_eq!(ch.clickhouse_params[0].1, "\\N");
+}
+
+#[test]
+fn test_parameters_from_json() {
+    let from_map = parameters_from_json(&json!({"id": 5})).unwrap();
+    assert_eq!(from_map.len(), 1);
+    assert_eq!(from_map[0].name.as_deref(), Some("id"));
+
+    let from_array =
+        parameters_from_json(&json!([{"name": "d", "value": "2024-01-01", "typeHint": "date"}]))
+            .unwrap();
+    assert_eq!(from_array[0].type_hint.as_deref(), Some("date"));
+
+    assert!(parameters_from_json(&json!("nope")).is_err());
+}
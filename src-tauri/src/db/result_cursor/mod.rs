@@ -0,0 +1,469 @@
+// =====================================================
+// RESULT CURSOR MODULE
+// Streaming, paginated query results fetched on demand
+// =====================================================
+
+use crate::clickhouse;
+use crate::db_types::{AppState, ConnectionConfig, DatabaseType, ResultStreamEvent};
+use crate::mssql;
+use crate::mysql;
+use crate::postgres;
+use crate::sqlite;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::{Arc, LazyLock};
+use tauri::State;
+use tokio::sync::{mpsc, watch, Mutex};
+
+// =====================================================
+// CONSTANTS
+// =====================================================
+
+pub const DEFAULT_RESULT_PAGE_SIZE: usize = 500;
+pub const MAX_RESULT_PAGE_SIZE: usize = 10_000;
+/// Number of row batches buffered ahead of the reader before the engine blocks.
+const RESULT_CURSOR_CHANNEL_CAPACITY: usize = 8;
+const RESULT_CURSOR_IDLE_TIMEOUT_SECS: i64 = 900;
+
+// =====================================================
+// GLOBAL STATE
+// =====================================================
+
+pub static RESULT_CURSORS: LazyLock<Mutex<HashMap<String, Arc<ResultCursorHandle>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+// =====================================================
+// STRUCTS
+// =====================================================
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultPage {
+    pub cursor_id: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+    pub rows_fetched: u64,
+    pub has_more: bool,
+    pub query_id: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultCursorInfo {
+    pub cursor_id: String,
+    pub session_id: Option<String>,
+    pub db_type: DatabaseType,
+    pub query: String,
+    pub rows_fetched: u64,
+    pub opened_at: i64,
+    pub last_access: i64,
+}
+
+pub struct ResultCursorState {
+    pub columns: Vec<String>,
+    pub rows_fetched: u64,
+    pub done: bool,
+    pub error: Option<String>,
+    pub last_access: i64,
+    pending: VecDeque<Vec<Value>>,
+}
+
+/// An open result stream. The engine task feeds `receiver`; closing the
+/// receiver makes the engine stop reading and abandon its connection.
+/// `state` is only locked briefly, so a close never waits behind a fetch.
+pub struct ResultCursorHandle {
+    pub cursor_id: String,
+    pub session_id: Option<String>,
+    pub db_type: DatabaseType,
+    pub query: String,
+    pub opened_at: i64,
+    pub query_id: Option<String>,
+    pub clickhouse_config: Option<ConnectionConfig>,
+    pub state: Mutex<ResultCursorState>,
+    /// Held by the fetch reading from the stream, one at a time
+    receiver: Mutex<mpsc::Receiver<ResultStreamEvent>>,
+    closed: watch::Sender<bool>,
+}
+
+impl ResultCursorHandle {
+    pub fn new(
+        cursor_id: String,
+        session_id: Option<String>,
+        db_type: DatabaseType,
+        query: String,
+        receiver: mpsc::Receiver<ResultStreamEvent>,
+    ) -> Self {
+        let now = chrono::Utc::now().timestamp();
+        Self {
+            cursor_id,
+            session_id,
+            db_type,
+            query,
+            opened_at: now,
+            query_id: None,
+            clickhouse_config: None,
+            state: Mutex::new(ResultCursorState {
+                columns: Vec::new(),
+                rows_fetched: 0,
+                done: false,
+                error: None,
+                last_access: now,
+                pending: VecDeque::new(),
+            }),
+            receiver: Mutex::new(receiver),
+            closed: watch::Sender::new(false),
+        }
+    }
+
+    /// Reads from the stream until `page_size` rows are buffered or the stream
+    /// ends, then hands back that page.
+    pub async fn fetch_page(&self, page_size: usize) -> ResultPage {
+        let page_size = page_size.clamp(1, MAX_RESULT_PAGE_SIZE);
+        let mut receiver = self.receiver.lock().await;
+        let mut closed = self.closed.subscribe();
+
+        loop {
+            {
+                let state = self.state.lock().await;
+                if state.pending.len() >= page_size || state.done {
+                    break;
+                }
+            }
+            let event = tokio::select! {
+                event = receiver.recv() => event,
+                _ = closed.wait_for(|closed| *closed) => {
+                    receiver.close();
+                    None
+                }
+            };
+
+            let mut state = self.state.lock().await;
+            if *self.closed.borrow() {
+                // A close in between already emptied the page
+                receiver.close();
+                break;
+            }
+            match event {
+                Some(ResultStreamEvent::Columns(columns)) => state.columns = columns,
+                Some(ResultStreamEvent::Rows(rows)) => state.pending.extend(rows),
+                Some(ResultStreamEvent::Error(err)) => {
+                    state.error = Some(err);
+                    state.done = true;
+                }
+                None => state.done = true,
+            }
+        }
+        drop(receiver);
+
+        let mut state = self.state.lock().await;
+        let take = page_size.min(state.pending.len());
+        let rows: Vec<Vec<Value>> = state.pending.drain(..take).collect();
+        state.rows_fetched += rows.len() as u64;
+        state.last_access = chrono::Utc::now().timestamp();
+
+        ResultPage {
+            cursor_id: self.cursor_id.clone(),
+            columns: state.columns.clone(),
+            rows,
+            rows_fetched: state.rows_fetched,
+            has_more: !state.done || !state.pending.is_empty(),
+            query_id: self.query_id.clone(),
+            error: state.error.clone(),
+        }
+    }
+
+    pub async fn info(&self) -> ResultCursorInfo {
+        let state = self.state.lock().await;
+        ResultCursorInfo {
+            cursor_id: self.cursor_id.clone(),
+            session_id: self.session_id.clone(),
+            db_type: self.db_type.clone(),
+            query: self.query.clone(),
+            rows_fetched: state.rows_fetched,
+            opened_at: self.opened_at,
+            last_access: state.last_access,
+        }
+    }
+
+    /// Stops the engine stream. ClickHouse keeps running a query after the HTTP
+    /// client goes away, so it is killed explicitly by query id.
+    pub async fn close(&self) {
+        // A pending fetch sees this and closes the receiver it holds
+        self.closed.send_replace(true);
+        if let Ok(mut receiver) = self.receiver.try_lock() {
+            receiver.close();
+        }
+        let was_running = {
+            let mut state = self.state.lock().await;
+            state.pending.clear();
+            let was_running = !state.done;
+            state.done = true;
+            was_running
+        };
+
+        if !was_running {
+            return;
+        }
+
+        if let (Some(config), Some(query_id)) = (&self.clickhouse_config, &self.query_id) {
+            if let Err(err) = clickhouse::kill_query_by_id(config, query_id).await {
+                eprintln!("Failed to kill ClickHouse query {}: {}", query_id, err);
+            }
+        }
+    }
+}
+
+// =====================================================
+// CURSOR REGISTRY
+// =====================================================
+
+async fn get_result_cursor(cursor_id: &str) -> Result<Arc<ResultCursorHandle>, String> {
+    let guard = RESULT_CURSORS.lock().await;
+    guard
+        .get(cursor_id)
+        .cloned()
+        .ok_or_else(|| format!("Result cursor not found: {}", cursor_id))
+}
+
+async fn remove_result_cursor(cursor_id: &str) -> Option<Arc<ResultCursorHandle>> {
+    let mut guard = RESULT_CURSORS.lock().await;
+    guard.remove(cursor_id)
+}
+
+async fn prune_idle_result_cursors() {
+    let cutoff = chrono::Utc::now().timestamp() - RESULT_CURSOR_IDLE_TIMEOUT_SECS;
+    let expired: Vec<Arc<ResultCursorHandle>> = {
+        let mut guard = RESULT_CURSORS.lock().await;
+        let expired_ids: Vec<String> = guard
+            .iter()
+            .filter(|(_, cursor)| {
+                // Cursors busy fetching are in use, never idle
+                cursor.receiver.try_lock().is_ok()
+                    && cursor
+                        .state
+                        .try_lock()
+                        .map(|state| state.last_access < cutoff)
+                        .unwrap_or(false)
+            })
+            .map(|(id, _)| id.clone())
+            .collect();
+        expired_ids.iter().filter_map(|id| guard.remove(id)).collect()
+    };
+
+    for cursor in expired {
+        cursor.close().await;
+    }
+}
+
+pub async fn close_result_cursors_for_session(session_id: &str) {
+    let cursors: Vec<Arc<ResultCursorHandle>> = {
+        let mut guard = RESULT_CURSORS.lock().await;
+        let ids: Vec<String> = guard
+            .values()
+            .filter(|cursor| cursor.session_id.as_deref() == Some(session_id))
+            .map(|cursor| cursor.cursor_id.clone())
+            .collect();
+        ids.iter().filter_map(|id| guard.remove(id)).collect()
+    };
+
+    for cursor in cursors {
+        cursor.close().await;
+    }
+}
+
+pub async fn close_all_result_cursors() {
+    let cursors: Vec<Arc<ResultCursorHandle>> = {
+        let mut guard = RESULT_CURSORS.lock().await;
+        guard.drain().map(|(_, cursor)| cursor).collect()
+    };
+
+    for cursor in cursors {
+        cursor.close().await;
+    }
+}
+
+fn spawn_result_stream<F>(tx: mpsc::Sender<ResultStreamEvent>, stream: F)
+where
+    F: Future<Output = Result<(), String>> + Send + 'static,
+{
+    tauri::async_runtime::spawn(async move {
+        if let Err(err) = stream.await {
+            let _ = tx.send(ResultStreamEvent::Error(err)).await;
+        }
+    });
+}
+
+/// Starts streaming `query` into `tx` on a background task. `query_id` tags the
+/// ClickHouse query so it can be killed by id.
+pub(crate) async fn spawn_query_stream(
+    app_state: &AppState,
+    db_type: &DatabaseType,
+    query: String,
+    query_id: &str,
+    tx: mpsc::Sender<ResultStreamEvent>,
+) -> Result<(), String> {
+    let sender = tx.clone();
+
+    match db_type {
+        DatabaseType::MySQL => {
+            let pool = {
+                let guard = app_state.mysql_pool.lock().await;
+                guard.clone().ok_or("No MySQL connection established")?
+            };
+            let normalized_query = {
+                let version_guard = app_state.mysql_version.lock().await;
+                match version_guard.as_ref() {
+                    Some(version) => mysql::normalize_mysql_query(&query, version),
+                    None => query.clone(),
+                }
+            };
+            spawn_result_stream(tx, async move {
+                mysql::stream_query(&pool, &normalized_query, &sender).await
+            });
+        }
+        DatabaseType::PostgreSQL => {
+            let pool = {
+                let guard = app_state.postgres_pool.lock().await;
+                guard.clone().ok_or("No PostgreSQL connection established")?
+            };
+            spawn_result_stream(tx, async move {
+                postgres::stream_query(&pool, &query, &sender).await
+            });
+        }
+        DatabaseType::MSSQL => {
+            let pool = {
+                let guard = app_state.mssql_pool.lock().await;
+                guard.clone().ok_or("No MSSQL connection established")?
+            };
+            spawn_result_stream(tx, async move {
+                mssql::stream_query(&pool, &query, &sender).await
+            });
+        }
+        DatabaseType::SQLite => {
+            let pool = {
+                let guard = app_state.sqlite_pool.lock().await;
+                guard.clone().ok_or("No SQLite connection established")?
+            };
+            spawn_result_stream(tx, async move {
+                sqlite::stream_query(&pool, &query, &sender).await
+            });
+        }
+        DatabaseType::ClickHouse => {
+            let config = {
+                let guard = app_state.clickhouse_config.lock().await;
+                guard.clone().ok_or("No ClickHouse connection established")?
+            };
+            let query_id = query_id.to_string();
+            spawn_result_stream(tx, async move {
+                clickhouse::stream_query(&config, &query, &query_id, &sender).await
+            });
+        }
+        DatabaseType::Disconnected => return Err("No connection established".into()),
+    }
+    Ok(())
+}
+
+async fn fetch_and_release(cursor: &ResultCursorHandle, page_size: usize) -> ResultPage {
+    let page = cursor.fetch_page(page_size).await;
+    if !page.has_more {
+        remove_result_cursor(&cursor.cursor_id).await;
+    }
+    page
+}
+
+// =====================================================
+// TAURI COMMANDS
+// =====================================================
+
+/// Starts streaming `query` and returns its first page. Later pages come from
+/// `fetch_result_page`; a cursor is released once a page reports `hasMore: false`.
+#[tauri::command]
+pub async fn open_result_cursor(
+    app_state: State<'_, AppState>,
+    query: String,
+    page_size: Option<usize>,
+    session_id: Option<String>,
+) -> Result<ResultPage, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
+    prune_idle_result_cursors().await;
+
+    let db_type = {
+        let guard = app_state.active_db_type.lock().await;
+        guard.clone()
+    };
+    let cursor_session_id = {
+        let guard = app_state.active_session_id.lock().await;
+        guard.clone()
+    };
+
+    let cursor_id = uuid::Uuid::new_v4().to_string();
+    let (tx, rx) = mpsc::channel(RESULT_CURSOR_CHANNEL_CAPACITY);
+    let mut cursor = ResultCursorHandle::new(
+        cursor_id.clone(),
+        cursor_session_id,
+        db_type.clone(),
+        query.clone(),
+        rx,
+    );
+    let query_id = format!("tactilesql-cursor-{}", cursor_id);
+    spawn_query_stream(&app_state, &db_type, query, &query_id, tx).await?;
+    if db_type == DatabaseType::ClickHouse {
+        cursor.query_id = Some(query_id);
+        cursor.clickhouse_config = {
+            let guard = app_state.clickhouse_config.lock().await;
+            guard.clone()
+        };
+    }
+
+    let cursor = Arc::new(cursor);
+    {
+        let mut guard = RESULT_CURSORS.lock().await;
+        guard.insert(cursor_id.clone(), cursor.clone());
+    }
+
+    let page = fetch_and_release(&cursor, page_size.unwrap_or(DEFAULT_RESULT_PAGE_SIZE)).await;
+    if page.rows_fetched == 0 {
+        if let Some(err) = page.error {
+            return Err(err);
+        }
+    }
+    Ok(page)
+}
+
+#[tauri::command]
+pub async fn fetch_result_page(
+    cursor_id: String,
+    page_size: Option<usize>,
+) -> Result<ResultPage, String> {
+    let cursor = get_result_cursor(&cursor_id).await?;
+    Ok(fetch_and_release(&cursor, page_size.unwrap_or(DEFAULT_RESULT_PAGE_SIZE)).await)
+}
+
+#[tauri::command]
+pub async fn close_result_cursor(cursor_id: String) -> Result<(), String> {
+    if let Some(cursor) = remove_result_cursor(&cursor_id).await {
+        cursor.close().await;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_result_cursors() -> Result<Vec<ResultCursorInfo>, String> {
+    let cursors: Vec<Arc<ResultCursorHandle>> = {
+        let guard = RESULT_CURSORS.lock().await;
+        guard.values().cloned().collect()
+    };
+
+    let mut infos = Vec::with_capacity(cursors.len());
+    for cursor in cursors {
+        infos.push(cursor.info().await);
+    }
+    infos.sort_by_key(|info| info.opened_at);
+    Ok(infos)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,88 @@
+use super::*;
+use serde_json::json;
+
+fn sample_cursor(rx: mpsc::Receiver<ResultStreamEvent>) -> ResultCursorHandle {
+    ResultCursorHandle::new(
+        "cursor-1".to_string(),
+        Some("session-1".to_string()),
+        DatabaseType::MySQL,
+        "SELECT id FROM t".to_string(),
+        rx,
+    )
+}
+
+fn rows(range: std::ops::Range<i64>) -> Vec<Vec<Value>> {
+    range.map(|i| vec![json!(i)]).collect()
+}
+
+#[tokio::test]
+async fn test_fetch_page_spans_batches() {
+    let (tx, rx) = mpsc::channel(8);
+    let cursor = sample_cursor(rx);
+
+    tx.send(ResultStreamEvent::Columns(vec!["id".to_string()])).await.unwrap();
+    tx.send(ResultStreamEvent::Rows(rows(0..3))).await.unwrap();
+    tx.send(ResultStreamEvent::Rows(rows(3..5))).await.unwrap();
+    drop(tx);
+
+    let first = cursor.fetch_page(4).await;
+    assert_eq!(first.columns, vec!["id".to_string()]);
+    assert_eq!(first.rows.len(), 4);
+    assert_eq!(first.rows_fetched, 4);
+    assert!(first.has_more);
+
+    let second = cursor.fetch_page(4).await;
+    assert_eq!(second.rows, vec![vec![json!(4)]]);
+    assert_eq!(second.rows_fetched, 5);
+    assert!(!second.has_more);
+    assert!(second.error.is_none());
+}
+
+#[tokio::test]
+async fn test_fetch_page_reports_stream_error() {
+    let (tx, rx) = mpsc::channel(8);
+    let cursor = sample_cursor(rx);
+
+    tx.send(ResultStreamEvent::Rows(rows(0..2))).await.unwrap();
+    tx.send(ResultStreamEvent::Error("connection lost".to_string())).await.unwrap();
+
+    let page = cursor.fetch_page(10).await;
+    assert_eq!(page.rows.len(), 2);
+    assert!(!page.has_more);
+    assert_eq!(page.error.as_deref(), Some("connection lost"));
+}
+
+#[tokio::test]
+async fn test_close_stops_sender() {
+    let (tx, rx) = mpsc::channel(1);
+    let cursor = sample_cursor(rx);
+
+    cursor.close().await;
+    assert!(tx.is_closed());
+    assert!(tx.send(ResultStreamEvent::Rows(rows(0..1))).await.is_err());
+
+    let page = cursor.fetch_page(10).await;
+    assert!(page.rows.is_empty());
+    assert!(!page.has_more);
+}
+
+#[tokio::test]
+async fn test_close_does_not_wait_for_pending_fetch() {
+    let (tx, rx) = mpsc::channel(1);
+    let cursor = Arc::new(sample_cursor(rx));
+
+    // Nothing is ever sent, so the fetch waits on the stream
+    let fetching = tokio::spawn({
+        let cursor = cursor.clone();
+        async move { cursor.fetch_page(10).await }
+    });
+    tokio::task::yield_now().await;
+
+    tokio::time::timeout(std::time::Duration::from_secs(1), cursor.close())
+        .await
+        .expect("close waited for the fetch");
+    let page = fetching.await.unwrap();
+    assert!(page.rows.is_empty());
+    assert!(!page.has_more);
+    assert!(tx.is_closed());
+}
@@ -0,0 +1,759 @@
+// =====================================================
+// RESULT EDITS MODULE
+// Pending grid edits turned into key-safe UPDATE/INSERT/DELETE statements
+// =====================================================
+
+use crate::db_types::{AppState, ColumnSchema, DatabaseType, PrimaryKey, QueryParameter};
+use crate::mssql;
+use crate::mysql;
+use crate::postgres;
+use crate::sqlite;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tauri::State;
+
+use super::query_params::{bind_query_parameters, BoundQuery};
+use super::sql_utils::{
+    quote_column_name, quote_identifier_mssql, quote_identifier_mysql, quote_identifier_postgres,
+    value_to_sql_literal,
+};
+use super::transactions::{
+    begin_detached_transaction, get_transaction, SavepointAction, TransactionControl,
+    TransactionSession,
+};
+
+const GRID_EDIT_SAVEPOINT: &str = "tactile_grid_edits";
+
+// =====================================================
+// STRUCTS
+// =====================================================
+
+/// One pending change from the result grid. `original` carries the row as it
+/// was loaded, and is used both to locate the row and to detect concurrent edits.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum RowEdit {
+    Update {
+        original: Map<String, Value>,
+        changes: Map<String, Value>,
+    },
+    Insert {
+        values: Map<String, Value>,
+    },
+    Delete {
+        original: Map<String, Value>,
+    },
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultEditRequest {
+    pub database: Option<String>,
+    pub schema: Option<String>,
+    pub table: String,
+    /// Overrides primary-key discovery, for tables keyed by a unique index
+    pub key_columns: Option<Vec<String>>,
+    pub edits: Vec<RowEdit>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EditStatementPreview {
+    pub edit_index: usize,
+    pub kind: String,
+    pub sql: String,
+    /// Columns the concurrency check cannot compare; concurrent changes to
+    /// them are not detected
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unchecked_columns: Vec<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultEditPreview {
+    pub table: String,
+    pub key_columns: Vec<String>,
+    pub statements: Vec<EditStatementPreview>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultEditApplyResult {
+    pub applied: usize,
+    /// False when the edits ran inside an already open transaction, which is
+    /// left for the user to commit
+    pub committed: bool,
+    pub statements: Vec<EditStatementPreview>,
+}
+
+/// A generated statement with `:pN` placeholders, its literal rendering for
+/// preview, and the query that must match exactly one row before it runs.
+#[derive(Debug, Clone)]
+pub struct GeneratedEdit {
+    pub edit_index: usize,
+    pub kind: &'static str,
+    pub sql: String,
+    pub params: Vec<QueryParameter>,
+    pub preview: String,
+    pub check: Option<(String, Vec<QueryParameter>)>,
+    pub unchecked_columns: Vec<String>,
+}
+
+struct EditTarget {
+    db_type: DatabaseType,
+    qualified_table: String,
+    columns: Vec<ColumnSchema>,
+    key_columns: Vec<String>,
+}
+
+/// Appends SQL text to the executable and preview forms in step, so values are
+/// bound in one and rendered as literals in the other.
+struct StatementBuilder<'a> {
+    db_type: &'a DatabaseType,
+    sql: String,
+    preview: String,
+    params: Vec<QueryParameter>,
+}
+
+impl<'a> StatementBuilder<'a> {
+    fn new(db_type: &'a DatabaseType) -> Self {
+        Self {
+            db_type,
+            sql: String::new(),
+            preview: String::new(),
+            params: Vec::new(),
+        }
+    }
+
+    fn push_sql(&mut self, text: &str) {
+        self.sql.push_str(text);
+        self.preview.push_str(text);
+    }
+
+    fn push_value(&mut self, column: &ColumnSchema, value: &Value) {
+        let name = format!("p{}", self.params.len() + 1);
+        let type_hint = column_type_hint(self.db_type, column);
+        self.sql.push(':');
+        self.sql.push_str(&name);
+        if *self.db_type == DatabaseType::PostgreSQL && type_hint.is_none() {
+            // Enums, arrays and the like only accept text through an explicit cast
+            let base_type = column.column_type.split('(').next().unwrap_or("").trim();
+            if !base_type.is_empty() {
+                self.sql.push_str("::");
+                self.sql.push_str(&quote_identifier_postgres(base_type));
+            }
+        }
+        self.preview.push_str(&value_to_sql_literal(value));
+        self.params.push(QueryParameter {
+            name: Some(name),
+            value: value.clone(),
+            type_hint: type_hint.map(str::to_string),
+        });
+    }
+
+    fn push_predicates(&mut self, predicates: &[(&ColumnSchema, &Value)]) {
+        for (i, (column, value)) in predicates.iter().enumerate() {
+            if i > 0 {
+                self.push_sql(" AND ");
+            }
+            self.push_sql(&quote_column_name(self.db_type, &column.name));
+            if value.is_null() {
+                self.push_sql(" IS NULL");
+            } else {
+                self.push_sql(" = ");
+                self.push_value(column, value);
+            }
+        }
+    }
+}
+
+// =====================================================
+// COLUMN HELPERS
+// =====================================================
+
+fn base_data_type(column: &ColumnSchema) -> String {
+    column
+        .data_type
+        .split('(')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_lowercase()
+}
+
+fn is_binary_column(column: &ColumnSchema) -> bool {
+    matches!(
+        base_data_type(column).as_str(),
+        "bytea"
+            | "blob"
+            | "tinyblob"
+            | "mediumblob"
+            | "longblob"
+            | "binary"
+            | "varbinary"
+            | "image"
+    )
+}
+
+/// Columns whose values cannot be compared reliably for equality, so they are
+/// left out of concurrency checks and reported as unchecked.
+fn is_comparable_column(column: &ColumnSchema) -> bool {
+    !is_binary_column(column)
+        && !matches!(
+            base_data_type(column).as_str(),
+            "json"
+                | "jsonb"
+                | "xml"
+                | "text"
+                | "ntext"
+                | "tinytext"
+                | "mediumtext"
+                | "longtext"
+                | "real"
+                | "float"
+                | "double"
+                | "double precision"
+                | "float4"
+                | "float8"
+                | "geometry"
+                | "geography"
+                | "point"
+                | "user-defined"
+                | "array"
+        )
+}
+
+/// Maps a column's declared type onto a bind type hint. SQLite is dynamically
+/// typed, so values are bound as they come.
+pub fn column_type_hint(db_type: &DatabaseType, column: &ColumnSchema) -> Option<&'static str> {
+    if *db_type == DatabaseType::SQLite {
+        return None;
+    }
+    let data_type = base_data_type(column);
+    let hint = match data_type.as_str() {
+        "bool" | "boolean" => "boolean",
+        "bit" if *db_type == DatabaseType::MSSQL => "boolean",
+        "int" | "integer" | "bigint" | "smallint" | "tinyint" | "mediumint" | "int2" | "int4"
+        | "int8" | "serial" | "bigserial" | "smallserial" => "bigint",
+        "real" | "float" | "double" | "double precision" | "float4" | "float8" => "double",
+        "decimal" | "numeric" | "money" | "smallmoney" => "decimal",
+        "date" => "date",
+        "time" | "time without time zone" => "time",
+        "datetime"
+        | "datetime2"
+        | "smalldatetime"
+        | "timestamp"
+        | "timestamp without time zone" => "datetime",
+        "timestamptz" | "timestamp with time zone" | "datetimeoffset" => "timestamptz",
+        "json" | "jsonb" => "json",
+        "uuid" | "uniqueidentifier" => "uuid",
+        "char" | "varchar" | "nchar" | "nvarchar" | "text" | "ntext" | "tinytext"
+        | "mediumtext" | "longtext" | "character" | "character varying" | "bpchar" | "enum"
+        | "set" => "text",
+        _ => return None,
+    };
+    Some(hint)
+}
+
+fn find_column<'c>(columns: &'c [ColumnSchema], name: &str) -> Result<&'c ColumnSchema, String> {
+    columns
+        .iter()
+        .find(|c| c.name == name)
+        .or_else(|| columns.iter().find(|c| c.name.eq_ignore_ascii_case(name)))
+        .ok_or_else(|| format!("Unknown column: {}", name))
+}
+
+fn editable_column<'c>(
+    columns: &'c [ColumnSchema],
+    name: &str,
+) -> Result<&'c ColumnSchema, String> {
+    let column = find_column(columns, name)?;
+    if is_binary_column(column) {
+        return Err(format!(
+            "Binary column {} cannot be edited from the result grid",
+            column.name
+        ));
+    }
+    Ok(column)
+}
+
+fn original_value<'v>(
+    original: &'v Map<String, Value>,
+    column: &ColumnSchema,
+) -> Option<&'v Value> {
+    original.get(&column.name).or_else(|| {
+        original
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(&column.name))
+            .map(|(_, v)| v)
+    })
+}
+
+type RowPredicates<'c, 'v> = (Vec<(&'c ColumnSchema, &'v Value)>, Vec<String>);
+
+/// Key columns plus any `extra` columns that must still hold their original
+/// values for the edit to go ahead, and the names of the `extra` columns that
+/// could not be compared.
+fn row_predicates<'c, 'v>(
+    columns: &'c [ColumnSchema],
+    key_columns: &[String],
+    original: &'v Map<String, Value>,
+    extra: &[&'c ColumnSchema],
+) -> Result<RowPredicates<'c, 'v>, String> {
+    let mut predicates = Vec::new();
+    for key in key_columns {
+        let column = find_column(columns, key)?;
+        let value = original_value(original, column)
+            .ok_or_else(|| format!("Original row is missing key column {}", column.name))?;
+        predicates.push((column, value));
+    }
+    let mut unchecked = Vec::new();
+    for column in extra {
+        if predicates.iter().any(|(c, _)| c.name == column.name) {
+            continue;
+        }
+        let Some(value) = original_value(original, column) else {
+            continue;
+        };
+        if is_comparable_column(column) {
+            predicates.push((*column, value));
+        } else {
+            unchecked.push(column.name.clone());
+        }
+    }
+    Ok((predicates, unchecked))
+}
+
+fn build_row_check(
+    db_type: &DatabaseType,
+    qualified_table: &str,
+    predicates: &[(&ColumnSchema, &Value)],
+) -> (String, Vec<QueryParameter>) {
+    let mut builder = StatementBuilder::new(db_type);
+    // Lock the row so it cannot change between the check and the write
+    match db_type {
+        DatabaseType::MSSQL => builder.push_sql(&format!(
+            "SELECT 1 FROM {} WITH (UPDLOCK, HOLDLOCK) WHERE ",
+            qualified_table
+        )),
+        _ => builder.push_sql(&format!("SELECT 1 FROM {} WHERE ", qualified_table)),
+    }
+    builder.push_predicates(predicates);
+    if matches!(db_type, DatabaseType::MySQL | DatabaseType::PostgreSQL) {
+        builder.push_sql(" FOR UPDATE");
+    }
+    (builder.sql, builder.params)
+}
+
+// =====================================================
+// STATEMENT GENERATION
+// =====================================================
+
+pub fn qualified_edit_table(
+    db_type: &DatabaseType,
+    database: Option<&str>,
+    schema: Option<&str>,
+    table: &str,
+) -> String {
+    let database = database.filter(|d| !d.is_empty() && *d != "default");
+    match db_type {
+        DatabaseType::PostgreSQL => format!(
+            "{}.{}",
+            quote_identifier_postgres(schema.unwrap_or("public")),
+            quote_identifier_postgres(table)
+        ),
+        DatabaseType::SQLite => quote_identifier_postgres(table),
+        DatabaseType::MSSQL => {
+            let schema = quote_identifier_mssql(schema.unwrap_or("dbo"));
+            match database {
+                Some(db) => format!(
+                    "{}.{}.{}",
+                    quote_identifier_mssql(db),
+                    schema,
+                    quote_identifier_mssql(table)
+                ),
+                None => format!("{}.{}", schema, quote_identifier_mssql(table)),
+            }
+        }
+        _ => match database {
+            Some(db) => format!(
+                "{}.{}",
+                quote_identifier_mysql(db),
+                quote_identifier_mysql(table)
+            ),
+            None => quote_identifier_mysql(table),
+        },
+    }
+}
+
+/// Generates one statement per edit. UPDATE and DELETE locate the row by its key
+/// and carry a check on the original values of the columns they touch.
+pub fn build_edit_statements(
+    db_type: &DatabaseType,
+    qualified_table: &str,
+    columns: &[ColumnSchema],
+    key_columns: &[String],
+    edits: &[RowEdit],
+) -> Result<Vec<GeneratedEdit>, String> {
+    if *db_type == DatabaseType::ClickHouse {
+        return Err("Editing result rows is not supported for ClickHouse".into());
+    }
+
+    let mut generated = Vec::with_capacity(edits.len());
+    for (edit_index, edit) in edits.iter().enumerate() {
+        let label = |e: String| format!("Edit #{}: {}", edit_index + 1, e);
+        let statement = match edit {
+            RowEdit::Update { original, changes } => {
+                if key_columns.is_empty() {
+                    return Err(label(
+                        "Table has no primary key; specify key columns".into(),
+                    ));
+                }
+                if changes.is_empty() {
+                    continue;
+                }
+                let mut builder = StatementBuilder::new(db_type);
+                builder.push_sql(&format!("UPDATE {} SET ", qualified_table));
+                let mut changed = Vec::new();
+                for (i, (name, value)) in changes.iter().enumerate() {
+                    let column = editable_column(columns, name).map_err(label)?;
+                    if i > 0 {
+                        builder.push_sql(", ");
+                    }
+                    builder.push_sql(&quote_column_name(db_type, &column.name));
+                    builder.push_sql(" = ");
+                    builder.push_value(column, value);
+                    changed.push(column);
+                }
+                let (predicates, unchecked_columns) =
+                    row_predicates(columns, key_columns, original, &changed).map_err(label)?;
+                builder.push_sql(" WHERE ");
+                builder.push_predicates(&predicates);
+                GeneratedEdit {
+                    edit_index,
+                    kind: "update",
+                    check: Some(build_row_check(db_type, qualified_table, &predicates)),
+                    sql: builder.sql,
+                    params: builder.params,
+                    preview: builder.preview,
+                    unchecked_columns,
+                }
+            }
+            RowEdit::Insert { values } => {
+                let mut builder = StatementBuilder::new(db_type);
+                if values.is_empty() {
+                    match db_type {
+                        DatabaseType::MySQL => builder
+                            .push_sql(&format!("INSERT INTO {} () VALUES ()", qualified_table)),
+                        _ => builder
+                            .push_sql(&format!("INSERT INTO {} DEFAULT VALUES", qualified_table)),
+                    }
+                } else {
+                    let targets = values
+                        .keys()
+                        .map(|name| editable_column(columns, name))
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(label)?;
+                    let column_list = targets
+                        .iter()
+                        .map(|c| quote_column_name(db_type, &c.name))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    builder.push_sql(&format!(
+                        "INSERT INTO {} ({}) VALUES (",
+                        qualified_table, column_list
+                    ));
+                    for (i, (column, value)) in targets.iter().zip(values.values()).enumerate() {
+                        if i > 0 {
+                            builder.push_sql(", ");
+                        }
+                        builder.push_value(column, value);
+                    }
+                    builder.push_sql(")");
+                }
+                GeneratedEdit {
+                    edit_index,
+                    kind: "insert",
+                    check: None,
+                    sql: builder.sql,
+                    params: builder.params,
+                    preview: builder.preview,
+                    unchecked_columns: Vec::new(),
+                }
+            }
+            RowEdit::Delete { original } => {
+                if key_columns.is_empty() {
+                    return Err(label(
+                        "Table has no primary key; specify key columns".into(),
+                    ));
+                }
+                let all_columns: Vec<&ColumnSchema> = columns.iter().collect();
+                let (predicates, unchecked_columns) =
+                    row_predicates(columns, key_columns, original, &all_columns).map_err(label)?;
+                let mut builder = StatementBuilder::new(db_type);
+                builder.push_sql(&format!("DELETE FROM {} WHERE ", qualified_table));
+                builder.push_predicates(&predicates);
+                GeneratedEdit {
+                    edit_index,
+                    kind: "delete",
+                    check: Some(build_row_check(db_type, qualified_table, &predicates)),
+                    sql: builder.sql,
+                    params: builder.params,
+                    preview: builder.preview,
+                    unchecked_columns,
+                }
+            }
+        };
+        generated.push(statement);
+    }
+    Ok(generated)
+}
+
+fn to_previews(generated: &[GeneratedEdit]) -> Vec<EditStatementPreview> {
+    generated
+        .iter()
+        .map(|edit| EditStatementPreview {
+            edit_index: edit.edit_index,
+            kind: edit.kind.to_string(),
+            sql: format!("{};", edit.preview),
+            unchecked_columns: edit.unchecked_columns.clone(),
+        })
+        .collect()
+}
+
+// =====================================================
+// TARGET LOADING
+// =====================================================
+
+async fn load_edit_target(
+    app_state: &AppState,
+    request: &ResultEditRequest,
+) -> Result<EditTarget, String> {
+    let db_type = {
+        let guard = app_state.active_db_type.lock().await;
+        guard.clone()
+    };
+    let database = request
+        .database
+        .clone()
+        .unwrap_or_else(|| "default".to_string());
+    let schema = request.schema.clone();
+
+    let (columns, primary_keys): (Vec<ColumnSchema>, Vec<PrimaryKey>) = match db_type {
+        DatabaseType::MySQL => {
+            let guard = app_state.mysql_pool.lock().await;
+            let pool = guard.as_ref().ok_or("No MySQL connection established")?;
+            (
+                mysql::get_table_schema(pool, &database, &request.table).await?,
+                mysql::get_table_primary_keys(pool, &database, &request.table).await?,
+            )
+        }
+        DatabaseType::PostgreSQL => {
+            let guard = app_state.postgres_pool.lock().await;
+            let pool = guard
+                .as_ref()
+                .ok_or("No PostgreSQL connection established")?;
+            let schema_name = schema.as_deref().unwrap_or("public");
+            (
+                postgres::get_table_schema(pool, schema_name, &request.table).await?,
+                postgres::get_table_primary_keys(pool, schema_name, &request.table).await?,
+            )
+        }
+        DatabaseType::MSSQL => {
+            let guard = app_state.mssql_pool.lock().await;
+            let pool = guard.as_ref().ok_or("No MSSQL connection established")?;
+            let schema_name = schema.as_deref().unwrap_or("dbo");
+            (
+                mssql::get_table_schema(pool, &database, schema_name, &request.table).await?,
+                mssql::get_table_primary_keys(pool, &database, schema_name, &request.table).await?,
+            )
+        }
+        DatabaseType::SQLite => {
+            let guard = app_state.sqlite_pool.lock().await;
+            let pool = guard.as_ref().ok_or("No SQLite connection established")?;
+            (
+                sqlite::get_table_schema(pool, &database, &request.table).await?,
+                sqlite::get_table_primary_keys(pool, &database, &request.table).await?,
+            )
+        }
+        DatabaseType::ClickHouse => {
+            return Err("Editing result rows is not supported for ClickHouse".into())
+        }
+        DatabaseType::Disconnected => return Err("No connection established".into()),
+    };
+
+    if columns.is_empty() {
+        return Err(format!("Table not found: {}", request.table));
+    }
+
+    let key_columns = match request.key_columns.as_ref().filter(|keys| !keys.is_empty()) {
+        Some(keys) => keys
+            .iter()
+            .map(|key| find_column(&columns, key).map(|c| c.name.clone()))
+            .collect::<Result<Vec<_>, _>>()?,
+        None => {
+            let mut primary_keys = primary_keys;
+            primary_keys.sort_by_key(|pk| pk.ordinal_position);
+            primary_keys.into_iter().map(|pk| pk.column_name).collect()
+        }
+    };
+
+    Ok(EditTarget {
+        qualified_table: qualified_edit_table(
+            &db_type,
+            request.database.as_deref(),
+            schema.as_deref(),
+            &request.table,
+        ),
+        db_type,
+        columns,
+        key_columns,
+    })
+}
+
+// =====================================================
+// EXECUTION
+// =====================================================
+
+async fn run_edits(
+    session: &mut TransactionSession,
+    statements: &[(usize, Option<BoundQuery>, BoundQuery)],
+) -> Result<(), String> {
+    for (edit_index, check, statement) in statements {
+        if let Some(check) = check {
+            let results = session
+                .execute_parameterized(&check.sql, &check.binds, None)
+                .await?;
+            let matched = results.first().map(|r| r.rows.len()).unwrap_or(0);
+            match matched {
+                1 => {}
+                0 => {
+                    return Err(format!(
+                        "Edit #{}: the row was changed or deleted by another session",
+                        edit_index + 1
+                    ))
+                }
+                n => {
+                    return Err(format!(
+                        "Edit #{}: key matches {} rows, refusing to apply",
+                        edit_index + 1,
+                        n
+                    ))
+                }
+            }
+        }
+        session
+            .execute_parameterized(&statement.sql, &statement.binds, None)
+            .await
+            .map_err(|e| format!("Edit #{}: {}", edit_index + 1, e))?;
+    }
+    Ok(())
+}
+
+async fn prepare_edits(
+    app_state: &AppState,
+    request: &ResultEditRequest,
+) -> Result<(EditTarget, Vec<GeneratedEdit>), String> {
+    let target = load_edit_target(app_state, request).await?;
+    let generated = build_edit_statements(
+        &target.db_type,
+        &target.qualified_table,
+        &target.columns,
+        &target.key_columns,
+        &request.edits,
+    )?;
+    Ok((target, generated))
+}
+
+// =====================================================
+// TAURI COMMANDS
+// =====================================================
+
+#[tauri::command]
+pub async fn preview_result_edits(
+    app_state: State<'_, AppState>,
+    request: ResultEditRequest,
+    session_id: Option<String>,
+) -> Result<ResultEditPreview, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
+    let (target, generated) = prepare_edits(&app_state, &request).await?;
+    Ok(ResultEditPreview {
+        table: target.qualified_table,
+        key_columns: target.key_columns,
+        statements: to_previews(&generated),
+    })
+}
+
+/// Applies all edits atomically. Inside an open transaction they are wrapped in
+/// a savepoint instead, and left uncommitted.
+#[tauri::command]
+pub async fn apply_result_edits(
+    app_state: State<'_, AppState>,
+    request: ResultEditRequest,
+    session_id: Option<String>,
+) -> Result<ResultEditApplyResult, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
+    let (target, generated) = prepare_edits(&app_state, &request).await?;
+
+    let statements = generated
+        .iter()
+        .map(|edit| -> Result<_, String> {
+            let check = edit
+                .check
+                .as_ref()
+                .map(|(sql, params)| bind_query_parameters(sql, params, &target.db_type))
+                .transpose()?;
+            let statement = bind_query_parameters(&edit.sql, &edit.params, &target.db_type)?;
+            Ok((edit.edit_index, check, statement))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let session_id = {
+        let guard = app_state.active_session_id.lock().await;
+        guard.clone().ok_or("No connection established")?
+    };
+
+    let committed = match get_transaction(&session_id).await {
+        Some(session) => {
+            let mut guard = session.lock().await;
+            guard
+                .savepoint(SavepointAction::Create, GRID_EDIT_SAVEPOINT)
+                .await?;
+            match run_edits(&mut guard, &statements).await {
+                Ok(()) => {
+                    guard
+                        .savepoint(SavepointAction::Release, GRID_EDIT_SAVEPOINT)
+                        .await?;
+                }
+                Err(e) => {
+                    let _ = guard
+                        .savepoint(SavepointAction::RollbackTo, GRID_EDIT_SAVEPOINT)
+                        .await;
+                    let _ = guard
+                        .savepoint(SavepointAction::Release, GRID_EDIT_SAVEPOINT)
+                        .await;
+                    return Err(e);
+                }
+            }
+            false
+        }
+        None => {
+            let mut session = begin_detached_transaction(&app_state, &session_id).await?;
+            if let Err(e) = run_edits(&mut session, &statements).await {
+                let _ = session.finish(TransactionControl::Rollback).await;
+                return Err(e);
+            }
+            session.finish(TransactionControl::Commit).await?;
+            true
+        }
+    };
+
+    Ok(ResultEditApplyResult {
+        applied: generated.len(),
+        committed,
+        statements: to_previews(&generated),
+    })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,185 @@
+use super::*;
+use serde_json::json;
+
+fn column(name: &str, data_type: &str) -> ColumnSchema {
+    ColumnSchema {
+        name: name.to_string(),
+        data_type: data_type.to_string(),
+        column_type: data_type.to_string(),
+        ..Default::default()
+    }
+}
+
+fn users_columns() -> Vec<ColumnSchema> {
+    vec![
+        column("id", "int"),
+        column("name", "varchar"),
+        column("payload", "json"),
+    ]
+}
+
+fn row(value: Value) -> Map<String, Value> {
+    value.as_object().cloned().unwrap()
+}
+
+#[test]
+fn test_build_update_checks_key_and_changed_columns() {
+    let edits = vec![RowEdit::Update {
+        original: row(json!({"id": 1, "name": "bob", "payload": {"a": 1}})),
+        changes: row(json!({"name": "alice", "payload": {"a": 2}})),
+    }];
+    let generated = build_edit_statements(
+        &DatabaseType::MySQL,
+        "`app`.`users`",
+        &users_columns(),
+        &["id".to_string()],
+        &edits,
+    )
+    .unwrap();
+
+    let update = &generated[0];
+    assert_eq!(
+        update.sql,
+        "UPDATE `app`.`users` SET `name` = :p1, `payload` = :p2 WHERE `id` = :p3 AND `name` = :p4"
+    );
+    assert_eq!(
+        update.preview,
+        "UPDATE `app`.`users` SET `name` = 'alice', `payload` = '{\"a\":2}' WHERE `id` = 1 AND `name` = 'bob'"
+    );
+    assert_eq!(update.params[2].type_hint.as_deref(), Some("bigint"));
+
+    let (check_sql, check_params) = update.check.clone().unwrap();
+    assert_eq!(
+        check_sql,
+        "SELECT 1 FROM `app`.`users` WHERE `id` = :p1 AND `name` = :p2 FOR UPDATE"
+    );
+    assert_eq!(check_params.len(), 2);
+    assert_eq!(update.unchecked_columns, vec!["payload".to_string()]);
+}
+
+#[test]
+fn test_build_insert_and_delete() {
+    let edits = vec![
+        RowEdit::Insert {
+            values: row(json!({"id": 2, "name": null})),
+        },
+        RowEdit::Delete {
+            original: row(json!({"id": 3, "name": null, "payload": null})),
+        },
+    ];
+    let generated = build_edit_statements(
+        &DatabaseType::MSSQL,
+        "[dbo].[users]",
+        &users_columns(),
+        &["id".to_string()],
+        &edits,
+    )
+    .unwrap();
+
+    assert_eq!(
+        generated[0].preview,
+        "INSERT INTO [dbo].[users] ([id], [name]) VALUES (2, NULL)"
+    );
+    assert!(generated[0].check.is_none());
+    // NULL originals compare with IS NULL, and JSON is left out of the check
+    assert_eq!(
+        generated[1].sql,
+        "DELETE FROM [dbo].[users] WHERE [id] = :p1 AND [name] IS NULL"
+    );
+    assert_eq!(
+        generated[1].check.as_ref().unwrap().0,
+        "SELECT 1 FROM [dbo].[users] WITH (UPDLOCK, HOLDLOCK) WHERE [id] = :p1 AND [name] IS NULL"
+    );
+    assert!(generated[0].unchecked_columns.is_empty());
+    assert_eq!(generated[1].unchecked_columns, vec!["payload".to_string()]);
+}
+
+#[test]
+fn test_build_edit_statements_rejects_unsafe_edits() {
+    let update = vec![RowEdit::Update {
+        original: row(json!({"name": "bob"})),
+        changes: row(json!({"name": "alice"})),
+    }];
+    let columns = users_columns();
+
+    // No key, no way to target a single row
+    assert!(build_edit_statements(&DatabaseType::MySQL, "t", &columns, &[], &update).is_err());
+    // Original row without its key value
+    assert!(build_edit_statements(
+        &DatabaseType::MySQL,
+        "t",
+        &columns,
+        &["id".to_string()],
+        &update
+    )
+    .is_err());
+
+    let unknown = vec![RowEdit::Insert {
+        values: row(json!({"missing": 1})),
+    }];
+    assert!(build_edit_statements(&DatabaseType::MySQL, "t", &columns, &[], &unknown).is_err());
+    assert!(
+        build_edit_statements(&DatabaseType::ClickHouse, "t", &columns, &[], &unknown).is_err()
+    );
+}
+
+#[test]
+fn test_postgres_casts_and_binding() {
+    let columns = vec![
+        column("id", "integer"),
+        ColumnSchema {
+            name: "mood".into(),
+            data_type: "USER-DEFINED".into(),
+            column_type: "mood".into(),
+            ..Default::default()
+        },
+    ];
+    let edits = vec![RowEdit::Update {
+        original: row(json!({"id": 1, "mood": "sad"})),
+        changes: row(json!({"mood": "happy"})),
+    }];
+    let generated = build_edit_statements(
+        &DatabaseType::PostgreSQL,
+        "\"public\".\"people\"",
+        &columns,
+        &["id".to_string()],
+        &edits,
+    )
+    .unwrap();
+    assert_eq!(
+        generated[0].sql,
+        "UPDATE \"public\".\"people\" SET \"mood\" = :p1::\"mood\" WHERE \"id\" = :p2"
+    );
+
+    let bound = bind_query_parameters(
+        &generated[0].sql,
+        &generated[0].params,
+        &DatabaseType::PostgreSQL,
+    )
+    .unwrap();
+    assert_eq!(
+        bound.sql,
+        "UPDATE \"public\".\"people\" SET \"mood\" = $1::\"mood\" WHERE \"id\" = $2"
+    );
+}
+
+#[test]
+fn test_qualified_edit_table() {
+    assert_eq!(
+        qualified_edit_table(&DatabaseType::MSSQL, Some("shop"), None, "orders"),
+        "[shop].[dbo].[orders]"
+    );
+    assert_eq!(
+        qualified_edit_table(
+            &DatabaseType::PostgreSQL,
+            Some("shop"),
+            Some("sales"),
+            "orders"
+        ),
+        "\"sales\".\"orders\""
+    );
+    assert_eq!(
+        qualified_edit_table(&DatabaseType::MySQL, Some("default"), None, "orders"),
+        "`orders`"
+    );
+}
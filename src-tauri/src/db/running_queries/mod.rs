@@ -0,0 +1,452 @@
+// =====================================================
+// RUNNING QUERIES MODULE
+// Per-execution registry of in-flight queries and their cancel handles
+// =====================================================
+
+use crate::clickhouse;
+use crate::db_types::{AppState, DatabaseType, QueryResult};
+use crate::mssql;
+use crate::mysql;
+use crate::postgres;
+use crate::sqlite;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use tauri::State;
+
+// =====================================================
+// CONSTANTS
+// =====================================================
+
+const QUERY_PREVIEW_CHARS: usize = 200;
+
+// =====================================================
+// GLOBAL STATE
+// =====================================================
+
+/// In-flight executions keyed by execution id. A std mutex, since entries are
+/// removed from `Drop`; it is never held across an await.
+pub static RUNNING_EXECUTIONS: LazyLock<Mutex<HashMap<String, RunningExecution>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+// =====================================================
+// STRUCTS
+// =====================================================
+
+/// How to stop the statement of one execution, per engine.
+#[derive(Debug, Clone)]
+pub enum CancelHandle {
+    /// Connection id for `KILL QUERY`
+    MySQL(i64),
+    /// Backend pid for `pg_cancel_backend`
+    PostgreSQL(i64),
+    /// `@@SPID` for `KILL`
+    MSSQL(i64),
+    /// `query_id` for `KILL QUERY WHERE query_id = ...`
+    ClickHouse(String),
+    SQLite(sqlite::SqliteInterruptHandle),
+}
+
+pub struct RunningExecution {
+    pub execution_id: String,
+    pub session_id: Option<String>,
+    pub db_type: DatabaseType,
+    pub query: String,
+    pub started_at: i64,
+    pub cancel_requested: bool,
+    handle: Option<CancelHandle>,
+    /// Session-scoped state, for the pools the cancel statement runs on
+    app_state: AppState,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RunningExecutionInfo {
+    pub execution_id: String,
+    pub session_id: Option<String>,
+    pub db_type: DatabaseType,
+    pub query: String,
+    pub started_at: i64,
+    pub cancel_requested: bool,
+    pub cancellable: bool,
+}
+
+impl RunningExecution {
+    fn info(&self) -> RunningExecutionInfo {
+        RunningExecutionInfo {
+            execution_id: self.execution_id.clone(),
+            session_id: self.session_id.clone(),
+            db_type: self.db_type.clone(),
+            query: self.query.clone(),
+            started_at: self.started_at,
+            cancel_requested: self.cancel_requested,
+            cancellable: self.handle.is_some(),
+        }
+    }
+}
+
+/// Keeps an execution registered until dropped.
+pub struct ExecutionGuard {
+    execution_id: String,
+}
+
+impl ExecutionGuard {
+    pub fn id(&self) -> &str {
+        &self.execution_id
+    }
+
+    /// Records the handle once the execution's connection is known.
+    pub fn set_cancel_handle(&self, handle: CancelHandle) {
+        let mut guard = RUNNING_EXECUTIONS.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(execution) = guard.get_mut(&self.execution_id) {
+            execution.handle = Some(handle);
+        }
+    }
+
+    /// Forgets the handle; called before the execution's pooled connection is
+    /// released, so a late cancel cannot reach whoever uses it next.
+    pub fn clear_cancel_handle(&self) {
+        let mut guard = RUNNING_EXECUTIONS.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(execution) = guard.get_mut(&self.execution_id) {
+            execution.handle = None;
+        }
+    }
+}
+
+impl Drop for ExecutionGuard {
+    fn drop(&mut self) {
+        let mut guard = RUNNING_EXECUTIONS.lock().unwrap_or_else(|e| e.into_inner());
+        guard.remove(&self.execution_id);
+    }
+}
+
+// =====================================================
+// REGISTRY
+// =====================================================
+
+fn query_preview(query: &str) -> String {
+    let trimmed = query.trim();
+    match trimmed.char_indices().nth(QUERY_PREVIEW_CHARS) {
+        Some((pos, _)) => format!("{}...", &trimmed[..pos]),
+        None => trimmed.to_string(),
+    }
+}
+
+/// Registers an execution under `execution_id`, or a fresh id when none is given.
+pub async fn register_execution(
+    app_state: &AppState,
+    query: &str,
+    execution_id: Option<String>,
+) -> Result<ExecutionGuard, String> {
+    let db_type = {
+        let guard = app_state.active_db_type.lock().await;
+        guard.clone()
+    };
+    let session_id = {
+        let guard = app_state.active_session_id.lock().await;
+        guard.clone()
+    };
+    // Pin the session's pools so a later connection switch cannot redirect the cancel
+    let app_state = match session_id.as_deref() {
+        Some(id) => app_state
+            .for_session(Some(id))
+            .await
+            .unwrap_or_else(|_| app_state.clone()),
+        None => app_state.clone(),
+    };
+    let execution_id = execution_id
+        .filter(|id| !id.trim().is_empty())
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    let mut guard = RUNNING_EXECUTIONS.lock().unwrap_or_else(|e| e.into_inner());
+    if guard.contains_key(&execution_id) {
+        return Err(format!("Execution {} is already running", execution_id));
+    }
+    guard.insert(
+        execution_id.clone(),
+        RunningExecution {
+            execution_id: execution_id.clone(),
+            session_id,
+            db_type,
+            query: query_preview(query),
+            started_at: chrono::Utc::now().timestamp_millis(),
+            cancel_requested: false,
+            handle: None,
+            app_state,
+        },
+    );
+    Ok(ExecutionGuard { execution_id })
+}
+
+pub fn list_executions(session_id: Option<&str>) -> Vec<RunningExecutionInfo> {
+    let guard = RUNNING_EXECUTIONS.lock().unwrap_or_else(|e| e.into_inner());
+    let mut infos: Vec<RunningExecutionInfo> = guard
+        .values()
+        .filter(|e| session_id.is_none() || e.session_id.as_deref() == session_id)
+        .map(RunningExecution::info)
+        .collect();
+    infos.sort_by_key(|info| info.started_at);
+    infos
+}
+
+pub fn is_cancel_requested(execution_id: &str) -> bool {
+    let guard = RUNNING_EXECUTIONS.lock().unwrap_or_else(|e| e.into_inner());
+    guard
+        .get(execution_id)
+        .map(|e| e.cancel_requested)
+        .unwrap_or(false)
+}
+
+pub async fn cancel_execution_by_id(execution_id: &str) -> Result<String, String> {
+    let (handle, app_state) = {
+        let mut guard = RUNNING_EXECUTIONS.lock().unwrap_or_else(|e| e.into_inner());
+        let execution = guard
+            .get_mut(execution_id)
+            .ok_or_else(|| format!("No running execution: {}", execution_id))?;
+        execution.cancel_requested = true;
+        let handle = execution
+            .handle
+            .clone()
+            .ok_or("Query has not started on the server yet")?;
+        if let CancelHandle::SQLite(handle) = handle {
+            // SAFETY: the handle is registered only while its execution holds
+            // the connection, and is cleared under this same lock before the
+            // connection is released, so it still points at an open connection
+            unsafe { sqlite::interrupt(handle) };
+            return Ok(format!("Execution {} interrupted", execution_id));
+        }
+        (handle, execution.app_state.clone())
+    };
+
+    match handle {
+        CancelHandle::MySQL(connection_id) => {
+            let pool = {
+                let guard = app_state.mysql_pool.lock().await;
+                guard.clone().ok_or("No MySQL connection established")?
+            };
+            mysql::kill_query(&pool, connection_id).await
+        }
+        CancelHandle::PostgreSQL(pid) => {
+            let pool = {
+                let guard = app_state.postgres_pool.lock().await;
+                guard
+                    .clone()
+                    .ok_or("No PostgreSQL connection established")?
+            };
+            postgres::cancel_backend(&pool, pid).await
+        }
+        CancelHandle::MSSQL(spid) => {
+            let pool = {
+                let guard = app_state.mssql_pool.lock().await;
+                guard.clone().ok_or("No MSSQL connection established")?
+            };
+            mssql::kill_process(&pool, spid).await
+        }
+        CancelHandle::ClickHouse(query_id) => {
+            let config = {
+                let guard = app_state.clickhouse_config.lock().await;
+                guard
+                    .clone()
+                    .ok_or("No ClickHouse connection established")?
+            };
+            clickhouse::kill_query_by_id(&config, &query_id).await
+        }
+        CancelHandle::SQLite(_) => unreachable!("SQLite executions are interrupted above"),
+    }
+}
+
+// =====================================================
+// EXECUTION
+// =====================================================
+
+/// Runs `query` on a dedicated connection whose cancel handle is registered on
+/// `execution` for the duration of the call. `with_status` also returns the
+/// MySQL session status diff.
+pub async fn execute_cancellable(
+    app_state: &AppState,
+    execution: &ExecutionGuard,
+    query: &str,
+    query_timeout_seconds: Option<u64>,
+    with_status: bool,
+) -> Result<(Vec<QueryResult>, Option<HashMap<String, i64>>), String> {
+    execute_on_dedicated_connection(
+        app_state,
+        execution,
+        query,
+        query_timeout_seconds,
+        with_status,
+    )
+    .await
+    .map_err(|e| annotate_cancelled(execution, e))
+}
+
+/// Marks the error of an execution that was cancelled as such.
+pub fn annotate_cancelled(execution: &ExecutionGuard, error: String) -> String {
+    if is_cancel_requested(execution.id()) {
+        format!("Query cancelled: {}", error)
+    } else {
+        error
+    }
+}
+
+async fn execute_on_dedicated_connection(
+    app_state: &AppState,
+    execution: &ExecutionGuard,
+    query: &str,
+    query_timeout_seconds: Option<u64>,
+    with_status: bool,
+) -> Result<(Vec<QueryResult>, Option<HashMap<String, i64>>), String> {
+    let db_type = {
+        let guard = app_state.active_db_type.lock().await;
+        guard.clone()
+    };
+
+    match db_type {
+        DatabaseType::MySQL => {
+            let pool = {
+                let guard = app_state.mysql_pool.lock().await;
+                guard.clone().ok_or("No MySQL connection established")?
+            };
+            let mut conn = pool
+                .acquire()
+                .await
+                .map_err(|e| format!("Failed to acquire connection: {}", e))?;
+            if let Ok(id) = mysql::current_connection_id(conn.as_mut()).await {
+                execution.set_cancel_handle(CancelHandle::MySQL(id));
+            }
+            let result = if with_status {
+                mysql::execute_query_with_status_on_connection(
+                    conn.as_mut(),
+                    query,
+                    query_timeout_seconds,
+                )
+                .await
+            } else {
+                mysql::execute_query_on_connection(conn.as_mut(), query, query_timeout_seconds)
+                    .await
+                    .map(|results| (results, None))
+            };
+            execution.clear_cancel_handle();
+            result
+        }
+        DatabaseType::PostgreSQL => {
+            let pool = {
+                let guard = app_state.postgres_pool.lock().await;
+                guard
+                    .clone()
+                    .ok_or("No PostgreSQL connection established")?
+            };
+            let mut conn = pool
+                .acquire()
+                .await
+                .map_err(|e| format!("Failed to acquire connection: {}", e))?;
+            if let Ok(pid) = postgres::current_backend_pid(conn.as_mut()).await {
+                execution.set_cancel_handle(CancelHandle::PostgreSQL(pid));
+            }
+            let result =
+                postgres::execute_query_on_connection(conn.as_mut(), query, query_timeout_seconds)
+                    .await;
+            execution.clear_cancel_handle();
+            Ok((result?, None))
+        }
+        DatabaseType::MSSQL => {
+            let pool = {
+                let guard = app_state.mssql_pool.lock().await;
+                guard.clone().ok_or("No MSSQL connection established")?
+            };
+            let mut conn = pool.get().await.map_err(|e| e.to_string())?;
+            if let Ok(spid) = mssql::current_session_id(&mut conn).await {
+                execution.set_cancel_handle(CancelHandle::MSSQL(spid));
+            }
+            let result = mssql::execute_query_on_client(&mut conn, query).await;
+            execution.clear_cancel_handle();
+            Ok((result?, None))
+        }
+        DatabaseType::ClickHouse => {
+            let config = {
+                let guard = app_state.clickhouse_config.lock().await;
+                guard
+                    .clone()
+                    .ok_or("No ClickHouse connection established")?
+            };
+            let query_id = uuid::Uuid::new_v4().to_string();
+            execution.set_cancel_handle(CancelHandle::ClickHouse(query_id.clone()));
+            let results =
+                clickhouse::execute_query_tracked(&config, query, &query_id, query_timeout_seconds)
+                    .await?;
+            Ok((results, None))
+        }
+        DatabaseType::SQLite => {
+            let pool = {
+                let guard = app_state.sqlite_pool.lock().await;
+                guard.clone().ok_or("No SQLite connection established")?
+            };
+            let mut conn = pool
+                .acquire()
+                .await
+                .map_err(|e| format!("Failed to acquire connection: {}", e))?;
+            let handle = sqlite::interrupt_handle(conn.as_mut()).await?;
+            execution.set_cancel_handle(CancelHandle::SQLite(handle));
+            let result = sqlite::execute_query_on_connection(conn.as_mut(), query).await;
+            execution.clear_cancel_handle();
+            Ok((result?, None))
+        }
+        DatabaseType::Disconnected => Err("No connection established".into()),
+    }
+}
+
+// =====================================================
+// TAURI COMMANDS
+// =====================================================
+
+#[tauri::command]
+pub async fn list_running_executions(
+    session_id: Option<String>,
+) -> Result<Vec<RunningExecutionInfo>, String> {
+    Ok(list_executions(session_id.as_deref()))
+}
+
+#[tauri::command]
+pub async fn cancel_execution(execution_id: String) -> Result<String, String> {
+    cancel_execution_by_id(&execution_id).await
+}
+
+/// Cancels every in-flight execution of the session.
+#[tauri::command]
+pub async fn cancel_running_query(
+    app_state: State<'_, AppState>,
+    session_id: Option<String>,
+) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
+    let session_id = {
+        let guard = app_state.active_session_id.lock().await;
+        guard.clone()
+    };
+
+    let running = list_executions(session_id.as_deref());
+    if running.is_empty() {
+        return Err("No running query to cancel".to_string());
+    }
+
+    let mut errors = Vec::new();
+    for execution in &running {
+        if let Err(e) = cancel_execution_by_id(&execution.execution_id).await {
+            errors.push(e);
+        }
+    }
+    if errors.len() == running.len() {
+        return Err(format!("Failed to cancel query: {}", errors.join(" / ")));
+    }
+    Ok(format!(
+        "Cancelled {} running quer{}",
+        running.len() - errors.len(),
+        if running.len() - errors.len() == 1 {
+            "y"
+        } else {
+            "ies"
+        }
+    ))
+}
+
+#[cfg(test)]
+mod tests;
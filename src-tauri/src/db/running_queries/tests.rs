@@ -0,0 +1,70 @@
+use super::*;
+
+fn is_registered(execution_id: &str) -> bool {
+    list_executions(None)
+        .iter()
+        .any(|e| e.execution_id == execution_id)
+}
+
+#[test]
+fn test_query_preview_truncates_long_queries() {
+    assert_eq!(query_preview("  SELECT 1  "), "SELECT 1");
+
+    let long = format!("SELECT '{}'", "é".repeat(QUERY_PREVIEW_CHARS));
+    let preview = query_preview(&long);
+    assert!(preview.ends_with("..."));
+    assert_eq!(preview.chars().count(), QUERY_PREVIEW_CHARS + 3);
+}
+
+#[tokio::test]
+async fn test_execution_is_unregistered_on_drop() {
+    let state = AppState::default();
+    let guard = register_execution(&state, "SELECT 1", None).await.unwrap();
+    let execution_id = guard.id().to_string();
+    assert!(is_registered(&execution_id));
+
+    let info = list_executions(None)
+        .into_iter()
+        .find(|e| e.execution_id == execution_id)
+        .unwrap();
+    assert_eq!(info.query, "SELECT 1");
+    assert!(!info.cancellable);
+
+    drop(guard);
+    assert!(!is_registered(&execution_id));
+}
+
+#[tokio::test]
+async fn test_duplicate_execution_id_is_rejected() {
+    let state = AppState::default();
+    let execution_id = uuid::Uuid::new_v4().to_string();
+    let _guard = register_execution(&state, "SELECT 1", Some(execution_id.clone()))
+        .await
+        .unwrap();
+    assert!(register_execution(&state, "SELECT 2", Some(execution_id))
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_cancel_before_handle_marks_request() {
+    let state = AppState::default();
+    let guard = register_execution(&state, "SELECT 1", None).await.unwrap();
+
+    assert!(cancel_execution_by_id(guard.id()).await.is_err());
+    assert!(is_cancel_requested(guard.id()));
+    assert!(cancel_execution_by_id("missing-execution").await.is_err());
+}
+
+#[tokio::test]
+async fn test_only_cancelled_errors_are_annotated() {
+    let state = AppState::default();
+    let guard = register_execution(&state, "SELECT 1", None).await.unwrap();
+    assert_eq!(annotate_cancelled(&guard, "timeout".to_string()), "timeout");
+
+    let _ = cancel_execution_by_id(guard.id()).await;
+    assert_eq!(
+        annotate_cancelled(&guard, "interrupted".to_string()),
+        "Query cancelled: interrupted"
+    );
+}
@@ -0,0 +1,540 @@
+// =====================================================
+// SCRIPT RUNNER MODULE
+// Dialect-aware statement splitting and per-statement script execution
+// =====================================================
+
+use crate::clickhouse;
+use crate::db_types::{AppState, DatabaseType, QueryResult};
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+use tauri::State;
+
+use super::sql_lexer::{is_ident_char, Opened, SqlLexer};
+use super::transactions::{
+    begin_detached_transaction, get_transaction, pin_connection, PinnedConnection, SavepointAction,
+    TransactionControl, TransactionSession,
+};
+
+const SCRIPT_SAVEPOINT: &str = "tactile_script";
+
+// =====================================================
+// STRUCTS
+// =====================================================
+
+/// One statement (or MSSQL batch) cut out of a script.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptStatement {
+    pub index: usize,
+    /// 1-based line of the statement's first token
+    pub line: usize,
+    pub sql: String,
+}
+
+/// What to do with the rest of a script once a statement fails.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ScriptErrorPolicy {
+    /// Skip the remaining statements, keeping what already ran
+    #[default]
+    Stop,
+    /// Run every statement regardless of failures
+    Continue,
+    /// Run the script as one transaction and undo it on the first failure
+    Rollback,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StatementStatus {
+    Succeeded,
+    Failed,
+    Skipped,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StatementOutcome {
+    pub index: usize,
+    pub line: usize,
+    pub sql: String,
+    pub status: StatementStatus,
+    pub duration_ms: f64,
+    /// `None` where the driver does not report it (MSSQL, ClickHouse)
+    pub rows_affected: Option<u64>,
+    pub rows_returned: usize,
+    pub results: Vec<QueryResult>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptRunResult {
+    pub policy: ScriptErrorPolicy,
+    pub statements: Vec<StatementOutcome>,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    /// True when the `rollback` policy undid the script's changes
+    pub rolled_back: bool,
+    pub duration_ms: f64,
+}
+
+// =====================================================
+// STATEMENT SPLITTER
+// =====================================================
+
+struct Splitter {
+    lexer: SqlLexer,
+    delimiter: String,
+    statements: Vec<ScriptStatement>,
+    current: String,
+    has_code: bool,
+    start_line: usize,
+    word: String,
+    head_words: Vec<String>,
+    block_depth: usize,
+    next_index: usize,
+}
+
+impl Splitter {
+    fn new(db_type: &DatabaseType) -> Self {
+        Splitter {
+            lexer: SqlLexer::new(db_type),
+            delimiter: ";".to_string(),
+            statements: Vec::new(),
+            current: String::new(),
+            has_code: false,
+            start_line: 1,
+            word: String::new(),
+            head_words: Vec::new(),
+            block_depth: 0,
+            next_index: 0,
+        }
+    }
+
+    fn mark_code(&mut self, line_no: usize) {
+        if !self.has_code {
+            self.has_code = true;
+            self.start_line = line_no;
+        }
+    }
+
+    fn is_trigger(&self) -> bool {
+        let head: Vec<&str> = self.head_words.iter().map(String::as_str).collect();
+        matches!(
+            head.as_slice(),
+            ["CREATE", "TRIGGER", ..] | ["CREATE", "TEMP" | "TEMPORARY", "TRIGGER", ..]
+        )
+    }
+
+    fn end_word(&mut self) {
+        if self.word.is_empty() {
+            return;
+        }
+        let word = std::mem::take(&mut self.word).to_uppercase();
+        if self.lexer.dialect.trigger_blocks && self.is_trigger() {
+            match word.as_str() {
+                "BEGIN" => self.block_depth += 1,
+                "CASE" if self.block_depth > 0 => self.block_depth += 1,
+                "END" if self.block_depth > 0 => self.block_depth -= 1,
+                _ => {}
+            }
+        }
+        if self.head_words.len() < 4 {
+            self.head_words.push(word);
+        }
+    }
+
+    fn flush(&mut self, repeat: usize) {
+        self.end_word();
+        if self.has_code {
+            let sql = self.current.trim().to_string();
+            for _ in 0..repeat.max(1) {
+                self.statements.push(ScriptStatement {
+                    index: self.next_index,
+                    line: self.start_line,
+                    sql: sql.clone(),
+                });
+                self.next_index += 1;
+            }
+        }
+        self.current.clear();
+        self.has_code = false;
+        self.head_words.clear();
+        self.block_depth = 0;
+    }
+
+    /// Handles client-side directive lines; returns true when the line was consumed.
+    fn directive(&mut self, line: &str) -> bool {
+        let trimmed = line.trim();
+        if self.lexer.dialect.go_batches {
+            if let Some(repeat) = parse_go_line(trimmed) {
+                self.flush(repeat);
+                return true;
+            }
+        }
+        if self.lexer.dialect.delimiter_directive {
+            let mut parts = trimmed.splitn(2, char::is_whitespace);
+            if parts
+                .next()
+                .is_some_and(|kw| kw.eq_ignore_ascii_case("DELIMITER"))
+            {
+                if let Some(delimiter) = parts.next().map(str::trim).filter(|d| !d.is_empty()) {
+                    self.flush(1);
+                    self.delimiter = delimiter.to_string();
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    fn feed_line(&mut self, line: &str, line_no: usize) {
+        if self.lexer.in_code() && self.directive(line) {
+            return;
+        }
+
+        let chars: Vec<char> = line.chars().collect();
+        let mut i = 0;
+        while i < chars.len() {
+            if !self.lexer.in_code() {
+                let taken = self.lexer.step(&chars[i..]);
+                self.current.extend(&chars[i..i + taken]);
+                i += taken;
+                continue;
+            }
+
+            let c = chars[i];
+            if !is_ident_char(c) {
+                // Postgres E'...' strings honour backslash escapes
+                let e_prefix = self.word.eq_ignore_ascii_case("E");
+                self.end_word();
+
+                if !self.lexer.dialect.go_batches && self.block_depth == 0 {
+                    let delimiter: Vec<char> = self.delimiter.chars().collect();
+                    if chars[i..].starts_with(&delimiter) {
+                        self.flush(1);
+                        i += delimiter.len();
+                        continue;
+                    }
+                }
+
+                let prev = i.checked_sub(1).map(|p| chars[p]);
+                if let Some((opened, len)) = self.lexer.open(&chars[i..], prev, e_prefix) {
+                    if opened != Opened::Comment {
+                        self.mark_code(line_no);
+                    }
+                    self.current.extend(&chars[i..i + len]);
+                    i += len;
+                    continue;
+                }
+            } else {
+                self.word.push(c);
+            }
+
+            if !c.is_whitespace() {
+                self.mark_code(line_no);
+            }
+            self.current.push(c);
+            i += 1;
+        }
+    }
+
+    fn finish(mut self) -> Vec<ScriptStatement> {
+        self.flush(1);
+        self.statements
+    }
+}
+
+/// Incremental form of `split_sql_script`, for scripts read line by line.
+pub struct ScriptSplitter {
+    splitter: Splitter,
+    line_no: usize,
+}
+
+impl ScriptSplitter {
+    pub fn new(db_type: &DatabaseType) -> Self {
+        ScriptSplitter {
+            splitter: Splitter::new(db_type),
+            line_no: 0,
+        }
+    }
+
+    /// Feeds one line (including its newline) and returns the statements it completed.
+    pub fn push_line(&mut self, line: &str) -> Vec<ScriptStatement> {
+        self.line_no += 1;
+        self.splitter.feed_line(line, self.line_no);
+        std::mem::take(&mut self.splitter.statements)
+    }
+
+    /// Returns the trailing statement left without a terminator, if any.
+    pub fn finish(self) -> Vec<ScriptStatement> {
+        self.splitter.finish()
+    }
+}
+
+/// `GO` or `GO <count>`, optionally followed by `;`.
+fn parse_go_line(line: &str) -> Option<usize> {
+    let line = line.trim_end_matches(';').trim_end();
+    let mut parts = line.split_whitespace();
+    if !parts.next()?.eq_ignore_ascii_case("GO") {
+        return None;
+    }
+    let repeat = match parts.next() {
+        Some(count) => count.parse::<usize>().ok()?,
+        None => 1,
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(repeat)
+}
+
+/// Splits `script` into statements following the dialect's quoting, comment and
+/// batch rules. Comment-only fragments are dropped.
+pub fn split_sql_script(script: &str, db_type: &DatabaseType) -> Vec<ScriptStatement> {
+    let mut splitter = ScriptSplitter::new(db_type);
+    let mut statements = Vec::new();
+    for line in script.split_inclusive('\n') {
+        statements.extend(splitter.push_line(line));
+    }
+    statements.extend(splitter.finish());
+    statements
+}
+
+// =====================================================
+// EXECUTION
+// =====================================================
+
+/// Where script statements run: a pinned connection, a transaction, or
+/// ClickHouse's stateless HTTP interface.
+pub(crate) enum ScriptTarget<'a> {
+    Connection(PinnedConnection),
+    Transaction(&'a mut TransactionSession),
+    ClickHouse(Box<crate::db_types::ConnectionConfig>),
+}
+
+impl ScriptTarget<'_> {
+    pub(crate) async fn execute(
+        &mut self,
+        query: &str,
+        query_timeout_seconds: Option<u64>,
+    ) -> Result<(Vec<QueryResult>, Option<u64>), String> {
+        match self {
+            ScriptTarget::Connection(conn) => {
+                conn.execute_script_statement(query, query_timeout_seconds)
+                    .await
+            }
+            ScriptTarget::Transaction(session) => {
+                session
+                    .execute_script_statement(query, query_timeout_seconds)
+                    .await
+            }
+            ScriptTarget::ClickHouse(config) => clickhouse::execute_query_with_timeout(
+                config,
+                query.to_string(),
+                query_timeout_seconds,
+            )
+            .await
+            .map(|results| (results, None)),
+        }
+    }
+}
+
+/// Pins a connection for running statements outside any transaction.
+pub(crate) async fn connect_script_target(
+    app_state: &AppState,
+    db_type: &DatabaseType,
+) -> Result<ScriptTarget<'static>, String> {
+    if *db_type == DatabaseType::ClickHouse {
+        let config = {
+            let guard = app_state.clickhouse_config.lock().await;
+            guard
+                .clone()
+                .ok_or("No ClickHouse connection established")?
+        };
+        return Ok(ScriptTarget::ClickHouse(Box::new(config)));
+    }
+    Ok(ScriptTarget::Connection(
+        pin_connection(app_state, db_type).await?,
+    ))
+}
+
+pub(crate) async fn run_statements(
+    target: &mut ScriptTarget<'_>,
+    statements: &[ScriptStatement],
+    policy: ScriptErrorPolicy,
+    query_timeout_seconds: Option<u64>,
+) -> Vec<StatementOutcome> {
+    let mut outcomes = Vec::with_capacity(statements.len());
+    let mut halted = false;
+
+    for statement in statements {
+        let mut outcome = StatementOutcome {
+            index: statement.index,
+            line: statement.line,
+            sql: statement.sql.clone(),
+            status: StatementStatus::Skipped,
+            duration_ms: 0.0,
+            rows_affected: None,
+            rows_returned: 0,
+            results: Vec::new(),
+            error: None,
+        };
+        if halted {
+            outcomes.push(outcome);
+            continue;
+        }
+
+        let started = Instant::now();
+        let result = target.execute(&statement.sql, query_timeout_seconds).await;
+        outcome.duration_ms = started.elapsed().as_secs_f64() * 1000.0;
+        match result {
+            Ok((results, rows_affected)) => {
+                outcome.status = StatementStatus::Succeeded;
+                outcome.rows_affected = rows_affected;
+                outcome.rows_returned = results.iter().map(|r| r.rows.len()).sum();
+                outcome.results = results;
+            }
+            Err(e) => {
+                outcome.status = StatementStatus::Failed;
+                outcome.error = Some(e);
+                halted = policy != ScriptErrorPolicy::Continue;
+            }
+        }
+        outcomes.push(outcome);
+    }
+    outcomes
+}
+
+/// Runs `statements` one by one on a single connection. An open transaction on
+/// the session is reused, with the `rollback` policy scoped to a savepoint.
+pub async fn run_script(
+    app_state: &AppState,
+    statements: &[ScriptStatement],
+    policy: ScriptErrorPolicy,
+    query_timeout_seconds: Option<u64>,
+) -> Result<ScriptRunResult, String> {
+    let started = Instant::now();
+    let db_type = {
+        let guard = app_state.active_db_type.lock().await;
+        guard.clone()
+    };
+    let session_id = {
+        let guard = app_state.active_session_id.lock().await;
+        guard.clone()
+    };
+    let open_transaction = match session_id.as_deref() {
+        Some(id) => get_transaction(id).await,
+        None => None,
+    };
+
+    let rollback = policy == ScriptErrorPolicy::Rollback;
+    let (outcomes, rolled_back) = match open_transaction {
+        Some(session) => {
+            let mut guard = session.lock().await;
+            if rollback {
+                guard
+                    .savepoint(SavepointAction::Create, SCRIPT_SAVEPOINT)
+                    .await?;
+            }
+            let outcomes = {
+                let mut target = ScriptTarget::Transaction(&mut guard);
+                run_statements(&mut target, statements, policy, query_timeout_seconds).await
+            };
+            let failed = outcomes.iter().any(|o| o.status == StatementStatus::Failed);
+            if rollback {
+                if failed {
+                    guard
+                        .savepoint(SavepointAction::RollbackTo, SCRIPT_SAVEPOINT)
+                        .await?;
+                }
+                guard
+                    .savepoint(SavepointAction::Release, SCRIPT_SAVEPOINT)
+                    .await?;
+            }
+            (outcomes, rollback && failed)
+        }
+        None if rollback => {
+            let mut session =
+                begin_detached_transaction(app_state, session_id.as_deref().unwrap_or_default())
+                    .await?;
+            let outcomes = {
+                let mut target = ScriptTarget::Transaction(&mut session);
+                run_statements(&mut target, statements, policy, query_timeout_seconds).await
+            };
+            let failed = outcomes.iter().any(|o| o.status == StatementStatus::Failed);
+            if failed {
+                session.finish(TransactionControl::Rollback).await?;
+            } else {
+                session.finish(TransactionControl::Commit).await?;
+            }
+            (outcomes, failed)
+        }
+        None => {
+            let mut target = connect_script_target(app_state, &db_type).await?;
+            let outcomes =
+                run_statements(&mut target, statements, policy, query_timeout_seconds).await;
+            (outcomes, false)
+        }
+    };
+
+    let count = |status: StatementStatus| outcomes.iter().filter(|o| o.status == status).count();
+    Ok(ScriptRunResult {
+        policy,
+        succeeded: count(StatementStatus::Succeeded),
+        failed: count(StatementStatus::Failed),
+        skipped: count(StatementStatus::Skipped),
+        statements: outcomes,
+        rolled_back,
+        duration_ms: started.elapsed().as_secs_f64() * 1000.0,
+    })
+}
+
+// =====================================================
+// TAURI COMMANDS
+// =====================================================
+
+#[tauri::command]
+pub async fn split_script_statements(
+    app_state: State<'_, AppState>,
+    script: String,
+    session_id: Option<String>,
+) -> Result<Vec<ScriptStatement>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
+    let db_type = {
+        let guard = app_state.active_db_type.lock().await;
+        guard.clone()
+    };
+    Ok(split_sql_script(&script, &db_type))
+}
+
+#[tauri::command]
+pub async fn execute_sql_script(
+    app_state: State<'_, AppState>,
+    script: String,
+    error_policy: Option<ScriptErrorPolicy>,
+    query_timeout_seconds: Option<u64>,
+    session_id: Option<String>,
+) -> Result<ScriptRunResult, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
+    let db_type = {
+        let guard = app_state.active_db_type.lock().await;
+        guard.clone()
+    };
+    let statements = split_sql_script(&script, &db_type);
+    if statements.is_empty() {
+        return Err("Script contains no statements".to_string());
+    }
+    run_script(
+        &app_state,
+        &statements,
+        error_policy.unwrap_or_default(),
+        query_timeout_seconds,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,111 @@
+use super::*;
+
+fn sqls(script: &str, db_type: DatabaseType) -> Vec<String> {
+    split_sql_script(script, &db_type)
+        .into_iter()
+        .map(|s| s.sql)
+        .collect()
+}
+
+#[test]
+fn test_split_ignores_delimiters_in_strings_and_comments() {
+    let script = "SELECT 'a;b', \"c;d\"; -- note; here\nSELECT 2 /* x; y */;\n";
+    assert_eq!(
+        sqls(script, DatabaseType::PostgreSQL),
+        vec![
+            "SELECT 'a;b', \"c;d\"",
+            "-- note; here\nSELECT 2 /* x; y */"
+        ]
+    );
+}
+
+#[test]
+fn test_split_drops_comment_only_fragments_and_tracks_lines() {
+    let script = "-- header\n\nSELECT 1;\n\n/* trailing */\n";
+    let statements = split_sql_script(script, &DatabaseType::SQLite);
+    assert_eq!(statements.len(), 1);
+    assert_eq!(statements[0].line, 3);
+    assert_eq!(statements[0].index, 0);
+}
+
+#[test]
+fn test_split_mysql_delimiter_and_escapes() {
+    let script = "DELIMITER $$\n\
+        CREATE PROCEDURE p() BEGIN SELECT 'it\\'s;'; SELECT 2; END$$\n\
+        DELIMITER ;\n\
+        CALL p();\n";
+    assert_eq!(
+        sqls(script, DatabaseType::MySQL),
+        vec![
+            "CREATE PROCEDURE p() BEGIN SELECT 'it\\'s;'; SELECT 2; END",
+            "CALL p()"
+        ]
+    );
+}
+
+#[test]
+fn test_split_mysql_dash_comment_needs_space() {
+    assert_eq!(
+        sqls("SELECT 5--1; SELECT 2; -- done", DatabaseType::MySQL),
+        vec!["SELECT 5--1", "SELECT 2"]
+    );
+}
+
+#[test]
+fn test_split_postgres_dollar_bodies() {
+    let script =
+        "CREATE FUNCTION f() RETURNS int AS $body$ BEGIN RETURN 1; END; $body$ LANGUAGE plpgsql;\n\
+        DO $$ BEGIN PERFORM 1; END $$;\n\
+        SELECT $1;";
+    assert_eq!(
+        sqls(script, DatabaseType::PostgreSQL),
+        vec![
+            "CREATE FUNCTION f() RETURNS int AS $body$ BEGIN RETURN 1; END; $body$ LANGUAGE plpgsql",
+            "DO $$ BEGIN PERFORM 1; END $$",
+            "SELECT $1"
+        ]
+    );
+}
+
+#[test]
+fn test_split_mssql_go_batches() {
+    let script =
+        "CREATE TABLE [a;b] (id int);\nINSERT INTO [a;b] VALUES (1);\nGO\nSELECT 1\ngo 2\n";
+    assert_eq!(
+        sqls(script, DatabaseType::MSSQL),
+        vec![
+            "CREATE TABLE [a;b] (id int);\nINSERT INTO [a;b] VALUES (1);",
+            "SELECT 1",
+            "SELECT 1"
+        ]
+    );
+}
+
+#[test]
+fn test_split_sqlite_trigger_body() {
+    let script = "CREATE TRIGGER t AFTER INSERT ON a BEGIN\n\
+        UPDATE b SET n = CASE WHEN n > 0 THEN n ELSE 0 END;\n\
+        DELETE FROM c;\n\
+        END;\n\
+        BEGIN;\nCOMMIT;";
+    let statements = sqls(script, DatabaseType::SQLite);
+    assert_eq!(statements.len(), 3);
+    assert!(statements[0].ends_with("DELETE FROM c;\nEND"));
+    assert_eq!(statements[1], "BEGIN");
+    assert_eq!(statements[2], "COMMIT");
+}
+
+#[test]
+fn test_parse_go_line() {
+    assert_eq!(parse_go_line("GO"), Some(1));
+    assert_eq!(parse_go_line("go 3;"), Some(3));
+    assert_eq!(parse_go_line("GOTO label"), None);
+    assert_eq!(parse_go_line("GO x"), None);
+}
+
+#[test]
+fn test_error_policy_deserializes_lowercase() {
+    let policy: ScriptErrorPolicy = serde_json::from_str("\"rollback\"").unwrap();
+    assert_eq!(policy, ScriptErrorPolicy::Rollback);
+    assert_eq!(ScriptErrorPolicy::default(), ScriptErrorPolicy::Stop);
+}
@@ -0,0 +1,251 @@
+// =====================================================
+// SQL LEXER
+// Dialect quoting and comment rules shared by the script splitter and the
+// bind placeholder scanner, so both agree on what is code
+// =====================================================
+
+use crate::db_types::DatabaseType;
+
+/// Lexical rules of one dialect.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Dialect {
+    /// `\'` escapes inside strings (MySQL, ClickHouse)
+    pub backslash_escapes: bool,
+    pub backtick_quotes: bool,
+    pub bracket_quotes: bool,
+    /// `$tag$ ... $tag$` bodies (PostgreSQL)
+    pub dollar_quotes: bool,
+    pub nested_comments: bool,
+    pub hash_comments: bool,
+    /// MySQL only treats `--` as a comment when followed by whitespace
+    pub dash_comment_needs_space: bool,
+    /// Client-side `DELIMITER xx` lines (MySQL)
+    pub delimiter_directive: bool,
+    /// `GO` lines separate batches and `;` does not split (MSSQL)
+    pub go_batches: bool,
+    /// `;` inside `CREATE TRIGGER ... BEGIN ... END` does not split (SQLite)
+    pub trigger_blocks: bool,
+}
+
+impl Dialect {
+    pub fn for_db(db_type: &DatabaseType) -> Self {
+        let base = Dialect {
+            backslash_escapes: false,
+            backtick_quotes: false,
+            bracket_quotes: false,
+            dollar_quotes: false,
+            nested_comments: false,
+            hash_comments: false,
+            dash_comment_needs_space: false,
+            delimiter_directive: false,
+            go_batches: false,
+            trigger_blocks: false,
+        };
+        match db_type {
+            DatabaseType::MySQL => Dialect {
+                backslash_escapes: true,
+                backtick_quotes: true,
+                hash_comments: true,
+                dash_comment_needs_space: true,
+                delimiter_directive: true,
+                ..base
+            },
+            DatabaseType::PostgreSQL => Dialect {
+                dollar_quotes: true,
+                nested_comments: true,
+                ..base
+            },
+            DatabaseType::MSSQL => Dialect {
+                bracket_quotes: true,
+                go_batches: true,
+                ..base
+            },
+            DatabaseType::ClickHouse => Dialect {
+                backslash_escapes: true,
+                backtick_quotes: true,
+                ..base
+            },
+            DatabaseType::SQLite => Dialect {
+                backtick_quotes: true,
+                bracket_quotes: true,
+                trigger_blocks: true,
+                ..base
+            },
+            DatabaseType::Disconnected => base,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum LexState {
+    Normal,
+    LineComment,
+    BlockComment(usize),
+    Quoted { close: char, backslash: bool },
+    Dollar(String),
+}
+
+/// What `SqlLexer::open` entered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Opened {
+    Comment,
+    Quote,
+    Dollar,
+}
+
+pub(crate) fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Tracks whether the text fed so far ends inside a comment, string, quoted
+/// identifier or dollar-quoted body. Callers handle code themselves and hand
+/// the rest to the lexer.
+#[derive(Debug, Clone)]
+pub(crate) struct SqlLexer {
+    pub dialect: Dialect,
+    pub state: LexState,
+}
+
+impl SqlLexer {
+    pub fn new(db_type: &DatabaseType) -> Self {
+        Self {
+            dialect: Dialect::for_db(db_type),
+            state: LexState::Normal,
+        }
+    }
+
+    pub fn in_code(&self) -> bool {
+        self.state == LexState::Normal
+    }
+
+    /// In code: enters the comment, string or dollar body that starts at
+    /// `chars[0]` and returns what it was and the opener's length. `prev` is
+    /// the character before, and `e_prefix` says the word just before is
+    /// PostgreSQL's `E` string prefix.
+    pub fn open(
+        &mut self,
+        chars: &[char],
+        prev: Option<char>,
+        e_prefix: bool,
+    ) -> Option<(Opened, usize)> {
+        let c = *chars.first()?;
+        let next = chars.get(1).copied();
+
+        if c == '-' && next == Some('-') {
+            let spaced = chars.get(2).is_none_or(|after| after.is_whitespace());
+            if !self.dialect.dash_comment_needs_space || spaced {
+                self.state = LexState::LineComment;
+                return Some((Opened::Comment, 2));
+            }
+        }
+        if self.dialect.hash_comments && c == '#' {
+            self.state = LexState::LineComment;
+            return Some((Opened::Comment, 1));
+        }
+        if c == '/' && next == Some('*') {
+            self.state = LexState::BlockComment(1);
+            return Some((Opened::Comment, 2));
+        }
+
+        let escape_string = e_prefix && self.dialect.dollar_quotes;
+        let quote = match c {
+            '\'' => Some(('\'', self.dialect.backslash_escapes || escape_string)),
+            '"' => Some(('"', self.dialect.backslash_escapes)),
+            '`' if self.dialect.backtick_quotes => Some(('`', false)),
+            '[' if self.dialect.bracket_quotes => Some((']', false)),
+            _ => None,
+        };
+        if let Some((close, backslash)) = quote {
+            self.state = LexState::Quoted { close, backslash };
+            return Some((Opened::Quote, 1));
+        }
+
+        if c == '$' && self.dialect.dollar_quotes && !prev.is_some_and(is_ident_char) {
+            if let Some(tag) = dollar_tag(chars) {
+                let len = tag.chars().count();
+                self.state = LexState::Dollar(tag);
+                return Some((Opened::Dollar, len));
+            }
+        }
+        None
+    }
+
+    /// Outside code: consumes the next character (or escape pair, doubled
+    /// quote or closing delimiter) at `chars[0]` and returns how many it took.
+    pub fn step(&mut self, chars: &[char]) -> usize {
+        let Some(&c) = chars.first() else {
+            return 0;
+        };
+        let next = chars.get(1).copied();
+
+        match &self.state {
+            LexState::Normal => 1,
+            LexState::LineComment => {
+                if c == '\n' {
+                    self.state = LexState::Normal;
+                }
+                1
+            }
+            LexState::BlockComment(depth) => {
+                let depth = *depth;
+                if c == '*' && next == Some('/') {
+                    self.state = if depth > 1 {
+                        LexState::BlockComment(depth - 1)
+                    } else {
+                        LexState::Normal
+                    };
+                    2
+                } else if self.dialect.nested_comments && c == '/' && next == Some('*') {
+                    self.state = LexState::BlockComment(depth + 1);
+                    2
+                } else {
+                    1
+                }
+            }
+            LexState::Quoted { close, backslash } => {
+                let (close, backslash) = (*close, *backslash);
+                if backslash && c == '\\' {
+                    if next.is_some() {
+                        2
+                    } else {
+                        1
+                    }
+                } else if c == close {
+                    if next == Some(close) {
+                        2
+                    } else {
+                        self.state = LexState::Normal;
+                        1
+                    }
+                } else {
+                    1
+                }
+            }
+            LexState::Dollar(tag) => {
+                let tag_chars: Vec<char> = tag.chars().collect();
+                if chars.starts_with(&tag_chars) {
+                    self.state = LexState::Normal;
+                    tag_chars.len()
+                } else {
+                    1
+                }
+            }
+        }
+    }
+}
+
+/// Opening `$tag$` of a dollar-quoted body, if `chars` starts with one.
+fn dollar_tag(chars: &[char]) -> Option<String> {
+    let mut end = 1;
+    while end < chars.len() && is_ident_char(chars[end]) {
+        end += 1;
+    }
+    if chars.get(end) != Some(&'$') {
+        return None;
+    }
+    if chars.get(1).is_some_and(|c| c.is_ascii_digit()) {
+        // `$1` placeholders, not a tag
+        return None;
+    }
+    Some(chars[..=end].iter().collect())
+}
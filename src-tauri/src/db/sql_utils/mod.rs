@@ -143,5 +143,42 @@ pub fn build_insert_statements(
         .collect()
 }
 
+/// One multi-row `INSERT` for `rows`, or `None` when there is nothing to insert.
+pub fn build_batched_insert_statement(
+    db_type: &DatabaseType,
+    database: &str,
+    table: &str,
+    columns: &[String],
+    rows: &[Vec<serde_json::Value>],
+) -> Option<String> {
+    if columns.is_empty() || rows.is_empty() {
+        return None;
+    }
+
+    let quoted_columns = columns
+        .iter()
+        .map(|col| quote_column_name(db_type, col))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let values = rows
+        .iter()
+        .map(|row| {
+            let literals = row
+                .iter()
+                .map(value_to_sql_literal)
+                .collect::<Vec<String>>()
+                .join(", ");
+            format!("({})", literals)
+        })
+        .collect::<Vec<String>>()
+        .join(",\n");
+    Some(format!(
+        "INSERT INTO {} ({}) VALUES\n{};",
+        qualified_table_name(db_type, database, table),
+        quoted_columns,
+        values
+    ))
+}
+
 #[cfg(test)]
 mod tests;
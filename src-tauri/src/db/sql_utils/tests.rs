@@ -77,3 +77,19 @@ fn test_build_insert_statements() {
         "INSERT INTO `db`.`t` (`id`, `name`) VALUES (2, 'b''c');"
     );
 }
+
+#[test]
+fn test_build_batched_insert_statement() {
+    let columns = vec!["id".to_string(), "name".to_string()];
+    let rows = vec![
+        vec![serde_json::json!(1), serde_json::json!("a")],
+        vec![serde_json::json!(2), serde_json::Value::Null],
+    ];
+
+    assert_eq!(
+        build_batched_insert_statement(&DatabaseType::PostgreSQL, "public", "t", &columns, &rows)
+            .unwrap(),
+        "INSERT INTO \"public\".\"t\" (\"id\", \"name\") VALUES\n(1, 'a'),\n(2, NULL);"
+    );
+    assert!(build_batched_insert_statement(&DatabaseType::MySQL, "db", "t", &columns, &[]).is_none());
+}
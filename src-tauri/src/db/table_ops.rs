@@ -26,7 +26,9 @@ pub async fn truncate_table(
     database: String,
     schema: Option<String>,
     table: String,
+    session_id: Option<String>,
 ) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -102,7 +104,9 @@ pub async fn drop_table(
     schema: Option<String>,
     table: String,
     cascade: bool,
+    session_id: Option<String>,
 ) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -179,7 +183,9 @@ pub async fn rename_table(
     schema: Option<String>,
     table: String,
     new_name: String,
+    session_id: Option<String>,
 ) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -255,7 +261,9 @@ pub async fn duplicate_table(
     table: String,
     new_name: String,
     include_data: bool,
+    session_id: Option<String>,
 ) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -372,7 +380,9 @@ pub async fn vacuum_table(
     table: String,
     full: bool,
     analyze: bool,
+    session_id: Option<String>,
 ) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -417,7 +427,9 @@ pub async fn reindex_table(
     database: String,
     schema: Option<String>,
     table: String,
+    session_id: Option<String>,
 ) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -456,7 +468,9 @@ pub async fn get_table_dependencies(
     database: String,
     schema: Option<String>,
     table: String,
+    session_id: Option<String>,
 ) -> Result<TableDependencies, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -667,7 +681,9 @@ pub async fn drop_view(
     database: String,
     schema: Option<String>,
     view: String,
+    session_id: Option<String>,
 ) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -742,7 +758,9 @@ pub async fn drop_trigger(
     schema: Option<String>,
     trigger: String,
     table: Option<String>,
+    session_id: Option<String>,
 ) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -786,7 +804,9 @@ pub async fn drop_trigger(
 pub async fn drop_database(
     app_state: State<'_, AppState>,
     database: String,
+    session_id: Option<String>,
 ) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -838,7 +858,9 @@ pub async fn drop_database(
 pub async fn create_database(
     app_state: State<'_, AppState>,
     database: String,
+    session_id: Option<String>,
 ) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
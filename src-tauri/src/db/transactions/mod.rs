@@ -0,0 +1,879 @@
+// =====================================================
+// TRANSACTIONS MODULE
+// Explicit transactions pinned to a single pooled connection
+// =====================================================
+
+use crate::db::running_queries::{annotate_cancelled, CancelHandle, ExecutionGuard};
+use crate::db_types::{AppState, BindValue, DatabaseType, QueryResult};
+use crate::mssql;
+use crate::mysql;
+use crate::postgres;
+use crate::sqlite;
+use deadpool_tiberius::deadpool::managed::Object;
+use serde::Serialize;
+use sqlx::pool::PoolConnection;
+use sqlx::{MySql, Postgres, Sqlite};
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::Mutex;
+use tokio::time::{sleep, Duration};
+
+// =====================================================
+// CONSTANTS
+// =====================================================
+
+pub const TRANSACTION_IDLE_TIMEOUT_SECS: i64 = 900;
+const TRANSACTION_REAPER_INTERVAL_SECS: u64 = 30;
+pub const TRANSACTION_AUTO_ROLLBACK_EVENT: &str = "transaction-auto-rollback";
+
+// =====================================================
+// GLOBAL STATE
+// =====================================================
+
+/// Open transactions keyed by connection session id.
+pub static TRANSACTION_SESSIONS: LazyLock<Mutex<HashMap<String, Arc<Mutex<TransactionSession>>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+// =====================================================
+// STRUCTS
+// =====================================================
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransactionControl {
+    Begin,
+    Commit,
+    Rollback,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SavepointAction {
+    Create,
+    RollbackTo,
+    Release,
+}
+
+/// A query split around the transaction statements it starts and/or ends with.
+#[derive(Debug, PartialEq)]
+pub struct TransactionScript<'a> {
+    pub begin: bool,
+    pub body: &'a str,
+    pub end: Option<TransactionControl>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionInfo {
+    pub session_id: String,
+    pub db_type: DatabaseType,
+    pub started_at: i64,
+    pub last_activity: i64,
+    pub statement_count: u64,
+    pub has_uncommitted_changes: bool,
+    pub savepoints: Vec<String>,
+}
+
+pub enum PinnedConnection {
+    MySQL(PoolConnection<MySql>),
+    PostgreSQL(PoolConnection<Postgres>),
+    SQLite(PoolConnection<Sqlite>),
+    /// Boxed: a pooled tiberius client dwarfs the sqlx handles
+    MSSQL(Box<Object<deadpool_tiberius::Manager>>),
+}
+
+impl PinnedConnection {
+    async fn execute(
+        &mut self,
+        query: &str,
+        query_timeout_seconds: Option<u64>,
+    ) -> Result<Vec<QueryResult>, String> {
+        match self {
+            PinnedConnection::MySQL(conn) => {
+                mysql::execute_query_on_connection(conn.as_mut(), query, query_timeout_seconds).await
+            }
+            PinnedConnection::PostgreSQL(conn) => {
+                postgres::execute_query_on_connection(conn.as_mut(), query, query_timeout_seconds)
+                    .await
+            }
+            PinnedConnection::SQLite(conn) => {
+                sqlite::execute_query_on_connection(conn.as_mut(), query).await
+            }
+            PinnedConnection::MSSQL(conn) => mssql::execute_query_on_client((**conn).as_mut(), query).await,
+        }
+    }
+
+    pub(crate) async fn execute_parameterized(
+        &mut self,
+        query: &str,
+        params: &[BindValue],
+        query_timeout_seconds: Option<u64>,
+    ) -> Result<Vec<QueryResult>, String> {
+        match self {
+            PinnedConnection::MySQL(conn) => {
+                mysql::execute_parameterized_query(conn.as_mut(), query, params, query_timeout_seconds)
+                    .await
+            }
+            PinnedConnection::PostgreSQL(conn) => {
+                postgres::execute_parameterized_query(
+                    conn.as_mut(),
+                    query,
+                    params,
+                    query_timeout_seconds,
+                )
+                .await
+            }
+            PinnedConnection::SQLite(conn) => {
+                sqlite::execute_parameterized_query(conn.as_mut(), query, params).await
+            }
+            PinnedConnection::MSSQL(conn) => {
+                mssql::execute_parameterized_query_on_client((**conn).as_mut(), query, params).await
+            }
+        }
+    }
+
+    /// Runs one statement of a script, also reporting rows affected where the
+    /// driver exposes it.
+    pub(crate) async fn execute_script_statement(
+        &mut self,
+        query: &str,
+        query_timeout_seconds: Option<u64>,
+    ) -> Result<(Vec<QueryResult>, Option<u64>), String> {
+        match self {
+            PinnedConnection::MySQL(conn) => {
+                mysql::execute_script_statement(conn.as_mut(), query, query_timeout_seconds)
+                    .await
+                    .map(|(results, rows)| (results, Some(rows)))
+            }
+            PinnedConnection::PostgreSQL(conn) => {
+                postgres::execute_script_statement(conn.as_mut(), query, query_timeout_seconds)
+                    .await
+                    .map(|(results, rows)| (results, Some(rows)))
+            }
+            PinnedConnection::SQLite(conn) => sqlite::execute_script_statement(conn.as_mut(), query)
+                .await
+                .map(|(results, rows)| (results, Some(rows))),
+            PinnedConnection::MSSQL(conn) => mssql::execute_query_on_client((**conn).as_mut(), query)
+                .await
+                .map(|results| (results, None)),
+        }
+    }
+
+    /// How to stop a statement running on this connection.
+    pub(crate) async fn cancel_handle(&mut self) -> Option<CancelHandle> {
+        match self {
+            PinnedConnection::MySQL(conn) => mysql::current_connection_id(conn.as_mut())
+                .await
+                .ok()
+                .map(CancelHandle::MySQL),
+            PinnedConnection::PostgreSQL(conn) => postgres::current_backend_pid(conn.as_mut())
+                .await
+                .ok()
+                .map(CancelHandle::PostgreSQL),
+            PinnedConnection::SQLite(conn) => sqlite::interrupt_handle(conn.as_mut())
+                .await
+                .ok()
+                .map(CancelHandle::SQLite),
+            PinnedConnection::MSSQL(conn) => mssql::current_session_id((**conn).as_mut())
+                .await
+                .ok()
+                .map(CancelHandle::MSSQL),
+        }
+    }
+
+    /// Drops the connection without handing it back to the pool, for when its
+    /// transaction state can no longer be trusted.
+    fn discard(self) {
+        match self {
+            PinnedConnection::MySQL(conn) => {
+                let _ = conn.detach();
+            }
+            PinnedConnection::PostgreSQL(conn) => {
+                let _ = conn.detach();
+            }
+            PinnedConnection::SQLite(conn) => {
+                let _ = conn.detach();
+            }
+            PinnedConnection::MSSQL(conn) => {
+                let _ = Object::take(*conn);
+            }
+        }
+    }
+}
+
+pub struct TransactionSession {
+    pub session_id: String,
+    pub db_type: DatabaseType,
+    pub started_at: i64,
+    pub last_activity: i64,
+    pub statement_count: u64,
+    pub has_uncommitted_changes: bool,
+    pub savepoints: Vec<String>,
+    connection: Option<PinnedConnection>,
+    /// Looked up once, since the pinned connection never changes
+    cancel_handle: Option<CancelHandle>,
+}
+
+impl TransactionSession {
+    fn new(session_id: String, db_type: DatabaseType, connection: PinnedConnection) -> Self {
+        let now = chrono::Utc::now().timestamp();
+        Self {
+            session_id,
+            db_type,
+            started_at: now,
+            last_activity: now,
+            statement_count: 0,
+            has_uncommitted_changes: false,
+            savepoints: Vec::new(),
+            connection: Some(connection),
+            cancel_handle: None,
+        }
+    }
+
+    pub fn info(&self) -> TransactionInfo {
+        TransactionInfo {
+            session_id: self.session_id.clone(),
+            db_type: self.db_type.clone(),
+            started_at: self.started_at,
+            last_activity: self.last_activity,
+            statement_count: self.statement_count,
+            has_uncommitted_changes: self.has_uncommitted_changes,
+            savepoints: self.savepoints.clone(),
+        }
+    }
+
+    async fn run(
+        &mut self,
+        query: &str,
+        query_timeout_seconds: Option<u64>,
+    ) -> Result<Vec<QueryResult>, String> {
+        let connection = self
+            .connection
+            .as_mut()
+            .ok_or("Transaction has already ended")?;
+        self.last_activity = chrono::Utc::now().timestamp();
+        connection.execute(query, query_timeout_seconds).await
+    }
+
+    pub async fn execute(
+        &mut self,
+        query: &str,
+        query_timeout_seconds: Option<u64>,
+    ) -> Result<Vec<QueryResult>, String> {
+        let results = self.run(query, query_timeout_seconds).await?;
+        self.record_statement(query);
+        Ok(results)
+    }
+
+    pub async fn execute_parameterized(
+        &mut self,
+        query: &str,
+        params: &[BindValue],
+        query_timeout_seconds: Option<u64>,
+    ) -> Result<Vec<QueryResult>, String> {
+        let connection = self
+            .connection
+            .as_mut()
+            .ok_or("Transaction has already ended")?;
+        self.last_activity = chrono::Utc::now().timestamp();
+        let results = connection
+            .execute_parameterized(query, params, query_timeout_seconds)
+            .await?;
+        self.record_statement(query);
+        Ok(results)
+    }
+
+    pub(crate) async fn execute_script_statement(
+        &mut self,
+        query: &str,
+        query_timeout_seconds: Option<u64>,
+    ) -> Result<(Vec<QueryResult>, Option<u64>), String> {
+        let connection = self
+            .connection
+            .as_mut()
+            .ok_or("Transaction has already ended")?;
+        self.last_activity = chrono::Utc::now().timestamp();
+        let outcome = connection
+            .execute_script_statement(query, query_timeout_seconds)
+            .await?;
+        self.record_statement(query);
+        Ok(outcome)
+    }
+
+    /// Registers the pinned connection's cancel handle on `execution`. The
+    /// caller clears it before releasing the session lock.
+    pub(crate) async fn track(&mut self, execution: &ExecutionGuard) {
+        if self.cancel_handle.is_none() {
+            if let Some(connection) = self.connection.as_mut() {
+                self.cancel_handle = connection.cancel_handle().await;
+            }
+        }
+        if let Some(handle) = self.cancel_handle.clone() {
+            execution.set_cancel_handle(handle);
+        }
+    }
+
+    fn record_statement(&mut self, query: &str) {
+        self.statement_count += 1;
+        if !is_read_only_statement(query) {
+            self.has_uncommitted_changes = true;
+        }
+    }
+
+    /// Ends the transaction. The connection goes back to the pool only when the
+    /// COMMIT/ROLLBACK itself succeeded.
+    pub(crate) async fn finish(&mut self, control: TransactionControl) -> Result<(), String> {
+        let statement = end_statement(&self.db_type, control);
+        let result = self.run(statement, None).await.map(|_| ());
+        if let Some(connection) = self.connection.take() {
+            if result.is_err() {
+                connection.discard();
+            }
+        }
+        result
+    }
+
+    pub(crate) async fn savepoint(&mut self, action: SavepointAction, name: &str) -> Result<(), String> {
+        validate_savepoint_name(name)?;
+        if action != SavepointAction::Create && !self.savepoints.iter().any(|s| s == name) {
+            return Err(format!("Savepoint not found: {}", name));
+        }
+
+        if let Some(statement) = savepoint_statement(&self.db_type, action, name) {
+            self.run(&statement, None).await?;
+        }
+
+        match action {
+            SavepointAction::Create => self.savepoints.push(name.to_string()),
+            SavepointAction::RollbackTo => {
+                if let Some(pos) = self.savepoints.iter().position(|s| s == name) {
+                    self.savepoints.truncate(pos + 1);
+                }
+            }
+            SavepointAction::Release => {
+                if let Some(pos) = self.savepoints.iter().position(|s| s == name) {
+                    self.savepoints.truncate(pos);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// =====================================================
+// SQL HELPERS
+// =====================================================
+
+fn normalize_statement(statement: &str) -> String {
+    statement
+        .trim()
+        .trim_end_matches(';')
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_uppercase()
+}
+
+/// Recognises a bare transaction-control statement such as `BEGIN` or `COMMIT WORK`.
+pub fn classify_transaction_control(statement: &str) -> Option<TransactionControl> {
+    match normalize_statement(statement).as_str() {
+        "BEGIN" | "BEGIN WORK" | "BEGIN TRAN" | "BEGIN TRANSACTION" | "START TRANSACTION" => {
+            Some(TransactionControl::Begin)
+        }
+        // Bare `END` is left alone: it also closes procedure and trigger bodies
+        "COMMIT" | "COMMIT WORK" | "COMMIT TRAN" | "COMMIT TRANSACTION" | "END TRANSACTION" => {
+            Some(TransactionControl::Commit)
+        }
+        "ROLLBACK" | "ROLLBACK WORK" | "ROLLBACK TRAN" | "ROLLBACK TRANSACTION" | "ABORT" => {
+            Some(TransactionControl::Rollback)
+        }
+        _ => None,
+    }
+}
+
+/// Splits off a leading `BEGIN` and a trailing `COMMIT`/`ROLLBACK` so that
+/// `BEGIN; UPDATE ...;` and a later `COMMIT` both land on the pinned connection.
+pub fn parse_transaction_script(query: &str) -> TransactionScript<'_> {
+    let mut body = query.trim();
+    let mut begin = false;
+
+    let (first, rest) = body.split_once(';').unwrap_or((body, ""));
+    if classify_transaction_control(first) == Some(TransactionControl::Begin) {
+        begin = true;
+        body = rest.trim();
+    }
+
+    let trimmed = body.trim_end_matches(|c: char| c == ';' || c.is_whitespace());
+    let (head, last) = match trimmed.rfind(';') {
+        Some(pos) => (&trimmed[..=pos], &trimmed[pos + 1..]),
+        None => ("", trimmed),
+    };
+    let end = match classify_transaction_control(last) {
+        Some(TransactionControl::Begin) | None => None,
+        control => control,
+    };
+    if end.is_some() {
+        body = head.trim();
+    }
+
+    TransactionScript { begin, body, end }
+}
+
+/// Statements that cannot leave uncommitted changes behind.
+pub fn is_read_only_statement(query: &str) -> bool {
+    let head = normalize_statement(query);
+    let keyword = head.split(' ').next().unwrap_or("");
+    if query.trim().trim_end_matches(';').contains(';') {
+        return false;
+    }
+    matches!(keyword, "SELECT" | "SHOW" | "DESCRIBE" | "DESC" | "EXPLAIN" | "PRAGMA")
+        || (keyword == "WITH"
+            && !["INSERT", "UPDATE", "DELETE", "MERGE"]
+                .iter()
+                .any(|kw| head.contains(kw)))
+}
+
+pub fn begin_statement(db_type: &DatabaseType) -> Result<&'static str, String> {
+    match db_type {
+        DatabaseType::MySQL => Ok("START TRANSACTION"),
+        DatabaseType::PostgreSQL | DatabaseType::SQLite => Ok("BEGIN"),
+        DatabaseType::MSSQL => Ok("BEGIN TRANSACTION"),
+        DatabaseType::ClickHouse => Err("Transactions are not supported for ClickHouse".into()),
+        DatabaseType::Disconnected => Err("No connection established".into()),
+    }
+}
+
+pub fn end_statement(db_type: &DatabaseType, control: TransactionControl) -> &'static str {
+    match (db_type, control) {
+        (DatabaseType::MSSQL, TransactionControl::Commit) => "COMMIT TRANSACTION",
+        (DatabaseType::MSSQL, _) => "ROLLBACK TRANSACTION",
+        (_, TransactionControl::Commit) => "COMMIT",
+        (_, _) => "ROLLBACK",
+    }
+}
+
+/// MSSQL has no `RELEASE SAVEPOINT`; releasing there only forgets the name.
+pub fn savepoint_statement(
+    db_type: &DatabaseType,
+    action: SavepointAction,
+    name: &str,
+) -> Option<String> {
+    match (db_type, action) {
+        (DatabaseType::MSSQL, SavepointAction::Create) => Some(format!("SAVE TRANSACTION {}", name)),
+        (DatabaseType::MSSQL, SavepointAction::RollbackTo) => {
+            Some(format!("ROLLBACK TRANSACTION {}", name))
+        }
+        (DatabaseType::MSSQL, SavepointAction::Release) => None,
+        (_, SavepointAction::Create) => Some(format!("SAVEPOINT {}", name)),
+        (_, SavepointAction::RollbackTo) => Some(format!("ROLLBACK TO SAVEPOINT {}", name)),
+        (_, SavepointAction::Release) => Some(format!("RELEASE SAVEPOINT {}", name)),
+    }
+}
+
+pub fn validate_savepoint_name(name: &str) -> Result<(), String> {
+    let mut chars = name.chars();
+    let valid_start = chars
+        .next()
+        .map(|c| c.is_ascii_alphabetic() || c == '_')
+        .unwrap_or(false);
+    if !valid_start || !chars.all(|c| c.is_ascii_alphanumeric() || c == '_') || name.len() > 64 {
+        return Err(format!("Invalid savepoint name: {}", name));
+    }
+    Ok(())
+}
+
+// =====================================================
+// TRANSACTION REGISTRY
+// =====================================================
+
+async fn resolve_session_id(app_state: &AppState) -> Result<String, String> {
+    let guard = app_state.active_session_id.lock().await;
+    guard.clone().ok_or_else(|| "No connection established".to_string())
+}
+
+pub async fn get_transaction(session_id: &str) -> Option<Arc<Mutex<TransactionSession>>> {
+    let guard = TRANSACTION_SESSIONS.lock().await;
+    guard.get(session_id).cloned()
+}
+
+async fn require_transaction(session_id: &str) -> Result<Arc<Mutex<TransactionSession>>, String> {
+    get_transaction(session_id)
+        .await
+        .ok_or_else(|| "No open transaction for this connection".to_string())
+}
+
+pub(crate) async fn pin_connection(
+    app_state: &AppState,
+    db_type: &DatabaseType,
+) -> Result<PinnedConnection, String> {
+    match db_type {
+        DatabaseType::MySQL => {
+            let pool = {
+                let guard = app_state.mysql_pool.lock().await;
+                guard.clone().ok_or("No MySQL connection established")?
+            };
+            let conn = pool
+                .acquire()
+                .await
+                .map_err(|e| format!("Failed to acquire connection: {}", e))?;
+            Ok(PinnedConnection::MySQL(conn))
+        }
+        DatabaseType::PostgreSQL => {
+            let pool = {
+                let guard = app_state.postgres_pool.lock().await;
+                guard.clone().ok_or("No PostgreSQL connection established")?
+            };
+            let conn = pool
+                .acquire()
+                .await
+                .map_err(|e| format!("Failed to acquire connection: {}", e))?;
+            Ok(PinnedConnection::PostgreSQL(conn))
+        }
+        DatabaseType::SQLite => {
+            let pool = {
+                let guard = app_state.sqlite_pool.lock().await;
+                guard.clone().ok_or("No SQLite connection established")?
+            };
+            let conn = pool
+                .acquire()
+                .await
+                .map_err(|e| format!("Failed to acquire connection: {}", e))?;
+            Ok(PinnedConnection::SQLite(conn))
+        }
+        DatabaseType::MSSQL => {
+            let pool = {
+                let guard = app_state.mssql_pool.lock().await;
+                guard.clone().ok_or("No MSSQL connection established")?
+            };
+            let conn = pool.get().await.map_err(|e| e.to_string())?;
+            Ok(PinnedConnection::MSSQL(Box::new(conn)))
+        }
+        DatabaseType::ClickHouse => Err("Transactions are not supported for ClickHouse".into()),
+        DatabaseType::Disconnected => Err("No connection established".into()),
+    }
+}
+
+async fn start_transaction(
+    app_state: &AppState,
+    session_id: &str,
+) -> Result<Arc<Mutex<TransactionSession>>, String> {
+    if get_transaction(session_id).await.is_some() {
+        return Err("A transaction is already open for this connection".into());
+    }
+
+    let db_type = {
+        let guard = app_state.active_db_type.lock().await;
+        guard.clone()
+    };
+    let begin = begin_statement(&db_type)?;
+    let connection = pin_connection(app_state, &db_type).await?;
+
+    let mut session = TransactionSession::new(session_id.to_string(), db_type, connection);
+    if let Err(e) = session.run(begin, None).await {
+        if let Some(connection) = session.connection.take() {
+            connection.discard();
+        }
+        return Err(e);
+    }
+
+    let session = Arc::new(Mutex::new(session));
+    let mut guard = TRANSACTION_SESSIONS.lock().await;
+    if guard.contains_key(session_id) {
+        drop(guard);
+        let _ = session.lock().await.finish(TransactionControl::Rollback).await;
+        return Err("A transaction is already open for this connection".into());
+    }
+    guard.insert(session_id.to_string(), session.clone());
+    Ok(session)
+}
+
+/// Opens a transaction on a freshly pinned connection without registering it
+/// for the session, for operations that commit or roll back on their own.
+pub(crate) async fn begin_detached_transaction(
+    app_state: &AppState,
+    session_id: &str,
+) -> Result<TransactionSession, String> {
+    let db_type = {
+        let guard = app_state.active_db_type.lock().await;
+        guard.clone()
+    };
+    let begin = begin_statement(&db_type)?;
+    let connection = pin_connection(app_state, &db_type).await?;
+
+    let mut session = TransactionSession::new(session_id.to_string(), db_type, connection);
+    if let Err(e) = session.run(begin, None).await {
+        if let Some(connection) = session.connection.take() {
+            connection.discard();
+        }
+        return Err(e);
+    }
+    Ok(session)
+}
+
+async fn end_transaction(
+    session_id: &str,
+    control: TransactionControl,
+) -> Result<TransactionInfo, String> {
+    let session = {
+        let mut guard = TRANSACTION_SESSIONS.lock().await;
+        guard.remove(session_id)
+    }
+    .ok_or_else(|| "No open transaction for this connection".to_string())?;
+
+    let mut session = session.lock().await;
+    let info = session.info();
+    session.finish(control).await?;
+    Ok(info)
+}
+
+/// Rolls back the session's open transaction, if any. Must run before the
+/// session's pools are closed, since a pinned connection blocks pool shutdown.
+pub async fn rollback_transaction_for_session(session_id: &str) {
+    if get_transaction(session_id).await.is_none() {
+        return;
+    }
+    if let Err(e) = end_transaction(session_id, TransactionControl::Rollback).await {
+        eprintln!("Failed to roll back transaction for {}: {}", session_id, e);
+    }
+}
+
+pub async fn rollback_all_transactions() {
+    let session_ids: Vec<String> = {
+        let guard = TRANSACTION_SESSIONS.lock().await;
+        guard.keys().cloned().collect()
+    };
+    for session_id in session_ids {
+        rollback_transaction_for_session(&session_id).await;
+    }
+}
+
+/// Routes `query` through the session's open transaction. Returns `None` when no
+/// transaction is open and the query does not start one, so callers fall back
+/// to the pool.
+pub async fn execute_in_transaction(
+    app_state: &AppState,
+    execution: &ExecutionGuard,
+    query: &str,
+    query_timeout_seconds: Option<u64>,
+) -> Result<Option<Vec<QueryResult>>, String> {
+    let session_id = {
+        let guard = app_state.active_session_id.lock().await;
+        guard.clone()
+    };
+    let Some(session_id) = session_id else {
+        return Ok(None);
+    };
+
+    let script = parse_transaction_script(query);
+    let session = match get_transaction(&session_id).await {
+        Some(_) if script.begin => {
+            return Err("A transaction is already open for this connection".into())
+        }
+        Some(session) => session,
+        None if script.begin => start_transaction(app_state, &session_id).await?,
+        None => return Ok(None),
+    };
+
+    let mut results = if script.body.is_empty() {
+        Vec::new()
+    } else {
+        let mut guard = session.lock().await;
+        guard.track(execution).await;
+        let result = guard.execute(script.body, query_timeout_seconds).await;
+        execution.clear_cancel_handle();
+        result.map_err(|e| annotate_cancelled(execution, e))?
+    };
+
+    if let Some(control) = script.end {
+        end_transaction(&session_id, control).await?;
+    }
+
+    if results.is_empty() {
+        results.push(QueryResult {
+            columns: vec![],
+            rows: vec![],
+            query_id: None,
+            statistics: None,
+            warnings: vec![],
+        });
+    }
+    Ok(Some(results))
+}
+
+/// Runs an already-bound statement in the session's open transaction, or returns
+/// `None` when there is none.
+pub async fn execute_parameterized_in_transaction(
+    app_state: &AppState,
+    execution: &ExecutionGuard,
+    query: &str,
+    params: &[BindValue],
+    query_timeout_seconds: Option<u64>,
+) -> Result<Option<Vec<QueryResult>>, String> {
+    let session_id = {
+        let guard = app_state.active_session_id.lock().await;
+        guard.clone()
+    };
+    let Some(session_id) = session_id else {
+        return Ok(None);
+    };
+    let Some(session) = get_transaction(&session_id).await else {
+        return Ok(None);
+    };
+
+    let mut guard = session.lock().await;
+    guard.track(execution).await;
+    let result = guard
+        .execute_parameterized(query, params, query_timeout_seconds)
+        .await;
+    execution.clear_cancel_handle();
+    result
+        .map(Some)
+        .map_err(|e| annotate_cancelled(execution, e))
+}
+
+/// Rolls back transactions left idle past `TRANSACTION_IDLE_TIMEOUT_SECS` so they
+/// do not hold locks indefinitely.
+pub fn start_transaction_reaper(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            sleep(Duration::from_secs(TRANSACTION_REAPER_INTERVAL_SECS)).await;
+
+            let cutoff = chrono::Utc::now().timestamp() - TRANSACTION_IDLE_TIMEOUT_SECS;
+            let expired: Vec<String> = {
+                let guard = TRANSACTION_SESSIONS.lock().await;
+                guard
+                    .iter()
+                    .filter(|(_, session)| {
+                        // A session busy running a statement is not idle
+                        session
+                            .try_lock()
+                            .map(|s| s.last_activity < cutoff)
+                            .unwrap_or(false)
+                    })
+                    .map(|(id, _)| id.clone())
+                    .collect()
+            };
+
+            for session_id in expired {
+                match end_transaction(&session_id, TransactionControl::Rollback).await {
+                    Ok(info) => {
+                        let _ = app.emit(TRANSACTION_AUTO_ROLLBACK_EVENT, info);
+                    }
+                    Err(e) => eprintln!("Idle transaction rollback failed for {}: {}", session_id, e),
+                }
+            }
+        }
+    });
+}
+
+// =====================================================
+// TAURI COMMANDS
+// =====================================================
+
+#[tauri::command]
+pub async fn begin_transaction(
+    app_state: State<'_, AppState>,
+    session_id: Option<String>,
+) -> Result<TransactionInfo, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
+    let session_id = resolve_session_id(&app_state).await?;
+    let session = start_transaction(&app_state, &session_id).await?;
+    let info = session.lock().await.info();
+    Ok(info)
+}
+
+#[tauri::command]
+pub async fn commit_transaction(
+    app_state: State<'_, AppState>,
+    session_id: Option<String>,
+) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
+    let session_id = resolve_session_id(&app_state).await?;
+    end_transaction(&session_id, TransactionControl::Commit).await?;
+    Ok("Transaction committed".to_string())
+}
+
+#[tauri::command]
+pub async fn rollback_transaction(
+    app_state: State<'_, AppState>,
+    session_id: Option<String>,
+) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
+    let session_id = resolve_session_id(&app_state).await?;
+    end_transaction(&session_id, TransactionControl::Rollback).await?;
+    Ok("Transaction rolled back".to_string())
+}
+
+async fn apply_savepoint(
+    app_state: &AppState,
+    action: SavepointAction,
+    name: &str,
+) -> Result<TransactionInfo, String> {
+    let session_id = resolve_session_id(app_state).await?;
+    let session = require_transaction(&session_id).await?;
+    let mut guard = session.lock().await;
+    guard.savepoint(action, name).await?;
+    Ok(guard.info())
+}
+
+#[tauri::command]
+pub async fn create_savepoint(
+    app_state: State<'_, AppState>,
+    name: String,
+    session_id: Option<String>,
+) -> Result<TransactionInfo, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
+    apply_savepoint(&app_state, SavepointAction::Create, &name).await
+}
+
+#[tauri::command]
+pub async fn rollback_to_savepoint(
+    app_state: State<'_, AppState>,
+    name: String,
+    session_id: Option<String>,
+) -> Result<TransactionInfo, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
+    apply_savepoint(&app_state, SavepointAction::RollbackTo, &name).await
+}
+
+#[tauri::command]
+pub async fn release_savepoint(
+    app_state: State<'_, AppState>,
+    name: String,
+    session_id: Option<String>,
+) -> Result<TransactionInfo, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
+    apply_savepoint(&app_state, SavepointAction::Release, &name).await
+}
+
+#[tauri::command]
+pub async fn get_transaction_status(
+    app_state: State<'_, AppState>,
+    session_id: Option<String>,
+) -> Result<Option<TransactionInfo>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
+    let Ok(session_id) = resolve_session_id(&app_state).await else {
+        return Ok(None);
+    };
+    match get_transaction(&session_id).await {
+        Some(session) => Ok(Some(session.lock().await.info())),
+        None => Ok(None),
+    }
+}
+
+#[tauri::command]
+pub async fn list_open_transactions() -> Result<Vec<TransactionInfo>, String> {
+    let sessions: Vec<Arc<Mutex<TransactionSession>>> = {
+        let guard = TRANSACTION_SESSIONS.lock().await;
+        guard.values().cloned().collect()
+    };
+
+    let mut infos = Vec::with_capacity(sessions.len());
+    for session in sessions {
+        infos.push(session.lock().await.info());
+    }
+    infos.sort_by_key(|info| info.started_at);
+    Ok(infos)
+}
+
+#[cfg(test)]
+mod tests;
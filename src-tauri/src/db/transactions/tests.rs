@@ -0,0 +1,70 @@
+use super::*;
+
+#[test]
+fn test_classify_transaction_control() {
+    assert_eq!(classify_transaction_control("begin"), Some(TransactionControl::Begin));
+    assert_eq!(classify_transaction_control(" START   TRANSACTION; "), Some(TransactionControl::Begin));
+    assert_eq!(classify_transaction_control("COMMIT WORK"), Some(TransactionControl::Commit));
+    assert_eq!(classify_transaction_control("rollback tran"), Some(TransactionControl::Rollback));
+    assert_eq!(classify_transaction_control("END"), None);
+    assert_eq!(classify_transaction_control("ROLLBACK TO SAVEPOINT sp1"), None);
+    assert_eq!(classify_transaction_control("SELECT 1"), None);
+}
+
+#[test]
+fn test_parse_transaction_script() {
+    assert_eq!(
+        parse_transaction_script("BEGIN; UPDATE t SET a = 1;"),
+        TransactionScript { begin: true, body: "UPDATE t SET a = 1;", end: None }
+    );
+    assert_eq!(
+        parse_transaction_script("BEGIN; UPDATE t SET a = 1; COMMIT;"),
+        TransactionScript { begin: true, body: "UPDATE t SET a = 1;", end: Some(TransactionControl::Commit) }
+    );
+    assert_eq!(
+        parse_transaction_script("rollback"),
+        TransactionScript { begin: false, body: "", end: Some(TransactionControl::Rollback) }
+    );
+    assert_eq!(
+        parse_transaction_script("SELECT ';COMMIT'"),
+        TransactionScript { begin: false, body: "SELECT ';COMMIT'", end: None }
+    );
+}
+
+#[test]
+fn test_is_read_only_statement() {
+    assert!(is_read_only_statement("SELECT * FROM users"));
+    assert!(is_read_only_statement("  show tables;"));
+    assert!(is_read_only_statement("WITH x AS (SELECT 1) SELECT * FROM x"));
+    assert!(!is_read_only_statement("WITH x AS (SELECT 1) DELETE FROM t"));
+    assert!(!is_read_only_statement("UPDATE users SET name = 'a'"));
+    assert!(!is_read_only_statement("SELECT 1; DELETE FROM t"));
+}
+
+#[test]
+fn test_transaction_statements_per_engine() {
+    assert_eq!(begin_statement(&DatabaseType::MySQL).unwrap(), "START TRANSACTION");
+    assert_eq!(begin_statement(&DatabaseType::MSSQL).unwrap(), "BEGIN TRANSACTION");
+    assert!(begin_statement(&DatabaseType::ClickHouse).is_err());
+
+    assert_eq!(end_statement(&DatabaseType::PostgreSQL, TransactionControl::Commit), "COMMIT");
+    assert_eq!(end_statement(&DatabaseType::MSSQL, TransactionControl::Rollback), "ROLLBACK TRANSACTION");
+
+    assert_eq!(
+        savepoint_statement(&DatabaseType::SQLite, SavepointAction::RollbackTo, "sp1").as_deref(),
+        Some("ROLLBACK TO SAVEPOINT sp1")
+    );
+    assert_eq!(
+        savepoint_statement(&DatabaseType::MSSQL, SavepointAction::Create, "sp1").as_deref(),
+        Some("SAVE TRANSACTION sp1")
+    );
+    assert!(savepoint_statement(&DatabaseType::MSSQL, SavepointAction::Release, "sp1").is_none());
+}
+
+#[test]
+fn test_validate_savepoint_name() {
+    assert!(validate_savepoint_name("before_update_1").is_ok());
+    assert!(validate_savepoint_name("1abc").is_err());
+    assert!(validate_savepoint_name("sp; DROP TABLE t").is_err());
+    assert!(validate_savepoint_name("").is_err());
+}
@@ -6,7 +6,8 @@ use crate::postgres;
 use crate::clickhouse;
 
 #[tauri::command]
-pub async fn get_users(app_state: State<'_, AppState>) -> Result<Vec<MySqlUser>, String> {
+pub async fn get_users(app_state: State<'_, AppState>, session_id: Option<String>) -> Result<Vec<MySqlUser>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -31,7 +32,7 @@ pub async fn get_users(app_state: State<'_, AppState>) -> Result<Vec<MySqlUser>,
             Ok(Vec::new())
         }
         DatabaseType::ClickHouse => {
-            clickhouse::get_users(app_state.inner()).await
+            clickhouse::get_users(&app_state).await
         }
         DatabaseType::SQLite => {
             Ok(Vec::new())
@@ -45,7 +46,9 @@ pub async fn get_user_privileges(
     app_state: State<'_, AppState>,
     user: String,
     host: String,
+    session_id: Option<String>,
 ) -> Result<UserPrivileges, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -83,7 +86,9 @@ pub async fn manage_privilege(
     table: String,
     user: String,
     host: String,
+    session_id: Option<String>,
 ) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let guard = app_state.mysql_pool.lock().await;
     let pool = guard.as_ref().ok_or("No MySQL connection established")?;
     mysql::manage_privilege(pool, &action, &privilege, &database, &table, &user, &host).await
@@ -95,7 +100,9 @@ pub async fn manage_user_status(
     user: String,
     host: String,
     lock: bool,
+    session_id: Option<String>,
 ) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let guard = app_state.mysql_pool.lock().await;
     let pool = guard.as_ref().ok_or("No MySQL connection established")?;
     mysql::manage_user_status(pool, &user, &host, lock).await
@@ -108,14 +115,17 @@ pub async fn manage_role(
     role_name: String,
     user: Option<String>,
     host: Option<String>,
+    session_id: Option<String>,
 ) -> Result<String, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let guard = app_state.mysql_pool.lock().await;
     let pool = guard.as_ref().ok_or("No MySQL connection established")?;
     mysql::manage_role(pool, &action, &role_name, user.as_deref(), host.as_deref()).await
 }
 
 #[tauri::command]
-pub async fn get_role_edges(app_state: State<'_, AppState>) -> Result<Vec<MySqlRoleEdge>, String> {
+pub async fn get_role_edges(app_state: State<'_, AppState>, session_id: Option<String>) -> Result<Vec<MySqlRoleEdge>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let guard = app_state.mysql_pool.lock().await;
     let pool = guard.as_ref().ok_or("No MySQL connection established")?;
     mysql::get_role_edges(pool).await
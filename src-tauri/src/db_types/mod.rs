@@ -4,6 +4,7 @@
 
 use serde::{Deserialize, Serialize};
 use sqlx::{MySql, Pool, Postgres, Sqlite};
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
@@ -42,6 +43,98 @@ pub struct MySqlVersion {
     pub auth_plugin: Option<String>,
 }
 
+// --- Connection Session ---
+// One live connection registered under its saved connection id. Every session
+// owns its own per-engine pool slots so several servers can stay open at once.
+pub struct ConnectionSession {
+    pub session_id: String,
+    pub config: ConnectionConfig,
+    pub db_type: DatabaseType,
+    pub tunnel_key: Option<String>,
+    pub opened_at: i64,
+    pub mysql_pool: Arc<Mutex<Option<Pool<MySql>>>>,
+    pub postgres_pool: Arc<Mutex<Option<Pool<Postgres>>>>,
+    pub mssql_pool: Arc<Mutex<Option<deadpool_tiberius::Pool>>>,
+    pub clickhouse_pool: Arc<Mutex<Option<clickhouse::Client>>>,
+    pub clickhouse_config: Arc<Mutex<Option<ConnectionConfig>>>,
+    pub sqlite_pool: Arc<Mutex<Option<Pool<Sqlite>>>>,
+    pub sqlite_db_path: Arc<Mutex<Option<String>>>,
+    pub mysql_version: Arc<Mutex<Option<MySqlVersion>>>,
+    pub last_monitor_tick: Arc<Mutex<i64>>,
+    pub last_monitor_status: Arc<Mutex<Option<ServerStatus>>>,
+}
+
+impl ConnectionSession {
+    pub fn new(session_id: String, config: ConnectionConfig, tunnel_key: Option<String>) -> Self {
+        Self {
+            session_id,
+            db_type: config.db_type.clone(),
+            config,
+            tunnel_key,
+            opened_at: chrono::Utc::now().timestamp(),
+            mysql_pool: Arc::new(Mutex::new(None)),
+            postgres_pool: Arc::new(Mutex::new(None)),
+            mssql_pool: Arc::new(Mutex::new(None)),
+            clickhouse_pool: Arc::new(Mutex::new(None)),
+            clickhouse_config: Arc::new(Mutex::new(None)),
+            sqlite_pool: Arc::new(Mutex::new(None)),
+            sqlite_db_path: Arc::new(Mutex::new(None)),
+            mysql_version: Arc::new(Mutex::new(None)),
+            last_monitor_tick: Arc::new(Mutex::new(0)),
+            last_monitor_status: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn info(&self, active_session_id: Option<&str>) -> SessionInfo {
+        SessionInfo {
+            session_id: self.session_id.clone(),
+            name: self.config.name.clone(),
+            db_type: self.db_type.clone(),
+            host: self.config.host.clone(),
+            port: self.config.port,
+            database: self.config.database.clone(),
+            color: self.config.color.clone(),
+            opened_at: self.opened_at,
+            is_active: active_session_id == Some(self.session_id.as_str()),
+        }
+    }
+
+    /// Closes every pool owned by this session.
+    pub async fn close(&self) {
+        if let Some(pool) = self.mysql_pool.lock().await.take() {
+            pool.close().await;
+        }
+        if let Some(pool) = self.postgres_pool.lock().await.take() {
+            pool.close().await;
+        }
+        if let Some(pool) = self.mssql_pool.lock().await.take() {
+            pool.close();
+        }
+        if let Some(pool) = self.sqlite_pool.lock().await.take() {
+            pool.close().await;
+        }
+        // ClickHouse HTTP client doesn't need explicit close
+        *self.clickhouse_pool.lock().await = None;
+        *self.clickhouse_config.lock().await = None;
+        *self.sqlite_db_path.lock().await = None;
+        *self.mysql_version.lock().await = None;
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionInfo {
+    pub session_id: String,
+    pub name: Option<String>,
+    pub db_type: DatabaseType,
+    pub host: String,
+    pub port: u16,
+    pub database: Option<String>,
+    pub color: Option<String>,
+    pub opened_at: i64,
+    pub is_active: bool,
+}
+
 // --- State Management ---
 pub struct AppState {
     pub mysql_pool: Arc<Mutex<Option<Pool<MySql>>>>,
@@ -70,8 +163,8 @@ pub struct AppState {
     pub task_last_retention_purge_epoch: Arc<Mutex<i64>>,
     pub local_db_pool: Arc<Mutex<Option<Pool<Sqlite>>>>,
     pub mysql_version: Arc<Mutex<Option<MySqlVersion>>>,
-    pub running_query_pid: Arc<Mutex<Option<i64>>>,
-    pub query_cancel_requested: Arc<Mutex<bool>>,
+    pub sessions: Arc<Mutex<HashMap<String, Arc<ConnectionSession>>>>,
+    pub active_session_id: Arc<Mutex<Option<String>>>,
 }
 
 impl Default for AppState {
@@ -102,8 +195,8 @@ impl Default for AppState {
             task_last_retention_purge_epoch: Arc::new(Mutex::new(0)),
             local_db_pool: Arc::new(Mutex::new(None)),
             mysql_version: Arc::new(Mutex::new(None)),
-            running_query_pid: Arc::new(Mutex::new(None)),
-            query_cancel_requested: Arc::new(Mutex::new(false)),
+            sessions: Arc::new(Mutex::new(HashMap::new())),
+            active_session_id: Arc::new(Mutex::new(None)),
         }
     }
 }
@@ -134,12 +227,84 @@ impl Clone for AppState {
             task_last_retention_purge_epoch: Arc::clone(&self.task_last_retention_purge_epoch),
             local_db_pool: Arc::clone(&self.local_db_pool),
             mysql_version: Arc::clone(&self.mysql_version),
-            running_query_pid: Arc::clone(&self.running_query_pid),
-            query_cancel_requested: Arc::clone(&self.query_cancel_requested),
+            sessions: Arc::clone(&self.sessions),
+            active_session_id: Arc::clone(&self.active_session_id),
         }
     }
 }
 
+impl AppState {
+    pub async fn get_session(&self, session_id: &str) -> Result<Arc<ConnectionSession>, String> {
+        let guard = self.sessions.lock().await;
+        guard
+            .get(session_id)
+            .cloned()
+            .ok_or_else(|| format!("No open connection session '{}'", session_id))
+    }
+
+    /// Returns a state view whose connection fields point at the given session's
+    /// pools. `None` keeps the currently active session, so callers that do not
+    /// pass a session id behave exactly as before.
+    pub async fn for_session(&self, session_id: Option<&str>) -> Result<AppState, String> {
+        let Some(session_id) = session_id.map(str::trim).filter(|id| !id.is_empty()) else {
+            return Ok(self.clone());
+        };
+
+        let session = self.get_session(session_id).await?;
+        let mut scoped = self.clone();
+        scoped.mysql_pool = Arc::clone(&session.mysql_pool);
+        scoped.postgres_pool = Arc::clone(&session.postgres_pool);
+        scoped.mssql_pool = Arc::clone(&session.mssql_pool);
+        scoped.clickhouse_pool = Arc::clone(&session.clickhouse_pool);
+        scoped.clickhouse_config = Arc::clone(&session.clickhouse_config);
+        scoped.sqlite_pool = Arc::clone(&session.sqlite_pool);
+        scoped.sqlite_db_path = Arc::clone(&session.sqlite_db_path);
+        scoped.mysql_version = Arc::clone(&session.mysql_version);
+        scoped.active_db_type = Arc::new(Mutex::new(session.db_type.clone()));
+        scoped.last_monitor_tick = Arc::clone(&session.last_monitor_tick);
+        scoped.last_monitor_status = Arc::clone(&session.last_monitor_status);
+        scoped.active_session_id = Arc::new(Mutex::new(Some(session.session_id.clone())));
+        Ok(scoped)
+    }
+
+    /// Mirrors a session's pools into the legacy single-connection fields so
+    /// commands called without a session id target it.
+    pub async fn activate_session(&self, session: &ConnectionSession) {
+        *self.mysql_pool.lock().await = session.mysql_pool.lock().await.clone();
+        *self.postgres_pool.lock().await = session.postgres_pool.lock().await.clone();
+        *self.mssql_pool.lock().await = session.mssql_pool.lock().await.clone();
+        *self.clickhouse_pool.lock().await = session.clickhouse_pool.lock().await.clone();
+        *self.clickhouse_config.lock().await = session.clickhouse_config.lock().await.clone();
+        *self.sqlite_pool.lock().await = session.sqlite_pool.lock().await.clone();
+        *self.sqlite_db_path.lock().await = session.sqlite_db_path.lock().await.clone();
+        *self.mysql_version.lock().await = session.mysql_version.lock().await.clone();
+        *self.active_db_type.lock().await = session.db_type.clone();
+        *self.active_session_id.lock().await = Some(session.session_id.clone());
+    }
+
+    /// Drops the legacy active-connection pointers without closing the pools;
+    /// the pools themselves belong to their sessions.
+    pub async fn clear_active_session(&self) {
+        *self.mysql_pool.lock().await = None;
+        *self.postgres_pool.lock().await = None;
+        *self.mssql_pool.lock().await = None;
+        *self.clickhouse_pool.lock().await = None;
+        *self.clickhouse_config.lock().await = None;
+        *self.sqlite_pool.lock().await = None;
+        *self.sqlite_db_path.lock().await = None;
+        *self.mysql_version.lock().await = None;
+        *self.active_db_type.lock().await = DatabaseType::Disconnected;
+        *self.active_session_id.lock().await = None;
+    }
+
+    pub async fn list_sessions(&self) -> Vec<Arc<ConnectionSession>> {
+        let guard = self.sessions.lock().await;
+        let mut sessions: Vec<Arc<ConnectionSession>> = guard.values().cloned().collect();
+        sessions.sort_by_key(|session| session.opened_at);
+        sessions
+    }
+}
+
 // --- Connection Configuration ---
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct ConnectionConfig {
@@ -191,6 +356,49 @@ pub struct QueryResult {
     pub warnings: Vec<String>,
 }
 
+// --- Streamed Query Result ---
+// Rows are pushed through a bounded channel so a slow consumer holds back the
+// server-side stream instead of buffering the whole result in memory.
+pub const RESULT_STREAM_BATCH_SIZE: usize = 500;
+
+#[derive(Debug, Clone)]
+pub enum ResultStreamEvent {
+    Columns(Vec<String>),
+    Rows(Vec<Vec<serde_json::Value>>),
+    Error(String),
+}
+
+// --- Bind Parameters ---
+// `name` is set for `:name` / `@name` placeholders and left empty for
+// positional `?` / `$1` ones. `type_hint` overrides inference from the JSON value.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryParameter {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub value: serde_json::Value,
+    #[serde(default, alias = "type")]
+    pub type_hint: Option<String>,
+}
+
+/// A typed bind value; `None` binds a NULL of that type.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BindValue {
+    Bool(Option<bool>),
+    Int(Option<i64>),
+    Float(Option<f64>),
+    Decimal(Option<String>),
+    Text(Option<String>),
+    Json(Option<serde_json::Value>),
+    Bytes(Option<Vec<u8>>),
+    Date(Option<chrono::NaiveDate>),
+    Time(Option<chrono::NaiveTime>),
+    DateTime(Option<chrono::NaiveDateTime>),
+    Timestamp(Option<chrono::DateTime<chrono::Utc>>),
+    Uuid(Option<String>),
+}
+
 // --- Column Schema ---
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 #[serde(rename_all = "snake_case")]
@@ -237,6 +445,9 @@ pub struct TableConstraint {
     pub name: String,
     pub constraint_type: String,
     pub column_name: String,
+    /// Expression of CHECK constraints, where the engine exposes it
+    #[serde(default)]
+    pub definition: Option<String>,
 }
 
 // --- Table Stats ---
@@ -258,6 +469,9 @@ pub struct TriggerInfo {
     pub event: String,
     pub timing: String,
     pub table_name: String,
+    /// Full CREATE TRIGGER statement, captured for schema snapshots
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub definition: Option<String>,
 }
 
 // --- Routine Info (Procedures/Functions) ---
@@ -265,6 +479,12 @@ pub struct TriggerInfo {
 pub struct RoutineInfo {
     pub name: String,
     pub definer: String,
+    /// PROCEDURE or FUNCTION
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub routine_type: Option<String>,
+    /// Full CREATE statement, captured for schema snapshots
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub definition: Option<String>,
 }
 
 // --- Event Info ---
@@ -763,3 +983,6 @@ pub struct ApplyRecommendationResult {
     pub message: String,
     pub updated_score: Option<i32>,
 }
+
+#[cfg(test)]
+mod tests;
@@ -41,3 +41,76 @@ fn test_task_status_conversion() {
     assert_eq!(TaskStatus::from_db("active").unwrap(), TaskStatus::Active);
     assert_eq!(TaskStatus::Active.as_str(), "active");
 }
+
+fn sample_config(id: &str, db_type: DatabaseType) -> ConnectionConfig {
+    ConnectionConfig {
+        id: Some(id.to_string()),
+        name: Some(format!("conn-{}", id)),
+        db_type,
+        host: "localhost".to_string(),
+        port: 3306,
+        username: "root".to_string(),
+        password: None,
+        database: None,
+        password_encrypted: false,
+        color: None,
+        ssl_mode: None,
+        schema: None,
+        use_ssh_tunnel: false,
+        ssh_host: None,
+        ssh_port: None,
+        ssh_username: None,
+        ssh_password: None,
+        ssh_key_path: None,
+    }
+}
+
+#[tokio::test]
+async fn test_for_session_scopes_connection_fields() {
+    let state = AppState::default();
+    let session = Arc::new(ConnectionSession::new(
+        "replica".to_string(),
+        sample_config("replica", DatabaseType::PostgreSQL),
+        None,
+    ));
+    state
+        .sessions
+        .lock()
+        .await
+        .insert("replica".to_string(), Arc::clone(&session));
+
+    let scoped = state.for_session(Some("replica")).await.unwrap();
+    assert_eq!(*scoped.active_db_type.lock().await, DatabaseType::PostgreSQL);
+    assert!(Arc::ptr_eq(&scoped.postgres_pool, &session.postgres_pool));
+    assert_eq!(
+        scoped.active_session_id.lock().await.as_deref(),
+        Some("replica")
+    );
+
+    // The global active connection is untouched by scoping
+    assert_eq!(*state.active_db_type.lock().await, DatabaseType::Disconnected);
+
+    let unscoped = state.for_session(None).await.unwrap();
+    assert!(Arc::ptr_eq(&unscoped.postgres_pool, &state.postgres_pool));
+
+    assert!(state.for_session(Some("missing")).await.is_err());
+}
+
+#[tokio::test]
+async fn test_activate_and_clear_session() {
+    let state = AppState::default();
+    let staging = ConnectionSession::new(
+        "staging".to_string(),
+        sample_config("staging", DatabaseType::MySQL),
+        None,
+    );
+
+    state.activate_session(&staging).await;
+    assert_eq!(*state.active_db_type.lock().await, DatabaseType::MySQL);
+    assert_eq!(state.active_session_id.lock().await.as_deref(), Some("staging"));
+    assert!(staging.info(Some("staging")).is_active);
+
+    state.clear_active_session().await;
+    assert_eq!(*state.active_db_type.lock().await, DatabaseType::Disconnected);
+    assert!(state.active_session_id.lock().await.is_none());
+}
@@ -11,7 +11,9 @@ pub async fn get_dependency_graph(
     database: Option<String>,
     table_name: Option<String>,
     hop_depth: Option<u8>,
+    session_id: Option<String>,
 ) -> Result<DependencyGraphData, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -124,7 +126,9 @@ pub async fn get_clickhouse_data_lineage(
     app_state: State<'_, AppState>,
     connection_id: String,
     database: String,
+    session_id: Option<String>,
 ) -> Result<DependencyGraphData, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let guard = app_state.clickhouse_config.lock().await;
     let config = guard.as_ref().ok_or("No ClickHouse connection established")?;
     
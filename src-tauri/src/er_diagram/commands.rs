@@ -13,7 +13,9 @@ pub async fn build_er_graph(
     connection_id: String,
     database: String,
     include_views: bool,
+    session_id: Option<String>,
 ) -> Result<ErDiagramGraph, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -7,7 +7,9 @@ pub async fn check_impact(
     app_state: State<'_, AppState>,
     database: String,
     table: String,
+    session_id: Option<String>,
 ) -> Result<ImpactGraph, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
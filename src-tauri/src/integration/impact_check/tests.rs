@@ -49,6 +49,7 @@ fn ambiguous_table_name_produces_ambiguity_warning() {
             row_count: None,
         }],
         modified_tables: vec![],
+        ..Default::default()
     };
 
     let warnings = check_schema_change_impact(&diff, &graph);
@@ -250,6 +250,10 @@ pub fn run() {
             let monitor_handle = app.handle().clone();
             crate::db::diagnostics::worker::start_monitoring_worker(monitor_handle);
 
+            // Roll back transactions left idle
+            let transaction_handle = app.handle().clone();
+            crate::db::start_transaction_reaper(transaction_handle);
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -267,6 +271,9 @@ pub fn run() {
             db::close_ssh_tunnel,
             db::establish_connection,
             db::disconnect,
+            db::disconnect_all,
+            db::list_connection_sessions,
+            db::set_active_session,
             db::get_active_db_type,
             db::save_connection,
             db::load_connections,
@@ -284,21 +291,47 @@ pub fn run() {
             db::cancel_mock_data_generation,
             db::backup_database,
             db::restore_database,
+            db::cancel_backup_job,
             db::compare_table_data,
             db::generate_data_sync_script,
             data_transfer::commands::preview_data_transfer_plan,
             data_transfer::commands::start_data_transfer,
+            data_transfer::commands::resume_data_transfer,
             data_transfer::commands::get_data_transfer_status,
             data_transfer::commands::list_data_transfer_runs,
             data_transfer::commands::cancel_data_transfer,
             data_transfer::commands::validate_data_transfer_mapping,
+            data_transfer::commands::save_data_transfer_mapping_profile,
+            data_transfer::commands::list_data_transfer_mapping_profiles,
+            data_transfer::commands::delete_data_transfer_mapping_profile,
             data_transfer::commands::generate_transfer_task_payload,
             // Query Execution
             db::execute_query,
             db::execute_query_profiled,
+            db::execute_parameterized_query,
             db::cancel_running_query,
-            db::get_running_query_pid,
-            db::set_running_query_pid,
+            db::list_running_executions,
+            db::cancel_execution,
+            // Result Cursors
+            db::open_result_cursor,
+            db::fetch_result_page,
+            db::close_result_cursor,
+            db::list_result_cursors,
+            // Result Edits
+            db::preview_result_edits,
+            db::apply_result_edits,
+            // Script Runner
+            db::split_script_statements,
+            db::execute_sql_script,
+            // Transactions
+            db::begin_transaction,
+            db::commit_transaction,
+            db::rollback_transaction,
+            db::create_savepoint,
+            db::rollback_to_savepoint,
+            db::release_savepoint,
+            db::get_transaction_status,
+            db::list_open_transactions,
             // ClickHouse specific
             clickhouse::get_clickhouse_table_info,
             clickhouse::get_clickhouse_partitions,
@@ -449,8 +482,17 @@ pub fn run() {
             schema_tracker::commands::detect_breaking_changes,
             schema_tracker::commands::generate_migration,
             schema_tracker::commands::generate_migration_plan,
+            schema_tracker::commands::apply_migration_plan,
+            schema_tracker::commands::get_migration_history,
+            schema_tracker::commands::export_migration_files,
+            schema_tracker::commands::get_migration_directory_status,
+            schema_tracker::commands::compare_schema_file,
+            schema_tracker::commands::get_online_schema_change_status,
+            schema_tracker::commands::control_online_schema_change,
             schema_tracker::commands::add_snapshot_tag,
             schema_tracker::commands::get_schema_snapshots,
+            schema_tracker::commands::get_schema_drift_events,
+            schema_tracker::commands::acknowledge_schema_drift_event,
             schema_tracker::commands::save_ai_impact_report,
             schema_tracker::commands::get_ai_impact_report,
             schema_tracker::commands::generate_story_command,
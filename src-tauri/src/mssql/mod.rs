@@ -4,11 +4,12 @@
 
 use crate::db_types::*;
 use deadpool_tiberius::{Manager, Pool};
-use tiberius::{AuthMethod, Config, Client, QueryItem};
+use tiberius::{AuthMethod, Config, Client, Query, QueryItem, QueryStream};
 use tokio::net::TcpStream;
 use tokio_util::compat::TokioAsyncWriteCompatExt;
 use futures::TryStreamExt;
 use serde_json::Value;
+use std::collections::HashMap;
 
 // --- Connection ---
 
@@ -75,15 +76,100 @@ pub async fn execute_query(
     execute_query_with_timeout(pool, query, None).await
 }
 
+pub(crate) fn row_to_json_values(row: &tiberius::Row) -> Vec<Value> {
+    let mut row_data = Vec::new();
+    for i in 0..row.len() {
+        let val: Value = if let Ok(Some(v)) = row.try_get::<i64, _>(i) {
+            serde_json::json!(v)
+        } else if let Ok(Some(v)) = row.try_get::<i32, _>(i) {
+            serde_json::json!(v)
+        } else if let Ok(Some(v)) = row.try_get::<i16, _>(i) {
+            serde_json::json!(v)
+        } else if let Ok(Some(v)) = row.try_get::<f64, _>(i) {
+            serde_json::json!(v)
+        } else if let Ok(Some(v)) = row.try_get::<f32, _>(i) {
+            serde_json::json!(v)
+        } else if let Ok(Some(v)) = row.try_get::<bool, _>(i) {
+            serde_json::json!(v)
+        } else if let Ok(Some(v)) = row.try_get::<&str, _>(i) {
+            serde_json::json!(v)
+        } else if let Ok(Some(v)) = row.try_get::<&[u8], _>(i) {
+            serde_json::json!(format!("0x{}", hex::encode(v)))
+        } else {
+            Value::Null
+        };
+        row_data.push(val);
+    }
+    row_data
+}
+
 pub async fn execute_query_with_timeout(
     pool: &Pool,
     query: String,
     _query_timeout_seconds: Option<u64>,
 ) -> Result<Vec<QueryResult>, String> {
     let mut conn = pool.get().await.map_err(|e| e.to_string())?;
-    
+    execute_query_on_client(&mut conn, &query).await
+}
+
+/// Runs `query` on an already-held client, e.g. one pinned to a transaction.
+pub async fn execute_query_on_client(
+    client: &mut deadpool_tiberius::Client,
+    query: &str,
+) -> Result<Vec<QueryResult>, String> {
+    let stream = client.query(query, &[]).await.map_err(|e| e.to_string())?;
+    collect_query_results(stream).await
+}
+
+fn bind_parameters<'a>(query: &mut Query<'a>, params: &'a [BindValue]) -> Result<(), String> {
+    for param in params {
+        match param {
+            BindValue::Bool(v) => query.bind(*v),
+            BindValue::Int(v) => query.bind(*v),
+            BindValue::Float(v) => query.bind(*v),
+            BindValue::Decimal(v) | BindValue::Text(v) => query.bind(v.as_deref()),
+            BindValue::Json(v) => query.bind(v.as_ref().map(|v| v.to_string())),
+            BindValue::Bytes(v) => query.bind(v.as_deref()),
+            BindValue::Date(v) => query.bind(*v),
+            BindValue::Time(v) => query.bind(*v),
+            BindValue::DateTime(v) => query.bind(*v),
+            BindValue::Timestamp(v) => query.bind(*v),
+            BindValue::Uuid(v) => {
+                let uuid = v
+                    .as_deref()
+                    .map(tiberius::Uuid::parse_str)
+                    .transpose()
+                    .map_err(|e| format!("Invalid uuid parameter: {}", e))?;
+                query.bind(uuid)
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Runs one statement with `@P1`.. placeholders bound to `params`.
+pub async fn execute_parameterized_query(
+    pool: &Pool,
+    query: &str,
+    params: &[BindValue],
+) -> Result<Vec<QueryResult>, String> {
+    let mut conn = pool.get().await.map_err(|e| e.to_string())?;
+    execute_parameterized_query_on_client(&mut conn, query, params).await
+}
+
+pub async fn execute_parameterized_query_on_client(
+    client: &mut deadpool_tiberius::Client,
+    query: &str,
+    params: &[BindValue],
+) -> Result<Vec<QueryResult>, String> {
+    let mut statement = Query::new(query);
+    bind_parameters(&mut statement, params)?;
+    let stream = statement.query(client).await.map_err(|e| e.to_string())?;
+    collect_query_results(stream).await
+}
+
+async fn collect_query_results(mut stream: QueryStream<'_>) -> Result<Vec<QueryResult>, String> {
     let mut results = Vec::new();
-    let mut stream = conn.query(query, &[]).await.map_err(|e| e.to_string())?;
 
     let mut current_rows = Vec::new();
     let mut current_columns = Vec::new();
@@ -95,30 +181,7 @@ pub async fn execute_query_with_timeout(
                     current_columns = row.columns().iter().map(|c| c.name().to_string()).collect();
                 }
 
-                let mut row_data = Vec::new();
-                for i in 0..row.len() {
-                    let val: Value = if let Ok(Some(v)) = row.try_get::<i64, _>(i) {
-                        serde_json::json!(v)
-                    } else if let Ok(Some(v)) = row.try_get::<i32, _>(i) {
-                        serde_json::json!(v)
-                    } else if let Ok(Some(v)) = row.try_get::<i16, _>(i) {
-                        serde_json::json!(v)
-                    } else if let Ok(Some(v)) = row.try_get::<f64, _>(i) {
-                        serde_json::json!(v)
-                    } else if let Ok(Some(v)) = row.try_get::<f32, _>(i) {
-                        serde_json::json!(v)
-                    } else if let Ok(Some(v)) = row.try_get::<bool, _>(i) {
-                        serde_json::json!(v)
-                    } else if let Ok(Some(v)) = row.try_get::<&str, _>(i) {
-                        serde_json::json!(v)
-                    } else if let Ok(Some(v)) = row.try_get::<&[u8], _>(i) {
-                        serde_json::json!(format!("0x{}", hex::encode(v)))
-                    } else {
-                        Value::Null
-                    };
-                    row_data.push(val);
-                }
-                current_rows.push(row_data);
+                current_rows.push(row_to_json_values(&row));
             }
             QueryItem::Metadata(meta) => {
                 if !current_rows.is_empty() || !current_columns.is_empty() {
@@ -154,6 +217,75 @@ pub async fn execute_query_with_timeout(
     Ok(results)
 }
 
+/// Streams the first result set of `query` into `tx` in batches. Returns early,
+/// discarding the pooled connection, once the receiving cursor is closed.
+pub async fn stream_query(
+    pool: &Pool,
+    query: &str,
+    tx: &tokio::sync::mpsc::Sender<ResultStreamEvent>,
+) -> Result<(), String> {
+    let mut conn = pool.get().await.map_err(|e| e.to_string())?;
+
+    let mut cancelled = false;
+    {
+        let mut stream = conn.query(query, &[]).await.map_err(|e| e.to_string())?;
+        let mut columns_sent = false;
+        let mut batch = Vec::with_capacity(RESULT_STREAM_BATCH_SIZE);
+
+        loop {
+            let next = tokio::select! {
+                item = stream.try_next() => item.map_err(|e| e.to_string())?,
+                _ = tx.closed() => {
+                    cancelled = true;
+                    break;
+                }
+            };
+            let Some(item) = next else {
+                break;
+            };
+
+            match item {
+                QueryItem::Metadata(meta) => {
+                    // Only the first result set is streamed
+                    if columns_sent {
+                        cancelled = true;
+                        break;
+                    }
+                    let columns = meta.columns().iter().map(|c| c.name().to_string()).collect();
+                    if tx.send(ResultStreamEvent::Columns(columns)).await.is_err() {
+                        cancelled = true;
+                        break;
+                    }
+                    columns_sent = true;
+                }
+                QueryItem::Row(row) => {
+                    batch.push(row_to_json_values(&row));
+                    if batch.len() >= RESULT_STREAM_BATCH_SIZE
+                        && tx
+                            .send(ResultStreamEvent::Rows(std::mem::take(&mut batch)))
+                            .await
+                            .is_err()
+                    {
+                        cancelled = true;
+                        break;
+                    }
+                }
+            }
+        }
+
+        if !batch.is_empty() && !tx.is_closed() {
+            let _ = tx.send(ResultStreamEvent::Rows(batch)).await;
+        }
+    }
+
+    if cancelled {
+        // Detach from the pool so the unread remainder is never drained on recycle
+        let _ = deadpool_tiberius::deadpool::managed::Object::take(conn);
+    }
+
+    Ok(())
+}
+
 // --- Metadata Helpers ---
 
 async fn query_metadata(pool: &Pool, query: &str) -> Result<Vec<String>, String> {
@@ -354,6 +486,7 @@ pub async fn get_table_ddl(pool: &Pool, database: &str, schema: &str, table: &st
             return Err(format!("Table '{}.{}' not found", s_name, t_name));
         }
 
+        let generated = get_generated_columns(pool, &db_prefix, actual_schema, t_name).await?;
         let mut ddl = format!("CREATE TABLE [{}].[{}] (\n", s_name, t_name);
         let mut column_defs = Vec::new();
 
@@ -364,6 +497,11 @@ pub async fn get_table_ddl(pool: &Pool, database: &str, schema: &str, table: &st
             let is_nullable = row[3].as_str().unwrap_or("YES") == "YES";
             let default_val = row[4].as_str();
 
+            if let Some(GeneratedColumn::Computed(definition)) = generated.get(name) {
+                column_defs.push(format!("    [{}] AS {}", name, definition));
+                continue;
+            }
+
             let mut def = format!("    [{}] {}", name, data_type);
             
             if let Some(len) = max_len {
@@ -374,6 +512,10 @@ pub async fn get_table_ddl(pool: &Pool, database: &str, schema: &str, table: &st
                 }
             }
 
+            if let Some(GeneratedColumn::Identity { seed, increment }) = generated.get(name) {
+                def.push_str(&format!(" IDENTITY({}, {})", seed, increment));
+            }
+
             if !is_nullable {
                 def.push_str(" NOT NULL");
             }
@@ -394,6 +536,72 @@ pub async fn get_table_ddl(pool: &Pool, database: &str, schema: &str, table: &st
     }
 }
 
+/// Server-generated column values that DDL and data dumps must treat specially.
+enum GeneratedColumn {
+    Identity { seed: i64, increment: i64 },
+    Computed(String),
+}
+
+async fn get_generated_columns(
+    pool: &Pool,
+    db_prefix: &str,
+    schema: &str,
+    table: &str,
+) -> Result<HashMap<String, GeneratedColumn>, String> {
+    let query = format!(
+        "SELECT c.name, c.is_identity, CAST(ic.seed_value AS bigint), CAST(ic.increment_value AS bigint), cc.definition \
+         FROM {0}sys.columns c \
+         LEFT JOIN {0}sys.identity_columns ic ON ic.object_id = c.object_id AND ic.column_id = c.column_id \
+         LEFT JOIN {0}sys.computed_columns cc ON cc.object_id = c.object_id AND cc.column_id = c.column_id \
+         WHERE c.object_id = OBJECT_ID('{0}[{1}].[{2}]') AND (c.is_identity = 1 OR c.is_computed = 1)",
+        db_prefix, schema, table
+    );
+
+    let res = execute_query(pool, query).await?;
+    let mut columns = HashMap::new();
+    for row in res.first().map(|r| r.rows.as_slice()).unwrap_or(&[]) {
+        let name = row[0].as_str().unwrap_or("").to_string();
+        let column = if row[1].as_bool().unwrap_or(false) {
+            GeneratedColumn::Identity {
+                seed: row[2].as_i64().unwrap_or(1),
+                increment: row[3].as_i64().unwrap_or(1),
+            }
+        } else {
+            GeneratedColumn::Computed(row[4].as_str().unwrap_or("").to_string())
+        };
+        columns.insert(name, column);
+    }
+    Ok(columns)
+}
+
+/// Columns a data dump can insert explicit values into (computed and rowversion
+/// columns are filled by the server), and whether the table has an identity column.
+pub async fn get_insertable_columns(pool: &Pool, database: &str, schema: &str, table: &str) -> Result<(Vec<String>, bool), String> {
+    let db_prefix = if !database.is_empty() && database != "default" {
+        format!("[{}].", database)
+    } else {
+        "".to_string()
+    };
+    let (s_name, t_name) = split_table_name(schema, table);
+
+    let query = format!(
+        "SELECT c.name, c.is_identity FROM {0}sys.columns c \
+         INNER JOIN {0}sys.types ty ON c.user_type_id = ty.user_type_id \
+         WHERE c.object_id = OBJECT_ID('{0}[{1}].[{2}]') AND c.is_computed = 0 AND ty.name <> 'timestamp' \
+         ORDER BY c.column_id",
+        db_prefix, s_name, t_name
+    );
+
+    let res = execute_query(pool, query).await?;
+    let rows = res.first().map(|r| r.rows.as_slice()).unwrap_or(&[]);
+    let columns = rows
+        .iter()
+        .map(|row| row[0].as_str().unwrap_or("").to_string())
+        .collect();
+    let has_identity = rows.iter().any(|row| row[1].as_bool().unwrap_or(false));
+    Ok((columns, has_identity))
+}
+
 pub async fn get_table_indexes(pool: &Pool, database: &str, schema: &str, table: &str) -> Result<Vec<TableIndex>, String> {
     let db_prefix = if !database.is_empty() && database != "default" {
         format!("[{}].", database)
@@ -525,6 +733,7 @@ pub async fn get_table_constraints(pool: &Pool, database: &str, schema: &str, ta
             name: row[0].as_str().unwrap_or("").to_string(),
             constraint_type: row[1].as_str().unwrap_or("").to_string(),
             column_name: "".to_string(),
+            definition: None,
         }).collect())
     } else {
         Ok(vec![])
@@ -616,6 +825,16 @@ pub async fn get_session_id(pool: &Pool) -> Result<Option<i64>, String> {
     Ok(None)
 }
 
+/// `@@SPID` of the given client, as accepted by `KILL`.
+pub async fn current_session_id(client: &mut deadpool_tiberius::Client) -> Result<i64, String> {
+    let res = execute_query_on_client(client, "SELECT @@SPID AS spid").await?;
+    res.first()
+        .and_then(|r| r.rows.first())
+        .and_then(|row| row.first())
+        .and_then(|v| v.as_i64())
+        .ok_or_else(|| "Failed to get session ID".to_string())
+}
+
 pub async fn kill_process(pool: &Pool, process_id: i64) -> Result<String, String> {
     execute_query_with_timeout(pool, format!("KILL {}", process_id), Some(5)).await?;
     Ok(format!("Process {} killed", process_id))
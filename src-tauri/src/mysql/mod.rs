@@ -5,9 +5,10 @@
 use crate::db_types::*;
 use futures::StreamExt;
 use serde_json::Value;
-use sqlx::mysql::MySqlConnectOptions;
+use sqlx::mysql::{MySqlConnectOptions, MySqlRow};
 use sqlx::ConnectOptions;
-use sqlx::{Column, Executor, MySql, MySqlConnection, Pool, Row};
+use sqlx::query::Query;
+use sqlx::{Column, Database, Execute, Executor, MySql, MySqlConnection, Pool, Row};
 use std::collections::{HashMap, HashSet};
 use tokio::time::{timeout, Duration};
 use chrono::{DateTime, Utc};
@@ -60,6 +61,15 @@ pub async fn test_connection(config: &ConnectionConfig) -> Result<String, String
 }
 
 pub async fn create_pool(config: &ConnectionConfig) -> Result<Pool<MySql>, String> {
+    create_pool_with_session(config, &[]).await
+}
+
+/// Like `create_pool`, but every new connection runs `SET SESSION name = value`
+/// for each of `session_settings` before it is handed out.
+pub async fn create_pool_with_session(
+    config: &ConnectionConfig,
+    session_settings: &[(String, String)],
+) -> Result<Pool<MySql>, String> {
     let mut options = MySqlConnectOptions::new()
         .host(&config.host)
         .port(config.port)
@@ -78,6 +88,11 @@ pub async fn create_pool(config: &ConnectionConfig) -> Result<Pool<MySql>, Strin
     // Use utf8mb4 as default if possible, otherwise it falls back to sqlx default
     options = options.charset("utf8mb4");
 
+    let session_statements = session_settings
+        .iter()
+        .map(|(name, value)| format!("SET SESSION {} = {}", name, value))
+        .collect::<Vec<_>>();
+
     // Try connection with default configuration
     let pool_result = sqlx::mysql::MySqlPoolOptions::new()
         .max_connections(10)
@@ -85,6 +100,15 @@ pub async fn create_pool(config: &ConnectionConfig) -> Result<Pool<MySql>, Strin
         .acquire_timeout(std::time::Duration::from_secs(10))
         .idle_timeout(std::time::Duration::from_secs(300))
         .max_lifetime(std::time::Duration::from_secs(1800))
+        .after_connect(move |conn, _meta| {
+            let statements = session_statements.clone();
+            Box::pin(async move {
+                for statement in statements {
+                    conn.execute(statement.as_str()).await?;
+                }
+                Ok(())
+            })
+        })
         .connect_with(options.clone())
         .await;
 
@@ -348,6 +372,69 @@ fn normalize_query_timeout_seconds(query_timeout_seconds: Option<u64>) -> Option
     }
 }
 
+pub(crate) fn row_to_json_values(row: &MySqlRow, column_count: usize) -> Vec<Value> {
+    let mut row_data = Vec::new();
+    for i in 0..column_count {
+        let val: serde_json::Value = row
+            .try_get_unchecked::<i64, _>(i)
+            .map(|v| serde_json::json!(v))
+            .or_else(|_| {
+                row.try_get_unchecked::<i32, _>(i)
+                    .map(|v| serde_json::json!(v))
+            })
+            .or_else(|_| {
+                row.try_get_unchecked::<i16, _>(i)
+                    .map(|v| serde_json::json!(v))
+            })
+            .or_else(|_| {
+                row.try_get_unchecked::<i8, _>(i)
+                    .map(|v| serde_json::json!(v))
+            })
+            .or_else(|_| {
+                row.try_get_unchecked::<u64, _>(i)
+                    .map(|v| serde_json::json!(v))
+            })
+            .or_else(|_| {
+                row.try_get_unchecked::<u32, _>(i)
+                    .map(|v| serde_json::json!(v))
+            })
+            .or_else(|_| {
+                row.try_get_unchecked::<u16, _>(i)
+                    .map(|v| serde_json::json!(v))
+            })
+            .or_else(|_| {
+                row.try_get_unchecked::<u8, _>(i)
+                    .map(|v| serde_json::json!(v))
+            })
+            .or_else(|_| {
+                row.try_get_unchecked::<f64, _>(i)
+                    .map(|v| serde_json::json!(v))
+            })
+            .or_else(|_| {
+                row.try_get_unchecked::<f32, _>(i)
+                    .map(|v| serde_json::json!(v))
+            })
+            .or_else(|_| {
+                row.try_get_unchecked::<bool, _>(i)
+                    .map(|v| serde_json::json!(v))
+            })
+            .or_else(|_| {
+                row.try_get_unchecked::<String, _>(i)
+                    .map(|v| serde_json::json!(v))
+            })
+            .or_else(|_| {
+                row.try_get_unchecked::<Vec<u8>, _>(i).map(|bytes| {
+                    serde_json::json!(
+                        String::from_utf8_lossy(&bytes).to_string()
+                    )
+                })
+            })
+            .unwrap_or(serde_json::Value::Null);
+        row_data.push(val);
+    }
+    row_data
+}
+
 async fn execute_query_with_executor<'a, E>(
     executor: E,
     query: &'a str,
@@ -367,14 +454,39 @@ where
         }]);
     }
 
+    execute_statement_with_executor(executor, sqlx::raw_sql(query), query_timeout_seconds).await
+}
+
+async fn execute_statement_with_executor<'a, E, S>(
+    executor: E,
+    statement: S,
+    query_timeout_seconds: Option<u64>,
+) -> Result<Vec<QueryResult>, String>
+where
+    E: Executor<'a, Database = MySql>,
+    S: Execute<'a, MySql> + 'a,
+{
+    execute_statement_counting(executor, statement, query_timeout_seconds)
+        .await
+        .map(|(results, _)| results)
+}
+
+/// Like `execute_statement_with_executor`, also returning the total rows
+/// affected across the statement's results.
+async fn execute_statement_counting<'a, E, S>(
+    executor: E,
+    statement: S,
+    query_timeout_seconds: Option<u64>,
+) -> Result<(Vec<QueryResult>, u64), String>
+where
+    E: Executor<'a, Database = MySql>,
+    S: Execute<'a, MySql> + 'a,
+{
     let mut results = Vec::new();
+    let mut rows_affected = 0u64;
 
     let stream_future = async {
-        // Apply MySQL-specific normalization if applicable
-        // Note: For now we don't have the version here, we need to pass it or fetch it.
-        // Let's modify the signature to accept Option<&MySqlVersion>
-        
-        let mut stream = sqlx::raw_sql(query).fetch_many(executor);
+        let mut stream = executor.fetch_many(statement);
 
         let mut current_rows = Vec::new();
         let mut current_columns = Vec::new();
@@ -384,7 +496,8 @@ where
                 Ok(either) => {
                     use sqlx::Either;
                     match either {
-                        Either::Left(_done) => {
+                        Either::Left(done) => {
+                            rows_affected += done.rows_affected();
                             if !current_rows.is_empty() || !current_columns.is_empty() {
                                 results.push(QueryResult {
                                     columns: current_columns.clone(),
@@ -403,66 +516,7 @@ where
                                     row.columns().iter().map(|c| c.name().to_string()).collect();
                             }
 
-                            let mut row_data = Vec::new();
-                            for (i, _) in current_columns.iter().enumerate() {
-                                let val: serde_json::Value = row
-                                    .try_get_unchecked::<i64, _>(i)
-                                    .map(|v| serde_json::json!(v))
-                                    .or_else(|_| {
-                                        row.try_get_unchecked::<i32, _>(i)
-                                            .map(|v| serde_json::json!(v))
-                                    })
-                                    .or_else(|_| {
-                                        row.try_get_unchecked::<i16, _>(i)
-                                            .map(|v| serde_json::json!(v))
-                                    })
-                                    .or_else(|_| {
-                                        row.try_get_unchecked::<i8, _>(i)
-                                            .map(|v| serde_json::json!(v))
-                                    })
-                                    .or_else(|_| {
-                                        row.try_get_unchecked::<u64, _>(i)
-                                            .map(|v| serde_json::json!(v))
-                                    })
-                                    .or_else(|_| {
-                                        row.try_get_unchecked::<u32, _>(i)
-                                            .map(|v| serde_json::json!(v))
-                                    })
-                                    .or_else(|_| {
-                                        row.try_get_unchecked::<u16, _>(i)
-                                            .map(|v| serde_json::json!(v))
-                                    })
-                                    .or_else(|_| {
-                                        row.try_get_unchecked::<u8, _>(i)
-                                            .map(|v| serde_json::json!(v))
-                                    })
-                                    .or_else(|_| {
-                                        row.try_get_unchecked::<f64, _>(i)
-                                            .map(|v| serde_json::json!(v))
-                                    })
-                                    .or_else(|_| {
-                                        row.try_get_unchecked::<f32, _>(i)
-                                            .map(|v| serde_json::json!(v))
-                                    })
-                                    .or_else(|_| {
-                                        row.try_get_unchecked::<bool, _>(i)
-                                            .map(|v| serde_json::json!(v))
-                                    })
-                                    .or_else(|_| {
-                                        row.try_get_unchecked::<String, _>(i)
-                                            .map(|v| serde_json::json!(v))
-                                    })
-                                    .or_else(|_| {
-                                        row.try_get_unchecked::<Vec<u8>, _>(i).map(|bytes| {
-                                            serde_json::json!(
-                                                String::from_utf8_lossy(&bytes).to_string()
-                                            )
-                                        })
-                                    })
-                                    .unwrap_or(serde_json::Value::Null);
-                                row_data.push(val);
-                            }
-                            current_rows.push(row_data);
+                            current_rows.push(row_to_json_values(&row, current_columns.len()));
                         }
                     }
                 }
@@ -492,16 +546,16 @@ where
     }
 
     if results.is_empty() {
-        return Ok(vec![QueryResult {
+        results.push(QueryResult {
             columns: vec![],
             rows: vec![],
             query_id: None,
             statistics: None,
             warnings: vec![],
-        }]);
+        });
     }
 
-    Ok(results)
+    Ok((results, rows_affected))
 }
 
 async fn fetch_session_status(conn: &mut MySqlConnection) -> Result<HashMap<String, i64>, String> {
@@ -565,12 +619,21 @@ pub async fn execute_query_with_status_with_timeout(
         .await
         .map_err(|e| format!("Failed to acquire connection: {}", e))?;
 
-    let before_status = fetch_session_status(conn.as_mut()).await.ok();
-    let results =
-        execute_query_with_executor(conn.as_mut(), &query, query_timeout_seconds).await?;
+    execute_query_with_status_on_connection(conn.as_mut(), &query, query_timeout_seconds).await
+}
+
+/// Runs `query` on an already-held connection and diffs its session status
+/// around the execution.
+pub async fn execute_query_with_status_on_connection(
+    conn: &mut MySqlConnection,
+    query: &str,
+    query_timeout_seconds: Option<u64>,
+) -> Result<(Vec<QueryResult>, Option<HashMap<String, i64>>), String> {
+    let before_status = fetch_session_status(&mut *conn).await.ok();
+    let results = execute_query_with_executor(&mut *conn, query, query_timeout_seconds).await?;
 
     let status_diff = if let Some(before) = before_status {
-        match fetch_session_status(conn.as_mut()).await {
+        match fetch_session_status(&mut *conn).await {
             Ok(after) => Some(compute_status_diff(&before, &after)),
             Err(_) => None,
         }
@@ -593,6 +656,137 @@ pub async fn execute_query_with_timeout(
     execute_query_with_executor(pool, &query, query_timeout_seconds).await
 }
 
+/// Runs `query` on an already-held connection, e.g. one pinned to a transaction.
+pub async fn execute_query_on_connection(
+    conn: &mut MySqlConnection,
+    query: &str,
+    query_timeout_seconds: Option<u64>,
+) -> Result<Vec<QueryResult>, String> {
+    execute_query_with_executor(conn, query, query_timeout_seconds).await
+}
+
+/// Runs one script statement on an already-held connection, returning its
+/// results and the rows it affected.
+pub async fn execute_script_statement(
+    conn: &mut MySqlConnection,
+    query: &str,
+    query_timeout_seconds: Option<u64>,
+) -> Result<(Vec<QueryResult>, u64), String> {
+    execute_statement_counting(conn, sqlx::raw_sql(query), query_timeout_seconds).await
+}
+
+fn bind_parameters<'q>(
+    mut query: Query<'q, MySql, <MySql as Database>::Arguments<'q>>,
+    params: &'q [BindValue],
+) -> Query<'q, MySql, <MySql as Database>::Arguments<'q>> {
+    for param in params {
+        query = match param {
+            BindValue::Bool(v) => query.bind(*v),
+            BindValue::Int(v) => query.bind(*v),
+            BindValue::Float(v) => query.bind(*v),
+            BindValue::Decimal(v) | BindValue::Text(v) | BindValue::Uuid(v) => {
+                query.bind(v.as_deref())
+            }
+            BindValue::Json(v) => query.bind(v.as_ref().map(sqlx::types::Json)),
+            BindValue::Bytes(v) => query.bind(v.as_deref()),
+            BindValue::Date(v) => query.bind(*v),
+            BindValue::Time(v) => query.bind(*v),
+            BindValue::DateTime(v) => query.bind(*v),
+            BindValue::Timestamp(v) => query.bind(*v),
+        };
+    }
+    query
+}
+
+/// Runs one prepared statement with `?` placeholders bound to `params`, on either
+/// a pool or a pinned connection.
+pub async fn execute_parameterized_query<'a, E>(
+    executor: E,
+    query: &'a str,
+    params: &'a [BindValue],
+    query_timeout_seconds: Option<u64>,
+) -> Result<Vec<QueryResult>, String>
+where
+    E: Executor<'a, Database = MySql>,
+{
+    let statement = bind_parameters(sqlx::query(query), params);
+    execute_statement_with_executor(executor, statement, query_timeout_seconds).await
+}
+
+/// Streams the first result set of `query` into `tx` in batches. Returns early,
+/// abandoning the connection, once the receiving cursor is closed.
+pub async fn stream_query(
+    pool: &Pool<MySql>,
+    query: &str,
+    tx: &tokio::sync::mpsc::Sender<ResultStreamEvent>,
+) -> Result<(), String> {
+    let mut conn = pool
+        .acquire()
+        .await
+        .map_err(|e| format!("Failed to acquire connection: {}", e))?;
+
+    let mut cancelled = false;
+    {
+        let mut stream = sqlx::raw_sql(query).fetch_many(conn.as_mut());
+        let mut columns_sent = false;
+        let mut batch = Vec::with_capacity(RESULT_STREAM_BATCH_SIZE);
+
+        loop {
+            let next = tokio::select! {
+                item = stream.next() => item,
+                _ = tx.closed() => {
+                    cancelled = true;
+                    break;
+                }
+            };
+            let Some(item) = next else {
+                break;
+            };
+
+            match item.map_err(|e| format!("Query error: {}", e))? {
+                sqlx::Either::Left(_done) => {
+                    // Only the first result set is streamed
+                    if columns_sent {
+                        break;
+                    }
+                }
+                sqlx::Either::Right(row) => {
+                    if !columns_sent {
+                        let columns = row.columns().iter().map(|c| c.name().to_string()).collect();
+                        if tx.send(ResultStreamEvent::Columns(columns)).await.is_err() {
+                            cancelled = true;
+                            break;
+                        }
+                        columns_sent = true;
+                    }
+
+                    batch.push(row_to_json_values(&row, row.columns().len()));
+                    if batch.len() >= RESULT_STREAM_BATCH_SIZE
+                        && tx
+                            .send(ResultStreamEvent::Rows(std::mem::take(&mut batch)))
+                            .await
+                            .is_err()
+                    {
+                        cancelled = true;
+                        break;
+                    }
+                }
+            }
+        }
+
+        if !cancelled && !batch.is_empty() {
+            let _ = tx.send(ResultStreamEvent::Rows(batch)).await;
+        }
+    }
+
+    if cancelled {
+        // Drop the connection instead of draining the rest of the result set
+        let _ = conn.detach();
+    }
+
+    Ok(())
+}
+
 // --- Database/Table Operations ---
 
 pub async fn get_databases(pool: &Pool<MySql>) -> Result<Vec<String>, String> {
@@ -862,6 +1056,7 @@ pub async fn get_table_constraints(
             name: row.try_get("CONSTRAINT_NAME").unwrap_or_default(),
             constraint_type: row.try_get("CONSTRAINT_TYPE").unwrap_or_default(),
             column_name: row.try_get("COLUMN_NAME").unwrap_or_default(),
+            definition: None,
         });
     }
 
@@ -996,6 +1191,7 @@ pub async fn get_triggers(pool: &Pool<MySql>, database: &str) -> Result<Vec<Trig
             event: row.try_get("Event").unwrap_or_default(),
             timing: row.try_get("Timing").unwrap_or_default(),
             table_name: row.try_get("Table").unwrap_or_default(),
+            definition: None,
         });
     }
 
@@ -1024,6 +1220,7 @@ pub async fn get_table_triggers(
             event: row.try_get("Event").unwrap_or_default(),
             timing: row.try_get("Timing").unwrap_or_default(),
             table_name: row.try_get("Table").unwrap_or_default(),
+            definition: None,
         });
     }
 
@@ -1048,6 +1245,8 @@ pub async fn get_procedures(
         procedures.push(RoutineInfo {
             name: row.try_get("Name").unwrap_or_default(),
             definer: row.try_get("Definer").unwrap_or_default(),
+            routine_type: Some("PROCEDURE".to_string()),
+            definition: None,
         });
     }
 
@@ -1067,6 +1266,8 @@ pub async fn get_functions(pool: &Pool<MySql>, database: &str) -> Result<Vec<Rou
         functions.push(RoutineInfo {
             name: row.try_get("Name").unwrap_or_default(),
             definer: row.try_get("Definer").unwrap_or_default(),
+            routine_type: Some("FUNCTION".to_string()),
+            definition: None,
         });
     }
 
@@ -1513,6 +1714,27 @@ pub async fn get_connection_id(pool: &Pool<MySql>) -> Result<Option<i64>, String
     Ok(Some(id))
 }
 
+/// Thread id of the given connection, as accepted by `KILL QUERY`.
+pub async fn current_connection_id(conn: &mut MySqlConnection) -> Result<i64, String> {
+    let row = sqlx::query("SELECT CONNECTION_ID() as id")
+        .fetch_one(conn)
+        .await
+        .map_err(|e| format!("Failed to get connection ID: {}", e))?;
+    let id: u64 = row
+        .try_get("id")
+        .map_err(|e| format!("Failed to get connection ID: {}", e))?;
+    Ok(id as i64)
+}
+
+/// Aborts the statement running on `connection_id` but keeps the connection.
+pub async fn kill_query(pool: &Pool<MySql>, connection_id: i64) -> Result<String, String> {
+    sqlx::query(&format!("KILL QUERY {}", connection_id))
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Failed to cancel query: {}", e))?;
+    Ok(format!("Query on connection {} cancelled", connection_id))
+}
+
 pub async fn kill_process(pool: &Pool<MySql>, process_id: i64) -> Result<String, String> {
     let query = format!("KILL {}", process_id);
     sqlx::query(&query)
@@ -5,9 +5,10 @@
 use crate::db_types::*;
 use futures::StreamExt;
 use serde_json::Value;
-use sqlx::postgres::PgConnectOptions;
+use sqlx::postgres::{PgConnectOptions, PgRow};
 use sqlx::ConnectOptions;
-use sqlx::{Column, Executor, Pool, Postgres, Row};
+use sqlx::query::Query;
+use sqlx::{Column, Database, Execute, Executor, PgConnection, Pool, Postgres, Row};
 use std::collections::HashMap;
 use tokio::time::{timeout, Duration};
 
@@ -64,6 +65,15 @@ pub async fn test_connection(config: &ConnectionConfig) -> Result<String, String
 }
 
 pub async fn create_pool(config: &ConnectionConfig) -> Result<Pool<Postgres>, String> {
+    create_pool_with_session(config, &[]).await
+}
+
+/// Like `create_pool`, but every connection starts with `session_settings`
+/// applied as run-time parameters (`-c name=value`).
+pub async fn create_pool_with_session(
+    config: &ConnectionConfig,
+    session_settings: &[(String, String)],
+) -> Result<Pool<Postgres>, String> {
     let mut options = PgConnectOptions::new()
         .host(&config.host)
         .port(config.port)
@@ -88,6 +98,10 @@ pub async fn create_pool(config: &ConnectionConfig) -> Result<Pool<Postgres>, St
         };
     }
 
+    if !session_settings.is_empty() {
+        options = options.options(session_settings.iter().map(|(name, value)| (name, value)));
+    }
+
     sqlx::postgres::PgPoolOptions::new()
         .max_connections(10)
         .min_connections(2)
@@ -117,6 +131,49 @@ fn normalize_query_timeout_seconds(query_timeout_seconds: Option<u64>) -> Option
     }
 }
 
+pub(crate) fn row_to_json_values(row: &PgRow, column_count: usize) -> Vec<Value> {
+    let mut row_data = Vec::new();
+    for i in 0..column_count {
+        let val: serde_json::Value = row
+            .try_get_unchecked::<i64, _>(i)
+            .map(|v| serde_json::json!(v))
+            .or_else(|_| {
+                row.try_get_unchecked::<i32, _>(i)
+                    .map(|v| serde_json::json!(v))
+            })
+            .or_else(|_| {
+                row.try_get_unchecked::<i16, _>(i)
+                    .map(|v| serde_json::json!(v))
+            })
+            .or_else(|_| {
+                row.try_get_unchecked::<f64, _>(i)
+                    .map(|v| serde_json::json!(v))
+            })
+            .or_else(|_| {
+                row.try_get_unchecked::<f32, _>(i)
+                    .map(|v| serde_json::json!(v))
+            })
+            .or_else(|_| {
+                row.try_get_unchecked::<bool, _>(i)
+                    .map(|v| serde_json::json!(v))
+            })
+            .or_else(|_| {
+                row.try_get_unchecked::<String, _>(i)
+                    .map(|v| serde_json::json!(v))
+            })
+            .or_else(|_| {
+                row.try_get_unchecked::<Vec<u8>, _>(i).map(|bytes| {
+                    serde_json::json!(
+                        String::from_utf8_lossy(&bytes).to_string()
+                    )
+                })
+            })
+            .unwrap_or(serde_json::Value::Null);
+        row_data.push(val);
+    }
+    row_data
+}
+
 pub async fn execute_query(
     pool: &Pool<Postgres>,
     query: String,
@@ -129,10 +186,107 @@ pub async fn execute_query_with_timeout(
     query: String,
     query_timeout_seconds: Option<u64>,
 ) -> Result<Vec<QueryResult>, String> {
+    execute_query_with_executor(pool, &query, query_timeout_seconds).await
+}
+
+/// Runs `query` on an already-held connection, e.g. one pinned to a transaction.
+pub async fn execute_query_on_connection(
+    conn: &mut PgConnection,
+    query: &str,
+    query_timeout_seconds: Option<u64>,
+) -> Result<Vec<QueryResult>, String> {
+    execute_query_with_executor(conn, query, query_timeout_seconds).await
+}
+
+/// Runs one script statement on an already-held connection, returning its
+/// results and the rows it affected.
+pub async fn execute_script_statement(
+    conn: &mut PgConnection,
+    query: &str,
+    query_timeout_seconds: Option<u64>,
+) -> Result<(Vec<QueryResult>, u64), String> {
+    execute_statement_counting(conn, sqlx::raw_sql(query), query_timeout_seconds).await
+}
+
+fn bind_parameters<'q>(
+    mut query: Query<'q, Postgres, <Postgres as Database>::Arguments<'q>>,
+    params: &'q [BindValue],
+) -> Query<'q, Postgres, <Postgres as Database>::Arguments<'q>> {
+    for param in params {
+        query = match param {
+            BindValue::Bool(v) => query.bind(*v),
+            BindValue::Int(v) => query.bind(*v),
+            BindValue::Float(v) => query.bind(*v),
+            BindValue::Decimal(v) | BindValue::Text(v) | BindValue::Uuid(v) => {
+                query.bind(v.as_deref())
+            }
+            BindValue::Json(v) => query.bind(v.as_ref().map(sqlx::types::Json)),
+            BindValue::Bytes(v) => query.bind(v.as_deref()),
+            BindValue::Date(v) => query.bind(*v),
+            BindValue::Time(v) => query.bind(*v),
+            BindValue::DateTime(v) => query.bind(*v),
+            BindValue::Timestamp(v) => query.bind(*v),
+        };
+    }
+    query
+}
+
+/// Runs one prepared statement with `$n` placeholders bound to `params`, on either
+/// a pool or a pinned connection.
+pub async fn execute_parameterized_query<'a, E>(
+    executor: E,
+    query: &'a str,
+    params: &'a [BindValue],
+    query_timeout_seconds: Option<u64>,
+) -> Result<Vec<QueryResult>, String>
+where
+    E: Executor<'a, Database = Postgres>,
+{
+    let statement = bind_parameters(sqlx::query(query), params);
+    execute_statement_with_executor(executor, statement, query_timeout_seconds).await
+}
+
+async fn execute_query_with_executor<'a, E>(
+    executor: E,
+    query: &'a str,
+    query_timeout_seconds: Option<u64>,
+) -> Result<Vec<QueryResult>, String>
+where
+    E: Executor<'a, Database = Postgres>,
+{
+    execute_statement_with_executor(executor, sqlx::raw_sql(query), query_timeout_seconds).await
+}
+
+async fn execute_statement_with_executor<'a, E, S>(
+    executor: E,
+    statement: S,
+    query_timeout_seconds: Option<u64>,
+) -> Result<Vec<QueryResult>, String>
+where
+    E: Executor<'a, Database = Postgres>,
+    S: Execute<'a, Postgres> + 'a,
+{
+    execute_statement_counting(executor, statement, query_timeout_seconds)
+        .await
+        .map(|(results, _)| results)
+}
+
+/// Like `execute_statement_with_executor`, also returning the total rows
+/// affected across the statement's results.
+async fn execute_statement_counting<'a, E, S>(
+    executor: E,
+    statement: S,
+    query_timeout_seconds: Option<u64>,
+) -> Result<(Vec<QueryResult>, u64), String>
+where
+    E: Executor<'a, Database = Postgres>,
+    S: Execute<'a, Postgres> + 'a,
+{
     let mut results = Vec::new();
+    let mut rows_affected = 0u64;
 
     let stream_future = async {
-        let mut stream = sqlx::raw_sql(&query).fetch_many(pool);
+        let mut stream = executor.fetch_many(statement);
 
         let mut current_rows = Vec::new();
         let mut current_columns = Vec::new();
@@ -142,7 +296,8 @@ pub async fn execute_query_with_timeout(
                 Ok(either) => {
                     use sqlx::Either;
                     match either {
-                        Either::Left(_done) => {
+                        Either::Left(done) => {
+                            rows_affected += done.rows_affected();
                             if !current_rows.is_empty() || !current_columns.is_empty() {
                                 results.push(QueryResult {
                                     columns: current_columns.clone(),
@@ -161,46 +316,7 @@ pub async fn execute_query_with_timeout(
                                     row.columns().iter().map(|c| c.name().to_string()).collect();
                             }
 
-                            let mut row_data = Vec::new();
-                            for (i, _) in current_columns.iter().enumerate() {
-                                let val: serde_json::Value = row
-                                    .try_get_unchecked::<i64, _>(i)
-                                    .map(|v| serde_json::json!(v))
-                                    .or_else(|_| {
-                                        row.try_get_unchecked::<i32, _>(i)
-                                            .map(|v| serde_json::json!(v))
-                                    })
-                                    .or_else(|_| {
-                                        row.try_get_unchecked::<i16, _>(i)
-                                            .map(|v| serde_json::json!(v))
-                                    })
-                                    .or_else(|_| {
-                                        row.try_get_unchecked::<f64, _>(i)
-                                            .map(|v| serde_json::json!(v))
-                                    })
-                                    .or_else(|_| {
-                                        row.try_get_unchecked::<f32, _>(i)
-                                            .map(|v| serde_json::json!(v))
-                                    })
-                                    .or_else(|_| {
-                                        row.try_get_unchecked::<bool, _>(i)
-                                            .map(|v| serde_json::json!(v))
-                                    })
-                                    .or_else(|_| {
-                                        row.try_get_unchecked::<String, _>(i)
-                                            .map(|v| serde_json::json!(v))
-                                    })
-                                    .or_else(|_| {
-                                        row.try_get_unchecked::<Vec<u8>, _>(i).map(|bytes| {
-                                            serde_json::json!(
-                                                String::from_utf8_lossy(&bytes).to_string()
-                                            )
-                                        })
-                                    })
-                                    .unwrap_or(serde_json::Value::Null);
-                                row_data.push(val);
-                            }
-                            current_rows.push(row_data);
+                            current_rows.push(row_to_json_values(&row, current_columns.len()));
                         }
                     }
                 }
@@ -230,16 +346,90 @@ pub async fn execute_query_with_timeout(
     }
 
     if results.is_empty() {
-        return Ok(vec![QueryResult {
+        results.push(QueryResult {
             columns: vec![],
             rows: vec![],
             query_id: None,
             statistics: None,
             warnings: vec![],
-        }]);
+        });
     }
 
-    Ok(results)
+    Ok((results, rows_affected))
+}
+
+/// Streams the first result set of `query` into `tx` in batches. Returns early,
+/// abandoning the connection, once the receiving cursor is closed.
+pub async fn stream_query(
+    pool: &Pool<Postgres>,
+    query: &str,
+    tx: &tokio::sync::mpsc::Sender<ResultStreamEvent>,
+) -> Result<(), String> {
+    let mut conn = pool
+        .acquire()
+        .await
+        .map_err(|e| format!("Failed to acquire connection: {}", e))?;
+
+    let mut cancelled = false;
+    {
+        let mut stream = sqlx::raw_sql(query).fetch_many(conn.as_mut());
+        let mut columns_sent = false;
+        let mut batch = Vec::with_capacity(RESULT_STREAM_BATCH_SIZE);
+
+        loop {
+            let next = tokio::select! {
+                item = stream.next() => item,
+                _ = tx.closed() => {
+                    cancelled = true;
+                    break;
+                }
+            };
+            let Some(item) = next else {
+                break;
+            };
+
+            match item.map_err(|e| format!("Query error: {}", e))? {
+                sqlx::Either::Left(_done) => {
+                    // Only the first result set is streamed
+                    if columns_sent {
+                        break;
+                    }
+                }
+                sqlx::Either::Right(row) => {
+                    if !columns_sent {
+                        let columns = row.columns().iter().map(|c| c.name().to_string()).collect();
+                        if tx.send(ResultStreamEvent::Columns(columns)).await.is_err() {
+                            cancelled = true;
+                            break;
+                        }
+                        columns_sent = true;
+                    }
+
+                    batch.push(row_to_json_values(&row, row.columns().len()));
+                    if batch.len() >= RESULT_STREAM_BATCH_SIZE
+                        && tx
+                            .send(ResultStreamEvent::Rows(std::mem::take(&mut batch)))
+                            .await
+                            .is_err()
+                    {
+                        cancelled = true;
+                        break;
+                    }
+                }
+            }
+        }
+
+        if !cancelled && !batch.is_empty() {
+            let _ = tx.send(ResultStreamEvent::Rows(batch)).await;
+        }
+    }
+
+    if cancelled {
+        // Drop the connection instead of draining the rest of the result set
+        let _ = conn.detach();
+    }
+
+    Ok(())
 }
 
 // --- Database/Table Operations ---
@@ -651,6 +841,7 @@ pub async fn get_table_constraints(
             name: row.try_get("constraint_name").unwrap_or_default(),
             constraint_type: row.try_get("constraint_type").unwrap_or_default(),
             column_name: row.try_get("column_name").unwrap_or_default(),
+            definition: None,
         });
     }
 
@@ -786,6 +977,7 @@ pub async fn get_triggers(pool: &Pool<Postgres>, schema: &str) -> Result<Vec<Tri
             event: row.try_get("event").unwrap_or_default(),
             timing: row.try_get("timing").unwrap_or_default(),
             table_name: row.try_get("table_name").unwrap_or_default(),
+            definition: None,
         });
     }
 
@@ -831,6 +1023,7 @@ pub async fn get_table_triggers(
             event: row.try_get("event").unwrap_or_default(),
             timing: row.try_get("timing").unwrap_or_default(),
             table_name: row.try_get("table_name").unwrap_or_default(),
+            definition: None,
         });
     }
 
@@ -868,6 +1061,8 @@ pub async fn get_procedures(
         procedures.push(RoutineInfo {
             name: row.try_get("name").unwrap_or_default(),
             definer: row.try_get("definer").unwrap_or_default(),
+            routine_type: Some("PROCEDURE".to_string()),
+            definition: None,
         });
     }
 
@@ -903,6 +1098,8 @@ pub async fn get_functions(
         functions.push(RoutineInfo {
             name: row.try_get("name").unwrap_or_default(),
             definer: row.try_get("definer").unwrap_or_default(),
+            routine_type: Some("FUNCTION".to_string()),
+            definition: None,
         });
     }
 
@@ -1186,6 +1383,28 @@ pub async fn get_backend_pid(pool: &Pool<Postgres>) -> Result<Option<i64>, Strin
     Ok(Some(pid))
 }
 
+pub async fn current_backend_pid(conn: &mut PgConnection) -> Result<i64, String> {
+    let row = sqlx::query("SELECT pg_backend_pid() as pid")
+        .fetch_one(conn)
+        .await
+        .map_err(|e| format!("Failed to get backend PID: {}", e))?;
+    Ok(row.try_get::<i32, _>("pid").unwrap_or(0) as i64)
+}
+
+/// Cancels the backend's current statement without terminating its session.
+pub async fn cancel_backend(pool: &Pool<Postgres>, pid: i64) -> Result<String, String> {
+    let row = sqlx::query("SELECT pg_cancel_backend($1::int) as cancelled")
+        .bind(pid)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| format!("Failed to cancel query: {}", e))?;
+    if row.try_get::<bool, _>("cancelled").unwrap_or(false) {
+        Ok(format!("Query on backend {} cancelled", pid))
+    } else {
+        Err(format!("Backend {} is no longer running", pid))
+    }
+}
+
 pub async fn kill_process(pool: &Pool<Postgres>, process_id: i64) -> Result<String, String> {
     let query = format!("SELECT pg_terminate_backend({})", process_id);
     sqlx::query(&query)
@@ -11,7 +11,9 @@ pub async fn run_quality_analysis(
     database: String,
     table_name: String,
     sample_percent: Option<f64>,
+    session_id: Option<String>,
 ) -> Result<TableQualityReport, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -149,7 +151,9 @@ pub async fn delete_quality_rule(app_state: State<'_, AppState>, id: i64) -> Res
 pub async fn check_charset_mismatches(
     app_state: State<'_, AppState>,
     database: String,
+    session_id: Option<String>,
 ) -> Result<Vec<DataQualityIssue>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -0,0 +1,648 @@
+// =====================================================
+// MIGRATION APPLY MODULE
+// Runs migration plans against the active connection and records each run
+// in a history table on the target database
+// =====================================================
+
+use crate::db::sql_utils::{
+    escape_sql_string, quote_identifier_mssql, quote_identifier_mysql, quote_identifier_postgres,
+};
+use crate::db::{
+    run_statements, split_sql_script, ScriptErrorPolicy, ScriptStatement, ScriptTarget,
+    StatementOutcome, StatementStatus,
+};
+use crate::db_types::{AppState, DatabaseType, QueryResult};
+use crate::schema_tracker::migration::{MigrationPlan, MigrationStrategy};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::time::Instant;
+
+/// History table created on every migrated database. Schema diffs ignore it.
+pub const MIGRATION_HISTORY_TABLE: &str = "tactilesql_schema_migrations";
+
+const HISTORY_COLUMNS: [&str; 11] = [
+    "checksum",
+    "name",
+    "base_snapshot_id",
+    "target_snapshot_id",
+    "result_snapshot_id",
+    "strategy",
+    "applied_at",
+    "duration_ms",
+    "status",
+    "statements_applied",
+    "error_message",
+];
+
+// =====================================================
+// STRUCTS
+// =====================================================
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MigrationApplyRequest {
+    pub plan: MigrationPlan,
+    pub connection_id: String,
+    pub database: String,
+    /// Label stored in the history table; defaults to the checksum prefix
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Snapshots the plan was generated from, kept for the timeline
+    #[serde(default)]
+    pub base_snapshot_id: Option<i64>,
+    #[serde(default)]
+    pub target_snapshot_id: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MigrationApplyStatus {
+    Applied,
+    Failed,
+}
+
+impl MigrationApplyStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Applied => "applied",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+#[derive(Serialize, Clone)]
+pub struct MigrationApplyResult {
+    pub name: String,
+    pub checksum: String,
+    pub status: MigrationApplyStatus,
+    pub statements: Vec<StatementOutcome>,
+    pub duration_ms: f64,
+    /// Snapshot captured after the run, when the store accepted it
+    pub snapshot_id: Option<i64>,
+    pub error: Option<String>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct MigrationHistoryEntry {
+    pub id: i64,
+    pub checksum: String,
+    pub name: String,
+    pub base_snapshot_id: Option<i64>,
+    pub target_snapshot_id: Option<i64>,
+    pub result_snapshot_id: Option<i64>,
+    pub strategy: String,
+    pub applied_at: String,
+    pub duration_ms: i64,
+    pub status: String,
+    pub statements_applied: i64,
+    pub error_message: Option<String>,
+}
+
+// =====================================================
+// SQL BUILDING
+// =====================================================
+
+/// Checksum over the executable statements only, so comments, lock warning
+/// headers and whitespace do not make the same migration look new.
+pub fn migration_checksum(statements: &[ScriptStatement]) -> String {
+    let normalized = statements
+        .iter()
+        .map(|s| {
+            strip_leading_comments(&s.sql)
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut hasher = Sha256::new();
+    hasher.update(normalized);
+    format!("{:x}", hasher.finalize())
+}
+
+/// The splitter keeps comments that precede a statement; they are not part of it.
+fn strip_leading_comments(sql: &str) -> &str {
+    let mut rest = sql.trim_start();
+    loop {
+        if rest.starts_with("--") {
+            rest = rest.split_once('\n').map(|(_, tail)| tail).unwrap_or("");
+        } else if let Some(body) = rest.strip_prefix("/*") {
+            rest = body.split_once("*/").map(|(_, tail)| tail).unwrap_or("");
+        } else {
+            return rest;
+        }
+        rest = rest.trim_start();
+    }
+}
+
+pub fn is_migration_history_table(table_name: &str) -> bool {
+    table_name
+        .rsplit('.')
+        .next()
+        .unwrap_or(table_name)
+        .eq_ignore_ascii_case(MIGRATION_HISTORY_TABLE)
+}
+
+/// Points the pinned connection at `database` so unqualified plan statements land there.
+fn session_setup_statement(db_type: &DatabaseType, database: &str) -> Option<String> {
+    match db_type {
+        DatabaseType::MySQL => Some(format!("USE {}", quote_identifier_mysql(database))),
+        DatabaseType::PostgreSQL => Some(format!(
+            "SET search_path TO {}",
+            quote_identifier_postgres(database)
+        )),
+        DatabaseType::MSSQL => Some(format!("USE {}", quote_identifier_mssql(database))),
+        _ => None,
+    }
+}
+
+fn history_table_name(db_type: &DatabaseType) -> String {
+    match db_type {
+        DatabaseType::MSSQL => format!(
+            "{}.{}",
+            quote_identifier_mssql("dbo"),
+            quote_identifier_mssql(MIGRATION_HISTORY_TABLE)
+        ),
+        DatabaseType::PostgreSQL | DatabaseType::SQLite => {
+            quote_identifier_postgres(MIGRATION_HISTORY_TABLE)
+        }
+        _ => quote_identifier_mysql(MIGRATION_HISTORY_TABLE),
+    }
+}
+
+pub(crate) fn history_table_ddl(db_type: &DatabaseType) -> String {
+    let table = history_table_name(db_type);
+    match db_type {
+        DatabaseType::MySQL => format!(
+            "CREATE TABLE IF NOT EXISTS {} (
+    id BIGINT AUTO_INCREMENT PRIMARY KEY,
+    checksum VARCHAR(64) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    base_snapshot_id BIGINT NULL,
+    target_snapshot_id BIGINT NULL,
+    result_snapshot_id BIGINT NULL,
+    strategy VARCHAR(32) NOT NULL,
+    applied_at VARCHAR(40) NOT NULL,
+    duration_ms BIGINT NOT NULL,
+    status VARCHAR(16) NOT NULL,
+    statements_applied INT NOT NULL,
+    error_message TEXT NULL
+)",
+            table
+        ),
+        DatabaseType::PostgreSQL => format!(
+            "CREATE TABLE IF NOT EXISTS {} (
+    id BIGSERIAL PRIMARY KEY,
+    checksum VARCHAR(64) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    base_snapshot_id BIGINT NULL,
+    target_snapshot_id BIGINT NULL,
+    result_snapshot_id BIGINT NULL,
+    strategy VARCHAR(32) NOT NULL,
+    applied_at VARCHAR(40) NOT NULL,
+    duration_ms BIGINT NOT NULL,
+    status VARCHAR(16) NOT NULL,
+    statements_applied INTEGER NOT NULL,
+    error_message TEXT NULL
+)",
+            table
+        ),
+        DatabaseType::MSSQL => format!(
+            "IF OBJECT_ID(N'dbo.{}', N'U') IS NULL
+CREATE TABLE {} (
+    id BIGINT IDENTITY(1,1) PRIMARY KEY,
+    checksum NVARCHAR(64) NOT NULL,
+    name NVARCHAR(255) NOT NULL,
+    base_snapshot_id BIGINT NULL,
+    target_snapshot_id BIGINT NULL,
+    result_snapshot_id BIGINT NULL,
+    strategy NVARCHAR(32) NOT NULL,
+    applied_at NVARCHAR(40) NOT NULL,
+    duration_ms BIGINT NOT NULL,
+    status NVARCHAR(16) NOT NULL,
+    statements_applied INT NOT NULL,
+    error_message NVARCHAR(MAX) NULL
+)",
+            MIGRATION_HISTORY_TABLE, table
+        ),
+        DatabaseType::SQLite => format!(
+            "CREATE TABLE IF NOT EXISTS {} (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    checksum TEXT NOT NULL,
+    name TEXT NOT NULL,
+    base_snapshot_id INTEGER NULL,
+    target_snapshot_id INTEGER NULL,
+    result_snapshot_id INTEGER NULL,
+    strategy TEXT NOT NULL,
+    applied_at TEXT NOT NULL,
+    duration_ms INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    statements_applied INTEGER NOT NULL,
+    error_message TEXT NULL
+)",
+            table
+        ),
+        _ => format!(
+            "CREATE TABLE IF NOT EXISTS {} (
+    id Int64,
+    checksum String,
+    name String,
+    base_snapshot_id Nullable(Int64),
+    target_snapshot_id Nullable(Int64),
+    result_snapshot_id Nullable(Int64),
+    strategy String,
+    applied_at String,
+    duration_ms Int64,
+    status String,
+    statements_applied Int64,
+    error_message Nullable(String)
+) ENGINE = MergeTree() ORDER BY id",
+            table
+        ),
+    }
+}
+
+fn string_literal(db_type: &DatabaseType, value: &str) -> String {
+    match db_type {
+        // Only MySQL and ClickHouse treat backslashes as escapes
+        DatabaseType::MySQL | DatabaseType::ClickHouse => {
+            format!("'{}'", escape_sql_string(value))
+        }
+        DatabaseType::MSSQL => format!("N'{}'", value.replace('\'', "''")),
+        _ => format!("'{}'", value.replace('\'', "''")),
+    }
+}
+
+fn optional_id_literal(value: Option<i64>) -> String {
+    value
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| "NULL".to_string())
+}
+
+pub(crate) fn build_history_insert(
+    db_type: &DatabaseType,
+    entry: &MigrationHistoryEntry,
+) -> String {
+    let values = [
+        string_literal(db_type, &entry.checksum),
+        string_literal(db_type, &entry.name),
+        optional_id_literal(entry.base_snapshot_id),
+        optional_id_literal(entry.target_snapshot_id),
+        optional_id_literal(entry.result_snapshot_id),
+        string_literal(db_type, &entry.strategy),
+        string_literal(db_type, &entry.applied_at),
+        entry.duration_ms.to_string(),
+        string_literal(db_type, &entry.status),
+        entry.statements_applied.to_string(),
+        entry
+            .error_message
+            .as_deref()
+            .map(|e| string_literal(db_type, e))
+            .unwrap_or_else(|| "NULL".to_string()),
+    ];
+
+    // ClickHouse has no auto-increment; millisecond timestamps keep ids ordered
+    let (id_column, id_value) = if *db_type == DatabaseType::ClickHouse {
+        ("id, ", format!("{}, ", entry.id))
+    } else {
+        ("", String::new())
+    };
+
+    format!(
+        "INSERT INTO {} ({}{}) VALUES ({}{})",
+        history_table_name(db_type),
+        id_column,
+        HISTORY_COLUMNS.join(", "),
+        id_value,
+        values.join(", ")
+    )
+}
+
+/// Counts the history table in the database the connection is scoped to.
+pub(crate) fn history_table_exists_query(db_type: &DatabaseType) -> String {
+    let name = string_literal(db_type, MIGRATION_HISTORY_TABLE);
+    match db_type {
+        DatabaseType::MySQL => format!(
+            "SELECT COUNT(*) FROM information_schema.TABLES WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = {}",
+            name
+        ),
+        DatabaseType::PostgreSQL => format!(
+            "SELECT COUNT(*) FROM information_schema.tables WHERE table_schema = current_schema() AND table_name = {}",
+            name
+        ),
+        DatabaseType::MSSQL => format!(
+            "SELECT COUNT(*) FROM sys.tables WHERE object_id = OBJECT_ID(N'dbo.{}', N'U')",
+            MIGRATION_HISTORY_TABLE
+        ),
+        DatabaseType::SQLite => format!(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = {}",
+            name
+        ),
+        _ => format!(
+            "SELECT count() FROM system.tables WHERE database = currentDatabase() AND name = {}",
+            name
+        ),
+    }
+}
+
+fn applied_count_query(db_type: &DatabaseType, checksum: &str) -> String {
+    format!(
+        "SELECT COUNT(*) FROM {} WHERE checksum = {} AND status = {}",
+        history_table_name(db_type),
+        string_literal(db_type, checksum),
+        string_literal(db_type, MigrationApplyStatus::Applied.as_str())
+    )
+}
+
+// =====================================================
+// RESULT PARSING
+// =====================================================
+
+pub(crate) fn value_as_i64(value: &serde_json::Value) -> Option<i64> {
+    match value {
+        serde_json::Value::Number(n) => n.as_i64(),
+        // ClickHouse quotes 64-bit integers in JSON output
+        serde_json::Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+pub(crate) fn value_as_string(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::Null => None,
+        serde_json::Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+fn first_count(results: &[QueryResult]) -> i64 {
+    results
+        .first()
+        .and_then(|r| r.rows.first())
+        .and_then(|row| row.first())
+        .and_then(value_as_i64)
+        .unwrap_or(0)
+}
+
+pub(crate) fn parse_history_rows(result: &QueryResult) -> Vec<MigrationHistoryEntry> {
+    let column = |name: &str| {
+        result
+            .columns
+            .iter()
+            .position(|c| c.eq_ignore_ascii_case(name))
+    };
+
+    result
+        .rows
+        .iter()
+        .map(|row| {
+            let get = |name: &str| column(name).and_then(|p| row.get(p));
+            let int = |name: &str| get(name).and_then(value_as_i64);
+            let text = |name: &str| get(name).and_then(value_as_string);
+            MigrationHistoryEntry {
+                id: int("id").unwrap_or_default(),
+                checksum: text("checksum").unwrap_or_default(),
+                name: text("name").unwrap_or_default(),
+                base_snapshot_id: int("base_snapshot_id"),
+                target_snapshot_id: int("target_snapshot_id"),
+                result_snapshot_id: int("result_snapshot_id"),
+                strategy: text("strategy").unwrap_or_default(),
+                applied_at: text("applied_at").unwrap_or_default(),
+                duration_ms: int("duration_ms").unwrap_or_default(),
+                status: text("status").unwrap_or_default(),
+                statements_applied: int("statements_applied").unwrap_or_default(),
+                error_message: text("error_message"),
+            }
+        })
+        .collect()
+}
+
+// =====================================================
+// EXECUTION
+// =====================================================
+
+/// Opens a connection whose unqualified names resolve inside `database`.
+pub(crate) async fn connect_database_target(
+    app_state: &AppState,
+    db_type: &DatabaseType,
+    database: &str,
+) -> Result<ScriptTarget<'static>, String> {
+    let mut target = if *db_type == DatabaseType::ClickHouse {
+        let mut config = {
+            let guard = app_state.clickhouse_config.lock().await;
+            guard
+                .clone()
+                .ok_or("No ClickHouse connection established")?
+        };
+        config.database = Some(database.to_string());
+        ScriptTarget::ClickHouse(Box::new(config))
+    } else {
+        crate::db::connect_script_target(app_state, db_type).await?
+    };
+
+    if let Some(setup) = session_setup_statement(db_type, database) {
+        target
+            .execute(&setup, None)
+            .await
+            .map_err(|e| format!("Failed to select database '{}': {}", database, e))?;
+    }
+    Ok(target)
+}
+
+/// Opens a connection scoped to `database` with the history table in place.
+async fn open_migration_target(
+    app_state: &AppState,
+    db_type: &DatabaseType,
+    database: &str,
+) -> Result<ScriptTarget<'static>, String> {
+    let mut target = connect_database_target(app_state, db_type, database).await?;
+    target
+        .execute(&history_table_ddl(db_type), None)
+        .await
+        .map_err(|e| format!("Failed to create migration history table: {}", e))?;
+
+    Ok(target)
+}
+
+/// Runs `request.plan` statement by statement, stopping at the first failure.
+/// Plans whose checksum is already recorded as applied are refused.
+pub async fn apply_migration_plan(
+    app_state: &AppState,
+    request: MigrationApplyRequest,
+) -> Result<MigrationApplyResult, String> {
+    let started = Instant::now();
+    let db_type = {
+        let guard = app_state.active_db_type.lock().await;
+        guard.clone()
+    };
+    if db_type == DatabaseType::Disconnected {
+        return Err("No connection established".to_string());
+    }
+    let database = request.database.trim();
+    if database.is_empty() {
+        return Err("Target database is required to apply a migration".to_string());
+    }
+
+    let strategy = MigrationStrategy::from_str(&request.plan.strategy)?;
+    if matches!(
+        strategy,
+        MigrationStrategy::PtOsc | MigrationStrategy::GhOst
+    ) {
+        return Err(
+            "Plans using an external online schema change tool must be run with their generated commands"
+                .to_string(),
+        );
+    }
+
+    let statements = split_sql_script(&request.plan.script, &db_type);
+    if statements.is_empty() {
+        return Err("Migration plan contains no statements".to_string());
+    }
+    let checksum = migration_checksum(&statements);
+    let name = request
+        .name
+        .as_deref()
+        .map(str::trim)
+        .filter(|n| !n.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("migration_{}", &checksum[..12]));
+
+    let mut target = open_migration_target(app_state, &db_type, database).await?;
+    let already_applied = target
+        .execute(&applied_count_query(&db_type, &checksum), None)
+        .await
+        .map(|(results, _)| first_count(&results))
+        .map_err(|e| format!("Failed to read migration history: {}", e))?;
+    if already_applied > 0 {
+        return Err(format!(
+            "Migration '{}' ({}) was already applied to '{}'",
+            name,
+            &checksum[..12],
+            database
+        ));
+    }
+
+    let applied_at = Utc::now();
+    let outcomes = run_statements(&mut target, &statements, ScriptErrorPolicy::Stop, None).await;
+    let duration_ms = started.elapsed().as_secs_f64() * 1000.0;
+    // Release the pinned connection; single-connection pools need it for the capture
+    drop(target);
+
+    let failure = outcomes
+        .iter()
+        .find(|o| o.status == StatementStatus::Failed);
+    let status = if failure.is_some() {
+        MigrationApplyStatus::Failed
+    } else {
+        MigrationApplyStatus::Applied
+    };
+    let error = failure.map(|o| {
+        format!(
+            "Statement {} (line {}) failed: {}",
+            o.index + 1,
+            o.line,
+            o.error.as_deref().unwrap_or("unknown error")
+        )
+    });
+    let statements_applied = outcomes
+        .iter()
+        .filter(|o| o.status == StatementStatus::Succeeded)
+        .count();
+
+    // A partial run changes the schema too, so the timeline gets a snapshot either way
+    let mut warnings = Vec::new();
+    let snapshot_id = if statements_applied > 0 {
+        match crate::schema_tracker::commands::capture_and_store_snapshot(
+            app_state,
+            &request.connection_id,
+            database,
+        )
+        .await
+        {
+            Ok(snapshot) => snapshot.id,
+            Err(e) => {
+                warnings.push(format!("Failed to capture post-migration snapshot: {}", e));
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let entry = MigrationHistoryEntry {
+        id: applied_at.timestamp_millis(),
+        checksum: checksum.clone(),
+        name: name.clone(),
+        base_snapshot_id: request.base_snapshot_id,
+        target_snapshot_id: request.target_snapshot_id,
+        result_snapshot_id: snapshot_id,
+        strategy: request.plan.strategy.clone(),
+        applied_at: applied_at.to_rfc3339(),
+        duration_ms: duration_ms.round() as i64,
+        status: status.as_str().to_string(),
+        statements_applied: statements_applied as i64,
+        error_message: error.clone(),
+    };
+    let record = async {
+        let mut target = open_migration_target(app_state, &db_type, database).await?;
+        target
+            .execute(&build_history_insert(&db_type, &entry), None)
+            .await
+            .map(|_| ())
+    }
+    .await;
+    if let Err(e) = record {
+        warnings.push(format!("Failed to record migration history: {}", e));
+    }
+
+    Ok(MigrationApplyResult {
+        name,
+        checksum,
+        status,
+        statements: outcomes,
+        duration_ms,
+        snapshot_id,
+        error,
+        warnings,
+    })
+}
+
+/// Recorded runs on `database`, newest first; empty when nothing was applied yet.
+pub async fn get_migration_history(
+    app_state: &AppState,
+    database: &str,
+) -> Result<Vec<MigrationHistoryEntry>, String> {
+    let db_type = {
+        let guard = app_state.active_db_type.lock().await;
+        guard.clone()
+    };
+    if db_type == DatabaseType::Disconnected {
+        return Err("No connection established".to_string());
+    }
+
+    // Reading history must not create the table as a side effect
+    let mut target = connect_database_target(app_state, &db_type, database).await?;
+    let (exists, _) = target
+        .execute(&history_table_exists_query(&db_type), None)
+        .await
+        .map_err(|e| format!("Failed to look up migration history table: {}", e))?;
+    if first_count(&exists) == 0 {
+        return Ok(Vec::new());
+    }
+
+    let query = format!(
+        "SELECT id, {} FROM {} ORDER BY id DESC",
+        HISTORY_COLUMNS.join(", "),
+        history_table_name(&db_type)
+    );
+    let (results, _) = target
+        .execute(&query, None)
+        .await
+        .map_err(|e| format!("Failed to read migration history: {}", e))?;
+
+    Ok(results.first().map(parse_history_rows).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests;
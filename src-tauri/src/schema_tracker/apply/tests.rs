@@ -0,0 +1,147 @@
+use super::*;
+
+fn statements(script: &str, db_type: &DatabaseType) -> Vec<ScriptStatement> {
+    split_sql_script(script, db_type)
+}
+
+fn entry() -> MigrationHistoryEntry {
+    MigrationHistoryEntry {
+        id: 1_700_000_000_000,
+        checksum: "abc123".to_string(),
+        name: "add 'orders' index".to_string(),
+        base_snapshot_id: Some(3),
+        target_snapshot_id: None,
+        result_snapshot_id: Some(4),
+        strategy: "native".to_string(),
+        applied_at: "2024-01-01T00:00:00+00:00".to_string(),
+        duration_ms: 42,
+        status: "applied".to_string(),
+        statements_applied: 2,
+        error_message: None,
+    }
+}
+
+#[test]
+fn checksum_ignores_comments_and_whitespace() {
+    let first = statements(
+        "-- Migration Strategy: native\nALTER TABLE t ADD COLUMN a INT;\n\nCREATE INDEX i ON t (a);",
+        &DatabaseType::MySQL,
+    );
+    let second = statements(
+        "ALTER TABLE t\n    ADD COLUMN a INT;\n-- note\nCREATE INDEX i ON t (a);",
+        &DatabaseType::MySQL,
+    );
+    let different = statements("ALTER TABLE t ADD COLUMN b INT;", &DatabaseType::MySQL);
+
+    assert_eq!(migration_checksum(&first), migration_checksum(&second));
+    assert_ne!(migration_checksum(&first), migration_checksum(&different));
+    assert_eq!(migration_checksum(&first).len(), 64);
+}
+
+#[test]
+fn history_table_is_recognised_with_schema_prefix() {
+    assert!(is_migration_history_table("tactilesql_schema_migrations"));
+    assert!(is_migration_history_table(
+        "dbo.TACTILESQL_SCHEMA_MIGRATIONS"
+    ));
+    assert!(!is_migration_history_table("schema_migrations"));
+}
+
+#[test]
+fn history_ddl_is_idempotent_per_dialect() {
+    assert!(history_table_ddl(&DatabaseType::MySQL)
+        .starts_with("CREATE TABLE IF NOT EXISTS `tactilesql_schema_migrations`"));
+    assert!(history_table_ddl(&DatabaseType::PostgreSQL).contains("BIGSERIAL"));
+    assert!(history_table_ddl(&DatabaseType::SQLite).contains("AUTOINCREMENT"));
+    let mssql = history_table_ddl(&DatabaseType::MSSQL);
+    assert!(mssql.starts_with("IF OBJECT_ID(N'dbo.tactilesql_schema_migrations', N'U') IS NULL"));
+    assert!(mssql.contains("IDENTITY(1,1)"));
+    assert!(history_table_ddl(&DatabaseType::ClickHouse).contains("ENGINE = MergeTree()"));
+}
+
+#[tokio::test]
+async fn history_lookup_does_not_create_the_table() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    let query = history_table_exists_query(&DatabaseType::SQLite);
+    let count = || async {
+        sqlx::query_scalar::<_, i64>(&query)
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+    };
+
+    assert_eq!(count().await, 0);
+    sqlx::query(&history_table_ddl(&DatabaseType::SQLite))
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(count().await, 1);
+    assert!(history_table_exists_query(&DatabaseType::MSSQL)
+        .contains("OBJECT_ID(N'dbo.tactilesql_schema_migrations', N'U')"));
+}
+
+#[test]
+fn history_insert_escapes_values_and_sets_clickhouse_id() {
+    let pg = build_history_insert(&DatabaseType::PostgreSQL, &entry());
+    assert!(pg.starts_with("INSERT INTO \"tactilesql_schema_migrations\" (checksum, name,"));
+    assert!(pg.contains("'add ''orders'' index'"));
+    assert!(pg.contains("3, NULL, 4"));
+    assert!(pg.ends_with("42, 'applied', 2, NULL)"));
+
+    let mssql = build_history_insert(&DatabaseType::MSSQL, &entry());
+    assert!(mssql.contains("N'add ''orders'' index'"));
+
+    let clickhouse = build_history_insert(&DatabaseType::ClickHouse, &entry());
+    assert!(clickhouse.contains("(id, checksum,"));
+    assert!(clickhouse.contains("VALUES (1700000000000, 'abc123'"));
+    assert!(clickhouse.contains("'add ''orders'' index'"));
+}
+
+#[test]
+fn count_parsing_accepts_numbers_and_strings() {
+    let result = |value: serde_json::Value| QueryResult {
+        columns: vec!["count".to_string()],
+        rows: vec![vec![value]],
+        query_id: None,
+        statistics: None,
+        warnings: Vec::new(),
+    };
+    assert_eq!(first_count(&[result(serde_json::json!(2))]), 2);
+    assert_eq!(first_count(&[result(serde_json::json!("1"))]), 1);
+    assert_eq!(first_count(&[]), 0);
+}
+
+#[test]
+fn history_rows_are_parsed_by_column_name() {
+    let result = QueryResult {
+        columns: vec![
+            "id".to_string(),
+            "checksum".to_string(),
+            "status".to_string(),
+            "result_snapshot_id".to_string(),
+            "error_message".to_string(),
+        ],
+        rows: vec![vec![
+            serde_json::json!("7"),
+            serde_json::json!("abc"),
+            serde_json::json!("failed"),
+            serde_json::Value::Null,
+            serde_json::json!("boom"),
+        ]],
+        query_id: None,
+        statistics: None,
+        warnings: Vec::new(),
+    };
+
+    let rows = parse_history_rows(&result);
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].id, 7);
+    assert_eq!(rows[0].checksum, "abc");
+    assert_eq!(rows[0].status, "failed");
+    assert_eq!(rows[0].result_snapshot_id, None);
+    assert_eq!(rows[0].error_message.as_deref(), Some("boom"));
+}
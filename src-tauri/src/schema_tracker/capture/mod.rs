@@ -1,10 +1,37 @@
 use crate::db_types::*;
+use crate::schema_tracker::diff::compare_schemas;
 use crate::schema_tracker::models::{SchemaSnapshot, TableDefinition};
 use chrono::Utc;
 use sha2::{Digest, Sha256};
-use sqlx::{MySql, Pool, Postgres, Row, Column};
+use sqlx::{Column, MySql, Pool, Postgres, Row, Sqlite};
 use std::collections::HashMap;
 
+/// Hashes every captured object, so a snapshot that only differs in a view body,
+/// routine or trigger is stored separately instead of replacing its predecessor.
+pub(crate) fn compute_schema_hash(
+    tables: &[TableDefinition],
+    views: &[ViewDefinition],
+    routines: &[RoutineInfo],
+    triggers: &[TriggerInfo],
+) -> String {
+    let mut hasher = Sha256::new();
+    let schema_json =
+        serde_json::to_string(&(tables, views, routines, triggers)).unwrap_or_default();
+    hasher.update(schema_json);
+    format!("{:x}", hasher.finalize())
+}
+
+/// Keeps `latest`'s hash when `snapshot` holds the same schema, so a snapshot
+/// stored before views, routines and triggers (or their bodies) were hashed is
+/// matched instead of being recorded again.
+pub(crate) fn carry_unchanged_hash(snapshot: &mut SchemaSnapshot, latest: &SchemaSnapshot) {
+    let same_tables =
+        serde_json::to_string(&snapshot.tables).ok() == serde_json::to_string(&latest.tables).ok();
+    if same_tables && !compare_schemas(latest, snapshot).has_changes() {
+        snapshot.schema_hash = latest.schema_hash.clone();
+    }
+}
+
 // Internal abstraction to allow same capture logic for MySQL (sqlx) and ClickHouse (HTTP Client)
 #[async_trait::async_trait]
 pub trait SchemaCaptureExecutor {
@@ -50,14 +77,37 @@ impl SchemaCaptureExecutor for Pool<MySql> {
     async fn fetch_routines(&self, database: &str) -> Result<Vec<RoutineInfo>, String> {
         let mut routines = crate::mysql::get_procedures(self, database).await?;
         routines.extend(crate::mysql::get_functions(self, database).await?);
+        for routine in &mut routines {
+            let (kind, column) = match routine.routine_type.as_deref() {
+                Some("FUNCTION") => ("FUNCTION", "Create Function"),
+                _ => ("PROCEDURE", "Create Procedure"),
+            };
+            let query = format!("SHOW CREATE {} `{}`.`{}`", kind, database, routine.name);
+            routine.definition = mysql_show_create(self, &query, column).await;
+        }
         Ok(routines)
     }
 
     async fn fetch_triggers(&self, database: &str) -> Result<Vec<TriggerInfo>, String> {
-        crate::mysql::get_triggers(self, database).await
+        let mut triggers = crate::mysql::get_triggers(self, database).await?;
+        for trigger in &mut triggers {
+            let query = format!("SHOW CREATE TRIGGER `{}`.`{}`", database, trigger.name);
+            trigger.definition = mysql_show_create(self, &query, "SQL Original Statement").await;
+        }
+        Ok(triggers)
     }
 }
 
+/// Statement text of a SHOW CREATE result; None when it is hidden from the user.
+async fn mysql_show_create(pool: &Pool<MySql>, query: &str, column: &str) -> Option<String> {
+    let rows = pool.fetch_all_as_maps(query).await.ok()?;
+    rows.first()?
+        .get(column)?
+        .as_str()
+        .filter(|s| !s.trim().is_empty())
+        .map(str::to_string)
+}
+
 // ConnectionConfig impl removed as we use dedicated capture_snapshot_clickhouse
 
 
@@ -246,6 +296,7 @@ pub async fn capture_snapshot_mysql<E: SchemaCaptureExecutor>(
             name: row.get("CONSTRAINT_NAME").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
             constraint_type: row.get("CONSTRAINT_TYPE").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
             column_name: row.get("COLUMN_NAME").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+            definition: None,
         });
     }
 
@@ -273,10 +324,7 @@ pub async fn capture_snapshot_mysql<E: SchemaCaptureExecutor>(
     let routines = executor.fetch_routines(database).await.unwrap_or_default();
     let triggers = executor.fetch_triggers(database).await.unwrap_or_default();
 
-    let mut hasher = Sha256::new();
-    let schema_json = serde_json::to_string(&tables).unwrap_or_default();
-    hasher.update(schema_json);
-    let hash = format!("{:x}", hasher.finalize());
+    let hash = compute_schema_hash(&tables, &views, &routines, &triggers);
 
     Ok(SchemaSnapshot {
         id: None,
@@ -291,6 +339,68 @@ pub async fn capture_snapshot_mysql<E: SchemaCaptureExecutor>(
     })
 }
 
+/// Fills in routine and trigger bodies from `pg_get_functiondef` / `pg_get_triggerdef`.
+async fn attach_postgres_definitions(
+    pool: &Pool<Postgres>,
+    schema: &str,
+    routines: &mut [RoutineInfo],
+    triggers: &mut [TriggerInfo],
+) -> Result<(), String> {
+    let routine_rows = sqlx::query(
+        r#"
+        SELECT p.proname AS name, pg_get_functiondef(p.oid) AS definition
+        FROM pg_proc p
+        JOIN pg_namespace n ON p.pronamespace = n.oid
+        WHERE n.nspname = $1 AND p.prokind IN ('f', 'p')
+        ORDER BY p.oid
+    "#,
+    )
+    .bind(schema)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to fetch routine definitions: {}", e))?;
+
+    // Overloads share a name; the oldest one stands for all of them
+    let mut routine_definitions: HashMap<String, String> = HashMap::new();
+    for row in routine_rows {
+        let name: String = row.try_get("name").unwrap_or_default();
+        if let Ok(definition) = row.try_get::<String, _>("definition") {
+            routine_definitions.entry(name).or_insert(definition);
+        }
+    }
+    for routine in routines.iter_mut() {
+        routine.definition = routine_definitions.get(&routine.name).cloned();
+    }
+
+    let trigger_rows = sqlx::query(
+        r#"
+        SELECT t.tgname AS name, c.relname AS table_name, pg_get_triggerdef(t.oid) AS definition
+        FROM pg_trigger t
+        JOIN pg_class c ON t.tgrelid = c.oid
+        JOIN pg_namespace n ON c.relnamespace = n.oid
+        WHERE n.nspname = $1 AND NOT t.tgisinternal
+    "#,
+    )
+    .bind(schema)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to fetch trigger definitions: {}", e))?;
+
+    let mut trigger_definitions: HashMap<(String, String), String> = HashMap::new();
+    for row in trigger_rows {
+        let name: String = row.try_get("name").unwrap_or_default();
+        let table_name: String = row.try_get("table_name").unwrap_or_default();
+        if let Ok(definition) = row.try_get::<String, _>("definition") {
+            trigger_definitions.insert((table_name, name), definition);
+        }
+    }
+    for trigger in triggers.iter_mut() {
+        let key = (trigger.table_name.clone(), trigger.name.clone());
+        trigger.definition = trigger_definitions.get(&key).cloned();
+    }
+    Ok(())
+}
+
 pub async fn capture_snapshot_postgres(
     pool: &Pool<Postgres>,
     schema: &str, 
@@ -554,6 +664,7 @@ pub async fn capture_snapshot_postgres(
                 name: row.try_get("constraint_name").unwrap_or_default(),
                 constraint_type: row.try_get("constraint_type").unwrap_or_default(),
                 column_name: row.try_get("column_name").unwrap_or_default(),
+                definition: None,
             });
     }
 
@@ -579,12 +690,6 @@ pub async fn capture_snapshot_postgres(
         })
         .collect();
 
-    // Hash
-    let mut hasher = Sha256::new();
-    let schema_json = serde_json::to_string(&tables).unwrap_or_default();
-    hasher.update(schema_json);
-    let hash = format!("{:x}", hasher.finalize());
-
     let view_names = crate::postgres::get_views(pool, schema).await?;
     let mut views = Vec::new();
     for view_name in view_names {
@@ -597,7 +702,11 @@ pub async fn capture_snapshot_postgres(
     routines.extend(crate::postgres::get_procedures(pool, schema).await?);
     routines.extend(crate::postgres::get_functions(pool, schema).await?);
 
-    let triggers = crate::postgres::get_triggers(pool, schema).await?;
+    let mut triggers = crate::postgres::get_triggers(pool, schema).await?;
+    attach_postgres_definitions(pool, schema, &mut routines, &mut triggers).await?;
+
+    // Hash
+    let hash = compute_schema_hash(&tables, &views, &routines, &triggers);
 
     Ok(SchemaSnapshot {
         id: None,
@@ -767,10 +876,7 @@ pub async fn capture_snapshot_clickhouse(
     // 4. Finalize
     let tables: Vec<TableDefinition> = tables_map.into_values().collect();
 
-    let mut hasher = Sha256::new();
-    let schema_json = serde_json::to_string(&tables).unwrap_or_default();
-    hasher.update(schema_json);
-    let hash = format!("{:x}", hasher.finalize());
+    let hash = compute_schema_hash(&tables, &views, &[], &[]);
 
     Ok(SchemaSnapshot {
         id: None,
@@ -784,3 +890,679 @@ pub async fn capture_snapshot_clickhouse(
         triggers: Vec::new(),
     })
 }
+
+// --- MSSQL ---
+
+/// Runs a catalog query and keys each row by column name.
+async fn fetch_mssql_maps(
+    pool: &deadpool_tiberius::Pool,
+    query: String,
+) -> Result<Vec<HashMap<String, serde_json::Value>>, String> {
+    let results = crate::mssql::execute_query(pool, query).await?;
+    let Some(result) = results.into_iter().next() else {
+        return Ok(Vec::new());
+    };
+    Ok(result
+        .rows
+        .into_iter()
+        .map(|row| result.columns.iter().cloned().zip(row).collect())
+        .collect())
+}
+
+fn map_str(row: &HashMap<String, serde_json::Value>, key: &str) -> String {
+    row.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string()
+}
+
+fn map_i64(row: &HashMap<String, serde_json::Value>, key: &str) -> Option<i64> {
+    row.get(key).and_then(|v| v.as_i64())
+}
+
+fn map_bool(row: &HashMap<String, serde_json::Value>, key: &str) -> bool {
+    row.get(key)
+        .and_then(|v| v.as_bool().or_else(|| v.as_i64().map(|i| i != 0)))
+        .unwrap_or(false)
+}
+
+/// Renders an MSSQL column type the way it is declared, e.g. `nvarchar(50)`,
+/// `varbinary(MAX)` or `decimal(10,2)`. `max_length` is in bytes, as in `sys.columns`.
+pub(crate) fn format_mssql_column_type(type_name: &str, max_length: i64, precision: i64, scale: i64) -> String {
+    let lower = type_name.to_lowercase();
+    match lower.as_str() {
+        "varchar" | "char" | "varbinary" | "binary" => {
+            if max_length == -1 {
+                format!("{}(MAX)", type_name)
+            } else {
+                format!("{}({})", type_name, max_length)
+            }
+        }
+        "nvarchar" | "nchar" => {
+            if max_length == -1 {
+                format!("{}(MAX)", type_name)
+            } else {
+                format!("{}({})", type_name, max_length / 2)
+            }
+        }
+        "decimal" | "numeric" => format!("{}({},{})", type_name, precision, scale),
+        "datetime2" | "datetimeoffset" | "time" => format!("{}({})", type_name, scale),
+        _ => type_name.to_string(),
+    }
+}
+
+/// Captures every user schema of an MSSQL database from the `sys` catalog views.
+/// Tables are named `schema.table`, matching `mssql::get_tables`.
+pub async fn capture_snapshot_mssql(
+    pool: &deadpool_tiberius::Pool,
+    database: &str,
+    connection_id: &str,
+) -> Result<SchemaSnapshot, String> {
+    let db = if !database.is_empty() && database != "default" {
+        format!("[{}].", database.replace(']', "]]"))
+    } else {
+        String::new()
+    };
+
+    // 1. Tables with row counts from the heap / clustered index partitions
+    let tables_query = format!(
+        r#"
+        SELECT s.name AS schema_name, t.name AS table_name, CAST(SUM(p.rows) AS bigint) AS row_count
+        FROM {0}sys.tables t
+        INNER JOIN {0}sys.schemas s ON s.schema_id = t.schema_id
+        LEFT JOIN {0}sys.partitions p ON p.object_id = t.object_id AND p.index_id IN (0, 1)
+        WHERE t.is_ms_shipped = 0
+        GROUP BY s.name, t.name
+        ORDER BY s.name, t.name
+    "#,
+        db
+    );
+    let mut table_names = Vec::new();
+    let mut table_stats: HashMap<String, Option<u64>> = HashMap::new();
+    for row in fetch_mssql_maps(pool, tables_query).await? {
+        let name = format!("{}.{}", map_str(&row, "schema_name"), map_str(&row, "table_name"));
+        table_stats.insert(name.clone(), map_i64(&row, "row_count").map(|r| r.max(0) as u64));
+        table_names.push(name);
+    }
+
+    // 2. Columns, including identity, computed and default definitions
+    let columns_query = format!(
+        r#"
+        SELECT
+            s.name + '.' + t.name AS table_name,
+            c.name AS column_name,
+            ty.name AS type_name,
+            CAST(c.max_length AS int) AS max_length,
+            CAST(c.precision AS int) AS precision,
+            CAST(c.scale AS int) AS scale,
+            c.is_nullable,
+            c.is_identity,
+            c.is_computed,
+            c.collation_name,
+            dc.definition AS default_definition,
+            cc.definition AS computed_definition,
+            cc.is_persisted,
+            CAST(ic.seed_value AS bigint) AS identity_seed,
+            CAST(ic.increment_value AS bigint) AS identity_increment
+        FROM {0}sys.columns c
+        INNER JOIN {0}sys.tables t ON t.object_id = c.object_id
+        INNER JOIN {0}sys.schemas s ON s.schema_id = t.schema_id
+        INNER JOIN {0}sys.types ty ON ty.user_type_id = c.user_type_id
+        LEFT JOIN {0}sys.default_constraints dc ON dc.object_id = c.default_object_id
+        LEFT JOIN {0}sys.computed_columns cc ON cc.object_id = c.object_id AND cc.column_id = c.column_id
+        LEFT JOIN {0}sys.identity_columns ic ON ic.object_id = c.object_id AND ic.column_id = c.column_id
+        WHERE t.is_ms_shipped = 0
+        ORDER BY s.name, t.name, c.column_id
+    "#,
+        db
+    );
+    let mut columns_by_table: HashMap<String, Vec<ColumnSchema>> = HashMap::new();
+    for row in fetch_mssql_maps(pool, columns_query).await? {
+        let type_name = map_str(&row, "type_name");
+        let column_type = format_mssql_column_type(
+            &type_name,
+            map_i64(&row, "max_length").unwrap_or(0),
+            map_i64(&row, "precision").unwrap_or(0),
+            map_i64(&row, "scale").unwrap_or(0),
+        );
+        let extra = if map_bool(&row, "is_computed") {
+            let persisted = if map_bool(&row, "is_persisted") { " PERSISTED" } else { "" };
+            format!("AS {}{}", map_str(&row, "computed_definition"), persisted)
+        } else if map_bool(&row, "is_identity") {
+            format!(
+                "IDENTITY({},{})",
+                map_i64(&row, "identity_seed").unwrap_or(1),
+                map_i64(&row, "identity_increment").unwrap_or(1)
+            )
+        } else {
+            String::new()
+        };
+
+        columns_by_table
+            .entry(map_str(&row, "table_name"))
+            .or_default()
+            .push(ColumnSchema {
+                name: map_str(&row, "column_name"),
+                data_type: type_name,
+                column_type,
+                is_nullable: map_bool(&row, "is_nullable"),
+                column_key: String::new(), // Populated later via PKs
+                column_default: row
+                    .get("default_definition")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string()),
+                extra,
+                collation: row
+                    .get("collation_name")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string()),
+            });
+    }
+
+    // 3. Indexes (key columns only; heaps have no index name)
+    let indexes_query = format!(
+        r#"
+        SELECT
+            s.name + '.' + t.name AS table_name,
+            i.name AS index_name,
+            c.name AS column_name,
+            i.is_unique,
+            i.type_desc
+        FROM {0}sys.indexes i
+        INNER JOIN {0}sys.tables t ON t.object_id = i.object_id
+        INNER JOIN {0}sys.schemas s ON s.schema_id = t.schema_id
+        INNER JOIN {0}sys.index_columns ic ON ic.object_id = i.object_id AND ic.index_id = i.index_id
+        INNER JOIN {0}sys.columns c ON c.object_id = ic.object_id AND c.column_id = ic.column_id
+        WHERE t.is_ms_shipped = 0 AND i.name IS NOT NULL AND ic.is_included_column = 0
+        ORDER BY s.name, t.name, i.name, ic.key_ordinal
+    "#,
+        db
+    );
+    let mut indexes_by_table: HashMap<String, Vec<TableIndex>> = HashMap::new();
+    for row in fetch_mssql_maps(pool, indexes_query).await? {
+        indexes_by_table
+            .entry(map_str(&row, "table_name"))
+            .or_default()
+            .push(TableIndex {
+                name: map_str(&row, "index_name"),
+                column_name: map_str(&row, "column_name"),
+                non_unique: !map_bool(&row, "is_unique"),
+                index_type: map_str(&row, "type_desc"),
+            });
+    }
+
+    // 4. Foreign keys
+    let fk_query = format!(
+        r#"
+        SELECT
+            s.name + '.' + t.name AS table_name,
+            fk.name AS constraint_name,
+            c.name AS column_name,
+            rt.name AS referenced_table,
+            rc.name AS referenced_column,
+            rs.name AS referenced_schema
+        FROM {0}sys.foreign_key_columns fkc
+        INNER JOIN {0}sys.foreign_keys fk ON fk.object_id = fkc.constraint_object_id
+        INNER JOIN {0}sys.tables t ON t.object_id = fkc.parent_object_id
+        INNER JOIN {0}sys.schemas s ON s.schema_id = t.schema_id
+        INNER JOIN {0}sys.columns c ON c.object_id = fkc.parent_object_id AND c.column_id = fkc.parent_column_id
+        INNER JOIN {0}sys.tables rt ON rt.object_id = fkc.referenced_object_id
+        INNER JOIN {0}sys.schemas rs ON rs.schema_id = rt.schema_id
+        INNER JOIN {0}sys.columns rc ON rc.object_id = fkc.referenced_object_id AND rc.column_id = fkc.referenced_column_id
+        ORDER BY s.name, t.name, fk.name, fkc.constraint_column_id
+    "#,
+        db
+    );
+    let mut foreign_keys_by_table: HashMap<String, Vec<ForeignKey>> = HashMap::new();
+    for row in fetch_mssql_maps(pool, fk_query).await? {
+        foreign_keys_by_table
+            .entry(map_str(&row, "table_name"))
+            .or_default()
+            .push(ForeignKey {
+                constraint_name: map_str(&row, "constraint_name"),
+                column_name: map_str(&row, "column_name"),
+                referenced_table: map_str(&row, "referenced_table"),
+                referenced_column: map_str(&row, "referenced_column"),
+                referenced_schema: row
+                    .get("referenced_schema")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string()),
+            });
+    }
+
+    // 5. Primary key and unique constraints with their columns
+    let key_query = format!(
+        r#"
+        SELECT
+            s.name + '.' + t.name AS table_name,
+            kc.name AS constraint_name,
+            kc.type AS constraint_kind,
+            c.name AS column_name,
+            CAST(ic.key_ordinal AS int) AS ordinal_position
+        FROM {0}sys.key_constraints kc
+        INNER JOIN {0}sys.tables t ON t.object_id = kc.parent_object_id
+        INNER JOIN {0}sys.schemas s ON s.schema_id = t.schema_id
+        INNER JOIN {0}sys.index_columns ic ON ic.object_id = kc.parent_object_id AND ic.index_id = kc.unique_index_id
+        INNER JOIN {0}sys.columns c ON c.object_id = ic.object_id AND c.column_id = ic.column_id
+        WHERE t.is_ms_shipped = 0
+        ORDER BY s.name, t.name, kc.name, ic.key_ordinal
+    "#,
+        db
+    );
+    let mut primary_keys_by_table: HashMap<String, Vec<PrimaryKey>> = HashMap::new();
+    let mut constraints_by_table: HashMap<String, Vec<TableConstraint>> = HashMap::new();
+    for row in fetch_mssql_maps(pool, key_query).await? {
+        let t_name = map_str(&row, "table_name");
+        let c_name = map_str(&row, "column_name");
+        let is_primary = map_str(&row, "constraint_kind").trim() == "PK";
+
+        if is_primary {
+            if let Some(cols) = columns_by_table.get_mut(&t_name) {
+                for col in cols.iter_mut().filter(|col| col.name == c_name) {
+                    col.column_key = "PRI".to_string();
+                }
+            }
+            primary_keys_by_table
+                .entry(t_name.clone())
+                .or_default()
+                .push(PrimaryKey {
+                    column_name: c_name.clone(),
+                    ordinal_position: map_i64(&row, "ordinal_position").unwrap_or(0) as i32,
+                });
+        }
+        constraints_by_table
+            .entry(t_name)
+            .or_default()
+            .push(TableConstraint {
+                name: map_str(&row, "constraint_name"),
+                constraint_type: if is_primary { "PRIMARY KEY" } else { "UNIQUE" }.to_string(),
+                column_name: c_name,
+                definition: None,
+            });
+    }
+    for (t_name, fks) in &foreign_keys_by_table {
+        constraints_by_table
+            .entry(t_name.clone())
+            .or_default()
+            .extend(fks.iter().map(|fk| TableConstraint {
+                name: fk.constraint_name.clone(),
+                constraint_type: "FOREIGN KEY".to_string(),
+                column_name: fk.column_name.clone(),
+                definition: None,
+            }));
+    }
+
+    // 6. Check constraints; table-level checks have no parent column
+    let check_query = format!(
+        r#"
+        SELECT
+            s.name + '.' + t.name AS table_name,
+            cc.name AS constraint_name,
+            c.name AS column_name,
+            cc.definition
+        FROM {0}sys.check_constraints cc
+        INNER JOIN {0}sys.tables t ON t.object_id = cc.parent_object_id
+        INNER JOIN {0}sys.schemas s ON s.schema_id = t.schema_id
+        LEFT JOIN {0}sys.columns c ON c.object_id = cc.parent_object_id AND c.column_id = cc.parent_column_id
+        WHERE t.is_ms_shipped = 0
+        ORDER BY s.name, t.name, cc.name
+    "#,
+        db
+    );
+    for row in fetch_mssql_maps(pool, check_query).await? {
+        constraints_by_table
+            .entry(map_str(&row, "table_name"))
+            .or_default()
+            .push(TableConstraint {
+                name: map_str(&row, "constraint_name"),
+                constraint_type: "CHECK".to_string(),
+                column_name: map_str(&row, "column_name"),
+                definition: row
+                    .get("definition")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string()),
+            });
+    }
+
+    let tables: Vec<TableDefinition> = table_names
+        .iter()
+        .map(|t_name| TableDefinition {
+            name: t_name.clone(),
+            columns: columns_by_table.get(t_name).cloned().unwrap_or_default(),
+            indexes: indexes_by_table.get(t_name).cloned().unwrap_or_default(),
+            foreign_keys: foreign_keys_by_table.get(t_name).cloned().unwrap_or_default(),
+            primary_keys: primary_keys_by_table.get(t_name).cloned().unwrap_or_default(),
+            constraints: constraints_by_table.get(t_name).cloned().unwrap_or_default(),
+            row_count: table_stats.get(t_name).cloned().flatten(),
+        })
+        .collect();
+
+    // 7. Views, routines and triggers
+    let views_query = format!(
+        r#"
+        SELECT s.name + '.' + v.name AS name, m.definition
+        FROM {0}sys.views v
+        INNER JOIN {0}sys.schemas s ON s.schema_id = v.schema_id
+        LEFT JOIN {0}sys.sql_modules m ON m.object_id = v.object_id
+        WHERE v.is_ms_shipped = 0
+        ORDER BY s.name, v.name
+    "#,
+        db
+    );
+    let views: Vec<ViewDefinition> = fetch_mssql_maps(pool, views_query)
+        .await?
+        .iter()
+        .map(|row| ViewDefinition {
+            name: map_str(row, "name"),
+            definition: map_str(row, "definition"),
+        })
+        .collect();
+
+    let routines_query = format!(
+        r#"
+        SELECT s.name + '.' + o.name AS name, ISNULL(p.name, '') AS definer,
+            CASE WHEN o.type = 'P' THEN 'PROCEDURE' ELSE 'FUNCTION' END AS routine_type,
+            m.definition
+        FROM {0}sys.objects o
+        INNER JOIN {0}sys.schemas s ON s.schema_id = o.schema_id
+        LEFT JOIN {0}sys.database_principals p ON p.principal_id = ISNULL(o.principal_id, s.principal_id)
+        LEFT JOIN {0}sys.sql_modules m ON m.object_id = o.object_id
+        WHERE o.type IN ('P', 'FN', 'IF', 'TF') AND o.is_ms_shipped = 0
+        ORDER BY s.name, o.name
+    "#,
+        db
+    );
+    let routines: Vec<RoutineInfo> = fetch_mssql_maps(pool, routines_query)
+        .await?
+        .iter()
+        .map(|row| RoutineInfo {
+            name: map_str(row, "name"),
+            definer: map_str(row, "definer"),
+            routine_type: Some(map_str(row, "routine_type")),
+            definition: Some(map_str(row, "definition")).filter(|d| !d.is_empty()),
+        })
+        .collect();
+
+    let triggers_query = format!(
+        r#"
+        SELECT
+            tr.name,
+            s.name + '.' + t.name AS table_name,
+            CASE WHEN tr.is_instead_of_trigger = 1 THEN 'INSTEAD OF' ELSE 'AFTER' END AS timing,
+            STUFF((
+                SELECT ', ' + te.type_desc FROM {0}sys.trigger_events te
+                WHERE te.object_id = tr.object_id
+                FOR XML PATH('')
+            ), 1, 2, '') AS event,
+            m.definition
+        FROM {0}sys.triggers tr
+        INNER JOIN {0}sys.tables t ON t.object_id = tr.parent_id
+        INNER JOIN {0}sys.schemas s ON s.schema_id = t.schema_id
+        LEFT JOIN {0}sys.sql_modules m ON m.object_id = tr.object_id
+        WHERE tr.is_ms_shipped = 0
+        ORDER BY tr.name
+    "#,
+        db
+    );
+    let triggers: Vec<TriggerInfo> = fetch_mssql_maps(pool, triggers_query)
+        .await?
+        .iter()
+        .map(|row| TriggerInfo {
+            name: map_str(row, "name"),
+            event: map_str(row, "event"),
+            timing: map_str(row, "timing"),
+            table_name: map_str(row, "table_name"),
+            definition: Some(map_str(row, "definition")).filter(|d| !d.is_empty()),
+        })
+        .collect();
+
+    let hash = compute_schema_hash(&tables, &views, &routines, &triggers);
+
+    Ok(SchemaSnapshot {
+        id: None,
+        connection_id: connection_id.to_string(),
+        database_name: Some(database.to_string()),
+        timestamp: Utc::now(),
+        schema_hash: hash,
+        tables,
+        views,
+        routines,
+        triggers,
+    })
+}
+
+// --- SQLite ---
+
+/// Maps `pragma_table_xinfo.hidden` to a MySQL-style EXTRA value for generated columns.
+pub(crate) fn sqlite_generated_extra(hidden: i64) -> &'static str {
+    match hidden {
+        2 => "VIRTUAL GENERATED",
+        3 => "STORED GENERATED",
+        _ => "",
+    }
+}
+
+/// Captures a SQLite database from its pragma table-valued functions.
+pub async fn capture_snapshot_sqlite(
+    pool: &Pool<Sqlite>,
+    database: &str,
+    connection_id: &str,
+) -> Result<SchemaSnapshot, String> {
+    // 1. Tables; AUTOINCREMENT is only visible in the stored DDL
+    let table_rows = sqlx::query(
+        "SELECT name, sql FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to fetch tables: {}", e))?;
+
+    let mut table_names = Vec::new();
+    let mut autoincrement_tables = Vec::new();
+    let mut table_stats: HashMap<String, Option<u64>> = HashMap::new();
+    for row in &table_rows {
+        let name: String = row.try_get("name").unwrap_or_default();
+        let sql: String = row.try_get("sql").unwrap_or_default();
+        if sql.to_uppercase().contains("AUTOINCREMENT") {
+            autoincrement_tables.push(name.clone());
+        }
+        let count: Option<i64> = sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM \"{}\"",
+            name.replace('"', "\"\"")
+        ))
+        .fetch_one(pool)
+        .await
+        .ok();
+        table_stats.insert(name.clone(), count.map(|c| c.max(0) as u64));
+        table_names.push(name);
+    }
+
+    // 2. Columns (table_xinfo also lists generated columns)
+    let column_rows = sqlx::query(
+        r#"
+        SELECT m.name AS table_name, p.name, p.type, p."notnull" AS not_null, p.dflt_value, p.pk, p.hidden
+        FROM sqlite_master m
+        JOIN pragma_table_xinfo(m.name) p
+        WHERE m.type = 'table' AND m.name NOT LIKE 'sqlite_%'
+        ORDER BY m.name, p.cid
+    "#,
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to fetch columns: {}", e))?;
+
+    let mut columns_by_table: HashMap<String, Vec<ColumnSchema>> = HashMap::new();
+    let mut primary_keys_by_table: HashMap<String, Vec<PrimaryKey>> = HashMap::new();
+    for row in column_rows {
+        let table_name: String = row.try_get("table_name").unwrap_or_default();
+        let name: String = row.try_get("name").unwrap_or_default();
+        let column_type: String = row.try_get("type").unwrap_or_default();
+        let pk: i64 = row.try_get("pk").unwrap_or(0);
+        let hidden: i64 = row.try_get("hidden").unwrap_or(0);
+
+        let extra = if pk > 0 && autoincrement_tables.contains(&table_name) {
+            "auto_increment"
+        } else {
+            sqlite_generated_extra(hidden)
+        };
+        if pk > 0 {
+            primary_keys_by_table
+                .entry(table_name.clone())
+                .or_default()
+                .push(PrimaryKey {
+                    column_name: name.clone(),
+                    ordinal_position: pk as i32,
+                });
+        }
+
+        columns_by_table
+            .entry(table_name)
+            .or_default()
+            .push(ColumnSchema {
+                name,
+                data_type: column_type
+                    .split('(')
+                    .next()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string(),
+                column_type,
+                is_nullable: row.try_get::<i64, _>("not_null").unwrap_or(0) == 0,
+                column_key: if pk > 0 { "PRI".to_string() } else { String::new() },
+                column_default: row.try_get("dflt_value").ok().flatten(),
+                extra: extra.to_string(),
+                collation: None,
+            });
+    }
+    for pks in primary_keys_by_table.values_mut() {
+        pks.sort_by_key(|pk| pk.ordinal_position);
+    }
+
+    // 3. Indexes; unique constraints are recorded as constraints instead
+    let index_rows = sqlx::query(
+        r#"
+        SELECT m.name AS table_name, il.name AS index_name, il."unique" AS is_unique, il.origin, ii.name AS column_name
+        FROM sqlite_master m
+        JOIN pragma_index_list(m.name) il
+        JOIN pragma_index_info(il.name) ii
+        WHERE m.type = 'table' AND m.name NOT LIKE 'sqlite_%'
+        ORDER BY m.name, il.name, ii.seqno
+    "#,
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to fetch indexes: {}", e))?;
+
+    let mut indexes_by_table: HashMap<String, Vec<TableIndex>> = HashMap::new();
+    let mut constraints_by_table: HashMap<String, Vec<TableConstraint>> = HashMap::new();
+    for row in index_rows {
+        let table_name: String = row.try_get("table_name").unwrap_or_default();
+        let index_name: String = row.try_get("index_name").unwrap_or_default();
+        let column_name: String = row.try_get::<Option<String>, _>("column_name").ok().flatten().unwrap_or_default();
+        let origin: String = row.try_get("origin").unwrap_or_default();
+
+        match origin.as_str() {
+            "pk" => {}
+            "u" => constraints_by_table
+                .entry(table_name)
+                .or_default()
+                .push(TableConstraint {
+                    name: index_name,
+                    constraint_type: "UNIQUE".to_string(),
+                    column_name,
+                    definition: None,
+                }),
+            _ => indexes_by_table
+                .entry(table_name)
+                .or_default()
+                .push(TableIndex {
+                    name: index_name,
+                    column_name,
+                    non_unique: row.try_get::<i64, _>("is_unique").unwrap_or(0) == 0,
+                    index_type: "BTREE".to_string(),
+                }),
+        }
+    }
+
+    // 4. Foreign keys
+    let fk_rows = sqlx::query(
+        r#"
+        SELECT m.name AS table_name, fk.id, fk."from" AS column_name, fk."table" AS referenced_table, fk."to" AS referenced_column
+        FROM sqlite_master m
+        JOIN pragma_foreign_key_list(m.name) fk
+        WHERE m.type = 'table' AND m.name NOT LIKE 'sqlite_%'
+        ORDER BY m.name, fk.id, fk.seq
+    "#,
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to fetch foreign keys: {}", e))?;
+
+    let mut foreign_keys_by_table: HashMap<String, Vec<ForeignKey>> = HashMap::new();
+    for row in fk_rows {
+        let table_name: String = row.try_get("table_name").unwrap_or_default();
+        let id: i64 = row.try_get("id").unwrap_or(0);
+        let fk = ForeignKey {
+            // Same naming as sqlite::get_table_foreign_keys; SQLite does not keep FK names
+            constraint_name: format!("fk_{}_{}", table_name, id),
+            column_name: row.try_get("column_name").unwrap_or_default(),
+            referenced_table: row.try_get("referenced_table").unwrap_or_default(),
+            // `to` is NULL when the FK targets the parent's primary key implicitly
+            referenced_column: row.try_get::<Option<String>, _>("referenced_column").ok().flatten().unwrap_or_default(),
+            referenced_schema: None,
+        };
+        constraints_by_table
+            .entry(table_name.clone())
+            .or_default()
+            .push(TableConstraint {
+                name: fk.constraint_name.clone(),
+                constraint_type: "FOREIGN KEY".to_string(),
+                column_name: fk.column_name.clone(),
+                definition: None,
+            });
+        foreign_keys_by_table.entry(table_name).or_default().push(fk);
+    }
+    for (t_name, pks) in &primary_keys_by_table {
+        constraints_by_table
+            .entry(t_name.clone())
+            .or_default()
+            .extend(pks.iter().map(|pk| TableConstraint {
+                name: "PRIMARY".to_string(),
+                constraint_type: "PRIMARY KEY".to_string(),
+                column_name: pk.column_name.clone(),
+                definition: None,
+            }));
+    }
+
+    let tables: Vec<TableDefinition> = table_names
+        .iter()
+        .map(|t_name| TableDefinition {
+            name: t_name.clone(),
+            columns: columns_by_table.get(t_name).cloned().unwrap_or_default(),
+            indexes: indexes_by_table.get(t_name).cloned().unwrap_or_default(),
+            foreign_keys: foreign_keys_by_table.get(t_name).cloned().unwrap_or_default(),
+            primary_keys: primary_keys_by_table.get(t_name).cloned().unwrap_or_default(),
+            constraints: constraints_by_table.get(t_name).cloned().unwrap_or_default(),
+            row_count: table_stats.get(t_name).cloned().flatten(),
+        })
+        .collect();
+
+    let views = crate::sqlite::get_views(pool).await?;
+    let mut triggers = crate::sqlite::get_triggers(pool).await?;
+    for trigger in &mut triggers {
+        trigger.definition = crate::sqlite::get_trigger_ddl(pool, &trigger.name).await.ok();
+    }
+
+    let hash = compute_schema_hash(&tables, &views, &[], &triggers);
+
+    Ok(SchemaSnapshot {
+        id: None,
+        connection_id: connection_id.to_string(),
+        database_name: Some(database.to_string()),
+        timestamp: Utc::now(),
+        schema_hash: hash,
+        tables,
+        views,
+        routines: Vec::new(),
+        triggers,
+    })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,95 @@
+use super::*;
+use sqlx::sqlite::SqlitePoolOptions;
+
+#[test]
+fn test_format_mssql_column_type() {
+    assert_eq!(format_mssql_column_type("nvarchar", 100, 0, 0), "nvarchar(50)");
+    assert_eq!(format_mssql_column_type("varbinary", -1, 0, 0), "varbinary(MAX)");
+    assert_eq!(format_mssql_column_type("decimal", 9, 10, 2), "decimal(10,2)");
+    assert_eq!(format_mssql_column_type("datetime2", 8, 27, 7), "datetime2(7)");
+    assert_eq!(format_mssql_column_type("int", 4, 10, 0), "int");
+}
+
+#[test]
+fn test_sqlite_generated_extra() {
+    assert_eq!(sqlite_generated_extra(0), "");
+    assert_eq!(sqlite_generated_extra(2), "VIRTUAL GENERATED");
+    assert_eq!(sqlite_generated_extra(3), "STORED GENERATED");
+}
+
+#[tokio::test]
+async fn test_capture_snapshot_sqlite() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    for statement in [
+        "CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, email TEXT NOT NULL UNIQUE, name VARCHAR(50) DEFAULT 'x')",
+        "CREATE TABLE orders (id INTEGER PRIMARY KEY, user_id INTEGER REFERENCES users(id), total REAL, doubled REAL GENERATED ALWAYS AS (total * 2) VIRTUAL)",
+        "CREATE INDEX idx_orders_user ON orders(user_id)",
+        "CREATE VIEW big_orders AS SELECT * FROM orders WHERE total > 100",
+        "INSERT INTO users (email) VALUES ('a@example.com')",
+    ] {
+        sqlx::query(statement).execute(&pool).await.unwrap();
+    }
+
+    let snapshot = capture_snapshot_sqlite(&pool, "main", "conn").await.unwrap();
+    assert_eq!(snapshot.tables.len(), 2);
+    assert_eq!(snapshot.views.len(), 1);
+
+    let orders = snapshot.tables.iter().find(|t| t.name == "orders").unwrap();
+    assert_eq!(orders.columns.len(), 4);
+    assert_eq!(orders.columns[3].extra, "VIRTUAL GENERATED");
+    assert_eq!(orders.indexes[0].name, "idx_orders_user");
+    assert_eq!(orders.foreign_keys[0].referenced_table, "users");
+
+    let users = snapshot.tables.iter().find(|t| t.name == "users").unwrap();
+    assert_eq!(users.row_count, Some(1));
+    assert_eq!(users.columns[0].extra, "auto_increment");
+    assert_eq!(users.columns[2].column_type, "VARCHAR(50)");
+    assert_eq!(users.columns[2].data_type, "VARCHAR");
+    assert!(!users.columns[1].is_nullable);
+    assert!(users.constraints.iter().any(|c| c.constraint_type == "UNIQUE" && c.column_name == "email"));
+    assert_eq!(users.primary_keys[0].column_name, "id");
+}
+
+#[tokio::test]
+async fn test_unchanged_snapshot_keeps_previous_hash() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    for statement in [
+        "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)",
+        "CREATE TABLE audit (user_id INTEGER)",
+        "CREATE TRIGGER trg_users AFTER INSERT ON users BEGIN INSERT INTO audit VALUES (new.id); END",
+    ] {
+        sqlx::query(statement).execute(&pool).await.unwrap();
+    }
+
+    let snapshot = capture_snapshot_sqlite(&pool, "main", "conn").await.unwrap();
+    assert!(snapshot.triggers[0]
+        .definition
+        .as_deref()
+        .is_some_and(|d| d.contains("INSERT INTO audit")));
+
+    // Stored before trigger bodies were captured and hashed
+    let mut legacy = snapshot.clone();
+    legacy.triggers[0].definition = None;
+    legacy.schema_hash = "legacy".to_string();
+    let mut current = snapshot.clone();
+    carry_unchanged_hash(&mut current, &legacy);
+    assert_eq!(current.schema_hash, "legacy");
+
+    for statement in [
+        "DROP TRIGGER trg_users",
+        "CREATE TRIGGER trg_users AFTER INSERT ON users BEGIN INSERT INTO audit VALUES (0); END",
+    ] {
+        sqlx::query(statement).execute(&pool).await.unwrap();
+    }
+    let mut changed = capture_snapshot_sqlite(&pool, "main", "conn").await.unwrap();
+    carry_unchanged_hash(&mut changed, &snapshot);
+    assert_ne!(changed.schema_hash, snapshot.schema_hash);
+}
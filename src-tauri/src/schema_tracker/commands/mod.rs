@@ -1,6 +1,7 @@
 use crate::db_types::{AppState, DatabaseType};
-use crate::schema_tracker::models::{SchemaSnapshot, SchemaDiff, SchemaImpactAiReport};
+use crate::schema_tracker::models::{SchemaSnapshot, SchemaDiff, SchemaDriftEvent, SchemaImpactAiReport};
 use crate::schema_tracker::migration::MigrationPlan;
+use crate::schema_tracker::online_schema_change::{self, OscControlAction, OscRunState};
 use tauri::{command, State};
 
 #[command]
@@ -8,8 +9,43 @@ pub async fn capture_schema_snapshot(
     app_state: State<'_, AppState>,
     connection_id: String,
     database: Option<String>,
+    session_id: Option<String>,
 ) -> Result<SchemaSnapshot, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
     let database = database.ok_or("Database name is required for schema snapshot")?;
+    capture_and_store_snapshot(&app_state, &connection_id, &database).await
+}
+
+/// Captures `database` on the active connection and saves it to the snapshot store.
+/// A store failure is logged rather than returned; the snapshot then has no id.
+pub(crate) async fn capture_and_store_snapshot(
+    app_state: &AppState,
+    connection_id: &str,
+    database: &str,
+) -> Result<SchemaSnapshot, String> {
+    let mut snapshot = capture_live_snapshot(app_state, connection_id, database).await?;
+
+    // Save snapshot to store
+    let store_guard = app_state.schema_tracker_store.lock().await;
+    if let Some(store) = store_guard.as_ref() {
+        if let Err(e) = store.adopt_latest_hash(&mut snapshot).await {
+            eprintln!("Failed to compare with the previous schema snapshot: {}", e);
+        }
+        match store.save_snapshot(&snapshot).await {
+            Ok(id) => snapshot.id = Some(id),
+            Err(e) => eprintln!("Failed to save schema snapshot: {}", e),
+        }
+    }
+
+    Ok(snapshot)
+}
+
+/// Captures `database` on the active connection without storing it.
+pub(crate) async fn capture_live_snapshot(
+    app_state: &AppState,
+    connection_id: &str,
+    database: &str,
+) -> Result<SchemaSnapshot, String> {
     let db_type = {
         let guard = app_state.active_db_type.lock().await;
         guard.clone()
@@ -19,37 +55,33 @@ pub async fn capture_schema_snapshot(
         DatabaseType::MySQL => {
             let pool_guard = app_state.mysql_pool.lock().await;
             let pool = pool_guard.as_ref().ok_or("No active MySQL connection")?;
-            crate::schema_tracker::capture::capture_snapshot_mysql(pool, &database, &connection_id).await?
+            crate::schema_tracker::capture::capture_snapshot_mysql(pool, database, connection_id).await?
         }
         DatabaseType::PostgreSQL => {
             let pool_guard = app_state.postgres_pool.lock().await;
             let pool = pool_guard
                 .as_ref()
                 .ok_or("No active PostgreSQL connection")?;
-            crate::schema_tracker::capture::capture_snapshot_postgres(pool, &database, &connection_id).await?
+            crate::schema_tracker::capture::capture_snapshot_postgres(pool, database, connection_id).await?
         }
         DatabaseType::ClickHouse => {
             let guard = app_state.clickhouse_config.lock().await;
             let config = guard.as_ref().ok_or("No ClickHouse connection established")?;
-            crate::schema_tracker::capture::capture_snapshot_clickhouse(config, &database, &connection_id).await?
+            crate::schema_tracker::capture::capture_snapshot_clickhouse(config, database, connection_id).await?
         }
         DatabaseType::MSSQL => {
-            return Err("Schema tracking not yet supported for MSSQL".into());
+            let guard = app_state.mssql_pool.lock().await;
+            let pool = guard.as_ref().ok_or("No MSSQL connection established")?;
+            crate::schema_tracker::capture::capture_snapshot_mssql(pool, database, connection_id).await?
         }
         DatabaseType::SQLite => {
-            return Err("Schema tracking not yet supported for SQLite".into());
+            let guard = app_state.sqlite_pool.lock().await;
+            let pool = guard.as_ref().ok_or("No SQLite connection established")?;
+            crate::schema_tracker::capture::capture_snapshot_sqlite(pool, database, connection_id).await?
         }
         DatabaseType::Disconnected => return Err("No connection established".into()),
     };
 
-    // Save snapshot to store
-    let store_guard = app_state.schema_tracker_store.lock().await;
-    if let Some(store) = store_guard.as_ref() {
-        if let Err(e) = store.save_snapshot(&snapshot).await {
-            eprintln!("Failed to save schema snapshot: {}", e);
-        }
-    }
-
     Ok(snapshot)
 }
 
@@ -84,25 +116,105 @@ pub async fn generate_migration(
         "postgresql" | "postgres" => DatabaseType::PostgreSQL,
         "mssql" => DatabaseType::MSSQL,
         "clickhouse" => DatabaseType::ClickHouse,
+        "sqlite" => DatabaseType::SQLite,
         _ => return Err(format!("Unsupported database type: {}", db_type)),
     };
     Ok(crate::schema_tracker::migration::generate_migration_script(&diff, &db_enum))
 }
 
+fn parse_db_type(db_type: &str) -> Result<DatabaseType, String> {
+    match db_type.to_lowercase().as_str() {
+        "mysql" => Ok(DatabaseType::MySQL),
+        "postgresql" | "postgres" => Ok(DatabaseType::PostgreSQL),
+        "mssql" => Ok(DatabaseType::MSSQL),
+        "clickhouse" => Ok(DatabaseType::ClickHouse),
+        "sqlite" => Ok(DatabaseType::SQLite),
+        _ => Err(format!("Unsupported database type: {}", db_type)),
+    }
+}
+
+/// `source_db_type` names the engine the diff was captured from when it differs
+/// from `db_type`; column types are then translated before generating DDL.
 #[command]
 pub async fn generate_migration_plan(
     _app_state: State<'_, AppState>,
     diff: SchemaDiff,
     db_type: String,
+    source_db_type: Option<String>,
 ) -> Result<MigrationPlan, String> {
-    let db_enum = match db_type.to_lowercase().as_str() {
-        "mysql" => DatabaseType::MySQL,
-        "postgresql" | "postgres" => DatabaseType::PostgreSQL,
-        "mssql" => DatabaseType::MSSQL,
-        "clickhouse" => DatabaseType::ClickHouse,
-        _ => return Err(format!("Unsupported database type: {}", db_type)),
-    };
-    Ok(crate::schema_tracker::migration::generate_migration_plan(&diff, &db_enum, None))
+    let db_enum = parse_db_type(&db_type)?;
+    match source_db_type.as_deref().map(parse_db_type).transpose()? {
+        Some(source) if source != db_enum => Ok(
+            crate::schema_tracker::migration::generate_cross_engine_migration_plan(
+                &diff, &source, &db_enum, None,
+            ),
+        ),
+        _ => Ok(crate::schema_tracker::migration::generate_migration_plan(
+            &diff, &db_enum, None,
+        )),
+    }
+}
+
+#[command]
+pub async fn apply_migration_plan(
+    app_state: State<'_, AppState>,
+    request: crate::schema_tracker::apply::MigrationApplyRequest,
+    session_id: Option<String>,
+) -> Result<crate::schema_tracker::apply::MigrationApplyResult, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
+    crate::schema_tracker::apply::apply_migration_plan(&app_state, request).await
+}
+
+#[command]
+pub async fn get_migration_history(
+    app_state: State<'_, AppState>,
+    database: String,
+    session_id: Option<String>,
+) -> Result<Vec<crate::schema_tracker::apply::MigrationHistoryEntry>, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
+    crate::schema_tracker::apply::get_migration_history(&app_state, &database).await
+}
+
+#[command]
+pub async fn export_migration_files(
+    request: crate::schema_tracker::migration_files::MigrationExportRequest,
+) -> Result<Vec<String>, String> {
+    crate::schema_tracker::migration_files::export_migration_files(&request)
+}
+
+#[command]
+pub async fn get_migration_directory_status(
+    app_state: State<'_, AppState>,
+    request: crate::schema_tracker::migration_files::MigrationDirectoryRequest,
+    session_id: Option<String>,
+) -> Result<crate::schema_tracker::migration_files::MigrationDirectoryStatus, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
+    crate::schema_tracker::migration_files::get_migration_directory_status(&app_state, &request)
+        .await
+}
+
+#[command]
+pub async fn compare_schema_file(
+    app_state: State<'_, AppState>,
+    request: crate::schema_tracker::ddl_import::SchemaFileCompareRequest,
+    session_id: Option<String>,
+) -> Result<crate::schema_tracker::ddl_import::SchemaFileComparison, String> {
+    let app_state = app_state.for_session(session_id.as_deref()).await?;
+    crate::schema_tracker::ddl_import::compare_schema_file(&app_state, &request).await
+}
+
+#[command]
+pub async fn get_online_schema_change_status(run_id: String) -> Result<OscRunState, String> {
+    online_schema_change::get_online_schema_change_status(&run_id).await
+}
+
+#[command]
+pub async fn control_online_schema_change(
+    run_id: String,
+    action: String,
+) -> Result<OscRunState, String> {
+    let action = OscControlAction::parse(&action)?;
+    online_schema_change::control_online_schema_change(&run_id, action).await
 }
 
 #[command]
@@ -134,6 +246,33 @@ pub async fn get_schema_snapshots(
     }
 }
 
+#[command]
+pub async fn get_schema_drift_events(
+    app_state: State<'_, AppState>,
+    connection_id: String,
+    database: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<SchemaDriftEvent>, String> {
+    let guard = app_state.schema_tracker_store.lock().await;
+    if let Some(store) = guard.as_ref() {
+        store
+            .get_drift_events(&connection_id, database.as_deref(), limit.unwrap_or(50))
+            .await
+    } else {
+        Ok(Vec::new())
+    }
+}
+
+#[command]
+pub async fn acknowledge_schema_drift_event(
+    app_state: State<'_, AppState>,
+    event_id: i64,
+) -> Result<(), String> {
+    let guard = app_state.schema_tracker_store.lock().await;
+    let store = guard.as_ref().ok_or("Store not initialized")?;
+    store.acknowledge_drift_event(event_id).await
+}
+
 #[command]
 pub async fn save_ai_impact_report(
     app_state: State<'_, AppState>,
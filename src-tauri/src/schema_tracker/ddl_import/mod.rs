@@ -0,0 +1,1276 @@
+// =====================================================
+// DDL IMPORT MODULE
+// Reads schema-as-code DDL files into snapshots so the desired schema can be
+// diffed against a live connection and turned into a migration plan
+// =====================================================
+
+use crate::db::split_sql_script;
+use crate::db_types::{
+    AppState, ColumnSchema, DatabaseType, ForeignKey, PrimaryKey, TableConstraint, TableIndex,
+    ViewDefinition,
+};
+use crate::schema_tracker::capture::compute_schema_hash;
+use crate::schema_tracker::diff::compare_schemas;
+use crate::schema_tracker::migration::{generate_migration_plan, MigrationPlan};
+use crate::schema_tracker::models::{SchemaDiff, SchemaSnapshot, TableDefinition};
+use crate::schema_tracker::type_mapping::{self, CanonicalType};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::{
+    AlterColumnOperation, AlterTableOperation, ColumnDef, ColumnOption, CreateIndex, CreateTable,
+    Expr, GeneratedAs, GeneratedExpressionMode, Ident, ObjectName, OneOrManyWithParens,
+    SequenceOptions, Statement, TableConstraint as DdlConstraint,
+};
+use sqlparser::dialect::{
+    ClickHouseDialect, Dialect, MsSqlDialect, MySqlDialect, PostgreSqlDialect, SQLiteDialect,
+};
+use sqlparser::parser::Parser;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+const MSSQL_DEFAULT_SCHEMA: &str = "dbo";
+
+// =====================================================
+// STRUCTS
+// =====================================================
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SchemaFileCompareRequest {
+    pub connection_id: String,
+    pub database: String,
+    /// A `.sql` file, or a directory whose `.sql` files are read in name order
+    pub path: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct SchemaFileComparison {
+    pub files: Vec<String>,
+    /// Schema declared by the files
+    pub desired: SchemaSnapshot,
+    /// Changes that bring the live database to the declared schema
+    pub diff: SchemaDiff,
+    pub plan: MigrationPlan,
+    /// Statements that were skipped or only partly understood
+    pub warnings: Vec<String>,
+}
+
+// =====================================================
+// FILES
+// =====================================================
+
+/// Reads `path` as a single DDL file, or every `.sql` file of a directory in name order.
+pub fn read_schema_files(path: &str) -> Result<Vec<(String, String)>, String> {
+    let root = Path::new(path);
+    let mut paths = Vec::new();
+    if root.is_dir() {
+        let dir = fs::read_dir(root)
+            .map_err(|e| format!("Failed to read schema directory '{}': {}", path, e))?;
+        for entry in dir {
+            let entry_path = entry.map_err(|e| e.to_string())?.path();
+            if entry_path.is_file()
+                && entry_path.extension().and_then(|e| e.to_str()) == Some("sql")
+            {
+                paths.push(entry_path);
+            }
+        }
+        paths.sort();
+        if paths.is_empty() {
+            return Err(format!("No .sql files found in '{}'", path));
+        }
+    } else {
+        paths.push(root.to_path_buf());
+    }
+
+    paths
+        .iter()
+        .map(|file| {
+            let name = file
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            fs::read_to_string(file)
+                .map(|content| (name.clone(), content))
+                .map_err(|e| format!("Failed to read schema file '{}': {}", name, e))
+        })
+        .collect()
+}
+
+// =====================================================
+// PARSING
+// =====================================================
+
+fn dialect_for(db_type: &DatabaseType) -> Box<dyn Dialect> {
+    match db_type {
+        DatabaseType::PostgreSQL => Box::new(PostgreSqlDialect {}),
+        DatabaseType::MSSQL => Box::new(MsSqlDialect {}),
+        DatabaseType::SQLite => Box::new(SQLiteDialect {}),
+        DatabaseType::ClickHouse => Box::new(ClickHouseDialect {}),
+        DatabaseType::MySQL | DatabaseType::Disconnected => Box::new(MySqlDialect {}),
+    }
+}
+
+/// Replaces every ASCII case-insensitive occurrence of `pattern` (given in upper case).
+fn replace_ignore_case(sql: &str, pattern: &str, replacement: &str) -> String {
+    let upper = sql.to_ascii_uppercase();
+    let mut out = String::with_capacity(sql.len());
+    let mut last = 0;
+    for (pos, _) in upper.match_indices(pattern) {
+        out.push_str(&sql[last..pos]);
+        out.push_str(replacement);
+        last = pos + pattern.len();
+    }
+    out.push_str(&sql[last..]);
+    out
+}
+
+/// sqlparser does not know T-SQL `IDENTITY(seed, increment)`, so it is rewritten to
+/// the standard identity clause carrying the same seed and increment.
+fn rewrite_mssql_identity(sql: &str) -> String {
+    let is_word = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '@';
+    let mut out = String::with_capacity(sql.len());
+    let mut in_quote = false;
+    let mut idx = 0;
+    while let Some(ch) = sql[idx..].chars().next() {
+        let rest = &sql[idx..];
+        let keyword = !in_quote
+            && rest
+                .get(..8)
+                .is_some_and(|word| word.eq_ignore_ascii_case("identity"))
+            && !out.chars().next_back().is_some_and(is_word)
+            && !rest[8..].chars().next().is_some_and(is_word);
+        if !keyword {
+            if ch == '\'' {
+                in_quote = !in_quote;
+            }
+            out.push(ch);
+            idx += ch.len_utf8();
+            continue;
+        }
+
+        idx += 8;
+        out.push_str("GENERATED BY DEFAULT AS IDENTITY");
+        let after = &sql[idx..];
+        let trimmed = after.trim_start();
+        if let Some(args) = trimmed.strip_prefix('(') {
+            if let Some(close) = args.find(')') {
+                let parts: Vec<&str> = args[..close].split(',').map(str::trim).collect();
+                if let [seed, increment] = parts.as_slice() {
+                    out.push_str(&format!(
+                        " (INCREMENT BY {} START WITH {})",
+                        increment, seed
+                    ));
+                    idx += after.len() - trimmed.len() + close + 2;
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Works around engine syntax the parser does not accept; the rewrites do not
+/// change what the statement declares.
+fn prepare_statement(sql: &str, db_type: &DatabaseType) -> String {
+    match db_type {
+        DatabaseType::MySQL => {
+            let sql = replace_ignore_case(sql, ") USING BTREE", ")");
+            replace_ignore_case(&sql, ") USING HASH", ")")
+        }
+        DatabaseType::MSSQL => {
+            let mut sql = rewrite_mssql_identity(sql);
+            for clustering in ["NONCLUSTERED INDEX", "CLUSTERED INDEX"] {
+                sql = replace_ignore_case(&sql, clustering, "INDEX");
+            }
+            sql
+        }
+        DatabaseType::ClickHouse => replace_ignore_case(sql, "MERGETREE()", "MergeTree"),
+        _ => sql.to_string(),
+    }
+}
+
+/// PostgreSQL folds unquoted identifiers to lower case; other engines keep them as written.
+fn ident_name(ident: &Ident, db_type: &DatabaseType) -> String {
+    if *db_type == DatabaseType::PostgreSQL && ident.quote_style.is_none() {
+        ident.value.to_lowercase()
+    } else {
+        ident.value.clone()
+    }
+}
+
+/// Table names as the capture reports them: `schema.table` for MSSQL, bare elsewhere.
+fn table_name(name: &ObjectName, db_type: &DatabaseType) -> String {
+    let parts: Vec<String> = name.0.iter().map(|i| ident_name(i, db_type)).collect();
+    let table = parts.last().cloned().unwrap_or_default();
+    if *db_type == DatabaseType::MSSQL {
+        let schema = parts
+            .len()
+            .checked_sub(2)
+            .map(|idx| parts[idx].clone())
+            .unwrap_or_else(|| MSSQL_DEFAULT_SCHEMA.to_string());
+        format!("{}.{}", schema, table)
+    } else {
+        table
+    }
+}
+
+fn bare_name(table: &str) -> &str {
+    table.rsplit('.').next().unwrap_or(table)
+}
+
+/// Column names of a ClickHouse key expression; function keys have none.
+fn key_columns(expr: &Expr) -> Vec<String> {
+    match expr {
+        Expr::Identifier(ident) => vec![ident.value.clone()],
+        Expr::CompoundIdentifier(parts) => {
+            parts.last().map(|i| i.value.clone()).into_iter().collect()
+        }
+        Expr::Nested(inner) => key_columns(inner),
+        Expr::Tuple(items) => items.iter().flat_map(key_columns).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn is_constraint(constraint: &TableConstraint, kind: &str) -> bool {
+    constraint.constraint_type.eq_ignore_ascii_case(kind)
+}
+
+/// Collects tables, indexes and views from DDL statements, using the catalog
+/// conventions of `db_type` for names the DDL leaves implicit.
+struct DdlReader {
+    db_type: DatabaseType,
+    tables: Vec<TableDefinition>,
+    views: Vec<ViewDefinition>,
+    /// Declared primary key constraint names, applied when the table is finished
+    primary_key_names: HashMap<String, String>,
+    warnings: Vec<String>,
+    ignored: usize,
+}
+
+impl DdlReader {
+    fn new(db_type: &DatabaseType) -> Self {
+        Self {
+            db_type: db_type.clone(),
+            tables: Vec::new(),
+            views: Vec::new(),
+            primary_key_names: HashMap::new(),
+            warnings: Vec::new(),
+            ignored: 0,
+        }
+    }
+
+    fn read_script(&mut self, source: &str, sql: &str) {
+        let dialect = dialect_for(&self.db_type);
+        for statement in split_sql_script(sql, &self.db_type) {
+            let location = format!("{}:{}", source, statement.line);
+            let text = prepare_statement(&statement.sql, &self.db_type);
+            match Parser::parse_sql(&*dialect, &text) {
+                Ok(parsed) => {
+                    for stmt in parsed {
+                        self.read_statement(stmt, &location);
+                    }
+                }
+                Err(e) => self.warnings.push(format!(
+                    "{}: statement skipped, it could not be parsed ({})",
+                    location, e
+                )),
+            }
+        }
+    }
+
+    fn read_statement(&mut self, statement: Statement, location: &str) {
+        match statement {
+            Statement::CreateTable(create) => self.read_create_table(&create, location),
+            Statement::CreateIndex(index) => self.read_create_index(&index, location),
+            Statement::CreateView { name, query, .. } => {
+                let name = table_name(&name, &self.db_type);
+                self.views.retain(|v| v.name != name);
+                self.views.push(ViewDefinition {
+                    name,
+                    definition: query.to_string(),
+                });
+            }
+            Statement::AlterTable {
+                name, operations, ..
+            } => self.read_alter_table(&name, &operations, location),
+            _ => self.ignored += 1,
+        }
+    }
+
+    fn read_create_table(&mut self, create: &CreateTable, location: &str) {
+        let name = table_name(&create.name, &self.db_type);
+        if create.query.is_some() || create.like.is_some() || create.clone.is_some() {
+            self.warnings.push(format!(
+                "{}: {} is created from another table or query and was skipped",
+                location, name
+            ));
+            return;
+        }
+
+        let mut table = TableDefinition {
+            name: name.clone(),
+            ..Default::default()
+        };
+        for column in &create.columns {
+            self.add_column(&mut table, column);
+        }
+        for constraint in &create.constraints {
+            self.add_constraint(&mut table, constraint);
+        }
+        if self.db_type == DatabaseType::ClickHouse {
+            // The sorting key doubles as the primary key when none is given
+            let key = match (&create.primary_key, &create.order_by) {
+                (Some(primary_key), _) => key_columns(primary_key),
+                (None, Some(OneOrManyWithParens::One(expr))) => key_columns(expr),
+                (None, Some(OneOrManyWithParens::Many(exprs))) => {
+                    exprs.iter().flat_map(key_columns).collect()
+                }
+                (None, None) => Vec::new(),
+            };
+            self.add_primary_key(&mut table, &key, None);
+        }
+
+        if let Some(pos) = self.tables.iter().position(|t| t.name == name) {
+            self.warnings.push(format!(
+                "{}: {} is defined more than once; the last definition is used",
+                location, name
+            ));
+            self.tables.remove(pos);
+        }
+        self.tables.push(table);
+    }
+
+    fn read_create_index(&mut self, index: &CreateIndex, location: &str) {
+        let table = table_name(&index.table_name, &self.db_type);
+        let Some(pos) = self.tables.iter().position(|t| t.name == table) else {
+            self.warnings.push(format!(
+                "{}: index on {} skipped, the table is not defined in the schema files",
+                location, table
+            ));
+            return;
+        };
+
+        let mut columns = Vec::new();
+        for column in &index.columns {
+            match &column.expr {
+                Expr::Identifier(ident) => columns.push(ident_name(ident, &self.db_type)),
+                Expr::CompoundIdentifier(parts) => {
+                    columns.extend(parts.last().map(|ident| ident_name(ident, &self.db_type)))
+                }
+                other => {
+                    self.warnings.push(format!(
+                        "{}: expression index on {} ({}) is not compared",
+                        location, table, other
+                    ));
+                    return;
+                }
+            }
+        }
+
+        let name = index
+            .name
+            .as_ref()
+            .and_then(|n| n.0.last())
+            .map(|ident| ident_name(ident, &self.db_type));
+        let index_type = index.using.as_ref().map(|using| using.value.clone());
+        let mut definition = std::mem::take(&mut self.tables[pos]);
+        self.add_index(&mut definition, name, &columns, index.unique, index_type);
+        self.tables[pos] = definition;
+    }
+
+    fn read_alter_table(
+        &mut self,
+        name: &ObjectName,
+        operations: &[AlterTableOperation],
+        location: &str,
+    ) {
+        let table_name = table_name(name, &self.db_type);
+        let Some(pos) = self.tables.iter().position(|t| t.name == table_name) else {
+            self.warnings.push(format!(
+                "{}: ALTER TABLE {} skipped, the table is not defined in the schema files",
+                location, table_name
+            ));
+            return;
+        };
+
+        let mut table = std::mem::take(&mut self.tables[pos]);
+        for operation in operations {
+            match operation {
+                AlterTableOperation::AddConstraint(constraint) => {
+                    self.add_constraint(&mut table, constraint)
+                }
+                AlterTableOperation::AddColumn { column_def, .. } => {
+                    self.add_column(&mut table, column_def)
+                }
+                AlterTableOperation::AlterColumn { column_name, op } => {
+                    let column_name = ident_name(column_name, &self.db_type);
+                    let Some(column) = table.columns.iter_mut().find(|c| c.name == column_name)
+                    else {
+                        self.warnings.push(format!(
+                            "{}: column {}.{} is not defined",
+                            location, table_name, column_name
+                        ));
+                        continue;
+                    };
+                    match op {
+                        AlterColumnOperation::SetNotNull => column.is_nullable = false,
+                        AlterColumnOperation::DropNotNull => column.is_nullable = true,
+                        AlterColumnOperation::SetDefault { value } => {
+                            let value = value.to_string();
+                            // pg_dump attaches serial sequences this way
+                            if value.to_ascii_lowercase().starts_with("nextval(") {
+                                column.extra = "auto_increment".to_string();
+                            }
+                            column.column_default = Some(value);
+                        }
+                        AlterColumnOperation::DropDefault => column.column_default = None,
+                        AlterColumnOperation::SetDataType { data_type, .. } => {
+                            column.column_type = data_type.to_string();
+                        }
+                        other => self.warnings.push(format!(
+                            "{}: ALTER COLUMN {}.{} {} is not applied",
+                            location, table_name, column_name, other
+                        )),
+                    }
+                }
+                other => self.warnings.push(format!(
+                    "{}: ALTER TABLE {} {} is not applied",
+                    location, table_name, other
+                )),
+            }
+        }
+        self.tables[pos] = table;
+    }
+
+    fn add_column(&mut self, table: &mut TableDefinition, column: &ColumnDef) {
+        let name = ident_name(&column.name, &self.db_type);
+        let column_type = column.data_type.to_string();
+        let mut col = ColumnSchema {
+            name: name.clone(),
+            data_type: column_type
+                .split(['(', ' '])
+                .next()
+                .unwrap_or_default()
+                .to_lowercase(),
+            // ClickHouse columns are only nullable through their type
+            is_nullable: self.db_type != DatabaseType::ClickHouse
+                || column_type.starts_with("Nullable("),
+            column_type,
+            column_key: String::new(),
+            column_default: None,
+            extra: String::new(),
+            collation: column.collation.as_ref().map(|c| c.to_string()),
+        };
+
+        let mut auto_increment = false;
+        let mut extra = Vec::new();
+        for option in &column.options {
+            let constraint_name = option.name.as_ref().map(|n| ident_name(n, &self.db_type));
+            match &option.option {
+                ColumnOption::Null => col.is_nullable = true,
+                ColumnOption::NotNull => col.is_nullable = false,
+                ColumnOption::Default(expr) => col.column_default = Some(expr.to_string()),
+                ColumnOption::Unique {
+                    is_primary: true, ..
+                } => self.add_primary_key(table, std::slice::from_ref(&name), constraint_name),
+                ColumnOption::Unique {
+                    is_primary: false, ..
+                } => self.add_unique(table, std::slice::from_ref(&name), constraint_name),
+                ColumnOption::ForeignKey {
+                    foreign_table,
+                    referred_columns,
+                    ..
+                } => self.add_foreign_key(
+                    table,
+                    std::slice::from_ref(&name),
+                    foreign_table,
+                    referred_columns,
+                    constraint_name,
+                ),
+                ColumnOption::Check(expr) => self.add_check(table, &name, expr, constraint_name),
+                ColumnOption::DialectSpecific(tokens) => {
+                    auto_increment |= tokens.iter().any(|token| {
+                        let token = token.to_string();
+                        token.eq_ignore_ascii_case("auto_increment")
+                            || token.eq_ignore_ascii_case("autoincrement")
+                    })
+                }
+                ColumnOption::OnUpdate(expr) => extra.push(format!("on update {}", expr)),
+                ColumnOption::Generated {
+                    generation_expr: None,
+                    sequence_options,
+                    ..
+                } => match self.db_type {
+                    DatabaseType::MSSQL => {
+                        let option_value = |pick: fn(&SequenceOptions) -> Option<&Expr>| {
+                            sequence_options
+                                .iter()
+                                .flatten()
+                                .find_map(pick)
+                                .map(|e| e.to_string())
+                                .unwrap_or_else(|| "1".to_string())
+                        };
+                        extra.push(format!(
+                            "IDENTITY({},{})",
+                            option_value(|o| match o {
+                                SequenceOptions::StartWith(e, _) => Some(e),
+                                _ => None,
+                            }),
+                            option_value(|o| match o {
+                                SequenceOptions::IncrementBy(e, _) => Some(e),
+                                _ => None,
+                            })
+                        ));
+                    }
+                    // The PostgreSQL capture cannot tell identity columns from plain ones
+                    DatabaseType::PostgreSQL => {}
+                    _ => auto_increment = true,
+                },
+                ColumnOption::Generated {
+                    generation_expr: Some(expr),
+                    generated_as,
+                    generation_expr_mode,
+                    ..
+                } => {
+                    let stored = *generated_as == GeneratedAs::ExpStored
+                        || *generation_expr_mode == Some(GeneratedExpressionMode::Stored);
+                    match self.db_type {
+                        DatabaseType::MySQL | DatabaseType::SQLite => extra.push(
+                            if stored {
+                                "STORED GENERATED"
+                            } else {
+                                "VIRTUAL GENERATED"
+                            }
+                            .to_string(),
+                        ),
+                        DatabaseType::MSSQL => extra.push(format!(
+                            "AS ({}){}",
+                            expr,
+                            if stored { " PERSISTED" } else { "" }
+                        )),
+                        _ => {}
+                    }
+                }
+                ColumnOption::Comment(comment) if self.db_type == DatabaseType::ClickHouse => {
+                    extra.push(comment.clone())
+                }
+                _ => {}
+            }
+        }
+
+        if auto_increment
+            || (self.db_type == DatabaseType::PostgreSQL && type_mapping::is_auto_increment(&col))
+        {
+            extra.insert(0, "auto_increment".to_string());
+        }
+        col.extra = extra.join(" ");
+
+        if let Some(pos) = table.columns.iter().position(|c| c.name == name) {
+            table.columns[pos] = col;
+        } else {
+            table.columns.push(col);
+        }
+    }
+
+    fn add_constraint(&mut self, table: &mut TableDefinition, constraint: &DdlConstraint) {
+        let name_of = |ident: &Option<Ident>| ident.as_ref().map(|i| ident_name(i, &self.db_type));
+        let columns_of = |idents: &[Ident]| -> Vec<String> {
+            idents
+                .iter()
+                .map(|i| ident_name(i, &self.db_type))
+                .collect()
+        };
+        match constraint {
+            DdlConstraint::PrimaryKey { name, columns, .. } => {
+                self.add_primary_key(table, &columns_of(columns), name_of(name))
+            }
+            DdlConstraint::Unique {
+                name,
+                index_name,
+                columns,
+                ..
+            } => self.add_unique(
+                table,
+                &columns_of(columns),
+                name_of(name).or_else(|| name_of(index_name)),
+            ),
+            DdlConstraint::ForeignKey {
+                name,
+                columns,
+                foreign_table,
+                referred_columns,
+                ..
+            } => self.add_foreign_key(
+                table,
+                &columns_of(columns),
+                foreign_table,
+                referred_columns,
+                name_of(name),
+            ),
+            DdlConstraint::Check { name, expr } => self.add_check(table, "", expr, name_of(name)),
+            DdlConstraint::Index {
+                name,
+                index_type,
+                columns,
+                ..
+            } => self.add_index(
+                table,
+                name_of(name),
+                &columns_of(columns),
+                false,
+                index_type.as_ref().map(|t| t.to_string()),
+            ),
+            DdlConstraint::FulltextOrSpatial {
+                fulltext,
+                opt_index_name,
+                columns,
+                ..
+            } => self.add_index(
+                table,
+                name_of(opt_index_name),
+                &columns_of(columns),
+                false,
+                Some(if *fulltext { "FULLTEXT" } else { "SPATIAL" }.to_string()),
+            ),
+        }
+    }
+
+    fn add_primary_key(
+        &mut self,
+        table: &mut TableDefinition,
+        columns: &[String],
+        name: Option<String>,
+    ) {
+        if columns.is_empty() {
+            return;
+        }
+        table.primary_keys = columns
+            .iter()
+            .enumerate()
+            .map(|(idx, column)| PrimaryKey {
+                column_name: column.clone(),
+                ordinal_position: idx as i32 + 1,
+            })
+            .collect();
+        match name {
+            Some(name) => self.primary_key_names.insert(table.name.clone(), name),
+            None => self.primary_key_names.remove(&table.name),
+        };
+    }
+
+    fn add_unique(
+        &mut self,
+        table: &mut TableDefinition,
+        columns: &[String],
+        name: Option<String>,
+    ) {
+        let name = name.unwrap_or_else(|| self.default_name(table, "UNIQUE", columns));
+        table
+            .constraints
+            .extend(columns.iter().map(|column| TableConstraint {
+                name: name.clone(),
+                constraint_type: "UNIQUE".to_string(),
+                column_name: column.clone(),
+                definition: None,
+            }));
+    }
+
+    fn add_foreign_key(
+        &mut self,
+        table: &mut TableDefinition,
+        columns: &[String],
+        foreign_table: &ObjectName,
+        referred_columns: &[Ident],
+        name: Option<String>,
+    ) {
+        let name = name.unwrap_or_else(|| self.default_name(table, "FOREIGN KEY", columns));
+        let referenced = table_name(foreign_table, &self.db_type);
+        let referenced_schema = (self.db_type == DatabaseType::MSSQL)
+            .then(|| referenced.split('.').next().unwrap_or_default().to_string());
+        for (idx, column) in columns.iter().enumerate() {
+            table.foreign_keys.push(ForeignKey {
+                constraint_name: name.clone(),
+                column_name: column.clone(),
+                referenced_table: bare_name(&referenced).to_string(),
+                // Filled from the referenced primary key when the table is finished
+                referenced_column: referred_columns
+                    .get(idx)
+                    .map(|i| ident_name(i, &self.db_type))
+                    .unwrap_or_default(),
+                referenced_schema: referenced_schema.clone(),
+            });
+            table.constraints.push(TableConstraint {
+                name: name.clone(),
+                constraint_type: "FOREIGN KEY".to_string(),
+                column_name: column.clone(),
+                definition: None,
+            });
+        }
+    }
+
+    fn add_check(
+        &mut self,
+        table: &mut TableDefinition,
+        column: &str,
+        expr: &Expr,
+        name: Option<String>,
+    ) {
+        let columns: Vec<String> = Some(column.to_string())
+            .filter(|c| !c.is_empty())
+            .into_iter()
+            .collect();
+        let name = name.unwrap_or_else(|| self.default_name(table, "CHECK", &columns));
+        table.constraints.push(TableConstraint {
+            name,
+            constraint_type: "CHECK".to_string(),
+            column_name: column.to_string(),
+            definition: Some(expr.to_string()),
+        });
+    }
+
+    fn add_index(
+        &mut self,
+        table: &mut TableDefinition,
+        name: Option<String>,
+        columns: &[String],
+        unique: bool,
+        index_type: Option<String>,
+    ) {
+        let name = name.unwrap_or_else(|| self.default_name(table, "INDEX", columns));
+        let index_type = index_type.unwrap_or_else(|| self.default_index_type(false));
+        table.indexes.retain(|idx| idx.name != name);
+        table
+            .indexes
+            .extend(columns.iter().map(|column| TableIndex {
+                name: name.clone(),
+                column_name: column.clone(),
+                non_unique: !unique,
+                index_type: index_type.clone(),
+            }));
+    }
+
+    fn default_index_type(&self, primary: bool) -> String {
+        match self.db_type {
+            DatabaseType::MSSQL if primary => "CLUSTERED",
+            DatabaseType::MSSQL => "NONCLUSTERED",
+            DatabaseType::ClickHouse => "",
+            _ => "BTREE",
+        }
+        .to_string()
+    }
+
+    /// Name the engine would generate for an unnamed constraint or index.
+    fn default_name(&self, table: &TableDefinition, kind: &str, columns: &[String]) -> String {
+        let bare = bare_name(&table.name);
+        let joined = columns.join("_");
+        let count = if kind == "INDEX" {
+            table
+                .indexes
+                .iter()
+                .map(|i| &i.name)
+                .collect::<HashSet<_>>()
+                .len()
+        } else {
+            table
+                .constraints
+                .iter()
+                .filter(|c| is_constraint(c, kind))
+                .map(|c| &c.name)
+                .collect::<HashSet<_>>()
+                .len()
+        } + 1;
+
+        match (&self.db_type, kind) {
+            (DatabaseType::MySQL, "UNIQUE" | "INDEX") => {
+                columns.first().cloned().unwrap_or_default()
+            }
+            (DatabaseType::MySQL, "FOREIGN KEY") => format!("{}_ibfk_{}", bare, count),
+            (DatabaseType::MySQL, _) => format!("{}_chk_{}", bare, count),
+            (DatabaseType::PostgreSQL, "UNIQUE") => format!("{}_{}_key", bare, joined),
+            (DatabaseType::PostgreSQL, "FOREIGN KEY") => format!("{}_{}_fkey", bare, joined),
+            (DatabaseType::PostgreSQL, "INDEX") => format!("{}_{}_idx", bare, joined),
+            (DatabaseType::PostgreSQL, _) if columns.is_empty() => format!("{}_check", bare),
+            (DatabaseType::PostgreSQL, _) => format!("{}_{}_check", bare, joined),
+            (DatabaseType::SQLite, "UNIQUE") => format!("sqlite_autoindex_{}_{}", bare, count),
+            (DatabaseType::SQLite, "FOREIGN KEY") => format!("fk_{}_{}", bare, count - 1),
+            (_, "UNIQUE") => format!("UQ_{}_{}", bare, joined),
+            (_, "FOREIGN KEY") => format!("FK_{}_{}", bare, joined),
+            (_, "INDEX") => format!("IX_{}_{}", bare, joined),
+            _ => format!("CK_{}_{}", bare, count),
+        }
+    }
+
+    /// Adds what the engine derives from the declared keys: key constraints, their
+    /// backing indexes, NOT NULL key columns and implicit foreign key targets.
+    fn finish_table(&self, table: &mut TableDefinition) {
+        let primary: Vec<String> = table
+            .primary_keys
+            .iter()
+            .map(|pk| pk.column_name.clone())
+            .collect();
+        for column in &mut table.columns {
+            if primary.contains(&column.name) {
+                column.is_nullable = false;
+                column.column_key = "PRI".to_string();
+            }
+        }
+
+        for fk in &mut table.foreign_keys {
+            if !fk.referenced_column.is_empty() {
+                continue;
+            }
+            let position = table
+                .constraints
+                .iter()
+                .filter(|c| c.name == fk.constraint_name && is_constraint(c, "FOREIGN KEY"))
+                .position(|c| c.column_name == fk.column_name)
+                .unwrap_or(0);
+            let referenced_pk = self
+                .tables
+                .iter()
+                .find(|t| bare_name(&t.name) == fk.referenced_table)
+                .map(|t| t.primary_keys.clone())
+                .unwrap_or_else(|| table.primary_keys.clone());
+            if let Some(pk) = referenced_pk.get(position) {
+                fk.referenced_column = pk.column_name.clone();
+            }
+        }
+
+        if self.db_type == DatabaseType::ClickHouse {
+            return;
+        }
+
+        if !primary.is_empty() {
+            let name = match self.db_type {
+                // MySQL always names the primary key PRIMARY; SQLite keeps no name
+                DatabaseType::MySQL | DatabaseType::SQLite => "PRIMARY".to_string(),
+                _ => self
+                    .primary_key_names
+                    .get(&table.name)
+                    .cloned()
+                    .unwrap_or_else(|| match self.db_type {
+                        DatabaseType::PostgreSQL => format!("{}_pkey", bare_name(&table.name)),
+                        _ => format!("PK_{}", bare_name(&table.name)),
+                    }),
+            };
+            let key_constraints = primary.iter().map(|column| TableConstraint {
+                name: name.clone(),
+                constraint_type: "PRIMARY KEY".to_string(),
+                column_name: column.clone(),
+                definition: None,
+            });
+            table.constraints.splice(0..0, key_constraints);
+
+            if self.db_type != DatabaseType::SQLite {
+                let key_indexes: Vec<TableIndex> = primary
+                    .iter()
+                    .map(|column| TableIndex {
+                        name: name.clone(),
+                        column_name: column.clone(),
+                        non_unique: false,
+                        index_type: self.default_index_type(true),
+                    })
+                    .collect();
+                table.indexes.splice(0..0, key_indexes);
+            }
+        }
+
+        // Unique constraints are backed by an index; SQLite reports them as constraints only
+        if self.db_type != DatabaseType::SQLite {
+            let mut unique: BTreeMap<String, Vec<String>> = BTreeMap::new();
+            for constraint in table
+                .constraints
+                .iter()
+                .filter(|c| is_constraint(c, "UNIQUE"))
+            {
+                unique
+                    .entry(constraint.name.clone())
+                    .or_default()
+                    .push(constraint.column_name.clone());
+            }
+            for (name, columns) in unique {
+                if !table.indexes.iter().any(|idx| idx.name == name) {
+                    table
+                        .indexes
+                        .extend(columns.into_iter().map(|column| TableIndex {
+                            name: name.clone(),
+                            column_name: column,
+                            non_unique: false,
+                            index_type: self.default_index_type(false),
+                        }));
+                }
+            }
+        }
+
+        if self.db_type == DatabaseType::MySQL {
+            // MySQL lists unique indexes as constraints and indexes every foreign key
+            let mut unique_indexes: BTreeMap<String, Vec<String>> = BTreeMap::new();
+            for idx in table
+                .indexes
+                .iter()
+                .filter(|idx| !idx.non_unique && idx.name != "PRIMARY")
+            {
+                unique_indexes
+                    .entry(idx.name.clone())
+                    .or_default()
+                    .push(idx.column_name.clone());
+            }
+            for (name, columns) in unique_indexes {
+                if !table.constraints.iter().any(|c| c.name == name) {
+                    table
+                        .constraints
+                        .extend(columns.into_iter().map(|column| TableConstraint {
+                            name: name.clone(),
+                            constraint_type: "UNIQUE".to_string(),
+                            column_name: column,
+                            definition: None,
+                        }));
+                }
+            }
+
+            let mut fk_columns: BTreeMap<String, Vec<String>> = BTreeMap::new();
+            for fk in &table.foreign_keys {
+                fk_columns
+                    .entry(fk.constraint_name.clone())
+                    .or_default()
+                    .push(fk.column_name.clone());
+            }
+            for (name, columns) in fk_columns {
+                let mut index_columns: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+                for idx in &table.indexes {
+                    index_columns
+                        .entry(idx.name.as_str())
+                        .or_default()
+                        .push(idx.column_name.as_str());
+                }
+                let covered = index_columns
+                    .values()
+                    .any(|cols| cols.len() >= columns.len() && cols[..columns.len()] == columns);
+                if !covered {
+                    let fk_indexes: Vec<TableIndex> = columns
+                        .into_iter()
+                        .map(|column| TableIndex {
+                            name: name.clone(),
+                            column_name: column,
+                            non_unique: true,
+                            index_type: self.default_index_type(false),
+                        })
+                        .collect();
+                    table.indexes.extend(fk_indexes);
+                }
+            }
+        }
+    }
+
+    fn finish(mut self) -> (Vec<TableDefinition>, Vec<ViewDefinition>, Vec<String>) {
+        let mut tables = std::mem::take(&mut self.tables);
+        // Implicit foreign key targets need every table, so look them up before finishing
+        self.tables = tables.clone();
+        for table in &mut tables {
+            self.finish_table(table);
+        }
+        if self.ignored > 0 {
+            self.warnings.push(format!(
+                "{} statement(s) other than CREATE TABLE, CREATE INDEX, CREATE VIEW and ALTER TABLE were ignored",
+                self.ignored
+            ));
+        }
+        (tables, self.views, self.warnings)
+    }
+}
+
+/// Parses `(source, sql)` scripts, in order, into the schema they declare.
+pub fn parse_schema_ddl(
+    scripts: &[(String, String)],
+    db_type: &DatabaseType,
+    connection_id: &str,
+    database: &str,
+) -> Result<(SchemaSnapshot, Vec<String>), String> {
+    let mut reader = DdlReader::new(db_type);
+    for (source, sql) in scripts {
+        reader.read_script(source, sql);
+    }
+    let (tables, views, warnings) = reader.finish();
+    // An empty desired schema would plan dropping every table
+    if tables.is_empty() {
+        let mut message = "No CREATE TABLE statements were found in the schema files".to_string();
+        if let Some(first) = warnings.first() {
+            message.push_str(&format!(" ({})", first));
+        }
+        return Err(message);
+    }
+
+    let schema_hash = compute_schema_hash(&tables, &views, &[], &[]);
+    Ok((
+        SchemaSnapshot {
+            id: None,
+            connection_id: connection_id.to_string(),
+            database_name: Some(database.to_string()),
+            timestamp: Utc::now(),
+            schema_hash,
+            tables,
+            views,
+            routines: Vec::new(),
+            triggers: Vec::new(),
+        },
+        warnings,
+    ))
+}
+
+// =====================================================
+// COMPARISON
+// =====================================================
+
+/// Rewrites `snapshot` into the spelling both sides of a file-vs-live comparison share:
+/// canonical types and defaults, key markers limited to PRI, and catalog-only details
+/// (PostgreSQL NOT NULL checks, MySQL DEFAULT_GENERATED) dropped.
+pub fn normalize_for_comparison(
+    snapshot: &SchemaSnapshot,
+    db_type: &DatabaseType,
+) -> SchemaSnapshot {
+    let mut normalized = type_mapping::normalize_snapshot(snapshot, db_type, false);
+    for table in &mut normalized.tables {
+        let primary: HashSet<&str> = table
+            .primary_keys
+            .iter()
+            .map(|pk| pk.column_name.as_str())
+            .collect();
+        for column in &mut table.columns {
+            let ty = type_mapping::parse_column_type(&column.column_type, db_type);
+            if matches!(ty, CanonicalType::Unknown { .. }) && *db_type != DatabaseType::ClickHouse {
+                column.column_type = column.column_type.to_lowercase();
+            }
+            column.column_key = if primary.contains(column.name.as_str()) {
+                "PRI".to_string()
+            } else {
+                String::new()
+            };
+        }
+        table
+            .constraints
+            .retain(|c| !(is_constraint(c, "CHECK") && c.name.ends_with("_not_null")));
+    }
+    normalized
+}
+
+fn group_by_name<T>(items: &[T], name_of: &impl Fn(&T) -> String) -> BTreeMap<String, Vec<usize>> {
+    let mut groups: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (idx, item) in items.iter().enumerate() {
+        groups
+            .entry(name_of(item).to_lowercase())
+            .or_default()
+            .push(idx);
+    }
+    groups
+}
+
+/// Renames desired groups the live table does not know to the live name of a
+/// structurally identical group, so engine-generated names do not show up as changes.
+fn adopt_names<T: Clone>(
+    desired: &mut [T],
+    live: &[T],
+    name_of: impl Fn(&T) -> String,
+    signature_of: impl Fn(&[&T]) -> Vec<String>,
+    adopt: impl Fn(&mut T, &T),
+) {
+    let desired_groups = group_by_name(desired, &name_of);
+    let live_groups = group_by_name(live, &name_of);
+    let mut claimed: HashSet<String> = HashSet::new();
+
+    for (name, members) in &desired_groups {
+        if live_groups.contains_key(name) {
+            continue;
+        }
+        let signature = signature_of(&members.iter().map(|&i| &desired[i]).collect::<Vec<_>>());
+        let matched = live_groups.iter().find(|(live_name, live_members)| {
+            !desired_groups.contains_key(*live_name)
+                && !claimed.contains(*live_name)
+                && signature_of(&live_members.iter().map(|&i| &live[i]).collect::<Vec<_>>())
+                    == signature
+        });
+        if let Some((live_name, live_members)) = matched {
+            claimed.insert(live_name.clone());
+            let template = live[live_members[0]].clone();
+            for &idx in members {
+                adopt(&mut desired[idx], &template);
+            }
+        }
+    }
+}
+
+/// Comparable form of a CHECK expression across catalogs (`([qty]>(0))` vs `qty > 0`).
+fn loose_definition(definition: &str) -> String {
+    definition
+        .chars()
+        .filter(|c| !c.is_whitespace() && !matches!(c, '(' | ')' | '[' | ']' | '`' | '"'))
+        .collect::<String>()
+        .to_lowercase()
+}
+
+fn adopt_live_names(desired: &mut TableDefinition, live: &TableDefinition) {
+    adopt_names(
+        &mut desired.indexes,
+        &live.indexes,
+        |idx| idx.name.clone(),
+        |group| {
+            let mut signature: Vec<String> = group
+                .iter()
+                .map(|idx| idx.column_name.to_lowercase())
+                .collect();
+            signature.push(group.iter().all(|idx| idx.non_unique).to_string());
+            signature
+        },
+        |idx, live| {
+            idx.name = live.name.clone();
+            idx.index_type = live.index_type.clone();
+        },
+    );
+    adopt_names(
+        &mut desired.foreign_keys,
+        &live.foreign_keys,
+        |fk| fk.constraint_name.clone(),
+        |group| {
+            let mut signature = vec![group
+                .first()
+                .map(|fk| fk.referenced_table.to_lowercase())
+                .unwrap_or_default()];
+            signature.extend(
+                group.iter().map(|fk| {
+                    format!("{}->{}", fk.column_name, fk.referenced_column).to_lowercase()
+                }),
+            );
+            signature
+        },
+        |fk, live| fk.constraint_name = live.constraint_name.clone(),
+    );
+    adopt_names(
+        &mut desired.constraints,
+        &live.constraints,
+        |c| c.name.clone(),
+        |group| {
+            let mut signature = vec![group
+                .first()
+                .map(|c| c.constraint_type.to_uppercase())
+                .unwrap_or_default()];
+            let mut columns: Vec<String> = group
+                .iter()
+                .map(|c| c.column_name.to_lowercase())
+                .filter(|c| !c.is_empty())
+                .collect();
+            columns.sort();
+            signature.extend(columns);
+            if let Some(definition) = group.first().and_then(|c| c.definition.as_deref()) {
+                signature.push(loose_definition(definition));
+            }
+            signature
+        },
+        |c, live| c.name = live.name.clone(),
+    );
+
+    // Catalogs without CHECK bodies (or with their own formatting of them) are the
+    // reference for a check that exists on both sides under the same name
+    let live_checks = group_by_name(&live.constraints, &|c: &TableConstraint| c.name.clone());
+    let mut replaced = HashSet::new();
+    let mut constraints = Vec::new();
+    for constraint in std::mem::take(&mut desired.constraints) {
+        let key = constraint.name.to_lowercase();
+        let live_group: Vec<&TableConstraint> = live_checks
+            .get(&key)
+            .map(|members| members.iter().map(|&i| &live.constraints[i]).collect())
+            .unwrap_or_default();
+        let same_check = is_constraint(&constraint, "CHECK")
+            && live_group.first().is_some_and(|live| {
+                is_constraint(live, "CHECK")
+                    && match (&live.definition, &constraint.definition) {
+                        (Some(live_def), Some(def)) => {
+                            loose_definition(live_def) == loose_definition(def)
+                        }
+                        (None, _) => true,
+                        (Some(_), None) => false,
+                    }
+            });
+        if !same_check {
+            constraints.push(constraint);
+        } else if replaced.insert(key) {
+            constraints.extend(live_group.into_iter().cloned());
+        }
+    }
+    desired.constraints = constraints;
+}
+
+/// Diff that takes `live` to the schema declared in `desired`. Routines and triggers
+/// are never declared by DDL files, and live views are only compared when the files
+/// declare views, so neither is planned for removal. Columns are compared in their
+/// normalised form but keep the types each side declares.
+pub fn compare_with_live(
+    desired: &SchemaSnapshot,
+    live: &SchemaSnapshot,
+    db_type: &DatabaseType,
+    warnings: &mut Vec<String>,
+) -> SchemaDiff {
+    let declared_live = live;
+    let declared_desired = desired;
+    let mut live = normalize_for_comparison(live, db_type);
+    let mut desired = normalize_for_comparison(desired, db_type);
+
+    if !live.routines.is_empty() || !live.triggers.is_empty() {
+        warnings.push("Routines and triggers are not compared with schema files".to_string());
+        live.routines.clear();
+        live.triggers.clear();
+    }
+    if desired.views.is_empty() && !live.views.is_empty() {
+        warnings.push(
+            "The schema files declare no views; live views are left out of the comparison"
+                .to_string(),
+        );
+        live.views.clear();
+    }
+
+    for table in &mut desired.tables {
+        if let Some(live_table) = live.tables.iter().find(|t| t.name == table.name) {
+            adopt_live_names(table, live_table);
+        }
+    }
+    let mut diff = compare_schemas(&live, &desired);
+    type_mapping::restore_declared_types(&mut diff, declared_live, declared_desired);
+    diff
+}
+
+pub async fn compare_schema_file(
+    app_state: &AppState,
+    request: &SchemaFileCompareRequest,
+) -> Result<SchemaFileComparison, String> {
+    let db_type = {
+        let guard = app_state.active_db_type.lock().await;
+        guard.clone()
+    };
+    if db_type == DatabaseType::Disconnected {
+        return Err("No connection established".to_string());
+    }
+
+    let scripts = read_schema_files(&request.path)?;
+    let (desired, mut warnings) = parse_schema_ddl(
+        &scripts,
+        &db_type,
+        &request.connection_id,
+        &request.database,
+    )?;
+    let live = crate::schema_tracker::commands::capture_live_snapshot(
+        app_state,
+        &request.connection_id,
+        &request.database,
+    )
+    .await?;
+
+    let diff = compare_with_live(&desired, &live, &db_type, &mut warnings);
+    let plan = generate_migration_plan(&diff, &db_type, None);
+    Ok(SchemaFileComparison {
+        files: scripts.into_iter().map(|(name, _)| name).collect(),
+        desired,
+        diff,
+        plan,
+        warnings,
+    })
+}
+
+#[cfg(test)]
+mod tests;
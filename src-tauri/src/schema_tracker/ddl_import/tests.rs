@@ -0,0 +1,291 @@
+use super::*;
+
+fn parse(sql: &str, db_type: DatabaseType) -> (SchemaSnapshot, Vec<String>) {
+    parse_schema_ddl(
+        &[("schema.sql".to_string(), sql.to_string())],
+        &db_type,
+        "conn-1",
+        "app",
+    )
+    .unwrap()
+}
+
+fn table<'a>(snapshot: &'a SchemaSnapshot, name: &str) -> &'a TableDefinition {
+    snapshot.tables.iter().find(|t| t.name == name).unwrap()
+}
+
+fn index_names(table: &TableDefinition) -> Vec<&str> {
+    let mut names: Vec<&str> = table.indexes.iter().map(|i| i.name.as_str()).collect();
+    names.dedup();
+    names
+}
+
+fn live_column(name: &str, column_type: &str, nullable: bool, key: &str) -> ColumnSchema {
+    ColumnSchema {
+        name: name.to_string(),
+        data_type: column_type
+            .split('(')
+            .next()
+            .unwrap_or_default()
+            .to_string(),
+        column_type: column_type.to_string(),
+        is_nullable: nullable,
+        column_key: key.to_string(),
+        column_default: None,
+        extra: String::new(),
+        collation: None,
+    }
+}
+
+fn live_index(name: &str, column: &str) -> TableIndex {
+    TableIndex {
+        name: name.to_string(),
+        column_name: column.to_string(),
+        non_unique: false,
+        index_type: "BTREE".to_string(),
+    }
+}
+
+fn live_constraint(name: &str, kind: &str, column: &str) -> TableConstraint {
+    TableConstraint {
+        name: name.to_string(),
+        constraint_type: kind.to_string(),
+        column_name: column.to_string(),
+        definition: None,
+    }
+}
+
+/// `users` as the MySQL capture reports it
+fn live_mysql_users() -> SchemaSnapshot {
+    let mut id = live_column("id", "int", false, "PRI");
+    id.extra = "auto_increment".to_string();
+    let mut status = live_column("status", "varchar(20)", false, "");
+    status.column_default = Some("new".to_string());
+    let mut balance = live_column("balance", "decimal(10,2)", true, "");
+    balance.column_default = Some("0.00".to_string());
+
+    SchemaSnapshot {
+        id: None,
+        connection_id: "conn-1".to_string(),
+        database_name: Some("app".to_string()),
+        timestamp: Utc::now(),
+        schema_hash: "live".to_string(),
+        tables: vec![TableDefinition {
+            name: "users".to_string(),
+            columns: vec![
+                id,
+                live_column("email", "varchar(255)", false, "UNI"),
+                status,
+                balance,
+            ],
+            indexes: vec![
+                live_index("PRIMARY", "id"),
+                live_index("users_email_unique", "email"),
+            ],
+            primary_keys: vec![PrimaryKey {
+                column_name: "id".to_string(),
+                ordinal_position: 1,
+            }],
+            constraints: vec![
+                live_constraint("PRIMARY", "PRIMARY KEY", "id"),
+                live_constraint("users_email_unique", "UNIQUE", "email"),
+            ],
+            ..Default::default()
+        }],
+        views: vec![ViewDefinition {
+            name: "active_users".to_string(),
+            definition: "select 1".to_string(),
+        }],
+        routines: vec![],
+        triggers: vec![],
+    }
+}
+
+const MYSQL_USERS: &str = "CREATE TABLE `users` (
+  `id` INT NOT NULL AUTO_INCREMENT,
+  `email` VARCHAR(255) NOT NULL UNIQUE,
+  `status` VARCHAR(20) NOT NULL DEFAULT 'new',
+  `balance` DECIMAL(10,2) DEFAULT '0.00',
+  PRIMARY KEY (`id`)
+) ENGINE=InnoDB;";
+
+#[test]
+fn mysql_ddl_gets_implicit_keys_and_indexes() {
+    let (snapshot, warnings) = parse(
+        "CREATE TABLE users (
+            id INT NOT NULL AUTO_INCREMENT,
+            email VARCHAR(255) NOT NULL,
+            PRIMARY KEY (id),
+            UNIQUE KEY uq_email (email)
+        ) ENGINE=InnoDB;
+        CREATE TABLE orders (
+            id INT AUTO_INCREMENT PRIMARY KEY,
+            user_id INT NOT NULL,
+            total DECIMAL(10,2),
+            CONSTRAINT fk_orders_user FOREIGN KEY (user_id) REFERENCES users (id)
+        );
+        CREATE INDEX idx_orders_total ON orders (total);
+        INSERT INTO users (email) VALUES ('a@example.com');",
+        DatabaseType::MySQL,
+    );
+
+    let users = table(&snapshot, "users");
+    assert_eq!(users.columns[0].extra, "auto_increment");
+    assert_eq!(users.columns[0].column_key, "PRI");
+    assert_eq!(index_names(users), vec!["PRIMARY", "uq_email"]);
+    assert!(users
+        .constraints
+        .iter()
+        .any(|c| c.name == "uq_email" && c.constraint_type == "UNIQUE"));
+
+    let orders = table(&snapshot, "orders");
+    assert!(!orders.columns[0].is_nullable);
+    assert_eq!(orders.foreign_keys[0].referenced_table, "users");
+    assert_eq!(orders.foreign_keys[0].referenced_column, "id");
+    // The foreign key gets the index MySQL creates for it
+    assert_eq!(
+        index_names(orders),
+        vec!["PRIMARY", "idx_orders_total", "fk_orders_user"]
+    );
+
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].starts_with("1 statement(s)"));
+}
+
+#[test]
+fn pg_dump_constraints_and_sequences_are_applied() {
+    let (snapshot, warnings) = parse(
+        "SET statement_timeout = 0;
+        CREATE TABLE public.users (
+            id integer NOT NULL,
+            Email character varying(255) NOT NULL
+        );
+        ALTER TABLE ONLY public.users ALTER COLUMN id SET DEFAULT nextval('public.users_id_seq'::regclass);
+        ALTER TABLE ONLY public.users ADD CONSTRAINT users_pkey PRIMARY KEY (id);
+        CREATE UNIQUE INDEX users_email_idx ON public.users USING btree (email);",
+        DatabaseType::PostgreSQL,
+    );
+
+    let users = table(&snapshot, "users");
+    assert_eq!(users.columns[1].name, "email");
+    assert_eq!(users.columns[0].extra, "auto_increment");
+    assert_eq!(users.constraints[0].name, "users_pkey");
+    assert_eq!(index_names(users), vec!["users_pkey", "users_email_idx"]);
+    assert!(!users.indexes[1].non_unique);
+    assert!(warnings.iter().any(|w| w.contains("were ignored")));
+}
+
+#[test]
+fn mssql_identity_and_clustering_are_understood() {
+    let (snapshot, _) = parse(
+        "CREATE TABLE orders (
+            id INT IDENTITY(100, 5) NOT NULL,
+            code NVARCHAR(20) NOT NULL,
+            CONSTRAINT PK_orders PRIMARY KEY CLUSTERED (id)
+        );
+        GO
+        CREATE NONCLUSTERED INDEX IX_orders_code ON dbo.orders (code);
+        GO",
+        DatabaseType::MSSQL,
+    );
+
+    let orders = table(&snapshot, "dbo.orders");
+    assert_eq!(orders.columns[0].extra, "IDENTITY(100,5)");
+    assert_eq!(index_names(orders), vec!["PK_orders", "IX_orders_code"]);
+    assert_eq!(orders.indexes[0].index_type, "CLUSTERED");
+    assert_eq!(orders.indexes[1].index_type, "NONCLUSTERED");
+}
+
+#[test]
+fn matching_live_schema_has_no_changes() {
+    let (desired, mut warnings) = parse(MYSQL_USERS, DatabaseType::MySQL);
+    let diff = compare_with_live(
+        &desired,
+        &live_mysql_users(),
+        &DatabaseType::MySQL,
+        &mut warnings,
+    );
+
+    assert!(!diff.has_changes(), "{:?}", diff);
+    // Views are not declared, so the live view is not planned for removal
+    assert!(warnings.iter().any(|w| w.contains("no views")));
+}
+
+#[test]
+fn new_column_in_file_is_planned() {
+    let sql = MYSQL_USERS.replace(
+        "PRIMARY KEY (`id`)",
+        "`age` INT NULL,\n  PRIMARY KEY (`id`)",
+    );
+    let (desired, mut warnings) = parse(&sql, DatabaseType::MySQL);
+    let diff = compare_with_live(
+        &desired,
+        &live_mysql_users(),
+        &DatabaseType::MySQL,
+        &mut warnings,
+    );
+
+    assert_eq!(diff.modified_tables.len(), 1);
+    assert_eq!(diff.modified_tables[0].new_columns[0].name, "age");
+    let plan = generate_migration_plan(&diff, &DatabaseType::MySQL, None);
+    assert!(plan.script.contains("ADD COLUMN"));
+    assert!(!plan.script.contains("DROP"));
+}
+
+#[test]
+fn unparsable_statements_warn_and_empty_schemas_fail() {
+    let (snapshot, warnings) = parse(
+        "CREATE TABLE t (id INT);\nCREATE TABLE broken (id INT,;",
+        DatabaseType::MySQL,
+    );
+    assert_eq!(snapshot.tables.len(), 1);
+    assert!(warnings[0].starts_with("schema.sql:2:"));
+
+    let empty = parse_schema_ddl(
+        &[("a.sql".to_string(), "SELECT 1;".to_string())],
+        &DatabaseType::MySQL,
+        "conn-1",
+        "app",
+    );
+    assert!(empty.is_err());
+}
+
+#[test]
+fn identity_rewrite_leaves_strings_and_names_alone() {
+    assert_eq!(
+        rewrite_mssql_identity("id INT IDENTITY(1,1), note VARCHAR(9) DEFAULT 'identity'"),
+        "id INT GENERATED BY DEFAULT AS IDENTITY (INCREMENT BY 1 START WITH 1), note VARCHAR(9) DEFAULT 'identity'"
+    );
+    assert_eq!(
+        rewrite_mssql_identity("identity_id INT IDENTITY"),
+        "identity_id INT GENERATED BY DEFAULT AS IDENTITY"
+    );
+}
+
+#[test]
+fn plan_keeps_declared_types_of_normalised_columns() {
+    let sql = MYSQL_USERS.replace(
+        "PRIMARY KEY (`id`)",
+        "`bio` MEDIUMTEXT NOT NULL,\n  `born` YEAR NULL,\n  PRIMARY KEY (`id`)",
+    );
+    let (desired, mut warnings) = parse(&sql, DatabaseType::MySQL);
+    let mut live = live_mysql_users();
+    live.tables[0]
+        .columns
+        .push(live_column("bio", "mediumtext", true, ""));
+    let diff = compare_with_live(&desired, &live, &DatabaseType::MySQL, &mut warnings);
+
+    let modified = &diff.modified_tables[0];
+    assert_eq!(modified.modified_columns[0].column_name, "bio");
+    assert_eq!(modified.new_columns[0].name, "born");
+    let plan = generate_migration_plan(&diff, &DatabaseType::MySQL, None)
+        .script
+        .to_lowercase();
+    assert!(plan.contains("mediumtext"), "{}", plan);
+    assert!(plan.contains("year"), "{}", plan);
+    assert!(
+        !plan.contains("longtext") && !plan.contains("smallint"),
+        "{}",
+        plan
+    );
+}
@@ -1,19 +1,26 @@
 use crate::db_types::*;
 use crate::schema_tracker::models::*;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 pub fn compare_schemas(old: &SchemaSnapshot, new: &SchemaSnapshot) -> SchemaDiff {
-    let mut diff = SchemaDiff {
-        new_tables: vec![],
-        dropped_tables: vec![],
-        modified_tables: vec![],
-    };
-
-    let old_tables: HashMap<String, &TableDefinition> =
-        old.tables.iter().map(|t| (t.name.clone(), t)).collect();
-    let new_tables: HashMap<String, &TableDefinition> =
-        new.tables.iter().map(|t| (t.name.clone(), t)).collect();
+    let mut diff = SchemaDiff::default();
+
+    // The migration history table is bookkeeping, not schema drift
+    let tracked =
+        |t: &&TableDefinition| !crate::schema_tracker::apply::is_migration_history_table(&t.name);
+    let old_tables: HashMap<String, &TableDefinition> = old
+        .tables
+        .iter()
+        .filter(tracked)
+        .map(|t| (t.name.clone(), t))
+        .collect();
+    let new_tables: HashMap<String, &TableDefinition> = new
+        .tables
+        .iter()
+        .filter(tracked)
+        .map(|t| (t.name.clone(), t))
+        .collect();
 
     // 1. Detect New and Modified Tables
     for (name, new_def) in &new_tables {
@@ -36,9 +43,221 @@ pub fn compare_schemas(old: &SchemaSnapshot, new: &SchemaSnapshot) -> SchemaDiff
         }
     }
 
+    // 3. Dropped/new pairs that look like renames
+    diff.renamed_tables = detect_table_renames(&diff.dropped_tables, &diff.new_tables);
+
+    // 4. Views, routines and triggers (snapshot order keeps reports stable)
+    compare_views(old, new, &mut diff);
+    compare_routines(old, new, &mut diff);
+    compare_triggers(old, new, &mut diff);
+
     diff
 }
 
+/// Builds the diff that undoes `diff`, used to generate rollback scripts.
+pub fn reverse_diff(diff: &SchemaDiff) -> SchemaDiff {
+    SchemaDiff {
+        new_tables: diff.dropped_tables.clone(),
+        dropped_tables: diff.new_tables.clone(),
+        modified_tables: diff
+            .modified_tables
+            .iter()
+            .map(reverse_table_diff)
+            .collect(),
+        new_views: diff.dropped_views.clone(),
+        dropped_views: diff.new_views.clone(),
+        modified_views: diff
+            .modified_views
+            .iter()
+            .map(|view| ViewDiff {
+                view_name: view.view_name.clone(),
+                old_definition: view.new_definition.clone(),
+                new_definition: view.old_definition.clone(),
+            })
+            .collect(),
+        new_routines: diff.dropped_routines.clone(),
+        dropped_routines: diff.new_routines.clone(),
+        new_triggers: diff.dropped_triggers.clone(),
+        dropped_triggers: diff.new_triggers.clone(),
+        renamed_tables: diff
+            .renamed_tables
+            .iter()
+            .map(|rename| TableRename {
+                old_name: rename.new_name.clone(),
+                new_name: rename.old_name.clone(),
+                confidence: rename.confidence,
+                confirmed: rename.confirmed,
+                changes: rename.changes.as_ref().map(|changes| TableDiff {
+                    table_name: rename.old_name.clone(),
+                    ..reverse_table_diff(changes)
+                }),
+            })
+            .collect(),
+    }
+}
+
+fn reverse_table_diff(diff: &TableDiff) -> TableDiff {
+    TableDiff {
+        table_name: diff.table_name.clone(),
+        new_columns: diff.dropped_columns.clone(),
+        dropped_columns: diff.new_columns.clone(),
+        modified_columns: diff
+            .modified_columns
+            .iter()
+            .map(|col| ColumnDiff {
+                column_name: col.column_name.clone(),
+                old_column: col.new_column.clone(),
+                new_column: col.old_column.clone(),
+                changes: col.changes.iter().map(reverse_change).collect(),
+            })
+            .collect(),
+        new_indexes: diff.dropped_indexes.clone(),
+        dropped_indexes: diff.new_indexes.clone(),
+        new_foreign_keys: diff.dropped_foreign_keys.clone(),
+        dropped_foreign_keys: diff.new_foreign_keys.clone(),
+        new_constraints: diff.dropped_constraints.clone(),
+        dropped_constraints: diff.new_constraints.clone(),
+        renamed_columns: diff
+            .renamed_columns
+            .iter()
+            .map(|rename| ColumnRename {
+                old_column: rename.new_column.clone(),
+                new_column: rename.old_column.clone(),
+                confidence: rename.confidence,
+                confirmed: rename.confirmed,
+                changes: rename.changes.iter().map(reverse_change).collect(),
+            })
+            .collect(),
+        row_count_change: diff.row_count_change.map(|change| -change),
+    }
+}
+
+fn reverse_change(change: &DiffType) -> DiffType {
+    match change {
+        DiffType::TypeChanged { old, new } => DiffType::TypeChanged {
+            old: new.clone(),
+            new: old.clone(),
+        },
+        DiffType::NullableChanged { old, new } => DiffType::NullableChanged {
+            old: *new,
+            new: *old,
+        },
+        DiffType::DefaultChanged { old, new } => DiffType::DefaultChanged {
+            old: new.clone(),
+            new: old.clone(),
+        },
+        other => other.clone(),
+    }
+}
+
+fn normalize_definition(definition: &str) -> String {
+    definition
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_end_matches(';')
+        .trim_end()
+        .to_string()
+}
+
+fn compare_views(old: &SchemaSnapshot, new: &SchemaSnapshot, diff: &mut SchemaDiff) {
+    let old_views: HashMap<&str, &ViewDefinition> =
+        old.views.iter().map(|v| (v.name.as_str(), v)).collect();
+    let new_names: HashSet<&str> = new.views.iter().map(|v| v.name.as_str()).collect();
+
+    for view in &new.views {
+        match old_views.get(view.name.as_str()) {
+            Some(old_view) => {
+                if normalize_definition(&old_view.definition)
+                    != normalize_definition(&view.definition)
+                {
+                    diff.modified_views.push(ViewDiff {
+                        view_name: view.name.clone(),
+                        old_definition: old_view.definition.clone(),
+                        new_definition: view.definition.clone(),
+                    });
+                }
+            }
+            None => diff.new_views.push(view.clone()),
+        }
+    }
+
+    for view in &old.views {
+        if !new_names.contains(view.name.as_str()) {
+            diff.dropped_views.push(view.clone());
+        }
+    }
+}
+
+/// Bodies are only compared when both snapshots captured them; older snapshots
+/// carry names only.
+fn bodies_differ(old: Option<&String>, new: Option<&String>) -> bool {
+    match (old, new) {
+        (Some(old), Some(new)) => normalize_definition(old) != normalize_definition(new),
+        _ => false,
+    }
+}
+
+fn compare_routines(old: &SchemaSnapshot, new: &SchemaSnapshot, diff: &mut SchemaDiff) {
+    let old_routines: HashMap<&str, &RoutineInfo> =
+        old.routines.iter().map(|r| (r.name.as_str(), r)).collect();
+    let new_names: HashSet<&str> = new.routines.iter().map(|r| r.name.as_str()).collect();
+
+    for routine in &new.routines {
+        match old_routines.get(routine.name.as_str()) {
+            Some(old_routine) => {
+                // A changed body means the routine was redefined
+                if bodies_differ(old_routine.definition.as_ref(), routine.definition.as_ref()) {
+                    diff.dropped_routines.push((*old_routine).clone());
+                    diff.new_routines.push(routine.clone());
+                }
+            }
+            None => diff.new_routines.push(routine.clone()),
+        }
+    }
+    for routine in &old.routines {
+        if !new_names.contains(routine.name.as_str()) {
+            diff.dropped_routines.push(routine.clone());
+        }
+    }
+}
+
+fn trigger_key(trigger: &TriggerInfo) -> (String, String) {
+    (
+        normalize_index_token(&trigger.table_name),
+        normalize_index_token(&trigger.name),
+    )
+}
+
+fn compare_triggers(old: &SchemaSnapshot, new: &SchemaSnapshot, diff: &mut SchemaDiff) {
+    let old_triggers: HashMap<(String, String), &TriggerInfo> =
+        old.triggers.iter().map(|t| (trigger_key(t), t)).collect();
+    let new_triggers: HashMap<(String, String), &TriggerInfo> =
+        new.triggers.iter().map(|t| (trigger_key(t), t)).collect();
+
+    for trigger in &new.triggers {
+        match old_triggers.get(&trigger_key(trigger)) {
+            Some(old_trigger) => {
+                // A different timing, event or body means the trigger was redefined
+                if !old_trigger.timing.eq_ignore_ascii_case(&trigger.timing)
+                    || !old_trigger.event.eq_ignore_ascii_case(&trigger.event)
+                    || bodies_differ(old_trigger.definition.as_ref(), trigger.definition.as_ref())
+                {
+                    diff.dropped_triggers.push((*old_trigger).clone());
+                    diff.new_triggers.push(trigger.clone());
+                }
+            }
+            None => diff.new_triggers.push(trigger.clone()),
+        }
+    }
+
+    for trigger in &old.triggers {
+        if !new_triggers.contains_key(&trigger_key(trigger)) {
+            diff.dropped_triggers.push(trigger.clone());
+        }
+    }
+}
+
 fn compare_tables(old: &TableDefinition, new: &TableDefinition) -> Option<TableDiff> {
     let row_count_change = match (old.row_count, new.row_count) {
         (Some(old_count), Some(new_count)) => Some(new_count as i64 - old_count as i64),
@@ -52,6 +271,11 @@ fn compare_tables(old: &TableDefinition, new: &TableDefinition) -> Option<TableD
         modified_columns: vec![],
         new_indexes: vec![],
         dropped_indexes: vec![],
+        new_foreign_keys: vec![],
+        dropped_foreign_keys: vec![],
+        new_constraints: vec![],
+        dropped_constraints: vec![],
+        renamed_columns: vec![],
         row_count_change,
     };
 
@@ -84,6 +308,13 @@ fn compare_tables(old: &TableDefinition, new: &TableDefinition) -> Option<TableD
         }
     }
 
+    table_diff.renamed_columns = detect_column_renames(
+        old,
+        new,
+        &table_diff.dropped_columns,
+        &table_diff.new_columns,
+    );
+
     // Indexes
     // Compare by name first, then by structure (columns/type/unique) to detect renames
     // and modifications under the same name.
@@ -152,11 +383,30 @@ fn compare_tables(old: &TableDefinition, new: &TableDefinition) -> Option<TableD
         }
     }
 
+    // Foreign keys and constraints are matched by name; a changed definition is drop+create.
+    let (new_fks, dropped_fks) = diff_named_groups(
+        group_foreign_keys(&old.foreign_keys),
+        group_foreign_keys(&new.foreign_keys),
+    );
+    table_diff.new_foreign_keys = new_fks;
+    table_diff.dropped_foreign_keys = dropped_fks;
+
+    let (new_constraints, dropped_constraints) = diff_named_groups(
+        group_constraints(&old.constraints),
+        group_constraints(&new.constraints),
+    );
+    table_diff.new_constraints = new_constraints;
+    table_diff.dropped_constraints = dropped_constraints;
+
     if table_diff.new_columns.is_empty()
         && table_diff.dropped_columns.is_empty()
         && table_diff.modified_columns.is_empty()
         && table_diff.new_indexes.is_empty()
         && table_diff.dropped_indexes.is_empty()
+        && table_diff.new_foreign_keys.is_empty()
+        && table_diff.dropped_foreign_keys.is_empty()
+        && table_diff.new_constraints.is_empty()
+        && table_diff.dropped_constraints.is_empty()
         && (table_diff.row_count_change.is_none() || table_diff.row_count_change == Some(0))
     {
         return None;
@@ -271,6 +521,311 @@ fn build_index_profiles(indexes: &[TableIndex]) -> HashMap<String, IndexProfile>
     profiles
 }
 
+// ============================================================================
+// RENAME DETECTION
+// ============================================================================
+
+/// Pairs scoring below this are reported as a plain drop and add.
+const RENAME_CONFIDENCE_THRESHOLD: f64 = 0.6;
+
+fn round_confidence(score: f64) -> f64 {
+    (score.clamp(0.0, 1.0) * 100.0).round() / 100.0
+}
+
+/// Greedily pairs candidates by descending score so every name is used at most once.
+fn pick_rename_pairs(mut scored: Vec<(f64, usize, usize)>) -> Vec<(f64, usize, usize)> {
+    scored.retain(|(score, _, _)| *score >= RENAME_CONFIDENCE_THRESHOLD);
+    scored.sort_by(|a, b| {
+        b.0.partial_cmp(&a.0)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.1.cmp(&b.1))
+            .then(a.2.cmp(&b.2))
+    });
+
+    let mut used_old = HashSet::new();
+    let mut used_new = HashSet::new();
+    scored
+        .into_iter()
+        .filter(|(_, old_idx, new_idx)| {
+            if used_old.contains(old_idx) || used_new.contains(new_idx) {
+                return false;
+            }
+            used_old.insert(*old_idx);
+            used_new.insert(*new_idx);
+            true
+        })
+        .collect()
+}
+
+/// Shape of the indexes a column belongs to, independent of index and column names.
+fn column_index_membership(table: &TableDefinition, column: &str) -> Vec<(bool, usize)> {
+    let mut membership: Vec<(bool, usize)> = build_index_profiles(&table.indexes)
+        .into_values()
+        .filter(|profile| {
+            profile
+                .signature
+                .columns
+                .iter()
+                .any(|c| c.eq_ignore_ascii_case(column))
+        })
+        .map(|profile| {
+            (
+                profile.signature.non_unique,
+                profile.signature.columns.len(),
+            )
+        })
+        .collect();
+    membership.sort();
+    membership
+}
+
+fn column_position(table: &TableDefinition, column: &str) -> Option<usize> {
+    table.columns.iter().position(|c| c.name == column)
+}
+
+/// Scores how likely `new_col` is `old_col` under a new name: type, nullability,
+/// default, ordinal position and index membership each contribute.
+fn score_column_rename(
+    old_table: &TableDefinition,
+    new_table: &TableDefinition,
+    old_col: &ColumnSchema,
+    new_col: &ColumnSchema,
+) -> f64 {
+    let mut score = if old_col
+        .column_type
+        .eq_ignore_ascii_case(&new_col.column_type)
+    {
+        0.4
+    } else if old_col.data_type.eq_ignore_ascii_case(&new_col.data_type) {
+        0.2
+    } else {
+        // Different base types are never treated as a rename
+        return 0.0;
+    };
+
+    if old_col.is_nullable == new_col.is_nullable {
+        score += 0.15;
+    }
+    if old_col.column_default == new_col.column_default {
+        score += 0.15;
+    }
+
+    // Type, nullability and default match plenty of unrelated columns, so a rename
+    // also needs the same place in the table or the same key/index role
+    let mut structural = false;
+    match (
+        column_position(old_table, &old_col.name),
+        column_position(new_table, &new_col.name),
+    ) {
+        (Some(old_pos), Some(new_pos)) if old_pos == new_pos => {
+            score += 0.15;
+            structural = true;
+        }
+        (Some(old_pos), Some(new_pos)) if old_pos.abs_diff(new_pos) == 1 => {
+            score += 0.075;
+            structural = true;
+        }
+        _ => {}
+    }
+
+    let membership = column_index_membership(old_table, &old_col.name);
+    if old_col.column_key == new_col.column_key
+        && membership == column_index_membership(new_table, &new_col.name)
+    {
+        score += 0.15;
+        structural |= !membership.is_empty() || !old_col.column_key.is_empty();
+    }
+
+    if structural {
+        score
+    } else {
+        0.0
+    }
+}
+
+fn detect_column_renames(
+    old_table: &TableDefinition,
+    new_table: &TableDefinition,
+    dropped: &[ColumnSchema],
+    added: &[ColumnSchema],
+) -> Vec<ColumnRename> {
+    let mut scored = Vec::new();
+    for (old_idx, old_col) in dropped.iter().enumerate() {
+        for (new_idx, new_col) in added.iter().enumerate() {
+            let score = score_column_rename(old_table, new_table, old_col, new_col);
+            scored.push((score, old_idx, new_idx));
+        }
+    }
+
+    pick_rename_pairs(scored)
+        .into_iter()
+        .map(|(score, old_idx, new_idx)| ColumnRename {
+            old_column: dropped[old_idx].clone(),
+            new_column: added[new_idx].clone(),
+            confidence: round_confidence(score),
+            confirmed: false,
+            changes: compare_columns(&dropped[old_idx], &added[new_idx]),
+        })
+        .collect()
+}
+
+/// Scores how likely `new_table` is `old_table` under a new name: mostly the overlap of
+/// (column, type) pairs, plus matching row counts and index shapes.
+fn score_table_rename(old_table: &TableDefinition, new_table: &TableDefinition) -> f64 {
+    let column_set = |table: &TableDefinition| -> HashSet<(String, String)> {
+        table
+            .columns
+            .iter()
+            .map(|c| {
+                (
+                    normalize_index_token(&c.name),
+                    normalize_index_token(&c.column_type),
+                )
+            })
+            .collect()
+    };
+    let old_columns = column_set(old_table);
+    let new_columns = column_set(new_table);
+    let union = old_columns.union(&new_columns).count();
+    if union == 0 {
+        return 0.0;
+    }
+    let mut score = 0.7 * old_columns.intersection(&new_columns).count() as f64 / union as f64;
+
+    score += match (old_table.row_count, new_table.row_count) {
+        (Some(old_rows), Some(new_rows)) if old_rows == new_rows => 0.15,
+        (Some(old_rows), Some(new_rows)) if old_rows.abs_diff(new_rows) * 10 <= old_rows => 0.1,
+        (None, _) | (_, None) => 0.075,
+        _ => 0.0,
+    };
+
+    let index_shapes = |table: &TableDefinition| -> Vec<IndexSignature> {
+        let mut shapes: Vec<IndexSignature> = build_index_profiles(&table.indexes)
+            .into_values()
+            .map(|profile| profile.signature)
+            .collect();
+        shapes.sort_by(|a, b| a.columns.cmp(&b.columns));
+        shapes
+    };
+    if index_shapes(old_table) == index_shapes(new_table) {
+        score += 0.15;
+    }
+
+    score
+}
+
+fn detect_table_renames(
+    dropped: &[TableDefinition],
+    added: &[TableDefinition],
+) -> Vec<TableRename> {
+    let mut scored = Vec::new();
+    for (old_idx, old_table) in dropped.iter().enumerate() {
+        for (new_idx, new_table) in added.iter().enumerate() {
+            scored.push((score_table_rename(old_table, new_table), old_idx, new_idx));
+        }
+    }
+
+    pick_rename_pairs(scored)
+        .into_iter()
+        .map(|(score, old_idx, new_idx)| {
+            let old_table = &dropped[old_idx];
+            let new_table = &added[new_idx];
+            let changes = compare_tables(old_table, new_table).map(|mut table_diff| {
+                table_diff.table_name = new_table.name.clone();
+                table_diff
+            });
+            TableRename {
+                old_name: old_table.name.clone(),
+                new_name: new_table.name.clone(),
+                confidence: round_confidence(score),
+                confirmed: false,
+                changes,
+            }
+        })
+        .collect()
+}
+
+/// Entries sharing one constraint name, plus a comparable signature of their definition.
+struct NamedGroup<T> {
+    signature: Vec<String>,
+    entries: Vec<T>,
+}
+
+fn group_foreign_keys(foreign_keys: &[ForeignKey]) -> BTreeMap<String, NamedGroup<ForeignKey>> {
+    let mut grouped: BTreeMap<String, NamedGroup<ForeignKey>> = BTreeMap::new();
+    for fk in foreign_keys {
+        let group = grouped
+            .entry(normalize_index_token(&fk.constraint_name))
+            .or_insert_with(|| NamedGroup {
+                signature: vec![normalize_index_token(&fk.referenced_table)],
+                entries: Vec::new(),
+            });
+        group.signature.push(format!(
+            "{}->{}",
+            normalize_index_token(&fk.column_name),
+            normalize_index_token(&fk.referenced_column)
+        ));
+        group.entries.push(fk.clone());
+    }
+    grouped
+}
+
+/// Foreign key constraints are left out: they are diffed through `foreign_keys`.
+fn group_constraints(
+    constraints: &[TableConstraint],
+) -> BTreeMap<String, NamedGroup<TableConstraint>> {
+    let mut grouped: BTreeMap<String, NamedGroup<TableConstraint>> = BTreeMap::new();
+    for constraint in constraints {
+        let constraint_type = constraint.constraint_type.trim().to_ascii_uppercase();
+        if constraint_type == "FOREIGN KEY" {
+            continue;
+        }
+        let group = grouped
+            .entry(normalize_index_token(&constraint.name))
+            .or_insert_with(|| NamedGroup {
+                signature: vec![
+                    constraint_type,
+                    constraint
+                        .definition
+                        .as_deref()
+                        .map(normalize_definition)
+                        .unwrap_or_default(),
+                ],
+                entries: Vec::new(),
+            });
+        let column = normalize_index_token(&constraint.column_name);
+        if !column.is_empty() && !group.signature.contains(&column) {
+            group.signature.push(column);
+        }
+        group.entries.push(constraint.clone());
+    }
+    grouped
+}
+
+fn diff_named_groups<T: Clone>(
+    old: BTreeMap<String, NamedGroup<T>>,
+    mut new: BTreeMap<String, NamedGroup<T>>,
+) -> (Vec<T>, Vec<T>) {
+    let mut added = Vec::new();
+    let mut dropped = Vec::new();
+
+    for (name, old_group) in old {
+        match new.remove(&name) {
+            Some(new_group) if new_group.signature == old_group.signature => {}
+            Some(new_group) => {
+                dropped.extend(old_group.entries);
+                added.extend(new_group.entries);
+            }
+            None => dropped.extend(old_group.entries),
+        }
+    }
+    for new_group in new.into_values() {
+        added.extend(new_group.entries);
+    }
+
+    (added, dropped)
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct BreakingChange {
     pub table_name: String,
@@ -336,6 +891,29 @@ pub fn detect_breaking_changes(diff: &SchemaDiff) -> Vec<BreakingChange> {
         }
     }
 
+    // 3. Dropped views and routines break anything still querying or calling them
+    for view in &diff.dropped_views {
+        breaking_changes.push(BreakingChange {
+            table_name: view.name.clone(),
+            change_type: "View Dropped".to_string(),
+            description: format!("View '{}' was dropped.", view.name),
+        });
+    }
+
+    // A redefined routine is listed as dropped and new; it still exists
+    let redefined: HashSet<&str> = diff.new_routines.iter().map(|r| r.name.as_str()).collect();
+    for routine in diff
+        .dropped_routines
+        .iter()
+        .filter(|r| !redefined.contains(r.name.as_str()))
+    {
+        breaking_changes.push(BreakingChange {
+            table_name: routine.name.clone(),
+            change_type: "Routine Dropped".to_string(),
+            description: format!("Routine '{}' was dropped.", routine.name),
+        });
+    }
+
     breaking_changes
 }
 
@@ -382,7 +960,7 @@ fn parse_type(raw: &str) -> Option<ParsedType> {
     })
 }
 
-fn is_type_change_safe(old: &str, new: &str) -> bool {
+pub(crate) fn is_type_change_safe(old: &str, new: &str) -> bool {
     let old_t = match parse_type(old) {
         Some(t) => t,
         None => return false,
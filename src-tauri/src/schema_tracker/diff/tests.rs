@@ -68,3 +68,319 @@ fn index_definition_change_with_same_name_is_reported() {
     assert!(!table_diff.dropped_indexes.is_empty());
     assert!(!table_diff.new_indexes.is_empty());
 }
+
+fn foreign_key(name: &str, column: &str, referenced_table: &str) -> ForeignKey {
+    ForeignKey {
+        constraint_name: name.to_string(),
+        column_name: column.to_string(),
+        referenced_table: referenced_table.to_string(),
+        referenced_column: "id".to_string(),
+        referenced_schema: None,
+    }
+}
+
+#[test]
+fn dropped_foreign_key_and_changed_check_are_reported() {
+    let mut old_table = table_with_indexes("orders", vec![]);
+    old_table.foreign_keys = vec![foreign_key(
+        "fk_orders_customer",
+        "customer_id",
+        "customers",
+    )];
+    old_table.constraints = vec![TableConstraint {
+        name: "chk_total".to_string(),
+        constraint_type: "CHECK".to_string(),
+        column_name: String::new(),
+        definition: Some("([total]>=(0))".to_string()),
+    }];
+
+    let mut new_table = table_with_indexes("orders", vec![]);
+    new_table.constraints = vec![TableConstraint {
+        definition: Some("([total]>(0))".to_string()),
+        ..old_table.constraints[0].clone()
+    }];
+
+    let diff = compare_schemas(
+        &snapshot_with_tables(vec![old_table]),
+        &snapshot_with_tables(vec![new_table]),
+    );
+    assert_eq!(diff.modified_tables.len(), 1);
+    let table_diff = &diff.modified_tables[0];
+    assert_eq!(table_diff.dropped_foreign_keys.len(), 1);
+    assert!(table_diff.new_foreign_keys.is_empty());
+    assert_eq!(table_diff.dropped_constraints.len(), 1);
+    assert_eq!(table_diff.new_constraints.len(), 1);
+}
+
+#[test]
+fn foreign_key_constraint_rows_are_not_double_counted() {
+    let mut table = table_with_indexes("orders", vec![]);
+    table.foreign_keys = vec![foreign_key(
+        "fk_orders_customer",
+        "customer_id",
+        "customers",
+    )];
+    table.constraints = vec![TableConstraint {
+        name: "fk_orders_customer".to_string(),
+        constraint_type: "FOREIGN KEY".to_string(),
+        column_name: "customer_id".to_string(),
+        definition: None,
+    }];
+
+    let old = snapshot_with_tables(vec![table.clone()]);
+    let mut new = snapshot_with_tables(vec![table]);
+    new.tables[0].foreign_keys[0].referenced_table = "clients".to_string();
+
+    let diff = compare_schemas(&old, &new);
+    let table_diff = &diff.modified_tables[0];
+    assert_eq!(table_diff.dropped_foreign_keys.len(), 1);
+    assert_eq!(table_diff.new_foreign_keys.len(), 1);
+    assert!(table_diff.new_constraints.is_empty());
+    assert!(table_diff.dropped_constraints.is_empty());
+}
+
+#[test]
+fn view_routine_and_trigger_changes_are_reported() {
+    let mut old = snapshot_with_tables(vec![]);
+    old.views = vec![
+        ViewDefinition {
+            name: "active_users".to_string(),
+            definition: "CREATE VIEW active_users AS SELECT * FROM users WHERE active = 1"
+                .to_string(),
+        },
+        ViewDefinition {
+            name: "legacy".to_string(),
+            definition: "CREATE VIEW legacy AS SELECT 1".to_string(),
+        },
+    ];
+    old.routines = vec![RoutineInfo {
+        name: "refresh_stats".to_string(),
+        definer: "root".to_string(),
+        routine_type: None,
+        definition: None,
+    }];
+    old.triggers = vec![TriggerInfo {
+        name: "trg_audit".to_string(),
+        event: "INSERT".to_string(),
+        timing: "AFTER".to_string(),
+        table_name: "users".to_string(),
+        definition: None,
+    }];
+
+    let mut new = old.clone();
+    new.views[0].definition =
+        "CREATE VIEW active_users AS SELECT id FROM users WHERE active = 1".to_string();
+    // Whitespace-only differences are not a change
+    new.views[1].definition = "CREATE VIEW legacy AS\n    SELECT 1;".to_string();
+    new.routines = vec![];
+    new.triggers[0].event = "UPDATE".to_string();
+
+    let diff = compare_schemas(&old, &new);
+    assert!(diff.has_changes());
+    assert_eq!(diff.modified_views.len(), 1);
+    assert_eq!(diff.modified_views[0].view_name, "active_users");
+    assert_eq!(diff.dropped_routines.len(), 1);
+    assert_eq!(diff.dropped_triggers.len(), 1);
+    assert_eq!(diff.new_triggers.len(), 1);
+
+    let breaking = detect_breaking_changes(&diff);
+    assert!(breaking.iter().any(|c| c.change_type == "Routine Dropped"));
+}
+
+#[test]
+fn reverse_diff_swaps_every_direction() {
+    let old_col = ColumnSchema {
+        name: "total".to_string(),
+        column_type: "int".to_string(),
+        ..Default::default()
+    };
+    let new_col = ColumnSchema {
+        column_type: "bigint".to_string(),
+        is_nullable: true,
+        ..old_col.clone()
+    };
+    let mut old = snapshot_with_tables(vec![TableDefinition {
+        columns: vec![old_col],
+        ..table_with_indexes("orders", vec![])
+    }]);
+    old.views = vec![ViewDefinition {
+        name: "v".to_string(),
+        definition: "CREATE VIEW v AS SELECT 1".to_string(),
+    }];
+    let mut new = snapshot_with_tables(vec![
+        TableDefinition {
+            columns: vec![new_col],
+            ..table_with_indexes("orders", vec![])
+        },
+        table_with_indexes("invoices", vec![]),
+    ]);
+    new.views = vec![ViewDefinition {
+        name: "v".to_string(),
+        definition: "CREATE VIEW v AS SELECT 2".to_string(),
+    }];
+
+    let forward = compare_schemas(&old, &new);
+    let reversed = reverse_diff(&forward);
+    let expected = compare_schemas(&new, &old);
+
+    assert_eq!(reversed.dropped_tables.len(), 1);
+    assert_eq!(reversed.dropped_tables[0].name, "invoices");
+    assert_eq!(
+        reversed.modified_views[0].new_definition,
+        "CREATE VIEW v AS SELECT 1"
+    );
+    let reversed_col = &reversed.modified_tables[0].modified_columns[0];
+    let expected_col = &expected.modified_tables[0].modified_columns[0];
+    assert_eq!(reversed_col.new_column.column_type, "int");
+    assert_eq!(reversed_col.changes, expected_col.changes);
+}
+
+fn column(name: &str, column_type: &str) -> ColumnSchema {
+    ColumnSchema {
+        name: name.to_string(),
+        data_type: column_type
+            .split('(')
+            .next()
+            .unwrap_or(column_type)
+            .to_string(),
+        column_type: column_type.to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn renamed_column_is_reported_with_confidence() {
+    let old = snapshot_with_tables(vec![TableDefinition {
+        columns: vec![column("id", "int"), column("mail", "varchar(255)")],
+        ..table_with_indexes(
+            "users",
+            vec![index("idx_users_mail", "mail", false, "BTREE")],
+        )
+    }]);
+    let new = snapshot_with_tables(vec![TableDefinition {
+        columns: vec![
+            column("id", "int"),
+            column("email", "varchar(255)"),
+            column("age", "int"),
+        ],
+        ..table_with_indexes(
+            "users",
+            vec![index("idx_users_mail", "email", false, "BTREE")],
+        )
+    }]);
+
+    let diff = compare_schemas(&old, &new);
+    let table_diff = &diff.modified_tables[0];
+    assert_eq!(table_diff.dropped_columns.len(), 1);
+    assert_eq!(table_diff.new_columns.len(), 2);
+    assert_eq!(table_diff.renamed_columns.len(), 1);
+    let rename = &table_diff.renamed_columns[0];
+    assert_eq!(rename.old_column.name, "mail");
+    assert_eq!(rename.new_column.name, "email");
+    assert_eq!(rename.confidence, 1.0);
+    assert!(!rename.confirmed);
+    assert!(rename.changes.is_empty());
+}
+
+#[test]
+fn matching_type_alone_is_not_a_rename() {
+    let old = snapshot_with_tables(vec![TableDefinition {
+        columns: vec![
+            column("id", "int"),
+            column("legacy_code", "varchar(32)"),
+            column("name", "varchar(255)"),
+            column("city", "varchar(64)"),
+        ],
+        ..table_with_indexes("users", vec![])
+    }]);
+    let new = snapshot_with_tables(vec![TableDefinition {
+        columns: vec![
+            column("id", "int"),
+            column("name", "varchar(255)"),
+            column("city", "varchar(64)"),
+            column("country", "varchar(64)"),
+            column("referral_code", "varchar(32)"),
+        ],
+        ..table_with_indexes("users", vec![])
+    }]);
+
+    let diff = compare_schemas(&old, &new);
+    let table_diff = &diff.modified_tables[0];
+    assert_eq!(table_diff.dropped_columns.len(), 1);
+    assert_eq!(table_diff.new_columns.len(), 2);
+    assert!(table_diff.renamed_columns.is_empty());
+}
+
+#[test]
+fn type_change_is_never_a_rename_and_tables_pair_by_columns() {
+    let old = snapshot_with_tables(vec![TableDefinition {
+        columns: vec![
+            column("id", "int"),
+            column("customer_id", "int"),
+            column("total", "decimal(10,2)"),
+            column("created", "datetime"),
+        ],
+        row_count: Some(10),
+        ..table_with_indexes("orders", vec![])
+    }]);
+    let new = snapshot_with_tables(vec![TableDefinition {
+        columns: vec![
+            column("id", "int"),
+            column("customer_id", "int"),
+            column("total", "decimal(10,2)"),
+            column("created_on", "varchar(32)"),
+        ],
+        row_count: Some(10),
+        ..table_with_indexes("purchase_orders", vec![])
+    }]);
+
+    let diff = compare_schemas(&old, &new);
+    assert_eq!(diff.renamed_tables.len(), 1);
+    let rename = &diff.renamed_tables[0];
+    assert_eq!(rename.old_name, "orders");
+    assert_eq!(rename.new_name, "purchase_orders");
+    assert!(rename.confidence >= 0.6 && rename.confidence < 1.0);
+    let changes = rename.changes.as_ref().unwrap();
+    assert_eq!(changes.table_name, "purchase_orders");
+    assert!(changes.renamed_columns.is_empty());
+}
+
+#[test]
+fn routine_and_trigger_body_changes_are_reported() {
+    let mut old = snapshot_with_tables(vec![]);
+    old.routines = vec![RoutineInfo {
+        name: "refresh_stats".to_string(),
+        definer: "root".to_string(),
+        routine_type: Some("PROCEDURE".to_string()),
+        definition: Some("CREATE PROCEDURE refresh_stats() BEGIN SELECT 1; END".to_string()),
+    }];
+    old.triggers = vec![TriggerInfo {
+        name: "trg_audit".to_string(),
+        event: "INSERT".to_string(),
+        timing: "AFTER".to_string(),
+        table_name: "users".to_string(),
+        definition: Some(
+            "CREATE TRIGGER trg_audit AFTER INSERT ON users FOR EACH ROW SET @n = 1".to_string(),
+        ),
+    }];
+
+    let mut new = old.clone();
+    new.routines[0].definition =
+        Some("CREATE PROCEDURE refresh_stats() BEGIN SELECT 2; END".to_string());
+    new.triggers[0].definition =
+        Some("CREATE TRIGGER trg_audit AFTER INSERT ON users FOR EACH ROW SET @n = 2".to_string());
+
+    let diff = compare_schemas(&old, &new);
+    assert_eq!(diff.dropped_routines.len(), 1);
+    assert_eq!(diff.new_routines.len(), 1);
+    assert_eq!(diff.dropped_triggers.len(), 1);
+    assert_eq!(diff.new_triggers.len(), 1);
+    // Redefining a routine does not remove it
+    assert!(detect_breaking_changes(&diff).is_empty());
+
+    // A snapshot without bodies only compares names
+    let mut legacy = old.clone();
+    legacy.routines[0].definition = None;
+    legacy.triggers[0].definition = None;
+    assert!(!compare_schemas(&legacy, &new).has_changes());
+}
@@ -0,0 +1,173 @@
+// =====================================================
+// DRIFT WATCH MODULE
+// Compares scheduled captures with a baseline snapshot and records the
+// changes nobody planned as drift events
+// =====================================================
+
+use crate::schema_tracker::diff::{compare_schemas, detect_breaking_changes};
+use crate::schema_tracker::models::{SchemaDiff, SchemaDriftEvent, SchemaSnapshot};
+use crate::schema_tracker::storage::SchemaTrackerStore;
+use chrono::Utc;
+
+/// Tag that pins a snapshot as the reference for drift checks.
+pub const DEFAULT_BASELINE_TAG: &str = "baseline";
+
+/// Snapshot a capture is checked against.
+#[derive(Debug, Clone)]
+pub struct DriftBaseline {
+    pub snapshot: SchemaSnapshot,
+    /// Tag that pinned the snapshot; None for the previous capture
+    pub tag: Option<String>,
+}
+
+/// Pinned snapshot carrying `tag` when there is one, otherwise the latest stored
+/// capture. Must run before the new capture is saved.
+pub async fn resolve_baseline(
+    store: &SchemaTrackerStore,
+    connection_id: &str,
+    database_name: Option<&str>,
+    tag: &str,
+) -> Result<Option<DriftBaseline>, String> {
+    if let Some(snapshot) = store
+        .get_tagged_snapshot(connection_id, database_name, tag)
+        .await?
+    {
+        return Ok(Some(DriftBaseline {
+            snapshot,
+            tag: Some(tag.to_string()),
+        }));
+    }
+
+    let previous = store
+        .get_snapshots(connection_id, database_name.map(str::to_string))
+        .await?
+        .into_iter()
+        .next();
+    Ok(previous.map(|snapshot| DriftBaseline {
+        snapshot,
+        tag: None,
+    }))
+}
+
+/// Rename suggestions are not counted; their drop/add pairs already are.
+pub fn count_changes(diff: &SchemaDiff) -> usize {
+    diff.new_tables.len()
+        + diff.dropped_tables.len()
+        + diff
+            .modified_tables
+            .iter()
+            .map(|t| {
+                t.new_columns.len()
+                    + t.dropped_columns.len()
+                    + t.modified_columns.len()
+                    + t.new_indexes.len()
+                    + t.dropped_indexes.len()
+                    + t.new_foreign_keys.len()
+                    + t.dropped_foreign_keys.len()
+                    + t.new_constraints.len()
+                    + t.dropped_constraints.len()
+            })
+            .sum::<usize>()
+        + diff.new_views.len()
+        + diff.dropped_views.len()
+        + diff.modified_views.len()
+        + diff.new_routines.len()
+        + diff.dropped_routines.len()
+        + diff.new_triggers.len()
+        + diff.dropped_triggers.len()
+}
+
+/// Drift between `baseline` and `current`, or None when the schema is unchanged.
+pub fn build_drift_event(
+    baseline: &DriftBaseline,
+    current: &SchemaSnapshot,
+    snapshot_id: Option<i64>,
+    task_id: Option<&str>,
+) -> Option<SchemaDriftEvent> {
+    if baseline.snapshot.schema_hash == current.schema_hash {
+        return None;
+    }
+    let diff = compare_schemas(&baseline.snapshot, current);
+    if !diff.has_changes() {
+        return None;
+    }
+
+    Some(SchemaDriftEvent {
+        id: None,
+        connection_id: current.connection_id.clone(),
+        database_name: current.database_name.clone(),
+        task_id: task_id.map(str::to_string),
+        baseline_snapshot_id: baseline.snapshot.id,
+        baseline_tag: baseline.tag.clone(),
+        snapshot_id,
+        schema_hash: current.schema_hash.clone(),
+        change_count: count_changes(&diff),
+        breaking_changes: detect_breaking_changes(&diff),
+        diff,
+        notified: false,
+        acknowledged: false,
+        detected_at: Utc::now(),
+    })
+}
+
+/// A pinned baseline keeps reporting the same drift on every run; only a schema
+/// state that was not already recorded against the same baseline is new.
+pub fn is_new_drift(event: &SchemaDriftEvent, last: Option<&SchemaDriftEvent>) -> bool {
+    last.is_none_or(|last| {
+        last.schema_hash != event.schema_hash
+            || last.baseline_snapshot_id != event.baseline_snapshot_id
+    })
+}
+
+pub fn drift_notification_body(event: &SchemaDriftEvent) -> String {
+    let target = match &event.database_name {
+        Some(database) => format!("{}/{}", event.connection_id, database),
+        None => event.connection_id.clone(),
+    };
+    let baseline = match &event.baseline_tag {
+        Some(tag) => format!("baseline '{}'", tag),
+        None => "the previous snapshot".to_string(),
+    };
+    let mut body = format!(
+        "[{}] {} schema change(s) since {}",
+        target, event.change_count, baseline
+    );
+    if !event.breaking_changes.is_empty() {
+        body.push_str(&format!(
+            ", {} breaking: {}",
+            event.breaking_changes.len(),
+            event.breaking_changes[0].description
+        ));
+    }
+    body
+}
+
+/// Records the drift of `current` against `baseline`. Returns the event and whether
+/// it is new; repeated drift is returned but not stored again.
+pub async fn record_drift(
+    store: &SchemaTrackerStore,
+    baseline: &DriftBaseline,
+    current: &SchemaSnapshot,
+    snapshot_id: Option<i64>,
+    task_id: Option<&str>,
+    notify: bool,
+) -> Result<Option<(SchemaDriftEvent, bool)>, String> {
+    let Some(mut event) = build_drift_event(baseline, current, snapshot_id, task_id) else {
+        return Ok(None);
+    };
+
+    let last = store
+        .get_drift_events(&event.connection_id, event.database_name.as_deref(), 1)
+        .await?
+        .into_iter()
+        .next();
+    let is_new = is_new_drift(&event, last.as_ref());
+    if is_new {
+        event.notified = notify;
+        event.id = Some(store.save_drift_event(&event).await?);
+    }
+    Ok(Some((event, is_new)))
+}
+
+#[cfg(test)]
+mod tests;
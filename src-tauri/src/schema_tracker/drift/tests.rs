@@ -0,0 +1,82 @@
+use super::*;
+use crate::db_types::ColumnSchema;
+use crate::schema_tracker::models::TableDefinition;
+
+fn column(name: &str) -> ColumnSchema {
+    ColumnSchema {
+        name: name.to_string(),
+        data_type: "int".to_string(),
+        column_type: "int".to_string(),
+        is_nullable: true,
+        column_key: String::new(),
+        column_default: None,
+        extra: String::new(),
+        collation: None,
+    }
+}
+
+fn snapshot(id: Option<i64>, hash: &str, columns: &[&str]) -> SchemaSnapshot {
+    SchemaSnapshot {
+        id,
+        connection_id: "conn-1".to_string(),
+        database_name: Some("app".to_string()),
+        timestamp: Utc::now(),
+        schema_hash: hash.to_string(),
+        tables: vec![TableDefinition {
+            name: "users".to_string(),
+            columns: columns.iter().map(|c| column(c)).collect(),
+            ..Default::default()
+        }],
+        views: vec![],
+        routines: vec![],
+        triggers: vec![],
+    }
+}
+
+fn baseline(tag: Option<&str>, columns: &[&str]) -> DriftBaseline {
+    DriftBaseline {
+        snapshot: snapshot(Some(1), "base", columns),
+        tag: tag.map(str::to_string),
+    }
+}
+
+#[test]
+fn unchanged_schema_is_not_drift() {
+    let baseline = baseline(None, &["id", "email"]);
+    let mut current = snapshot(None, "base", &["id", "email"]);
+    assert!(build_drift_event(&baseline, &current, Some(2), None).is_none());
+
+    // A different hash alone is not drift when nothing compares differently
+    current.schema_hash = "other".to_string();
+    assert!(build_drift_event(&baseline, &current, Some(2), None).is_none());
+}
+
+#[test]
+fn changes_are_counted_and_breaking_ones_flagged() {
+    let baseline = baseline(Some("baseline"), &["id", "email"]);
+    let current = snapshot(None, "next", &["id", "name"]);
+
+    let event = build_drift_event(&baseline, &current, Some(7), Some("task-1")).unwrap();
+    assert_eq!(event.change_count, 2);
+    assert_eq!(event.baseline_snapshot_id, Some(1));
+    assert_eq!(event.snapshot_id, Some(7));
+    assert_eq!(event.breaking_changes.len(), 1);
+    assert_eq!(event.breaking_changes[0].change_type, "Column Dropped");
+
+    let body = drift_notification_body(&event);
+    assert!(body.starts_with("[conn-1/app] 2 schema change(s) since baseline 'baseline'"));
+    assert!(body.contains("1 breaking"));
+}
+
+#[test]
+fn repeated_drift_against_same_baseline_is_not_new() {
+    let baseline = baseline(Some("baseline"), &["id"]);
+    let event =
+        build_drift_event(&baseline, &snapshot(None, "h2", &["id", "a"]), None, None).unwrap();
+    assert!(is_new_drift(&event, None));
+    assert!(!is_new_drift(&event, Some(&event)));
+
+    let further =
+        build_drift_event(&baseline, &snapshot(None, "h3", &["id", "b"]), None, None).unwrap();
+    assert!(is_new_drift(&further, Some(&event)));
+}
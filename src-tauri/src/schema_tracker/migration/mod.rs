@@ -1,5 +1,8 @@
-use crate::db_types::{ColumnSchema, DatabaseType, TableIndex};
+use crate::db_types::{
+    ColumnSchema, DatabaseType, ForeignKey, RoutineInfo, TableConstraint, TableIndex, TriggerInfo,
+};
 use crate::schema_tracker::models::*;
+use crate::schema_tracker::type_mapping;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashSet};
 
@@ -48,6 +51,12 @@ pub struct MigrationPlan {
     pub external_commands: Vec<String>,
     pub unsupported_statements: Vec<String>,
     pub strategy: String,
+    /// Native statements that undo `script`, in dependency-safe order
+    #[serde(default)]
+    pub rollback_script: String,
+    /// Changes a rollback cannot fully restore (lost data, missing bodies)
+    #[serde(default)]
+    pub irreversible_changes: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -57,6 +66,23 @@ struct IndexGroup {
     columns: Vec<String>,
 }
 
+#[derive(Debug, Clone)]
+struct ForeignKeyGroup {
+    name: String,
+    columns: Vec<String>,
+    referenced_table: String,
+    referenced_schema: Option<String>,
+    referenced_columns: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+struct ConstraintGroup {
+    name: String,
+    constraint_type: String,
+    columns: Vec<String>,
+    definition: Option<String>,
+}
+
 pub fn generate_migration_script(diff: &SchemaDiff, db_type: &DatabaseType) -> String {
     generate_migration_plan(diff, db_type, Some(MigrationStrategy::Native)).script
 }
@@ -81,6 +107,29 @@ pub fn generate_schema_story_summary(diff: &SchemaDiff) -> String {
     if !diff.modified_tables.is_empty() {
         parts.push(format!("modified {} tables", diff.modified_tables.len()));
     }
+    if !diff.new_views.is_empty() {
+        parts.push(format!("added {} views", diff.new_views.len()));
+    }
+    if !diff.dropped_views.is_empty() {
+        parts.push(format!("removed {} views", diff.dropped_views.len()));
+    }
+    if !diff.modified_views.is_empty() {
+        parts.push(format!("modified {} views", diff.modified_views.len()));
+    }
+    if !diff.new_routines.is_empty() || !diff.dropped_routines.is_empty() {
+        parts.push(format!(
+            "added {} and removed {} routines",
+            diff.new_routines.len(),
+            diff.dropped_routines.len()
+        ));
+    }
+    if !diff.new_triggers.is_empty() || !diff.dropped_triggers.is_empty() {
+        parts.push(format!(
+            "added {} and removed {} triggers",
+            diff.new_triggers.len(),
+            diff.dropped_triggers.len()
+        ));
+    }
 
     if parts.is_empty() {
         "no schema changes detected".to_string()
@@ -124,7 +173,7 @@ pub fn generate_migration_plan(
             );
             script_lines.push(String::new());
             for statement in &base_statements {
-                script_lines.push(format!("-- {}", terminate_statement(statement)));
+                script_lines.push(comment_out(&terminate_statement(statement)));
             }
 
             if !external_commands.is_empty() {
@@ -139,42 +188,327 @@ pub fn generate_migration_plan(
                 script_lines.push(String::new());
                 script_lines.push("-- Unsupported statements for OSC mode".to_string());
                 for statement in &unsupported_statements {
-                    script_lines.push(format!("-- {}", terminate_statement(statement)));
-                }
-            }
-        }
-        _ => {
-            for (idx, statement) in final_statements.iter().enumerate() {
-                script_lines.push(terminate_statement(statement));
-                if idx + 1 < final_statements.len() {
-                    script_lines.push(String::new());
+                    script_lines.push(comment_out(&terminate_statement(statement)));
                 }
             }
         }
+        _ => push_script_statements(&mut script_lines, &final_statements, db_type),
     }
 
+    let irreversible_changes = detect_irreversible_changes(diff);
+    let rollback_script = build_rollback_script(diff, db_type, &irreversible_changes);
+
     MigrationPlan {
         script: script_lines.join("\n"),
         warnings,
         external_commands,
         unsupported_statements,
         strategy: selected_strategy.as_str().to_string(),
+        rollback_script,
+        irreversible_changes,
+    }
+}
+
+/// Plan for a diff whose column types are spelled in `source_db_type`, generated for
+/// `target_db_type`. Lossy type and default conversions are reported as warnings.
+pub fn generate_cross_engine_migration_plan(
+    diff: &SchemaDiff,
+    source_db_type: &DatabaseType,
+    target_db_type: &DatabaseType,
+    strategy: Option<MigrationStrategy>,
+) -> MigrationPlan {
+    let (translated, notes) = type_mapping::translate_diff(diff, source_db_type, target_db_type);
+    let mut plan = generate_migration_plan(&translated, target_db_type, strategy);
+    plan.warnings
+        .extend(notes.into_iter().map(|message| MigrationWarning {
+            severity: "medium".to_string(),
+            message,
+        }));
+    plan
+}
+
+fn build_rollback_script(
+    diff: &SchemaDiff,
+    db_type: &DatabaseType,
+    irreversible_changes: &[String],
+) -> String {
+    let rollback_diff = crate::schema_tracker::diff::reverse_diff(diff);
+    let statements = build_base_statements(&rollback_diff, db_type);
+
+    let mut script_lines = Vec::new();
+    script_lines.push("-- Rollback Script".to_string());
+    script_lines.push(format!("-- Database Engine: {:?}", db_type));
+    if !irreversible_changes.is_empty() {
+        script_lines.push(format!(
+            "-- Irreversible Changes: {}",
+            irreversible_changes.len()
+        ));
+        for change in irreversible_changes {
+            script_lines.push(format!("--   {}", change));
+        }
+    }
+    script_lines.push(String::new());
+
+    push_script_statements(&mut script_lines, &statements, db_type);
+
+    script_lines.join("\n")
+}
+
+/// Appends executable statements; MSSQL gets a `GO` after each so script runners
+/// split them into separate batches (`;` does not split there).
+fn push_script_statements(
+    script_lines: &mut Vec<String>,
+    statements: &[String],
+    db_type: &DatabaseType,
+) {
+    for (idx, statement) in statements.iter().enumerate() {
+        let body = statement.trim().trim_end_matches(';');
+        if *db_type == DatabaseType::MySQL && body.contains(';') {
+            // Routine and trigger bodies hold `;`, so they need their own delimiter
+            script_lines.push("DELIMITER $$".to_string());
+            script_lines.push(format!("{}$$", body));
+            script_lines.push("DELIMITER ;".to_string());
+        } else {
+            script_lines.push(terminate_statement(statement));
+        }
+        if *db_type == DatabaseType::MSSQL {
+            script_lines.push("GO".to_string());
+        }
+        if idx + 1 < statements.len() {
+            script_lines.push(String::new());
+        }
+    }
+}
+
+/// Lists what rolling `diff` back cannot restore: rows of dropped tables and columns,
+/// values changed by lossy type conversions, and objects whose bodies are not captured.
+fn detect_irreversible_changes(diff: &SchemaDiff) -> Vec<String> {
+    let mut changes = Vec::new();
+    let (renamed_from, renamed_to) = confirmed_table_renames(diff);
+
+    for table in &diff.dropped_tables {
+        if renamed_from.contains(table.name.as_str()) {
+            continue;
+        }
+        changes.push(format!(
+            "Table '{}' is dropped; rollback recreates it without its rows",
+            table.name
+        ));
+    }
+
+    for table in &diff.new_tables {
+        if renamed_to.contains(table.name.as_str()) {
+            continue;
+        }
+        changes.push(format!(
+            "Table '{}' is created; rollback drops it with any rows written after the migration",
+            table.name
+        ));
+    }
+
+    for table_diff in effective_table_diffs(diff) {
+        let table = &table_diff.table_name;
+        let (columns_from, columns_to) = confirmed_column_renames(table_diff);
+        for col in &table_diff.dropped_columns {
+            if columns_from.contains(col.name.as_str()) {
+                continue;
+            }
+            changes.push(format!(
+                "Column '{}.{}' is dropped; rollback re-adds it without its values",
+                table, col.name
+            ));
+        }
+
+        for col in &table_diff.new_columns {
+            if columns_to.contains(col.name.as_str()) {
+                continue;
+            }
+            changes.push(format!(
+                "Column '{}.{}' is added; rollback drops it with any values written after the migration",
+                table, col.name
+            ));
+        }
+
+        for col_diff in &effective_column_changes(table_diff) {
+            for change in &col_diff.changes {
+                if let DiffType::TypeChanged { old, new } = change {
+                    let message = if crate::schema_tracker::diff::is_type_change_safe(old, new) {
+                        format!(
+                            "Column '{}.{}' widens from '{}' to '{}'; rollback may truncate newer values",
+                            table, col_diff.column_name, old, new
+                        )
+                    } else {
+                        format!(
+                            "Column '{}.{}' narrows from '{}' to '{}'; truncated values cannot be restored",
+                            table, col_diff.column_name, old, new
+                        )
+                    };
+                    changes.push(message);
+                }
+            }
+        }
+
+        for constraint in &table_diff.dropped_constraints {
+            if constraint.constraint_type.eq_ignore_ascii_case("CHECK")
+                && constraint.definition.is_none()
+            {
+                changes.push(format!(
+                    "Check constraint '{}' on '{}' is dropped; its expression was not captured",
+                    constraint.name, table
+                ));
+            }
+        }
+    }
+
+    for routine in diff
+        .dropped_routines
+        .iter()
+        .filter(|r| r.definition.is_none())
+    {
+        changes.push(format!(
+            "Routine '{}' is dropped; its body was not captured",
+            routine.name
+        ));
     }
+
+    for trigger in diff
+        .dropped_triggers
+        .iter()
+        .filter(|t| t.definition.is_none())
+    {
+        changes.push(format!(
+            "Trigger '{}' on '{}' is dropped; its body was not captured",
+            trigger.name, trigger.table_name
+        ));
+    }
+
+    changes
 }
 
 fn build_base_statements(diff: &SchemaDiff, db_type: &DatabaseType) -> Vec<String> {
     let mut statements = Vec::new();
 
-    for table in &diff.new_tables {
+    // Objects that depend on tables are removed first and recreated last.
+    for trigger in &diff.dropped_triggers {
+        statements.extend(generate_drop_trigger(trigger, db_type));
+    }
+
+    let replaced_views = diff.modified_views.iter().map(|v| &v.view_name);
+    for view_name in diff
+        .dropped_views
+        .iter()
+        .map(|v| &v.name)
+        .chain(replaced_views)
+    {
+        statements.push(format!("DROP VIEW IF EXISTS {}", view_name));
+    }
+
+    for routine in &diff.dropped_routines {
+        statements.push(generate_drop_routine(routine, db_type).unwrap_or_else(|| {
+            format!(
+                "-- Routine {} was dropped; remove it with DROP PROCEDURE or DROP FUNCTION",
+                routine.name
+            )
+        }));
+    }
+
+    // Confirmed renames replace the matching DROP TABLE / CREATE TABLE pair
+    let (renamed_from, renamed_to) = confirmed_table_renames(diff);
+    for rename in diff.renamed_tables.iter().filter(|r| r.confirmed) {
+        statements.extend(generate_rename_table(
+            &rename.old_name,
+            &rename.new_name,
+            db_type,
+        ));
+    }
+    let table_diffs = effective_table_diffs(diff);
+
+    for table_diff in &table_diffs {
+        for fk in group_foreign_keys_by_name(&table_diff.dropped_foreign_keys) {
+            statements.extend(generate_drop_foreign_key(
+                &table_diff.table_name,
+                &fk,
+                db_type,
+            ));
+        }
+    }
+
+    let new_tables = diff
+        .new_tables
+        .iter()
+        .filter(|t| !renamed_to.contains(t.name.as_str()));
+    for table in new_tables.clone() {
         statements.push(generate_create_table(table, db_type));
+        statements.extend(generate_table_indexes(table, db_type));
     }
 
-    for table_diff in &diff.modified_tables {
+    for table_diff in &table_diffs {
         statements.extend(generate_alter_table(table_diff, db_type));
     }
 
     for table in &diff.dropped_tables {
-        statements.push(format!("DROP TABLE {}", table.name));
+        if !renamed_from.contains(table.name.as_str()) {
+            statements.push(format!("DROP TABLE {}", table.name));
+        }
+    }
+
+    // SQLite declares foreign keys of new tables inline in CREATE TABLE
+    if *db_type != DatabaseType::SQLite {
+        for table in new_tables {
+            for fk in group_foreign_keys_by_name(&table.foreign_keys) {
+                statements.extend(generate_add_foreign_key(&table.name, &fk, db_type));
+            }
+        }
+    }
+
+    for table_diff in &table_diffs {
+        for fk in group_foreign_keys_by_name(&table_diff.new_foreign_keys) {
+            statements.extend(generate_add_foreign_key(
+                &table_diff.table_name,
+                &fk,
+                db_type,
+            ));
+        }
+    }
+
+    let new_view_definitions = diff
+        .new_views
+        .iter()
+        .map(|v| (&v.name, &v.definition))
+        .chain(
+            diff.modified_views
+                .iter()
+                .map(|v| (&v.view_name, &v.new_definition)),
+        );
+    for (view_name, definition) in new_view_definitions {
+        if definition.trim().is_empty() {
+            statements.push(format!(
+                "-- View {} must be recreated manually; its definition was not captured",
+                view_name
+            ));
+        } else {
+            statements.push(definition.trim().to_string());
+        }
+    }
+
+    for routine in &diff.new_routines {
+        match routine.definition.as_deref().map(str::trim) {
+            Some(definition) if !definition.is_empty() => statements.push(definition.to_string()),
+            _ => statements.push(format!(
+                "-- Routine {} was added; its body was not captured",
+                routine.name
+            )),
+        }
+    }
+
+    for trigger in &diff.new_triggers {
+        match trigger.definition.as_deref().map(str::trim) {
+            Some(definition) if !definition.is_empty() => statements.push(definition.to_string()),
+            _ => statements.push(format!(
+                "-- Trigger {} ({} {} ON {}) was added; its body was not captured",
+                trigger.name, trigger.timing, trigger.event, trigger.table_name
+            )),
+        }
     }
 
     statements
@@ -292,6 +626,14 @@ fn normalize_mysql_table_name(raw_table: &str) -> String {
         .to_string()
 }
 
+fn comment_out(statement: &str) -> String {
+    statement
+        .lines()
+        .map(|line| format!("-- {}", line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 fn terminate_statement(statement: &str) -> String {
     let trimmed = statement.trim();
     if trimmed.ends_with(';') {
@@ -437,9 +779,12 @@ fn generate_create_table(table: &TableDefinition, db_type: &DatabaseType) -> Str
         let col_def = match db_type {
             DatabaseType::MySQL => format_column_mysql(col),
             DatabaseType::PostgreSQL => format_column_postgres(col),
-            DatabaseType::ClickHouse => format_column_mysql(col), // ClickHouse via MySQL bridge
+            DatabaseType::ClickHouse => format_column_clickhouse(col),
             DatabaseType::MSSQL => format_column_mssql(col),
-            DatabaseType::SQLite => format_column_mysql(col), // SQLite similar to MySQL
+            DatabaseType::SQLite => format_column_sqlite(
+                col,
+                sqlite_rowid_column(table).is_some_and(|rowid| rowid.name == col.name),
+            ),
             DatabaseType::Disconnected => String::new(),
         };
         columns_def.push(col_def);
@@ -475,11 +820,25 @@ fn generate_create_table(table: &TableDefinition, db_type: &DatabaseType) -> Str
                 }
             )
         }
-        DatabaseType::SQLite => format!(
-            "CREATE TABLE {} (\n    {}\n);",
-            table.name,
-            columns_def.join(",\n    ")
-        ),
+        DatabaseType::SQLite => {
+            // An AUTOINCREMENT column declares the primary key inline
+            if !pk_cols.is_empty() && sqlite_rowid_column(table).is_none() {
+                columns_def.push(format!("PRIMARY KEY ({})", pk_cols.join(", ")));
+            }
+            for fk in group_foreign_keys_by_name(&table.foreign_keys) {
+                columns_def.push(format!(
+                    "FOREIGN KEY ({}) REFERENCES {} ({})",
+                    fk.columns.join(", "),
+                    fk.referenced_table,
+                    fk.referenced_columns.join(", ")
+                ));
+            }
+            format!(
+                "CREATE TABLE {} (\n    {}\n);",
+                table.name,
+                columns_def.join(",\n    ")
+            )
+        }
         DatabaseType::Disconnected => String::new(),
     }
 }
@@ -488,7 +847,37 @@ fn generate_alter_table(diff: &TableDiff, db_type: &DatabaseType) -> Vec<String>
     let mut stmts = Vec::new();
     let table = &diff.table_name;
 
+    let dropped_constraints = group_constraints_by_name(&diff.dropped_constraints);
+    let new_constraints = group_constraints_by_name(&diff.new_constraints);
+
+    // Primary key and unique constraints are backed by an index of the same name;
+    // the constraint statement manages both, so the index is skipped below.
+    let constraint_index_names: HashSet<String> = dropped_constraints
+        .iter()
+        .chain(new_constraints.iter())
+        .filter(|c| c.constraint_type != "CHECK")
+        .map(|c| c.name.to_ascii_lowercase())
+        .collect();
+
+    for constraint in &dropped_constraints {
+        stmts.extend(generate_drop_constraint(table, constraint, db_type));
+    }
+
+    // Confirmed renames replace the matching DROP COLUMN / ADD COLUMN pair
+    let (renamed_from, renamed_to) = confirmed_column_renames(diff);
+    for rename in diff.renamed_columns.iter().filter(|r| r.confirmed) {
+        stmts.extend(generate_rename_column(
+            table,
+            &rename.old_column.name,
+            &rename.new_column.name,
+            db_type,
+        ));
+    }
+
     for col in &diff.new_columns {
+        if renamed_to.contains(col.name.as_str()) {
+            continue;
+        }
         match db_type {
             DatabaseType::MySQL => stmts.push(format!(
                 "ALTER TABLE {} ADD COLUMN {}",
@@ -508,22 +897,24 @@ fn generate_alter_table(diff: &TableDiff, db_type: &DatabaseType) -> Vec<String>
             DatabaseType::ClickHouse => stmts.push(format!(
                 "ALTER TABLE {} ADD COLUMN {}",
                 table,
-                format_column_mysql(col)
+                format_column_clickhouse(col)
             )),
             DatabaseType::SQLite => stmts.push(format!(
                 "ALTER TABLE {} ADD COLUMN {}",
                 table,
-                format_column_mysql(col)
+                format_column_sqlite(col, false)
             )),
             DatabaseType::Disconnected => {}
         }
     }
 
     for col in &diff.dropped_columns {
-        stmts.push(format!("ALTER TABLE {} DROP COLUMN {}", table, col.name));
+        if !renamed_from.contains(col.name.as_str()) {
+            stmts.push(format!("ALTER TABLE {} DROP COLUMN {}", table, col.name));
+        }
     }
 
-    for col_diff in &diff.modified_columns {
+    for col_diff in &effective_column_changes(diff) {
         match db_type {
             DatabaseType::MySQL => stmts.push(format!(
                 "ALTER TABLE {} MODIFY COLUMN {}",
@@ -586,7 +977,7 @@ fn generate_alter_table(diff: &TableDiff, db_type: &DatabaseType) -> Vec<String>
             DatabaseType::ClickHouse => stmts.push(format!(
                 "ALTER TABLE {} MODIFY COLUMN {}",
                 table,
-                format_column_mysql(&col_diff.new_column)
+                format_column_clickhouse(&col_diff.new_column)
             )),
             DatabaseType::SQLite => {
                 // SQLite ALTER TABLE is limited; column modifications require table rebuild
@@ -600,19 +991,19 @@ fn generate_alter_table(diff: &TableDiff, db_type: &DatabaseType) -> Vec<String>
     }
 
     for idx in group_indexes_by_name(&diff.new_indexes) {
-        if idx.name.eq_ignore_ascii_case("PRIMARY") || idx.columns.is_empty() {
+        if idx.name.eq_ignore_ascii_case("PRIMARY")
+            || idx.columns.is_empty()
+            || constraint_index_names.contains(&idx.name.to_ascii_lowercase())
+        {
             continue;
         }
-        let unique_part = if idx.non_unique { "" } else { "UNIQUE " };
-        let columns = idx.columns.join(", ");
-        stmts.push(format!(
-            "CREATE {}INDEX {} ON {} ({})",
-            unique_part, idx.name, table, columns
-        ));
+        stmts.push(generate_create_index(table, &idx));
     }
 
     for idx in group_indexes_by_name(&diff.dropped_indexes) {
-        if idx.name.eq_ignore_ascii_case("PRIMARY") {
+        if idx.name.eq_ignore_ascii_case("PRIMARY")
+            || constraint_index_names.contains(&idx.name.to_ascii_lowercase())
+        {
             continue;
         }
         match db_type {
@@ -627,9 +1018,289 @@ fn generate_alter_table(diff: &TableDiff, db_type: &DatabaseType) -> Vec<String>
         }
     }
 
+    for constraint in &new_constraints {
+        stmts.extend(generate_add_constraint(table, constraint, db_type));
+    }
+
     stmts
 }
 
+fn confirmed_table_renames(diff: &SchemaDiff) -> (HashSet<&str>, HashSet<&str>) {
+    diff.renamed_tables
+        .iter()
+        .filter(|r| r.confirmed)
+        .map(|r| (r.old_name.as_str(), r.new_name.as_str()))
+        .unzip()
+}
+
+fn confirmed_column_renames(diff: &TableDiff) -> (HashSet<&str>, HashSet<&str>) {
+    diff.renamed_columns
+        .iter()
+        .filter(|r| r.confirmed)
+        .map(|r| (r.old_column.name.as_str(), r.new_column.name.as_str()))
+        .unzip()
+}
+
+/// Modified tables plus whatever else changed on tables behind a confirmed rename.
+fn effective_table_diffs(diff: &SchemaDiff) -> Vec<&TableDiff> {
+    let renamed = diff
+        .renamed_tables
+        .iter()
+        .filter(|r| r.confirmed)
+        .filter_map(|r| r.changes.as_ref());
+    diff.modified_tables.iter().chain(renamed).collect()
+}
+
+/// Modified columns plus definition changes that ride along with a confirmed rename.
+fn effective_column_changes(diff: &TableDiff) -> Vec<ColumnDiff> {
+    let renamed = diff
+        .renamed_columns
+        .iter()
+        .filter(|r| r.confirmed && !r.changes.is_empty())
+        .map(|r| ColumnDiff {
+            column_name: r.new_column.name.clone(),
+            old_column: r.old_column.clone(),
+            new_column: r.new_column.clone(),
+            changes: r.changes.clone(),
+        });
+    diff.modified_columns
+        .iter()
+        .cloned()
+        .chain(renamed)
+        .collect()
+}
+
+fn generate_rename_table(old_name: &str, new_name: &str, db_type: &DatabaseType) -> Option<String> {
+    match db_type {
+        DatabaseType::MySQL | DatabaseType::ClickHouse => {
+            Some(format!("RENAME TABLE {} TO {}", old_name, new_name))
+        }
+        DatabaseType::PostgreSQL | DatabaseType::SQLite => {
+            Some(format!("ALTER TABLE {} RENAME TO {}", old_name, new_name))
+        }
+        DatabaseType::MSSQL => {
+            // sp_rename keeps the schema; moving between schemas needs ALTER SCHEMA TRANSFER
+            let (old_schema, _) = crate::mssql::split_table_name("dbo", old_name);
+            let (new_schema, new_table) = crate::mssql::split_table_name("dbo", new_name);
+            if old_schema != new_schema {
+                return Some(format!(
+                    "-- MSSQL: Moving {} to schema {} requires ALTER SCHEMA ... TRANSFER before renaming",
+                    old_name, new_schema
+                ));
+            }
+            Some(format!(
+                "EXEC sp_rename '{}', '{}'",
+                old_name.replace('\'', "''"),
+                new_table.replace('\'', "''")
+            ))
+        }
+        DatabaseType::Disconnected => None,
+    }
+}
+
+fn generate_rename_column(
+    table: &str,
+    old_name: &str,
+    new_name: &str,
+    db_type: &DatabaseType,
+) -> Option<String> {
+    match db_type {
+        DatabaseType::MSSQL => Some(format!(
+            "EXEC sp_rename '{}.{}', '{}', 'COLUMN'",
+            table.replace('\'', "''"),
+            old_name.replace('\'', "''"),
+            new_name.replace('\'', "''")
+        )),
+        DatabaseType::Disconnected => None,
+        _ => Some(format!(
+            "ALTER TABLE {} RENAME COLUMN {} TO {}",
+            table, old_name, new_name
+        )),
+    }
+}
+
+fn generate_create_index(table: &str, idx: &IndexGroup) -> String {
+    let unique_part = if idx.non_unique { "" } else { "UNIQUE " };
+    format!(
+        "CREATE {}INDEX {} ON {} ({})",
+        unique_part,
+        idx.name,
+        table,
+        idx.columns.join(", ")
+    )
+}
+
+/// Secondary indexes of a newly created table; primary key indexes come with CREATE TABLE.
+fn generate_table_indexes(table: &TableDefinition, db_type: &DatabaseType) -> Vec<String> {
+    if matches!(
+        db_type,
+        DatabaseType::ClickHouse | DatabaseType::Disconnected
+    ) {
+        return Vec::new();
+    }
+
+    let primary_key_names: HashSet<String> = table
+        .constraints
+        .iter()
+        .filter(|c| c.constraint_type.eq_ignore_ascii_case("PRIMARY KEY"))
+        .map(|c| c.name.to_ascii_lowercase())
+        .collect();
+
+    group_indexes_by_name(&table.indexes)
+        .into_iter()
+        .filter(|idx| {
+            !idx.columns.is_empty()
+                && !idx.name.eq_ignore_ascii_case("PRIMARY")
+                && !idx.name.starts_with("sqlite_autoindex_")
+                && !primary_key_names.contains(&idx.name.to_ascii_lowercase())
+        })
+        .map(|idx| generate_create_index(&table.name, &idx))
+        .collect()
+}
+
+fn generate_drop_foreign_key(
+    table: &str,
+    fk: &ForeignKeyGroup,
+    db_type: &DatabaseType,
+) -> Option<String> {
+    match db_type {
+        DatabaseType::MySQL => Some(format!(
+            "ALTER TABLE {} DROP FOREIGN KEY {}",
+            table, fk.name
+        )),
+        DatabaseType::PostgreSQL | DatabaseType::MSSQL => {
+            Some(format!("ALTER TABLE {} DROP CONSTRAINT {}", table, fk.name))
+        }
+        DatabaseType::SQLite => Some(format!(
+            "-- SQLite: Dropping foreign key {} on {} requires table rebuild",
+            fk.name, table
+        )),
+        DatabaseType::ClickHouse | DatabaseType::Disconnected => None,
+    }
+}
+
+fn generate_add_foreign_key(
+    table: &str,
+    fk: &ForeignKeyGroup,
+    db_type: &DatabaseType,
+) -> Option<String> {
+    let referenced_table = match (db_type, fk.referenced_schema.as_deref()) {
+        // MSSQL snapshots name tables "schema.table", so references follow suit
+        (DatabaseType::MSSQL, Some(schema)) if !schema.is_empty() => {
+            format!("{}.{}", schema, fk.referenced_table)
+        }
+        _ => fk.referenced_table.clone(),
+    };
+
+    match db_type {
+        DatabaseType::MySQL | DatabaseType::PostgreSQL | DatabaseType::MSSQL => Some(format!(
+            "ALTER TABLE {} ADD CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {} ({})",
+            table,
+            fk.name,
+            fk.columns.join(", "),
+            referenced_table,
+            fk.referenced_columns.join(", ")
+        )),
+        DatabaseType::SQLite => Some(format!(
+            "-- SQLite: Adding foreign key {} on {} requires table rebuild",
+            fk.name, table
+        )),
+        DatabaseType::ClickHouse | DatabaseType::Disconnected => None,
+    }
+}
+
+fn generate_drop_constraint(
+    table: &str,
+    constraint: &ConstraintGroup,
+    db_type: &DatabaseType,
+) -> Option<String> {
+    match db_type {
+        DatabaseType::MySQL => Some(match constraint.constraint_type.as_str() {
+            "PRIMARY KEY" => format!("ALTER TABLE {} DROP PRIMARY KEY", table),
+            "UNIQUE" => format!("ALTER TABLE {} DROP INDEX {}", table, constraint.name),
+            _ => format!("ALTER TABLE {} DROP CHECK {}", table, constraint.name),
+        }),
+        DatabaseType::PostgreSQL | DatabaseType::MSSQL | DatabaseType::ClickHouse => Some(format!(
+            "ALTER TABLE {} DROP CONSTRAINT {}",
+            table, constraint.name
+        )),
+        DatabaseType::SQLite => Some(format!(
+            "-- SQLite: Dropping constraint {} on {} requires table rebuild",
+            constraint.name, table
+        )),
+        DatabaseType::Disconnected => None,
+    }
+}
+
+fn generate_add_constraint(
+    table: &str,
+    constraint: &ConstraintGroup,
+    db_type: &DatabaseType,
+) -> Option<String> {
+    if *db_type == DatabaseType::Disconnected {
+        return None;
+    }
+    if *db_type == DatabaseType::SQLite {
+        return Some(format!(
+            "-- SQLite: Adding constraint {} on {} requires table rebuild",
+            constraint.name, table
+        ));
+    }
+
+    let columns = constraint.columns.join(", ");
+    match constraint.constraint_type.as_str() {
+        "PRIMARY KEY" if *db_type == DatabaseType::MySQL => Some(format!(
+            "ALTER TABLE {} ADD PRIMARY KEY ({})",
+            table, columns
+        )),
+        "PRIMARY KEY" | "UNIQUE" => Some(format!(
+            "ALTER TABLE {} ADD CONSTRAINT {} {} ({})",
+            table, constraint.name, constraint.constraint_type, columns
+        )),
+        _ => Some(match &constraint.definition {
+            Some(definition) => format!(
+                "ALTER TABLE {} ADD CONSTRAINT {} CHECK ({})",
+                table, constraint.name, definition
+            ),
+            None => format!(
+                "-- Constraint {} on {} must be added manually; its expression was not captured",
+                constraint.name, table
+            ),
+        }),
+    }
+}
+
+fn generate_drop_trigger(trigger: &TriggerInfo, db_type: &DatabaseType) -> Option<String> {
+    match db_type {
+        DatabaseType::MySQL | DatabaseType::SQLite => {
+            Some(format!("DROP TRIGGER IF EXISTS {}", trigger.name))
+        }
+        DatabaseType::PostgreSQL => Some(format!(
+            "DROP TRIGGER IF EXISTS {} ON {}",
+            trigger.name, trigger.table_name
+        )),
+        DatabaseType::MSSQL => {
+            // Triggers live in the schema of their table
+            let name = match trigger.table_name.split_once('.') {
+                Some((schema, _)) => format!("{}.{}", schema, trigger.name),
+                None => trigger.name.clone(),
+            };
+            Some(format!("DROP TRIGGER IF EXISTS {}", name))
+        }
+        DatabaseType::ClickHouse | DatabaseType::Disconnected => None,
+    }
+}
+
+fn generate_drop_routine(routine: &RoutineInfo, db_type: &DatabaseType) -> Option<String> {
+    let kind = routine.routine_type.as_deref()?;
+    match db_type {
+        DatabaseType::MySQL | DatabaseType::PostgreSQL | DatabaseType::MSSQL => {
+            Some(format!("DROP {} IF EXISTS {}", kind, routine.name))
+        }
+        DatabaseType::SQLite | DatabaseType::ClickHouse | DatabaseType::Disconnected => None,
+    }
+}
+
 fn group_indexes_by_name(indexes: &[TableIndex]) -> Vec<IndexGroup> {
     let mut grouped: BTreeMap<String, IndexGroup> = BTreeMap::new();
 
@@ -658,6 +1329,56 @@ fn group_indexes_by_name(indexes: &[TableIndex]) -> Vec<IndexGroup> {
     grouped.into_values().collect()
 }
 
+fn group_foreign_keys_by_name(foreign_keys: &[ForeignKey]) -> Vec<ForeignKeyGroup> {
+    let mut grouped: BTreeMap<String, ForeignKeyGroup> = BTreeMap::new();
+
+    for fk in foreign_keys {
+        let entry = grouped
+            .entry(fk.constraint_name.clone())
+            .or_insert_with(|| ForeignKeyGroup {
+                name: fk.constraint_name.clone(),
+                columns: Vec::new(),
+                referenced_table: fk.referenced_table.clone(),
+                referenced_schema: fk.referenced_schema.clone(),
+                referenced_columns: Vec::new(),
+            });
+        entry.columns.push(fk.column_name.clone());
+        entry.referenced_columns.push(fk.referenced_column.clone());
+    }
+
+    grouped.into_values().collect()
+}
+
+fn group_constraints_by_name(constraints: &[TableConstraint]) -> Vec<ConstraintGroup> {
+    let mut grouped: BTreeMap<String, ConstraintGroup> = BTreeMap::new();
+
+    for constraint in constraints {
+        let constraint_type = constraint.constraint_type.trim().to_ascii_uppercase();
+        if constraint_type == "FOREIGN KEY" {
+            continue;
+        }
+        let entry = grouped
+            .entry(constraint.name.clone())
+            .or_insert_with(|| ConstraintGroup {
+                name: constraint.name.clone(),
+                constraint_type,
+                columns: Vec::new(),
+                definition: constraint.definition.clone(),
+            });
+
+        if !constraint.column_name.is_empty()
+            && !entry
+                .columns
+                .iter()
+                .any(|existing| existing.eq_ignore_ascii_case(&constraint.column_name))
+        {
+            entry.columns.push(constraint.column_name.clone());
+        }
+    }
+
+    grouped.into_values().collect()
+}
+
 fn format_column_mysql(col: &ColumnSchema) -> String {
     let null_def = if col.is_nullable { "NULL" } else { "NOT NULL" };
     let default_def = if let Some(ref def) = col.column_default {
@@ -665,14 +1386,31 @@ fn format_column_mysql(col: &ColumnSchema) -> String {
     } else {
         String::new()
     };
+    let auto_increment_def = if col.extra.to_ascii_lowercase().contains("auto_increment") {
+        "AUTO_INCREMENT"
+    } else {
+        ""
+    };
 
     format!(
-        "{} {} {} {}",
-        col.name, col.column_type, null_def, default_def
+        "{} {} {} {} {}",
+        col.name, col.column_type, null_def, default_def, auto_increment_def
     )
 }
 
 fn format_column_postgres(col: &ColumnSchema) -> String {
+    // A nextval() default points at a sequence the target may not have; serial creates it
+    if type_mapping::is_auto_increment(col) {
+        let ty = type_mapping::parse_column_type(&col.column_type, &DatabaseType::PostgreSQL);
+        if matches!(ty, type_mapping::CanonicalType::Integer { .. }) {
+            return format!(
+                "{} {} NOT NULL",
+                col.name,
+                type_mapping::render_column_type(&ty, &DatabaseType::PostgreSQL, true)
+            );
+        }
+    }
+
     let null_def = if col.is_nullable { "NULL" } else { "NOT NULL" };
     let default_def = if let Some(ref def) = col.column_default {
         format!("DEFAULT {}", def)
@@ -686,7 +1424,37 @@ fn format_column_postgres(col: &ColumnSchema) -> String {
     )
 }
 
-fn format_column_mssql(col: &ColumnSchema) -> String {
+fn format_column_clickhouse(col: &ColumnSchema) -> String {
+    // Nullability is part of the type; NULL / NOT NULL modifiers clash with Nullable(...)
+    let column_type = if col.is_nullable && !col.column_type.starts_with("Nullable(") {
+        format!("Nullable({})", col.column_type)
+    } else {
+        col.column_type.clone()
+    };
+    let default_def = if let Some(ref def) = col.column_default {
+        format!("DEFAULT {}", def)
+    } else {
+        String::new()
+    };
+
+    format!("{} {} {}", col.name, column_type, default_def)
+}
+
+/// The single INTEGER primary key column of an AUTOINCREMENT table, if any.
+fn sqlite_rowid_column(table: &TableDefinition) -> Option<&ColumnSchema> {
+    if table.primary_keys.len() != 1 {
+        return None;
+    }
+    table.columns.iter().find(|col| {
+        col.name == table.primary_keys[0].column_name && type_mapping::is_auto_increment(col)
+    })
+}
+
+fn format_column_sqlite(col: &ColumnSchema, rowid_primary_key: bool) -> String {
+    if rowid_primary_key {
+        return format!("{} INTEGER PRIMARY KEY AUTOINCREMENT", col.name);
+    }
+
     let null_def = if col.is_nullable { "NULL" } else { "NOT NULL" };
     let default_def = if let Some(ref def) = col.column_default {
         format!("DEFAULT {}", def)
@@ -700,5 +1468,30 @@ fn format_column_mssql(col: &ColumnSchema) -> String {
     )
 }
 
+fn format_column_mssql(col: &ColumnSchema) -> String {
+    // Computed columns carry their expression in `extra` ("AS <expr> [PERSISTED]")
+    if col.extra.starts_with("AS ") {
+        return format!("{} {}", col.name, col.extra);
+    }
+
+    let null_def = if col.is_nullable { "NULL" } else { "NOT NULL" };
+    let default_def = if let Some(ref def) = col.column_default {
+        format!("DEFAULT {}", def)
+    } else {
+        String::new()
+    };
+
+    let identity_def = if col.extra.starts_with("IDENTITY") {
+        col.extra.as_str()
+    } else {
+        ""
+    };
+
+    format!(
+        "{} {} {} {} {}",
+        col.name, col.column_type, identity_def, null_def, default_def
+    )
+}
+
 #[cfg(test)]
 mod tests;
@@ -1,4 +1,5 @@
 use super::*;
+use crate::db_types::{ForeignKey, RoutineInfo, TableConstraint, TriggerInfo};
 
 #[test]
 fn test_migration_strategy_parsing() {
@@ -40,3 +41,300 @@ fn test_schema_story_summary() {
     });
     assert_eq!(generate_schema_story_summary(&diff), "added 1 tables");
 }
+
+#[test]
+fn test_format_column_mssql_generated_columns() {
+    let identity = ColumnSchema {
+        name: "id".to_string(),
+        data_type: "int".to_string(),
+        column_type: "int".to_string(),
+        extra: "IDENTITY(1,1)".to_string(),
+        ..Default::default()
+    };
+    let computed = ColumnSchema {
+        name: "total".to_string(),
+        data_type: "decimal".to_string(),
+        column_type: "decimal(10,2)".to_string(),
+        is_nullable: true,
+        extra: "AS ([price]*[qty]) PERSISTED".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(
+        format_column_mssql(&identity).trim(),
+        "id int IDENTITY(1,1) NOT NULL"
+    );
+    assert_eq!(
+        format_column_mssql(&computed),
+        "total AS ([price]*[qty]) PERSISTED"
+    );
+}
+
+#[test]
+fn test_migration_emits_object_and_constraint_statements() {
+    let diff = SchemaDiff {
+        modified_tables: vec![TableDiff {
+            table_name: "orders".to_string(),
+            dropped_foreign_keys: vec![ForeignKey {
+                constraint_name: "fk_orders_customer".to_string(),
+                column_name: "customer_id".to_string(),
+                referenced_table: "customers".to_string(),
+                referenced_column: "id".to_string(),
+                referenced_schema: None,
+            }],
+            new_constraints: vec![TableConstraint {
+                name: "uq_orders_ref".to_string(),
+                constraint_type: "UNIQUE".to_string(),
+                column_name: "reference".to_string(),
+                definition: None,
+            }],
+            new_indexes: vec![TableIndex {
+                name: "uq_orders_ref".to_string(),
+                column_name: "reference".to_string(),
+                non_unique: false,
+                index_type: "BTREE".to_string(),
+            }],
+            ..Default::default()
+        }],
+        modified_views: vec![ViewDiff {
+            view_name: "order_totals".to_string(),
+            old_definition: "CREATE VIEW order_totals AS SELECT 1".to_string(),
+            new_definition: "CREATE VIEW order_totals AS SELECT 2".to_string(),
+        }],
+        dropped_triggers: vec![TriggerInfo {
+            name: "trg_orders".to_string(),
+            event: "INSERT".to_string(),
+            timing: "AFTER".to_string(),
+            table_name: "orders".to_string(),
+            definition: None,
+        }],
+        ..Default::default()
+    };
+
+    let mysql = generate_migration_script(&diff, &DatabaseType::MySQL);
+    assert!(mysql.contains("DROP TRIGGER IF EXISTS trg_orders;"));
+    assert!(mysql.contains("DROP VIEW IF EXISTS order_totals;"));
+    assert!(mysql.contains("ALTER TABLE orders DROP FOREIGN KEY fk_orders_customer;"));
+    assert!(mysql.contains("ALTER TABLE orders ADD CONSTRAINT uq_orders_ref UNIQUE (reference);"));
+    assert!(!mysql.contains("CREATE UNIQUE INDEX uq_orders_ref"));
+    assert!(
+        mysql.find("DROP VIEW").unwrap()
+            < mysql.find("CREATE VIEW order_totals AS SELECT 2").unwrap()
+    );
+
+    let postgres = generate_migration_script(&diff, &DatabaseType::PostgreSQL);
+    assert!(postgres.contains("DROP TRIGGER IF EXISTS trg_orders ON orders;"));
+    assert!(postgres.contains("ALTER TABLE orders DROP CONSTRAINT fk_orders_customer;"));
+}
+
+#[test]
+fn test_plan_carries_rollback_script_and_irreversible_changes() {
+    let email = ColumnSchema {
+        name: "email".to_string(),
+        data_type: "varchar".to_string(),
+        column_type: "varchar(255)".to_string(),
+        is_nullable: true,
+        ..Default::default()
+    };
+    let diff = SchemaDiff {
+        dropped_tables: vec![TableDefinition {
+            name: "audit_log".to_string(),
+            columns: vec![ColumnSchema {
+                name: "id".to_string(),
+                data_type: "int".to_string(),
+                column_type: "int".to_string(),
+                ..Default::default()
+            }],
+            indexes: vec![TableIndex {
+                name: "idx_audit_created".to_string(),
+                column_name: "id".to_string(),
+                non_unique: true,
+                index_type: "BTREE".to_string(),
+            }],
+            ..Default::default()
+        }],
+        modified_tables: vec![TableDiff {
+            table_name: "users".to_string(),
+            dropped_columns: vec![email],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    let plan = generate_migration_plan(&diff, &DatabaseType::MySQL, None);
+    assert!(plan.script.contains("DROP TABLE audit_log;"));
+    assert!(plan.rollback_script.contains("CREATE TABLE audit_log ("));
+    assert!(plan
+        .rollback_script
+        .contains("CREATE INDEX idx_audit_created ON audit_log (id);"));
+    assert!(plan
+        .rollback_script
+        .contains("ALTER TABLE users ADD COLUMN email varchar(255) NULL"));
+    assert!(!plan.rollback_script.contains("DROP TABLE"));
+    assert_eq!(plan.irreversible_changes.len(), 2);
+    assert!(plan.rollback_script.contains("-- Irreversible Changes: 2"));
+}
+
+#[test]
+fn test_routine_and_trigger_bodies_are_recreated() {
+    let old_body = "CREATE PROCEDURE refresh_stats()\nBEGIN\n  DELETE FROM stats;\n  INSERT INTO stats SELECT 1;\nEND";
+    let diff = SchemaDiff {
+        dropped_routines: vec![RoutineInfo {
+            name: "refresh_stats".to_string(),
+            definer: "root@%".to_string(),
+            routine_type: Some("PROCEDURE".to_string()),
+            definition: Some(old_body.to_string()),
+        }],
+        new_routines: vec![RoutineInfo {
+            name: "refresh_stats".to_string(),
+            definer: "root@%".to_string(),
+            routine_type: Some("PROCEDURE".to_string()),
+            definition: Some(old_body.replace("SELECT 1", "SELECT 2")),
+        }],
+        new_triggers: vec![TriggerInfo {
+            name: "trg_orders".to_string(),
+            event: "INSERT".to_string(),
+            timing: "AFTER".to_string(),
+            table_name: "orders".to_string(),
+            definition: Some(
+                "CREATE TRIGGER trg_orders AFTER INSERT ON orders FOR EACH ROW SET @n = 1"
+                    .to_string(),
+            ),
+        }],
+        ..Default::default()
+    };
+
+    let plan = generate_migration_plan(&diff, &DatabaseType::MySQL, None);
+    assert!(plan
+        .script
+        .contains("DROP PROCEDURE IF EXISTS refresh_stats;"));
+    assert!(plan
+        .script
+        .contains("CREATE TRIGGER trg_orders AFTER INSERT ON orders FOR EACH ROW SET @n = 1;"));
+    assert!(plan.irreversible_changes.is_empty());
+
+    let statements: Vec<String> = crate::db::split_sql_script(&plan.script, &DatabaseType::MySQL)
+        .into_iter()
+        .map(|s| s.sql)
+        .collect();
+    assert_eq!(statements.len(), 3);
+    assert!(statements[1].ends_with("INSERT INTO stats SELECT 2;\nEND"));
+    assert!(plan.rollback_script.contains("INSERT INTO stats SELECT 1;"));
+    assert!(plan
+        .rollback_script
+        .contains("DROP TRIGGER IF EXISTS trg_orders;"));
+}
+
+#[test]
+fn test_confirmed_renames_replace_drop_and_add() {
+    let old_col = ColumnSchema {
+        name: "mail".to_string(),
+        data_type: "varchar".to_string(),
+        column_type: "varchar(255)".to_string(),
+        ..Default::default()
+    };
+    let new_col = ColumnSchema {
+        name: "email".to_string(),
+        ..old_col.clone()
+    };
+    let mut diff = SchemaDiff {
+        dropped_tables: vec![TableDefinition {
+            name: "dbo.orders".to_string(),
+            ..Default::default()
+        }],
+        new_tables: vec![TableDefinition {
+            name: "dbo.purchase_orders".to_string(),
+            ..Default::default()
+        }],
+        renamed_tables: vec![TableRename {
+            old_name: "dbo.orders".to_string(),
+            new_name: "dbo.purchase_orders".to_string(),
+            confidence: 0.9,
+            confirmed: true,
+            changes: None,
+        }],
+        modified_tables: vec![TableDiff {
+            table_name: "dbo.users".to_string(),
+            dropped_columns: vec![old_col.clone()],
+            new_columns: vec![new_col.clone()],
+            renamed_columns: vec![ColumnRename {
+                old_column: old_col,
+                new_column: new_col,
+                confidence: 1.0,
+                confirmed: false,
+                changes: vec![],
+            }],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    let unconfirmed = generate_migration_script(&diff, &DatabaseType::MSSQL);
+    assert!(unconfirmed.contains("ALTER TABLE dbo.users DROP COLUMN mail;"));
+    assert!(unconfirmed.contains("EXEC sp_rename 'dbo.orders', 'purchase_orders';"));
+    assert!(!unconfirmed.contains("DROP TABLE dbo.orders"));
+
+    diff.modified_tables[0].renamed_columns[0].confirmed = true;
+    let mssql = generate_migration_script(&diff, &DatabaseType::MSSQL);
+    assert!(mssql.contains("EXEC sp_rename 'dbo.users.mail', 'email', 'COLUMN';"));
+    assert!(!mssql.contains("DROP COLUMN"));
+    assert!(!mssql.contains("ADD email"));
+
+    let plan = generate_migration_plan(&diff, &DatabaseType::PostgreSQL, None);
+    assert!(plan
+        .script
+        .contains("ALTER TABLE dbo.users RENAME COLUMN mail TO email;"));
+    assert!(plan
+        .rollback_script
+        .contains("ALTER TABLE dbo.users RENAME COLUMN email TO mail;"));
+    assert!(plan.irreversible_changes.is_empty());
+
+    let rollback = generate_migration_plan(&diff, &DatabaseType::MSSQL, None).rollback_script;
+    assert!(rollback.contains("EXEC sp_rename 'dbo.purchase_orders', 'orders';"));
+}
+
+#[test]
+fn test_cross_engine_plan_translates_column_types() {
+    let diff = SchemaDiff {
+        new_tables: vec![TableDefinition {
+            name: "users".to_string(),
+            columns: vec![
+                ColumnSchema {
+                    name: "id".to_string(),
+                    data_type: "int".to_string(),
+                    column_type: "int".to_string(),
+                    extra: "auto_increment".to_string(),
+                    ..Default::default()
+                },
+                ColumnSchema {
+                    name: "active".to_string(),
+                    data_type: "tinyint".to_string(),
+                    column_type: "tinyint(1)".to_string(),
+                    column_default: Some("1".to_string()),
+                    ..Default::default()
+                },
+                ColumnSchema {
+                    name: "tags".to_string(),
+                    data_type: "geometry".to_string(),
+                    column_type: "geometry".to_string(),
+                    is_nullable: true,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    let plan = generate_cross_engine_migration_plan(
+        &diff,
+        &DatabaseType::MySQL,
+        &DatabaseType::PostgreSQL,
+        None,
+    );
+    assert!(plan.script.contains("id serial NOT NULL"));
+    assert!(plan.script.contains("active boolean NOT NULL DEFAULT true"));
+    assert!(plan
+        .warnings
+        .iter()
+        .any(|w| w.severity == "medium" && w.message.contains("tags")));
+}
@@ -0,0 +1,671 @@
+// =====================================================
+// MIGRATION FILES MODULE
+// Exports migration plans as Flyway / Liquibase-style files and reads
+// migration directories back to compare them with a live connection
+// =====================================================
+
+use crate::db::split_sql_script;
+use crate::db_types::{AppState, DatabaseType, QueryResult};
+use crate::schema_tracker::apply::{
+    connect_database_target, get_migration_history, migration_checksum, value_as_string,
+    MigrationApplyStatus, MigrationHistoryEntry,
+};
+use crate::schema_tracker::migration::MigrationPlan;
+use crate::schema_tracker::models::SchemaDiff;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+const LIQUIBASE_HEADER: &str = "--liquibase formatted sql";
+const LIQUIBASE_AUTHOR: &str = "tactilesql";
+
+// =====================================================
+// STRUCTS
+// =====================================================
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationFileLayout {
+    /// `V<version>__<desc>.sql` with a matching `U<version>__<desc>.sql` undo file
+    #[default]
+    Flyway,
+    /// Liquibase formatted SQL, one changeset per file with `--rollback` lines
+    Liquibase,
+}
+
+impl MigrationFileLayout {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Flyway => "flyway",
+            Self::Liquibase => "liquibase",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationFileKind {
+    Versioned,
+    /// Flyway `R__` files, re-run whenever their checksum changes
+    Repeatable,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MigrationExportRequest {
+    pub plan: MigrationPlan,
+    pub db_type: DatabaseType,
+    pub directory: String,
+    pub description: String,
+    /// Defaults to a UTC timestamp (`YYYYMMDDHHMMSS`)
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub layout: MigrationFileLayout,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MigrationFile {
+    pub file_name: String,
+    pub kind: MigrationFileKind,
+    /// Flyway version or Liquibase changeset id; `None` for repeatable files
+    pub version: Option<String>,
+    pub description: String,
+    pub script: String,
+    /// Matching Flyway undo file or the changeset's `--rollback` lines
+    pub undo_script: Option<String>,
+    /// Same checksum `apply_migration_plan` records for this script
+    pub checksum: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MigrationDirectoryRequest {
+    pub connection_id: String,
+    pub database: String,
+    pub directory: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct MigrationFileStatus {
+    pub file: MigrationFile,
+    pub applied: bool,
+    pub applied_at: Option<String>,
+    /// `tactilesql`, `flyway` or `liquibase`: whose history table recorded the run
+    pub applied_by: Option<String>,
+    pub result_snapshot_id: Option<i64>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct MigrationDirectoryStatus {
+    pub layout: MigrationFileLayout,
+    pub files: Vec<MigrationFileStatus>,
+    pub pending: usize,
+    /// Snapshot recorded after the latest applied file, i.e. the expected schema
+    pub expected_snapshot_id: Option<i64>,
+    /// Expected schema compared with the live one; `None` when no snapshot is known
+    pub drift: Option<SchemaDiff>,
+    pub warnings: Vec<String>,
+}
+
+// =====================================================
+// EXPORT
+// =====================================================
+
+fn sanitize_description(description: &str) -> String {
+    let mut sanitized = String::new();
+    for c in description.trim().chars() {
+        if c.is_ascii_alphanumeric() {
+            sanitized.push(c.to_ascii_lowercase());
+        } else if !sanitized.ends_with('_') {
+            sanitized.push('_');
+        }
+    }
+    let sanitized = sanitized.trim_matches('_');
+    if sanitized.is_empty() {
+        "migration".to_string()
+    } else {
+        sanitized.to_string()
+    }
+}
+
+fn validate_version(version: &str) -> Result<(), String> {
+    let valid = !version.is_empty()
+        && version
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '.' || c == '_')
+        && !version.contains("__")
+        && version.chars().next().is_some_and(|c| c.is_ascii_digit());
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid migration version '{}': use digits separated by '.' or '_'",
+            version
+        ))
+    }
+}
+
+/// Executable lines of `script`, without the plan's header comments.
+fn statement_lines(script: &str) -> Vec<&str> {
+    script
+        .lines()
+        .filter(|line| {
+            let trimmed = line.trim();
+            !trimmed.is_empty() && !trimmed.starts_with("--")
+        })
+        .collect()
+}
+
+fn render_liquibase_changeset(
+    plan: &MigrationPlan,
+    db_type: &DatabaseType,
+    version: &str,
+    description: &str,
+) -> String {
+    let mut changeset = format!("--changeset {}:{}", LIQUIBASE_AUTHOR, version);
+    if *db_type == DatabaseType::MSSQL {
+        changeset.push_str(" endDelimiter:GO");
+    }
+
+    let mut lines = vec![
+        LIQUIBASE_HEADER.to_string(),
+        String::new(),
+        changeset,
+        format!("--comment: {}", description),
+        plan.script.trim_end().to_string(),
+    ];
+    let rollback = statement_lines(&plan.rollback_script);
+    if !rollback.is_empty() {
+        lines.push(String::new());
+        for change in &plan.irreversible_changes {
+            lines.push(format!("-- Irreversible: {}", change));
+        }
+        lines.extend(rollback.iter().map(|line| format!("--rollback {}", line)));
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// File names and contents for `plan`; the caller decides where they go.
+pub fn render_migration_files(
+    plan: &MigrationPlan,
+    db_type: &DatabaseType,
+    layout: MigrationFileLayout,
+    version: &str,
+    description: &str,
+) -> Result<Vec<(String, String)>, String> {
+    validate_version(version)?;
+    let slug = sanitize_description(description);
+
+    match layout {
+        MigrationFileLayout::Flyway => {
+            let mut files = vec![(
+                format!("V{}__{}.sql", version, slug),
+                format!("{}\n", plan.script.trim_end()),
+            )];
+            if !statement_lines(&plan.rollback_script).is_empty() {
+                files.push((
+                    format!("U{}__{}.sql", version, slug),
+                    format!("{}\n", plan.rollback_script.trim_end()),
+                ));
+            }
+            Ok(files)
+        }
+        MigrationFileLayout::Liquibase => Ok(vec![(
+            format!("{}__{}.sql", version, slug),
+            render_liquibase_changeset(plan, db_type, version, description.trim()),
+        )]),
+    }
+}
+
+/// Writes `request.plan` into `request.directory`. Existing files are never overwritten.
+pub fn export_migration_files(request: &MigrationExportRequest) -> Result<Vec<String>, String> {
+    let version = match request.version.as_deref().map(str::trim) {
+        Some(version) if !version.is_empty() => version.to_string(),
+        _ => Utc::now().format("%Y%m%d%H%M%S").to_string(),
+    };
+    let files = render_migration_files(
+        &request.plan,
+        &request.db_type,
+        request.layout,
+        &version,
+        &request.description,
+    )?;
+
+    let directory = Path::new(&request.directory);
+    fs::create_dir_all(directory)
+        .map_err(|e| format!("Failed to create migration directory: {}", e))?;
+    for (file_name, _) in &files {
+        if directory.join(file_name).exists() {
+            return Err(format!("Migration file '{}' already exists", file_name));
+        }
+    }
+
+    let mut written = Vec::new();
+    for (file_name, content) in files {
+        let path = directory.join(&file_name);
+        fs::write(&path, content)
+            .map_err(|e| format!("Failed to write migration file '{}': {}", file_name, e))?;
+        written.push(path.to_string_lossy().to_string());
+    }
+    Ok(written)
+}
+
+// =====================================================
+// READING
+// =====================================================
+
+#[derive(Debug, Clone, PartialEq)]
+enum FlywayName {
+    Versioned {
+        version: String,
+        description: String,
+    },
+    Undo {
+        version: String,
+    },
+    Repeatable {
+        description: String,
+    },
+}
+
+fn parse_flyway_file_name(file_name: &str) -> Option<FlywayName> {
+    let stem = file_name.strip_suffix(".sql")?;
+    let (prefix, description) = stem.split_once("__")?;
+    let description = description.replace('_', " ");
+
+    if prefix == "R" {
+        return Some(FlywayName::Repeatable { description });
+    }
+    let mut chars = prefix.chars();
+    let kind = chars.next()?;
+    let version = chars.as_str();
+    validate_version(version).ok()?;
+    let version = version.replace('_', ".");
+    match kind {
+        'V' => Some(FlywayName::Versioned {
+            version,
+            description,
+        }),
+        'U' => Some(FlywayName::Undo { version }),
+        _ => None,
+    }
+}
+
+/// Flyway ordering: numeric parts compared left to right, `1.10` after `1.9`.
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let parts = |v: &str| -> Vec<u64> {
+        v.split(['.', '_'])
+            .map(|part| part.parse().unwrap_or(0))
+            .collect()
+    };
+    let (a_parts, b_parts) = (parts(a), parts(b));
+    for idx in 0..a_parts.len().max(b_parts.len()) {
+        let ordering = a_parts
+            .get(idx)
+            .unwrap_or(&0)
+            .cmp(b_parts.get(idx).unwrap_or(&0));
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+fn file_checksum(script: &str, db_type: &DatabaseType) -> String {
+    migration_checksum(&split_sql_script(script, db_type))
+}
+
+/// Splits a Liquibase formatted SQL file into its changesets.
+fn parse_liquibase_file(
+    file_name: &str,
+    content: &str,
+    db_type: &DatabaseType,
+) -> Vec<MigrationFile> {
+    struct Changeset {
+        id: String,
+        description: String,
+        body: Vec<String>,
+        rollback: Vec<String>,
+    }
+
+    let mut changesets: Vec<Changeset> = Vec::new();
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if let Some(header) = trimmed.strip_prefix("--changeset") {
+            // `author:id` followed by optional attributes
+            let id = header
+                .split_whitespace()
+                .next()
+                .and_then(|author_id| author_id.split_once(':'))
+                .map(|(_, id)| id.to_string())
+                .unwrap_or_default();
+            changesets.push(Changeset {
+                id,
+                description: String::new(),
+                body: Vec::new(),
+                rollback: Vec::new(),
+            });
+            continue;
+        }
+        let Some(current) = changesets.last_mut() else {
+            continue;
+        };
+        if let Some(rollback) = trimmed.strip_prefix("--rollback") {
+            current.rollback.push(rollback.trim_start().to_string());
+        } else if let Some(comment) = trimmed.strip_prefix("--comment:") {
+            current.description = comment.trim().to_string();
+        } else if trimmed.starts_with("--precondition") {
+            continue;
+        } else {
+            current.body.push(line.to_string());
+        }
+    }
+
+    changesets
+        .into_iter()
+        .map(|changeset| {
+            let script = changeset.body.join("\n").trim().to_string();
+            let undo_script = Some(changeset.rollback.join("\n").trim().to_string())
+                .filter(|undo| !undo.is_empty());
+            MigrationFile {
+                file_name: file_name.to_string(),
+                kind: MigrationFileKind::Versioned,
+                version: Some(changeset.id),
+                description: changeset.description,
+                checksum: file_checksum(&script, db_type),
+                script,
+                undo_script,
+            }
+        })
+        .collect()
+}
+
+/// Flyway entries sorted by version (repeatables last, by name), undo files folded
+/// into their versioned file.
+pub(crate) fn parse_flyway_files(
+    entries: &[(String, String)],
+    db_type: &DatabaseType,
+) -> Result<Vec<MigrationFile>, String> {
+    let mut versioned = Vec::new();
+    let mut repeatable = Vec::new();
+    let mut undo: HashMap<String, String> = HashMap::new();
+
+    for (file_name, content) in entries {
+        match parse_flyway_file_name(file_name) {
+            Some(FlywayName::Versioned {
+                version,
+                description,
+            }) => versioned.push(MigrationFile {
+                file_name: file_name.clone(),
+                kind: MigrationFileKind::Versioned,
+                version: Some(version),
+                description,
+                script: content.clone(),
+                undo_script: None,
+                checksum: file_checksum(content, db_type),
+            }),
+            Some(FlywayName::Repeatable { description }) => repeatable.push(MigrationFile {
+                file_name: file_name.clone(),
+                kind: MigrationFileKind::Repeatable,
+                version: None,
+                description,
+                script: content.clone(),
+                undo_script: None,
+                checksum: file_checksum(content, db_type),
+            }),
+            Some(FlywayName::Undo { version }) => {
+                undo.insert(version, content.clone());
+            }
+            None => {}
+        }
+    }
+
+    let mut seen = HashSet::new();
+    for file in &versioned {
+        let version = file.version.as_deref().unwrap_or_default();
+        if !seen.insert(version.to_string()) {
+            return Err(format!(
+                "Found more than one migration with version {}",
+                version
+            ));
+        }
+    }
+
+    versioned.sort_by(|a, b| {
+        compare_versions(
+            a.version.as_deref().unwrap_or_default(),
+            b.version.as_deref().unwrap_or_default(),
+        )
+    });
+    for file in &mut versioned {
+        if let Some(version) = &file.version {
+            file.undo_script = undo.remove(version);
+        }
+    }
+    repeatable.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+    versioned.extend(repeatable);
+    Ok(versioned)
+}
+
+/// Reads every `.sql` file in `directory`. A directory whose files start with
+/// `--liquibase formatted sql` is read as Liquibase, anything else as Flyway.
+pub fn read_migration_directory(
+    directory: &str,
+    db_type: &DatabaseType,
+) -> Result<(MigrationFileLayout, Vec<MigrationFile>), String> {
+    let mut entries = Vec::new();
+    let dir = fs::read_dir(directory)
+        .map_err(|e| format!("Failed to read migration directory '{}': {}", directory, e))?;
+    for entry in dir {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if !path.is_file() || path.extension().and_then(|e| e.to_str()) != Some("sql") {
+            continue;
+        }
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read migration file '{}': {}", file_name, e))?;
+        entries.push((file_name, content));
+    }
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let liquibase = entries
+        .iter()
+        .any(|(_, content)| content.trim_start().starts_with(LIQUIBASE_HEADER));
+    if liquibase {
+        let files = entries
+            .iter()
+            .filter(|(_, content)| content.trim_start().starts_with(LIQUIBASE_HEADER))
+            .flat_map(|(file_name, content)| parse_liquibase_file(file_name, content, db_type))
+            .collect();
+        return Ok((MigrationFileLayout::Liquibase, files));
+    }
+    Ok((
+        MigrationFileLayout::Flyway,
+        parse_flyway_files(&entries, db_type)?,
+    ))
+}
+
+// =====================================================
+// STATUS
+// =====================================================
+
+/// Query against the tool's own history table, when one exists.
+fn external_history_query(layout: MigrationFileLayout) -> &'static str {
+    match layout {
+        MigrationFileLayout::Flyway => "SELECT version, success FROM flyway_schema_history",
+        MigrationFileLayout::Liquibase => "SELECT id, exectype FROM databasechangelog",
+    }
+}
+
+/// Versions (or changeset ids) recorded as successful by Flyway or Liquibase.
+pub(crate) fn parse_external_history(
+    layout: MigrationFileLayout,
+    results: &[QueryResult],
+) -> HashSet<String> {
+    let Some(result) = results.first() else {
+        return HashSet::new();
+    };
+    result
+        .rows
+        .iter()
+        .filter_map(|row| {
+            let version = row.first().and_then(value_as_string)?;
+            let outcome = row.get(1).and_then(value_as_string).unwrap_or_default();
+            let succeeded = match layout {
+                MigrationFileLayout::Flyway => {
+                    matches!(outcome.to_ascii_lowercase().as_str(), "1" | "true" | "t")
+                }
+                MigrationFileLayout::Liquibase => {
+                    !matches!(outcome.to_ascii_uppercase().as_str(), "FAILED" | "SKIPPED")
+                }
+            };
+            succeeded.then_some(version)
+        })
+        .collect()
+}
+
+/// Marks each file applied when its checksum is in TactileSQL's history or its
+/// version is in the Flyway / Liquibase history table.
+pub(crate) fn match_applied_files(
+    layout: MigrationFileLayout,
+    files: Vec<MigrationFile>,
+    history: &[MigrationHistoryEntry],
+    external: &HashSet<String>,
+) -> Vec<MigrationFileStatus> {
+    let applied_status = MigrationApplyStatus::Applied.as_str();
+    files
+        .into_iter()
+        .map(|file| {
+            // History is newest first; the latest run of this checksum wins
+            let recorded = history
+                .iter()
+                .find(|entry| entry.checksum == file.checksum && entry.status == applied_status);
+            if let Some(entry) = recorded {
+                return MigrationFileStatus {
+                    applied: true,
+                    applied_at: Some(entry.applied_at.clone()),
+                    applied_by: Some("tactilesql".to_string()),
+                    result_snapshot_id: entry.result_snapshot_id,
+                    file,
+                };
+            }
+            let external_run = file.kind == MigrationFileKind::Versioned
+                && file
+                    .version
+                    .as_deref()
+                    .is_some_and(|version| external.contains(version));
+            MigrationFileStatus {
+                applied: external_run,
+                applied_at: None,
+                applied_by: external_run.then(|| layout.as_str().to_string()),
+                result_snapshot_id: None,
+                file,
+            }
+        })
+        .collect()
+}
+
+fn out_of_order_warnings(files: &[MigrationFileStatus]) -> Vec<String> {
+    let last_applied = files
+        .iter()
+        .rposition(|f| f.applied && f.file.kind == MigrationFileKind::Versioned);
+    let Some(last_applied) = last_applied else {
+        return Vec::new();
+    };
+    files[..last_applied]
+        .iter()
+        .filter(|f| !f.applied && f.file.kind == MigrationFileKind::Versioned)
+        .map(|f| {
+            format!(
+                "{} is pending but later migration {} is already applied",
+                f.file.file_name, files[last_applied].file.file_name
+            )
+        })
+        .collect()
+}
+
+/// Which files in `request.directory` are applied to `request.database`, and how the
+/// live schema differs from the one recorded after the latest applied file.
+pub async fn get_migration_directory_status(
+    app_state: &AppState,
+    request: &MigrationDirectoryRequest,
+) -> Result<MigrationDirectoryStatus, String> {
+    let db_type = {
+        let guard = app_state.active_db_type.lock().await;
+        guard.clone()
+    };
+    if db_type == DatabaseType::Disconnected {
+        return Err("No connection established".to_string());
+    }
+
+    let (layout, files) = read_migration_directory(&request.directory, &db_type)?;
+    let history = get_migration_history(app_state, &request.database).await?;
+
+    // The external table is optional; a missing table just means no external runs
+    let external = {
+        let mut target = connect_database_target(app_state, &db_type, &request.database).await?;
+        match target.execute(external_history_query(layout), None).await {
+            Ok((results, _)) => parse_external_history(layout, &results),
+            Err(_) => HashSet::new(),
+        }
+    };
+
+    let files = match_applied_files(layout, files, &history, &external);
+    let pending = files.iter().filter(|f| !f.applied).count();
+    let mut warnings = out_of_order_warnings(&files);
+
+    let latest_applied = files
+        .iter()
+        .rev()
+        .find(|f| f.applied && f.file.kind == MigrationFileKind::Versioned);
+    let expected_snapshot_id = latest_applied.and_then(|f| f.result_snapshot_id);
+    if let (Some(file), None) = (latest_applied, expected_snapshot_id) {
+        warnings.push(format!(
+            "No snapshot was recorded after {}; apply migrations through TactileSQL to check drift",
+            file.file.file_name
+        ));
+    }
+
+    let mut drift = None;
+    if let Some(snapshot_id) = expected_snapshot_id {
+        let expected = {
+            let guard = app_state.schema_tracker_store.lock().await;
+            match guard.as_ref() {
+                Some(store) => store.get_snapshot(snapshot_id).await?,
+                None => None,
+            }
+        };
+        match expected {
+            Some(expected) => {
+                let live = crate::schema_tracker::commands::capture_live_snapshot(
+                    app_state,
+                    &request.connection_id,
+                    &request.database,
+                )
+                .await?;
+                drift = Some(crate::schema_tracker::diff::compare_schemas(
+                    &expected, &live,
+                ));
+            }
+            None => warnings.push(format!("Snapshot {} is no longer stored", snapshot_id)),
+        }
+    }
+
+    Ok(MigrationDirectoryStatus {
+        layout,
+        files,
+        pending,
+        expected_snapshot_id,
+        drift,
+        warnings,
+    })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,247 @@
+use super::*;
+
+fn plan(script: &str, rollback: &str) -> MigrationPlan {
+    MigrationPlan {
+        script: script.to_string(),
+        warnings: Vec::new(),
+        external_commands: Vec::new(),
+        unsupported_statements: Vec::new(),
+        strategy: "native".to_string(),
+        rollback_script: rollback.to_string(),
+        irreversible_changes: Vec::new(),
+    }
+}
+
+fn history_entry(checksum: &str, snapshot_id: Option<i64>) -> MigrationHistoryEntry {
+    MigrationHistoryEntry {
+        checksum: checksum.to_string(),
+        status: "applied".to_string(),
+        applied_at: "2024-01-01T00:00:00+00:00".to_string(),
+        result_snapshot_id: snapshot_id,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn flyway_export_writes_versioned_and_undo_files() {
+    let plan = plan(
+        "-- Migration Strategy: native\n\nALTER TABLE users ADD COLUMN age INT;",
+        "-- Rollback Script\n\nALTER TABLE users DROP COLUMN age;",
+    );
+    let files = render_migration_files(
+        &plan,
+        &DatabaseType::MySQL,
+        MigrationFileLayout::Flyway,
+        "2",
+        "Add user age!",
+    )
+    .unwrap();
+
+    assert_eq!(files.len(), 2);
+    assert_eq!(files[0].0, "V2__add_user_age.sql");
+    assert!(files[0].1.contains("ADD COLUMN age INT;"));
+    assert_eq!(files[1].0, "U2__add_user_age.sql");
+    assert!(files[1].1.contains("DROP COLUMN age;"));
+
+    let without_rollback = render_migration_files(
+        &self::plan("CREATE TABLE t (id INT);", "-- Rollback Script\n"),
+        &DatabaseType::MySQL,
+        MigrationFileLayout::Flyway,
+        "3",
+        "",
+    )
+    .unwrap();
+    assert_eq!(without_rollback.len(), 1);
+    assert_eq!(without_rollback[0].0, "V3__migration.sql");
+}
+
+#[test]
+fn export_rejects_invalid_versions() {
+    let plan = plan("CREATE TABLE t (id INT);", "");
+    for version in ["", "v1", "1__2", "1-2"] {
+        assert!(render_migration_files(
+            &plan,
+            &DatabaseType::MySQL,
+            MigrationFileLayout::Flyway,
+            version,
+            "x"
+        )
+        .is_err());
+    }
+}
+
+#[test]
+fn liquibase_export_round_trips_through_reader() {
+    let plan = plan(
+        "-- Migration Strategy: native\n\nCREATE TABLE t (id INT);\nGO",
+        "-- Rollback Script\n\nDROP TABLE t;\nGO",
+    );
+    let files = render_migration_files(
+        &plan,
+        &DatabaseType::MSSQL,
+        MigrationFileLayout::Liquibase,
+        "20240101120000",
+        "Create t",
+    )
+    .unwrap();
+    assert_eq!(files.len(), 1);
+    let (file_name, content) = &files[0];
+    assert!(content.starts_with("--liquibase formatted sql"));
+    assert!(content.contains("--changeset tactilesql:20240101120000 endDelimiter:GO"));
+    assert!(content.contains("--rollback DROP TABLE t;"));
+
+    let parsed = parse_liquibase_file(file_name, content, &DatabaseType::MSSQL);
+    assert_eq!(parsed.len(), 1);
+    assert_eq!(parsed[0].version.as_deref(), Some("20240101120000"));
+    assert_eq!(parsed[0].description, "Create t");
+    assert_eq!(parsed[0].undo_script.as_deref(), Some("DROP TABLE t;\nGO"));
+    assert_eq!(
+        parsed[0].checksum,
+        migration_checksum(&split_sql_script(&plan.script, &DatabaseType::MSSQL))
+    );
+}
+
+#[test]
+fn flyway_names_are_parsed() {
+    assert_eq!(
+        parse_flyway_file_name("V1_2__add_users.sql"),
+        Some(FlywayName::Versioned {
+            version: "1.2".to_string(),
+            description: "add users".to_string(),
+        })
+    );
+    assert_eq!(
+        parse_flyway_file_name("U3__drop.sql"),
+        Some(FlywayName::Undo {
+            version: "3".to_string()
+        })
+    );
+    assert_eq!(
+        parse_flyway_file_name("R__views.sql"),
+        Some(FlywayName::Repeatable {
+            description: "views".to_string()
+        })
+    );
+    assert_eq!(parse_flyway_file_name("__x.sql"), None);
+    assert_eq!(parse_flyway_file_name("V1_add.sql"), None);
+    assert_eq!(parse_flyway_file_name("notes.txt"), None);
+}
+
+#[test]
+fn flyway_files_sort_numerically_and_pair_undo() {
+    let entries = vec![
+        (
+            "R__views.sql".to_string(),
+            "CREATE VIEW v AS SELECT 1;".to_string(),
+        ),
+        ("U1_10__c.sql".to_string(), "DROP TABLE c;".to_string()),
+        (
+            "V1_10__c.sql".to_string(),
+            "CREATE TABLE c (id INT);".to_string(),
+        ),
+        (
+            "V1_9__b.sql".to_string(),
+            "CREATE TABLE b (id INT);".to_string(),
+        ),
+        (
+            "V1__a.sql".to_string(),
+            "CREATE TABLE a (id INT);".to_string(),
+        ),
+    ];
+    let files = parse_flyway_files(&entries, &DatabaseType::PostgreSQL).unwrap();
+
+    let names: Vec<&str> = files.iter().map(|f| f.file_name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec!["V1__a.sql", "V1_9__b.sql", "V1_10__c.sql", "R__views.sql"]
+    );
+    assert_eq!(files[2].undo_script.as_deref(), Some("DROP TABLE c;"));
+    assert_eq!(files[3].kind, MigrationFileKind::Repeatable);
+
+    let duplicate = vec![
+        ("V1__a.sql".to_string(), "SELECT 1;".to_string()),
+        ("V1__b.sql".to_string(), "SELECT 2;".to_string()),
+    ];
+    assert!(parse_flyway_files(&duplicate, &DatabaseType::PostgreSQL).is_err());
+}
+
+#[test]
+fn applied_files_match_by_checksum_or_external_version() {
+    let entries = vec![
+        (
+            "V1__a.sql".to_string(),
+            "CREATE TABLE a (id INT);".to_string(),
+        ),
+        (
+            "V2__b.sql".to_string(),
+            "CREATE TABLE b (id INT);".to_string(),
+        ),
+        (
+            "V3__c.sql".to_string(),
+            "CREATE TABLE c (id INT);".to_string(),
+        ),
+    ];
+    let files = parse_flyway_files(&entries, &DatabaseType::MySQL).unwrap();
+    let history = vec![history_entry(&files[1].checksum, Some(9))];
+    let external: HashSet<String> = ["1".to_string()].into_iter().collect();
+
+    let statuses = match_applied_files(MigrationFileLayout::Flyway, files, &history, &external);
+    assert!(statuses[0].applied);
+    assert_eq!(statuses[0].applied_by.as_deref(), Some("flyway"));
+    assert!(statuses[1].applied);
+    assert_eq!(statuses[1].applied_by.as_deref(), Some("tactilesql"));
+    assert_eq!(statuses[1].result_snapshot_id, Some(9));
+    assert!(!statuses[2].applied);
+    assert!(out_of_order_warnings(&statuses).is_empty());
+}
+
+#[test]
+fn pending_file_before_applied_one_is_reported() {
+    let entries = vec![
+        (
+            "V1__a.sql".to_string(),
+            "CREATE TABLE a (id INT);".to_string(),
+        ),
+        (
+            "V2__b.sql".to_string(),
+            "CREATE TABLE b (id INT);".to_string(),
+        ),
+    ];
+    let files = parse_flyway_files(&entries, &DatabaseType::MySQL).unwrap();
+    let external: HashSet<String> = ["2".to_string()].into_iter().collect();
+
+    let statuses = match_applied_files(MigrationFileLayout::Flyway, files, &[], &external);
+    let warnings = out_of_order_warnings(&statuses);
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].starts_with("V1__a.sql is pending"));
+}
+
+#[test]
+fn external_history_respects_success_flags() {
+    let result = |rows: Vec<Vec<serde_json::Value>>| QueryResult {
+        columns: vec!["version".to_string(), "success".to_string()],
+        rows,
+        query_id: None,
+        statistics: None,
+        warnings: Vec::new(),
+    };
+
+    let flyway = parse_external_history(
+        MigrationFileLayout::Flyway,
+        &[result(vec![
+            vec![serde_json::json!("1"), serde_json::json!(true)],
+            vec![serde_json::json!("2"), serde_json::json!(0)],
+            vec![serde_json::json!("3"), serde_json::json!(1)],
+        ])],
+    );
+    assert!(flyway.contains("1") && flyway.contains("3") && !flyway.contains("2"));
+
+    let liquibase = parse_external_history(
+        MigrationFileLayout::Liquibase,
+        &[result(vec![
+            vec![serde_json::json!("a"), serde_json::json!("EXECUTED")],
+            vec![serde_json::json!("b"), serde_json::json!("FAILED")],
+        ])],
+    );
+    assert!(liquibase.contains("a") && !liquibase.contains("b"));
+}
@@ -1,9 +1,15 @@
+pub mod apply;
 pub mod capture;
 pub mod commands;
+pub mod ddl_import;
 pub mod diff;
 pub use diff::*;
+pub mod drift;
 pub mod migration;
+pub mod migration_files;
 pub mod models;
 pub use models::*;
+pub mod online_schema_change;
 pub mod storage;
 pub use storage::*;
+pub mod type_mapping;
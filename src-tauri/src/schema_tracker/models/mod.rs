@@ -2,6 +2,7 @@ use crate::db_types::{
     ColumnSchema, ForeignKey, PrimaryKey, RoutineInfo, TableConstraint, TableIndex, TriggerInfo,
     ViewDefinition,
 };
+use crate::schema_tracker::diff::BreakingChange;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -34,6 +35,37 @@ pub struct SchemaDiff {
     pub new_tables: Vec<TableDefinition>,
     pub dropped_tables: Vec<TableDefinition>, // Store full def for revert scripts
     pub modified_tables: Vec<TableDiff>,
+    #[serde(default)]
+    pub new_views: Vec<ViewDefinition>,
+    #[serde(default)]
+    pub dropped_views: Vec<ViewDefinition>,
+    #[serde(default)]
+    pub modified_views: Vec<ViewDiff>,
+    #[serde(default)]
+    pub new_routines: Vec<RoutineInfo>,
+    #[serde(default)]
+    pub dropped_routines: Vec<RoutineInfo>, // A redefined routine shows up in both lists
+    #[serde(default)]
+    pub new_triggers: Vec<TriggerInfo>,
+    #[serde(default)]
+    pub dropped_triggers: Vec<TriggerInfo>, // A redefined trigger shows up in both lists
+    #[serde(default)]
+    pub renamed_tables: Vec<TableRename>,
+}
+
+impl SchemaDiff {
+    pub fn has_changes(&self) -> bool {
+        !self.new_tables.is_empty()
+            || !self.dropped_tables.is_empty()
+            || !self.modified_tables.is_empty()
+            || !self.new_views.is_empty()
+            || !self.dropped_views.is_empty()
+            || !self.modified_views.is_empty()
+            || !self.new_routines.is_empty()
+            || !self.dropped_routines.is_empty()
+            || !self.new_triggers.is_empty()
+            || !self.dropped_triggers.is_empty()
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
@@ -44,9 +76,52 @@ pub struct TableDiff {
     pub modified_columns: Vec<ColumnDiff>,
     pub new_indexes: Vec<TableIndex>,
     pub dropped_indexes: Vec<TableIndex>,
+    #[serde(default)]
+    pub new_foreign_keys: Vec<ForeignKey>,
+    #[serde(default)]
+    pub dropped_foreign_keys: Vec<ForeignKey>,
+    #[serde(default)]
+    pub new_constraints: Vec<TableConstraint>,
+    #[serde(default)]
+    pub dropped_constraints: Vec<TableConstraint>,
+    #[serde(default)]
+    pub renamed_columns: Vec<ColumnRename>,
     pub row_count_change: Option<i64>,
 }
 
+/// A dropped/new table pair that looks like a rename. The pair stays in
+/// `dropped_tables`/`new_tables`; migrations only rename once `confirmed` is set.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TableRename {
+    pub old_name: String,
+    pub new_name: String,
+    pub confidence: f64, // 0.0 - 1.0
+    #[serde(default)]
+    pub confirmed: bool,
+    /// Remaining differences, applied to the table under its new name
+    #[serde(default)]
+    pub changes: Option<TableDiff>,
+}
+
+/// A dropped/new column pair that looks like a rename, see `TableRename`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ColumnRename {
+    pub old_column: ColumnSchema,
+    pub new_column: ColumnSchema,
+    pub confidence: f64, // 0.0 - 1.0
+    #[serde(default)]
+    pub confirmed: bool,
+    #[serde(default)]
+    pub changes: Vec<DiffType>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ViewDiff {
+    pub view_name: String,
+    pub old_definition: String,
+    pub new_definition: String,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ColumnDiff {
     pub column_name: String,
@@ -85,3 +160,23 @@ pub struct SchemaImpactAiReport {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
+
+/// Changes found between a scheduled capture and its drift baseline.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SchemaDriftEvent {
+    pub id: Option<i64>,
+    pub connection_id: String,
+    pub database_name: Option<String>,
+    pub task_id: Option<String>,
+    pub baseline_snapshot_id: Option<i64>,
+    /// Tag that pinned the baseline; None when the previous snapshot was used
+    pub baseline_tag: Option<String>,
+    pub snapshot_id: Option<i64>,
+    pub schema_hash: String,
+    pub change_count: usize,
+    pub breaking_changes: Vec<BreakingChange>,
+    pub diff: SchemaDiff,
+    pub notified: bool,
+    pub acknowledged: bool,
+    pub detected_at: DateTime<Utc>,
+}
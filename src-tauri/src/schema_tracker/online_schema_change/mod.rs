@@ -0,0 +1,898 @@
+// =====================================================
+// ONLINE SCHEMA CHANGE MODULE
+// Runs the pt-online-schema-change / gh-ost commands of a migration plan as
+// managed subprocesses with streamed output and runtime controls
+// =====================================================
+
+use crate::db_types::AppState;
+use crate::schema_tracker::migration::{MigrationPlan, MigrationStrategy};
+use crate::task_manager::models::{LogLevel, RunStatus};
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::LazyLock;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::{Child, Command};
+use tokio::sync::Mutex;
+use tokio::time::{interval, timeout, Duration};
+
+static OSC_RUN_STORE: LazyLock<Mutex<HashMap<String, OscRunEntry>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+static PT_OSC_PROGRESS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"Copying .*?(\d+(?:\.\d+)?)%").unwrap());
+static GH_OST_PROGRESS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"Copy: \d+/\d+ (\d+(?:\.\d+)?)%").unwrap());
+
+/// Seconds a tool gets to clean up (pt-osc drops its triggers) after SIGTERM.
+const TERMINATE_GRACE_SECONDS: u64 = 30;
+const OUTPUT_TAIL_LINES: usize = 20;
+/// Finished runs stay queryable for this long before the store forgets them.
+const FINISHED_RUN_RETENTION_MINUTES: i64 = 60;
+
+// =====================================================
+// STRUCTS
+// =====================================================
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OscTool {
+    PtOsc,
+    GhOst,
+}
+
+impl OscTool {
+    pub fn binary_name(&self) -> &'static str {
+        match self {
+            Self::PtOsc => "pt-online-schema-change",
+            Self::GhOst => "gh-ost",
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::PtOsc => "pt_osc",
+            Self::GhOst => "gh_ost",
+        }
+    }
+}
+
+/// One table change of a plan, as given to the tool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OscInvocation {
+    pub table: String,
+    pub alter: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OscOptions {
+    /// Tool executable; looked up on PATH when not set
+    pub binary_path: Option<String>,
+    pub chunk_size: Option<u64>,
+    /// Status thresholds such as `Threads_running=25`
+    pub max_load: Option<String>,
+    pub max_lag_seconds: Option<u64>,
+    /// gh-ost only: copy rows but wait for an explicit cut-over
+    pub postpone_cut_over: bool,
+    /// gh-ost only: run against a primary instead of inspecting a replica
+    pub allow_on_master: Option<bool>,
+    pub dry_run: bool,
+    pub extra_args: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct OscConnection {
+    pub host: String,
+    pub port: u16,
+    pub user: String,
+    pub password: Option<String>,
+    pub database: String,
+}
+
+/// Flag files the tools poll; creating or removing them steers a running tool.
+#[derive(Debug, Clone)]
+pub struct OscControlFiles {
+    /// pt-osc `--pause-file` / gh-ost `--throttle-flag-file`
+    pub pause: PathBuf,
+    /// gh-ost `--postpone-cut-over-flag-file`
+    pub postpone_cut_over: Option<PathBuf>,
+    /// `[client]` option file with the password, pt-osc `--defaults-file` / gh-ost `--conf`
+    pub credentials: PathBuf,
+}
+
+impl OscControlFiles {
+    pub fn new(dir: &Path, tool: OscTool, options: &OscOptions) -> Self {
+        Self {
+            pause: dir.join("pause.flag"),
+            credentials: dir.join("client.cnf"),
+            postpone_cut_over: (tool == OscTool::GhOst && options.postpone_cut_over)
+                .then(|| dir.join("postpone-cut-over.flag")),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OscRunStatus {
+    Running,
+    Paused,
+    WaitingForCutOver,
+    Completed,
+    Failed,
+    Aborted,
+}
+
+impl OscRunStatus {
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::Completed | Self::Failed | Self::Aborted)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OscRunState {
+    pub run_id: String,
+    pub task_id: String,
+    pub tool: OscTool,
+    pub status: OscRunStatus,
+    pub current_step: usize,
+    pub total_steps: usize,
+    pub table: Option<String>,
+    pub progress_pct: Option<f64>,
+    pub cut_over_postponed: bool,
+    pub last_output: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OscControlAction {
+    /// Throttles row copying; replication of ongoing writes continues
+    Pause,
+    Resume,
+    CutOver,
+    Abort,
+}
+
+impl OscControlAction {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "pause" | "throttle" => Ok(Self::Pause),
+            "resume" | "unthrottle" | "no_throttle" | "no-throttle" => Ok(Self::Resume),
+            "cut_over" | "cutover" | "cut-over" => Ok(Self::CutOver),
+            "abort" | "cancel" => Ok(Self::Abort),
+            other => Err(format!("Unknown online schema change action: {}", other)),
+        }
+    }
+}
+
+struct OscRunEntry {
+    state: OscRunState,
+    files: OscControlFiles,
+    abort_requested: bool,
+}
+
+pub struct OscRunRequest {
+    pub run_id: String,
+    pub task_id: String,
+    /// Stream output into the task run logs of `run_id`
+    pub log_to_run: bool,
+    pub tool: OscTool,
+    pub invocations: Vec<OscInvocation>,
+    pub connection: OscConnection,
+    pub options: OscOptions,
+}
+
+// =====================================================
+// PLAN PARSING
+// =====================================================
+
+/// Splits a generated command line on whitespace outside double quotes; `\"`
+/// inside quotes is a literal quote.
+fn split_command_line(command: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut has_token = false;
+    let mut chars = command.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' => {
+                in_quotes = !in_quotes;
+                has_token = true;
+            }
+            '\\' if in_quotes && chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if has_token {
+                    args.push(std::mem::take(&mut current));
+                    has_token = false;
+                }
+            }
+            c => {
+                current.push(c);
+                has_token = true;
+            }
+        }
+    }
+    if has_token {
+        args.push(current);
+    }
+    args
+}
+
+/// Reads the table and ALTER clause back out of a command from `external_commands`.
+pub fn parse_external_command(tool: OscTool, command: &str) -> Result<OscInvocation, String> {
+    let args = split_command_line(command);
+    let (table, alter) = match tool {
+        OscTool::PtOsc => {
+            let alter = args
+                .iter()
+                .position(|arg| arg == "--alter")
+                .and_then(|idx| args.get(idx + 1))
+                .cloned();
+            // The DSN is the argument made of key=value pairs only
+            let table = args
+                .iter()
+                .filter(|arg| !arg.starts_with("--") && arg.split(',').all(|p| p.contains('=')))
+                .flat_map(|arg| arg.split(','))
+                .find_map(|part| part.strip_prefix("t="))
+                .map(str::to_string);
+            (table, alter)
+        }
+        OscTool::GhOst => {
+            let value = |flag: &str| {
+                args.iter()
+                    .find_map(|arg| arg.strip_prefix(flag))
+                    .map(str::to_string)
+            };
+            (value("--table="), value("--alter="))
+        }
+    };
+
+    match (table, alter) {
+        (Some(table), Some(alter)) if !table.is_empty() && !alter.trim().is_empty() => {
+            Ok(OscInvocation { table, alter })
+        }
+        _ => Err(format!(
+            "Could not read table and ALTER clause from command: {}",
+            command
+        )),
+    }
+}
+
+/// Tool and table changes of an OSC-mode plan.
+pub fn plan_invocations(plan: &MigrationPlan) -> Result<(OscTool, Vec<OscInvocation>), String> {
+    let tool = match MigrationStrategy::from_str(&plan.strategy)? {
+        MigrationStrategy::PtOsc => OscTool::PtOsc,
+        MigrationStrategy::GhOst => OscTool::GhOst,
+        other => {
+            return Err(format!(
+                "Migration plan uses the {} strategy, not an online schema change tool",
+                other.as_str()
+            ))
+        }
+    };
+    if !plan.unsupported_statements.is_empty() {
+        return Err(format!(
+            "{} statement(s) cannot run through {}; apply them separately first: {}",
+            plan.unsupported_statements.len(),
+            tool.binary_name(),
+            plan.unsupported_statements.join("; ")
+        ));
+    }
+    if plan.external_commands.is_empty() {
+        return Err("Migration plan has no online schema change commands".to_string());
+    }
+
+    let invocations = plan
+        .external_commands
+        .iter()
+        .map(|command| parse_external_command(tool, command))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((tool, invocations))
+}
+
+// =====================================================
+// COMMAND LINE
+// =====================================================
+
+pub fn build_osc_args(
+    tool: OscTool,
+    invocation: &OscInvocation,
+    connection: &OscConnection,
+    options: &OscOptions,
+    files: &OscControlFiles,
+) -> Vec<String> {
+    let mut args = Vec::new();
+    match tool {
+        OscTool::PtOsc => {
+            args.push("--alter".to_string());
+            args.push(invocation.alter.clone());
+            args.push(format!(
+                "h={},P={},u={},D={},t={}",
+                connection.host,
+                connection.port,
+                connection.user,
+                connection.database,
+                invocation.table
+            ));
+            if connection.password.is_some() {
+                args.push(format!("--defaults-file={}", files.credentials.display()));
+            }
+            args.push(format!("--pause-file={}", files.pause.display()));
+            args.push("--progress=time,5".to_string());
+            if let Some(chunk_size) = options.chunk_size {
+                args.push(format!("--chunk-size={}", chunk_size));
+            }
+            if let Some(max_load) = &options.max_load {
+                args.push(format!("--max-load={}", max_load));
+            }
+            if let Some(max_lag) = options.max_lag_seconds {
+                args.push(format!("--max-lag={}", max_lag));
+            }
+            args.extend(options.extra_args.iter().cloned());
+            args.push(
+                if options.dry_run {
+                    "--dry-run"
+                } else {
+                    "--execute"
+                }
+                .to_string(),
+            );
+        }
+        OscTool::GhOst => {
+            args.push(format!("--host={}", connection.host));
+            args.push(format!("--port={}", connection.port));
+            args.push(format!("--user={}", connection.user));
+            if connection.password.is_some() {
+                args.push(format!("--conf={}", files.credentials.display()));
+            }
+            args.push(format!("--database={}", connection.database));
+            args.push(format!("--table={}", invocation.table));
+            args.push(format!("--alter={}", invocation.alter));
+            args.push(format!("--throttle-flag-file={}", files.pause.display()));
+            if let Some(postpone) = &files.postpone_cut_over {
+                args.push(format!(
+                    "--postpone-cut-over-flag-file={}",
+                    postpone.display()
+                ));
+            }
+            if let Some(chunk_size) = options.chunk_size {
+                args.push(format!("--chunk-size={}", chunk_size));
+            }
+            if let Some(max_load) = &options.max_load {
+                args.push(format!("--max-load={}", max_load));
+            }
+            if let Some(max_lag) = options.max_lag_seconds {
+                args.push(format!("--max-lag-millis={}", max_lag * 1000));
+            }
+            if options.allow_on_master.unwrap_or(true) {
+                args.push("--allow-on-master".to_string());
+            }
+            args.extend(options.extra_args.iter().cloned());
+            if !options.dry_run {
+                args.push("--execute".to_string());
+            }
+        }
+    }
+    args
+}
+
+/// Contents of the option file both tools read the password from.
+pub fn credentials_file_contents(password: &str) -> String {
+    format!(
+        "[client]\npassword=\"{}\"\n",
+        password.replace('\\', "\\\\").replace('"', "\\\"")
+    )
+}
+
+/// Writes the password where only the current user can read it, so it never
+/// appears on the tool's command line.
+fn write_credentials_file(path: &Path, password: &str) -> Result<(), String> {
+    let mut open = fs::OpenOptions::new();
+    open.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut open, 0o600);
+    let mut file = open
+        .open(path)
+        .map_err(|e| format!("Failed to create credentials file: {}", e))?;
+    file.write_all(credentials_file_contents(password).as_bytes())
+        .map_err(|e| format!("Failed to write credentials file: {}", e))
+}
+
+/// Command line for logs, with any password passed through `extraArgs` masked.
+pub fn display_command(binary: &Path, args: &[String]) -> String {
+    let mut parts = vec![binary.display().to_string()];
+    parts.extend(args.iter().map(|arg| {
+        let shown = if arg.starts_with("--password=") {
+            "--password=******".to_string()
+        } else {
+            arg.clone()
+        };
+        if shown.contains(char::is_whitespace) {
+            format!("\"{}\"", shown.replace('"', "\\\""))
+        } else {
+            shown
+        }
+    }));
+    parts.join(" ")
+}
+
+/// Copy progress reported in a line of tool output.
+pub fn parse_progress(tool: OscTool, line: &str) -> Option<f64> {
+    let pattern = match tool {
+        OscTool::PtOsc => &PT_OSC_PROGRESS,
+        OscTool::GhOst => &GH_OST_PROGRESS,
+    };
+    pattern
+        .captures(line)
+        .and_then(|caps| caps[1].parse::<f64>().ok())
+}
+
+fn output_level(line: &str) -> LogLevel {
+    let upper = line.to_ascii_uppercase();
+    if upper.contains("ERROR") || upper.contains("FATAL") {
+        LogLevel::Error
+    } else if upper.contains("WARN") {
+        LogLevel::Warning
+    } else {
+        LogLevel::Info
+    }
+}
+
+/// Finds the tool executable and checks that it starts.
+pub async fn resolve_binary(
+    tool: OscTool,
+    options: &OscOptions,
+) -> Result<(PathBuf, String), String> {
+    let binary = match options.binary_path.as_deref().map(str::trim) {
+        Some(path) if !path.is_empty() => {
+            let path = PathBuf::from(path);
+            if !path.is_file() {
+                return Err(format!(
+                    "{} was not found at '{}'",
+                    tool.binary_name(),
+                    path.display()
+                ));
+            }
+            path
+        }
+        _ => std::env::var_os("PATH")
+            .iter()
+            .flat_map(std::env::split_paths)
+            .flat_map(|dir| {
+                [
+                    dir.join(tool.binary_name()),
+                    dir.join(format!("{}.exe", tool.binary_name())),
+                ]
+            })
+            .find(|candidate| candidate.is_file())
+            .ok_or_else(|| {
+                format!(
+                    "{} was not found on PATH; install it or set options.binaryPath",
+                    tool.binary_name()
+                )
+            })?,
+    };
+
+    let output = timeout(
+        Duration::from_secs(15),
+        Command::new(&binary).arg("--version").output(),
+    )
+    .await
+    .map_err(|_| format!("{} --version did not finish", binary.display()))?
+    .map_err(|e| format!("Failed to run {}: {}", binary.display(), e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "{} --version failed: {}",
+            binary.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let version = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .chain(String::from_utf8_lossy(&output.stderr).lines())
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default()
+        .to_string();
+    Ok((binary, version))
+}
+
+// =====================================================
+// CONTROLS
+// =====================================================
+
+pub async fn get_online_schema_change_status(run_id: &str) -> Result<OscRunState, String> {
+    let store = OSC_RUN_STORE.lock().await;
+    store
+        .get(run_id.trim())
+        .map(|entry| entry.state.clone())
+        .ok_or_else(|| format!("Online schema change run '{}' not found", run_id))
+}
+
+pub async fn control_online_schema_change(
+    run_id: &str,
+    action: OscControlAction,
+) -> Result<OscRunState, String> {
+    let mut store = OSC_RUN_STORE.lock().await;
+    let entry = store
+        .get_mut(run_id.trim())
+        .ok_or_else(|| format!("Online schema change run '{}' not found", run_id))?;
+    if entry.state.status.is_terminal() {
+        return Err(format!(
+            "Online schema change run '{}' has already finished",
+            run_id
+        ));
+    }
+
+    match action {
+        OscControlAction::Pause => {
+            fs::write(&entry.files.pause, b"")
+                .map_err(|e| format!("Failed to create pause flag file: {}", e))?;
+            entry.state.status = OscRunStatus::Paused;
+        }
+        OscControlAction::Resume => {
+            if entry.files.pause.exists() {
+                fs::remove_file(&entry.files.pause)
+                    .map_err(|e| format!("Failed to remove pause flag file: {}", e))?;
+            }
+            entry.state.status = if entry.state.cut_over_postponed {
+                OscRunStatus::WaitingForCutOver
+            } else {
+                OscRunStatus::Running
+            };
+        }
+        OscControlAction::CutOver => {
+            let postpone = entry
+                .files
+                .postpone_cut_over
+                .as_ref()
+                .filter(|path| path.exists())
+                .ok_or_else(|| match entry.state.tool {
+                    OscTool::PtOsc => {
+                        "pt-online-schema-change swaps tables on its own; cut-over cannot be triggered"
+                            .to_string()
+                    }
+                    OscTool::GhOst => {
+                        "Cut-over was not postponed for this run, or has already been released"
+                            .to_string()
+                    }
+                })?;
+            fs::remove_file(postpone)
+                .map_err(|e| format!("Failed to remove cut-over flag file: {}", e))?;
+            entry.state.cut_over_postponed = false;
+            if entry.state.status == OscRunStatus::WaitingForCutOver {
+                entry.state.status = OscRunStatus::Running;
+            }
+        }
+        OscControlAction::Abort => entry.abort_requested = true,
+    }
+    entry.state.updated_at = Utc::now();
+    Ok(entry.state.clone())
+}
+
+// =====================================================
+// EXECUTION
+// =====================================================
+
+async fn log_to_run(
+    app_state: &AppState,
+    request: &OscRunRequest,
+    level: LogLevel,
+    message: &str,
+    metadata: serde_json::Value,
+) {
+    if !request.log_to_run {
+        return;
+    }
+    let guard = app_state.task_manager_store.lock().await;
+    if let Some(store) = guard.as_ref() {
+        if let Err(e) = store
+            .append_task_run_log(&request.run_id, &request.task_id, level, message, metadata)
+            .await
+        {
+            eprintln!("Failed to log online schema change output: {}", e);
+        }
+    }
+}
+
+async fn stop_requested(app_state: &AppState, request: &OscRunRequest) -> bool {
+    if OSC_RUN_STORE
+        .lock()
+        .await
+        .get(&request.run_id)
+        .is_some_and(|entry| entry.abort_requested)
+    {
+        return true;
+    }
+    if !request.log_to_run {
+        return false;
+    }
+    let guard = app_state.task_manager_store.lock().await;
+    match guard.as_ref() {
+        Some(store) => matches!(
+            store.get_task_run(&request.run_id).await,
+            Ok(Some(run)) if run.status == RunStatus::Cancelled
+        ),
+        None => false,
+    }
+}
+
+/// Forgets runs that finished more than the retention period before `now`.
+fn prune_finished_runs(store: &mut HashMap<String, OscRunEntry>, now: DateTime<Utc>) {
+    let cutoff = now - chrono::Duration::minutes(FINISHED_RUN_RETENTION_MINUTES);
+    store.retain(|_, entry| {
+        !entry.state.status.is_terminal()
+            || entry
+                .state
+                .finished_at
+                .is_none_or(|finished| finished > cutoff)
+    });
+}
+
+async fn update_state(run_id: &str, update: impl FnOnce(&mut OscRunState)) {
+    let mut store = OSC_RUN_STORE.lock().await;
+    if let Some(entry) = store.get_mut(run_id) {
+        update(&mut entry.state);
+        entry.state.updated_at = Utc::now();
+    }
+}
+
+/// Asks the tool to stop so it can clean up, then kills it after a grace period.
+async fn terminate(child: &mut Child) {
+    #[cfg(unix)]
+    if let Some(pid) = child.id() {
+        let _ = Command::new("kill")
+            .args(["-TERM", &pid.to_string()])
+            .status()
+            .await;
+        if timeout(Duration::from_secs(TERMINATE_GRACE_SECONDS), child.wait())
+            .await
+            .is_ok()
+        {
+            return;
+        }
+    }
+    let _ = child.kill().await;
+}
+
+/// Runs one table change to completion, streaming output into the run logs.
+async fn run_invocation(
+    app_state: &AppState,
+    request: &OscRunRequest,
+    binary: &Path,
+    args: &[String],
+) -> Result<(), String> {
+    let mut child = Command::new(binary)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to start {}: {}", binary.display(), e))?;
+
+    let mut stdout = BufReader::new(child.stdout.take().ok_or("Tool stdout unavailable")?).lines();
+    let mut stderr = BufReader::new(child.stderr.take().ok_or("Tool stderr unavailable")?).lines();
+    let (mut stdout_done, mut stderr_done) = (false, false);
+    let mut tail: Vec<String> = Vec::new();
+    let mut ticker = interval(Duration::from_secs(2));
+
+    while !(stdout_done && stderr_done) {
+        let line = tokio::select! {
+            line = stdout.next_line(), if !stdout_done => match line {
+                Ok(Some(line)) => Some(line),
+                _ => { stdout_done = true; None }
+            },
+            line = stderr.next_line(), if !stderr_done => match line {
+                Ok(Some(line)) => Some(line),
+                _ => { stderr_done = true; None }
+            },
+            _ = ticker.tick() => {
+                if stop_requested(app_state, request).await {
+                    terminate(&mut child).await;
+                    return Err(format!("{} was aborted", request.tool.binary_name()));
+                }
+                None
+            }
+        };
+        let Some(line) = line
+            .map(|l| l.trim_end().to_string())
+            .filter(|l| !l.is_empty())
+        else {
+            continue;
+        };
+
+        let progress = parse_progress(request.tool, &line);
+        let postponed = line.to_ascii_lowercase().contains("postponing cut-over");
+        update_state(&request.run_id, |state| {
+            if progress.is_some() {
+                state.progress_pct = progress;
+            }
+            if postponed && state.cut_over_postponed && state.status == OscRunStatus::Running {
+                state.status = OscRunStatus::WaitingForCutOver;
+            }
+            state.last_output = Some(line.clone());
+        })
+        .await;
+        log_to_run(
+            app_state,
+            request,
+            output_level(&line),
+            &line,
+            serde_json::json!({ "tool": request.tool.as_str(), "progressPct": progress }),
+        )
+        .await;
+
+        tail.push(line);
+        if tail.len() > OUTPUT_TAIL_LINES {
+            tail.remove(0);
+        }
+    }
+
+    let status = child
+        .wait()
+        .await
+        .map_err(|e| format!("Failed to wait for {}: {}", binary.display(), e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} exited with {}: {}",
+            request.tool.binary_name(),
+            status,
+            tail.last().cloned().unwrap_or_default()
+        ))
+    }
+}
+
+/// Runs every table change of the request in order and returns the final state.
+/// The run stays queryable through [`get_online_schema_change_status`] for
+/// `FINISHED_RUN_RETENTION_MINUTES` afterwards.
+pub async fn run_online_schema_change(
+    app_state: &AppState,
+    request: OscRunRequest,
+) -> Result<OscRunState, String> {
+    let (binary, version) = resolve_binary(request.tool, &request.options).await?;
+    let control_dir = std::env::temp_dir()
+        .join("tactilesql-osc")
+        .join(&request.run_id);
+    fs::create_dir_all(&control_dir)
+        .map_err(|e| format!("Failed to create control directory: {}", e))?;
+    let files = OscControlFiles::new(&control_dir, request.tool, &request.options);
+    if let Some(postpone) = &files.postpone_cut_over {
+        fs::write(postpone, b"")
+            .map_err(|e| format!("Failed to create cut-over flag file: {}", e))?;
+    }
+    if let Some(password) = &request.connection.password {
+        if let Err(error) = write_credentials_file(&files.credentials, password) {
+            let _ = fs::remove_dir_all(&control_dir);
+            return Err(error);
+        }
+    }
+
+    let now = Utc::now();
+    let mut store = OSC_RUN_STORE.lock().await;
+    prune_finished_runs(&mut store, now);
+    store.insert(
+        request.run_id.clone(),
+        OscRunEntry {
+            state: OscRunState {
+                run_id: request.run_id.clone(),
+                task_id: request.task_id.clone(),
+                tool: request.tool,
+                status: OscRunStatus::Running,
+                current_step: 0,
+                total_steps: request.invocations.len(),
+                table: None,
+                progress_pct: None,
+                cut_over_postponed: files.postpone_cut_over.is_some(),
+                last_output: None,
+                started_at: now,
+                updated_at: now,
+                finished_at: None,
+                error: None,
+            },
+            files: files.clone(),
+            abort_requested: false,
+        },
+    );
+    drop(store);
+    log_to_run(
+        app_state,
+        &request,
+        LogLevel::Info,
+        &format!("Using {} ({})", binary.display(), version),
+        serde_json::json!({ "tool": request.tool.as_str() }),
+    )
+    .await;
+
+    let mut result = Ok(());
+    for (idx, invocation) in request.invocations.iter().enumerate() {
+        update_state(&request.run_id, |state| {
+            state.current_step = idx + 1;
+            state.table = Some(invocation.table.clone());
+            state.progress_pct = None;
+        })
+        .await;
+        let args = build_osc_args(
+            request.tool,
+            invocation,
+            &request.connection,
+            &request.options,
+            &files,
+        );
+        log_to_run(
+            app_state,
+            &request,
+            LogLevel::Info,
+            &format!(
+                "Step {}/{}: {}",
+                idx + 1,
+                request.invocations.len(),
+                display_command(&binary, &args)
+            ),
+            serde_json::json!({ "table": invocation.table, "step": idx + 1 }),
+        )
+        .await;
+
+        result = run_invocation(app_state, &request, &binary, &args).await;
+        if result.is_err() {
+            break;
+        }
+    }
+
+    let aborted = OSC_RUN_STORE
+        .lock()
+        .await
+        .get(&request.run_id)
+        .is_some_and(|entry| entry.abort_requested);
+    if let Err(error) = &result {
+        let hint = match request.tool {
+            OscTool::PtOsc => "check the table for leftover pt_osc triggers and _new tables",
+            OscTool::GhOst => "leftover _gho and _ghc tables can be dropped",
+        };
+        log_to_run(
+            app_state,
+            &request,
+            LogLevel::Error,
+            &format!("{}; {}", error, hint),
+            serde_json::json!({ "tool": request.tool.as_str() }),
+        )
+        .await;
+    }
+    let _ = fs::remove_dir_all(&control_dir);
+
+    let mut store = OSC_RUN_STORE.lock().await;
+    let entry = store
+        .get_mut(&request.run_id)
+        .ok_or("Online schema change run state was lost")?;
+    entry.state.status = match (&result, aborted) {
+        (Ok(()), _) => OscRunStatus::Completed,
+        (Err(_), true) => OscRunStatus::Aborted,
+        (Err(_), false) => OscRunStatus::Failed,
+    };
+    entry.state.error = result.as_ref().err().cloned();
+    entry.state.cut_over_postponed = false;
+    entry.state.finished_at = Some(Utc::now());
+    entry.state.updated_at = Utc::now();
+    let state = entry.state.clone();
+    drop(store);
+
+    result.map(|_| state)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,206 @@
+use super::*;
+use crate::db_types::DatabaseType;
+use crate::schema_tracker::migration::generate_migration_plan;
+use crate::schema_tracker::models::SchemaDiff;
+
+fn connection() -> OscConnection {
+    OscConnection {
+        host: "db.internal".to_string(),
+        port: 3306,
+        user: "migrator".to_string(),
+        password: Some("s3cret".to_string()),
+        database: "app".to_string(),
+    }
+}
+
+fn invocation() -> OscInvocation {
+    OscInvocation {
+        table: "users".to_string(),
+        alter: "ADD COLUMN note VARCHAR(20) DEFAULT \"x\"".to_string(),
+    }
+}
+
+fn files(tool: OscTool, options: &OscOptions) -> OscControlFiles {
+    OscControlFiles::new(Path::new("/tmp/osc/run-1"), tool, options)
+}
+
+#[test]
+fn generated_commands_parse_back_into_invocations() {
+    let pt = parse_external_command(
+        OscTool::PtOsc,
+        "pt-online-schema-change --alter \"ADD COLUMN note VARCHAR(20) DEFAULT \\\"x\\\"\" D=<database>,t=users --execute",
+    )
+    .unwrap();
+    assert_eq!(pt, invocation());
+
+    let gh = parse_external_command(
+        OscTool::GhOst,
+        "gh-ost --host=\"<host>\" --user=\"<user>\" --database=\"<database>\" --table=\"users\" --alter=\"ADD COLUMN note VARCHAR(20) DEFAULT \\\"x\\\"\" --execute",
+    )
+    .unwrap();
+    assert_eq!(gh, invocation());
+
+    assert!(parse_external_command(OscTool::GhOst, "gh-ost --table=\"users\"").is_err());
+}
+
+#[test]
+fn plans_must_use_an_osc_strategy() {
+    let mut plan = generate_migration_plan(&SchemaDiff::default(), &DatabaseType::MySQL, None);
+    assert!(plan_invocations(&plan).is_err());
+
+    plan.strategy = "gh_ost".to_string();
+    plan.external_commands = vec![
+        "gh-ost --table=\"orders\" --alter=\"ADD INDEX idx_total (total)\" --execute".to_string(),
+    ];
+    let (tool, invocations) = plan_invocations(&plan).unwrap();
+    assert_eq!(tool, OscTool::GhOst);
+    assert_eq!(invocations[0].table, "orders");
+
+    plan.unsupported_statements = vec!["CREATE TABLE t (id INT)".to_string()];
+    assert!(plan_invocations(&plan).is_err());
+}
+
+#[test]
+fn pt_osc_arguments_carry_connection_and_controls() {
+    let options = OscOptions {
+        chunk_size: Some(500),
+        max_lag_seconds: Some(3),
+        dry_run: true,
+        ..Default::default()
+    };
+    let args = build_osc_args(
+        OscTool::PtOsc,
+        &invocation(),
+        &connection(),
+        &options,
+        &files(OscTool::PtOsc, &options),
+    );
+
+    assert_eq!(args[0], "--alter");
+    assert_eq!(args[1], invocation().alter);
+    assert_eq!(args[2], "h=db.internal,P=3306,u=migrator,D=app,t=users");
+    assert!(args.contains(&"--pause-file=/tmp/osc/run-1/pause.flag".to_string()));
+    assert!(args.contains(&"--chunk-size=500".to_string()));
+    assert!(args.contains(&"--max-lag=3".to_string()));
+    assert_eq!(args.last().unwrap(), "--dry-run");
+
+    assert!(args.contains(&"--defaults-file=/tmp/osc/run-1/client.cnf".to_string()));
+    assert!(!args.iter().any(|arg| arg.contains("s3cret")));
+
+    let shown = display_command(Path::new("pt-online-schema-change"), &args);
+    assert!(shown.contains("\"ADD COLUMN note VARCHAR(20) DEFAULT \\\"x\\\"\""));
+}
+
+#[test]
+fn gh_ost_arguments_postpone_cut_over_when_asked() {
+    let options = OscOptions {
+        postpone_cut_over: true,
+        max_lag_seconds: Some(2),
+        allow_on_master: Some(false),
+        ..Default::default()
+    };
+    let args = build_osc_args(
+        OscTool::GhOst,
+        &invocation(),
+        &connection(),
+        &options,
+        &files(OscTool::GhOst, &options),
+    );
+
+    assert!(args.contains(&"--table=users".to_string()));
+    assert!(args.contains(&"--conf=/tmp/osc/run-1/client.cnf".to_string()));
+    assert!(!args.iter().any(|arg| arg.contains("s3cret")));
+    assert!(args.contains(&"--throttle-flag-file=/tmp/osc/run-1/pause.flag".to_string()));
+    assert!(args.contains(
+        &"--postpone-cut-over-flag-file=/tmp/osc/run-1/postpone-cut-over.flag".to_string()
+    ));
+    assert!(args.contains(&"--max-lag-millis=2000".to_string()));
+    assert!(!args.contains(&"--allow-on-master".to_string()));
+    assert_eq!(args.last().unwrap(), "--execute");
+
+    // pt-osc has no postponed cut-over
+    let pt_options = OscOptions {
+        postpone_cut_over: true,
+        ..Default::default()
+    };
+    assert!(files(OscTool::PtOsc, &pt_options)
+        .postpone_cut_over
+        .is_none());
+}
+
+#[test]
+fn progress_and_actions_are_parsed() {
+    assert_eq!(
+        parse_progress(OscTool::PtOsc, "Copying `app`.`users`:  42% 01:23 remain"),
+        Some(42.0)
+    );
+    assert_eq!(
+        parse_progress(
+            OscTool::GhOst,
+            "Copy: 1500/10000 15.0%; Applied: 0; Backlog: 0/1000; Time: 3s(total), 2s(copy)"
+        ),
+        Some(15.0)
+    );
+    assert_eq!(
+        parse_progress(OscTool::GhOst, "Migrating `app`.`users`"),
+        None
+    );
+
+    assert_eq!(
+        OscControlAction::parse("throttle").unwrap(),
+        OscControlAction::Pause
+    );
+    assert_eq!(
+        OscControlAction::parse("Cut-Over").unwrap(),
+        OscControlAction::CutOver
+    );
+    assert!(OscControlAction::parse("rewind").is_err());
+}
+
+#[test]
+fn credentials_file_quotes_the_password() {
+    assert_eq!(
+        credentials_file_contents("pa\"ss\\#1"),
+        "[client]\npassword=\"pa\\\"ss\\\\#1\"\n"
+    );
+}
+
+#[test]
+fn finished_runs_are_pruned_after_retention() {
+    let now = Utc::now();
+    let entry = |status: OscRunStatus, finished_minutes_ago: Option<i64>| {
+        let finished_at = finished_minutes_ago.map(|m| now - chrono::Duration::minutes(m));
+        OscRunEntry {
+            state: OscRunState {
+                run_id: "run".to_string(),
+                task_id: "task".to_string(),
+                tool: OscTool::GhOst,
+                status,
+                current_step: 1,
+                total_steps: 1,
+                table: None,
+                progress_pct: None,
+                cut_over_postponed: false,
+                last_output: None,
+                started_at: now,
+                updated_at: now,
+                finished_at,
+                error: None,
+            },
+            files: files(OscTool::GhOst, &OscOptions::default()),
+            abort_requested: false,
+        }
+    };
+    let mut store = HashMap::new();
+    store.insert("running".to_string(), entry(OscRunStatus::Running, None));
+    store.insert(
+        "recent".to_string(),
+        entry(OscRunStatus::Completed, Some(5)),
+    );
+    store.insert("old".to_string(), entry(OscRunStatus::Failed, Some(120)));
+
+    prune_finished_runs(&mut store, now);
+    let mut kept: Vec<&str> = store.keys().map(String::as_str).collect();
+    kept.sort();
+    assert_eq!(kept, vec!["recent", "running"]);
+}
@@ -1,4 +1,5 @@
-use crate::schema_tracker::models::{SchemaImpactAiReport, SchemaSnapshot};
+use crate::schema_tracker::capture::carry_unchanged_hash;
+use crate::schema_tracker::models::{SchemaDriftEvent, SchemaImpactAiReport, SchemaSnapshot};
 use sqlx::{sqlite::SqliteRow, Pool, Row, Sqlite};
 
 pub struct SchemaTrackerStore {
@@ -46,10 +47,22 @@ impl SchemaTrackerStore {
                 updated_at INTEGER NOT NULL,
                 UNIQUE(connection_id, base_snapshot_id, target_snapshot_id)
             );
+
+            CREATE TABLE IF NOT EXISTS schema_drift_events (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                connection_id TEXT NOT NULL,
+                database_name TEXT,
+                baseline_snapshot_id INTEGER,
+                schema_hash TEXT NOT NULL,
+                event_data BLOB NOT NULL,
+                acknowledged INTEGER NOT NULL DEFAULT 0,
+                detected_at INTEGER NOT NULL
+            );
             
             CREATE INDEX IF NOT EXISTS idx_snapshots_connection ON schema_snapshots(connection_id, timestamp);
             CREATE INDEX IF NOT EXISTS idx_versions_snapshot ON schema_versions(snapshot_id);
             CREATE INDEX IF NOT EXISTS idx_schema_ai_impact_pair ON schema_ai_impact_reports(connection_id, base_snapshot_id, target_snapshot_id);
+            CREATE INDEX IF NOT EXISTS idx_schema_drift_connection ON schema_drift_events(connection_id, detected_at);
             "#
         )
         .execute(&self.pool)
@@ -92,6 +105,49 @@ impl SchemaTrackerStore {
         Ok(id)
     }
 
+    /// Most recent snapshot of the connection, limited to `database_name` when given.
+    pub async fn get_latest_snapshot(
+        &self,
+        connection_id: &str,
+        database_name: Option<&str>,
+    ) -> Result<Option<SchemaSnapshot>, String> {
+        let row = sqlx::query(
+            r#"
+            SELECT id
+            FROM schema_snapshots
+            WHERE connection_id = ? AND (? IS NULL OR database_name = ?)
+            ORDER BY timestamp DESC, id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(connection_id)
+        .bind(database_name)
+        .bind(database_name)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch latest snapshot: {}", e))?;
+
+        match row {
+            Some(row) => {
+                let id: i64 = row.try_get("id").map_err(|e| e.to_string())?;
+                self.get_snapshot(id).await
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Gives `snapshot` the latest stored snapshot's hash when the schema did not
+    /// change since, so saving it refreshes that row instead of adding one.
+    pub async fn adopt_latest_hash(&self, snapshot: &mut SchemaSnapshot) -> Result<(), String> {
+        let latest = self
+            .get_latest_snapshot(&snapshot.connection_id, snapshot.database_name.as_deref())
+            .await?;
+        if let Some(latest) = latest {
+            carry_unchanged_hash(snapshot, &latest);
+        }
+        Ok(())
+    }
+
     pub async fn get_snapshots(&self, connection_id: &str, database_filter: Option<String>) -> Result<Vec<SchemaSnapshot>, String> {
         // We fetch all for the connection, then filter in memory for simplicity unless performance becomes an issue
         // OR we can add WHERE clause dynamically. For now, let's filter in memory but select the column.
@@ -139,6 +195,26 @@ impl SchemaTrackerStore {
         Ok(snapshots)
     }
 
+    pub async fn get_snapshot(&self, snapshot_id: i64) -> Result<Option<SchemaSnapshot>, String> {
+        let row = sqlx::query("SELECT id, database_name, snapshot_data FROM schema_snapshots WHERE id = ?")
+            .bind(snapshot_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to fetch snapshot: {}", e))?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let data: Vec<u8> = row.try_get("snapshot_data").unwrap_or_default();
+        let mut snapshot: SchemaSnapshot =
+            serde_json::from_slice(&data).map_err(|e| e.to_string())?;
+        snapshot.id = Some(snapshot_id);
+        if snapshot.database_name.is_none() {
+            snapshot.database_name = row.try_get("database_name").ok();
+        }
+        Ok(Some(snapshot))
+    }
+
     pub async fn add_version_tag(
         &self,
         snapshot_id: i64,
@@ -159,6 +235,117 @@ impl SchemaTrackerStore {
         Ok(())
     }
 
+    /// Most recently tagged snapshot of the connection carrying `tag`.
+    pub async fn get_tagged_snapshot(
+        &self,
+        connection_id: &str,
+        database_name: Option<&str>,
+        tag: &str,
+    ) -> Result<Option<SchemaSnapshot>, String> {
+        let row = sqlx::query(
+            r#"
+            SELECT s.id
+            FROM schema_versions v
+            JOIN schema_snapshots s ON s.id = v.snapshot_id
+            WHERE s.connection_id = ? AND v.tag = ? AND (? IS NULL OR s.database_name = ?)
+            ORDER BY v.created_at DESC, v.id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(connection_id)
+        .bind(tag)
+        .bind(database_name)
+        .bind(database_name)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch tagged snapshot: {}", e))?;
+
+        match row {
+            Some(row) => {
+                let id: i64 = row.try_get("id").map_err(|e| e.to_string())?;
+                self.get_snapshot(id).await
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub async fn save_drift_event(&self, event: &SchemaDriftEvent) -> Result<i64, String> {
+        let data = serde_json::to_vec(event).map_err(|e| e.to_string())?;
+
+        let id = sqlx::query(
+            r#"
+            INSERT INTO schema_drift_events (
+                connection_id, database_name, baseline_snapshot_id, schema_hash, event_data, acknowledged, detected_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            RETURNING id
+            "#,
+        )
+        .bind(&event.connection_id)
+        .bind(&event.database_name)
+        .bind(event.baseline_snapshot_id)
+        .bind(&event.schema_hash)
+        .bind(data)
+        .bind(event.acknowledged)
+        .bind(event.detected_at.timestamp())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to save drift event: {}", e))?
+        .try_get::<i64, _>("id")
+        .map_err(|e| e.to_string())?;
+
+        Ok(id)
+    }
+
+    /// Drift events of the connection, newest first.
+    pub async fn get_drift_events(
+        &self,
+        connection_id: &str,
+        database_name: Option<&str>,
+        limit: u32,
+    ) -> Result<Vec<SchemaDriftEvent>, String> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, acknowledged, event_data
+            FROM schema_drift_events
+            WHERE connection_id = ? AND (? IS NULL OR database_name = ?)
+            ORDER BY detected_at DESC, id DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(connection_id)
+        .bind(database_name)
+        .bind(database_name)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch drift events: {}", e))?;
+
+        rows.iter().map(Self::row_to_drift_event).collect()
+    }
+
+    pub async fn acknowledge_drift_event(&self, event_id: i64) -> Result<(), String> {
+        let result = sqlx::query("UPDATE schema_drift_events SET acknowledged = 1 WHERE id = ?")
+            .bind(event_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to acknowledge drift event: {}", e))?;
+
+        if result.rows_affected() == 0 {
+            return Err(format!("Drift event {} not found", event_id));
+        }
+        Ok(())
+    }
+
+    fn row_to_drift_event(row: &SqliteRow) -> Result<SchemaDriftEvent, String> {
+        let data: Vec<u8> = row.try_get("event_data").map_err(|e| e.to_string())?;
+        let mut event: SchemaDriftEvent =
+            serde_json::from_slice(&data).map_err(|e| e.to_string())?;
+        event.id = Some(row.try_get("id").map_err(|e| e.to_string())?);
+        event.acknowledged = row.try_get("acknowledged").map_err(|e| e.to_string())?;
+        Ok(event)
+    }
+
     pub async fn save_ai_impact_report(
         &self,
         connection_id: &str,
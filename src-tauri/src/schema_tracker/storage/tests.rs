@@ -1,5 +1,5 @@
 use super::*;
-use crate::schema_tracker::models::SchemaSnapshot;
+use crate::schema_tracker::models::{SchemaDriftEvent, SchemaSnapshot};
 use chrono::Utc;
 
 #[tokio::test]
@@ -36,3 +36,55 @@ async fn test_schema_tracker_store_crud() {
     let filtered = store.get_snapshots("conn1", Some("other_db".to_string())).await.unwrap();
     assert_eq!(filtered.len(), 0);
 }
+
+#[tokio::test]
+async fn test_tagged_snapshots_and_drift_events() {
+    let pool = Pool::connect("sqlite::memory:").await.unwrap();
+    let store = SchemaTrackerStore::new(pool).await.unwrap();
+
+    let snapshot = |hash: &str| SchemaSnapshot {
+        id: None,
+        connection_id: "conn1".to_string(),
+        database_name: Some("db1".to_string()),
+        timestamp: Utc::now(),
+        schema_hash: hash.to_string(),
+        tables: vec![],
+        views: vec![],
+        routines: vec![],
+        triggers: vec![],
+    };
+    let pinned = store.save_snapshot(&snapshot("hash1")).await.unwrap();
+    store.save_snapshot(&snapshot("hash2")).await.unwrap();
+    store.add_version_tag(pinned, "baseline", "release 1").await.unwrap();
+
+    let tagged = store.get_tagged_snapshot("conn1", Some("db1"), "baseline").await.unwrap();
+    assert_eq!(tagged.unwrap().id, Some(pinned));
+    assert!(store.get_tagged_snapshot("conn1", Some("other_db"), "baseline").await.unwrap().is_none());
+    assert!(store.get_tagged_snapshot("conn1", None, "missing").await.unwrap().is_none());
+
+    let event = SchemaDriftEvent {
+        id: None,
+        connection_id: "conn1".to_string(),
+        database_name: Some("db1".to_string()),
+        task_id: Some("task-1".to_string()),
+        baseline_snapshot_id: Some(pinned),
+        baseline_tag: Some("baseline".to_string()),
+        snapshot_id: None,
+        schema_hash: "hash2".to_string(),
+        change_count: 1,
+        breaking_changes: vec![],
+        diff: Default::default(),
+        notified: true,
+        acknowledged: false,
+        detected_at: Utc::now(),
+    };
+    let event_id = store.save_drift_event(&event).await.unwrap();
+    store.acknowledge_drift_event(event_id).await.unwrap();
+    assert!(store.acknowledge_drift_event(event_id + 1).await.is_err());
+
+    let events = store.get_drift_events("conn1", None, 10).await.unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].id, Some(event_id));
+    assert!(events[0].acknowledged);
+    assert_eq!(events[0].baseline_snapshot_id, Some(pinned));
+}
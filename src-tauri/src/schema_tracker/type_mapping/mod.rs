@@ -0,0 +1,1216 @@
+// =====================================================
+// TYPE MAPPING MODULE
+// Canonical column type model and DDL translation between
+// MySQL, PostgreSQL, MSSQL, SQLite and ClickHouse
+// =====================================================
+
+use crate::db_types::{ColumnSchema, DatabaseType, ForeignKey, TableConstraint};
+use crate::schema_tracker::models::{
+    ColumnDiff, DiffType, SchemaDiff, SchemaSnapshot, TableDefinition, TableDiff,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CanonicalType {
+    Boolean,
+    /// `bytes` is 1, 2, 4 or 8
+    Integer {
+        bytes: u8,
+        unsigned: bool,
+    },
+    Decimal {
+        precision: Option<u32>,
+        scale: Option<u32>,
+    },
+    Float {
+        double: bool,
+    },
+    Char {
+        length: Option<u32>,
+    },
+    VarChar {
+        length: Option<u32>,
+    },
+    Text,
+    Binary {
+        length: Option<u32>,
+        fixed: bool,
+    },
+    Blob,
+    Date,
+    Time {
+        precision: Option<u32>,
+    },
+    DateTime {
+        precision: Option<u32>,
+        with_time_zone: bool,
+    },
+    Json,
+    Uuid,
+    Enum {
+        values: Vec<String>,
+    },
+    /// Engine-specific type without a portable meaning (arrays, intervals, geometry ...)
+    Unknown {
+        raw: String,
+    },
+}
+
+// =====================================================
+// PARSING
+// =====================================================
+
+/// Removes ClickHouse `Nullable(...)` / `LowCardinality(...)` wrappers.
+fn strip_type_wrappers(raw: &str) -> &str {
+    let mut current = raw.trim();
+    loop {
+        let lower = current.to_ascii_lowercase();
+        let inner = ["nullable(", "lowcardinality("]
+            .iter()
+            .find(|prefix| lower.starts_with(*prefix) && lower.ends_with(')'))
+            .map(|prefix| &current[prefix.len()..current.len() - 1]);
+        match inner {
+            Some(inner) => current = inner.trim(),
+            None => return current,
+        }
+    }
+}
+
+/// Splits a parenthesised argument list on commas outside quotes.
+fn split_type_args(inner: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    for c in inner.chars() {
+        match quote {
+            Some(q) if c == q => {
+                quote = None;
+                current.push(c);
+            }
+            Some(_) => current.push(c),
+            None if c == '\'' || c == '"' => {
+                quote = Some(c);
+                current.push(c);
+            }
+            None if c == ',' => {
+                args.push(current.trim().to_string());
+                current.clear();
+            }
+            None => current.push(c),
+        }
+    }
+    if !current.trim().is_empty() {
+        args.push(current.trim().to_string());
+    }
+    args
+}
+
+fn unquote(value: &str) -> String {
+    let trimmed = value.trim();
+    if trimmed.len() >= 2 && trimmed.starts_with('\'') && trimmed.ends_with('\'') {
+        trimmed[1..trimmed.len() - 1].replace("''", "'")
+    } else {
+        trimmed.to_string()
+    }
+}
+
+fn enum_values(args: &[String]) -> Vec<String> {
+    args.iter()
+        .map(|arg| {
+            // ClickHouse: 'value' = 1
+            let literal = arg
+                .rsplit_once('=')
+                .filter(|(_, number)| number.trim().parse::<i64>().is_ok())
+                .map(|(value, _)| value)
+                .unwrap_or(arg);
+            unquote(literal)
+        })
+        .collect()
+}
+
+/// Parses an engine's column type string (as captured in `ColumnSchema.column_type`).
+pub fn parse_column_type(column_type: &str, dialect: &DatabaseType) -> CanonicalType {
+    let raw = strip_type_wrappers(column_type);
+    let lower = raw.to_ascii_lowercase();
+    let (head, args) = match (lower.find('('), lower.rfind(')')) {
+        (Some(open), Some(close)) if close > open => (
+            lower[..open].trim().to_string(),
+            split_type_args(&raw[open + 1..close]),
+        ),
+        _ => (lower.clone(), Vec::new()),
+    };
+    let unsigned = lower.contains("unsigned");
+    let with_time_zone = lower.contains("with time zone");
+    let base = head
+        .replace("unsigned", "")
+        .replace("zerofill", "")
+        .replace("without time zone", "")
+        .replace("with time zone", "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let number = |idx: usize| -> Option<u32> { args.get(idx).and_then(|a| a.trim().parse().ok()) };
+    let is_max = args
+        .first()
+        .is_some_and(|a| a.trim().eq_ignore_ascii_case("max"));
+    let int = |bytes: u8| CanonicalType::Integer { bytes, unsigned };
+
+    match base.as_str() {
+        "tinyint" if *dialect == DatabaseType::MySQL && number(0) == Some(1) => {
+            CanonicalType::Boolean
+        }
+        "bool" | "boolean" => CanonicalType::Boolean,
+        "bit" if *dialect == DatabaseType::MSSQL || number(0).unwrap_or(1) == 1 => {
+            CanonicalType::Boolean
+        }
+        "bit" => CanonicalType::Integer {
+            bytes: 8,
+            unsigned: true,
+        },
+        // MSSQL tinyint is 0..255
+        "tinyint" if *dialect == DatabaseType::MSSQL => CanonicalType::Integer {
+            bytes: 1,
+            unsigned: true,
+        },
+        "tinyint" => int(1),
+        "int8" if *dialect == DatabaseType::ClickHouse => int(1),
+        "uint8" => CanonicalType::Integer {
+            bytes: 1,
+            unsigned: true,
+        },
+        "smallint" | "int2" | "int16" | "smallserial" | "year" => int(2),
+        "uint16" => CanonicalType::Integer {
+            bytes: 2,
+            unsigned: true,
+        },
+        // SQLite integers are always 64-bit
+        "integer" if *dialect == DatabaseType::SQLite => int(8),
+        "mediumint" | "int" | "integer" | "int4" | "int32" | "serial" => int(4),
+        "uint32" => CanonicalType::Integer {
+            bytes: 4,
+            unsigned: true,
+        },
+        "bigint" | "int8" | "int64" | "bigserial" => int(8),
+        "uint64" => CanonicalType::Integer {
+            bytes: 8,
+            unsigned: true,
+        },
+        "int128" | "uint128" | "int256" | "uint256" => CanonicalType::Decimal {
+            precision: Some(38),
+            scale: Some(0),
+        },
+        "decimal" | "numeric" | "dec" | "fixed" => CanonicalType::Decimal {
+            precision: number(0),
+            scale: number(1),
+        },
+        "decimal32" | "decimal64" | "decimal128" | "decimal256" => CanonicalType::Decimal {
+            precision: Some(match base.as_str() {
+                "decimal32" => 9,
+                "decimal64" => 18,
+                "decimal128" => 38,
+                _ => 76,
+            }),
+            scale: number(0),
+        },
+        "money" => CanonicalType::Decimal {
+            precision: Some(19),
+            scale: Some(4),
+        },
+        "smallmoney" => CanonicalType::Decimal {
+            precision: Some(10),
+            scale: Some(4),
+        },
+        "float" => CanonicalType::Float {
+            double: match dialect {
+                DatabaseType::MySQL => number(0).is_some_and(|p| p > 24),
+                DatabaseType::MSSQL => number(0).is_none_or(|p| p > 24),
+                _ => true,
+            },
+        },
+        "real" => CanonicalType::Float {
+            double: matches!(dialect, DatabaseType::MySQL | DatabaseType::SQLite),
+        },
+        "float4" | "float32" => CanonicalType::Float { double: false },
+        "double" | "double precision" | "float8" | "float64" => {
+            CanonicalType::Float { double: true }
+        }
+        "char" | "character" | "bpchar" | "nchar" | "national char" | "fixedstring" => {
+            CanonicalType::Char { length: number(0) }
+        }
+        "varchar" | "character varying" | "nvarchar" | "varchar2" | "nvarchar2"
+        | "national varchar" => {
+            if is_max {
+                CanonicalType::Text
+            } else {
+                CanonicalType::VarChar { length: number(0) }
+            }
+        }
+        "text" | "tinytext" | "mediumtext" | "longtext" | "ntext" | "clob" | "citext"
+        | "string" | "set" => CanonicalType::Text,
+        "binary" => CanonicalType::Binary {
+            length: number(0),
+            fixed: true,
+        },
+        "varbinary" if is_max => CanonicalType::Blob,
+        "varbinary" => CanonicalType::Binary {
+            length: number(0),
+            fixed: false,
+        },
+        "bytea" | "blob" | "tinyblob" | "mediumblob" | "longblob" | "image" => CanonicalType::Blob,
+        "date" | "date32" => CanonicalType::Date,
+        "time" | "timetz" => CanonicalType::Time {
+            precision: number(0),
+        },
+        "timestamptz" | "datetimeoffset" => CanonicalType::DateTime {
+            precision: number(0),
+            with_time_zone: true,
+        },
+        "datetime" | "datetime64" if *dialect == DatabaseType::ClickHouse => {
+            // DateTime('UTC'), DateTime64(3) or DateTime64(3, 'UTC')
+            let precision = if base == "datetime64" {
+                number(0)
+            } else {
+                None
+            };
+            CanonicalType::DateTime {
+                precision,
+                with_time_zone: args.iter().any(|a| a.starts_with('\'')),
+            }
+        }
+        "datetime" | "datetime2" | "smalldatetime" | "timestamp" => CanonicalType::DateTime {
+            precision: number(0),
+            with_time_zone,
+        },
+        "json" | "jsonb" | "object" => CanonicalType::Json,
+        "uuid" | "uniqueidentifier" => CanonicalType::Uuid,
+        "enum" | "enum8" | "enum16" => CanonicalType::Enum {
+            values: enum_values(&args),
+        },
+        _ => CanonicalType::Unknown {
+            raw: raw.to_string(),
+        },
+    }
+}
+
+/// Whether the column is filled by the engine (AUTO_INCREMENT, serial, IDENTITY).
+pub fn is_auto_increment(column: &ColumnSchema) -> bool {
+    let extra = column.extra.to_ascii_lowercase();
+    let column_type = column.column_type.to_ascii_lowercase();
+    extra.contains("auto_increment")
+        || extra.starts_with("identity")
+        || column_type.ends_with("serial")
+        || column
+            .column_default
+            .as_deref()
+            .is_some_and(|d| d.to_ascii_lowercase().starts_with("nextval("))
+}
+
+// =====================================================
+// RENDERING
+// =====================================================
+
+fn dialect_name(dialect: &DatabaseType) -> &'static str {
+    match dialect {
+        DatabaseType::MySQL => "MySQL",
+        DatabaseType::PostgreSQL => "PostgreSQL",
+        DatabaseType::MSSQL => "MSSQL",
+        DatabaseType::SQLite => "SQLite",
+        DatabaseType::ClickHouse => "ClickHouse",
+        DatabaseType::Disconnected => "disconnected",
+    }
+}
+
+fn quoted_list(values: &[String]) -> String {
+    values
+        .iter()
+        .map(|v| format!("'{}'", v.replace('\'', "''")))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn clamp_precision(precision: Option<u32>, max: u32) -> Option<u32> {
+    precision.map(|p| p.min(max))
+}
+
+fn with_precision(name: &str, precision: Option<u32>) -> String {
+    match precision {
+        Some(p) => format!("{}({})", name, p),
+        None => name.to_string(),
+    }
+}
+
+fn decimal(name: &str, precision: Option<u32>, scale: Option<u32>, max: u32) -> String {
+    match (precision, scale) {
+        (Some(p), Some(s)) => format!("{}({},{})", name, p.min(max), s.min(p.min(max))),
+        (Some(p), None) => format!("{}({})", name, p.min(max)),
+        _ => name.to_string(),
+    }
+}
+
+fn render_mysql(ty: &CanonicalType) -> String {
+    match ty {
+        CanonicalType::Boolean => "tinyint(1)".to_string(),
+        CanonicalType::Integer { bytes, unsigned } => {
+            let name = match bytes {
+                1 => "tinyint",
+                2 => "smallint",
+                4 => "int",
+                _ => "bigint",
+            };
+            if *unsigned {
+                format!("{} unsigned", name)
+            } else {
+                name.to_string()
+            }
+        }
+        CanonicalType::Decimal { precision, scale } => decimal("decimal", *precision, *scale, 65),
+        CanonicalType::Float { double } => if *double { "double" } else { "float" }.to_string(),
+        CanonicalType::Char { length } => format!("char({})", length.unwrap_or(1).min(255)),
+        CanonicalType::VarChar {
+            length: Some(length),
+        } if *length <= 16383 => format!("varchar({})", length),
+        CanonicalType::VarChar { .. } | CanonicalType::Text => "longtext".to_string(),
+        CanonicalType::Binary {
+            length: Some(length),
+            fixed: true,
+        } if *length <= 255 => format!("binary({})", length),
+        CanonicalType::Binary {
+            length: Some(length),
+            ..
+        } if *length <= 65535 => format!("varbinary({})", length),
+        CanonicalType::Binary { .. } | CanonicalType::Blob => "longblob".to_string(),
+        CanonicalType::Date => "date".to_string(),
+        CanonicalType::Time { precision } => with_precision("time", clamp_precision(*precision, 6)),
+        CanonicalType::DateTime { precision, .. } => {
+            with_precision("datetime", clamp_precision(*precision, 6))
+        }
+        CanonicalType::Json => "json".to_string(),
+        CanonicalType::Uuid => "char(36)".to_string(),
+        CanonicalType::Enum { values } => format!("enum({})", quoted_list(values)),
+        CanonicalType::Unknown { raw } => raw.clone(),
+    }
+}
+
+fn render_postgres(ty: &CanonicalType, auto_increment: bool) -> String {
+    match ty {
+        CanonicalType::Integer { bytes, unsigned } if auto_increment => match (*bytes, *unsigned) {
+            (1, _) | (2, false) => "smallserial",
+            (2, true) | (4, false) => "serial",
+            _ => "bigserial",
+        }
+        .to_string(),
+        CanonicalType::Boolean => "boolean".to_string(),
+        // Unsigned values need the next wider signed type
+        CanonicalType::Integer { bytes, unsigned } => match (*bytes, *unsigned) {
+            (1, _) | (2, false) => "smallint".to_string(),
+            (2, true) | (4, false) => "integer".to_string(),
+            (4, true) | (8, false) => "bigint".to_string(),
+            _ => "numeric(20,0)".to_string(),
+        },
+        CanonicalType::Decimal { precision, scale } => decimal("numeric", *precision, *scale, 1000),
+        CanonicalType::Float { double } => {
+            if *double { "double precision" } else { "real" }.to_string()
+        }
+        CanonicalType::Char { length } => format!("char({})", length.unwrap_or(1)),
+        CanonicalType::VarChar { length } => with_precision("varchar", *length),
+        CanonicalType::Text => "text".to_string(),
+        CanonicalType::Binary { .. } | CanonicalType::Blob => "bytea".to_string(),
+        CanonicalType::Date => "date".to_string(),
+        CanonicalType::Time { precision } => with_precision("time", clamp_precision(*precision, 6)),
+        CanonicalType::DateTime {
+            precision,
+            with_time_zone,
+        } => with_precision(
+            if *with_time_zone {
+                "timestamptz"
+            } else {
+                "timestamp"
+            },
+            clamp_precision(*precision, 6),
+        ),
+        CanonicalType::Json => "jsonb".to_string(),
+        CanonicalType::Uuid => "uuid".to_string(),
+        CanonicalType::Enum { values } => varchar_for_enum("varchar", values),
+        CanonicalType::Unknown { raw } => raw.clone(),
+    }
+}
+
+fn render_mssql(ty: &CanonicalType) -> String {
+    match ty {
+        CanonicalType::Boolean => "bit".to_string(),
+        CanonicalType::Integer { bytes, unsigned } => match (*bytes, *unsigned) {
+            (1, true) => "tinyint".to_string(),
+            (1, false) | (2, false) => "smallint".to_string(),
+            (2, true) | (4, false) => "int".to_string(),
+            (4, true) | (8, false) => "bigint".to_string(),
+            _ => "decimal(20,0)".to_string(),
+        },
+        CanonicalType::Decimal { precision, scale } => decimal("decimal", *precision, *scale, 38),
+        CanonicalType::Float { double } => if *double { "float" } else { "real" }.to_string(),
+        CanonicalType::Char { length } => format!("nchar({})", length.unwrap_or(1).min(4000)),
+        CanonicalType::VarChar {
+            length: Some(length),
+        } if *length <= 4000 => format!("nvarchar({})", length),
+        CanonicalType::VarChar { .. } | CanonicalType::Text | CanonicalType::Json => {
+            "nvarchar(MAX)".to_string()
+        }
+        CanonicalType::Binary {
+            length: Some(length),
+            fixed: true,
+        } if *length <= 8000 => format!("binary({})", length),
+        CanonicalType::Binary {
+            length: Some(length),
+            ..
+        } if *length <= 8000 => format!("varbinary({})", length),
+        CanonicalType::Binary { .. } | CanonicalType::Blob => "varbinary(MAX)".to_string(),
+        CanonicalType::Date => "date".to_string(),
+        CanonicalType::Time { precision } => with_precision("time", clamp_precision(*precision, 7)),
+        CanonicalType::DateTime {
+            precision,
+            with_time_zone,
+        } => with_precision(
+            if *with_time_zone {
+                "datetimeoffset"
+            } else {
+                "datetime2"
+            },
+            clamp_precision(*precision, 7),
+        ),
+        CanonicalType::Uuid => "uniqueidentifier".to_string(),
+        CanonicalType::Enum { values } => varchar_for_enum("nvarchar", values),
+        CanonicalType::Unknown { raw } => raw.clone(),
+    }
+}
+
+fn render_sqlite(ty: &CanonicalType) -> String {
+    match ty {
+        CanonicalType::Boolean => "BOOLEAN".to_string(),
+        CanonicalType::Integer { .. } => "INTEGER".to_string(),
+        CanonicalType::Decimal { .. } => "NUMERIC".to_string(),
+        CanonicalType::Float { .. } => "REAL".to_string(),
+        CanonicalType::Char { length } => with_precision("CHAR", *length),
+        CanonicalType::VarChar { length } => with_precision("VARCHAR", *length),
+        CanonicalType::Binary { .. } | CanonicalType::Blob => "BLOB".to_string(),
+        CanonicalType::Text
+        | CanonicalType::Date
+        | CanonicalType::Time { .. }
+        | CanonicalType::DateTime { .. }
+        | CanonicalType::Json
+        | CanonicalType::Uuid
+        | CanonicalType::Enum { .. } => "TEXT".to_string(),
+        CanonicalType::Unknown { raw } => raw.clone(),
+    }
+}
+
+/// Bare ClickHouse type; `Nullable(...)` is added by the column formatter.
+fn render_clickhouse(ty: &CanonicalType) -> String {
+    match ty {
+        CanonicalType::Boolean => "Bool".to_string(),
+        CanonicalType::Integer { bytes, unsigned } => format!(
+            "{}Int{}",
+            if *unsigned { "U" } else { "" },
+            u32::from(*bytes) * 8
+        ),
+        CanonicalType::Decimal { precision, scale } => format!(
+            "Decimal({}, {})",
+            precision.unwrap_or(38).min(76),
+            scale.unwrap_or(if precision.is_some() { 0 } else { 10 })
+        ),
+        CanonicalType::Float { double } => if *double { "Float64" } else { "Float32" }.to_string(),
+        CanonicalType::Char { .. }
+        | CanonicalType::VarChar { .. }
+        | CanonicalType::Text
+        | CanonicalType::Binary { .. }
+        | CanonicalType::Blob
+        | CanonicalType::Json
+        | CanonicalType::Time { .. } => "String".to_string(),
+        CanonicalType::Date => "Date32".to_string(),
+        CanonicalType::DateTime {
+            precision,
+            with_time_zone,
+        } => {
+            let zone = if *with_time_zone { ", 'UTC'" } else { "" };
+            match precision {
+                Some(p) if *p > 0 => format!("DateTime64({}{})", p.min(&9), zone),
+                _ if *with_time_zone => "DateTime('UTC')".to_string(),
+                _ => "DateTime".to_string(),
+            }
+        }
+        CanonicalType::Uuid => "UUID".to_string(),
+        CanonicalType::Enum { values } => format!(
+            "Enum16({})",
+            values
+                .iter()
+                .enumerate()
+                .map(|(idx, v)| format!("'{}' = {}", v.replace('\'', "\\'"), idx + 1))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        CanonicalType::Unknown { raw } => raw.clone(),
+    }
+}
+
+fn varchar_for_enum(name: &str, values: &[String]) -> String {
+    let longest = values.iter().map(|v| v.chars().count()).max().unwrap_or(1);
+    format!("{}({})", name, longest.max(1))
+}
+
+/// Renders `ty` in `dialect`. `auto_increment` only changes the PostgreSQL
+/// spelling (serial types); other engines mark it on the column instead.
+pub fn render_column_type(
+    ty: &CanonicalType,
+    dialect: &DatabaseType,
+    auto_increment: bool,
+) -> String {
+    match dialect {
+        DatabaseType::MySQL => render_mysql(ty),
+        DatabaseType::PostgreSQL => render_postgres(ty, auto_increment),
+        DatabaseType::MSSQL => render_mssql(ty),
+        DatabaseType::SQLite => render_sqlite(ty),
+        DatabaseType::ClickHouse => render_clickhouse(ty),
+        DatabaseType::Disconnected => String::new(),
+    }
+}
+
+/// Textual fallback for types the target cannot express.
+fn fallback_text_type(dialect: &DatabaseType) -> String {
+    render_column_type(&CanonicalType::Text, dialect, false)
+}
+
+// =====================================================
+// DEFAULTS
+// =====================================================
+
+/// Strips MSSQL's wrapping parentheses: `((0))` -> `0`.
+fn strip_wrapping_parens(value: &str) -> &str {
+    let mut current = value.trim();
+    while current.starts_with('(') && current.ends_with(')') {
+        let inner = &current[1..current.len() - 1];
+        let mut depth = 0i32;
+        let balanced = inner.chars().all(|c| {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ => {}
+            }
+            depth >= 0
+        }) && depth == 0;
+        if !balanced {
+            break;
+        }
+        current = inner.trim();
+    }
+    current
+}
+
+/// Drops a PostgreSQL cast suffix outside quotes: `'a'::character varying` -> `'a'`.
+fn strip_postgres_cast(value: &str) -> &str {
+    let mut in_quote = false;
+    let bytes = value.as_bytes();
+    for idx in 0..bytes.len() {
+        match bytes[idx] {
+            b'\'' => in_quote = !in_quote,
+            b':' if !in_quote && bytes.get(idx + 1) == Some(&b':') => {
+                return value[..idx].trim();
+            }
+            _ => {}
+        }
+    }
+    value
+}
+
+fn is_current_timestamp(lower: &str) -> bool {
+    let name = lower.split('(').next().unwrap_or(lower).trim();
+    matches!(
+        name,
+        "current_timestamp"
+            | "now"
+            | "now64"
+            | "getdate"
+            | "getutcdate"
+            | "sysdatetime"
+            | "sysutcdatetime"
+            | "sysdatetimeoffset"
+            | "localtimestamp"
+            | "statement_timestamp"
+            | "transaction_timestamp"
+    ) || lower == "datetime('now')"
+}
+
+/// Rewrites a default expression for `to`. Literals and current-timestamp functions
+/// translate; anything else is dropped with a warning.
+fn translate_default(
+    default: &str,
+    ty: &CanonicalType,
+    from: &DatabaseType,
+    to: &DatabaseType,
+    label: &str,
+    warnings: &mut Vec<String>,
+) -> Option<String> {
+    let mut value = strip_wrapping_parens(default);
+    if *from == DatabaseType::PostgreSQL {
+        value = strip_postgres_cast(value);
+    }
+    let lower = value.to_ascii_lowercase();
+
+    if lower == "null" || lower.starts_with("nextval(") || value.is_empty() {
+        return None;
+    }
+    if is_current_timestamp(&lower) {
+        return Some(
+            if *to == DatabaseType::ClickHouse {
+                "now()"
+            } else {
+                "CURRENT_TIMESTAMP"
+            }
+            .to_string(),
+        );
+    }
+    if *ty == CanonicalType::Boolean {
+        let truth = match lower.trim_matches('\'') {
+            "1" | "true" | "t" | "b'1" => Some(true),
+            "0" | "false" | "f" | "b'0" => Some(false),
+            _ => None,
+        };
+        if let Some(truth) = truth {
+            let literal = match (to, truth) {
+                (DatabaseType::PostgreSQL | DatabaseType::ClickHouse, true) => "true",
+                (DatabaseType::PostgreSQL | DatabaseType::ClickHouse, false) => "false",
+                (_, true) => "1",
+                (_, false) => "0",
+            };
+            return Some(literal.to_string());
+        }
+    }
+    if value.parse::<f64>().is_ok() {
+        return Some(value.to_string());
+    }
+    let string_literal = value
+        .strip_prefix('N')
+        .filter(|rest| rest.starts_with('\''))
+        .unwrap_or(value);
+    if string_literal.len() >= 2
+        && string_literal.starts_with('\'')
+        && string_literal.ends_with('\'')
+    {
+        return Some(string_literal.to_string());
+    }
+    // MySQL reports literal defaults without quotes
+    if *from == DatabaseType::MySQL && !value.contains('(') {
+        return Some(format!("'{}'", value.replace('\'', "''")));
+    }
+
+    warnings.push(format!(
+        "{}: default `{}` has no {} translation and was dropped",
+        label,
+        default,
+        dialect_name(to)
+    ));
+    None
+}
+
+/// Canonical spelling of a default as `dialect` reports it, so catalog output
+/// (`((0))`, `'a'::text`, unquoted MySQL literals) compares equal to the DDL it came
+/// from. Expressions without a portable form are kept as written.
+pub fn normalize_default(
+    default: &str,
+    ty: &CanonicalType,
+    dialect: &DatabaseType,
+) -> Option<String> {
+    let mut dropped = Vec::new();
+    let normalized = translate_default(default, ty, dialect, dialect, "", &mut dropped);
+    if dropped.is_empty() {
+        let numeric = matches!(
+            ty,
+            CanonicalType::Integer { .. }
+                | CanonicalType::Decimal { .. }
+                | CanonicalType::Float { .. }
+        );
+        // Dumps quote numeric defaults (`DEFAULT '0.00'`) that catalogs report bare
+        return normalized.map(|value| match value.trim_matches('\'').parse::<f64>() {
+            Ok(_) if numeric => value.trim_matches('\'').to_string(),
+            _ => value,
+        });
+    }
+    let value = strip_wrapping_parens(default);
+    Some(if *dialect == DatabaseType::PostgreSQL {
+        strip_postgres_cast(value).to_string()
+    } else {
+        value.to_string()
+    })
+}
+
+// =====================================================
+// TRANSLATION
+// =====================================================
+
+/// MSSQL names are `schema.table`; other engines get the bare table name.
+pub fn translate_table_name(name: &str, from: &DatabaseType, to: &DatabaseType) -> String {
+    if *from == DatabaseType::MSSQL && *to != DatabaseType::MSSQL {
+        name.rsplit('.').next().unwrap_or(name).to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// Rewrites `column` for `to`: type, auto-increment marker and default.
+pub fn translate_column(
+    column: &ColumnSchema,
+    table: &str,
+    from: &DatabaseType,
+    to: &DatabaseType,
+    warnings: &mut Vec<String>,
+) -> ColumnSchema {
+    let label = format!("{}.{}", table, column.name);
+    let ty = parse_column_type(&column.column_type, from);
+    let auto_increment = is_auto_increment(column);
+    let cross_engine = from != to;
+
+    let column_type = match &ty {
+        CanonicalType::Unknown { raw } if cross_engine => {
+            let fallback = fallback_text_type(to);
+            warnings.push(format!(
+                "{}: type `{}` has no {} equivalent; using {}",
+                label,
+                raw,
+                dialect_name(to),
+                fallback
+            ));
+            fallback
+        }
+        _ => render_column_type(&ty, to, auto_increment),
+    };
+    if cross_engine {
+        match &ty {
+            CanonicalType::DateTime {
+                with_time_zone: true,
+                ..
+            } if matches!(to, DatabaseType::MySQL | DatabaseType::SQLite) => {
+                warnings.push(format!(
+                    "{}: time zone offsets are not preserved in {}",
+                    label,
+                    dialect_name(to)
+                ))
+            }
+            CanonicalType::Time { .. } if *to == DatabaseType::ClickHouse => {
+                warnings.push(format!(
+                    "{}: ClickHouse has no TIME type; values are stored as String",
+                    label
+                ))
+            }
+            _ => {}
+        }
+    }
+
+    let computed = column.extra.starts_with("AS ") || column.extra.ends_with("GENERATED");
+    let extra = if !cross_engine {
+        column.extra.clone()
+    } else if computed {
+        warnings.push(format!(
+            "{}: computed column expression `{}` is not translated",
+            label, column.extra
+        ));
+        String::new()
+    } else if auto_increment {
+        match to {
+            DatabaseType::MSSQL if column.extra.starts_with("IDENTITY") => column.extra.clone(),
+            DatabaseType::MSSQL => "IDENTITY(1,1)".to_string(),
+            DatabaseType::ClickHouse => {
+                if cross_engine {
+                    warnings.push(format!(
+                        "{}: ClickHouse has no auto-increment; values must be supplied by the writer",
+                        label
+                    ));
+                }
+                String::new()
+            }
+            _ => "auto_increment".to_string(),
+        }
+    } else {
+        String::new()
+    };
+
+    let column_default = if auto_increment {
+        None
+    } else if cross_engine {
+        column
+            .column_default
+            .as_deref()
+            .and_then(|d| translate_default(d, &ty, from, to, &label, warnings))
+    } else {
+        column.column_default.clone()
+    };
+
+    let data_type = column_type
+        .split(['(', ' '])
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    ColumnSchema {
+        name: column.name.clone(),
+        data_type,
+        column_type,
+        is_nullable: column.is_nullable,
+        column_key: column.column_key.clone(),
+        column_default,
+        extra,
+        collation: if cross_engine {
+            None
+        } else {
+            column.collation.clone()
+        },
+    }
+}
+
+fn enum_check_constraint(table: &str, column: &ColumnSchema, values: &[String]) -> TableConstraint {
+    let bare_table = table.rsplit('.').next().unwrap_or(table);
+    TableConstraint {
+        name: format!("chk_{}_{}", bare_table, column.name),
+        constraint_type: "CHECK".to_string(),
+        column_name: column.name.clone(),
+        definition: Some(format!("{} IN ({})", column.name, quoted_list(values))),
+    }
+}
+
+/// Rewrites `table` for `to`. Enum columns on engines without enums keep their
+/// value list as a CHECK constraint.
+pub fn translate_table(
+    table: &TableDefinition,
+    from: &DatabaseType,
+    to: &DatabaseType,
+    warnings: &mut Vec<String>,
+) -> TableDefinition {
+    let name = translate_table_name(&table.name, from, to);
+    let mut translated = table.clone();
+    translated.name = name.clone();
+    translated.columns = table
+        .columns
+        .iter()
+        .map(|col| translate_column(col, &name, from, to, warnings))
+        .collect();
+
+    if from != to
+        && matches!(
+            to,
+            DatabaseType::PostgreSQL | DatabaseType::MSSQL | DatabaseType::SQLite
+        )
+    {
+        for col in &table.columns {
+            if let CanonicalType::Enum { values } = parse_column_type(&col.column_type, from) {
+                translated
+                    .constraints
+                    .push(enum_check_constraint(&name, col, &values));
+            }
+        }
+    }
+    for fk in &mut translated.foreign_keys {
+        translate_foreign_key(fk, from, to);
+    }
+    translated
+}
+
+fn translate_foreign_key(fk: &mut ForeignKey, from: &DatabaseType, to: &DatabaseType) {
+    fk.referenced_table = translate_table_name(&fk.referenced_table, from, to);
+    if *from == DatabaseType::MSSQL && *to != DatabaseType::MSSQL {
+        fk.referenced_schema = None;
+    }
+}
+
+fn translate_columns(
+    columns: &[ColumnSchema],
+    table: &str,
+    from: &DatabaseType,
+    to: &DatabaseType,
+    warnings: &mut Vec<String>,
+) -> Vec<ColumnSchema> {
+    columns
+        .iter()
+        .map(|col| translate_column(col, table, from, to, warnings))
+        .collect()
+}
+
+fn translate_table_diff(
+    diff: &TableDiff,
+    from: &DatabaseType,
+    to: &DatabaseType,
+    warnings: &mut Vec<String>,
+) -> TableDiff {
+    let table = translate_table_name(&diff.table_name, from, to);
+    let mut translated = diff.clone();
+    translated.new_columns = translate_columns(&diff.new_columns, &table, from, to, warnings);
+    translated.dropped_columns =
+        translate_columns(&diff.dropped_columns, &table, from, to, &mut Vec::new());
+    translated.modified_columns = diff
+        .modified_columns
+        .iter()
+        .map(|col_diff| {
+            let old_column =
+                translate_column(&col_diff.old_column, &table, from, to, &mut Vec::new());
+            let new_column = translate_column(&col_diff.new_column, &table, from, to, warnings);
+            let changes = col_diff
+                .changes
+                .iter()
+                .map(|change| match change {
+                    DiffType::TypeChanged { .. } => DiffType::TypeChanged {
+                        old: old_column.column_type.clone(),
+                        new: new_column.column_type.clone(),
+                    },
+                    DiffType::DefaultChanged { .. } => DiffType::DefaultChanged {
+                        old: old_column.column_default.clone(),
+                        new: new_column.column_default.clone(),
+                    },
+                    other => other.clone(),
+                })
+                .collect();
+            ColumnDiff {
+                column_name: col_diff.column_name.clone(),
+                old_column,
+                new_column,
+                changes,
+            }
+        })
+        .collect();
+    for rename in &mut translated.renamed_columns {
+        rename.old_column = translate_column(&rename.old_column, &table, from, to, &mut Vec::new());
+        rename.new_column = translate_column(&rename.new_column, &table, from, to, warnings);
+    }
+    for fk in &mut translated.new_foreign_keys {
+        translate_foreign_key(fk, from, to);
+    }
+    translated.table_name = table;
+    translated
+}
+
+/// Rewrites a diff computed between `from` schemas so its DDL can be generated for `to`.
+/// Returns the translated diff and the lossy conversions it had to make.
+pub fn translate_diff(
+    diff: &SchemaDiff,
+    from: &DatabaseType,
+    to: &DatabaseType,
+) -> (SchemaDiff, Vec<String>) {
+    let mut warnings = Vec::new();
+    let mut translated = diff.clone();
+
+    translated.new_tables = diff
+        .new_tables
+        .iter()
+        .map(|t| translate_table(t, from, to, &mut warnings))
+        .collect();
+    translated.dropped_tables = diff
+        .dropped_tables
+        .iter()
+        .map(|t| translate_table(t, from, to, &mut Vec::new()))
+        .collect();
+    translated.modified_tables = diff
+        .modified_tables
+        .iter()
+        .map(|t| translate_table_diff(t, from, to, &mut warnings))
+        .collect();
+    for rename in &mut translated.renamed_tables {
+        rename.old_name = translate_table_name(&rename.old_name, from, to);
+        rename.new_name = translate_table_name(&rename.new_name, from, to);
+        if let Some(changes) = &rename.changes {
+            rename.changes = Some(translate_table_diff(changes, from, to, &mut warnings));
+        }
+    }
+
+    if from != to {
+        if !diff.new_views.is_empty() || !diff.modified_views.is_empty() {
+            warnings.push(format!(
+                "View definitions are copied verbatim and may need {} syntax changes",
+                dialect_name(to)
+            ));
+        }
+        if !diff.new_routines.is_empty() || !diff.new_triggers.is_empty() {
+            warnings.push(format!(
+                "Routines and triggers are not translated to {}",
+                dialect_name(to)
+            ));
+        }
+    }
+
+    warnings.dedup();
+    (translated, warnings)
+}
+
+/// Rewrites every table of `snapshot` for `to`. With `from == to` this normalises
+/// type spellings, so snapshots translated from another engine compare cleanly.
+pub fn translate_snapshot(
+    snapshot: &SchemaSnapshot,
+    from: &DatabaseType,
+    to: &DatabaseType,
+) -> (SchemaSnapshot, Vec<String>) {
+    let mut warnings = Vec::new();
+    let mut translated = snapshot.clone();
+    translated.tables = snapshot
+        .tables
+        .iter()
+        .map(|t| translate_table(t, from, to, &mut warnings))
+        .collect();
+    for view in &mut translated.views {
+        view.name = translate_table_name(&view.name, from, to);
+    }
+    warnings.dedup();
+    (translated, warnings)
+}
+
+// =====================================================
+// COMPARISON
+// =====================================================
+
+/// `extra` as a translation from another engine writes it: only the auto-increment
+/// marker survives, computed expressions, comments and catalog flags do not.
+fn portable_extra(column: &ColumnSchema, dialect: &DatabaseType) -> String {
+    if !is_auto_increment(column) {
+        return String::new();
+    }
+    match dialect {
+        DatabaseType::MSSQL => "IDENTITY(1,1)".to_string(),
+        DatabaseType::ClickHouse => String::new(),
+        _ => "auto_increment".to_string(),
+    }
+}
+
+/// Rewrites `snapshot` into the spelling two schemas of `dialect` are compared in:
+/// types re-rendered from their canonical form, defaults normalised and MySQL's
+/// DEFAULT_GENERATED flag dropped. `portable` also reduces `extra` to the
+/// auto-increment marker and key markers to PRI, for comparing against a snapshot
+/// translated from another engine. Re-rendering is lossy (`varchar` and `nvarchar`
+/// compare equal), so plans take their types from `restore_declared_types`.
+pub fn normalize_snapshot(
+    snapshot: &SchemaSnapshot,
+    dialect: &DatabaseType,
+    portable: bool,
+) -> SchemaSnapshot {
+    let (mut normalized, _) = translate_snapshot(snapshot, dialect, dialect);
+    for (table, declared) in normalized.tables.iter_mut().zip(&snapshot.tables) {
+        for (column, declared) in table.columns.iter_mut().zip(&declared.columns) {
+            let ty = parse_column_type(&column.column_type, dialect);
+            column.column_default = column
+                .column_default
+                .as_deref()
+                .and_then(|d| normalize_default(d, &ty, dialect));
+            column.extra = if portable {
+                portable_extra(declared, dialect)
+            } else {
+                column
+                    .extra
+                    .split_whitespace()
+                    .filter(|word| !word.eq_ignore_ascii_case("DEFAULT_GENERATED"))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            };
+            if portable {
+                let primary = table
+                    .primary_keys
+                    .iter()
+                    .any(|pk| pk.column_name == column.name);
+                column.column_key = if primary {
+                    "PRI".to_string()
+                } else {
+                    String::new()
+                };
+            }
+        }
+    }
+    normalized
+}
+
+fn find_table<'a>(snapshot: &'a SchemaSnapshot, name: &str) -> Option<&'a TableDefinition> {
+    snapshot.tables.iter().find(|t| t.name == name)
+}
+
+fn restore_type(column: &mut ColumnSchema, table: Option<&TableDefinition>) {
+    let declared = table.and_then(|t| t.columns.iter().find(|c| c.name == column.name));
+    if let Some(declared) = declared {
+        column.column_type = declared.column_type.clone();
+        column.data_type = declared.data_type.clone();
+    }
+}
+
+/// Restores declared types on one side-by-side column pair. Without a type change the
+/// new side keeps the old declared type, so an ALTER for another attribute does not
+/// retype the column.
+fn restore_pair(
+    old_column: &mut ColumnSchema,
+    new_column: &mut ColumnSchema,
+    changes: &mut [DiffType],
+    old: Option<&TableDefinition>,
+    new: Option<&TableDefinition>,
+) {
+    restore_type(old_column, old);
+    let mut type_changed = false;
+    for change in changes.iter_mut() {
+        if let DiffType::TypeChanged { .. } = change {
+            restore_type(new_column, new);
+            *change = DiffType::TypeChanged {
+                old: old_column.column_type.clone(),
+                new: new_column.column_type.clone(),
+            };
+            type_changed = true;
+        }
+    }
+    if !type_changed {
+        new_column.column_type = old_column.column_type.clone();
+        new_column.data_type = old_column.data_type.clone();
+    }
+}
+
+fn restore_table_diff(
+    diff: &mut TableDiff,
+    old: Option<&TableDefinition>,
+    new: Option<&TableDefinition>,
+) {
+    for column in &mut diff.new_columns {
+        restore_type(column, new);
+    }
+    for column in &mut diff.dropped_columns {
+        restore_type(column, old);
+    }
+    for col_diff in &mut diff.modified_columns {
+        restore_pair(
+            &mut col_diff.old_column,
+            &mut col_diff.new_column,
+            &mut col_diff.changes,
+            old,
+            new,
+        );
+    }
+    for rename in &mut diff.renamed_columns {
+        restore_pair(
+            &mut rename.old_column,
+            &mut rename.new_column,
+            &mut rename.changes,
+            old,
+            new,
+        );
+    }
+}
+
+/// Puts the declared column types of `old` and `new` back into a diff computed between
+/// their `normalize_snapshot` forms, so generated DDL keeps the types as written.
+pub fn restore_declared_types(diff: &mut SchemaDiff, old: &SchemaSnapshot, new: &SchemaSnapshot) {
+    for (tables, snapshot) in [(&mut diff.new_tables, new), (&mut diff.dropped_tables, old)] {
+        for table in tables.iter_mut() {
+            let declared = find_table(snapshot, &table.name);
+            for column in &mut table.columns {
+                restore_type(column, declared);
+            }
+        }
+    }
+    for table_diff in &mut diff.modified_tables {
+        let name = table_diff.table_name.clone();
+        restore_table_diff(table_diff, find_table(old, &name), find_table(new, &name));
+    }
+    for rename in &mut diff.renamed_tables {
+        if let Some(changes) = &mut rename.changes {
+            restore_table_diff(
+                changes,
+                find_table(old, &rename.old_name),
+                find_table(new, &rename.new_name),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,359 @@
+use super::*;
+use crate::db_types::PrimaryKey;
+use chrono::Utc;
+
+fn snapshot(tables: Vec<TableDefinition>) -> SchemaSnapshot {
+    SchemaSnapshot {
+        id: None,
+        connection_id: "conn-1".to_string(),
+        database_name: None,
+        timestamp: Utc::now(),
+        schema_hash: "hash".to_string(),
+        tables,
+        views: vec![],
+        routines: vec![],
+        triggers: vec![],
+    }
+}
+
+fn column(name: &str, column_type: &str) -> ColumnSchema {
+    ColumnSchema {
+        name: name.to_string(),
+        data_type: column_type
+            .split('(')
+            .next()
+            .unwrap_or_default()
+            .to_string(),
+        column_type: column_type.to_string(),
+        is_nullable: true,
+        column_key: String::new(),
+        column_default: None,
+        extra: String::new(),
+        collation: None,
+    }
+}
+
+fn translate(
+    col: &ColumnSchema,
+    from: DatabaseType,
+    to: DatabaseType,
+) -> (ColumnSchema, Vec<String>) {
+    let mut warnings = Vec::new();
+    let translated = translate_column(col, "t", &from, &to, &mut warnings);
+    (translated, warnings)
+}
+
+#[test]
+fn parses_engine_specific_spellings() {
+    use DatabaseType::*;
+    assert_eq!(
+        parse_column_type("tinyint(1)", &MySQL),
+        CanonicalType::Boolean
+    );
+    assert_eq!(
+        parse_column_type("int(10) unsigned", &MySQL),
+        CanonicalType::Integer {
+            bytes: 4,
+            unsigned: true
+        }
+    );
+    assert_eq!(
+        parse_column_type("tinyint", &MSSQL),
+        CanonicalType::Integer {
+            bytes: 1,
+            unsigned: true
+        }
+    );
+    assert_eq!(
+        parse_column_type("Int8", &ClickHouse),
+        CanonicalType::Integer {
+            bytes: 1,
+            unsigned: false
+        }
+    );
+    assert_eq!(
+        parse_column_type("int8", &PostgreSQL),
+        CanonicalType::Integer {
+            bytes: 8,
+            unsigned: false
+        }
+    );
+    assert_eq!(
+        parse_column_type("nvarchar(MAX)", &MSSQL),
+        CanonicalType::Text
+    );
+    assert_eq!(
+        parse_column_type("LowCardinality(Nullable(String))", &ClickHouse),
+        CanonicalType::Text
+    );
+    assert_eq!(
+        parse_column_type("datetime(6)", &MySQL),
+        CanonicalType::DateTime {
+            precision: Some(6),
+            with_time_zone: false
+        }
+    );
+    assert_eq!(
+        parse_column_type("DateTime64(3, 'UTC')", &ClickHouse),
+        CanonicalType::DateTime {
+            precision: Some(3),
+            with_time_zone: true
+        }
+    );
+    assert_eq!(
+        parse_column_type("decimal(10,2)", &MySQL),
+        CanonicalType::Decimal {
+            precision: Some(10),
+            scale: Some(2)
+        }
+    );
+    assert_eq!(
+        parse_column_type("enum('a','b,c')", &MySQL),
+        CanonicalType::Enum {
+            values: vec!["a".to_string(), "b,c".to_string()]
+        }
+    );
+    assert_eq!(
+        parse_column_type("Enum8('on' = 1, 'off' = 2)", &ClickHouse),
+        CanonicalType::Enum {
+            values: vec!["on".to_string(), "off".to_string()]
+        }
+    );
+    assert_eq!(parse_column_type("jsonb", &PostgreSQL), CanonicalType::Json);
+    assert_eq!(
+        parse_column_type("uniqueidentifier", &MSSQL),
+        CanonicalType::Uuid
+    );
+    assert_eq!(
+        parse_column_type("_int4", &PostgreSQL),
+        CanonicalType::Unknown {
+            raw: "_int4".to_string()
+        }
+    );
+}
+
+#[test]
+fn mysql_types_render_for_every_engine() {
+    let cases = [
+        ("tinyint(1)", DatabaseType::PostgreSQL, "boolean"),
+        ("datetime(6)", DatabaseType::PostgreSQL, "timestamp(6)"),
+        ("int unsigned", DatabaseType::PostgreSQL, "bigint"),
+        ("bigint unsigned", DatabaseType::PostgreSQL, "numeric(20,0)"),
+        ("varchar(255)", DatabaseType::MSSQL, "nvarchar(255)"),
+        ("longtext", DatabaseType::MSSQL, "nvarchar(MAX)"),
+        ("json", DatabaseType::MSSQL, "nvarchar(MAX)"),
+        ("datetime(6)", DatabaseType::MSSQL, "datetime2(6)"),
+        ("int unsigned", DatabaseType::ClickHouse, "UInt32"),
+        ("decimal(10,2)", DatabaseType::ClickHouse, "Decimal(10, 2)"),
+        ("tinyint(1)", DatabaseType::SQLite, "BOOLEAN"),
+        ("double", DatabaseType::SQLite, "REAL"),
+    ];
+    for (source, target, expected) in cases {
+        let (translated, _) = translate(&column("c", source), DatabaseType::MySQL, target.clone());
+        assert_eq!(
+            translated.column_type, expected,
+            "{} -> {:?}",
+            source, target
+        );
+    }
+}
+
+#[test]
+fn auto_increment_maps_to_serial_and_identity() {
+    let mut id = column("id", "int");
+    id.is_nullable = false;
+    id.extra = "auto_increment".to_string();
+
+    let (pg, _) = translate(&id, DatabaseType::MySQL, DatabaseType::PostgreSQL);
+    assert_eq!(pg.column_type, "serial");
+    assert_eq!(pg.extra, "auto_increment");
+
+    let (mssql, _) = translate(&id, DatabaseType::MySQL, DatabaseType::MSSQL);
+    assert_eq!(mssql.column_type, "int");
+    assert_eq!(mssql.extra, "IDENTITY(1,1)");
+
+    let mut identity = column("id", "bigint");
+    identity.extra = "IDENTITY(1,1)".to_string();
+    let (mysql, _) = translate(&identity, DatabaseType::MSSQL, DatabaseType::MySQL);
+    assert_eq!(mysql.column_type, "bigint");
+    assert_eq!(mysql.extra, "auto_increment");
+
+    let (clickhouse, warnings) =
+        translate(&identity, DatabaseType::MSSQL, DatabaseType::ClickHouse);
+    assert!(clickhouse.extra.is_empty());
+    assert_eq!(warnings.len(), 1);
+}
+
+#[test]
+fn defaults_are_translated_or_dropped() {
+    let mut flag = column("active", "bit");
+    flag.column_default = Some("((1))".to_string());
+    let (pg, _) = translate(&flag, DatabaseType::MSSQL, DatabaseType::PostgreSQL);
+    assert_eq!(pg.column_default.as_deref(), Some("true"));
+
+    let mut created = column("created_at", "timestamptz");
+    created.column_default = Some("now()".to_string());
+    let (mysql, _) = translate(&created, DatabaseType::PostgreSQL, DatabaseType::MySQL);
+    assert_eq!(mysql.column_default.as_deref(), Some("CURRENT_TIMESTAMP"));
+
+    let mut status = column("status", "varchar(20)");
+    status.column_default = Some("'new'::character varying".to_string());
+    let (mssql, _) = translate(&status, DatabaseType::PostgreSQL, DatabaseType::MSSQL);
+    assert_eq!(mssql.column_default.as_deref(), Some("'new'"));
+
+    let mut unquoted = column("status", "varchar(20)");
+    unquoted.column_default = Some("pending".to_string());
+    let (pg, _) = translate(&unquoted, DatabaseType::MySQL, DatabaseType::PostgreSQL);
+    assert_eq!(pg.column_default.as_deref(), Some("'pending'"));
+
+    let mut computed = column("slug", "text");
+    computed.column_default = Some("lower(name)".to_string());
+    let (sqlite, warnings) = translate(&computed, DatabaseType::PostgreSQL, DatabaseType::SQLite);
+    assert_eq!(sqlite.column_default, None);
+    assert_eq!(warnings.len(), 1);
+}
+
+#[test]
+fn unknown_types_fall_back_to_text_with_warning() {
+    let (translated, warnings) = translate(
+        &column("tags", "_text"),
+        DatabaseType::PostgreSQL,
+        DatabaseType::MySQL,
+    );
+    assert_eq!(translated.column_type, "longtext");
+    assert_eq!(warnings.len(), 1);
+
+    let (same, warnings) = translate(
+        &column("tags", "_text"),
+        DatabaseType::PostgreSQL,
+        DatabaseType::PostgreSQL,
+    );
+    assert_eq!(same.column_type, "_text");
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn enum_tables_get_check_constraints_and_bare_names() {
+    let table = TableDefinition {
+        name: "dbo.orders".to_string(),
+        columns: vec![column("state", "varchar(10)")],
+        primary_keys: vec![PrimaryKey {
+            column_name: "state".to_string(),
+            ordinal_position: 1,
+        }],
+        ..Default::default()
+    };
+    let mut warnings = Vec::new();
+    let translated = translate_table(
+        &table,
+        &DatabaseType::MSSQL,
+        &DatabaseType::PostgreSQL,
+        &mut warnings,
+    );
+    assert_eq!(translated.name, "orders");
+    assert!(translated.constraints.is_empty());
+
+    let table = TableDefinition {
+        name: "orders".to_string(),
+        columns: vec![column("state", "enum('new','paid')")],
+        ..Default::default()
+    };
+    let translated = translate_table(
+        &table,
+        &DatabaseType::MySQL,
+        &DatabaseType::PostgreSQL,
+        &mut warnings,
+    );
+    assert_eq!(translated.columns[0].column_type, "varchar(4)");
+    assert_eq!(translated.constraints.len(), 1);
+    assert_eq!(
+        translated.constraints[0].definition.as_deref(),
+        Some("state IN ('new','paid')")
+    );
+}
+
+#[test]
+fn normalised_snapshots_compare_cleanly_across_engines() {
+    let source = snapshot(vec![TableDefinition {
+        name: "users".to_string(),
+        columns: vec![column("id", "int"), column("active", "tinyint(1)")],
+        ..Default::default()
+    }]);
+    let target = snapshot(vec![TableDefinition {
+        name: "users".to_string(),
+        columns: vec![column("id", "int4"), column("active", "bool")],
+        ..Default::default()
+    }]);
+
+    let (source, _) = translate_snapshot(&source, &DatabaseType::MySQL, &DatabaseType::PostgreSQL);
+    let (target, _) = translate_snapshot(
+        &target,
+        &DatabaseType::PostgreSQL,
+        &DatabaseType::PostgreSQL,
+    );
+    let diff = crate::schema_tracker::diff::compare_schemas(&target, &source);
+    assert!(!diff.has_changes());
+}
+
+#[test]
+fn cross_engine_comparison_ignores_catalog_spelling_and_keeps_declared_types() {
+    let primary_keys = vec![PrimaryKey {
+        column_name: "id".to_string(),
+        ordinal_position: 1,
+    }];
+    let source = snapshot(vec![TableDefinition {
+        name: "users".to_string(),
+        columns: vec![
+            ColumnSchema {
+                column_key: "PRI".to_string(),
+                is_nullable: false,
+                extra: "auto_increment".to_string(),
+                ..column("id", "int")
+            },
+            ColumnSchema {
+                column_default: Some("x".to_string()),
+                ..column("name", "varchar(50)")
+            },
+        ],
+        primary_keys: primary_keys.clone(),
+        ..Default::default()
+    }]);
+    let target = snapshot(vec![TableDefinition {
+        name: "users".to_string(),
+        columns: vec![
+            ColumnSchema {
+                is_nullable: false,
+                extra: "IDENTITY(1000,1)".to_string(),
+                ..column("id", "int")
+            },
+            ColumnSchema {
+                is_nullable: false,
+                column_default: Some("('x')".to_string()),
+                ..column("name", "varchar(50)")
+            },
+        ],
+        primary_keys,
+        ..Default::default()
+    }]);
+
+    let (translated, _) = translate_snapshot(&source, &DatabaseType::MySQL, &DatabaseType::MSSQL);
+    let mut diff = crate::schema_tracker::diff::compare_schemas(
+        &normalize_snapshot(&target, &DatabaseType::MSSQL, true),
+        &normalize_snapshot(&translated, &DatabaseType::MSSQL, true),
+    );
+    restore_declared_types(&mut diff, &target, &translated);
+
+    assert_eq!(diff.modified_tables.len(), 1);
+    let modified = &diff.modified_tables[0].modified_columns;
+    assert_eq!(modified.len(), 1);
+    assert_eq!(modified[0].column_name, "name");
+    assert_eq!(
+        modified[0].changes,
+        vec![DiffType::NullableChanged {
+            old: false,
+            new: true
+        }]
+    );
+    assert_eq!(modified[0].old_column.column_type, "varchar(50)");
+    assert_eq!(modified[0].new_column.column_type, "varchar(50)");
+}
@@ -5,9 +5,12 @@
 use crate::db_types::*;
 use futures::StreamExt;
 use serde_json::Value;
-use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use std::ffi::{CStr, CString};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions, SqliteRow};
 use sqlx::ConnectOptions;
-use sqlx::{Column, Pool, Row, Sqlite};
+use sqlx::pool::PoolConnection;
+use sqlx::query::Query;
+use sqlx::{Column, Database, Execute, Executor, Pool, Row, Sqlite, SqliteConnection};
 use tokio::time::{timeout, Duration};
 
 const DEFAULT_QUERY_TIMEOUT_SECS: u64 = 30;
@@ -46,11 +49,24 @@ fn build_connect_options(db_path: &str) -> SqliteConnectOptions {
 }
 
 pub async fn create_pool(db_path: &str) -> Result<Pool<Sqlite>, String> {
+    create_pool_with_session(db_path, &[]).await
+}
+
+/// Like `create_pool`, but every connection opens with `session_settings`
+/// applied as `PRAGMA name = value`.
+pub async fn create_pool_with_session(
+    db_path: &str,
+    session_settings: &[(String, String)],
+) -> Result<Pool<Sqlite>, String> {
     if db_path.is_empty() {
         return Err("Database file path is required".to_string());
     }
 
-    let options = build_connect_options(db_path);
+    let options = session_settings
+        .iter()
+        .fold(build_connect_options(db_path), |options, (name, value)| {
+            options.pragma(name.clone(), value.clone())
+        });
 
     SqlitePoolOptions::new()
         .max_connections(5)
@@ -263,6 +279,7 @@ pub async fn get_triggers(pool: &Pool<Sqlite>) -> Result<Vec<TriggerInfo>, Strin
                 event: "UNKNOWN".to_string(),
                 timing: "UNKNOWN".to_string(),
                 table_name: tbl_name,
+                definition: None,
             })
         })
         .collect())
@@ -289,6 +306,182 @@ pub async fn get_trigger_ddl(pool: &Pool<Sqlite>, trigger_name: &str) -> Result<
 // --- Query Execution ---
 
 pub async fn execute_query(pool: &Pool<Sqlite>, query: &str) -> Result<Vec<QueryResult>, String> {
+    execute_query_with_executor(pool, query).await
+}
+
+/// Runs `query` on an already-held connection, e.g. one pinned to a transaction.
+pub async fn execute_query_on_connection(
+    conn: &mut SqliteConnection,
+    query: &str,
+) -> Result<Vec<QueryResult>, String> {
+    execute_query_with_executor(conn, query).await
+}
+
+/// Runs one script statement on an already-held connection, returning its
+/// results and the rows it affected.
+pub async fn execute_script_statement(
+    conn: &mut SqliteConnection,
+    query: &str,
+) -> Result<(Vec<QueryResult>, u64), String> {
+    let query_timeout = Duration::from_secs(DEFAULT_QUERY_TIMEOUT_SECS);
+    timeout(query_timeout, execute_query_counting(conn, sqlx::raw_sql(query)))
+        .await
+        .map_err(|_| format!("Query timed out after {} seconds", DEFAULT_QUERY_TIMEOUT_SECS))?
+}
+
+/// Raw connection handle that `sqlite3_interrupt` can be called on from another
+/// thread while a statement runs.
+#[derive(Debug, Clone, Copy)]
+pub struct SqliteInterruptHandle(usize);
+
+pub async fn interrupt_handle(conn: &mut SqliteConnection) -> Result<SqliteInterruptHandle, String> {
+    let mut handle = conn
+        .lock_handle()
+        .await
+        .map_err(|e| format!("Failed to lock SQLite connection: {}", e))?;
+    Ok(SqliteInterruptHandle(handle.as_raw_handle().as_ptr() as usize))
+}
+
+/// Makes the statement running on `handle` fail with `SQLITE_INTERRUPT`.
+///
+/// # Safety
+///
+/// The connection `handle` was taken from must still be open: the handle holds
+/// no reference to it, so it must not outlive the connection, and a closed
+/// connection's pointer may already be freed.
+pub unsafe fn interrupt(handle: SqliteInterruptHandle) {
+    // SAFETY: sqlite3_interrupt may be called from any thread on an open
+    // connection, which the caller guarantees
+    unsafe { libsqlite3_sys::sqlite3_interrupt(handle.0 as *mut libsqlite3_sys::sqlite3) }
+}
+
+// --- Backup ---
+
+/// Writes a consistent copy of the open database to `file_path` with
+/// `VACUUM INTO`, which reads a single snapshot while other connections keep working.
+pub async fn backup_to_file(pool: &Pool<Sqlite>, file_path: &str) -> Result<(), String> {
+    if std::path::Path::new(file_path).exists() {
+        std::fs::remove_file(file_path)
+            .map_err(|e| format!("Failed to replace existing backup file: {}", e))?;
+    }
+    sqlx::query("VACUUM INTO ?")
+        .bind(file_path)
+        .execute(pool)
+        .await
+        .map_err(|e| format!("SQLite backup failed: {}", e))?;
+    Ok(())
+}
+
+/// Replaces the contents of the connection's main database with the database
+/// file at `source_path`, using the SQLite online backup API. The copy runs on a
+/// blocking thread that owns the connection until it finishes.
+pub async fn restore_from_file(
+    mut conn: PoolConnection<Sqlite>,
+    source_path: &str,
+) -> Result<(), String> {
+    let source = CString::new(source_path).map_err(|_| "Invalid backup file path".to_string())?;
+    let runtime = tokio::runtime::Handle::current();
+    tokio::task::spawn_blocking(move || {
+        let mut handle = runtime
+            .block_on(conn.lock_handle())
+            .map_err(|e| format!("Failed to lock SQLite connection: {}", e))?;
+        // SAFETY: `handle` keeps the connection locked until the copy returns
+        unsafe { restore_into(handle.as_raw_handle().as_ptr(), &source) }
+    })
+    .await
+    .map_err(|e| format!("SQLite restore failed: {}", e))?
+}
+
+/// Copies the database at `source` over the main database of `dest`.
+///
+/// # Safety
+///
+/// `dest` must be an open connection that nothing else uses until this returns.
+unsafe fn restore_into(dest: *mut libsqlite3_sys::sqlite3, source: &CStr) -> Result<(), String> {
+    use libsqlite3_sys as ffi;
+
+    let main = CString::new("main").unwrap();
+    // SAFETY: the caller guarantees `dest`, and `src` is opened, used and
+    // closed entirely within this block.
+    unsafe {
+        let mut src: *mut ffi::sqlite3 = std::ptr::null_mut();
+        let rc = ffi::sqlite3_open_v2(
+            source.as_ptr(),
+            &mut src,
+            ffi::SQLITE_OPEN_READONLY,
+            std::ptr::null(),
+        );
+        if rc != ffi::SQLITE_OK {
+            ffi::sqlite3_close(src);
+            return Err(format!("Failed to open backup file (SQLite error {})", rc));
+        }
+
+        let backup = ffi::sqlite3_backup_init(dest, main.as_ptr(), src, main.as_ptr());
+        if backup.is_null() {
+            let message = CStr::from_ptr(ffi::sqlite3_errmsg(dest))
+                .to_string_lossy()
+                .to_string();
+            ffi::sqlite3_close(src);
+            return Err(format!("SQLite restore failed: {}", message));
+        }
+        let step = ffi::sqlite3_backup_step(backup, -1);
+        let finish = ffi::sqlite3_backup_finish(backup);
+        ffi::sqlite3_close(src);
+
+        if step != ffi::SQLITE_DONE {
+            return Err(format!("SQLite restore failed (SQLite error {})", step));
+        }
+        if finish != ffi::SQLITE_OK {
+            return Err(format!("SQLite restore failed (SQLite error {})", finish));
+        }
+    }
+    Ok(())
+}
+
+fn bind_parameters<'q>(
+    mut query: Query<'q, Sqlite, <Sqlite as Database>::Arguments<'q>>,
+    params: &'q [BindValue],
+) -> Query<'q, Sqlite, <Sqlite as Database>::Arguments<'q>> {
+    for param in params {
+        query = match param {
+            BindValue::Bool(v) => query.bind(*v),
+            BindValue::Int(v) => query.bind(*v),
+            BindValue::Float(v) => query.bind(*v),
+            BindValue::Decimal(v) | BindValue::Text(v) | BindValue::Uuid(v) => {
+                query.bind(v.as_deref())
+            }
+            BindValue::Json(v) => query.bind(v.as_ref().map(sqlx::types::Json)),
+            BindValue::Bytes(v) => query.bind(v.as_deref()),
+            BindValue::Date(v) => query.bind(*v),
+            BindValue::Time(v) => query.bind(*v),
+            BindValue::DateTime(v) => query.bind(*v),
+            BindValue::Timestamp(v) => query.bind(*v),
+        };
+    }
+    query
+}
+
+/// Runs one prepared statement with `?` placeholders bound to `params`, on either
+/// a pool or a pinned connection.
+pub async fn execute_parameterized_query<'a, E>(
+    executor: E,
+    query: &'a str,
+    params: &'a [BindValue],
+) -> Result<Vec<QueryResult>, String>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let statement = bind_parameters(sqlx::query(query), params);
+    let query_timeout = Duration::from_secs(DEFAULT_QUERY_TIMEOUT_SECS);
+    timeout(query_timeout, execute_query_internal(executor, statement))
+        .await
+        .map_err(|_| format!("Query timed out after {} seconds", DEFAULT_QUERY_TIMEOUT_SECS))?
+}
+
+async fn execute_query_with_executor<'a, E>(executor: E, query: &'a str) -> Result<Vec<QueryResult>, String>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
     let query = query.trim();
     if query.is_empty() {
         return Ok(vec![QueryResult {
@@ -302,15 +495,57 @@ pub async fn execute_query(pool: &Pool<Sqlite>, query: &str) -> Result<Vec<Query
 
     let query_timeout = Duration::from_secs(DEFAULT_QUERY_TIMEOUT_SECS);
     
-    let result = timeout(query_timeout, execute_query_internal(pool, query)).await
+    let result = timeout(query_timeout, execute_query_internal(executor, sqlx::raw_sql(query))).await
         .map_err(|_| format!("Query timed out after {} seconds", DEFAULT_QUERY_TIMEOUT_SECS))??;
 
     Ok(result)
 }
 
-async fn execute_query_internal(pool: &Pool<Sqlite>, query: &str) -> Result<Vec<QueryResult>, String> {
+pub(crate) fn row_to_json_values(row: &SqliteRow) -> Vec<Value> {
+    let mut row_values = Vec::new();
+    for col in row.columns() {
+        let col_name = col.name();
+        let value = if let Ok(v) = row.try_get::<String, _>(col_name) {
+            Value::String(v)
+        } else if let Ok(v) = row.try_get::<i64, _>(col_name) {
+            serde_json::json!(v)
+        } else if let Ok(v) = row.try_get::<f64, _>(col_name) {
+            serde_json::json!(v)
+        } else if let Ok(v) = row.try_get::<Vec<u8>, _>(col_name) {
+            Value::String(base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &v))
+        } else if let Ok(v) = row.try_get::<bool, _>(col_name) {
+            Value::Bool(v)
+        } else {
+            Value::Null
+        };
+        row_values.push(value);
+    }
+    row_values
+}
+
+async fn execute_query_internal<'a, E, S>(executor: E, statement: S) -> Result<Vec<QueryResult>, String>
+where
+    E: Executor<'a, Database = Sqlite>,
+    S: Execute<'a, Sqlite> + 'a,
+{
+    execute_query_counting(executor, statement)
+        .await
+        .map(|(results, _)| results)
+}
+
+/// Like `execute_query_internal`, also returning the total rows affected
+/// across the statement's results.
+async fn execute_query_counting<'a, E, S>(
+    executor: E,
+    statement: S,
+) -> Result<(Vec<QueryResult>, u64), String>
+where
+    E: Executor<'a, Database = Sqlite>,
+    S: Execute<'a, Sqlite> + 'a,
+{
     let mut results = Vec::new();
-    let mut stream = sqlx::raw_sql(query).fetch_many(pool);
+    let mut rows_affected = 0u64;
+    let mut stream = executor.fetch_many(statement);
     let mut current_rows = Vec::new();
     let mut current_columns = Vec::new();
 
@@ -319,7 +554,8 @@ async fn execute_query_internal(pool: &Pool<Sqlite>, query: &str) -> Result<Vec<
             Ok(either) => {
                 use sqlx::Either;
                 match either {
-                    Either::Left(_done) => {
+                    Either::Left(done) => {
+                        rows_affected += done.rows_affected();
                         if !current_rows.is_empty() || !current_columns.is_empty() {
                             results.push(QueryResult {
                                 columns: current_columns.clone(),
@@ -341,25 +577,7 @@ async fn execute_query_internal(pool: &Pool<Sqlite>, query: &str) -> Result<Vec<
                                 .collect();
                         }
 
-                        let mut row_values = Vec::new();
-                        for col in row.columns() {
-                            let col_name = col.name();
-                            let value = if let Ok(v) = row.try_get::<String, _>(col_name) {
-                                Value::String(v)
-                            } else if let Ok(v) = row.try_get::<i64, _>(col_name) {
-                                serde_json::json!(v)
-                            } else if let Ok(v) = row.try_get::<f64, _>(col_name) {
-                                serde_json::json!(v)
-                            } else if let Ok(v) = row.try_get::<Vec<u8>, _>(col_name) {
-                                Value::String(base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &v))
-                            } else if let Ok(v) = row.try_get::<bool, _>(col_name) {
-                                Value::Bool(v)
-                            } else {
-                                Value::Null
-                            };
-                            row_values.push(value);
-                        }
-                        current_rows.push(row_values);
+                        current_rows.push(row_to_json_values(&row));
                     }
                 }
             }
@@ -389,7 +607,81 @@ async fn execute_query_internal(pool: &Pool<Sqlite>, query: &str) -> Result<Vec<
         });
     }
 
-    Ok(results)
+    Ok((results, rows_affected))
+}
+
+/// Streams the first result set of `query` into `tx` in batches. Returns early,
+/// abandoning the connection, once the receiving cursor is closed.
+pub async fn stream_query(
+    pool: &Pool<Sqlite>,
+    query: &str,
+    tx: &tokio::sync::mpsc::Sender<ResultStreamEvent>,
+) -> Result<(), String> {
+    let mut conn = pool
+        .acquire()
+        .await
+        .map_err(|e| format!("Failed to acquire connection: {}", e))?;
+
+    let mut cancelled = false;
+    {
+        let mut stream = sqlx::raw_sql(query).fetch_many(conn.as_mut());
+        let mut columns_sent = false;
+        let mut batch = Vec::with_capacity(RESULT_STREAM_BATCH_SIZE);
+
+        loop {
+            let next = tokio::select! {
+                item = stream.next() => item,
+                _ = tx.closed() => {
+                    cancelled = true;
+                    break;
+                }
+            };
+            let Some(item) = next else {
+                break;
+            };
+
+            match item.map_err(|e| format!("Query execution failed: {}", e))? {
+                sqlx::Either::Left(_done) => {
+                    // Only the first result set is streamed
+                    if columns_sent {
+                        break;
+                    }
+                }
+                sqlx::Either::Right(row) => {
+                    if !columns_sent {
+                        let columns = row.columns().iter().map(|c| c.name().to_string()).collect();
+                        if tx.send(ResultStreamEvent::Columns(columns)).await.is_err() {
+                            cancelled = true;
+                            break;
+                        }
+                        columns_sent = true;
+                    }
+
+                    batch.push(row_to_json_values(&row));
+                    if batch.len() >= RESULT_STREAM_BATCH_SIZE
+                        && tx
+                            .send(ResultStreamEvent::Rows(std::mem::take(&mut batch)))
+                            .await
+                            .is_err()
+                    {
+                        cancelled = true;
+                        break;
+                    }
+                }
+            }
+        }
+
+        if !cancelled && !batch.is_empty() {
+            let _ = tx.send(ResultStreamEvent::Rows(batch)).await;
+        }
+    }
+
+    if cancelled {
+        // Drop the connection instead of draining the rest of the result set
+        let _ = conn.detach();
+    }
+
+    Ok(())
 }
 
 pub async fn get_execution_plan(pool: &Pool<Sqlite>, query: &str) -> Result<String, String> {
@@ -385,31 +385,5 @@ pub async fn close_all_tunnels() -> Result<(), String> {
     Ok(())
 }
 
-pub async fn close_all_except(connection_key: Option<&str>) -> Result<(), String> {
-    let keep = connection_key.map(str::to_string);
-    let tunnels = {
-        let mut guard = ACTIVE_TUNNELS.lock().await;
-        let keys_to_remove: Vec<String> = guard
-            .keys()
-            .filter(|key| keep.as_ref().map(|k| k != *key).unwrap_or(true))
-            .cloned()
-            .collect();
-
-        let mut removed = Vec::with_capacity(keys_to_remove.len());
-        for key in keys_to_remove {
-            if let Some(tunnel) = guard.remove(&key) {
-                removed.push(tunnel);
-            }
-        }
-        removed
-    };
-
-    for mut tunnel in tunnels {
-        tunnel.stop();
-    }
-
-    Ok(())
-}
-
 #[cfg(test)]
 mod tests;
@@ -10,6 +10,7 @@ use std::fs;
 use std::path::Path;
 use std::time::Instant;
 use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
 use tokio::time::{sleep, Duration};
 
 #[derive(Clone, Default)]
@@ -49,7 +50,7 @@ pub async fn execute_task_with_run(
 async fn execute_non_composite_task(
     app: &AppHandle,
     task: &TaskDefinition,
-    _context: &ExecutorContext,
+    context: &ExecutorContext,
 ) -> Result<Value, String> {
     match task.task_type {
         TaskType::SqlScript => execute_sql_task(app, task).await,
@@ -57,6 +58,9 @@ async fn execute_non_composite_task(
         TaskType::SchemaSnapshot => execute_schema_snapshot_task(app, task).await,
         TaskType::DataCompareSync => execute_data_compare_sync_task(app, task).await,
         TaskType::DataTransferMigration => execute_data_transfer_migration_task(app, task).await,
+        TaskType::OnlineSchemaChange => {
+            execute_online_schema_change_task(app, task, context).await
+        }
         TaskType::Composite => Err("Nested composite tasks are not supported".to_string()),
     }
 }
@@ -67,14 +71,24 @@ async fn execute_sql_task(app: &AppHandle, task: &TaskDefinition) -> Result<Valu
         .ok_or("SQL task payload requires one of: sql, query, script".to_string())?;
 
     let timeout_seconds = get_payload_u64(payload, &["timeoutSeconds", "queryTimeoutSeconds"]);
+    let parameters = match payload.get("parameters").or_else(|| payload.get("params")) {
+        Some(value) => crate::db::parameters_from_json(value)?,
+        None => Vec::new(),
+    };
 
-    let state = app.state::<AppState>();
+    let state = resolve_task_state(app, payload).await?;
     let db_type = {
         let guard = state.active_db_type.lock().await;
         guard.clone()
     };
 
     let (result_sets, total_rows) = match db_type {
+        _ if !parameters.is_empty() => {
+            let results =
+                crate::db::execute_query_with_parameters(&state, &sql, &parameters, timeout_seconds)
+                    .await?;
+            summarize_result_sets(&results)
+        }
         DatabaseType::MySQL => {
             let pool = {
                 let guard = state.mysql_pool.lock().await;
@@ -153,170 +167,33 @@ async fn execute_backup_task(app: &AppHandle, task: &TaskDefinition) -> Result<V
     let database = get_payload_string(payload, &["database", "schema", "dbName"])
         .ok_or("Backup task payload requires database".to_string())?;
     let include_data = get_payload_bool(payload, &["includeData"]).unwrap_or(true);
-    let file_path = get_payload_string(payload, &["filePath", "path"])
-        .unwrap_or(default_backup_file_path(app, &task.id, &database)?);
+    let options = crate::db::BackupOptions {
+        compression: parse_payload_enum(payload, &["compression"])?,
+        format: parse_payload_enum(payload, &["format"])?,
+        insert_batch_size: get_payload_usize(payload, &["insertBatchSize", "batchSize"]),
+        job_id: Some(format!("task:{}", task.id)),
+    };
 
-    let state = app.state::<AppState>();
+    let state = resolve_task_state(app, payload).await?;
     let db_type = {
         let guard = state.active_db_type.lock().await;
         guard.clone()
     };
 
-    let mut output = String::new();
-    output.push_str("-- TactileSQL Task Backup\n");
-    output.push_str(&format!("-- Task ID: {}\n", task.id));
-    output.push_str(&format!("-- Database/Schema: {}\n", database));
-    output.push_str(&format!("-- Generated at: {}\n\n", Utc::now().to_rfc3339()));
-
-    let table_count: usize;
-
-    match db_type {
-        DatabaseType::PostgreSQL => {
-            let pool = {
-                let guard = state.postgres_pool.lock().await;
-                guard
-                    .as_ref()
-                    .cloned()
-                    .ok_or("No PostgreSQL connection established".to_string())?
-            };
-
-            let tables = crate::postgres::get_tables(&pool, &database).await?;
-            table_count = tables.len();
-
-            for table in tables {
-                output.push_str(&format!("-- Table: {}\n", table));
-                let ddl = crate::postgres::get_table_ddl(&pool, &database, &table).await?;
-                output.push_str(&ensure_sql_terminated(&ddl));
-                output.push('\n');
-
-                if include_data {
-                    let query = format!(
-                        "SELECT * FROM {}",
-                        qualified_table_name(&db_type, &database, &table)
-                    );
-                    let results = crate::postgres::execute_query(&pool, query).await?;
-                    if let Some(first) = results.first() {
-                        for stmt in build_insert_statements(&db_type, &database, &table, first) {
-                            output.push_str(&stmt);
-                            output.push('\n');
-                        }
-                    }
-                }
-                output.push('\n');
-            }
-        }
-        DatabaseType::MySQL => {
-            let pool = {
-                let guard = state.mysql_pool.lock().await;
-                guard
-                    .as_ref()
-                    .cloned()
-                    .ok_or("No MySQL connection established".to_string())?
-            };
-
-            let tables = crate::mysql::get_tables(&pool, &database).await?;
-            table_count = tables.len();
-
-            output.push_str(&format!("USE {};\n\n", quote_identifier_mysql(&database)));
-
-            for table in tables {
-                output.push_str(&format!("-- Table: {}\n", table));
-                let ddl = crate::mysql::get_table_ddl(&pool, &database, &table).await?;
-                output.push_str(&ensure_sql_terminated(&ddl));
-                output.push('\n');
-
-                if include_data {
-                    let query = format!(
-                        "SELECT * FROM {}",
-                        qualified_table_name(&db_type, &database, &table)
-                    );
-                    let results = crate::mysql::execute_query(&pool, query).await?;
-                    if let Some(first) = results.first() {
-                        for stmt in build_insert_statements(&db_type, &database, &table, first) {
-                            output.push_str(&stmt);
-                            output.push('\n');
-                        }
-                    }
-                }
-                output.push('\n');
-            }
+    let file_path = match get_payload_string(payload, &["filePath", "path"]) {
+        Some(path) => path,
+        None => {
+            // SQLite backups default to a copy of the database file
+            let native = db_type == DatabaseType::SQLite
+                && options.format != Some(crate::db::BackupFormat::Sql);
+            let extension = if native { "db" } else { "sql" };
+            default_backup_file_path(app, &task.id, &database, extension)?
         }
-        DatabaseType::MSSQL => {
-            let pool = {
-                let guard = state.mssql_pool.lock().await;
-                guard
-                    .as_ref()
-                    .cloned()
-                    .ok_or("No MSSQL connection established".to_string())?
-            };
-
-            let tables = mssql::get_tables(&pool, &database, "dbo").await?; // Assuming dbo for backup
-            table_count = tables.len();
-
-            for table in tables {
-                output.push_str(&format!("-- Table: {}\n", table));
-                // DDL implementation for MSSQL is pending in mssql.rs
-                let ddl = mssql::get_table_ddl(&pool, &database, "dbo", &table).await.unwrap_or_else(|_| format!("-- DDL not implemented for MSSQL: {}", table));
-                output.push_str(&ensure_sql_terminated(&ddl));
-                output.push('\n');
-
-                if include_data {
-                    let query = format!(
-                        "SELECT * FROM {}",
-                        qualified_table_name(&db_type, &database, &table)
-                    );
-                    let results = mssql::execute_query(&pool, query).await?;
-                    if let Some(first) = results.first() {
-                        for stmt in build_insert_statements(&db_type, &database, &table, first) {
-                            output.push_str(&stmt);
-                            output.push('\n');
-                        }
-                    }
-                }
-                output.push('\n');
-            }
-        }
-        DatabaseType::ClickHouse => {
-            let config = {
-                let guard = state.clickhouse_config.lock().await;
-                guard
-                    .as_ref()
-                    .cloned()
-                    .ok_or("No ClickHouse connection established".to_string())?
-            };
-
-            let tables = crate::clickhouse::get_tables(&config, &database).await?;
-            table_count = tables.len();
-
-            for table in tables {
-                output.push_str(&format!("-- Table: {}\n", table));
-                let ddl = crate::clickhouse::get_table_ddl(&config, &database, &table).await?;
-                output.push_str(&ensure_sql_terminated(&ddl));
-                output.push('\n');
-
-                if include_data {
-                    let query = format!(
-                        "SELECT * FROM {}",
-                        qualified_table_name(&db_type, &database, &table)
-                    );
-                    let results = crate::clickhouse::execute_query(&config, query).await?;
-                    if let Some(first) = results.first() {
-                        for stmt in build_insert_statements(&db_type, &database, &table, first) {
-                            output.push_str(&stmt);
-                            output.push('\n');
-                        }
-                    }
-                }
-                output.push('\n');
-            }
-        }
-        DatabaseType::SQLite => {
-            return Err("Backup task not yet supported for SQLite".to_string());
-        }
-        DatabaseType::Disconnected => return Err("No connection established".to_string()),
-    }
+    };
 
-    write_text_file(&file_path, &output)?;
+    let progress =
+        crate::db::backup_to_file(app, &state, &database, &file_path, include_data, options)
+            .await?;
 
     Ok(serde_json::json!({
         "executor": "backup",
@@ -324,13 +201,15 @@ async fn execute_backup_task(app: &AppHandle, task: &TaskDefinition) -> Result<V
         "database": database,
         "filePath": file_path,
         "includeData": include_data,
-        "tableCount": table_count,
+        "tableCount": progress.tables_done,
+        "rowCount": progress.rows_written,
+        "bytesWritten": progress.bytes_written,
     }))
 }
 
 async fn execute_schema_snapshot_task(app: &AppHandle, task: &TaskDefinition) -> Result<Value, String> {
     let payload = &task.payload;
-    let state = app.state::<AppState>();
+    let state = resolve_task_state(app, payload).await?;
 
     let db_type = {
         let guard = state.active_db_type.lock().await;
@@ -341,7 +220,7 @@ async fn execute_schema_snapshot_task(app: &AppHandle, task: &TaskDefinition) ->
         .unwrap_or_else(|| format!("task:{}", task.id));
     let persist_snapshot = get_payload_bool(payload, &["persistSnapshot"]).unwrap_or(true);
 
-    let snapshot = match db_type {
+    let mut snapshot = match db_type {
         DatabaseType::MySQL => {
             let pool = {
                 let guard = state.mysql_pool.lock().await;
@@ -400,7 +279,32 @@ async fn execute_schema_snapshot_task(app: &AppHandle, task: &TaskDefinition) ->
             .await?
         }
         DatabaseType::MSSQL => {
-            return Err("Schema snapshot not yet supported for MSSQL".to_string());
+            let pool = {
+                let guard = state.mssql_pool.lock().await;
+                guard
+                    .as_ref()
+                    .cloned()
+                    .ok_or("No MSSQL connection established".to_string())?
+            };
+
+            let database = match get_payload_string(payload, &["database", "schema", "dbName"]) {
+                Some(value) => value,
+                None => {
+                    let results = mssql::execute_query(&pool, "SELECT DB_NAME()".to_string()).await?;
+                    let row = results.first().and_then(|r| r.rows.first()).and_then(|row| row.first());
+                    row.and_then(|v| v.as_str()).map(|s| s.to_string()).unwrap_or_default()
+                }
+            };
+
+            if database.trim().is_empty() {
+                return Err(
+                    "Schema snapshot requires database. Provide payload.database or connect to a database."
+                        .to_string(),
+                );
+            }
+
+            crate::schema_tracker::capture::capture_snapshot_mssql(&pool, &database, &connection_id)
+                .await?
         }
         DatabaseType::ClickHouse => {
             let config = {
@@ -431,20 +335,97 @@ async fn execute_schema_snapshot_task(app: &AppHandle, task: &TaskDefinition) ->
                 .await?
         }
         DatabaseType::SQLite => {
-            return Err("Schema snapshot not yet supported for SQLite".to_string());
+            let pool = {
+                let guard = state.sqlite_pool.lock().await;
+                guard
+                    .as_ref()
+                    .cloned()
+                    .ok_or("No SQLite connection established".to_string())?
+            };
+            let database = get_payload_string(payload, &["database", "schema", "dbName"])
+                .unwrap_or_else(|| "main".to_string());
+
+            crate::schema_tracker::capture::capture_snapshot_sqlite(&pool, &database, &connection_id)
+                .await?
         }
         DatabaseType::Disconnected => return Err("No connection established".to_string()),
     };
 
-    let snapshot_id = if persist_snapshot {
-        let guard = state.schema_tracker_store.lock().await;
-        if let Some(store) = guard.as_ref() {
+    // Drift watch compares each capture with a pinned baseline or the previous capture
+    let drift_watch = get_payload_bool(payload, &["driftWatch", "watchDrift"]).unwrap_or(false);
+    let notify_on_drift = get_payload_bool(payload, &["notifyOnDrift", "driftNotify"]).unwrap_or(true);
+    let baseline_tag = get_payload_string(payload, &["baselineTag", "driftBaselineTag"])
+        .unwrap_or_else(|| crate::schema_tracker::drift::DEFAULT_BASELINE_TAG.to_string());
+
+    let guard = state.schema_tracker_store.lock().await;
+    let store = guard.as_ref();
+    let baseline = match store {
+        Some(store) if drift_watch => {
+            crate::schema_tracker::drift::resolve_baseline(
+                store,
+                &snapshot.connection_id,
+                snapshot.database_name.as_deref(),
+                &baseline_tag,
+            )
+            .await?
+        }
+        _ => None,
+    };
+
+    let snapshot_id = match store {
+        Some(store) if persist_snapshot => {
+            store.adopt_latest_hash(&mut snapshot).await?;
             Some(store.save_snapshot(&snapshot).await?)
-        } else {
-            None
         }
+        _ => None,
+    };
+
+    let drift = match (store, baseline.as_ref()) {
+        (Some(store), Some(baseline)) => {
+            crate::schema_tracker::drift::record_drift(
+                store,
+                baseline,
+                &snapshot,
+                snapshot_id,
+                Some(&task.id),
+                notify_on_drift,
+            )
+            .await?
+        }
+        _ => None,
+    };
+    drop(guard);
+
+    if let Some((event, true)) = drift.as_ref() {
+        if event.notified {
+            let _ = app
+                .notification()
+                .builder()
+                .title(format!("TactileSQL Schema Drift: {}", task.name))
+                .body(crate::schema_tracker::drift::drift_notification_body(event))
+                .show();
+        }
+    }
+
+    let drift_result = if !drift_watch {
+        Value::Null
+    } else if let Some((event, is_new)) = drift.as_ref() {
+        serde_json::json!({
+            "detected": true,
+            "new": is_new,
+            "eventId": event.id,
+            "baselineSnapshotId": event.baseline_snapshot_id,
+            "baselineTag": event.baseline_tag,
+            "changeCount": event.change_count,
+            "breakingChanges": event.breaking_changes,
+            "notified": *is_new && event.notified,
+        })
     } else {
-        None
+        serde_json::json!({
+            "detected": false,
+            "baselineSnapshotId": baseline.as_ref().and_then(|b| b.snapshot.id),
+            "baselineTag": baseline.as_ref().and_then(|b| b.tag.clone()),
+        })
     };
 
     Ok(serde_json::json!({
@@ -459,6 +440,7 @@ async fn execute_schema_snapshot_task(app: &AppHandle, task: &TaskDefinition) ->
         "viewCount": snapshot.views.len(),
         "routineCount": snapshot.routines.len(),
         "triggerCount": snapshot.triggers.len(),
+        "drift": drift_result,
     }))
 }
 
@@ -479,8 +461,8 @@ async fn execute_data_compare_sync_task(
     let include_script_in_result =
         get_payload_bool(payload, &["includeScriptInResult", "returnScript"]).unwrap_or(false);
 
-    let state = app.state::<AppState>();
-    let plan = crate::db::generate_data_sync_script_with_state(state.inner(), request).await?;
+    let state = resolve_task_state(app, payload).await?;
+    let plan = crate::db::generate_data_sync_script_with_state(&state, request).await?;
 
     if let Some(path) = output_file_path.as_ref() {
         write_text_file(path, &plan.script)?;
@@ -488,7 +470,7 @@ async fn execute_data_compare_sync_task(
 
     let mut applied = false;
     if apply_script && plan.statement_counts.total > 0 {
-        apply_raw_sql_script(app, &plan.script).await?;
+        apply_raw_sql_script(&state, &plan.script).await?;
         applied = true;
     }
 
@@ -588,6 +570,87 @@ async fn execute_data_transfer_migration_task(
     }))
 }
 
+async fn execute_online_schema_change_task(
+    app: &AppHandle,
+    task: &TaskDefinition,
+    context: &ExecutorContext,
+) -> Result<Value, String> {
+    use crate::schema_tracker::online_schema_change::{
+        plan_invocations, run_online_schema_change, OscConnection, OscOptions, OscRunRequest,
+    };
+
+    let payload = &task.payload;
+    let plan_value = get_payload_object(payload, &["plan", "migrationPlan"])
+        .ok_or("online_schema_change payload requires plan object".to_string())?;
+    let plan: crate::schema_tracker::migration::MigrationPlan =
+        serde_json::from_value(plan_value.clone())
+            .map_err(|e| format!("Invalid migration plan: {}", e))?;
+    let (tool, invocations) = plan_invocations(&plan)?;
+    let options: OscOptions = match payload.get("options") {
+        Some(value) => serde_json::from_value(value.clone())
+            .map_err(|e| format!("Invalid online schema change options: {}", e))?,
+        None => OscOptions::default(),
+    };
+    let connection_id = get_payload_string(payload, &["connectionId", "connection"])
+        .ok_or("online_schema_change payload requires connectionId".to_string())?;
+    let database = get_payload_string(payload, &["database", "schema", "dbName"])
+        .ok_or("online_schema_change payload requires database".to_string())?;
+
+    let app_state = app.state::<AppState>();
+    let resolved = crate::data_transfer::connection_resolver::resolve_connection_by_id(
+        app,
+        app_state.inner(),
+        &connection_id,
+    )?;
+    if resolved.db_type != DatabaseType::MySQL {
+        return Err(format!(
+            "{} only supports MySQL connections",
+            tool.binary_name()
+        ));
+    }
+    if resolved.config.use_ssh_tunnel {
+        return Err(format!(
+            "{} cannot reach the database through the connection's SSH tunnel; run it from a host with direct access",
+            tool.binary_name()
+        ));
+    }
+
+    let (run_id, log_to_run) = match &context.run_id {
+        Some(run_id) => (run_id.clone(), true),
+        None => (uuid::Uuid::new_v4().to_string(), false),
+    };
+    let start = Instant::now();
+    let run = run_online_schema_change(
+        app_state.inner(),
+        OscRunRequest {
+            run_id,
+            task_id: task.id.clone(),
+            log_to_run,
+            tool,
+            invocations,
+            connection: OscConnection {
+                host: resolved.host,
+                port: resolved.port,
+                user: resolved.config.username,
+                password: resolved.config.password,
+                database: database.clone(),
+            },
+            options,
+        },
+    )
+    .await?;
+
+    Ok(serde_json::json!({
+        "executor": "online_schema_change",
+        "taskId": task.id,
+        "connectionId": connection_id,
+        "database": database,
+        "tool": tool.as_str(),
+        "elapsedMs": start.elapsed().as_millis() as u64,
+        "run": run,
+    }))
+}
+
 async fn execute_composite_task(
     app: &AppHandle,
     task: &TaskDefinition,
@@ -1290,13 +1353,12 @@ fn build_data_transfer_plan_request(payload: &Value) -> Result<DataTransferPlanR
     Ok(request)
 }
 
-async fn apply_raw_sql_script(app: &AppHandle, script: &str) -> Result<(), String> {
+async fn apply_raw_sql_script(state: &AppState, script: &str) -> Result<(), String> {
     let sql = script.trim();
     if sql.is_empty() {
         return Ok(());
     }
 
-    let state = app.state::<AppState>();
     let db_type = {
         let guard = state.active_db_type.lock().await;
         guard.clone()
@@ -1376,6 +1438,21 @@ fn trimmed_option(value: String) -> Option<String> {
     }
 }
 
+/// Picks the connection session a task runs against: an explicit `sessionId`,
+/// else a `connectionId` that is currently open, else the active session.
+async fn resolve_task_state(app: &AppHandle, payload: &Value) -> Result<AppState, String> {
+    let state = app.state::<AppState>();
+    if let Some(session_id) = get_payload_string(payload, &["sessionId"]) {
+        return state.for_session(Some(&session_id)).await;
+    }
+    if let Some(connection_id) = get_payload_string(payload, &["connectionId", "connection"]) {
+        if state.get_session(&connection_id).await.is_ok() {
+            return state.for_session(Some(&connection_id)).await;
+        }
+    }
+    Ok(state.inner().clone())
+}
+
 fn get_payload_string(payload: &Value, keys: &[&str]) -> Option<String> {
     keys.iter()
         .find_map(|key| payload.get(*key))
@@ -1431,13 +1508,30 @@ fn get_payload_bool(payload: &Value, keys: &[&str]) -> Option<bool> {
         .and_then(Value::as_bool)
 }
 
+fn parse_payload_enum<T: serde::de::DeserializeOwned>(
+    payload: &Value,
+    keys: &[&str],
+) -> Result<Option<T>, String> {
+    match get_payload_string(payload, keys) {
+        Some(raw) => serde_json::from_value(Value::String(raw.to_lowercase()))
+            .map(Some)
+            .map_err(|e| format!("Invalid {} in task payload: {}", keys[0], e)),
+        None => Ok(None),
+    }
+}
+
 fn summarize_result_sets(results: &[crate::db_types::QueryResult]) -> (usize, usize) {
     let result_sets = results.len();
     let total_rows = results.iter().map(|set| set.rows.len()).sum();
     (result_sets, total_rows)
 }
 
-fn default_backup_file_path(app: &AppHandle, task_id: &str, database: &str) -> Result<String, String> {
+fn default_backup_file_path(
+    app: &AppHandle,
+    task_id: &str,
+    database: &str,
+    extension: &str,
+) -> Result<String, String> {
     let app_data_dir = app
         .path()
         .app_data_dir()
@@ -1457,10 +1551,11 @@ fn default_backup_file_path(app: &AppHandle, task_id: &str, database: &str) -> R
         })
         .collect::<String>();
     let file_name = format!(
-        "{}_{}_{}.sql",
+        "{}_{}_{}.{}",
         safe_db,
         task_id.replace('-', ""),
-        Utc::now().format("%Y%m%d%H%M%S")
+        Utc::now().format("%Y%m%d%H%M%S"),
+        extension
     );
     Ok(backups_dir.join(file_name).to_string_lossy().to_string())
 }
@@ -1476,78 +1571,6 @@ fn db_type_label(db_type: &DatabaseType) -> &'static str {
     }
 }
 
-fn quote_identifier_mysql(name: &str) -> String {
-    format!("`{}`", name.replace('`', "``"))
-}
-
-fn quote_identifier_postgres(name: &str) -> String {
-    format!("\"{}\"", name.replace('"', "\"\""))
-}
-
-fn quote_identifier_mssql(name: &str) -> String {
-    format!("[{}]", name.replace(']', "]]"))
-}
-
-fn quote_column_name(db_type: &DatabaseType, column: &str) -> String {
-    match db_type {
-        DatabaseType::PostgreSQL => quote_identifier_postgres(column),
-        DatabaseType::MSSQL => quote_identifier_mssql(column),
-        _ => quote_identifier_mysql(column),
-    }
-}
-
-fn qualified_table_name(db_type: &DatabaseType, database: &str, table: &str) -> String {
-    match db_type {
-        DatabaseType::PostgreSQL => format!(
-            "{}.{}",
-            quote_identifier_postgres(database),
-            quote_identifier_postgres(table)
-        ),
-        DatabaseType::MSSQL => format!(
-            "{}.{}",
-            quote_identifier_mssql(database),
-            quote_identifier_mssql(table)
-        ),
-        _ => format!(
-            "{}.{}",
-            quote_identifier_mysql(database),
-            quote_identifier_mysql(table)
-        ),
-    }
-}
-
-fn escape_sql_string(value: &str) -> String {
-    value.replace('\\', "\\\\").replace('\'', "''")
-}
-
-fn value_to_sql_literal(value: &serde_json::Value) -> String {
-    match value {
-        serde_json::Value::Null => "NULL".to_string(),
-        serde_json::Value::Bool(v) => {
-            if *v {
-                "TRUE".to_string()
-            } else {
-                "FALSE".to_string()
-            }
-        }
-        serde_json::Value::Number(num) => num.to_string(),
-        serde_json::Value::String(s) => format!("'{}'", escape_sql_string(s)),
-        other => format!("'{}'", escape_sql_string(&other.to_string())),
-    }
-}
-
-fn ensure_sql_terminated(statement: &str) -> String {
-    let trimmed = statement.trim();
-    if trimmed.is_empty() {
-        return String::new();
-    }
-    if trimmed.ends_with(';') {
-        trimmed.to_string()
-    } else {
-        format!("{};", trimmed)
-    }
-}
-
 fn write_text_file(file_path: &str, content: &str) -> Result<(), String> {
     let target = Path::new(file_path);
     if let Some(parent) = target.parent() {
@@ -1559,40 +1582,5 @@ fn write_text_file(file_path: &str, content: &str) -> Result<(), String> {
     fs::write(target, content).map_err(|e| format!("Failed to write file: {}", e))
 }
 
-fn build_insert_statements(
-    db_type: &DatabaseType,
-    database: &str,
-    table: &str,
-    result: &crate::db_types::QueryResult,
-) -> Vec<String> {
-    if result.columns.is_empty() || result.rows.is_empty() {
-        return Vec::new();
-    }
-
-    let qualified_table = qualified_table_name(db_type, database, table);
-    let quoted_columns = result
-        .columns
-        .iter()
-        .map(|column| quote_column_name(db_type, column))
-        .collect::<Vec<String>>()
-        .join(", ");
-
-    result
-        .rows
-        .iter()
-        .map(|row| {
-            let values = row
-                .iter()
-                .map(value_to_sql_literal)
-                .collect::<Vec<String>>()
-                .join(", ");
-            format!(
-                "INSERT INTO {} ({}) VALUES ({});",
-                qualified_table, quoted_columns, values
-            )
-        })
-        .collect()
-}
-
 #[cfg(test)]
 mod tests;
@@ -41,3 +41,16 @@ fn test_resolve_template_value() {
     let resolved = resolve_composite_step_payload(&payload, &outputs).unwrap();
     assert_eq!(resolved["query"], "SELECT * FROM t WHERE id = 123");
 }
+
+#[test]
+fn test_parse_payload_enum() {
+    let payload = json!({ "compression": "GZIP", "format": "tape" });
+    let compression: Option<crate::db::BackupCompression> =
+        parse_payload_enum(&payload, &["compression"]).unwrap();
+    assert_eq!(compression, Some(crate::db::BackupCompression::Gzip));
+    assert!(parse_payload_enum::<crate::db::BackupFormat>(&payload, &["format"]).is_err());
+    assert_eq!(
+        parse_payload_enum::<crate::db::BackupFormat>(&payload, &["missing"]).unwrap(),
+        None
+    );
+}
@@ -12,6 +12,7 @@ pub enum TaskType {
     SchemaSnapshot,
     DataCompareSync,
     DataTransferMigration,
+    OnlineSchemaChange,
     Composite,
 }
 
@@ -23,6 +24,7 @@ impl TaskType {
             Self::SchemaSnapshot => "schema_snapshot",
             Self::DataCompareSync => "data_compare_sync",
             Self::DataTransferMigration => "data_transfer_migration",
+            Self::OnlineSchemaChange => "online_schema_change",
             Self::Composite => "composite",
         }
     }
@@ -34,6 +36,7 @@ impl TaskType {
             "schema_snapshot" => Ok(Self::SchemaSnapshot),
             "data_compare_sync" => Ok(Self::DataCompareSync),
             "data_transfer_migration" => Ok(Self::DataTransferMigration),
+            "online_schema_change" => Ok(Self::OnlineSchemaChange),
             "composite" => Ok(Self::Composite),
             _ => Err(format!("Invalid task type in storage: {}", value)),
         }
@@ -815,6 +818,33 @@ pub(crate) fn validate_task_payload(task_type: &TaskType, payload: &Value) -> Re
                 }
             }
         }
+        TaskType::OnlineSchemaChange => {
+            if payload_string(payload, &["connectionId", "connection"]).is_none() {
+                return Err("online_schema_change payload requires connectionId".to_string());
+            }
+            if payload_string(payload, &["database", "schema", "dbName"]).is_none() {
+                return Err("online_schema_change payload requires database".to_string());
+            }
+            let plan = payload_object(payload, &["plan", "migrationPlan"])
+                .ok_or("online_schema_change payload requires plan object".to_string())?;
+            let strategy = payload_string(plan, &["strategy"]).unwrap_or_default();
+            if !matches!(strategy.as_str(), "pt_osc" | "gh_ost") {
+                return Err(format!(
+                    "online_schema_change plan strategy must be pt_osc or gh_ost, got '{}'",
+                    strategy
+                ));
+            }
+            let has_commands = plan
+                .get("external_commands")
+                .and_then(Value::as_array)
+                .map(|commands| !commands.is_empty())
+                .unwrap_or(false);
+            if !has_commands {
+                return Err(
+                    "online_schema_change plan requires non-empty external_commands".to_string(),
+                );
+            }
+        }
         TaskType::Composite => {
             if payload.get("steps").is_some() {
                 let has_steps = payload
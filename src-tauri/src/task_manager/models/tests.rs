@@ -49,3 +49,25 @@ fn test_normalize_tags() {
     let normalized = normalize_tags(&input);
     assert_eq!(normalized, vec!["test", "tag1"]);
 }
+
+#[test]
+fn test_validate_online_schema_change_payload() {
+    let payload = serde_json::json!({
+        "connectionId": "conn-1",
+        "database": "app",
+        "plan": {
+            "strategy": "gh_ost",
+            "external_commands": ["gh-ost --table=\"users\" --alter=\"ADD COLUMN a INT\" --execute"]
+        }
+    });
+    assert!(validate_task_payload(&TaskType::OnlineSchemaChange, &payload).is_ok());
+    assert_eq!(TaskType::from_db("online_schema_change").unwrap(), TaskType::OnlineSchemaChange);
+
+    let mut native = payload.clone();
+    native["plan"]["strategy"] = "native".into();
+    assert!(validate_task_payload(&TaskType::OnlineSchemaChange, &native).is_err());
+
+    let mut empty = payload.clone();
+    empty["plan"]["external_commands"] = serde_json::json!([]);
+    assert!(validate_task_payload(&TaskType::OnlineSchemaChange, &empty).is_err());
+}